@@ -156,6 +156,13 @@ mod tests {
         ) -> Result<oagw_sdk::Upstream, ServiceGatewayError> {
             unimplemented!()
         }
+        async fn create_upstreams(
+            &self,
+            _: modkit_security::SecurityContext,
+            _: Vec<oagw_sdk::CreateUpstreamRequest>,
+        ) -> Result<Vec<oagw_sdk::Upstream>, ServiceGatewayError> {
+            unimplemented!()
+        }
         async fn get_upstream(
             &self,
             _: modkit_security::SecurityContext,
@@ -192,6 +199,13 @@ mod tests {
         ) -> Result<oagw_sdk::Route, ServiceGatewayError> {
             unimplemented!()
         }
+        async fn create_routes(
+            &self,
+            _: modkit_security::SecurityContext,
+            _: Vec<oagw_sdk::CreateRouteRequest>,
+        ) -> Result<Vec<oagw_sdk::Route>, ServiceGatewayError> {
+            unimplemented!()
+        }
         async fn get_route(
             &self,
             _: modkit_security::SecurityContext,
@@ -222,6 +236,13 @@ mod tests {
         ) -> Result<(), ServiceGatewayError> {
             unimplemented!()
         }
+        async fn validate_config(
+            &self,
+            _: modkit_security::SecurityContext,
+            _: oagw_sdk::ConfigSnapshot,
+        ) -> Result<Vec<oagw_sdk::ConfigProblem>, ServiceGatewayError> {
+            unimplemented!()
+        }
         async fn resolve_proxy_target(
             &self,
             _: modkit_security::SecurityContext,
@@ -238,6 +259,72 @@ mod tests {
         ) -> Result<http::Response<oagw_sdk::Body>, ServiceGatewayError> {
             unimplemented!()
         }
+
+        async fn status(&self) -> oagw_sdk::GatewayStatus {
+            unimplemented!()
+        }
+
+        fn version_info(&self) -> oagw_sdk::VersionInfo {
+            unimplemented!()
+        }
+
+        async fn test_upstream(
+            &self,
+            _: modkit_security::SecurityContext,
+            _: uuid::Uuid,
+        ) -> Result<oagw_sdk::UpstreamTestResult, ServiceGatewayError> {
+            unimplemented!()
+        }
+
+        async fn describe_route(
+            &self,
+            _: modkit_security::SecurityContext,
+            _: uuid::Uuid,
+        ) -> Result<oagw_sdk::RouteDescription, ServiceGatewayError> {
+            unimplemented!()
+        }
+
+        async fn trace_request(
+            &self,
+            _: modkit_security::SecurityContext,
+            _: http::Request<oagw_sdk::Body>,
+            _: Option<http::Response<oagw_sdk::Body>>,
+        ) -> oagw_sdk::RequestTrace {
+            unimplemented!()
+        }
+
+        async fn list_runtime_states(
+            &self,
+            _: modkit_security::SecurityContext,
+            _: &oagw_sdk::ListQuery,
+        ) -> Result<Vec<oagw_sdk::UpstreamRuntimeState>, ServiceGatewayError> {
+            unimplemented!()
+        }
+
+        async fn reset_circuit_breaker(
+            &self,
+            _: modkit_security::SecurityContext,
+            _: uuid::Uuid,
+        ) -> Result<(), ServiceGatewayError> {
+            unimplemented!()
+        }
+
+        async fn reset_rate_limit(
+            &self,
+            _: modkit_security::SecurityContext,
+            _: uuid::Uuid,
+            _: uuid::Uuid,
+        ) -> Result<(), ServiceGatewayError> {
+            unimplemented!()
+        }
+
+        fn recent_errors(&self) -> Vec<oagw_sdk::RecentError> {
+            unimplemented!()
+        }
+
+        async fn flush_all_caches(&self) -> oagw_sdk::FlushedCaches {
+            unimplemented!()
+        }
     }
 
     fn null_gw() -> Arc<dyn ServiceGatewayClientV1> {
@@ -829,6 +829,13 @@ mod tests {
         ) -> Result<Upstream, ServiceGatewayError> {
             unimplemented!()
         }
+        async fn create_upstreams(
+            &self,
+            _: SecurityContext,
+            _: Vec<CreateUpstreamRequest>,
+        ) -> Result<Vec<Upstream>, ServiceGatewayError> {
+            unimplemented!()
+        }
         async fn get_upstream(
             &self,
             _: SecurityContext,
@@ -865,6 +872,13 @@ mod tests {
         ) -> Result<Route, ServiceGatewayError> {
             unimplemented!()
         }
+        async fn create_routes(
+            &self,
+            _: SecurityContext,
+            _: Vec<CreateRouteRequest>,
+        ) -> Result<Vec<Route>, ServiceGatewayError> {
+            unimplemented!()
+        }
         async fn get_route(
             &self,
             _: SecurityContext,
@@ -895,6 +909,13 @@ mod tests {
         ) -> Result<(), ServiceGatewayError> {
             unimplemented!()
         }
+        async fn validate_config(
+            &self,
+            _: SecurityContext,
+            _: oagw_sdk::ConfigSnapshot,
+        ) -> Result<Vec<oagw_sdk::ConfigProblem>, ServiceGatewayError> {
+            unimplemented!()
+        }
         async fn resolve_proxy_target(
             &self,
             _: SecurityContext,
@@ -952,6 +973,72 @@ mod tests {
                 MockResponse::Error(err) => Err(err),
             }
         }
+
+        async fn status(&self) -> oagw_sdk::GatewayStatus {
+            unimplemented!()
+        }
+
+        fn version_info(&self) -> oagw_sdk::VersionInfo {
+            unimplemented!()
+        }
+
+        async fn test_upstream(
+            &self,
+            _: SecurityContext,
+            _: uuid::Uuid,
+        ) -> Result<oagw_sdk::UpstreamTestResult, ServiceGatewayError> {
+            unimplemented!()
+        }
+
+        async fn describe_route(
+            &self,
+            _: SecurityContext,
+            _: uuid::Uuid,
+        ) -> Result<oagw_sdk::RouteDescription, ServiceGatewayError> {
+            unimplemented!()
+        }
+
+        async fn trace_request(
+            &self,
+            _: SecurityContext,
+            _: http::Request<Body>,
+            _: Option<http::Response<Body>>,
+        ) -> oagw_sdk::RequestTrace {
+            unimplemented!()
+        }
+
+        async fn list_runtime_states(
+            &self,
+            _: SecurityContext,
+            _: &oagw_sdk::ListQuery,
+        ) -> Result<Vec<oagw_sdk::UpstreamRuntimeState>, ServiceGatewayError> {
+            unimplemented!()
+        }
+
+        async fn reset_circuit_breaker(
+            &self,
+            _: SecurityContext,
+            _: uuid::Uuid,
+        ) -> Result<(), ServiceGatewayError> {
+            unimplemented!()
+        }
+
+        async fn reset_rate_limit(
+            &self,
+            _: SecurityContext,
+            _: uuid::Uuid,
+            _: uuid::Uuid,
+        ) -> Result<(), ServiceGatewayError> {
+            unimplemented!()
+        }
+
+        fn recent_errors(&self) -> Vec<oagw_sdk::RecentError> {
+            unimplemented!()
+        }
+
+        async fn flush_all_caches(&self) -> oagw_sdk::FlushedCaches {
+            unimplemented!()
+        }
     }
 
     fn test_security_context() -> SecurityContext {
@@ -213,6 +213,7 @@ async fn register_route(
             path: route_prefix.clone(),
             query_allowlist,
             path_suffix_mode: suffix_mode,
+            match_body: None,
         }),
         grpc: None,
     };
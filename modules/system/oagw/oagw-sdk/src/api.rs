@@ -1,3 +1,5 @@
+use std::time::SystemTime;
+
 use async_trait::async_trait;
 use modkit_security::SecurityContext;
 use uuid::Uuid;
@@ -5,8 +7,8 @@ use uuid::Uuid;
 use crate::body::Body;
 use crate::error::ServiceGatewayError;
 use crate::{
-    CreateRouteRequest, CreateUpstreamRequest, ListQuery, Route, UpdateRouteRequest,
-    UpdateUpstreamRequest, Upstream,
+    AuthConfig, CreateRouteRequest, CreateUpstreamRequest, HeadersConfig, ListQuery,
+    RateLimitConfig, Route, UpdateRouteRequest, UpdateUpstreamRequest, Upstream,
 };
 
 // ---------------------------------------------------------------------------
@@ -33,6 +35,233 @@ impl ErrorSource {
     }
 }
 
+/// Address of the caller a proxy request was received from, used for
+/// `X-Forwarded-For`.
+///
+/// Set on proxy requests via `req.extensions_mut().insert(ClientIp(..))`;
+/// in-process callers may leave it out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub std::net::IpAddr);
+
+// ---------------------------------------------------------------------------
+// Status types
+// ---------------------------------------------------------------------------
+
+/// Liveness/readiness snapshot returned by [`ServiceGatewayClientV1::status`].
+///
+/// `live` only reflects that the gateway process is up. `ready` additionally
+/// requires auth plugins to be initialized and healthy, provisioned
+/// configuration to be loaded, and (when configured) a sample upstream to be
+/// reachable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GatewayStatus {
+    pub live: bool,
+    pub ready: bool,
+    /// Human-readable reasons the gateway is not ready (empty when ready).
+    pub reasons: Vec<String>,
+    /// Rolling response time percentiles per upstream, for upstreams that
+    /// served traffic since the gateway started.
+    pub upstream_latencies: Vec<UpstreamLatency>,
+}
+
+/// Response time percentiles of one upstream over its most recent
+/// responses (time to response headers, in microseconds).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamLatency {
+    pub upstream_id: Uuid,
+    /// Responses the percentiles are computed over.
+    pub samples: usize,
+    pub p50_us: u64,
+    pub p95_us: u64,
+    pub p99_us: u64,
+}
+
+/// Build identification returned by [`ServiceGatewayClientV1::version_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionInfo {
+    /// Version of the gateway crate (`CARGO_PKG_VERSION`).
+    pub version: String,
+    /// Git commit the gateway was built from, taken from the `OAGW_GIT_SHA`
+    /// environment variable at build time. `None` if it was not set.
+    pub git_sha: Option<String>,
+    /// Cargo features the gateway was compiled with.
+    pub features: Vec<String>,
+}
+
+/// Outcome of [`ServiceGatewayClientV1::test_upstream`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UpstreamTestResult {
+    /// The auth plugin resolved its credentials (`true` without auth).
+    pub credential_resolved: bool,
+    /// The auth plugin prepared the probe request (`true` without auth).
+    pub auth_applied: bool,
+    /// HTTP status the upstream answered the probe with.
+    pub upstream_status: Option<u16>,
+    /// Round-trip time of the probe request in milliseconds.
+    pub latency_ms: Option<u64>,
+    /// Why the test stopped short, if it did.
+    pub error: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Runtime state types
+// ---------------------------------------------------------------------------
+
+/// State of an upstream's circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitStatus {
+    /// Requests flow; `failures` consecutive failures so far.
+    Closed { failures: u32 },
+    /// Requests are rejected for roughly `retry_after_secs` more seconds.
+    Open { retry_after_secs: u64 },
+    /// Probe requests are being let through to test recovery.
+    HalfOpen,
+}
+
+/// Current state of one rate-limit token bucket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitBucketState {
+    /// Bucket key: `upstream:{id}`, or `upstream:{id}:tenant:{tenant_id}`
+    /// for a tenant rate limit.
+    pub key: String,
+    pub limit: u64,
+    pub remaining: u64,
+    /// Seconds until the bucket is full again.
+    pub reset_secs: u64,
+}
+
+/// Circuit-breaker and rate-limit state held for an upstream by the
+/// gateway instance serving the call. State is not shared across replicas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamRuntimeState {
+    pub upstream_id: Uuid,
+    pub circuit: CircuitStatus,
+    /// Upstream-level buckets that have seen traffic, sorted by key.
+    pub rate_limits: Vec<RateLimitBucketState>,
+}
+
+/// A proxy request that failed inside the gateway, as kept by
+/// [`ServiceGatewayClientV1::recent_errors`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentError {
+    pub at: SystemTime,
+    /// Unset when the request failed before its route was resolved.
+    pub upstream_id: Option<Uuid>,
+    pub route_id: Option<Uuid>,
+    /// Kind of error in `snake_case`, e.g. `connection_timeout` or
+    /// `rate_limit_exceeded`.
+    pub category: String,
+    /// Error message with credentials, query strings and URL userinfo
+    /// masked.
+    pub message: String,
+}
+
+/// Entries evicted from each cache by
+/// [`ServiceGatewayClientV1::flush_all_caches`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FlushedCaches {
+    /// Cached upstream responses, including idempotency replays.
+    pub response_cache: usize,
+    /// Tokens cached by auth plugins, e.g. OAuth2 client credentials.
+    pub token_cache: usize,
+}
+
+// ---------------------------------------------------------------------------
+// Effective configuration types
+// ---------------------------------------------------------------------------
+
+/// Configuration layer an effective setting was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// The route itself.
+    Route,
+    /// The upstream the caller's alias resolves to.
+    Upstream,
+    /// An ancestor tenant's upstream with the same alias.
+    Ancestor,
+    /// A tenant rate limit set on the upstream for the calling tenant.
+    TenantPolicy,
+    /// The gateway-wide module default.
+    Gateway,
+}
+
+/// An effective setting and the layer it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sourced<T> {
+    pub value: T,
+    pub source: ConfigSource,
+}
+
+/// Outcome of [`ServiceGatewayClientV1::describe_route`]: the configuration
+/// the proxy applies to requests matching a route, as seen by the caller.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteDescription {
+    pub route: Route,
+    /// Upstream the route's alias resolves to for the caller.
+    pub upstream_id: Uuid,
+    pub timeout_secs: Sourced<u64>,
+    pub user_agent: Sourced<String>,
+    pub auth: Option<Sourced<AuthConfig>>,
+    pub headers: Option<Sourced<HeadersConfig>>,
+    /// Limit of the upstream bucket; a route limit tighter than the
+    /// upstream's is reported as coming from the route.
+    pub rate_limit: Option<Sourced<RateLimitConfig>>,
+    pub circuit_failure_threshold: Sourced<u32>,
+    pub circuit_cooldown_secs: Sourced<u64>,
+    pub circuit_half_open_probes: Sourced<u32>,
+}
+
+/// AuthZ decision recorded in a [`RequestTrace`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceAuthz {
+    Allowed,
+    Denied,
+    /// Not evaluated: the route is public or `authz_mode` is `allow_all`.
+    Skipped,
+}
+
+/// Outcome of [`ServiceGatewayClientV1::trace_request`]: what each stage of
+/// the proxy pipeline did with the request. Stages the request did not reach
+/// are left empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestTrace {
+    pub route_id: Option<Uuid>,
+    pub upstream_id: Option<Uuid>,
+    /// `host:port` the request was addressed to.
+    pub endpoint: Option<String>,
+    pub authz: Option<TraceAuthz>,
+    /// Auth plugins run, by plugin type.
+    pub plugins: Vec<String>,
+    /// Transforms applied, in order: `request_body_transformers`,
+    /// `transform_hook`, `header_rules`, `status_remap`, `body_rewrite`,
+    /// `response_body_transformers`, `response_header_allowlist`.
+    pub transforms: Vec<String>,
+    /// The mock response stood in for the upstream.
+    pub upstream_called: bool,
+    /// Status of the response the client would get; unset when the trace
+    /// ended before sending or the request failed.
+    pub status: Option<u16>,
+    /// Why the request failed, if it did.
+    pub error: Option<String>,
+}
+
+/// Upstreams and routes to check with
+/// [`ServiceGatewayClientV1::validate_config`] before importing them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigSnapshot {
+    pub upstreams: Vec<CreateUpstreamRequest>,
+    pub routes: Vec<CreateRouteRequest>,
+}
+
+/// A problem found by [`ServiceGatewayClientV1::validate_config`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    /// Offending entry of the snapshot, e.g. `upstreams[2]` or `routes[0]`;
+    /// `upstreams` or `routes` for problems with the list as a whole.
+    pub path: String,
+    pub detail: String,
+}
+
 // ---------------------------------------------------------------------------
 // Service trait
 // ---------------------------------------------------------------------------
@@ -53,6 +282,14 @@ pub trait ServiceGatewayClientV1: Send + Sync {
         req: CreateUpstreamRequest,
     ) -> Result<Upstream, ServiceGatewayError>;
 
+    /// Create several upstreams atomically: either all are created, in
+    /// order, or none are and the error names the first failing item.
+    async fn create_upstreams(
+        &self,
+        ctx: SecurityContext,
+        reqs: Vec<CreateUpstreamRequest>,
+    ) -> Result<Vec<Upstream>, ServiceGatewayError>;
+
     async fn get_upstream(
         &self,
         ctx: SecurityContext,
@@ -86,6 +323,13 @@ pub trait ServiceGatewayClientV1: Send + Sync {
         req: CreateRouteRequest,
     ) -> Result<Route, ServiceGatewayError>;
 
+    /// Create several routes atomically, like [`Self::create_upstreams`].
+    async fn create_routes(
+        &self,
+        ctx: SecurityContext,
+        reqs: Vec<CreateRouteRequest>,
+    ) -> Result<Vec<Route>, ServiceGatewayError>;
+
     async fn get_route(&self, ctx: SecurityContext, id: Uuid)
     -> Result<Route, ServiceGatewayError>;
 
@@ -106,6 +350,18 @@ pub trait ServiceGatewayClientV1: Send + Sync {
     async fn delete_route(&self, ctx: SecurityContext, id: Uuid)
     -> Result<(), ServiceGatewayError>;
 
+    // -- Validation --
+
+    /// Dry-run a bulk import: check every upstream and then every route of
+    /// `snapshot` as if it were created, and return all problems found
+    /// (empty when the snapshot is valid). Nothing is created. Routes refer
+    /// to upstreams that already exist, as with [`Self::create_routes`].
+    async fn validate_config(
+        &self,
+        ctx: SecurityContext,
+        snapshot: ConfigSnapshot,
+    ) -> Result<Vec<ConfigProblem>, ServiceGatewayError>;
+
     // -- Resolution --
 
     /// Resolve the effective (hierarchy-merged) upstream and matched route for
@@ -144,4 +400,86 @@ pub trait ServiceGatewayClientV1: Send + Sync {
         ctx: SecurityContext,
         req: http::Request<Body>,
     ) -> Result<http::Response<Body>, ServiceGatewayError>;
+
+    // -- Health --
+
+    /// Report gateway liveness and readiness (e.g. for Kubernetes probes).
+    async fn status(&self) -> GatewayStatus;
+
+    /// Report the running gateway build. Requires no security context.
+    fn version_info(&self) -> VersionInfo;
+
+    /// Test connection for an upstream: resolve its credentials, run its
+    /// auth plugin against a `GET /` probe and report whether the upstream
+    /// responded and how fast. Diagnostic failures are part of the result;
+    /// an error means the upstream could not be loaded.
+    async fn test_upstream(
+        &self,
+        ctx: SecurityContext,
+        id: Uuid,
+    ) -> Result<UpstreamTestResult, ServiceGatewayError>;
+
+    /// Describe the effective configuration of a route: timeouts, headers,
+    /// auth, rate limit and circuit breaker settings after the tenant
+    /// hierarchy merge, route overrides and gateway defaults, each with the
+    /// layer it came from. Intended for debugging configuration precedence.
+    async fn describe_route(
+        &self,
+        ctx: SecurityContext,
+        id: Uuid,
+    ) -> Result<RouteDescription, ServiceGatewayError>;
+
+    /// Run a request through the proxy pipeline without contacting the
+    /// upstream and report what each stage did. `mock_response` stands in
+    /// for the upstream's response; without one the trace ends once the
+    /// request is ready to send. Rate limits and the circuit breaker are
+    /// checked but not consumed. Intended for debugging route and plugin
+    /// configuration.
+    async fn trace_request(
+        &self,
+        ctx: SecurityContext,
+        req: http::Request<Body>,
+        mock_response: Option<http::Response<Body>>,
+    ) -> RequestTrace;
+
+    // -- Runtime state --
+
+    /// Circuit-breaker and rate-limit state of the caller's upstreams, one
+    /// page of upstreams at a time (paged like [`Self::list_upstreams`]).
+    async fn list_runtime_states(
+        &self,
+        ctx: SecurityContext,
+        query: &ListQuery,
+    ) -> Result<Vec<UpstreamRuntimeState>, ServiceGatewayError>;
+
+    /// Force an upstream's circuit breaker closed so traffic flows again
+    /// immediately, e.g. after the upstream recovered during an incident.
+    async fn reset_circuit_breaker(
+        &self,
+        ctx: SecurityContext,
+        upstream_id: Uuid,
+    ) -> Result<(), ServiceGatewayError>;
+
+    /// Refill the upstream rate-limit buckets that apply to `tenant_id`:
+    /// its tenant rate limit bucket and the upstream's own bucket, which is
+    /// shared by tenants without one. Route buckets are not touched.
+    async fn reset_rate_limit(
+        &self,
+        ctx: SecurityContext,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+    ) -> Result<(), ServiceGatewayError>;
+
+    /// The last proxy requests that failed inside the gateway instance
+    /// serving the call, oldest first: routing, auth, limit, timeout and
+    /// upstream connection errors, with secrets redacted from messages.
+    /// Not scoped to the caller's tenant; intended for operators.
+    fn recent_errors(&self) -> Vec<RecentError>;
+
+    /// Clear every cache held by the gateway instance serving the call and
+    /// report how many entries each held. Not scoped to the caller's
+    /// tenant; intended for operators, e.g. after rotating credentials.
+    /// Response cache backends that cannot enumerate their entries are left
+    /// untouched and report zero.
+    async fn flush_all_caches(&self) -> FlushedCaches;
 }
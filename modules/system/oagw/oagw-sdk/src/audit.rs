@@ -0,0 +1,83 @@
+//! Audit trail for OAGW configuration changes.
+//!
+//! The gateway emits an [`AuditEvent`] for every upstream/route create,
+//! update, and delete. Consumers that need an audit trail register an
+//! [`AuditSink`] implementation in the `ClientHub` before the `oagw` module
+//! initializes; when no sink is registered, no events are produced.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{Route, Upstream};
+
+/// Kind of configuration change recorded by an [`AuditEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+}
+
+impl AuditAction {
+    /// Returns a lowercase string representation for use in logs.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// Type of the configuration entity an [`AuditEvent`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditEntityType {
+    Upstream,
+    Route,
+}
+
+impl AuditEntityType {
+    /// Returns a lowercase string representation for use in logs.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Upstream => "upstream",
+            Self::Route => "route",
+        }
+    }
+}
+
+/// Snapshot of a configuration entity at one side of a change.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditSnapshot {
+    Upstream(Box<Upstream>),
+    Route(Box<Route>),
+}
+
+/// A single configuration change, attributed to the acting subject.
+///
+/// `before` is `None` for creates and `after` is `None` for deletes; updates
+/// carry both so the sink can derive a field-level diff.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEvent {
+    /// Subject that performed the change (from the caller's `SecurityContext`).
+    pub subject_id: Uuid,
+    /// Tenant the acting subject belongs to.
+    pub subject_tenant_id: Uuid,
+    pub action: AuditAction,
+    pub entity_type: AuditEntityType,
+    pub entity_id: Uuid,
+    pub before: Option<AuditSnapshot>,
+    pub after: Option<AuditSnapshot>,
+}
+
+/// Pluggable destination for [`AuditEvent`]s.
+///
+/// Recording is best-effort: the change has already been persisted when the
+/// sink is invoked, so implementations must handle their own failures
+/// (retry, buffer, or log) rather than surface them to the caller.
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    async fn record(&self, event: AuditEvent);
+}
@@ -1,7 +1,9 @@
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 
 use bytes::Bytes;
 use futures_core::Stream;
+use http::HeaderMap;
 
 /// Boxed error type for body stream errors.
 pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
@@ -147,6 +149,40 @@ impl From<BodyStream> for Body {
     }
 }
 
+/// HTTP trailer fields sent by the upstream after the response body
+/// (e.g. `grpc-status` from gRPC and other HTTP/2 upstreams).
+///
+/// Available on proxy responses via `resp.extensions().get::<Trailers>()`.
+/// Trailers arrive after the last body chunk, so [`Trailers::get`] returns
+/// `None` until the body has been read to the end, and stays `None` when the
+/// upstream sent none.
+#[derive(Clone, Default)]
+pub struct Trailers(Arc<Mutex<Option<HeaderMap>>>);
+
+impl Trailers {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the trailer fields received from the upstream.
+    pub fn set(&self, trailers: HeaderMap) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(trailers);
+    }
+
+    /// The trailer fields, once the body has been consumed.
+    #[must_use]
+    pub fn get(&self) -> Option<HeaderMap> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+impl std::fmt::Debug for Trailers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Trailers").field(&self.get()).finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -251,4 +287,17 @@ mod tests {
         let body = Body::Bytes(Bytes::from("data"));
         assert!(body.try_into_stream().is_err());
     }
+
+    #[test]
+    fn trailers_are_shared_between_clones() {
+        let trailers = Trailers::new();
+        let handle = trailers.clone();
+        assert!(handle.get().is_none());
+
+        let mut fields = HeaderMap::new();
+        fields.insert("grpc-status", "0".parse().unwrap());
+        trailers.set(fields);
+
+        assert_eq!(handle.get().unwrap().get("grpc-status").unwrap(), "0");
+    }
 }
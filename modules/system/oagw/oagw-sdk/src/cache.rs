@@ -0,0 +1,57 @@
+//! Response cache backend for OAGW.
+//!
+//! The gateway caches cacheable upstream responses (`GET`, `200 OK`, with a
+//! `Cache-Control: max-age`/`s-maxage` lifetime) through a [`ResponseCache`].
+//! An in-process cache is used by default; deployments running multiple
+//! replicas register a shared backend (e.g. Redis) in the `ClientHub` before
+//! the `oagw` module initializes.
+
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{HeaderMap, StatusCode};
+
+/// A fully buffered upstream response stored in the cache.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedResponse {
+    pub status: StatusCode,
+    /// Response headers after gateway sanitization.
+    pub headers: HeaderMap,
+    pub body: Bytes,
+    /// When the gateway stored the entry; served hits carry an `Age`
+    /// measured from here.
+    pub stored_at: SystemTime,
+    /// Freshness lifetime at store time. A hit whose age has reached it is
+    /// treated as a miss even if the backend still returns it.
+    pub fresh_for: Duration,
+}
+
+/// Pluggable storage for cached upstream responses.
+///
+/// Keys are opaque strings built by the gateway and already scoped by tenant,
+/// upstream, and route. Backends are best-effort: a failing `get` should
+/// behave as a miss and a failing `put` should be dropped, so that the cache
+/// never turns into a source of request failures.
+#[async_trait]
+pub trait ResponseCache: Send + Sync {
+    /// Look up a cached response. Returns `None` on miss or expiry.
+    async fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Store a response for at most `ttl`.
+    async fn put(&self, key: &str, response: CachedResponse, ttl: Duration);
+
+    /// Remove a cached response, if present.
+    async fn invalidate(&self, key: &str);
+
+    /// Remove every cached response and return how many were removed.
+    /// Backends that cannot enumerate their entries keep the default, which
+    /// removes nothing.
+    async fn clear(&self) -> usize {
+        0
+    }
+
+    /// Persist any buffered writes. Called once during graceful shutdown of
+    /// the gateway; backends that write through can keep the default no-op.
+    async fn flush(&self) {}
+}
@@ -25,6 +25,26 @@ pub enum ServiceGatewayError {
     #[error("{detail}")]
     PayloadTooLarge { detail: String, instance: String },
 
+    #[error("{detail}")]
+    UnsupportedMediaType { detail: String, instance: String },
+
+    /// The request URL exceeds the gateway's maximum length.
+    #[error("{detail}")]
+    UriTooLong { detail: String, instance: String },
+
+    /// A request header exceeds the upstream's size limit.
+    #[error("{detail}")]
+    HeaderTooLarge { detail: String, instance: String },
+
+    /// The upstream does not accept the request method.
+    #[error("{detail}")]
+    MethodNotAllowed {
+        detail: String,
+        instance: String,
+        /// Methods the upstream accepts.
+        allowed: Vec<String>,
+    },
+
     #[error("{detail}")]
     RateLimitExceeded {
         detail: String,
@@ -44,15 +64,64 @@ pub enum ServiceGatewayError {
     #[error("{detail}")]
     UpstreamDisabled { detail: String, instance: String },
 
+    /// The upstream is in maintenance mode; retry after the given delay.
+    #[error("{detail}")]
+    UpstreamMaintenance {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
+    /// The upstream's circuit breaker is open; retry after the cooldown.
+    #[error("{detail}")]
+    CircuitBreakerOpen {
+        detail: String,
+        instance: String,
+        retry_after_secs: u64,
+    },
+
+    /// No endpoint of the upstream passes its health check.
+    #[error("{detail}")]
+    NoHealthyTarget {
+        detail: String,
+        instance: String,
+        total: usize,
+        healthy: usize,
+    },
+
+    /// The gateway is at its concurrent request limit; retry later.
+    #[error("{detail}")]
+    GatewayOverloaded { detail: String, instance: String },
+
+    /// The auth plugin the route needs is disabled on the gateway.
+    #[error("{detail}")]
+    PluginDisabled { detail: String, instance: String },
+
+    /// The gateway could not get an authorization decision; retry later.
+    #[error("{detail}")]
+    AuthzUnavailable {
+        detail: String,
+        instance: String,
+        retry_after_secs: u64,
+    },
+
     #[error("{detail}")]
     ConnectionTimeout { detail: String, instance: String },
 
     #[error("{detail}")]
     RequestTimeout { detail: String, instance: String },
 
+    /// The client stalled while sending its request body.
+    #[error("{detail}")]
+    ClientTimeout { detail: String, instance: String },
+
     /// The caller is authenticated but not authorized to perform the requested action.
     #[error("access forbidden: {detail}")]
     Forbidden { detail: String },
+
+    /// The tenant owns as many upstreams or routes as its quota allows.
+    #[error("quota exceeded: {detail}")]
+    QuotaExceeded { detail: String },
 }
 
 /// Errors produced by the streaming helpers.
@@ -1,29 +1,45 @@
 pub mod api;
+pub mod audit;
 pub mod body;
+pub mod cache;
 pub mod codec;
 pub mod error;
+pub mod metrics;
 pub mod multipart;
+pub mod secret;
 pub mod sse;
+pub mod transform;
 pub mod ws;
 
 pub mod models;
 
 pub use models::{
-    AuthConfig, BurstConfig, CreateRouteRequest, CreateRouteRequestBuilder, CreateUpstreamRequest,
+    ApiVersion, AuthCondition, AuthConfig, AuthRule, BodyMatcher, BodyRewriteRule, BurstConfig,
+    CircuitBreakerConfig, CreateRouteRequest, CreateRouteRequestBuilder, CreateUpstreamRequest,
     CreateUpstreamRequestBuilder, Endpoint, GrpcMatch, HeadersConfig, HttpMatch, HttpMethod,
-    ListQuery, MatchRules, PassthroughMode, PathSuffixMode, PluginsConfig, RateLimitAlgorithm,
-    RateLimitConfig, RateLimitScope, RateLimitStrategy, RequestHeaderRules, ResponseHeaderRules,
-    Route, Scheme, Server, SharingMode, SustainedRate, UpdateRouteRequest,
-    UpdateRouteRequestBuilder, UpdateUpstreamRequest, UpdateUpstreamRequestBuilder, Upstream,
-    Window,
+    KeepaliveConfig, ListQuery, MatchRules, PassthroughMode, PathSuffixMode, PluginsConfig,
+    RateLimitAlgorithm, RateLimitConfig, RateLimitScope, RateLimitStrategy, RequestHeaderRules,
+    ResponseHeaderRules, RetryOnBody, Route, RouteKind, Scheme, Server, SharingMode, SustainedRate,
+    TrailingSlash, UpdateRouteRequest, UpdateRouteRequestBuilder, UpdateUpstreamRequest,
+    UpdateUpstreamRequestBuilder, Upstream, Window,
 };
 
-pub use api::ServiceGatewayClientV1;
-pub use body::Body;
+pub use api::{
+    CircuitStatus, ClientIp, ConfigProblem, ConfigSnapshot, ConfigSource, FlushedCaches,
+    GatewayStatus, RateLimitBucketState, RecentError, RequestTrace, RouteDescription,
+    ServiceGatewayClientV1, Sourced, TraceAuthz, UpstreamLatency, UpstreamRuntimeState,
+    UpstreamTestResult, VersionInfo,
+};
+pub use audit::{AuditAction, AuditEntityType, AuditEvent, AuditSink, AuditSnapshot};
+pub use body::{Body, Trailers};
+pub use cache::{CachedResponse, ResponseCache};
 pub use codec::Json;
 pub use error::StreamingError;
+pub use metrics::{MetricLabel, MetricsSink};
 pub use multipart::{MultipartBody, MultipartError, Part};
+pub use secret::{SecretResolver, SecretResolverError};
 pub use sse::{FromServerEvent, ServerEvent, ServerEventsResponse, ServerEventsStream};
+pub use transform::BodyTransformer;
 #[cfg(feature = "axum")]
 pub use ws::axum_adapter;
 pub use ws::{
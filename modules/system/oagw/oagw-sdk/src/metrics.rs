@@ -0,0 +1,46 @@
+//! Metrics backend for OAGW.
+//!
+//! The data plane records its counters and histograms through a
+//! [`MetricsSink`]. OpenTelemetry instruments (exported to Prometheus) are
+//! used by default; deployments shipping metrics elsewhere register their own
+//! sink in the `ClientHub` before the `oagw` module initializes.
+
+/// Histogram of `AuthPlugin::authenticate` latency, in seconds.
+/// Labels: `plugin_id`, `result` (`success` | `failure`).
+pub const AUTH_PLUGIN_DURATION: &str = "oagw.auth_plugin.duration";
+/// Counter of requests rejected for exceeding `max_body_size`.
+/// Labels: `route_id`.
+pub const BODY_LIMIT_EXCEEDED: &str = "oagw.body_limit.exceeded";
+/// Histogram of the declared size of rejected request bodies, in bytes.
+/// Labels: `route_id`.
+pub const BODY_LIMIT_DECLARED_SIZE: &str = "oagw.body_limit.declared_size";
+/// Counter of proxied responses.
+/// Labels: `upstream_id`, `status`, `result` (`success` | `error`, per the
+/// configured `error_status_codes`).
+pub const PROXY_RESPONSES: &str = "oagw.proxy.responses";
+/// Counter of cache lookups.
+/// Labels: `cache` (`response` | `token` | `dns`), `result` (`hit` | `miss`).
+pub const CACHE_LOOKUPS: &str = "oagw.cache.lookups";
+/// Gauge of the number of entries held by a cache.
+/// Labels: `cache` (`dns` only: the response and token caches evict
+/// silently and expose no entry count).
+pub const CACHE_SIZE: &str = "oagw.cache.size";
+
+/// Metric label as a `(key, value)` pair.
+pub type MetricLabel<'a> = (&'static str, &'a str);
+
+/// Pluggable destination for data-plane metrics.
+///
+/// Metric names are the constants in this module. Calls happen on the request
+/// path, so implementations should buffer or aggregate rather than block.
+pub trait MetricsSink: Send + Sync {
+    /// Add `value` to the counter `name`.
+    fn increment_counter(&self, name: &'static str, value: u64, labels: &[MetricLabel<'_>]);
+
+    /// Record one observation of `value` in the histogram `name`.
+    fn record_histogram(&self, name: &'static str, value: f64, labels: &[MetricLabel<'_>]);
+
+    /// Set the gauge `name` to `value`. Does nothing by default, so sinks
+    /// that only handle counters and histograms need not implement it.
+    fn record_gauge(&self, _name: &'static str, _value: u64, _labels: &[MetricLabel<'_>]) {}
+}
@@ -189,6 +189,127 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+// ---------------------------------------------------------------------------
+// RetryOnBody
+// ---------------------------------------------------------------------------
+
+/// Retries buffered 2xx responses whose body matches an error envelope.
+///
+/// Only evaluated for idempotent methods (`GET`, `HEAD`, `PUT`, `DELETE`,
+/// `OPTIONS`) with a non-streaming response body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryOnBody {
+    pub matcher: BodyMatcher,
+    /// Maximum number of additional attempts (1-5).
+    pub max_retries: u32,
+}
+
+/// Condition evaluated against a buffered response body.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BodyMatcher {
+    /// Matches when the JSON value at `pointer` (RFC 6901) equals `value`.
+    JsonPointer {
+        pointer: String,
+        value: serde_json::Value,
+    },
+    /// Matches when the body, decoded as UTF-8, matches `pattern`.
+    Regex { pattern: String },
+}
+
+// ---------------------------------------------------------------------------
+// BodyRewriteRule
+// ---------------------------------------------------------------------------
+
+/// Find/replace rule applied to buffered response bodies.
+///
+/// Only non-streaming responses with a `Content-Length` within the body size
+/// limit, a matching content type, and UTF-8 content are rewritten.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BodyRewriteRule {
+    /// Literal text, or a regular expression when `regex` is set.
+    pub find: String,
+    /// Replacement text; with `regex`, `$1`/`${name}` refer to capture groups.
+    pub replace: String,
+    pub regex: bool,
+    /// Response media types the rule applies to. Empty means textual types:
+    /// `text/*`, JSON, XML, and JavaScript.
+    pub content_types: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// AuthRule
+// ---------------------------------------------------------------------------
+
+/// Auth chosen for requests matching `condition`, in place of the upstream's
+/// `auth`. A route's rules are tried in order and the first match wins;
+/// requests matching none use the upstream's auth.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthRule {
+    pub condition: AuthCondition,
+    /// Auth to apply; `None` sends matching requests without upstream auth.
+    pub auth: Option<AuthConfig>,
+}
+
+/// Request condition of an [`AuthRule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthCondition {
+    /// The request path after the alias is `prefix` or continues it with `/`.
+    PathPrefix { prefix: String },
+    /// The request carries header `name`, equal to `value` when one is set.
+    Header { name: String, value: Option<String> },
+}
+
+// ---------------------------------------------------------------------------
+// ApiVersion
+// ---------------------------------------------------------------------------
+
+/// API version sent upstream on every request of a route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiVersion {
+    /// Version string, e.g. `2024-06-01`.
+    pub value: String,
+    /// Header carrying the version. Default: `Api-Version`.
+    pub header: Option<String>,
+    /// Query parameter also carrying the version, for upstreams expecting one.
+    pub query_param: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// KeepaliveConfig
+// ---------------------------------------------------------------------------
+
+/// Liveness probing of pooled upstream connections, so that half-open
+/// connections are detected while idle rather than on the next request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KeepaliveConfig {
+    /// Idle seconds before TCP keep-alive probes start. Unset leaves TCP
+    /// keep-alive off.
+    pub tcp_idle_secs: Option<u64>,
+    /// Seconds between TCP keep-alive probes. Defaults to `tcp_idle_secs`.
+    pub tcp_interval_secs: Option<u64>,
+    /// Unanswered probes after which the connection is dropped. Default: 3.
+    pub tcp_probe_count: Option<u32>,
+    /// Seconds between HTTP/2 `PING` frames on `h2c` and gRPC connections.
+    /// Unset disables pings.
+    pub h2_ping_interval_secs: Option<u64>,
+}
+
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+/// Per-upstream circuit breaker overrides. Unset fields fall back to the
+/// gateway-wide `cb_*` settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit.
+    pub failure_threshold: Option<u32>,
+    /// Seconds the circuit stays open before probing the upstream.
+    pub cooldown_secs: Option<u64>,
+    /// Requests admitted while half-open; all must succeed to close.
+    pub half_open_probes: Option<u32>,
+}
+
 // ---------------------------------------------------------------------------
 // PluginsConfig
 // ---------------------------------------------------------------------------
@@ -223,6 +344,18 @@ pub enum PathSuffixMode {
     Append,
 }
 
+/// How a trailing slash on the outbound path is treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingSlash {
+    /// Forward the path as built from the route and request.
+    #[default]
+    Preserve,
+    /// Remove a trailing slash (the root path `/` is kept).
+    Strip,
+    /// Append a slash to paths that lack one.
+    Add,
+}
+
 /// HTTP-protocol match rules for a route.
 #[derive(Debug, Clone, PartialEq)]
 pub struct HttpMatch {
@@ -233,6 +366,9 @@ pub struct HttpMatch {
     /// Allowed query parameters. Empty = allow none.
     pub query_allowlist: Vec<String>,
     pub path_suffix_mode: PathSuffixMode,
+    /// Condition on the request body. Using it forces the gateway to buffer
+    /// request bodies on this route (up to the configured maximum body size).
+    pub match_body: Option<BodyMatcher>,
 }
 
 /// gRPC-protocol match rules for a route (future use).
@@ -249,6 +385,24 @@ pub struct MatchRules {
     pub grpc: Option<GrpcMatch>,
 }
 
+/// What a matched route does with the request.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum RouteKind {
+    /// Forward to the route's upstream.
+    #[default]
+    Proxy,
+    /// Answer locally with a fixed response, without contacting any upstream.
+    ///
+    /// Static routes must not reference an upstream (`upstream_id` is nil).
+    /// Their match path is compared against the full proxy path, alias
+    /// segment included (e.g. `/.well-known/openid-configuration`).
+    Static {
+        status: u16,
+        headers: HashMap<String, String>,
+        body: String,
+    },
+}
+
 // ---------------------------------------------------------------------------
 // Domain entities
 // ---------------------------------------------------------------------------
@@ -263,8 +417,85 @@ pub struct Route {
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
     pub tags: Vec<String>,
+    /// Among routes matching a request, the highest priority wins; the
+    /// longest path prefix breaks ties.
     pub priority: i32,
     pub enabled: bool,
+    /// When true, proxy requests matching this route bypass the AuthZ
+    /// (`PolicyEnforcer`) check. Intended for public paths such as health checks.
+    /// Callers are still authenticated before the request reaches the gateway.
+    pub skip_authz: bool,
+    /// Proxy timeout override in seconds. Takes precedence over the
+    /// upstream's `timeout_secs` and the global `proxy_timeout_secs`.
+    pub timeout_secs: Option<u64>,
+    /// Upstream response status codes rewritten before returning to the
+    /// client (e.g. `418 → 429`). The body is preserved and the original
+    /// status is reported in `x-oagw-original-status`.
+    pub status_remap: HashMap<u16, u16>,
+    pub allowed_response_content_types: Vec<String>,
+    pub allowed_request_content_types: Vec<String>,
+    /// Fraction (`0.0..=1.0`) of matching requests logged in detail.
+    /// `None` disables detailed logging.
+    pub log_sample_rate: Option<f64>,
+    /// JSON Schema that buffered JSON request bodies must satisfy; bodies
+    /// that do not are rejected with 400 listing the violations.
+    pub request_schema: Option<serde_json::Value>,
+    /// Find/replace rules applied, in order, to buffered response bodies.
+    pub body_rewrite: Vec<BodyRewriteRule>,
+    /// Per-request auth selection overriding the upstream's `auth`.
+    pub auth_rules: Vec<AuthRule>,
+    /// Query parameters removed from the outbound URL before forwarding.
+    pub strip_query_params: Vec<String>,
+    /// Request headers removed before auth plugins run, in addition to the
+    /// gateway-wide `strip_inbound_headers`.
+    pub strip_inbound_headers: Vec<String>,
+    /// Seconds a response is replayed for requests repeating its
+    /// `Idempotency-Key`; unset disables deduplication.
+    pub idempotency_ttl_secs: Option<u64>,
+    /// Cache `GET` responses that carry explicit freshness. Off by
+    /// default: nothing is served from the response cache unless the route
+    /// opts in.
+    pub cache_responses: bool,
+    /// Seconds 404 and 410 responses to `GET` are cached for; unset
+    /// disables negative caching.
+    pub negative_cache_ttl_secs: Option<u64>,
+    /// When set, only these upstream response headers (plus body framing
+    /// headers) are forwarded; all others are dropped.
+    pub response_header_allowlist: Option<Vec<String>>,
+    /// Hard deadline in milliseconds for the whole exchange, connect through
+    /// the last body byte. Exceeding it fails with 504, or cuts off a
+    /// response already streaming.
+    pub max_total_duration_ms: Option<u64>,
+    /// Request body limit in bytes for this route. It can only tighten
+    /// the gateway-wide limit; larger bodies are rejected with 413.
+    pub max_body_size_bytes: Option<u64>,
+    /// Most upstream calls one inbound request may make, counting the
+    /// first attempt, connect retries, reconnects, failover endpoints and
+    /// body-based retries. A request that would need more fails with 502.
+    pub max_outbound_calls: Option<u32>,
+    /// Content type forced onto outbound requests that carry a body, for
+    /// upstreams stricter than their clients. One of the coercible types.
+    pub coerce_content_type: Option<String>,
+    /// API version header (and optionally query parameter) set on every
+    /// outbound request. Upstream header rules can still override the header.
+    pub api_version: Option<ApiVersion>,
+    /// Names of registered [`BodyTransformer`](crate::BodyTransformer)s run,
+    /// in order, over request and response bodies of matching content types.
+    pub body_transformers: Vec<String>,
+    /// JSON pointers of fields masked in sampled request logs, where `*`
+    /// matches any array element or key. Buffered JSON bodies are only
+    /// logged for routes that set it.
+    pub log_redact_paths: Vec<String>,
+    /// External service the request is POSTed to before forwarding; its
+    /// answer replaces the outbound headers and/or body.
+    pub transform_hook_url: Option<String>,
+    /// Responses carry `Deprecation: true`, announcing the route's removal.
+    pub deprecated: bool,
+    /// HTTP-date (IMF-fixdate) after which the route may stop working, sent
+    /// as the `Sunset` response header.
+    pub sunset: Option<String>,
+    /// Proxy to the upstream (default) or answer with a static response.
+    pub kind: RouteKind,
 }
 
 /// An external upstream service configuration.
@@ -274,7 +505,10 @@ pub struct Upstream {
     pub tenant_id: Uuid,
     pub alias: String,
     pub server: Server,
-    /// Protocol GTS identifier (e.g. `gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1`).
+    /// Protocol GTS identifier (e.g. `gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1`)
+    /// or short name: `http1`, `h2c` (HTTP/2 prior knowledge) or `grpc`.
+    /// gRPC responses carry the upstream trailers as a final gRPC-Web trailer
+    /// frame (flag `0x80`) in the body.
     pub protocol: String,
     pub enabled: bool,
     pub auth: Option<AuthConfig>,
@@ -282,6 +516,54 @@ pub struct Upstream {
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
     pub tags: Vec<String>,
+    /// When true, the gateway answers proxy requests with 503 without
+    /// contacting the upstream.
+    pub maintenance: bool,
+    /// Optional operator message returned in the 503 body during maintenance.
+    pub maintenance_message: Option<String>,
+    /// Retry buffered 2xx responses whose body matches an error envelope.
+    pub retry_on_body: Option<RetryOnBody>,
+    /// Send request bodies with `Transfer-Encoding: chunked` instead of
+    /// `Content-Length`, for upstreams that reject length-delimited bodies.
+    pub force_chunked: bool,
+    /// Secondary endpoints tried in order when the primary endpoint fails
+    /// (connection error, or 502/503/504 for idempotent requests).
+    pub failover: Vec<Endpoint>,
+    /// Proxy timeout override in seconds for all routes of this upstream.
+    /// Falls back to the global `proxy_timeout_secs` when unset.
+    pub timeout_secs: Option<u64>,
+    /// Keep-alive probing of idle pooled connections.
+    pub keepalive: Option<KeepaliveConfig>,
+    /// Circuit breaker overrides for this upstream.
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// `User-Agent` sent upstream, overriding the gateway-wide `user_agent`.
+    pub user_agent: Option<String>,
+    /// Pin requests to one endpoint by consistent hashing over a sticky key:
+    /// `header:<name>` (value of a request header) or `tenant`.
+    pub sticky_by: Option<String>,
+    /// Methods proxied to this upstream; others get 405 with an `Allow`
+    /// header before reaching the backend. Empty allows every method.
+    pub allowed_methods: Vec<HttpMethod>,
+    /// Trailing-slash policy applied to the outbound path.
+    pub trailing_slash: TrailingSlash,
+    /// Largest outbound header, name plus value, in bytes; requests with a
+    /// bigger one get 431. Unset means no limit.
+    pub max_header_size_bytes: Option<u64>,
+    /// Lowest TLS version accepted from the upstream: `1.2` or `1.3`.
+    /// Connections negotiating an older version are refused with 502.
+    pub tls_min_version: Option<String>,
+    /// TLS cipher suites accepted from the upstream, by name (e.g.
+    /// `TLS13_AES_256_GCM_SHA384`). Connections negotiating another suite
+    /// are refused with 502. Empty accepts any suite the gateway supports.
+    pub tls_cipher_suites: Vec<String>,
+    /// Hostname the upstream's TLS certificate is verified against, and
+    /// sent as SNI, instead of the endpoint host. For endpoints addressed
+    /// by IP or an internal name that the certificate does not cover.
+    pub tls_server_name: Option<String>,
+    /// Request header (e.g. `X-Timeout-Ms`) set to the milliseconds left of
+    /// the request's time budget, after queueing and auth, so the upstream
+    /// can limit its own work. Unset sends no budget.
+    pub budget_header: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -319,6 +601,23 @@ pub struct CreateUpstreamRequest {
     rate_limit: Option<RateLimitConfig>,
     tags: Vec<String>,
     enabled: bool,
+    maintenance: bool,
+    maintenance_message: Option<String>,
+    retry_on_body: Option<RetryOnBody>,
+    force_chunked: bool,
+    failover: Vec<Endpoint>,
+    timeout_secs: Option<u64>,
+    keepalive: Option<KeepaliveConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    user_agent: Option<String>,
+    sticky_by: Option<String>,
+    allowed_methods: Vec<HttpMethod>,
+    trailing_slash: TrailingSlash,
+    max_header_size_bytes: Option<u64>,
+    tls_min_version: Option<String>,
+    tls_cipher_suites: Vec<String>,
+    tls_server_name: Option<String>,
+    budget_header: Option<String>,
 }
 
 impl CreateUpstreamRequest {
@@ -334,6 +633,23 @@ impl CreateUpstreamRequest {
             rate_limit: None,
             tags: vec![],
             enabled: true,
+            maintenance: false,
+            maintenance_message: None,
+            retry_on_body: None,
+            force_chunked: false,
+            failover: vec![],
+            timeout_secs: None,
+            keepalive: None,
+            circuit_breaker: None,
+            user_agent: None,
+            sticky_by: None,
+            allowed_methods: vec![],
+            trailing_slash: TrailingSlash::default(),
+            max_header_size_bytes: None,
+            tls_min_version: None,
+            tls_cipher_suites: vec![],
+            tls_server_name: None,
+            budget_header: None,
         }
     }
 
@@ -364,6 +680,57 @@ impl CreateUpstreamRequest {
     pub fn enabled(&self) -> bool {
         self.enabled
     }
+    pub fn maintenance(&self) -> bool {
+        self.maintenance
+    }
+    pub fn maintenance_message(&self) -> Option<&str> {
+        self.maintenance_message.as_deref()
+    }
+    pub fn retry_on_body(&self) -> Option<&RetryOnBody> {
+        self.retry_on_body.as_ref()
+    }
+    pub fn force_chunked(&self) -> bool {
+        self.force_chunked
+    }
+    pub fn failover(&self) -> &[Endpoint] {
+        &self.failover
+    }
+    pub fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+    pub fn keepalive(&self) -> Option<&KeepaliveConfig> {
+        self.keepalive.as_ref()
+    }
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreakerConfig> {
+        self.circuit_breaker.as_ref()
+    }
+    pub fn user_agent(&self) -> Option<&str> {
+        self.user_agent.as_deref()
+    }
+    pub fn sticky_by(&self) -> Option<&str> {
+        self.sticky_by.as_deref()
+    }
+    pub fn allowed_methods(&self) -> &[HttpMethod] {
+        &self.allowed_methods
+    }
+    pub fn trailing_slash(&self) -> TrailingSlash {
+        self.trailing_slash
+    }
+    pub fn max_header_size_bytes(&self) -> Option<u64> {
+        self.max_header_size_bytes
+    }
+    pub fn tls_min_version(&self) -> Option<&str> {
+        self.tls_min_version.as_deref()
+    }
+    pub fn tls_cipher_suites(&self) -> &[String] {
+        &self.tls_cipher_suites
+    }
+    pub fn tls_server_name(&self) -> Option<&str> {
+        self.tls_server_name.as_deref()
+    }
+    pub fn budget_header(&self) -> Option<&str> {
+        self.budget_header.as_deref()
+    }
 }
 
 pub struct CreateUpstreamRequestBuilder {
@@ -376,6 +743,23 @@ pub struct CreateUpstreamRequestBuilder {
     rate_limit: Option<RateLimitConfig>,
     tags: Vec<String>,
     enabled: bool,
+    maintenance: bool,
+    maintenance_message: Option<String>,
+    retry_on_body: Option<RetryOnBody>,
+    force_chunked: bool,
+    failover: Vec<Endpoint>,
+    timeout_secs: Option<u64>,
+    keepalive: Option<KeepaliveConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    user_agent: Option<String>,
+    sticky_by: Option<String>,
+    allowed_methods: Vec<HttpMethod>,
+    trailing_slash: TrailingSlash,
+    max_header_size_bytes: Option<u64>,
+    tls_min_version: Option<String>,
+    tls_cipher_suites: Vec<String>,
+    tls_server_name: Option<String>,
+    budget_header: Option<String>,
 }
 
 impl CreateUpstreamRequestBuilder {
@@ -407,6 +791,74 @@ impl CreateUpstreamRequestBuilder {
         self.enabled = enabled;
         self
     }
+    pub fn maintenance(mut self, maintenance: bool) -> Self {
+        self.maintenance = maintenance;
+        self
+    }
+    pub fn maintenance_message(mut self, message: impl Into<String>) -> Self {
+        self.maintenance_message = Some(message.into());
+        self
+    }
+    pub fn retry_on_body(mut self, retry_on_body: RetryOnBody) -> Self {
+        self.retry_on_body = Some(retry_on_body);
+        self
+    }
+    pub fn force_chunked(mut self, force_chunked: bool) -> Self {
+        self.force_chunked = force_chunked;
+        self
+    }
+    pub fn failover(mut self, failover: Vec<Endpoint>) -> Self {
+        self.failover = failover;
+        self
+    }
+    pub fn timeout_secs(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = Some(timeout_secs);
+        self
+    }
+    pub fn keepalive(mut self, keepalive: KeepaliveConfig) -> Self {
+        self.keepalive = Some(keepalive);
+        self
+    }
+    pub fn circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+    pub fn sticky_by(mut self, sticky_by: impl Into<String>) -> Self {
+        self.sticky_by = Some(sticky_by.into());
+        self
+    }
+    pub fn allowed_methods(mut self, allowed_methods: Vec<HttpMethod>) -> Self {
+        self.allowed_methods = allowed_methods;
+        self
+    }
+    pub fn trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+        self.trailing_slash = trailing_slash;
+        self
+    }
+    pub fn max_header_size_bytes(mut self, max_header_size_bytes: u64) -> Self {
+        self.max_header_size_bytes = Some(max_header_size_bytes);
+        self
+    }
+    pub fn tls_min_version(mut self, tls_min_version: impl Into<String>) -> Self {
+        self.tls_min_version = Some(tls_min_version.into());
+        self
+    }
+    pub fn tls_cipher_suites(mut self, tls_cipher_suites: Vec<String>) -> Self {
+        self.tls_cipher_suites = tls_cipher_suites;
+        self
+    }
+    pub fn tls_server_name(mut self, tls_server_name: impl Into<String>) -> Self {
+        self.tls_server_name = Some(tls_server_name.into());
+        self
+    }
+    pub fn budget_header(mut self, budget_header: impl Into<String>) -> Self {
+        self.budget_header = Some(budget_header.into());
+        self
+    }
     pub fn build(self) -> CreateUpstreamRequest {
         CreateUpstreamRequest {
             server: self.server,
@@ -418,6 +870,23 @@ impl CreateUpstreamRequestBuilder {
             rate_limit: self.rate_limit,
             tags: self.tags,
             enabled: self.enabled,
+            maintenance: self.maintenance,
+            maintenance_message: self.maintenance_message,
+            retry_on_body: self.retry_on_body,
+            force_chunked: self.force_chunked,
+            failover: self.failover,
+            timeout_secs: self.timeout_secs,
+            keepalive: self.keepalive,
+            circuit_breaker: self.circuit_breaker,
+            user_agent: self.user_agent,
+            sticky_by: self.sticky_by,
+            allowed_methods: self.allowed_methods,
+            trailing_slash: self.trailing_slash,
+            max_header_size_bytes: self.max_header_size_bytes,
+            tls_min_version: self.tls_min_version,
+            tls_cipher_suites: self.tls_cipher_suites,
+            tls_server_name: self.tls_server_name,
+            budget_header: self.budget_header,
         }
     }
 }
@@ -435,6 +904,23 @@ pub struct UpdateUpstreamRequest {
     rate_limit: Option<RateLimitConfig>,
     tags: Option<Vec<String>>,
     enabled: Option<bool>,
+    maintenance: Option<bool>,
+    maintenance_message: Option<String>,
+    retry_on_body: Option<RetryOnBody>,
+    force_chunked: Option<bool>,
+    failover: Option<Vec<Endpoint>>,
+    timeout_secs: Option<u64>,
+    keepalive: Option<KeepaliveConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    user_agent: Option<String>,
+    sticky_by: Option<String>,
+    allowed_methods: Option<Vec<HttpMethod>>,
+    trailing_slash: Option<TrailingSlash>,
+    max_header_size_bytes: Option<u64>,
+    tls_min_version: Option<String>,
+    tls_cipher_suites: Option<Vec<String>>,
+    tls_server_name: Option<String>,
+    budget_header: Option<String>,
 }
 
 impl UpdateUpstreamRequest {
@@ -470,6 +956,57 @@ impl UpdateUpstreamRequest {
     pub fn enabled(&self) -> Option<bool> {
         self.enabled
     }
+    pub fn maintenance(&self) -> Option<bool> {
+        self.maintenance
+    }
+    pub fn maintenance_message(&self) -> Option<&str> {
+        self.maintenance_message.as_deref()
+    }
+    pub fn retry_on_body(&self) -> Option<&RetryOnBody> {
+        self.retry_on_body.as_ref()
+    }
+    pub fn force_chunked(&self) -> Option<bool> {
+        self.force_chunked
+    }
+    pub fn failover(&self) -> Option<&[Endpoint]> {
+        self.failover.as_deref()
+    }
+    pub fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+    pub fn keepalive(&self) -> Option<&KeepaliveConfig> {
+        self.keepalive.as_ref()
+    }
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreakerConfig> {
+        self.circuit_breaker.as_ref()
+    }
+    pub fn user_agent(&self) -> Option<&str> {
+        self.user_agent.as_deref()
+    }
+    pub fn sticky_by(&self) -> Option<&str> {
+        self.sticky_by.as_deref()
+    }
+    pub fn allowed_methods(&self) -> Option<&[HttpMethod]> {
+        self.allowed_methods.as_deref()
+    }
+    pub fn trailing_slash(&self) -> Option<TrailingSlash> {
+        self.trailing_slash
+    }
+    pub fn max_header_size_bytes(&self) -> Option<u64> {
+        self.max_header_size_bytes
+    }
+    pub fn tls_min_version(&self) -> Option<&str> {
+        self.tls_min_version.as_deref()
+    }
+    pub fn tls_cipher_suites(&self) -> Option<&[String]> {
+        self.tls_cipher_suites.as_deref()
+    }
+    pub fn tls_server_name(&self) -> Option<&str> {
+        self.tls_server_name.as_deref()
+    }
+    pub fn budget_header(&self) -> Option<&str> {
+        self.budget_header.as_deref()
+    }
 }
 
 #[derive(Default)]
@@ -483,6 +1020,23 @@ pub struct UpdateUpstreamRequestBuilder {
     rate_limit: Option<RateLimitConfig>,
     tags: Option<Vec<String>>,
     enabled: Option<bool>,
+    maintenance: Option<bool>,
+    maintenance_message: Option<String>,
+    retry_on_body: Option<RetryOnBody>,
+    force_chunked: Option<bool>,
+    failover: Option<Vec<Endpoint>>,
+    timeout_secs: Option<u64>,
+    keepalive: Option<KeepaliveConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    user_agent: Option<String>,
+    sticky_by: Option<String>,
+    allowed_methods: Option<Vec<HttpMethod>>,
+    trailing_slash: Option<TrailingSlash>,
+    max_header_size_bytes: Option<u64>,
+    tls_min_version: Option<String>,
+    tls_cipher_suites: Option<Vec<String>>,
+    tls_server_name: Option<String>,
+    budget_header: Option<String>,
 }
 
 impl UpdateUpstreamRequestBuilder {
@@ -522,6 +1076,74 @@ impl UpdateUpstreamRequestBuilder {
         self.enabled = Some(enabled);
         self
     }
+    pub fn maintenance(mut self, maintenance: bool) -> Self {
+        self.maintenance = Some(maintenance);
+        self
+    }
+    pub fn maintenance_message(mut self, message: impl Into<String>) -> Self {
+        self.maintenance_message = Some(message.into());
+        self
+    }
+    pub fn retry_on_body(mut self, retry_on_body: RetryOnBody) -> Self {
+        self.retry_on_body = Some(retry_on_body);
+        self
+    }
+    pub fn force_chunked(mut self, force_chunked: bool) -> Self {
+        self.force_chunked = Some(force_chunked);
+        self
+    }
+    pub fn failover(mut self, failover: Vec<Endpoint>) -> Self {
+        self.failover = Some(failover);
+        self
+    }
+    pub fn timeout_secs(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = Some(timeout_secs);
+        self
+    }
+    pub fn keepalive(mut self, keepalive: KeepaliveConfig) -> Self {
+        self.keepalive = Some(keepalive);
+        self
+    }
+    pub fn circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+    pub fn sticky_by(mut self, sticky_by: impl Into<String>) -> Self {
+        self.sticky_by = Some(sticky_by.into());
+        self
+    }
+    pub fn allowed_methods(mut self, allowed_methods: Vec<HttpMethod>) -> Self {
+        self.allowed_methods = Some(allowed_methods);
+        self
+    }
+    pub fn trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+        self.trailing_slash = Some(trailing_slash);
+        self
+    }
+    pub fn max_header_size_bytes(mut self, max_header_size_bytes: u64) -> Self {
+        self.max_header_size_bytes = Some(max_header_size_bytes);
+        self
+    }
+    pub fn tls_min_version(mut self, tls_min_version: impl Into<String>) -> Self {
+        self.tls_min_version = Some(tls_min_version.into());
+        self
+    }
+    pub fn tls_cipher_suites(mut self, tls_cipher_suites: Vec<String>) -> Self {
+        self.tls_cipher_suites = Some(tls_cipher_suites);
+        self
+    }
+    pub fn tls_server_name(mut self, tls_server_name: impl Into<String>) -> Self {
+        self.tls_server_name = Some(tls_server_name.into());
+        self
+    }
+    pub fn budget_header(mut self, budget_header: impl Into<String>) -> Self {
+        self.budget_header = Some(budget_header.into());
+        self
+    }
     pub fn build(self) -> UpdateUpstreamRequest {
         UpdateUpstreamRequest {
             server: self.server,
@@ -533,6 +1155,23 @@ impl UpdateUpstreamRequestBuilder {
             rate_limit: self.rate_limit,
             tags: self.tags,
             enabled: self.enabled,
+            maintenance: self.maintenance,
+            maintenance_message: self.maintenance_message,
+            retry_on_body: self.retry_on_body,
+            force_chunked: self.force_chunked,
+            failover: self.failover,
+            timeout_secs: self.timeout_secs,
+            keepalive: self.keepalive,
+            circuit_breaker: self.circuit_breaker,
+            user_agent: self.user_agent,
+            sticky_by: self.sticky_by,
+            allowed_methods: self.allowed_methods,
+            trailing_slash: self.trailing_slash,
+            max_header_size_bytes: self.max_header_size_bytes,
+            tls_min_version: self.tls_min_version,
+            tls_cipher_suites: self.tls_cipher_suites,
+            tls_server_name: self.tls_server_name,
+            budget_header: self.budget_header,
         }
     }
 }
@@ -551,6 +1190,32 @@ pub struct CreateRouteRequest {
     tags: Vec<String>,
     priority: i32,
     enabled: bool,
+    skip_authz: bool,
+    timeout_secs: Option<u64>,
+    status_remap: HashMap<u16, u16>,
+    allowed_response_content_types: Vec<String>,
+    allowed_request_content_types: Vec<String>,
+    log_sample_rate: Option<f64>,
+    request_schema: Option<serde_json::Value>,
+    body_rewrite: Vec<BodyRewriteRule>,
+    auth_rules: Vec<AuthRule>,
+    strip_query_params: Vec<String>,
+    strip_inbound_headers: Vec<String>,
+    idempotency_ttl_secs: Option<u64>,
+    cache_responses: bool,
+    negative_cache_ttl_secs: Option<u64>,
+    response_header_allowlist: Option<Vec<String>>,
+    max_total_duration_ms: Option<u64>,
+    max_body_size_bytes: Option<u64>,
+    max_outbound_calls: Option<u32>,
+    coerce_content_type: Option<String>,
+    api_version: Option<ApiVersion>,
+    body_transformers: Vec<String>,
+    log_redact_paths: Vec<String>,
+    transform_hook_url: Option<String>,
+    deprecated: bool,
+    sunset: Option<String>,
+    kind: RouteKind,
 }
 
 impl CreateRouteRequest {
@@ -564,6 +1229,32 @@ impl CreateRouteRequest {
             tags: vec![],
             priority: 0,
             enabled: true,
+            skip_authz: false,
+            timeout_secs: None,
+            status_remap: HashMap::new(),
+            allowed_response_content_types: vec![],
+            allowed_request_content_types: vec![],
+            log_sample_rate: None,
+            request_schema: None,
+            body_rewrite: vec![],
+            auth_rules: vec![],
+            strip_query_params: vec![],
+            strip_inbound_headers: vec![],
+            idempotency_ttl_secs: None,
+            cache_responses: false,
+            negative_cache_ttl_secs: None,
+            response_header_allowlist: None,
+            max_total_duration_ms: None,
+            max_body_size_bytes: None,
+            max_outbound_calls: None,
+            coerce_content_type: None,
+            api_version: None,
+            body_transformers: vec![],
+            log_redact_paths: vec![],
+            transform_hook_url: None,
+            deprecated: false,
+            sunset: None,
+            kind: RouteKind::Proxy,
         }
     }
 
@@ -588,6 +1279,88 @@ impl CreateRouteRequest {
     pub fn enabled(&self) -> bool {
         self.enabled
     }
+    pub fn skip_authz(&self) -> bool {
+        self.skip_authz
+    }
+    pub fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+    pub fn status_remap(&self) -> &HashMap<u16, u16> {
+        &self.status_remap
+    }
+    pub fn allowed_request_content_types(&self) -> &[String] {
+        &self.allowed_request_content_types
+    }
+    pub fn allowed_response_content_types(&self) -> &[String] {
+        &self.allowed_response_content_types
+    }
+    pub fn log_sample_rate(&self) -> Option<f64> {
+        self.log_sample_rate
+    }
+    pub fn request_schema(&self) -> Option<&serde_json::Value> {
+        self.request_schema.as_ref()
+    }
+    pub fn body_rewrite(&self) -> &[BodyRewriteRule] {
+        &self.body_rewrite
+    }
+    pub fn auth_rules(&self) -> &[AuthRule] {
+        &self.auth_rules
+    }
+
+    pub fn strip_query_params(&self) -> &[String] {
+        &self.strip_query_params
+    }
+
+    pub fn strip_inbound_headers(&self) -> &[String] {
+        &self.strip_inbound_headers
+    }
+
+    pub fn idempotency_ttl_secs(&self) -> Option<u64> {
+        self.idempotency_ttl_secs
+    }
+    pub fn cache_responses(&self) -> bool {
+        self.cache_responses
+    }
+    pub fn negative_cache_ttl_secs(&self) -> Option<u64> {
+        self.negative_cache_ttl_secs
+    }
+    pub fn response_header_allowlist(&self) -> Option<&[String]> {
+        self.response_header_allowlist.as_deref()
+    }
+    pub fn max_total_duration_ms(&self) -> Option<u64> {
+        self.max_total_duration_ms
+    }
+    pub fn max_body_size_bytes(&self) -> Option<u64> {
+        self.max_body_size_bytes
+    }
+    pub fn max_outbound_calls(&self) -> Option<u32> {
+        self.max_outbound_calls
+    }
+    pub fn coerce_content_type(&self) -> Option<&str> {
+        self.coerce_content_type.as_deref()
+    }
+    pub fn api_version(&self) -> Option<&ApiVersion> {
+        self.api_version.as_ref()
+    }
+
+    pub fn body_transformers(&self) -> &[String] {
+        &self.body_transformers
+    }
+    pub fn log_redact_paths(&self) -> &[String] {
+        &self.log_redact_paths
+    }
+    pub fn transform_hook_url(&self) -> Option<&str> {
+        self.transform_hook_url.as_deref()
+    }
+    pub fn deprecated(&self) -> bool {
+        self.deprecated
+    }
+    pub fn sunset(&self) -> Option<&str> {
+        self.sunset.as_deref()
+    }
+    pub fn kind(&self) -> &RouteKind {
+        &self.kind
+    }
 }
 
 pub struct CreateRouteRequestBuilder {
@@ -598,6 +1371,32 @@ pub struct CreateRouteRequestBuilder {
     tags: Vec<String>,
     priority: i32,
     enabled: bool,
+    skip_authz: bool,
+    timeout_secs: Option<u64>,
+    status_remap: HashMap<u16, u16>,
+    allowed_response_content_types: Vec<String>,
+    allowed_request_content_types: Vec<String>,
+    log_sample_rate: Option<f64>,
+    request_schema: Option<serde_json::Value>,
+    body_rewrite: Vec<BodyRewriteRule>,
+    auth_rules: Vec<AuthRule>,
+    strip_query_params: Vec<String>,
+    strip_inbound_headers: Vec<String>,
+    idempotency_ttl_secs: Option<u64>,
+    cache_responses: bool,
+    negative_cache_ttl_secs: Option<u64>,
+    response_header_allowlist: Option<Vec<String>>,
+    max_total_duration_ms: Option<u64>,
+    max_body_size_bytes: Option<u64>,
+    max_outbound_calls: Option<u32>,
+    coerce_content_type: Option<String>,
+    api_version: Option<ApiVersion>,
+    body_transformers: Vec<String>,
+    log_redact_paths: Vec<String>,
+    transform_hook_url: Option<String>,
+    deprecated: bool,
+    sunset: Option<String>,
+    kind: RouteKind,
 }
 
 impl CreateRouteRequestBuilder {
@@ -621,6 +1420,114 @@ impl CreateRouteRequestBuilder {
         self.enabled = enabled;
         self
     }
+    pub fn skip_authz(mut self, skip_authz: bool) -> Self {
+        self.skip_authz = skip_authz;
+        self
+    }
+    pub fn timeout_secs(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = Some(timeout_secs);
+        self
+    }
+    pub fn status_remap(mut self, status_remap: HashMap<u16, u16>) -> Self {
+        self.status_remap = status_remap;
+        self
+    }
+    pub fn allowed_request_content_types(mut self, types: Vec<String>) -> Self {
+        self.allowed_request_content_types = types;
+        self
+    }
+    pub fn allowed_response_content_types(mut self, types: Vec<String>) -> Self {
+        self.allowed_response_content_types = types;
+        self
+    }
+    pub fn log_sample_rate(mut self, rate: f64) -> Self {
+        self.log_sample_rate = Some(rate);
+        self
+    }
+    pub fn request_schema(mut self, schema: serde_json::Value) -> Self {
+        self.request_schema = Some(schema);
+        self
+    }
+    pub fn body_rewrite(mut self, rules: Vec<BodyRewriteRule>) -> Self {
+        self.body_rewrite = rules;
+        self
+    }
+    pub fn auth_rules(mut self, rules: Vec<AuthRule>) -> Self {
+        self.auth_rules = rules;
+        self
+    }
+
+    pub fn strip_query_params(mut self, params: Vec<String>) -> Self {
+        self.strip_query_params = params;
+        self
+    }
+
+    pub fn strip_inbound_headers(mut self, names: Vec<String>) -> Self {
+        self.strip_inbound_headers = names;
+        self
+    }
+
+    pub fn idempotency_ttl_secs(mut self, secs: u64) -> Self {
+        self.idempotency_ttl_secs = Some(secs);
+        self
+    }
+    pub fn cache_responses(mut self, cache_responses: bool) -> Self {
+        self.cache_responses = cache_responses;
+        self
+    }
+    pub fn negative_cache_ttl_secs(mut self, secs: u64) -> Self {
+        self.negative_cache_ttl_secs = Some(secs);
+        self
+    }
+    pub fn response_header_allowlist(mut self, headers: Vec<String>) -> Self {
+        self.response_header_allowlist = Some(headers);
+        self
+    }
+    pub fn max_total_duration_ms(mut self, ms: u64) -> Self {
+        self.max_total_duration_ms = Some(ms);
+        self
+    }
+    pub fn max_body_size_bytes(mut self, bytes: u64) -> Self {
+        self.max_body_size_bytes = Some(bytes);
+        self
+    }
+    pub fn max_outbound_calls(mut self, calls: u32) -> Self {
+        self.max_outbound_calls = Some(calls);
+        self
+    }
+    pub fn coerce_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.coerce_content_type = Some(content_type.into());
+        self
+    }
+    pub fn api_version(mut self, version: ApiVersion) -> Self {
+        self.api_version = Some(version);
+        self
+    }
+
+    pub fn body_transformers(mut self, names: Vec<String>) -> Self {
+        self.body_transformers = names;
+        self
+    }
+    pub fn log_redact_paths(mut self, paths: Vec<String>) -> Self {
+        self.log_redact_paths = paths;
+        self
+    }
+    pub fn transform_hook_url(mut self, url: impl Into<String>) -> Self {
+        self.transform_hook_url = Some(url.into());
+        self
+    }
+    pub fn deprecated(mut self, deprecated: bool) -> Self {
+        self.deprecated = deprecated;
+        self
+    }
+    pub fn sunset(mut self, sunset: impl Into<String>) -> Self {
+        self.sunset = Some(sunset.into());
+        self
+    }
+    pub fn kind(mut self, kind: RouteKind) -> Self {
+        self.kind = kind;
+        self
+    }
     pub fn build(self) -> CreateRouteRequest {
         CreateRouteRequest {
             upstream_id: self.upstream_id,
@@ -630,6 +1537,32 @@ impl CreateRouteRequestBuilder {
             tags: self.tags,
             priority: self.priority,
             enabled: self.enabled,
+            skip_authz: self.skip_authz,
+            timeout_secs: self.timeout_secs,
+            status_remap: self.status_remap,
+            allowed_response_content_types: self.allowed_response_content_types,
+            allowed_request_content_types: self.allowed_request_content_types,
+            log_sample_rate: self.log_sample_rate,
+            request_schema: self.request_schema,
+            body_rewrite: self.body_rewrite,
+            auth_rules: self.auth_rules,
+            strip_query_params: self.strip_query_params,
+            strip_inbound_headers: self.strip_inbound_headers,
+            idempotency_ttl_secs: self.idempotency_ttl_secs,
+            cache_responses: self.cache_responses,
+            negative_cache_ttl_secs: self.negative_cache_ttl_secs,
+            response_header_allowlist: self.response_header_allowlist,
+            max_total_duration_ms: self.max_total_duration_ms,
+            max_body_size_bytes: self.max_body_size_bytes,
+            max_outbound_calls: self.max_outbound_calls,
+            coerce_content_type: self.coerce_content_type,
+            api_version: self.api_version,
+            body_transformers: self.body_transformers,
+            log_redact_paths: self.log_redact_paths,
+            transform_hook_url: self.transform_hook_url,
+            deprecated: self.deprecated,
+            sunset: self.sunset,
+            kind: self.kind,
         }
     }
 }
@@ -644,6 +1577,32 @@ pub struct UpdateRouteRequest {
     tags: Option<Vec<String>>,
     priority: Option<i32>,
     enabled: Option<bool>,
+    skip_authz: Option<bool>,
+    timeout_secs: Option<u64>,
+    status_remap: Option<HashMap<u16, u16>>,
+    allowed_response_content_types: Option<Vec<String>>,
+    allowed_request_content_types: Option<Vec<String>>,
+    log_sample_rate: Option<f64>,
+    request_schema: Option<serde_json::Value>,
+    body_rewrite: Option<Vec<BodyRewriteRule>>,
+    auth_rules: Option<Vec<AuthRule>>,
+    strip_query_params: Option<Vec<String>>,
+    strip_inbound_headers: Option<Vec<String>>,
+    idempotency_ttl_secs: Option<u64>,
+    cache_responses: Option<bool>,
+    negative_cache_ttl_secs: Option<u64>,
+    response_header_allowlist: Option<Vec<String>>,
+    max_total_duration_ms: Option<u64>,
+    max_body_size_bytes: Option<u64>,
+    max_outbound_calls: Option<u32>,
+    coerce_content_type: Option<String>,
+    api_version: Option<ApiVersion>,
+    body_transformers: Option<Vec<String>>,
+    log_redact_paths: Option<Vec<String>>,
+    transform_hook_url: Option<String>,
+    deprecated: Option<bool>,
+    sunset: Option<String>,
+    kind: Option<RouteKind>,
 }
 
 impl UpdateRouteRequest {
@@ -670,6 +1629,88 @@ impl UpdateRouteRequest {
     pub fn enabled(&self) -> Option<bool> {
         self.enabled
     }
+    pub fn skip_authz(&self) -> Option<bool> {
+        self.skip_authz
+    }
+    pub fn timeout_secs(&self) -> Option<u64> {
+        self.timeout_secs
+    }
+    pub fn status_remap(&self) -> Option<&HashMap<u16, u16>> {
+        self.status_remap.as_ref()
+    }
+    pub fn allowed_request_content_types(&self) -> Option<&[String]> {
+        self.allowed_request_content_types.as_deref()
+    }
+    pub fn allowed_response_content_types(&self) -> Option<&[String]> {
+        self.allowed_response_content_types.as_deref()
+    }
+    pub fn log_sample_rate(&self) -> Option<f64> {
+        self.log_sample_rate
+    }
+    pub fn request_schema(&self) -> Option<&serde_json::Value> {
+        self.request_schema.as_ref()
+    }
+    pub fn body_rewrite(&self) -> Option<&[BodyRewriteRule]> {
+        self.body_rewrite.as_deref()
+    }
+    pub fn auth_rules(&self) -> Option<&[AuthRule]> {
+        self.auth_rules.as_deref()
+    }
+
+    pub fn strip_query_params(&self) -> Option<&[String]> {
+        self.strip_query_params.as_deref()
+    }
+
+    pub fn strip_inbound_headers(&self) -> Option<&[String]> {
+        self.strip_inbound_headers.as_deref()
+    }
+
+    pub fn idempotency_ttl_secs(&self) -> Option<u64> {
+        self.idempotency_ttl_secs
+    }
+    pub fn cache_responses(&self) -> Option<bool> {
+        self.cache_responses
+    }
+    pub fn negative_cache_ttl_secs(&self) -> Option<u64> {
+        self.negative_cache_ttl_secs
+    }
+    pub fn response_header_allowlist(&self) -> Option<&[String]> {
+        self.response_header_allowlist.as_deref()
+    }
+    pub fn max_total_duration_ms(&self) -> Option<u64> {
+        self.max_total_duration_ms
+    }
+    pub fn max_body_size_bytes(&self) -> Option<u64> {
+        self.max_body_size_bytes
+    }
+    pub fn max_outbound_calls(&self) -> Option<u32> {
+        self.max_outbound_calls
+    }
+    pub fn coerce_content_type(&self) -> Option<&str> {
+        self.coerce_content_type.as_deref()
+    }
+    pub fn api_version(&self) -> Option<&ApiVersion> {
+        self.api_version.as_ref()
+    }
+
+    pub fn body_transformers(&self) -> Option<&[String]> {
+        self.body_transformers.as_deref()
+    }
+    pub fn log_redact_paths(&self) -> Option<&[String]> {
+        self.log_redact_paths.as_deref()
+    }
+    pub fn transform_hook_url(&self) -> Option<&str> {
+        self.transform_hook_url.as_deref()
+    }
+    pub fn deprecated(&self) -> Option<bool> {
+        self.deprecated
+    }
+    pub fn sunset(&self) -> Option<&str> {
+        self.sunset.as_deref()
+    }
+    pub fn kind(&self) -> Option<&RouteKind> {
+        self.kind.as_ref()
+    }
 }
 
 #[derive(Default)]
@@ -680,6 +1721,32 @@ pub struct UpdateRouteRequestBuilder {
     tags: Option<Vec<String>>,
     priority: Option<i32>,
     enabled: Option<bool>,
+    skip_authz: Option<bool>,
+    timeout_secs: Option<u64>,
+    status_remap: Option<HashMap<u16, u16>>,
+    allowed_response_content_types: Option<Vec<String>>,
+    allowed_request_content_types: Option<Vec<String>>,
+    log_sample_rate: Option<f64>,
+    request_schema: Option<serde_json::Value>,
+    body_rewrite: Option<Vec<BodyRewriteRule>>,
+    auth_rules: Option<Vec<AuthRule>>,
+    strip_query_params: Option<Vec<String>>,
+    strip_inbound_headers: Option<Vec<String>>,
+    idempotency_ttl_secs: Option<u64>,
+    cache_responses: Option<bool>,
+    negative_cache_ttl_secs: Option<u64>,
+    response_header_allowlist: Option<Vec<String>>,
+    max_total_duration_ms: Option<u64>,
+    max_body_size_bytes: Option<u64>,
+    max_outbound_calls: Option<u32>,
+    coerce_content_type: Option<String>,
+    api_version: Option<ApiVersion>,
+    body_transformers: Option<Vec<String>>,
+    log_redact_paths: Option<Vec<String>>,
+    transform_hook_url: Option<String>,
+    deprecated: Option<bool>,
+    sunset: Option<String>,
+    kind: Option<RouteKind>,
 }
 
 impl UpdateRouteRequestBuilder {
@@ -707,6 +1774,114 @@ impl UpdateRouteRequestBuilder {
         self.enabled = Some(enabled);
         self
     }
+    pub fn skip_authz(mut self, skip_authz: bool) -> Self {
+        self.skip_authz = Some(skip_authz);
+        self
+    }
+    pub fn timeout_secs(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = Some(timeout_secs);
+        self
+    }
+    pub fn status_remap(mut self, status_remap: HashMap<u16, u16>) -> Self {
+        self.status_remap = Some(status_remap);
+        self
+    }
+    pub fn allowed_request_content_types(mut self, types: Vec<String>) -> Self {
+        self.allowed_request_content_types = Some(types);
+        self
+    }
+    pub fn allowed_response_content_types(mut self, types: Vec<String>) -> Self {
+        self.allowed_response_content_types = Some(types);
+        self
+    }
+    pub fn log_sample_rate(mut self, rate: f64) -> Self {
+        self.log_sample_rate = Some(rate);
+        self
+    }
+    pub fn request_schema(mut self, schema: serde_json::Value) -> Self {
+        self.request_schema = Some(schema);
+        self
+    }
+    pub fn body_rewrite(mut self, rules: Vec<BodyRewriteRule>) -> Self {
+        self.body_rewrite = Some(rules);
+        self
+    }
+    pub fn auth_rules(mut self, rules: Vec<AuthRule>) -> Self {
+        self.auth_rules = Some(rules);
+        self
+    }
+
+    pub fn strip_query_params(mut self, params: Vec<String>) -> Self {
+        self.strip_query_params = Some(params);
+        self
+    }
+
+    pub fn strip_inbound_headers(mut self, names: Vec<String>) -> Self {
+        self.strip_inbound_headers = Some(names);
+        self
+    }
+
+    pub fn idempotency_ttl_secs(mut self, secs: u64) -> Self {
+        self.idempotency_ttl_secs = Some(secs);
+        self
+    }
+    pub fn cache_responses(mut self, cache_responses: bool) -> Self {
+        self.cache_responses = Some(cache_responses);
+        self
+    }
+    pub fn negative_cache_ttl_secs(mut self, secs: u64) -> Self {
+        self.negative_cache_ttl_secs = Some(secs);
+        self
+    }
+    pub fn response_header_allowlist(mut self, headers: Vec<String>) -> Self {
+        self.response_header_allowlist = Some(headers);
+        self
+    }
+    pub fn max_total_duration_ms(mut self, ms: u64) -> Self {
+        self.max_total_duration_ms = Some(ms);
+        self
+    }
+    pub fn max_body_size_bytes(mut self, bytes: u64) -> Self {
+        self.max_body_size_bytes = Some(bytes);
+        self
+    }
+    pub fn max_outbound_calls(mut self, calls: u32) -> Self {
+        self.max_outbound_calls = Some(calls);
+        self
+    }
+    pub fn coerce_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.coerce_content_type = Some(content_type.into());
+        self
+    }
+    pub fn api_version(mut self, version: ApiVersion) -> Self {
+        self.api_version = Some(version);
+        self
+    }
+
+    pub fn body_transformers(mut self, names: Vec<String>) -> Self {
+        self.body_transformers = Some(names);
+        self
+    }
+    pub fn log_redact_paths(mut self, paths: Vec<String>) -> Self {
+        self.log_redact_paths = Some(paths);
+        self
+    }
+    pub fn transform_hook_url(mut self, url: impl Into<String>) -> Self {
+        self.transform_hook_url = Some(url.into());
+        self
+    }
+    pub fn deprecated(mut self, deprecated: bool) -> Self {
+        self.deprecated = Some(deprecated);
+        self
+    }
+    pub fn sunset(mut self, sunset: impl Into<String>) -> Self {
+        self.sunset = Some(sunset.into());
+        self
+    }
+    pub fn kind(mut self, kind: RouteKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
     pub fn build(self) -> UpdateRouteRequest {
         UpdateRouteRequest {
             match_rules: self.match_rules,
@@ -715,6 +1890,32 @@ impl UpdateRouteRequestBuilder {
             tags: self.tags,
             priority: self.priority,
             enabled: self.enabled,
+            skip_authz: self.skip_authz,
+            timeout_secs: self.timeout_secs,
+            status_remap: self.status_remap,
+            allowed_response_content_types: self.allowed_response_content_types,
+            allowed_request_content_types: self.allowed_request_content_types,
+            log_sample_rate: self.log_sample_rate,
+            request_schema: self.request_schema,
+            body_rewrite: self.body_rewrite,
+            auth_rules: self.auth_rules,
+            strip_query_params: self.strip_query_params,
+            strip_inbound_headers: self.strip_inbound_headers,
+            idempotency_ttl_secs: self.idempotency_ttl_secs,
+            cache_responses: self.cache_responses,
+            negative_cache_ttl_secs: self.negative_cache_ttl_secs,
+            response_header_allowlist: self.response_header_allowlist,
+            max_total_duration_ms: self.max_total_duration_ms,
+            max_body_size_bytes: self.max_body_size_bytes,
+            max_outbound_calls: self.max_outbound_calls,
+            coerce_content_type: self.coerce_content_type,
+            api_version: self.api_version,
+            body_transformers: self.body_transformers,
+            log_redact_paths: self.log_redact_paths,
+            transform_hook_url: self.transform_hook_url,
+            deprecated: self.deprecated,
+            sunset: self.sunset,
+            kind: self.kind,
         }
     }
 }
@@ -785,6 +1986,7 @@ mod tests {
                     path: "/v1/chat/completions".into(),
                     query_allowlist: vec![],
                     path_suffix_mode: PathSuffixMode::Append,
+                    match_body: None,
                 }),
                 grpc: None,
             },
@@ -793,6 +1995,32 @@ mod tests {
             tags: vec![],
             priority: 0,
             enabled: true,
+            skip_authz: false,
+            timeout_secs: None,
+            status_remap: HashMap::new(),
+            allowed_response_content_types: vec![],
+            allowed_request_content_types: vec![],
+            log_sample_rate: None,
+            request_schema: None,
+            body_rewrite: vec![],
+            auth_rules: vec![],
+            strip_query_params: vec![],
+            strip_inbound_headers: vec![],
+            idempotency_ttl_secs: None,
+            cache_responses: false,
+            negative_cache_ttl_secs: None,
+            response_header_allowlist: None,
+            max_total_duration_ms: None,
+            max_body_size_bytes: None,
+            max_outbound_calls: None,
+            coerce_content_type: None,
+            api_version: None,
+            body_transformers: vec![],
+            log_redact_paths: vec![],
+            transform_hook_url: None,
+            deprecated: false,
+            sunset: None,
+            kind: RouteKind::Proxy,
         };
         assert!(route.enabled);
         assert_eq!(route.priority, 0);
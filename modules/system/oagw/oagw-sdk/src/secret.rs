@@ -0,0 +1,41 @@
+//! Pluggable secret-reference resolvers for OAGW.
+//!
+//! Auth plugins name secrets by reference, e.g. `cred://openai-key`. The
+//! scheme before `://` selects the resolver: resolvers are registered in the
+//! `ClientHub` under a `ClientScope` holding their scheme, and `cred://`
+//! references (or references without a scheme) fall back to the credential
+//! store when no `cred` resolver is registered:
+//! ```ignore
+//! hub.register_scoped::<dyn SecretResolver>(
+//!     ClientScope::new("vault"),
+//!     Arc::new(VaultResolver::new(client)),
+//! );
+//! ```
+
+use async_trait::async_trait;
+use modkit_security::SecurityContext;
+
+/// Failure resolving a secret reference. A secret that does not exist is
+/// not an error; see [`SecretResolver::resolve`].
+#[derive(Debug, thiserror::Error)]
+pub enum SecretResolverError {
+    /// The reference is malformed or not allowed for this resolver.
+    #[error("invalid secret reference '{reference}': {reason}")]
+    InvalidReference { reference: String, reason: String },
+
+    /// The secret backend could not be read.
+    #[error("secret backend unavailable: {0}")]
+    Unavailable(String),
+}
+
+/// Source of secret values for one reference scheme.
+#[async_trait]
+pub trait SecretResolver: Send + Sync {
+    /// Resolve `reference`, the part after `<scheme>://`, for the caller.
+    /// Returns `None` when the secret does not exist.
+    async fn resolve(
+        &self,
+        ctx: &SecurityContext,
+        reference: &str,
+    ) -> Result<Option<String>, SecretResolverError>;
+}
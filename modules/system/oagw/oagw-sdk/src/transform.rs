@@ -0,0 +1,29 @@
+//! Pluggable body transformers for OAGW.
+//!
+//! A transformer rewrites whole request and response bodies of the content
+//! types it handles, e.g. a JSON minifier or a PII redactor. Transformers are
+//! registered in the `ClientHub` under a `ClientScope` holding their name,
+//! and routes opt in by listing names in `body_transformers`:
+//! ```ignore
+//! hub.register_scoped::<dyn BodyTransformer>(
+//!     ClientScope::new("redact-pii"),
+//!     Arc::new(PiiRedactor::default()),
+//! );
+//! ```
+
+use bytes::Bytes;
+
+/// Transformation of complete, uncompressed message bodies.
+///
+/// The gateway buffers a body (within its body size limits) before calling
+/// [`transform`](Self::transform) and sends the result with an updated
+/// `Content-Length`. Compressed bodies are never passed to a transformer.
+pub trait BodyTransformer: Send + Sync {
+    /// Media types handled, e.g. `application/json`. Matched
+    /// case-insensitively against the `Content-Type` without parameters; an
+    /// empty list matches no body.
+    fn content_types(&self) -> Vec<String>;
+
+    /// Transform a body of one of [`content_types`](Self::content_types).
+    fn transform(&self, body: Bytes) -> Bytes;
+}
@@ -16,7 +16,7 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use futures_util::{SinkExt, StreamExt};
 use modkit_security::SecurityContext;
-use oagw_sdk::api::ServiceGatewayClientV1;
+use oagw_sdk::api::{GatewayStatus, ServiceGatewayClientV1, VersionInfo};
 use oagw_sdk::body::{Body, BodyStream, BoxError};
 use oagw_sdk::codec::Json;
 use oagw_sdk::error::ServiceGatewayError;
@@ -73,6 +73,14 @@ impl ServiceGatewayClientV1 for MockGateway {
         unimplemented!()
     }
 
+    async fn create_upstreams(
+        &self,
+        _: SecurityContext,
+        _: Vec<oagw_sdk::CreateUpstreamRequest>,
+    ) -> Result<Vec<oagw_sdk::Upstream>, ServiceGatewayError> {
+        unimplemented!()
+    }
+
     async fn get_upstream(
         &self,
         _: SecurityContext,
@@ -114,6 +122,14 @@ impl ServiceGatewayClientV1 for MockGateway {
         unimplemented!()
     }
 
+    async fn create_routes(
+        &self,
+        _: SecurityContext,
+        _: Vec<oagw_sdk::CreateRouteRequest>,
+    ) -> Result<Vec<oagw_sdk::Route>, ServiceGatewayError> {
+        unimplemented!()
+    }
+
     async fn get_route(
         &self,
         _: SecurityContext,
@@ -148,6 +164,14 @@ impl ServiceGatewayClientV1 for MockGateway {
         unimplemented!()
     }
 
+    async fn validate_config(
+        &self,
+        _: SecurityContext,
+        _: oagw_sdk::ConfigSnapshot,
+    ) -> Result<Vec<oagw_sdk::ConfigProblem>, ServiceGatewayError> {
+        unimplemented!()
+    }
+
     async fn resolve_proxy_target(
         &self,
         _: SecurityContext,
@@ -170,6 +194,81 @@ impl ServiceGatewayClientV1 for MockGateway {
             .take()
             .expect("response already consumed"))
     }
+
+    async fn status(&self) -> GatewayStatus {
+        GatewayStatus {
+            live: true,
+            ready: true,
+            reasons: vec![],
+            upstream_latencies: vec![],
+        }
+    }
+
+    fn version_info(&self) -> VersionInfo {
+        VersionInfo {
+            version: "0.0.0".into(),
+            git_sha: None,
+            features: vec![],
+        }
+    }
+
+    async fn test_upstream(
+        &self,
+        _: SecurityContext,
+        _: uuid::Uuid,
+    ) -> Result<oagw_sdk::UpstreamTestResult, ServiceGatewayError> {
+        unimplemented!()
+    }
+
+    async fn describe_route(
+        &self,
+        _: SecurityContext,
+        _: uuid::Uuid,
+    ) -> Result<oagw_sdk::RouteDescription, ServiceGatewayError> {
+        unimplemented!()
+    }
+
+    async fn trace_request(
+        &self,
+        _: SecurityContext,
+        _: http::Request<Body>,
+        _: Option<http::Response<Body>>,
+    ) -> oagw_sdk::RequestTrace {
+        unimplemented!()
+    }
+
+    async fn list_runtime_states(
+        &self,
+        _: SecurityContext,
+        _: &oagw_sdk::ListQuery,
+    ) -> Result<Vec<oagw_sdk::UpstreamRuntimeState>, ServiceGatewayError> {
+        unimplemented!()
+    }
+
+    async fn reset_circuit_breaker(
+        &self,
+        _: SecurityContext,
+        _: uuid::Uuid,
+    ) -> Result<(), ServiceGatewayError> {
+        unimplemented!()
+    }
+
+    async fn reset_rate_limit(
+        &self,
+        _: SecurityContext,
+        _: uuid::Uuid,
+        _: uuid::Uuid,
+    ) -> Result<(), ServiceGatewayError> {
+        unimplemented!()
+    }
+
+    fn recent_errors(&self) -> Vec<oagw_sdk::RecentError> {
+        unimplemented!()
+    }
+
+    async fn flush_all_caches(&self) -> oagw_sdk::FlushedCaches {
+        unimplemented!()
+    }
 }
 
 // ===========================================================================
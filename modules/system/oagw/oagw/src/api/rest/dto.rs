@@ -190,6 +190,128 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+// ---------------------------------------------------------------------------
+// RetryOnBody
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RetryOnBody {
+    #[serde(rename = "match")]
+    pub matcher: BodyMatcher,
+    #[serde(default = "default_body_retries")]
+    pub max_retries: u32,
+}
+
+fn default_body_retries() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BodyMatcher {
+    JsonPointer {
+        pointer: String,
+        value: serde_json::Value,
+    },
+    Regex {
+        pattern: String,
+    },
+}
+
+// ---------------------------------------------------------------------------
+// BodyRewriteRule
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BodyRewriteRule {
+    /// Literal text, or a regular expression when `regex` is set.
+    pub find: String,
+    /// Replacement text; with `regex`, `$1`/`${name}` refer to capture groups.
+    pub replace: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub regex: bool,
+    /// Response media types the rule applies to; empty means textual types.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content_types: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// AuthRule
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AuthRule {
+    pub condition: AuthCondition,
+    /// Auth to apply; `null` sends matching requests without upstream auth.
+    #[serde(default)]
+    pub auth: Option<AuthConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuthCondition {
+    PathPrefix {
+        prefix: String,
+    },
+    Header {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        value: Option<String>,
+    },
+}
+
+// ---------------------------------------------------------------------------
+// ApiVersion
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ApiVersion {
+    pub value: String,
+    /// Header carrying the version. Default: `Api-Version`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+    /// Query parameter also carrying the version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_param: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// KeepaliveConfig
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct KeepaliveConfig {
+    /// Idle seconds before TCP keep-alive probes start; unset disables them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tcp_idle_secs: Option<u64>,
+    /// Seconds between TCP keep-alive probes. Defaults to `tcp_idle_secs`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tcp_interval_secs: Option<u64>,
+    /// Unanswered probes before the connection is dropped. Default: 3.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tcp_probe_count: Option<u32>,
+    /// Seconds between HTTP/2 PING frames (`h2c` and gRPC upstreams).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub h2_ping_interval_secs: Option<u64>,
+}
+
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_threshold: Option<u32>,
+    /// Seconds the circuit stays open before probing the upstream.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cooldown_secs: Option<u64>,
+    /// Requests admitted while half-open; all must succeed to close.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub half_open_probes: Option<u32>,
+}
+
 // ---------------------------------------------------------------------------
 // PluginsConfig
 // ---------------------------------------------------------------------------
@@ -225,6 +347,16 @@ pub enum PathSuffixMode {
     Append,
 }
 
+/// How a trailing slash on the outbound path is treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TrailingSlash {
+    #[default]
+    Preserve,
+    Strip,
+    Add,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct HttpMatch {
     pub methods: Vec<HttpMethod>,
@@ -233,6 +365,8 @@ pub struct HttpMatch {
     pub query_allowlist: Vec<String>,
     #[serde(default)]
     pub path_suffix_mode: PathSuffixMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_body: Option<BodyMatcher>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
@@ -249,6 +383,25 @@ pub struct MatchRules {
     pub grpc: Option<GrpcMatch>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RouteKind {
+    #[default]
+    Proxy,
+    Static {
+        #[serde(default = "default_static_status")]
+        status: u16,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        headers: HashMap<String, String>,
+        #[serde(default)]
+        body: String,
+    },
+}
+
+fn default_static_status() -> u16 {
+    200
+}
+
 // ---------------------------------------------------------------------------
 // Upstream request DTOs
 // ---------------------------------------------------------------------------
@@ -271,6 +424,55 @@ pub struct CreateUpstreamRequest {
     pub tags: Vec<String>,
     #[serde(default = "default_true")]
     pub enabled: bool,
+    #[serde(default)]
+    pub maintenance: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintenance_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_on_body: Option<RetryOnBody>,
+    #[serde(default)]
+    pub force_chunked: bool,
+    /// Ordered secondary endpoints tried when the primary fails.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failover: Vec<Endpoint>,
+    /// Per-upstream proxy timeout override in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Keep-alive probing of idle pooled connections.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keepalive: Option<KeepaliveConfig>,
+    /// Circuit breaker overrides; unset fields use the gateway defaults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// `User-Agent` sent upstream, overriding the gateway default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Consistent-hash endpoint selection key: `header:<name>` or `tenant`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sticky_by: Option<String>,
+    /// Methods proxied to this upstream; others get 405. Empty allows all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_methods: Vec<HttpMethod>,
+    /// `preserve` (default), `strip` or `add` a trailing slash upstream.
+    #[serde(default)]
+    pub trailing_slash: TrailingSlash,
+    /// Largest outbound header (name plus value) in bytes; bigger gets 431.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_header_size_bytes: Option<u64>,
+    /// Lowest TLS version accepted from the upstream: `1.2` or `1.3`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_min_version: Option<String>,
+    /// TLS cipher suites accepted from the upstream; empty accepts any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tls_cipher_suites: Vec<String>,
+    /// Hostname the upstream certificate is verified against, and sent as
+    /// SNI, instead of the endpoint host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_server_name: Option<String>,
+    /// Request header carrying the milliseconds left of the request's time
+    /// budget, for upstreams that limit their own work by it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_header: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, utoipa::ToSchema)]
@@ -293,6 +495,40 @@ pub struct UpdateUpstreamRequest {
     pub tags: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintenance: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintenance_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_on_body: Option<RetryOnBody>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub force_chunked: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failover: Option<Vec<Endpoint>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keepalive: Option<KeepaliveConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sticky_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_methods: Option<Vec<HttpMethod>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trailing_slash: Option<TrailingSlash>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_header_size_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_min_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_cipher_suites: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_server_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_header: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -301,6 +537,8 @@ pub struct UpdateUpstreamRequest {
 
 #[derive(Debug, Clone, Deserialize, Serialize, utoipa::ToSchema)]
 pub struct CreateRouteRequest {
+    /// Omitted (nil) for static routes.
+    #[serde(default)]
     pub upstream_id: Uuid,
     #[serde(rename = "match")]
     pub match_rules: MatchRules,
@@ -310,10 +548,98 @@ pub struct CreateRouteRequest {
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Among routes matching a request, the highest priority wins; the
+    /// longest path prefix breaks ties.
     #[serde(default)]
     pub priority: i32,
     #[serde(default = "default_true")]
     pub enabled: bool,
+    /// Bypass the AuthZ check for this route (e.g. public health checks).
+    #[serde(default)]
+    pub skip_authz: bool,
+    /// Per-route proxy timeout override in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Upstream status codes rewritten before returning (e.g. `{"418": 429}`).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub status_remap: HashMap<u16, u16>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_response_content_types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_request_content_types: Vec<String>,
+    /// Fraction (`0.0..=1.0`) of matching requests logged in detail.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_sample_rate: Option<f64>,
+    /// JSON Schema that buffered JSON request bodies must satisfy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_schema: Option<serde_json::Value>,
+    /// Find/replace rules applied, in order, to buffered response bodies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub body_rewrite: Vec<BodyRewriteRule>,
+    /// Auth chosen per request, in order, overriding the upstream's `auth`
+    /// (e.g. stronger auth for an `/admin` prefix).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auth_rules: Vec<AuthRule>,
+    /// Query parameters removed from the outbound URL before forwarding.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub strip_query_params: Vec<String>,
+    /// Request headers removed before auth plugins run, in addition to the
+    /// gateway-wide `strip_inbound_headers`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub strip_inbound_headers: Vec<String>,
+    /// Seconds a response is replayed for requests repeating its
+    /// `Idempotency-Key`; unset disables deduplication.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_ttl_secs: Option<u64>,
+    /// Serve `GET` responses with explicit freshness from the response
+    /// cache. Off by default.
+    #[serde(default)]
+    pub cache_responses: bool,
+    /// Seconds 404/410 responses to `GET` are cached for; unset disables
+    /// negative caching.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub negative_cache_ttl_secs: Option<u64>,
+    /// Forward only these upstream response headers (plus framing headers).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_header_allowlist: Option<Vec<String>>,
+    /// Hard deadline in milliseconds for the whole exchange; exceeding it
+    /// fails with 504 or cuts off a streaming response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_duration_ms: Option<u64>,
+    /// Request body limit in bytes for this route; it can only tighten
+    /// the gateway-wide limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_body_size_bytes: Option<u64>,
+    /// Most upstream calls one request may make, counting connect retries,
+    /// failover and body retries; exceeding it fails with 502.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_outbound_calls: Option<u32>,
+    /// Content type forced onto outbound requests that carry a body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coerce_content_type: Option<String>,
+    /// API version header (and optional query parameter) set upstream.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<ApiVersion>,
+    /// Names of registered body transformers run, in order, over request
+    /// and response bodies of matching content types.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub body_transformers: Vec<String>,
+    /// JSON pointers of fields masked in sampled body logs (`*` matches
+    /// any array element or key). Bodies are only logged when set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub log_redact_paths: Vec<String>,
+    /// Service the request is POSTed to before forwarding; its answer
+    /// replaces the outbound headers and/or body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform_hook_url: Option<String>,
+    /// Send `Deprecation: true` on responses from this route.
+    #[serde(default)]
+    pub deprecated: bool,
+    /// HTTP-date sent as the `Sunset` response header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<String>,
+    #[serde(default)]
+    pub kind: RouteKind,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, utoipa::ToSchema)]
@@ -330,6 +656,58 @@ pub struct UpdateRouteRequest {
     pub priority: Option<i32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_authz: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_remap: Option<HashMap<u16, u16>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_response_content_types: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_request_content_types: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_sample_rate: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_schema: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_rewrite: Option<Vec<BodyRewriteRule>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_rules: Option<Vec<AuthRule>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strip_query_params: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strip_inbound_headers: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_ttl_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_responses: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub negative_cache_ttl_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_header_allowlist: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_duration_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_body_size_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_outbound_calls: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coerce_content_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<ApiVersion>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_transformers: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_redact_paths: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform_hook_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<RouteKind>,
 }
 
 // ---------------------------------------------------------------------------
@@ -354,6 +732,40 @@ pub struct UpstreamResponse {
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub maintenance: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintenance_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_on_body: Option<RetryOnBody>,
+    #[serde(default)]
+    pub force_chunked: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failover: Vec<Endpoint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keepalive: Option<KeepaliveConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sticky_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_methods: Vec<HttpMethod>,
+    #[serde(default)]
+    pub trailing_slash: TrailingSlash,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_header_size_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_min_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tls_cipher_suites: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_server_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_header: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
@@ -371,6 +783,138 @@ pub struct RouteResponse {
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
+    #[serde(default)]
+    pub skip_authz: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub status_remap: HashMap<u16, u16>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_response_content_types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_request_content_types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_sample_rate: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_schema: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub body_rewrite: Vec<BodyRewriteRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auth_rules: Vec<AuthRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub strip_query_params: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub strip_inbound_headers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_ttl_secs: Option<u64>,
+    /// Serve `GET` responses with explicit freshness from the response
+    /// cache. Off by default.
+    #[serde(default)]
+    pub cache_responses: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub negative_cache_ttl_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_header_allowlist: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_duration_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_body_size_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_outbound_calls: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coerce_content_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<ApiVersion>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub body_transformers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub log_redact_paths: Vec<String>,
+    /// Service the request is POSTed to before forwarding; its answer
+    /// replaces the outbound headers and/or body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform_hook_url: Option<String>,
+    /// Send `Deprecation: true` on responses from this route.
+    #[serde(default)]
+    pub deprecated: bool,
+    /// HTTP-date sent as the `Sunset` response header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<String>,
+    #[serde(default)]
+    pub kind: RouteKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct LivenessResponse {
+    pub live: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ReadinessResponse {
+    pub ready: bool,
+    /// Reasons the gateway is not ready (empty when ready).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reasons: Vec<String>,
+    /// Rolling response time percentiles per upstream that served traffic.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub upstream_latencies: Vec<UpstreamLatencyDto>,
+}
+
+/// Response time percentiles of one upstream over its most recent
+/// responses, in microseconds to response headers.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UpstreamLatencyDto {
+    pub upstream_id: Uuid,
+    pub samples: usize,
+    pub p50_us: u64,
+    pub p95_us: u64,
+    pub p99_us: u64,
+}
+
+impl From<crate::domain::model::UpstreamLatency> for UpstreamLatencyDto {
+    fn from(l: crate::domain::model::UpstreamLatency) -> Self {
+        Self {
+            upstream_id: l.upstream_id,
+            samples: l.samples,
+            p50_us: l.p50_us,
+            p95_us: l.p95_us,
+            p99_us: l.p99_us,
+        }
+    }
+}
+
+/// Result of `POST /oagw/v1/upstreams/{id}/test`.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UpstreamTestResponse {
+    /// The auth plugin resolved its credentials (`true` without auth).
+    pub credential_resolved: bool,
+    /// The auth plugin prepared the probe request (`true` without auth).
+    pub auth_applied: bool,
+    /// HTTP status the upstream answered the probe with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_status: Option<u16>,
+    /// Round-trip time of the probe request in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Why the test stopped short, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A tenant's rate limit on an upstream, replacing the upstream's own limit
+/// for that tenant's requests.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TenantRateLimitResponse {
+    pub upstream_id: String,
+    pub tenant_id: Uuid,
+    pub rate_limit: RateLimitConfig,
+}
+
+/// Auth applied to a tenant's requests through upstreams that configure
+/// none of their own.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TenantAuthDefaultResponse {
+    pub tenant_id: Uuid,
+    pub auth: AuthConfig,
 }
 
 // ---------------------------------------------------------------------------
@@ -541,6 +1085,82 @@ impl From<RateLimitConfig> for domain::RateLimitConfig {
     }
 }
 
+impl From<RetryOnBody> for domain::RetryOnBody {
+    fn from(v: RetryOnBody) -> Self {
+        Self {
+            matcher: v.matcher.into(),
+            max_retries: v.max_retries,
+        }
+    }
+}
+
+impl From<CircuitBreakerConfig> for domain::CircuitBreakerConfig {
+    fn from(v: CircuitBreakerConfig) -> Self {
+        Self {
+            failure_threshold: v.failure_threshold,
+            cooldown_secs: v.cooldown_secs,
+            half_open_probes: v.half_open_probes,
+        }
+    }
+}
+
+impl From<ApiVersion> for domain::ApiVersion {
+    fn from(v: ApiVersion) -> Self {
+        Self {
+            value: v.value,
+            header: v.header,
+            query_param: v.query_param,
+        }
+    }
+}
+
+impl From<AuthRule> for domain::AuthRule {
+    fn from(v: AuthRule) -> Self {
+        Self {
+            condition: match v.condition {
+                AuthCondition::PathPrefix { prefix } => {
+                    domain::AuthCondition::PathPrefix { prefix }
+                }
+                AuthCondition::Header { name, value } => {
+                    domain::AuthCondition::Header { name, value }
+                }
+            },
+            auth: v.auth.map(Into::into),
+        }
+    }
+}
+
+impl From<BodyRewriteRule> for domain::BodyRewriteRule {
+    fn from(v: BodyRewriteRule) -> Self {
+        Self {
+            find: v.find,
+            replace: v.replace,
+            regex: v.regex,
+            content_types: v.content_types,
+        }
+    }
+}
+
+impl From<KeepaliveConfig> for domain::KeepaliveConfig {
+    fn from(v: KeepaliveConfig) -> Self {
+        Self {
+            tcp_idle_secs: v.tcp_idle_secs,
+            tcp_interval_secs: v.tcp_interval_secs,
+            tcp_probe_count: v.tcp_probe_count,
+            h2_ping_interval_secs: v.h2_ping_interval_secs,
+        }
+    }
+}
+
+impl From<BodyMatcher> for domain::BodyMatcher {
+    fn from(v: BodyMatcher) -> Self {
+        match v {
+            BodyMatcher::JsonPointer { pointer, value } => Self::JsonPointer { pointer, value },
+            BodyMatcher::Regex { pattern } => Self::Regex { pattern },
+        }
+    }
+}
+
 impl From<PluginsConfig> for domain::PluginsConfig {
     fn from(v: PluginsConfig) -> Self {
         Self {
@@ -571,6 +1191,16 @@ impl From<PathSuffixMode> for domain::PathSuffixMode {
     }
 }
 
+impl From<TrailingSlash> for domain::TrailingSlash {
+    fn from(v: TrailingSlash) -> Self {
+        match v {
+            TrailingSlash::Preserve => Self::Preserve,
+            TrailingSlash::Strip => Self::Strip,
+            TrailingSlash::Add => Self::Add,
+        }
+    }
+}
+
 impl From<HttpMatch> for domain::HttpMatch {
     fn from(v: HttpMatch) -> Self {
         Self {
@@ -578,6 +1208,7 @@ impl From<HttpMatch> for domain::HttpMatch {
             path: v.path,
             query_allowlist: v.query_allowlist,
             path_suffix_mode: v.path_suffix_mode.into(),
+            match_body: v.match_body.map(Into::into),
         }
     }
 }
@@ -600,6 +1231,23 @@ impl From<MatchRules> for domain::MatchRules {
     }
 }
 
+impl From<RouteKind> for domain::RouteKind {
+    fn from(v: RouteKind) -> Self {
+        match v {
+            RouteKind::Proxy => Self::Proxy,
+            RouteKind::Static {
+                status,
+                headers,
+                body,
+            } => Self::Static {
+                status,
+                headers,
+                body,
+            },
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // From conversions: domain value types → REST value types
 // ---------------------------------------------------------------------------
@@ -768,6 +1416,84 @@ impl From<domain::RateLimitConfig> for RateLimitConfig {
     }
 }
 
+impl From<domain::RetryOnBody> for RetryOnBody {
+    fn from(v: domain::RetryOnBody) -> Self {
+        Self {
+            matcher: v.matcher.into(),
+            max_retries: v.max_retries,
+        }
+    }
+}
+
+impl From<domain::CircuitBreakerConfig> for CircuitBreakerConfig {
+    fn from(v: domain::CircuitBreakerConfig) -> Self {
+        Self {
+            failure_threshold: v.failure_threshold,
+            cooldown_secs: v.cooldown_secs,
+            half_open_probes: v.half_open_probes,
+        }
+    }
+}
+
+impl From<domain::ApiVersion> for ApiVersion {
+    fn from(v: domain::ApiVersion) -> Self {
+        Self {
+            value: v.value,
+            header: v.header,
+            query_param: v.query_param,
+        }
+    }
+}
+
+impl From<domain::AuthRule> for AuthRule {
+    fn from(v: domain::AuthRule) -> Self {
+        Self {
+            condition: match v.condition {
+                domain::AuthCondition::PathPrefix { prefix } => {
+                    AuthCondition::PathPrefix { prefix }
+                }
+                domain::AuthCondition::Header { name, value } => {
+                    AuthCondition::Header { name, value }
+                }
+            },
+            auth: v.auth.map(Into::into),
+        }
+    }
+}
+
+impl From<domain::BodyRewriteRule> for BodyRewriteRule {
+    fn from(v: domain::BodyRewriteRule) -> Self {
+        Self {
+            find: v.find,
+            replace: v.replace,
+            regex: v.regex,
+            content_types: v.content_types,
+        }
+    }
+}
+
+impl From<domain::KeepaliveConfig> for KeepaliveConfig {
+    fn from(v: domain::KeepaliveConfig) -> Self {
+        Self {
+            tcp_idle_secs: v.tcp_idle_secs,
+            tcp_interval_secs: v.tcp_interval_secs,
+            tcp_probe_count: v.tcp_probe_count,
+            h2_ping_interval_secs: v.h2_ping_interval_secs,
+        }
+    }
+}
+
+impl From<domain::BodyMatcher> for BodyMatcher {
+    fn from(v: domain::BodyMatcher) -> Self {
+        match v {
+            domain::BodyMatcher::JsonPointer { pointer, value } => {
+                Self::JsonPointer { pointer, value }
+            }
+            domain::BodyMatcher::Regex { pattern } => Self::Regex { pattern },
+        }
+    }
+}
+
 impl From<domain::PluginsConfig> for PluginsConfig {
     fn from(v: domain::PluginsConfig) -> Self {
         Self {
@@ -798,6 +1524,16 @@ impl From<domain::PathSuffixMode> for PathSuffixMode {
     }
 }
 
+impl From<domain::TrailingSlash> for TrailingSlash {
+    fn from(v: domain::TrailingSlash) -> Self {
+        match v {
+            domain::TrailingSlash::Preserve => Self::Preserve,
+            domain::TrailingSlash::Strip => Self::Strip,
+            domain::TrailingSlash::Add => Self::Add,
+        }
+    }
+}
+
 impl From<domain::HttpMatch> for HttpMatch {
     fn from(v: domain::HttpMatch) -> Self {
         Self {
@@ -805,6 +1541,7 @@ impl From<domain::HttpMatch> for HttpMatch {
             path: v.path,
             query_allowlist: v.query_allowlist,
             path_suffix_mode: v.path_suffix_mode.into(),
+            match_body: v.match_body.map(Into::into),
         }
     }
 }
@@ -827,6 +1564,23 @@ impl From<domain::MatchRules> for MatchRules {
     }
 }
 
+impl From<domain::RouteKind> for RouteKind {
+    fn from(v: domain::RouteKind) -> Self {
+        match v {
+            domain::RouteKind::Proxy => Self::Proxy,
+            domain::RouteKind::Static {
+                status,
+                headers,
+                body,
+            } => Self::Static {
+                status,
+                headers,
+                body,
+            },
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // From conversions: REST request DTOs → domain request types
 // ---------------------------------------------------------------------------
@@ -843,6 +1597,23 @@ impl From<CreateUpstreamRequest> for domain::CreateUpstreamRequest {
             rate_limit: r.rate_limit.map(Into::into),
             tags: r.tags,
             enabled: r.enabled,
+            maintenance: r.maintenance,
+            maintenance_message: r.maintenance_message,
+            retry_on_body: r.retry_on_body.map(Into::into),
+            force_chunked: r.force_chunked,
+            failover: r.failover.into_iter().map(Into::into).collect(),
+            timeout_secs: r.timeout_secs,
+            keepalive: r.keepalive.map(Into::into),
+            circuit_breaker: r.circuit_breaker.map(Into::into),
+            user_agent: r.user_agent,
+            sticky_by: r.sticky_by,
+            allowed_methods: r.allowed_methods.into_iter().map(Into::into).collect(),
+            trailing_slash: r.trailing_slash.into(),
+            max_header_size_bytes: r.max_header_size_bytes,
+            tls_min_version: r.tls_min_version,
+            tls_cipher_suites: r.tls_cipher_suites,
+            tls_server_name: r.tls_server_name,
+            budget_header: r.budget_header,
         }
     }
 }
@@ -859,6 +1630,27 @@ impl From<UpdateUpstreamRequest> for domain::UpdateUpstreamRequest {
             rate_limit: r.rate_limit.map(Into::into),
             tags: r.tags,
             enabled: r.enabled,
+            maintenance: r.maintenance,
+            maintenance_message: r.maintenance_message,
+            retry_on_body: r.retry_on_body.map(Into::into),
+            force_chunked: r.force_chunked,
+            failover: r
+                .failover
+                .map(|eps| eps.into_iter().map(Into::into).collect()),
+            timeout_secs: r.timeout_secs,
+            keepalive: r.keepalive.map(Into::into),
+            circuit_breaker: r.circuit_breaker.map(Into::into),
+            user_agent: r.user_agent,
+            sticky_by: r.sticky_by,
+            allowed_methods: r
+                .allowed_methods
+                .map(|ms| ms.into_iter().map(Into::into).collect()),
+            trailing_slash: r.trailing_slash.map(Into::into),
+            max_header_size_bytes: r.max_header_size_bytes,
+            tls_min_version: r.tls_min_version,
+            tls_cipher_suites: r.tls_cipher_suites,
+            tls_server_name: r.tls_server_name,
+            budget_header: r.budget_header,
         }
     }
 }
@@ -873,6 +1665,32 @@ impl From<CreateRouteRequest> for domain::CreateRouteRequest {
             tags: r.tags,
             priority: r.priority,
             enabled: r.enabled,
+            skip_authz: r.skip_authz,
+            timeout_secs: r.timeout_secs,
+            status_remap: r.status_remap,
+            allowed_response_content_types: r.allowed_response_content_types,
+            allowed_request_content_types: r.allowed_request_content_types,
+            log_sample_rate: r.log_sample_rate,
+            request_schema: r.request_schema,
+            body_rewrite: r.body_rewrite.into_iter().map(Into::into).collect(),
+            auth_rules: r.auth_rules.into_iter().map(Into::into).collect(),
+            strip_query_params: r.strip_query_params,
+            strip_inbound_headers: r.strip_inbound_headers,
+            idempotency_ttl_secs: r.idempotency_ttl_secs,
+            cache_responses: r.cache_responses,
+            negative_cache_ttl_secs: r.negative_cache_ttl_secs,
+            response_header_allowlist: r.response_header_allowlist,
+            max_total_duration_ms: r.max_total_duration_ms,
+            max_body_size_bytes: r.max_body_size_bytes,
+            max_outbound_calls: r.max_outbound_calls,
+            coerce_content_type: r.coerce_content_type,
+            api_version: r.api_version.map(Into::into),
+            body_transformers: r.body_transformers,
+            log_redact_paths: r.log_redact_paths,
+            transform_hook_url: r.transform_hook_url,
+            deprecated: r.deprecated,
+            sunset: r.sunset,
+            kind: r.kind.into(),
         }
     }
 }
@@ -886,6 +1704,36 @@ impl From<UpdateRouteRequest> for domain::UpdateRouteRequest {
             tags: r.tags,
             priority: r.priority,
             enabled: r.enabled,
+            skip_authz: r.skip_authz,
+            timeout_secs: r.timeout_secs,
+            status_remap: r.status_remap,
+            allowed_response_content_types: r.allowed_response_content_types,
+            allowed_request_content_types: r.allowed_request_content_types,
+            log_sample_rate: r.log_sample_rate,
+            request_schema: r.request_schema,
+            body_rewrite: r
+                .body_rewrite
+                .map(|rules| rules.into_iter().map(Into::into).collect()),
+            auth_rules: r
+                .auth_rules
+                .map(|rules| rules.into_iter().map(Into::into).collect()),
+            strip_query_params: r.strip_query_params,
+            strip_inbound_headers: r.strip_inbound_headers,
+            idempotency_ttl_secs: r.idempotency_ttl_secs,
+            cache_responses: r.cache_responses,
+            negative_cache_ttl_secs: r.negative_cache_ttl_secs,
+            response_header_allowlist: r.response_header_allowlist,
+            max_total_duration_ms: r.max_total_duration_ms,
+            max_body_size_bytes: r.max_body_size_bytes,
+            max_outbound_calls: r.max_outbound_calls,
+            coerce_content_type: r.coerce_content_type,
+            api_version: r.api_version.map(Into::into),
+            body_transformers: r.body_transformers,
+            log_redact_paths: r.log_redact_paths,
+            transform_hook_url: r.transform_hook_url,
+            deprecated: r.deprecated,
+            sunset: r.sunset,
+            kind: r.kind.map(Into::into),
         }
     }
 }
@@ -898,9 +1746,16 @@ impl modkit::api::api_dto::RequestApiDto for CreateUpstreamRequest {}
 impl modkit::api::api_dto::RequestApiDto for UpdateUpstreamRequest {}
 impl modkit::api::api_dto::RequestApiDto for CreateRouteRequest {}
 impl modkit::api::api_dto::RequestApiDto for UpdateRouteRequest {}
+impl modkit::api::api_dto::RequestApiDto for RateLimitConfig {}
+impl modkit::api::api_dto::RequestApiDto for AuthConfig {}
 
 impl modkit::api::api_dto::ResponseApiDto for UpstreamResponse {}
 impl modkit::api::api_dto::ResponseApiDto for RouteResponse {}
+impl modkit::api::api_dto::ResponseApiDto for LivenessResponse {}
+impl modkit::api::api_dto::ResponseApiDto for ReadinessResponse {}
+impl modkit::api::api_dto::ResponseApiDto for UpstreamTestResponse {}
+impl modkit::api::api_dto::ResponseApiDto for TenantRateLimitResponse {}
+impl modkit::api::api_dto::ResponseApiDto for TenantAuthDefaultResponse {}
 
 // ---------------------------------------------------------------------------
 // Helpers
@@ -1,7 +1,8 @@
 use axum::response::{IntoResponse, Response};
-use http::{HeaderValue, StatusCode};
-use modkit::api::problem::Problem;
+use http::{HeaderName, HeaderValue, StatusCode};
+use modkit::api::problem::{Problem, ValidationViolation};
 
+use crate::config::ErrorBodyTemplate;
 use crate::domain::error::DomainError;
 use oagw_sdk::api::ErrorSource;
 
@@ -21,6 +22,13 @@ pub(crate) const ERR_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.resource
 pub(crate) const ERR_ROUTE_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.route.not_found.v1";
 pub(crate) const ERR_PAYLOAD_TOO_LARGE: &str =
     "gts.x.core.errors.err.v1~x.oagw.payload.too_large.v1";
+pub(crate) const ERR_UNSUPPORTED_MEDIA_TYPE: &str =
+    "gts.x.core.errors.err.v1~x.oagw.payload.unsupported_media_type.v1";
+pub(crate) const ERR_URI_TOO_LONG: &str = "gts.x.core.errors.err.v1~x.oagw.request.uri_too_long.v1";
+pub(crate) const ERR_HEADER_TOO_LARGE: &str =
+    "gts.x.core.errors.err.v1~x.oagw.request.header_too_large.v1";
+pub(crate) const ERR_METHOD_NOT_ALLOWED: &str =
+    "gts.x.core.errors.err.v1~x.oagw.request.method_not_allowed.v1";
 pub(crate) const ERR_RATE_LIMIT_EXCEEDED: &str =
     "gts.x.core.errors.err.v1~x.oagw.rate_limit.exceeded.v1";
 pub(crate) const ERR_SECRET_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.secret.not_found.v1";
@@ -28,10 +36,23 @@ pub(crate) const ERR_DOWNSTREAM: &str = "gts.x.core.errors.err.v1~x.oagw.downstr
 pub(crate) const ERR_PROTOCOL: &str = "gts.x.core.errors.err.v1~x.oagw.protocol.error.v1";
 pub(crate) const ERR_UPSTREAM_DISABLED: &str =
     "gts.x.core.errors.err.v1~x.oagw.routing.upstream_disabled.v1";
+pub(crate) const ERR_UPSTREAM_MAINTENANCE: &str =
+    "gts.x.core.errors.err.v1~x.oagw.routing.upstream_maintenance.v1";
+pub(crate) const ERR_CIRCUIT_BREAKER_OPEN: &str =
+    "gts.x.core.errors.err.v1~x.oagw.circuit_breaker.open.v1";
+pub(crate) const ERR_NO_HEALTHY_TARGET: &str =
+    "gts.x.core.errors.err.v1~x.oagw.routing.no_healthy_target.v1";
+pub(crate) const ERR_GATEWAY_OVERLOADED: &str =
+    "gts.x.core.errors.err.v1~x.oagw.gateway.overloaded.v1";
+pub(crate) const ERR_PLUGIN_DISABLED: &str = "gts.x.core.errors.err.v1~x.oagw.plugin.disabled.v1";
+pub(crate) const ERR_AUTHZ_UNAVAILABLE: &str =
+    "gts.x.core.errors.err.v1~x.oagw.authz.unavailable.v1";
 pub(crate) const ERR_CONNECTION_TIMEOUT: &str =
     "gts.x.core.errors.err.v1~x.oagw.timeout.connection.v1";
 pub(crate) const ERR_REQUEST_TIMEOUT: &str = "gts.x.core.errors.err.v1~x.oagw.timeout.request.v1";
+pub(crate) const ERR_CLIENT_TIMEOUT: &str = "gts.x.core.errors.err.v1~x.oagw.timeout.client.v1";
 pub(crate) const ERR_FORBIDDEN: &str = "gts.x.core.errors.err.v1~x.oagw.authz.forbidden.v1";
+pub(crate) const ERR_QUOTA_EXCEEDED: &str = "gts.x.core.errors.err.v1~x.oagw.quota.exceeded.v1";
 
 // ---------------------------------------------------------------------------
 // DomainError → Problem helpers
@@ -39,7 +60,9 @@ pub(crate) const ERR_FORBIDDEN: &str = "gts.x.core.errors.err.v1~x.oagw.authz.fo
 
 fn gts_type(err: &DomainError) -> &str {
     match err {
-        DomainError::Validation { .. } | DomainError::Conflict { .. } => ERR_VALIDATION,
+        DomainError::Validation { .. }
+        | DomainError::Conflict { .. }
+        | DomainError::SchemaViolation { .. } => ERR_VALIDATION,
         DomainError::MissingTargetHost { .. } => ERR_MISSING_TARGET_HOST,
         DomainError::InvalidTargetHost { .. } => ERR_INVALID_TARGET_HOST,
         DomainError::UnknownTargetHost { .. } => ERR_UNKNOWN_TARGET_HOST,
@@ -49,20 +72,33 @@ fn gts_type(err: &DomainError) -> &str {
         } => ERR_ROUTE_NOT_FOUND,
         DomainError::NotFound { .. } => ERR_NOT_FOUND,
         DomainError::PayloadTooLarge { .. } => ERR_PAYLOAD_TOO_LARGE,
+        DomainError::UnsupportedMediaType { .. } => ERR_UNSUPPORTED_MEDIA_TYPE,
+        DomainError::UriTooLong { .. } => ERR_URI_TOO_LONG,
+        DomainError::HeaderTooLarge { .. } => ERR_HEADER_TOO_LARGE,
+        DomainError::MethodNotAllowed { .. } => ERR_METHOD_NOT_ALLOWED,
         DomainError::RateLimitExceeded { .. } => ERR_RATE_LIMIT_EXCEEDED,
         DomainError::SecretNotFound { .. } => ERR_SECRET_NOT_FOUND,
         DomainError::DownstreamError { .. } | DomainError::Internal { .. } => ERR_DOWNSTREAM,
         DomainError::ProtocolError { .. } => ERR_PROTOCOL,
         DomainError::UpstreamDisabled { .. } => ERR_UPSTREAM_DISABLED,
+        DomainError::UpstreamMaintenance { .. } => ERR_UPSTREAM_MAINTENANCE,
+        DomainError::CircuitBreakerOpen { .. } => ERR_CIRCUIT_BREAKER_OPEN,
+        DomainError::NoHealthyTarget { .. } => ERR_NO_HEALTHY_TARGET,
+        DomainError::GatewayOverloaded { .. } => ERR_GATEWAY_OVERLOADED,
+        DomainError::PluginDisabled { .. } => ERR_PLUGIN_DISABLED,
+        DomainError::AuthzUnavailable { .. } => ERR_AUTHZ_UNAVAILABLE,
         DomainError::ConnectionTimeout { .. } => ERR_CONNECTION_TIMEOUT,
         DomainError::RequestTimeout { .. } => ERR_REQUEST_TIMEOUT,
+        DomainError::ClientTimeout { .. } => ERR_CLIENT_TIMEOUT,
         DomainError::Forbidden { .. } => ERR_FORBIDDEN,
+        DomainError::QuotaExceeded { .. } => ERR_QUOTA_EXCEEDED,
     }
 }
 
 fn http_status_code(err: &DomainError) -> StatusCode {
     match err {
         DomainError::Validation { .. }
+        | DomainError::SchemaViolation { .. }
         | DomainError::MissingTargetHost { .. }
         | DomainError::InvalidTargetHost { .. }
         | DomainError::UnknownTargetHost { .. } => StatusCode::BAD_REQUEST,
@@ -70,6 +106,10 @@ fn http_status_code(err: &DomainError) -> StatusCode {
         DomainError::AuthenticationFailed { .. } => StatusCode::UNAUTHORIZED,
         DomainError::NotFound { .. } => StatusCode::NOT_FOUND,
         DomainError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+        DomainError::UnsupportedMediaType { .. } => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        DomainError::UriTooLong { .. } => StatusCode::URI_TOO_LONG,
+        DomainError::HeaderTooLarge { .. } => StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+        DomainError::MethodNotAllowed { .. } => StatusCode::METHOD_NOT_ALLOWED,
         DomainError::RateLimitExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
         DomainError::SecretNotFound { .. } | DomainError::Internal { .. } => {
             StatusCode::INTERNAL_SERVER_ERROR
@@ -77,17 +117,24 @@ fn http_status_code(err: &DomainError) -> StatusCode {
         DomainError::DownstreamError { .. } | DomainError::ProtocolError { .. } => {
             StatusCode::BAD_GATEWAY
         }
-        DomainError::UpstreamDisabled { .. } => StatusCode::SERVICE_UNAVAILABLE,
+        DomainError::UpstreamDisabled { .. }
+        | DomainError::UpstreamMaintenance { .. }
+        | DomainError::CircuitBreakerOpen { .. }
+        | DomainError::NoHealthyTarget { .. }
+        | DomainError::GatewayOverloaded { .. }
+        | DomainError::PluginDisabled { .. }
+        | DomainError::AuthzUnavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
         DomainError::ConnectionTimeout { .. } | DomainError::RequestTimeout { .. } => {
             StatusCode::GATEWAY_TIMEOUT
         }
-        DomainError::Forbidden { .. } => StatusCode::FORBIDDEN,
+        DomainError::ClientTimeout { .. } => StatusCode::REQUEST_TIMEOUT,
+        DomainError::Forbidden { .. } | DomainError::QuotaExceeded { .. } => StatusCode::FORBIDDEN,
     }
 }
 
 fn error_title(err: &DomainError) -> &str {
     match err {
-        DomainError::Validation { .. } => "Validation Error",
+        DomainError::Validation { .. } | DomainError::SchemaViolation { .. } => "Validation Error",
         DomainError::Conflict { .. } => "Conflict",
         DomainError::MissingTargetHost { .. } => "Missing Target Host",
         DomainError::InvalidTargetHost { .. } => "Invalid Target Host",
@@ -95,36 +142,61 @@ fn error_title(err: &DomainError) -> &str {
         DomainError::AuthenticationFailed { .. } => "Authentication Failed",
         DomainError::NotFound { .. } => "Not Found",
         DomainError::PayloadTooLarge { .. } => "Payload Too Large",
+        DomainError::UnsupportedMediaType { .. } => "Unsupported Media Type",
+        DomainError::UriTooLong { .. } => "URI Too Long",
+        DomainError::HeaderTooLarge { .. } => "Request Header Fields Too Large",
+        DomainError::MethodNotAllowed { .. } => "Method Not Allowed",
         DomainError::RateLimitExceeded { .. } => "Rate Limit Exceeded",
         DomainError::SecretNotFound { .. } => "Secret Not Found",
         DomainError::DownstreamError { .. } | DomainError::Internal { .. } => "Downstream Error",
         DomainError::ProtocolError { .. } => "Protocol Error",
         DomainError::UpstreamDisabled { .. } => "Upstream Disabled",
+        DomainError::UpstreamMaintenance { .. } => "Upstream Maintenance",
+        DomainError::CircuitBreakerOpen { .. } => "Circuit Breaker Open",
+        DomainError::NoHealthyTarget { .. } => "No Healthy Upstream Target",
+        DomainError::GatewayOverloaded { .. } => "Gateway Overloaded",
+        DomainError::PluginDisabled { .. } => "Auth Plugin Disabled",
+        DomainError::AuthzUnavailable { .. } => "Authorization Unavailable",
         DomainError::ConnectionTimeout { .. } => "Connection Timeout",
         DomainError::RequestTimeout { .. } => "Request Timeout",
+        DomainError::ClientTimeout { .. } => "Client Timeout",
         DomainError::Forbidden { .. } => "Forbidden",
+        DomainError::QuotaExceeded { .. } => "Quota Exceeded",
     }
 }
 
 fn error_instance(err: &DomainError) -> &str {
     match err {
         DomainError::Validation { instance, .. }
+        | DomainError::SchemaViolation { instance, .. }
         | DomainError::MissingTargetHost { instance, .. }
         | DomainError::InvalidTargetHost { instance, .. }
         | DomainError::UnknownTargetHost { instance, .. }
         | DomainError::AuthenticationFailed { instance, .. }
         | DomainError::PayloadTooLarge { instance, .. }
+        | DomainError::UnsupportedMediaType { instance, .. }
+        | DomainError::UriTooLong { instance, .. }
+        | DomainError::HeaderTooLarge { instance, .. }
+        | DomainError::MethodNotAllowed { instance, .. }
         | DomainError::RateLimitExceeded { instance, .. }
+        | DomainError::UpstreamMaintenance { instance, .. }
+        | DomainError::CircuitBreakerOpen { instance, .. }
+        | DomainError::NoHealthyTarget { instance, .. }
+        | DomainError::GatewayOverloaded { instance, .. }
+        | DomainError::PluginDisabled { instance, .. }
+        | DomainError::AuthzUnavailable { instance, .. }
         | DomainError::SecretNotFound { instance, .. }
         | DomainError::DownstreamError { instance, .. }
         | DomainError::ProtocolError { instance, .. }
         | DomainError::ConnectionTimeout { instance, .. }
-        | DomainError::RequestTimeout { instance, .. } => instance,
+        | DomainError::RequestTimeout { instance, .. }
+        | DomainError::ClientTimeout { instance, .. } => instance,
         DomainError::NotFound { .. }
         | DomainError::Conflict { .. }
         | DomainError::UpstreamDisabled { .. }
         | DomainError::Internal { .. }
-        | DomainError::Forbidden { .. } => "",
+        | DomainError::Forbidden { .. }
+        | DomainError::QuotaExceeded { .. } => "",
     }
 }
 
@@ -140,9 +212,22 @@ impl From<DomainError> for Problem {
         let t = error_title(&err).to_string();
         let detail = err.to_string();
 
-        Problem::new(status, t, detail)
+        let problem = Problem::new(status, t, detail)
             .with_type(gts)
-            .with_instance(inst)
+            .with_instance(inst);
+        match err {
+            DomainError::SchemaViolation { violations, .. } => problem.with_errors(
+                violations
+                    .into_iter()
+                    .map(|(field, message)| ValidationViolation {
+                        field,
+                        message,
+                        code: None,
+                    })
+                    .collect(),
+            ),
+            _ => problem,
+        }
     }
 }
 
@@ -163,11 +248,29 @@ pub(crate) fn domain_error_to_problem(err: DomainError, instance: &str) -> Probl
 /// Convert a `DomainError` into an axum `Response` with the
 /// `x-oagw-error-source: gateway` header. Used by the proxy handler.
 pub fn error_response(err: DomainError) -> Response {
+    let rate_limit_headers = match &err {
+        DomainError::RateLimitExceeded { headers, .. } => headers.clone(),
+        _ => Vec::new(),
+    };
+    let allow = match &err {
+        DomainError::MethodNotAllowed { allowed, .. } => Some(allowed.join(", ")),
+        _ => None,
+    };
     let retry_after = match &err {
         DomainError::RateLimitExceeded {
             retry_after_secs: Some(secs),
             ..
+        }
+        | DomainError::UpstreamMaintenance {
+            retry_after_secs: Some(secs),
+            ..
         } => Some(*secs),
+        DomainError::CircuitBreakerOpen {
+            retry_after_secs, ..
+        }
+        | DomainError::AuthzUnavailable {
+            retry_after_secs, ..
+        } => Some(*retry_after_secs),
         _ => None,
     };
 
@@ -185,9 +288,98 @@ pub fn error_response(err: DomainError) -> Response {
         response.headers_mut().insert("retry-after", v);
     }
 
+    if let Some(allow) = allow
+        && let Ok(v) = HeaderValue::from_str(&allow)
+    {
+        response.headers_mut().insert(http::header::ALLOW, v);
+    }
+
+    for (name, value) in rate_limit_headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            response.headers_mut().insert(name, value);
+        }
+    }
+
     response
 }
 
+/// Like [`error_response`], but with the body rendered from `template` when
+/// one is configured. Used by the proxy handler.
+pub(crate) fn proxy_error_response(
+    err: DomainError,
+    template: Option<&ErrorBodyTemplate>,
+    request_id: &str,
+) -> Response {
+    let Some(template) = template else {
+        return error_response(err);
+    };
+    let code = http_status_code(&err).as_u16().to_string();
+    let body = render_error_body(template, &code, &err.to_string(), request_id);
+
+    let mut response = error_response(err);
+    let headers = response.headers_mut();
+    headers.remove(http::header::CONTENT_LENGTH);
+    match HeaderValue::from_str(&template.content_type) {
+        Ok(v) => {
+            headers.insert(http::header::CONTENT_TYPE, v);
+        }
+        Err(_) => {
+            headers.remove(http::header::CONTENT_TYPE);
+        }
+    }
+    *response.body_mut() = axum::body::Body::from(body);
+    response
+}
+
+/// Substitute `{code}`, `{message}` and `{request_id}` in a single pass, so
+/// placeholders inside substituted values are left as they are. Values are
+/// JSON-escaped for JSON content types.
+fn render_error_body(
+    template: &ErrorBodyTemplate,
+    code: &str,
+    message: &str,
+    request_id: &str,
+) -> String {
+    let json = template.content_type.contains("json");
+    let escape = |v: &str| {
+        if json {
+            let quoted = serde_json::Value::from(v).to_string();
+            quoted[1..quoted.len() - 1].to_string()
+        } else {
+            v.to_string()
+        }
+    };
+
+    let mut out = String::with_capacity(template.body.len());
+    let mut rest = template.body.as_str();
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = [
+            ("{code}", code),
+            ("{message}", message),
+            ("{request_id}", request_id),
+        ]
+        .into_iter()
+        .find(|(placeholder, _)| rest.starts_with(placeholder));
+        match value {
+            Some((placeholder, value)) => {
+                out.push_str(&escape(value));
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                out.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,6 +415,7 @@ mod tests {
             detail: "rate limit exceeded for upstream".into(),
             instance: "/oagw/v1/proxy/api.openai.com/v1/chat/completions".into(),
             retry_after_secs: Some(30),
+            headers: Vec::new(),
         };
         let p: Problem = err.into();
         assert_eq!(p.status, StatusCode::TOO_MANY_REQUESTS);
@@ -272,10 +465,28 @@ mod tests {
                 detail: "test".into(),
                 instance: "/test".into(),
             },
+            DomainError::UnsupportedMediaType {
+                detail: "test".into(),
+                instance: "/test".into(),
+            },
+            DomainError::UriTooLong {
+                detail: "test".into(),
+                instance: "/test".into(),
+            },
+            DomainError::HeaderTooLarge {
+                detail: "test".into(),
+                instance: "/test".into(),
+            },
+            DomainError::MethodNotAllowed {
+                detail: "test".into(),
+                instance: "/test".into(),
+                allowed: vec!["GET".into()],
+            },
             DomainError::RateLimitExceeded {
                 detail: "test".into(),
                 instance: "/test".into(),
                 retry_after_secs: None,
+                headers: Vec::new(),
             },
             DomainError::SecretNotFound {
                 detail: "test".into(),
@@ -292,6 +503,35 @@ mod tests {
             DomainError::UpstreamDisabled {
                 alias: "test".into(),
             },
+            DomainError::UpstreamMaintenance {
+                detail: "test".into(),
+                instance: "/test".into(),
+                retry_after_secs: Some(60),
+            },
+            DomainError::CircuitBreakerOpen {
+                detail: "test".into(),
+                instance: "/test".into(),
+                retry_after_secs: 30,
+            },
+            DomainError::NoHealthyTarget {
+                detail: "test".into(),
+                instance: "/test".into(),
+                total: 2,
+                healthy: 0,
+            },
+            DomainError::GatewayOverloaded {
+                detail: "test".into(),
+                instance: "/test".into(),
+            },
+            DomainError::PluginDisabled {
+                detail: "test".into(),
+                instance: "/test".into(),
+            },
+            DomainError::AuthzUnavailable {
+                detail: "test".into(),
+                instance: "/test".into(),
+                retry_after_secs: 1,
+            },
             DomainError::ConnectionTimeout {
                 detail: "test".into(),
                 instance: "/test".into(),
@@ -300,12 +540,19 @@ mod tests {
                 detail: "test".into(),
                 instance: "/test".into(),
             },
+            DomainError::ClientTimeout {
+                detail: "test".into(),
+                instance: "/test".into(),
+            },
             DomainError::Internal {
                 message: "test".into(),
             },
             DomainError::Forbidden {
                 detail: "test".into(),
             },
+            DomainError::QuotaExceeded {
+                detail: "test".into(),
+            },
         ];
         for err in errors {
             let p: Problem = err.into();
@@ -339,6 +586,75 @@ mod tests {
         assert_eq!(p.instance, "/oagw/v1/upstreams");
     }
 
+    #[test]
+    fn upstream_maintenance_sets_retry_after() {
+        let err = DomainError::UpstreamMaintenance {
+            detail: "down for maintenance".into(),
+            instance: "/oagw/v1/proxy/api.openai.com/v1/models".into(),
+            retry_after_secs: Some(60),
+        };
+        let resp = error_response(err);
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers().get("retry-after").unwrap(), "60");
+    }
+
+    #[test]
+    fn method_not_allowed_sets_allow_header() {
+        let err = DomainError::MethodNotAllowed {
+            detail: "method DELETE is not allowed".into(),
+            instance: "/oagw/v1/proxy/api.openai.com/v1/models".into(),
+            allowed: vec!["GET".into(), "HEAD".into(), "POST".into()],
+        };
+        let resp = error_response(err);
+        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(resp.headers().get("allow").unwrap(), "GET, HEAD, POST");
+    }
+
+    #[test]
+    fn circuit_breaker_open_sets_retry_after() {
+        let err = DomainError::CircuitBreakerOpen {
+            detail: "circuit breaker open".into(),
+            instance: "/oagw/v1/proxy/api.openai.com/v1/models".into(),
+            retry_after_secs: 12,
+        };
+        let resp = error_response(err);
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers().get("retry-after").unwrap(), "12");
+    }
+
+    #[test]
+    fn rate_limit_exceeded_sets_status_headers() {
+        let err = DomainError::RateLimitExceeded {
+            detail: "rate limit exceeded".into(),
+            instance: "/oagw/v1/proxy/api.openai.com/v1/models".into(),
+            retry_after_secs: Some(30),
+            headers: vec![
+                ("X-RateLimit-Limit".into(), "10".into()),
+                ("X-RateLimit-Remaining".into(), "0".into()),
+            ],
+        };
+        let resp = error_response(err);
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers().get("x-ratelimit-limit").unwrap(), "10");
+        assert_eq!(resp.headers().get("x-ratelimit-remaining").unwrap(), "0");
+    }
+
+    #[test]
+    fn schema_violation_lists_errors() {
+        let err = DomainError::SchemaViolation {
+            detail: "request body violates the route's request_schema".into(),
+            instance: "/oagw/v1/proxy/llm/chat".into(),
+            violations: vec![("/max_tokens".into(), "0 is less than 1".into())],
+        };
+        let p: Problem = err.into();
+        assert_eq!(p.status, StatusCode::BAD_REQUEST);
+        assert_eq!(p.type_url, ERR_VALIDATION);
+        let errors = p.errors.unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "/max_tokens");
+        assert_eq!(errors[0].message, "0 is less than 1");
+    }
+
     #[test]
     fn error_response_sets_gateway_header() {
         let err = DomainError::NotFound {
@@ -351,4 +667,87 @@ mod tests {
             "gateway"
         );
     }
+
+    #[tokio::test]
+    async fn proxy_error_response_renders_template() {
+        let template = ErrorBodyTemplate {
+            body: r#"{"error":{"status":{code},"msg":"{message}","rid":"{request_id}"}}"#.into(),
+            content_type: "application/json".into(),
+        };
+        let err = DomainError::PayloadTooLarge {
+            detail: "body \"too\" large {request_id}".into(),
+            instance: "/oagw/v1/proxy/api/upload".into(),
+        };
+        let resp = proxy_error_response(err, Some(&template), "req-7");
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(resp.headers()["content-type"], "application/json");
+        assert_eq!(resp.headers()["x-oagw-error-source"], "gateway");
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["status"], 413);
+        assert!(
+            json["error"]["msg"]
+                .as_str()
+                .unwrap()
+                .ends_with(r#"body "too" large {request_id}"#)
+        );
+        assert_eq!(json["error"]["rid"], "req-7");
+    }
+
+    #[tokio::test]
+    async fn proxy_error_response_without_template_is_problem_json() {
+        let err = DomainError::RequestTimeout {
+            detail: "upstream timed out".into(),
+            instance: "/oagw/v1/proxy/api/slow".into(),
+        };
+        let resp = proxy_error_response(err, None, "req-8");
+        assert_eq!(resp.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(resp.headers()["content-type"], "application/problem+json");
+    }
+
+    #[tokio::test]
+    async fn client_timeout_is_request_timeout() {
+        let err = DomainError::ClientTimeout {
+            detail: "no request body data received for 100 ms".into(),
+            instance: "/oagw/v1/proxy/api/upload".into(),
+        };
+        let resp = proxy_error_response(err, None, "req-9");
+        assert_eq!(resp.status(), StatusCode::REQUEST_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn no_healthy_target_is_service_unavailable() {
+        let err = DomainError::NoHealthyTarget {
+            detail: "no healthy upstream target for 'api': 0 of 3 targets healthy".into(),
+            instance: "/oagw/v1/proxy/api/items".into(),
+            total: 3,
+            healthy: 0,
+        };
+        let resp = proxy_error_response(err, None, "req-10");
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem["type"], ERR_NO_HEALTHY_TARGET);
+        assert_eq!(
+            problem["detail"],
+            "no healthy upstream target for 'api': 0 of 3 targets healthy"
+        );
+    }
+
+    #[test]
+    fn render_error_body_leaves_plain_text_and_unknown_placeholders() {
+        let template = ErrorBodyTemplate {
+            body: "{code} {oops} {message} [{request_id}]".into(),
+            content_type: "text/plain".into(),
+        };
+        assert_eq!(
+            render_error_body(&template, "504", "said \"late\"", ""),
+            r#"504 {oops} said "late" []"#
+        );
+    }
 }
@@ -35,3 +35,11 @@ impl PaginationQuery {
         }
     }
 }
+
+/// Query parameters for `DELETE /oagw/v1/upstreams/{id}`.
+#[derive(Debug, serde::Deserialize)]
+pub struct DeleteUpstreamQuery {
+    /// Also delete the upstream's routes instead of rejecting the request.
+    #[serde(default)]
+    pub cascade: bool,
+}
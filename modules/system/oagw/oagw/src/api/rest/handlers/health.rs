@@ -0,0 +1,34 @@
+use axum::Json;
+use axum::extract::Extension;
+use axum::response::IntoResponse;
+use http::StatusCode;
+
+use crate::api::rest::dto::{LivenessResponse, ReadinessResponse};
+use crate::module::AppState;
+
+/// Liveness probe: the process is up. Never consults plugins or upstreams.
+pub async fn liveness() -> impl IntoResponse {
+    Json(LivenessResponse { live: true })
+}
+
+/// Readiness probe: 200 when every readiness check passes, 503 otherwise.
+pub async fn readiness(Extension(state): Extension<AppState>) -> impl IntoResponse {
+    let status = state.dp.status().await;
+    let code = if status.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        code,
+        Json(ReadinessResponse {
+            ready: status.ready,
+            reasons: status.reasons,
+            upstream_latencies: status
+                .upstream_latencies
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }),
+    )
+}
@@ -1,3 +1,5 @@
+pub mod health;
 pub mod proxy;
 pub mod route;
+pub mod tenant;
 pub mod upstream;
@@ -1,11 +1,22 @@
+use std::net::SocketAddr;
+
 use crate::domain::error::DomainError;
-use axum::body::Body;
-use axum::extract::{Extension, Request};
+use axum::body::{Body, HttpBody as _};
+use axum::extract::{ConnectInfo, Extension, Request};
 use axum::response::Response;
+use bytes::Bytes;
+use futures_util::StreamExt;
+use http_body::Frame;
+use http_body_util::StreamBody;
+use hyper::upgrade::OnUpgrade;
+use hyper_util::rt::TokioIo;
 use modkit_security::SecurityContext;
-use oagw_sdk::api::ErrorSource;
+use oagw_sdk::api::{ClientIp, ErrorSource};
+use oagw_sdk::body::{BodyStream, BoxError, Trailers};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc;
 
-use crate::api::rest::error::error_response;
+use crate::api::rest::error::proxy_error_response;
 use crate::module::AppState;
 
 /// Proxy handler for `/oagw/v1/proxy/{alias}/{path:.*}`.
@@ -17,8 +28,24 @@ pub async fn proxy_handler(
     Extension(ctx): Extension<SecurityContext>,
     req: Request,
 ) -> Result<Response, Response> {
-    let max_body_size = state.config.max_body_size_bytes;
+    let config = state.config.load_full();
+    let max_body_size = config.max_body_size_bytes;
     let (mut parts, body) = req.into_parts();
+    // The data plane forwards the caller's address when configured to.
+    if let Some(ConnectInfo(peer)) = parts.extensions.get::<ConnectInfo<SocketAddr>>() {
+        let client_ip = ClientIp(peer.ip());
+        parts.extensions.insert(client_ip);
+    }
+
+    // Gateway errors use the configured error body template, if any.
+    let request_id = parts
+        .headers
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_owned();
+    let template = config.error_body_template.as_ref();
+    let error_response = |err| proxy_error_response(err, template, &request_id);
 
     // Parse alias from the URI to validate it's present.
     let path = parts.uri.path();
@@ -63,16 +90,6 @@ pub async fn proxy_handler(
         }
     }
 
-    // Read body bytes (limited to max_body_size).
-    let body_bytes = axum::body::to_bytes(body, max_body_size)
-        .await
-        .map_err(|_| {
-            error_response(DomainError::PayloadTooLarge {
-                detail: format!("request body exceeds maximum of {max_body_size} bytes"),
-                instance: path.to_string(),
-            })
-        })?;
-
     // Strip the proxy prefix from the URI so the DP receives /{alias}/{path}?query.
     let new_uri_str = if let Some(query) = parts.uri.query() {
         format!("/{remaining}?{query}")
@@ -86,8 +103,31 @@ pub async fn proxy_handler(
         })
     })?;
 
-    // Build http::Request<Body> for the DP service.
-    let sdk_body = oagw_sdk::Body::from(body_bytes);
+    // Build http::Request<Body> for the DP service. Non-empty bodies are
+    // forwarded as they arrive; the DP counts bytes on the streaming path and
+    // aborts with 413 once `max_body_size` is exceeded. A plugin that signs
+    // the request body would have to buffer it first — none of the built-in
+    // plugins do.
+    //
+    // On an upgrade request the body is instead the client's half of the
+    // tunnel, fed from the upgraded connection once the upstream accepts.
+    let on_upgrade = parts.extensions.remove::<OnUpgrade>();
+    let mut tunnel_tx = None;
+    let sdk_body = if on_upgrade.is_some() {
+        let (tx, rx) = mpsc::channel::<Bytes>(16);
+        tunnel_tx = Some(tx);
+        oagw_sdk::Body::Stream(Box::pin(futures_util::stream::unfold(
+            rx,
+            |mut rx| async move { rx.recv().await.map(|chunk| (Ok(chunk), rx)) },
+        )))
+    } else if body.is_end_stream() {
+        oagw_sdk::Body::Empty
+    } else {
+        oagw_sdk::Body::Stream(Box::pin(
+            body.into_data_stream()
+                .map(|chunk| chunk.map_err(|e| Box::new(e) as BoxError)),
+        ))
+    };
     let proxy_req = http::Request::from_parts(parts, sdk_body);
 
     // Execute proxy pipeline.
@@ -117,8 +157,25 @@ pub async fn proxy_handler(
     // Add error source header.
     builder = builder.header("x-oagw-error-source", error_source.as_str());
 
-    // Stream the response body.
-    let body = Body::from_stream(sdk_body.into_stream());
+    // After a `101` the upstream's bytes go to the upgraded connection,
+    // not into the response body.
+    if resp_parts.status == http::StatusCode::SWITCHING_PROTOCOLS
+        && let (Some(on_upgrade), Some(tx)) = (on_upgrade, tunnel_tx)
+    {
+        tokio::spawn(relay_upgraded(on_upgrade, tx, sdk_body.into_stream()));
+        return builder.body(Body::empty()).map_err(|e| {
+            error_response(DomainError::DownstreamError {
+                detail: format!("failed to build response: {e}"),
+                instance: String::new(),
+            })
+        });
+    }
+
+    // Stream the response body, followed by any upstream trailers.
+    let body = match resp_parts.extensions.get::<Trailers>() {
+        Some(trailers) => with_trailers(sdk_body.into_stream(), trailers.clone()),
+        None => Body::from_stream(sdk_body.into_stream()),
+    };
 
     builder.body(body).map_err(|e| {
         error_response(DomainError::DownstreamError {
@@ -128,6 +185,51 @@ pub async fn proxy_handler(
     })
 }
 
+/// Pipe an upgraded client connection through the data plane's tunnel:
+/// client bytes into `tx`, upstream bytes from `upstream` back to the client.
+/// Either side closing ends that direction only.
+async fn relay_upgraded(on_upgrade: OnUpgrade, tx: mpsc::Sender<Bytes>, mut upstream: BodyStream) {
+    let upgraded = match on_upgrade.await {
+        Ok(upgraded) => upgraded,
+        Err(e) => {
+            tracing::debug!(error = %e, "client connection upgrade failed");
+            return;
+        }
+    };
+    let (mut client_read, mut client_write) = tokio::io::split(TokioIo::new(upgraded));
+    let inbound = async move {
+        let mut buf = vec![0u8; 8192];
+        loop {
+            match client_read.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if tx.send(Bytes::copy_from_slice(&buf[..n])).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    };
+    let outbound = async move {
+        while let Some(Ok(chunk)) = upstream.next().await {
+            if client_write.write_all(&chunk).await.is_err() {
+                break;
+            }
+        }
+        let _ = client_write.shutdown().await;
+    };
+    tokio::join!(inbound, outbound);
+}
+
+/// Body that ends with a trailers frame when the upstream sent trailers.
+/// They are only known once the data stream is exhausted.
+fn with_trailers(stream: BodyStream, trailers: Trailers) -> Body {
+    let data = stream.map(|chunk| chunk.map(Frame::data));
+    let trailer = futures_util::stream::once(async move { trailers.get() })
+        .filter_map(|fields| async move { fields.map(|f| Ok(Frame::trailers(f))) });
+    Body::new(StreamBody::new(data.chain(trailer)))
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -189,4 +291,132 @@ mod tests {
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], ("my key".into(), "value".into()));
     }
+
+    #[tokio::test]
+    async fn body_ends_with_upstream_trailers() {
+        use http_body_util::BodyExt;
+        use oagw_sdk::body::{BodyStream, Trailers};
+
+        let trailers = Trailers::new();
+        let mut fields = http::HeaderMap::new();
+        fields.insert("x-checksum", "abc".parse().unwrap());
+        trailers.set(fields);
+        let stream: BodyStream = Box::pin(futures_util::stream::iter(vec![Ok(
+            bytes::Bytes::from("data"),
+        )]));
+
+        let collected = super::with_trailers(stream, trailers)
+            .collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            collected.trailers().unwrap().get("x-checksum").unwrap(),
+            "abc"
+        );
+        assert_eq!(collected.to_bytes(), "data");
+    }
+
+    #[tokio::test]
+    async fn body_without_trailers_has_no_trailer_frame() {
+        use http_body_util::BodyExt;
+        use oagw_sdk::body::{BodyStream, Trailers};
+
+        let stream: BodyStream = Box::pin(futures_util::stream::empty());
+        let collected = super::with_trailers(stream, Trailers::new())
+            .collect()
+            .await
+            .unwrap();
+
+        assert!(collected.trailers().is_none());
+    }
+
+    /// Send a request head over a raw socket and return the first response
+    /// head the gateway writes back.
+    async fn send_head(stream: &mut tokio::net::TcpStream, head: &str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        stream.write_all(head.as_bytes()).await.unwrap();
+        let mut buf = Vec::new();
+        let mut byte = [0u8; 1];
+        while !buf.ends_with(b"\r\n\r\n") {
+            assert_eq!(
+                stream.read(&mut byte).await.unwrap(),
+                1,
+                "connection closed"
+            );
+            buf.push(byte[0]);
+        }
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[tokio::test]
+    async fn expect_continue_is_answered_before_body_is_forwarded() {
+        use crate::test_support::{AppHarness, parse_resource_gts};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let h = AppHarness::builder().build().await;
+        let resp = h
+            .api_v1()
+            .post_upstream()
+            .with_body(serde_json::json!({
+                "server": {
+                    "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+                },
+                "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+                "alias": "expect-test",
+                "enabled": true,
+                "tags": []
+            }))
+            .expect_status(201)
+            .await;
+        let (_, upstream_id) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+        h.api_v1()
+            .post_route()
+            .with_body(serde_json::json!({
+                "upstream_id": upstream_id,
+                "match": {"http": {"methods": ["POST"], "path": "/echo"}},
+                "enabled": true,
+                "tags": [],
+                "priority": 0
+            }))
+            .expect_status(201)
+            .await;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(axum::serve(listener, h.router().clone()).into_future());
+
+        // Accepted request: `100 Continue` arrives before any body is sent,
+        // and the body then reaches the upstream.
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let interim = send_head(
+            &mut stream,
+            "POST /oagw/v1/proxy/expect-test/echo HTTP/1.1\r\nhost: localhost\r\n\
+             content-type: text/plain\r\ncontent-length: 5\r\nexpect: 100-continue\r\n\
+             connection: close\r\n\r\n",
+        )
+        .await;
+        assert!(interim.starts_with("HTTP/1.1 100 Continue"), "{interim}");
+        stream.write_all(b"hello").await.unwrap();
+        let mut rest = String::new();
+        stream.read_to_string(&mut rest).await.unwrap();
+        assert!(rest.starts_with("HTTP/1.1 200 OK"), "{rest}");
+        assert!(rest.contains(r#""body":"hello""#), "{rest}");
+        assert!(
+            !rest.contains("100-continue"),
+            "expectation leaked upstream: {rest}"
+        );
+
+        // Rejected request: the final status comes back without the client
+        // ever being asked for the body.
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let head = send_head(
+            &mut stream,
+            "POST /oagw/v1/proxy/no-such-alias/echo HTTP/1.1\r\nhost: localhost\r\n\
+             content-length: 5\r\nexpect: 100-continue\r\nconnection: close\r\n\r\n",
+        )
+        .await;
+        assert!(head.starts_with("HTTP/1.1 404"), "{head}");
+    }
 }
@@ -23,6 +23,32 @@ fn to_response(r: Route) -> RouteResponse {
         tags: r.tags,
         priority: r.priority,
         enabled: r.enabled,
+        skip_authz: r.skip_authz,
+        timeout_secs: r.timeout_secs,
+        status_remap: r.status_remap,
+        allowed_response_content_types: r.allowed_response_content_types,
+        allowed_request_content_types: r.allowed_request_content_types,
+        log_sample_rate: r.log_sample_rate,
+        request_schema: r.request_schema,
+        body_rewrite: r.body_rewrite.into_iter().map(Into::into).collect(),
+        auth_rules: r.auth_rules.into_iter().map(Into::into).collect(),
+        strip_query_params: r.strip_query_params,
+        strip_inbound_headers: r.strip_inbound_headers,
+        idempotency_ttl_secs: r.idempotency_ttl_secs,
+        cache_responses: r.cache_responses,
+        negative_cache_ttl_secs: r.negative_cache_ttl_secs,
+        response_header_allowlist: r.response_header_allowlist,
+        max_total_duration_ms: r.max_total_duration_ms,
+        max_body_size_bytes: r.max_body_size_bytes,
+        max_outbound_calls: r.max_outbound_calls,
+        coerce_content_type: r.coerce_content_type,
+        api_version: r.api_version.map(Into::into),
+        body_transformers: r.body_transformers,
+        log_redact_paths: r.log_redact_paths,
+        transform_hook_url: r.transform_hook_url,
+        deprecated: r.deprecated,
+        sunset: r.sunset,
+        kind: r.kind.into(),
     }
 }
 
@@ -0,0 +1,57 @@
+use axum::Json;
+use axum::extract::Extension;
+use axum::response::IntoResponse;
+use http::StatusCode;
+use modkit::api::problem::Problem;
+use modkit_security::SecurityContext;
+
+use crate::api::rest::dto::{AuthConfig, TenantAuthDefaultResponse};
+use crate::api::rest::error::domain_error_to_problem;
+use crate::domain::model::TenantAuthDefault;
+use crate::module::AppState;
+
+const DEFAULT_AUTH_PATH: &str = "/oagw/v1/tenant/default-auth";
+
+fn to_response(d: TenantAuthDefault) -> TenantAuthDefaultResponse {
+    TenantAuthDefaultResponse {
+        tenant_id: d.tenant_id,
+        auth: d.auth.into(),
+    }
+}
+
+pub async fn set_default_auth(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Json(req): Json<AuthConfig>,
+) -> Result<impl IntoResponse, Problem> {
+    let default = state
+        .cp
+        .set_tenant_default_auth(&ctx, req.into())
+        .await
+        .map_err(|e| domain_error_to_problem(e, DEFAULT_AUTH_PATH))?;
+    Ok(Json(to_response(default)))
+}
+
+pub async fn get_default_auth(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+) -> Result<impl IntoResponse, Problem> {
+    let default = state
+        .cp
+        .get_tenant_default_auth(&ctx)
+        .await
+        .map_err(|e| domain_error_to_problem(e, DEFAULT_AUTH_PATH))?;
+    Ok(Json(to_response(default)))
+}
+
+pub async fn delete_default_auth(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+) -> Result<impl IntoResponse, Problem> {
+    state
+        .cp
+        .delete_tenant_default_auth(&ctx)
+        .await
+        .map_err(|e| domain_error_to_problem(e, DEFAULT_AUTH_PATH))?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -4,12 +4,16 @@ use axum::response::IntoResponse;
 use http::StatusCode;
 use modkit::api::problem::Problem;
 use modkit_security::SecurityContext;
+use uuid::Uuid;
 
-use crate::api::rest::dto::{CreateUpstreamRequest, UpdateUpstreamRequest, UpstreamResponse};
+use crate::api::rest::dto::{
+    CreateUpstreamRequest, RateLimitConfig, TenantRateLimitResponse, UpdateUpstreamRequest,
+    UpstreamResponse, UpstreamTestResponse,
+};
 use crate::api::rest::error::domain_error_to_problem;
-use crate::api::rest::extractors::{PaginationQuery, parse_gts_id};
+use crate::api::rest::extractors::{DeleteUpstreamQuery, PaginationQuery, parse_gts_id};
 use crate::domain::gts_helpers as gts;
-use crate::domain::model::Upstream;
+use crate::domain::model::{TenantRateLimit, Upstream};
 use crate::module::AppState;
 
 fn to_response(u: Upstream) -> UpstreamResponse {
@@ -25,6 +29,31 @@ fn to_response(u: Upstream) -> UpstreamResponse {
         plugins: u.plugins.map(Into::into),
         rate_limit: u.rate_limit.map(Into::into),
         tags: u.tags,
+        maintenance: u.maintenance,
+        maintenance_message: u.maintenance_message,
+        retry_on_body: u.retry_on_body.map(Into::into),
+        force_chunked: u.force_chunked,
+        failover: u.failover.into_iter().map(Into::into).collect(),
+        timeout_secs: u.timeout_secs,
+        keepalive: u.keepalive.map(Into::into),
+        circuit_breaker: u.circuit_breaker.map(Into::into),
+        user_agent: u.user_agent,
+        sticky_by: u.sticky_by,
+        allowed_methods: u.allowed_methods.into_iter().map(Into::into).collect(),
+        trailing_slash: u.trailing_slash.into(),
+        max_header_size_bytes: u.max_header_size_bytes,
+        tls_min_version: u.tls_min_version,
+        tls_cipher_suites: u.tls_cipher_suites,
+        tls_server_name: u.tls_server_name,
+        budget_header: u.budget_header,
+    }
+}
+
+fn to_tenant_rate_limit_response(l: TenantRateLimit) -> TenantRateLimitResponse {
+    TenantRateLimitResponse {
+        upstream_id: gts::format_upstream_gts(l.upstream_id),
+        tenant_id: l.tenant_id,
+        rate_limit: l.rate_limit.into(),
     }
 }
 
@@ -94,15 +123,91 @@ pub async fn delete_upstream(
     Extension(state): Extension<AppState>,
     Extension(ctx): Extension<SecurityContext>,
     Path(id): Path<String>,
+    Query(query): Query<DeleteUpstreamQuery>,
 ) -> Result<impl IntoResponse, Problem> {
     let instance = format!("/oagw/v1/upstreams/{id}");
     let uuid = parse_gts_id(&id, &instance)?;
     state
         .cp
-        .delete_upstream(&ctx, uuid)
+        .delete_upstream(&ctx, uuid, query.cascade)
         .await
         .map_err(|e| domain_error_to_problem(e, &instance))?;
     state.backend_selector.invalidate(uuid);
     state.dp.remove_rate_limit_key(&format!("upstream:{uuid}"));
     Ok(StatusCode::NO_CONTENT)
 }
+
+pub async fn test_upstream(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, Problem> {
+    let instance = format!("/oagw/v1/upstreams/{id}/test");
+    let uuid = parse_gts_id(&id, &instance)?;
+    let upstream = state
+        .cp
+        .get_upstream(&ctx, uuid)
+        .await
+        .map_err(|e| domain_error_to_problem(e, &instance))?;
+    let result = state.dp.test_upstream(&ctx, &upstream).await;
+    Ok(Json(UpstreamTestResponse {
+        credential_resolved: result.credential_resolved,
+        auth_applied: result.auth_applied,
+        upstream_status: result.upstream_status,
+        latency_ms: result.latency_ms,
+        error: result.error,
+    }))
+}
+
+pub async fn set_tenant_rate_limit(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Path((id, tenant_id)): Path<(String, Uuid)>,
+    Json(req): Json<RateLimitConfig>,
+) -> Result<impl IntoResponse, Problem> {
+    let instance = format!("/oagw/v1/upstreams/{id}/tenant-rate-limits/{tenant_id}");
+    let uuid = parse_gts_id(&id, &instance)?;
+    let limit = state
+        .cp
+        .set_tenant_rate_limit(&ctx, uuid, tenant_id, req.into())
+        .await
+        .map_err(|e| domain_error_to_problem(e, &instance))?;
+    // Start the tenant from a full bucket under the new limit.
+    state
+        .dp
+        .remove_rate_limit_key(&format!("upstream:{uuid}:tenant:{tenant_id}"));
+    Ok(Json(to_tenant_rate_limit_response(limit)))
+}
+
+pub async fn get_tenant_rate_limit(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Path((id, tenant_id)): Path<(String, Uuid)>,
+) -> Result<impl IntoResponse, Problem> {
+    let instance = format!("/oagw/v1/upstreams/{id}/tenant-rate-limits/{tenant_id}");
+    let uuid = parse_gts_id(&id, &instance)?;
+    let limit = state
+        .cp
+        .get_tenant_rate_limit(&ctx, uuid, tenant_id)
+        .await
+        .map_err(|e| domain_error_to_problem(e, &instance))?;
+    Ok(Json(to_tenant_rate_limit_response(limit)))
+}
+
+pub async fn delete_tenant_rate_limit(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Path((id, tenant_id)): Path<(String, Uuid)>,
+) -> Result<impl IntoResponse, Problem> {
+    let instance = format!("/oagw/v1/upstreams/{id}/tenant-rate-limits/{tenant_id}");
+    let uuid = parse_gts_id(&id, &instance)?;
+    state
+        .cp
+        .delete_tenant_rate_limit(&ctx, uuid, tenant_id)
+        .await
+        .map_err(|e| domain_error_to_problem(e, &instance))?;
+    state
+        .dp
+        .remove_rate_limit_key(&format!("upstream:{uuid}:tenant:{tenant_id}"));
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,50 @@
+use axum::Router;
+use modkit::api::OpenApiRegistry;
+use modkit::api::operation_builder::OperationBuilder;
+
+use super::super::dto;
+use super::super::handlers;
+
+/// Register the unauthenticated liveness/readiness probe endpoints.
+pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Router {
+    // GET /oagw/v1/health/live — Liveness probe
+    router = OperationBuilder::get("/oagw/v1/health/live")
+        .operation_id("oagw.liveness")
+        .summary("Liveness probe")
+        .description("Report that the gateway process is up")
+        .tag("health")
+        .public()
+        .handler(handlers::health::liveness)
+        .json_response_with_schema::<dto::LivenessResponse>(
+            openapi,
+            http::StatusCode::OK,
+            "Gateway is live",
+        )
+        .register(router, openapi);
+
+    // GET /oagw/v1/health/ready — Readiness probe
+    router = OperationBuilder::get("/oagw/v1/health/ready")
+        .operation_id("oagw.readiness")
+        .summary("Readiness probe")
+        .description(
+            "Report whether plugins are initialized and healthy, provisioned configuration \
+             is loaded, and the optional sample upstream is reachable. Also reports rolling \
+             p50/p95/p99 response times per upstream",
+        )
+        .tag("health")
+        .public()
+        .handler(handlers::health::readiness)
+        .json_response_with_schema::<dto::ReadinessResponse>(
+            openapi,
+            http::StatusCode::OK,
+            "Gateway is ready",
+        )
+        .json_response_with_schema::<dto::ReadinessResponse>(
+            openapi,
+            http::StatusCode::SERVICE_UNAVAILABLE,
+            "Gateway is not ready",
+        )
+        .register(router, openapi);
+
+    router
+}
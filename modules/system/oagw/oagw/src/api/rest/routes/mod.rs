@@ -4,8 +4,10 @@ use modkit::api::operation_builder::LicenseFeature;
 
 use crate::module::AppState;
 
+mod health;
 mod proxy;
 mod route;
+mod tenant;
 mod upstream;
 
 pub(super) struct License;
@@ -26,6 +28,8 @@ pub fn register_routes(
 ) -> Router {
     router = upstream::register(router, openapi);
     router = route::register(router, openapi);
+    router = tenant::register(router, openapi);
+    router = health::register(router, openapi);
     router = proxy::register(router);
     router.layer(axum::Extension(state))
 }
@@ -36,7 +40,9 @@ pub fn register_routes(
 /// Suitable for integration tests that don't need an `OpenApiRegistry`.
 #[cfg(any(test, feature = "test-utils"))]
 pub fn test_router(state: AppState, ctx: modkit_security::SecurityContext) -> Router {
-    use crate::api::rest::handlers::{proxy as proxy_h, route as route_h, upstream as upstream_h};
+    use crate::api::rest::handlers::{
+        health as health_h, proxy as proxy_h, route as route_h, upstream as upstream_h,
+    };
     use axum::routing::{any, get, post};
 
     Router::new()
@@ -49,6 +55,10 @@ pub fn test_router(state: AppState, ctx: modkit_security::SecurityContext) -> Ro
                 .patch(upstream_h::update_upstream)
                 .delete(upstream_h::delete_upstream),
         )
+        .route(
+            "/oagw/v1/upstreams/{id}/test",
+            post(upstream_h::test_upstream),
+        )
         // Route CRUD
         .route("/oagw/v1/routes", post(route_h::create_route))
         .route(
@@ -61,6 +71,9 @@ pub fn test_router(state: AppState, ctx: modkit_security::SecurityContext) -> Ro
             "/oagw/v1/upstreams/{upstream_id}/routes",
             get(route_h::list_routes),
         )
+        // Health
+        .route("/oagw/v1/health/live", get(health_h::liveness))
+        .route("/oagw/v1/health/ready", get(health_h::readiness))
         // Proxy
         .route("/oagw/v1/proxy/{*path}", any(proxy_h::proxy_handler))
         .layer(axum::Extension(ctx))
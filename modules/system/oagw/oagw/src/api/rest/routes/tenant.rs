@@ -0,0 +1,62 @@
+use axum::Router;
+use modkit::api::OpenApiRegistry;
+use modkit::api::operation_builder::OperationBuilder;
+
+use super::super::dto;
+use super::super::handlers;
+use super::License;
+
+pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Router {
+    // PUT /oagw/v1/tenant/default-auth — Set tenant default auth
+    router = OperationBuilder::put("/oagw/v1/tenant/default-auth")
+        .operation_id("oagw.set_tenant_default_auth")
+        .summary("Set tenant default auth")
+        .description(
+            "Set the auth applied to the caller's tenant's requests through upstreams \
+             that configure none of their own",
+        )
+        .tag("tenant")
+        .authenticated()
+        .require_license_features::<License>([])
+        .json_request::<dto::AuthConfig>(openapi, "Default auth for the tenant")
+        .handler(handlers::tenant::set_default_auth)
+        .json_response_with_schema::<dto::TenantAuthDefaultResponse>(
+            openapi,
+            http::StatusCode::OK,
+            "Tenant default auth set",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /oagw/v1/tenant/default-auth — Get tenant default auth
+    router = OperationBuilder::get("/oagw/v1/tenant/default-auth")
+        .operation_id("oagw.get_tenant_default_auth")
+        .summary("Get tenant default auth")
+        .description("Retrieve the default auth of the caller's tenant")
+        .tag("tenant")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::tenant::get_default_auth)
+        .json_response_with_schema::<dto::TenantAuthDefaultResponse>(
+            openapi,
+            http::StatusCode::OK,
+            "Tenant default auth found",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // DELETE /oagw/v1/tenant/default-auth — Delete tenant default auth
+    router = OperationBuilder::delete("/oagw/v1/tenant/default-auth")
+        .operation_id("oagw.delete_tenant_default_auth")
+        .summary("Delete tenant default auth")
+        .description("Remove the tenant's default auth so upstreams without auth send none")
+        .tag("tenant")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::tenant::delete_default_auth)
+        .json_response(http::StatusCode::NO_CONTENT, "Tenant default auth deleted")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    router
+}
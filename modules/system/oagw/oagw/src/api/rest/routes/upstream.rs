@@ -90,9 +90,18 @@ pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Rou
     router = OperationBuilder::delete("/oagw/v1/upstreams/{id}")
         .operation_id("oagw.delete_upstream")
         .summary("Delete upstream")
-        .description("Delete an upstream and cascade-delete its routes")
+        .description(
+            "Delete an upstream. Fails with 409 while routes reference it unless \
+             `cascade=true`, which deletes those routes as well",
+        )
         .tag("upstreams")
         .path_param("id", "Upstream GTS identifier")
+        .query_param_typed(
+            "cascade",
+            false,
+            "Delete dependent routes together with the upstream",
+            "boolean",
+        )
         .authenticated()
         .require_license_features::<License>([])
         .handler(handlers::upstream::delete_upstream)
@@ -100,5 +109,85 @@ pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Rou
         .standard_errors(openapi)
         .register(router, openapi);
 
+    // POST /oagw/v1/upstreams/{id}/test — Test upstream connection
+    router = OperationBuilder::post("/oagw/v1/upstreams/{id}/test")
+        .operation_id("oagw.test_upstream")
+        .summary("Test upstream connection")
+        .description(
+            "Resolve the upstream's credentials, run its auth plugin against a probe \
+             request and report whether the upstream responded",
+        )
+        .tag("upstreams")
+        .path_param("id", "Upstream GTS identifier")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::upstream::test_upstream)
+        .json_response_with_schema::<dto::UpstreamTestResponse>(
+            openapi,
+            http::StatusCode::OK,
+            "Test connection result",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // PUT /oagw/v1/upstreams/{id}/tenant-rate-limits/{tenant_id} — Set tenant rate limit
+    router = OperationBuilder::put("/oagw/v1/upstreams/{id}/tenant-rate-limits/{tenant_id}")
+        .operation_id("oagw.set_tenant_rate_limit")
+        .summary("Set tenant rate limit")
+        .description(
+            "Set the rate limit applied to one tenant's requests through the upstream, \
+             replacing the upstream's own limit for that tenant",
+        )
+        .tag("upstreams")
+        .path_param("id", "Upstream GTS identifier")
+        .path_param("tenant_id", "Tenant the limit applies to")
+        .authenticated()
+        .require_license_features::<License>([])
+        .json_request::<dto::RateLimitConfig>(openapi, "Rate limit for the tenant")
+        .handler(handlers::upstream::set_tenant_rate_limit)
+        .json_response_with_schema::<dto::TenantRateLimitResponse>(
+            openapi,
+            http::StatusCode::OK,
+            "Tenant rate limit set",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /oagw/v1/upstreams/{id}/tenant-rate-limits/{tenant_id} — Get tenant rate limit
+    router = OperationBuilder::get("/oagw/v1/upstreams/{id}/tenant-rate-limits/{tenant_id}")
+        .operation_id("oagw.get_tenant_rate_limit")
+        .summary("Get tenant rate limit")
+        .description("Retrieve the rate limit set for one tenant on the upstream")
+        .tag("upstreams")
+        .path_param("id", "Upstream GTS identifier")
+        .path_param("tenant_id", "Tenant the limit applies to")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::upstream::get_tenant_rate_limit)
+        .json_response_with_schema::<dto::TenantRateLimitResponse>(
+            openapi,
+            http::StatusCode::OK,
+            "Tenant rate limit found",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // DELETE /oagw/v1/upstreams/{id}/tenant-rate-limits/{tenant_id} — Delete tenant rate limit
+    router = OperationBuilder::delete("/oagw/v1/upstreams/{id}/tenant-rate-limits/{tenant_id}")
+        .operation_id("oagw.delete_tenant_rate_limit")
+        .summary("Delete tenant rate limit")
+        .description(
+            "Remove the tenant's rate limit so its requests fall back to the upstream's limit",
+        )
+        .tag("upstreams")
+        .path_param("id", "Upstream GTS identifier")
+        .path_param("tenant_id", "Tenant the limit applies to")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::upstream::delete_tenant_rate_limit)
+        .json_response(http::StatusCode::NO_CONTENT, "Tenant rate limit deleted")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
     router
 }
@@ -1,6 +1,12 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
 use std::{fmt, time::Duration};
 
+use arc_swap::ArcSwap;
+
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 /// Configuration for the OAGW module.
 #[derive(Clone, Serialize, Deserialize)]
@@ -10,6 +16,31 @@ pub struct OagwConfig {
     pub proxy_timeout_secs: u64,
     #[serde(default = "default_max_body_size_bytes")]
     pub max_body_size_bytes: usize,
+    /// Maximum length, in bytes, of a proxied request's path and query.
+    /// Longer URLs are rejected with `414`. Default: 8192.
+    #[serde(default = "default_max_url_length")]
+    pub max_url_length: usize,
+    /// Maximum number of query parameters on a proxied request, counted
+    /// after the route's `strip_query_params` are dropped and its API
+    /// version parameter is added. More are rejected with `400`. Default:
+    /// unset (no cap).
+    #[serde(default)]
+    pub max_query_params: Option<usize>,
+    /// What the proxy does with a body sent on a `GET` or `HEAD` request.
+    /// A `HEAD` body is never forwarded. Default: `allow`.
+    #[serde(default)]
+    pub bodiless_method_body: BodilessMethodBody,
+    /// Largest upstream response body, in bytes, read to EOF when the
+    /// response carries neither `Content-Length` nor chunked encoding.
+    /// Larger responses fail with `502`. Default: 10 MB.
+    #[serde(default = "default_max_body_size_bytes")]
+    pub max_response_body_size_bytes: usize,
+    /// Pass EOF-delimited upstream responses through as streams instead of
+    /// buffering them up to `max_response_body_size_bytes`. Streamed
+    /// responses skip body rewrites, caching and body-triggered retries.
+    /// Default: false.
+    #[serde(default)]
+    pub stream_unframed_responses: bool,
     #[serde(default)]
     pub allow_http_upstream: bool,
     /// TTL in seconds for cached OAuth2 access tokens.
@@ -22,6 +53,355 @@ pub struct OagwConfig {
     /// Default: 10 000.
     #[serde(default = "default_token_cache_capacity")]
     pub token_cache_capacity: usize,
+    /// What to do when the credential store is unreachable.
+    /// Default: `fail_closed`.
+    #[serde(default)]
+    pub credstore_unavailable: CredStoreUnavailablePolicy,
+    /// Optional `host:port` of a sample upstream that must accept a TCP
+    /// connection for the module to report ready. Default: unset (no probe).
+    #[serde(default)]
+    pub readiness_probe_endpoint: Option<String>,
+    /// Seconds an idle pooled upstream connection is kept before it is
+    /// closed. Keep this below the idle timeout of any intermediary (load
+    /// balancer, NAT) between OAGW and its upstreams. Default: 90.
+    #[serde(default = "default_pool_idle_timeout_secs")]
+    pub pool_idle_timeout_secs: u64,
+    /// Gateway-wide cap on in-flight proxy requests, complementing per-route
+    /// rate limits. Requests beyond it are queued (see `queue_timeout_ms`)
+    /// and then shed with `503`. Default: unset (no cap).
+    #[serde(default)]
+    pub max_global_concurrency: Option<usize>,
+    /// Milliseconds a request waits for a slot once `max_global_concurrency`
+    /// is reached. `0` sheds immediately. Default: 0.
+    #[serde(default)]
+    pub queue_timeout_ms: u64,
+    /// Cap on simultaneous connections to a single upstream host
+    /// (`host:port`), across all upstreams. Requests beyond it wait for a
+    /// free connection (see `queue_wait_timeout_ms`). Default: unset (no cap).
+    #[serde(default)]
+    pub max_connections_per_host: Option<usize>,
+    /// Cap on in-flight requests to each upstream. Requests beyond it are
+    /// queued per tenant and freed slots go to the queued tenants in turn,
+    /// so one tenant's backlog cannot starve the others (see
+    /// `queue_wait_timeout_ms`). Default: unset (no cap).
+    #[serde(default)]
+    pub max_concurrent_requests_per_upstream: Option<usize>,
+    /// Milliseconds a request waits for a free connection once
+    /// `max_connections_per_host` is reached, or for an upstream slot once
+    /// `max_concurrent_requests_per_upstream` is; it is then shed with `503`.
+    /// The wait does not count against the upstream timeout. Default: unset
+    /// (wait up to the request timeout, then `504`).
+    #[serde(default)]
+    pub queue_wait_timeout_ms: Option<u64>,
+    /// Seconds a resolved upstream hostname is reused before it is looked
+    /// up again; cached hosts are refreshed in the background on the same
+    /// period. `0` disables caching. Default: 30.
+    #[serde(default = "default_dns_cache_ttl_secs")]
+    pub dns_cache_ttl_secs: u64,
+    /// Hostnames pinned to fixed addresses, bypassing DNS (testing, or
+    /// pinning an upstream to a known address). Default: empty.
+    #[serde(default)]
+    pub dns_override: HashMap<String, IpAddr>,
+    /// Static SRV-style target lists, keyed by upstream endpoint host. A
+    /// request to a listed host goes to one of its targets instead: the
+    /// lowest `priority` value wins and `weight` shares load within it.
+    /// Lower priorities are tried as failover endpoints. Default: empty.
+    #[serde(default)]
+    pub srv_targets: HashMap<String, Vec<SrvTarget>>,
+    /// Extra attempts to resolve an upstream host whose lookup failed,
+    /// separate from upstream retries and failover. `0` disables them.
+    /// Default: 0.
+    #[serde(default)]
+    pub dns_retry_max: u32,
+    /// Milliseconds between DNS retries. Default: 100.
+    #[serde(default = "default_dns_retry_backoff_ms")]
+    pub dns_retry_backoff_ms: u64,
+    /// Extra attempts to connect to an upstream endpoint after a refused or
+    /// timed-out TCP connect. The upstream never saw the failed attempts, so
+    /// they are separate from upstream retries and failover. `0` disables
+    /// them. Default: 0.
+    #[serde(default)]
+    pub connect_retry_max: u32,
+    /// Milliseconds between connect retries. Default: 50.
+    #[serde(default = "default_connect_retry_backoff_ms")]
+    pub connect_retry_backoff_ms: u64,
+    /// Milliseconds a server-sent event response may go without a chunk
+    /// from the upstream before the gateway ends it with
+    /// `stream_idle_terminal_event`. Default: unset (wait for the upstream).
+    #[serde(default)]
+    pub stream_idle_timeout_ms: Option<u64>,
+    /// SSE event written, verbatim, to a stream ended by
+    /// `stream_idle_timeout_ms`. Default: an `error` event with
+    /// `{"error":"upstream_stream_idle_timeout"}`.
+    #[serde(default = "default_stream_idle_terminal_event")]
+    pub stream_idle_terminal_event: String,
+    /// Server-sent event responses the gateway relays at once. A stream
+    /// beyond the cap is refused with `503` once the upstream answers, and
+    /// streams already running continue. Default: unset (no cap).
+    #[serde(default)]
+    pub max_concurrent_streams: Option<usize>,
+    /// Milliseconds a client may go without sending request body data
+    /// before the request is aborted with `408`. Headers are read by the
+    /// API gateway's listener before OAGW sees the request, so this covers
+    /// the body only. Default: unset (wait for the client).
+    #[serde(default)]
+    pub body_read_idle_timeout_ms: Option<u64>,
+    /// Seed for the sampler behind route `log_sample_rate`, making the set of
+    /// logged requests reproducible. Default: unset (seeded from the OS).
+    #[serde(default)]
+    pub log_sampling_seed: Option<u64>,
+    /// Gateway-generated proxy errors kept, with secrets redacted, for
+    /// `recent_errors` on the client API. `0` keeps none. Default: 50.
+    #[serde(default = "default_recent_errors_capacity")]
+    pub recent_errors_capacity: usize,
+    /// What the proxy answers when no route matches a request to a known
+    /// upstream alias. Default: `not_found` (`404`).
+    #[serde(default)]
+    pub unmatched_route: UnmatchedRouteBehavior,
+    /// Names of the headers reporting rate-limit state on responses of
+    /// rate-limited routes and upstreams, including the `429`.
+    /// Default: `X-RateLimit-Limit`, `X-RateLimit-Remaining`, `X-RateLimit-Reset`.
+    #[serde(default)]
+    pub rate_limit_headers: RateLimitHeaderNames,
+    /// `User-Agent` sent to upstreams unless the client's own is passed
+    /// through; an upstream's `user_agent` overrides it.
+    /// Default: `oagw/<version>`.
+    #[serde(default = "default_user_agent")]
+    pub user_agent: String,
+    /// Header names stripped from requests and responses on top of the
+    /// standard hop-by-hop set and those named in `Connection`.
+    #[serde(default)]
+    pub extra_hop_by_hop_headers: Vec<String>,
+    /// Request header names removed for every route before auth plugins
+    /// run, so headers such as internal session cookies never reach an
+    /// upstream. Headers set by auth plugins are unaffected; a route's own
+    /// `strip_inbound_headers` adds to this list. Default: empty.
+    #[serde(default)]
+    pub strip_inbound_headers: Vec<String>,
+    /// Cache `GET` responses marked `Cache-Control: private` per caller,
+    /// keyed by tenant and subject. Otherwise they are never cached.
+    /// Default: false.
+    #[serde(default)]
+    pub private_response_cache: bool,
+    /// Answer `HEAD` requests from a cached `GET` response of the same
+    /// resource (headers only) instead of calling the upstream.
+    /// Default: true.
+    #[serde(default = "default_head_from_response_cache")]
+    pub head_from_response_cache: bool,
+    /// Gzip buffered responses the upstream sent uncompressed, for clients
+    /// whose `Accept-Encoding` admits gzip. Streamed responses are never
+    /// compressed. Default: false.
+    #[serde(default)]
+    pub compress_responses: bool,
+    /// Smallest buffered response body, in bytes, that
+    /// `compress_responses` gzips. Default: 1024.
+    #[serde(default = "default_compress_min_size_bytes")]
+    pub compress_min_size_bytes: usize,
+    /// Response statuses counted as errors in the `oagw.proxy.responses`
+    /// metric and sampled request logs, e.g. to add `404` for APIs where it
+    /// signals a failure. Replaces the default. Default: every `5xx`.
+    #[serde(default = "default_error_status_codes")]
+    pub error_status_codes: Vec<u16>,
+    /// Auth plugin config keys (e.g. `secret_ref`) that a request may
+    /// override with an `X-OAGW-Auth-Config-<key>` header, for testing
+    /// upstreams with different credentials. Privileged: only enable where
+    /// callers may choose credentials. Default: empty (no overrides).
+    #[serde(default)]
+    pub auth_config_override_keys: Vec<String>,
+    /// Resolve `env://NAME` secret references from the gateway's
+    /// environment. Privileged: any upstream's auth config can then read
+    /// any environment variable. Default: false.
+    #[serde(default)]
+    pub env_secret_refs: bool,
+    /// Directory `file:///path` secret references may read from; files
+    /// outside it are rejected. Default: unset (`file://` disabled).
+    #[serde(default)]
+    pub file_secret_dir: Option<String>,
+    /// GTS identifiers of auth plugins to disable. Requests to routes that
+    /// use one fail with `503` instead of being proxied. Default: empty.
+    #[serde(default)]
+    pub disabled_auth_plugins: Vec<String>,
+    /// Append the caller's IP to `X-Forwarded-For` and set
+    /// `X-Forwarded-Proto`/`X-Forwarded-Host` on upstream requests.
+    /// Default: false.
+    #[serde(default)]
+    pub forward_client_ip: bool,
+    /// Proxies in front of the gateway whose `X-Forwarded-*` headers are
+    /// trusted. From any other peer they are replaced rather than extended.
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+    /// Consecutive upstream failures (connection errors, timeouts, `5xx`
+    /// gateway-class responses) that open an upstream's circuit breaker;
+    /// `0` disables it. Upstreams may override. Default: 5.
+    #[serde(default = "default_cb_failure_threshold")]
+    pub cb_failure_threshold: u32,
+    /// Seconds an open circuit rejects requests before probing the upstream.
+    /// Default: 30.
+    #[serde(default = "default_cb_cooldown_secs")]
+    pub cb_cooldown_secs: u64,
+    /// Probe requests admitted while half-open; all must succeed to close
+    /// the circuit. Default: 3.
+    #[serde(default = "default_cb_half_open_probes")]
+    pub cb_half_open_probes: u32,
+    /// Milliseconds the proxy waits for each AuthZ evaluation before
+    /// applying `authz_on_error`; `0` waits indefinitely. Default: 5000.
+    #[serde(default = "default_authz_timeout_ms")]
+    pub authz_timeout_ms: u64,
+    /// Milliseconds the proxy waits for a route's `transform_hook_url` to
+    /// answer before failing the request with `504`. Default: 5000.
+    #[serde(default = "default_transform_hook_timeout_ms")]
+    pub transform_hook_timeout_ms: u64,
+    /// Extra AuthZ evaluation attempts after a timeout or resolver failure.
+    /// Default: 0.
+    #[serde(default)]
+    pub authz_retries: u32,
+    /// Outcome of a proxy request whose AuthZ evaluation timed out or failed
+    /// on every attempt. An explicit deny from the PDP is always honoured.
+    /// Default: `deny`.
+    #[serde(default)]
+    pub authz_on_error: AuthzErrorPolicy,
+    /// Where AuthZ decisions come from. `allow_all` and `deny_all` run
+    /// without a registered `AuthZResolverClient`. Default: `required`.
+    #[serde(default)]
+    pub authz_mode: AuthzMode,
+    /// Upstreams a single tenant may own; creates beyond it are rejected.
+    /// `0` removes the limit. Default: 1000.
+    #[serde(default = "default_max_upstreams_per_tenant")]
+    pub max_upstreams_per_tenant: usize,
+    /// Routes a single tenant may own, static routes included; creates
+    /// beyond it are rejected. `0` removes the limit. Default: 10000.
+    #[serde(default = "default_max_routes_per_tenant")]
+    pub max_routes_per_tenant: usize,
+    /// Seconds added to the local clock for the time auth plugins sign
+    /// requests with (negative when the host clock runs ahead). Corrects
+    /// known drift that makes upstreams reject SigV4/HMAC timestamps.
+    /// Default: 0.
+    #[serde(default)]
+    pub clock_skew_correction_secs: i64,
+    /// Body of gateway-generated proxy errors (timeouts, `413`, auth
+    /// failures, ...), for matching a team's own error shape.
+    /// Default: unset (`application/problem+json` envelope).
+    #[serde(default)]
+    pub error_body_template: Option<ErrorBodyTemplate>,
+}
+
+/// Template for gateway-generated error bodies.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ErrorBodyTemplate {
+    /// Body text. `{code}` is replaced with the HTTP status, `{message}`
+    /// with the error detail and `{request_id}` with the request's
+    /// `X-Request-Id` (empty when absent).
+    pub body: String,
+    /// `Content-Type` of the rendered body; substituted values are
+    /// JSON-escaped when it is a JSON type. Default: `application/json`.
+    #[serde(default = "default_error_body_content_type")]
+    pub content_type: String,
+}
+
+/// One entry of an `srv_targets` list, as in a DNS SRV record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SrvTarget {
+    pub host: String,
+    pub port: u16,
+    /// Lower values are preferred. Default: 0.
+    #[serde(default)]
+    pub priority: u16,
+    /// Relative share of requests among targets of the same priority; `0`
+    /// receives none unless every target in the priority is `0`. Default: 1.
+    #[serde(default = "default_srv_weight")]
+    pub weight: u16,
+}
+
+/// Behaviour of credential-backed auth plugins during a credstore outage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredStoreUnavailablePolicy {
+    /// Reject the request.
+    #[default]
+    FailClosed,
+    /// Keep serving cached tokens past their TTL until the credstore recovers.
+    /// Requests without a cached token are still rejected.
+    ServeStale,
+}
+
+/// Proxy behaviour when the AuthZ resolver cannot give a decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthzErrorPolicy {
+    /// Let the request through (fail open).
+    Allow,
+    /// Reject the request with `503` and `Retry-After` (fail closed).
+    #[default]
+    Deny,
+}
+
+/// Source of AuthZ decisions for the control and data planes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthzMode {
+    /// Evaluate every request with the `AuthZResolverClient` registered in
+    /// the `ClientHub`; initialization fails without one.
+    #[default]
+    Required,
+    /// Allow every request without consulting a resolver.
+    AllowAll,
+    /// Deny every request without consulting a resolver.
+    DenyAll,
+}
+
+/// Proxy behaviour when no route matches a request.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnmatchedRouteBehavior {
+    /// Reject with `404`.
+    #[default]
+    NotFound,
+    /// Forward to this upstream (owned by the caller's tenant) with the
+    /// request path below the alias and all query parameters.
+    CatchAllUpstreamId(Uuid),
+    /// Answer locally with a fixed response, like a static route.
+    StaticResponse {
+        status: u16,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+        #[serde(default)]
+        body: String,
+    },
+}
+
+/// Proxy behaviour for a request body on a method without body semantics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BodilessMethodBody {
+    /// Forward the body of a `GET`.
+    #[default]
+    Allow,
+    /// Drop the body before forwarding.
+    Strip,
+    /// Reject the request with `400`.
+    Reject,
+}
+
+/// Header names for the rate-limit state: bucket capacity, tokens left, and
+/// seconds until the bucket is full again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitHeaderNames {
+    pub limit: String,
+    pub remaining: String,
+    pub reset: String,
+}
+
+impl Default for RateLimitHeaderNames {
+    fn default() -> Self {
+        Self {
+            limit: "X-RateLimit-Limit".into(),
+            remaining: "X-RateLimit-Remaining".into(),
+            reset: "X-RateLimit-Reset".into(),
+        }
+    }
 }
 
 impl Default for OagwConfig {
@@ -29,9 +409,63 @@ impl Default for OagwConfig {
         Self {
             proxy_timeout_secs: default_proxy_timeout_secs(),
             max_body_size_bytes: default_max_body_size_bytes(),
+            max_url_length: default_max_url_length(),
+            max_query_params: None,
+            bodiless_method_body: BodilessMethodBody::default(),
+            max_response_body_size_bytes: default_max_body_size_bytes(),
+            stream_unframed_responses: false,
             allow_http_upstream: false,
             token_cache_ttl_secs: default_token_cache_ttl_secs(),
             token_cache_capacity: default_token_cache_capacity(),
+            credstore_unavailable: CredStoreUnavailablePolicy::default(),
+            readiness_probe_endpoint: None,
+            pool_idle_timeout_secs: default_pool_idle_timeout_secs(),
+            max_global_concurrency: None,
+            queue_timeout_ms: 0,
+            max_connections_per_host: None,
+            max_concurrent_requests_per_upstream: None,
+            queue_wait_timeout_ms: None,
+            dns_cache_ttl_secs: default_dns_cache_ttl_secs(),
+            dns_override: HashMap::new(),
+            srv_targets: HashMap::new(),
+            dns_retry_max: 0,
+            dns_retry_backoff_ms: default_dns_retry_backoff_ms(),
+            connect_retry_max: 0,
+            connect_retry_backoff_ms: default_connect_retry_backoff_ms(),
+            stream_idle_timeout_ms: None,
+            stream_idle_terminal_event: default_stream_idle_terminal_event(),
+            max_concurrent_streams: None,
+            body_read_idle_timeout_ms: None,
+            log_sampling_seed: None,
+            recent_errors_capacity: default_recent_errors_capacity(),
+            unmatched_route: UnmatchedRouteBehavior::default(),
+            rate_limit_headers: RateLimitHeaderNames::default(),
+            user_agent: default_user_agent(),
+            extra_hop_by_hop_headers: Vec::new(),
+            strip_inbound_headers: Vec::new(),
+            private_response_cache: false,
+            head_from_response_cache: default_head_from_response_cache(),
+            compress_responses: false,
+            compress_min_size_bytes: default_compress_min_size_bytes(),
+            error_status_codes: default_error_status_codes(),
+            auth_config_override_keys: Vec::new(),
+            env_secret_refs: false,
+            file_secret_dir: None,
+            disabled_auth_plugins: Vec::new(),
+            forward_client_ip: false,
+            trusted_proxies: Vec::new(),
+            cb_failure_threshold: default_cb_failure_threshold(),
+            cb_cooldown_secs: default_cb_cooldown_secs(),
+            cb_half_open_probes: default_cb_half_open_probes(),
+            authz_timeout_ms: default_authz_timeout_ms(),
+            transform_hook_timeout_ms: default_transform_hook_timeout_ms(),
+            authz_retries: 0,
+            authz_on_error: AuthzErrorPolicy::default(),
+            authz_mode: AuthzMode::default(),
+            max_upstreams_per_tenant: default_max_upstreams_per_tenant(),
+            max_routes_per_tenant: default_max_routes_per_tenant(),
+            clock_skew_correction_secs: 0,
+            error_body_template: None,
         }
     }
 }
@@ -44,6 +478,86 @@ fn default_max_body_size_bytes() -> usize {
     10 * 1024 * 1024 // 10 MB
 }
 
+fn default_max_url_length() -> usize {
+    8192
+}
+
+fn default_pool_idle_timeout_secs() -> u64 {
+    90
+}
+
+fn default_dns_retry_backoff_ms() -> u64 {
+    100
+}
+
+fn default_connect_retry_backoff_ms() -> u64 {
+    50
+}
+
+fn default_dns_cache_ttl_secs() -> u64 {
+    30
+}
+
+fn default_srv_weight() -> u16 {
+    1
+}
+
+fn default_stream_idle_terminal_event() -> String {
+    crate::infra::proxy::stream_idle::DEFAULT_TERMINAL_EVENT.to_string()
+}
+
+fn default_recent_errors_capacity() -> usize {
+    crate::domain::recent_errors::DEFAULT_RECENT_ERRORS
+}
+
+fn default_head_from_response_cache() -> bool {
+    true
+}
+
+fn default_compress_min_size_bytes() -> usize {
+    1024
+}
+
+fn default_error_status_codes() -> Vec<u16> {
+    (500..=599).collect()
+}
+
+fn default_user_agent() -> String {
+    crate::infra::proxy::service::DEFAULT_USER_AGENT.to_string()
+}
+
+fn default_cb_failure_threshold() -> u32 {
+    5
+}
+
+fn default_cb_cooldown_secs() -> u64 {
+    30
+}
+
+fn default_cb_half_open_probes() -> u32 {
+    3
+}
+
+fn default_authz_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_transform_hook_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_max_upstreams_per_tenant() -> usize {
+    1000
+}
+
+fn default_max_routes_per_tenant() -> usize {
+    10_000
+}
+
+fn default_error_body_content_type() -> String {
+    "application/json".to_string()
+}
+
 fn default_token_cache_ttl_secs() -> u64 {
     300 // 5 minutes — acts as a ceiling; actual TTL is min(this, expires_in − 30s)
 }
@@ -52,18 +566,43 @@ fn default_token_cache_capacity() -> usize {
     10_000
 }
 
-/// Read-only runtime configuration exposed to handlers via `AppState`.
+/// Runtime configuration shared by the REST handlers and the data plane.
 ///
-/// Derived from [`OagwConfig`] at init time.
+/// Derived from [`OagwConfig`] at init time and replaceable while running
+/// through [`AppState::update_runtime_config`](crate::module::AppState::update_runtime_config).
 #[derive(Debug, Clone)]
 pub struct RuntimeConfig {
+    /// Upstream request timeout (`proxy_timeout_secs`).
+    pub request_timeout: Duration,
     pub max_body_size_bytes: usize,
+    pub error_body_template: Option<ErrorBodyTemplate>,
+}
+
+/// Handle through which every reader sees the current [`RuntimeConfig`].
+pub type SharedRuntimeConfig = Arc<ArcSwap<RuntimeConfig>>;
+
+impl RuntimeConfig {
+    /// Check the limits before they are applied.
+    ///
+    /// # Errors
+    /// Returns an error when the request timeout or body limit is zero.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.request_timeout.is_zero() {
+            anyhow::bail!("request timeout must be greater than zero");
+        }
+        if self.max_body_size_bytes == 0 {
+            anyhow::bail!("max_body_size_bytes must be greater than zero");
+        }
+        Ok(())
+    }
 }
 
 impl From<&OagwConfig> for RuntimeConfig {
     fn from(cfg: &OagwConfig) -> Self {
         Self {
+            request_timeout: Duration::from_secs(cfg.proxy_timeout_secs),
             max_body_size_bytes: cfg.max_body_size_bytes,
+            error_body_template: cfg.error_body_template.clone(),
         }
     }
 }
@@ -73,6 +612,7 @@ impl From<&OagwConfig> for RuntimeConfig {
 pub struct TokenCacheConfig {
     pub ttl: Duration,
     pub capacity: usize,
+    pub credstore_unavailable: CredStoreUnavailablePolicy,
 }
 
 impl Default for TokenCacheConfig {
@@ -80,6 +620,7 @@ impl Default for TokenCacheConfig {
         Self {
             ttl: Duration::from_secs(default_token_cache_ttl_secs()),
             capacity: default_token_cache_capacity(),
+            credstore_unavailable: CredStoreUnavailablePolicy::default(),
         }
     }
 }
@@ -89,6 +630,7 @@ impl From<&OagwConfig> for TokenCacheConfig {
         Self {
             ttl: Duration::from_secs(cfg.token_cache_ttl_secs),
             capacity: cfg.token_cache_capacity,
+            credstore_unavailable: cfg.credstore_unavailable,
         }
     }
 }
@@ -98,9 +640,75 @@ impl fmt::Debug for OagwConfig {
         f.debug_struct("OagwConfig")
             .field("proxy_timeout_secs", &self.proxy_timeout_secs)
             .field("max_body_size_bytes", &self.max_body_size_bytes)
+            .field("max_url_length", &self.max_url_length)
+            .field("max_query_params", &self.max_query_params)
+            .field("bodiless_method_body", &self.bodiless_method_body)
+            .field(
+                "max_response_body_size_bytes",
+                &self.max_response_body_size_bytes,
+            )
+            .field("stream_unframed_responses", &self.stream_unframed_responses)
             .field("allow_http_upstream", &self.allow_http_upstream)
             .field("token_cache_ttl_secs", &self.token_cache_ttl_secs)
             .field("token_cache_capacity", &self.token_cache_capacity)
+            .field("credstore_unavailable", &self.credstore_unavailable)
+            .field("readiness_probe_endpoint", &self.readiness_probe_endpoint)
+            .field("pool_idle_timeout_secs", &self.pool_idle_timeout_secs)
+            .field("max_global_concurrency", &self.max_global_concurrency)
+            .field("queue_timeout_ms", &self.queue_timeout_ms)
+            .field("max_connections_per_host", &self.max_connections_per_host)
+            .field(
+                "max_concurrent_requests_per_upstream",
+                &self.max_concurrent_requests_per_upstream,
+            )
+            .field("queue_wait_timeout_ms", &self.queue_wait_timeout_ms)
+            .field("dns_cache_ttl_secs", &self.dns_cache_ttl_secs)
+            .field("dns_override", &self.dns_override)
+            .field("srv_targets", &self.srv_targets)
+            .field("dns_retry_max", &self.dns_retry_max)
+            .field("dns_retry_backoff_ms", &self.dns_retry_backoff_ms)
+            .field("connect_retry_max", &self.connect_retry_max)
+            .field("connect_retry_backoff_ms", &self.connect_retry_backoff_ms)
+            .field("stream_idle_timeout_ms", &self.stream_idle_timeout_ms)
+            .field(
+                "stream_idle_terminal_event",
+                &self.stream_idle_terminal_event,
+            )
+            .field("max_concurrent_streams", &self.max_concurrent_streams)
+            .field("body_read_idle_timeout_ms", &self.body_read_idle_timeout_ms)
+            .field("log_sampling_seed", &self.log_sampling_seed)
+            .field("recent_errors_capacity", &self.recent_errors_capacity)
+            .field("unmatched_route", &self.unmatched_route)
+            .field("rate_limit_headers", &self.rate_limit_headers)
+            .field("user_agent", &self.user_agent)
+            .field("extra_hop_by_hop_headers", &self.extra_hop_by_hop_headers)
+            .field("strip_inbound_headers", &self.strip_inbound_headers)
+            .field("private_response_cache", &self.private_response_cache)
+            .field("head_from_response_cache", &self.head_from_response_cache)
+            .field("compress_responses", &self.compress_responses)
+            .field("compress_min_size_bytes", &self.compress_min_size_bytes)
+            .field("error_status_codes", &self.error_status_codes)
+            .field("auth_config_override_keys", &self.auth_config_override_keys)
+            .field("env_secret_refs", &self.env_secret_refs)
+            .field("file_secret_dir", &self.file_secret_dir)
+            .field("disabled_auth_plugins", &self.disabled_auth_plugins)
+            .field("forward_client_ip", &self.forward_client_ip)
+            .field("trusted_proxies", &self.trusted_proxies)
+            .field("cb_failure_threshold", &self.cb_failure_threshold)
+            .field("cb_cooldown_secs", &self.cb_cooldown_secs)
+            .field("cb_half_open_probes", &self.cb_half_open_probes)
+            .field("authz_timeout_ms", &self.authz_timeout_ms)
+            .field("transform_hook_timeout_ms", &self.transform_hook_timeout_ms)
+            .field("authz_retries", &self.authz_retries)
+            .field("authz_on_error", &self.authz_on_error)
+            .field("authz_mode", &self.authz_mode)
+            .field("max_upstreams_per_tenant", &self.max_upstreams_per_tenant)
+            .field("max_routes_per_tenant", &self.max_routes_per_tenant)
+            .field(
+                "clock_skew_correction_secs",
+                &self.clock_skew_correction_secs,
+            )
+            .field("error_body_template", &self.error_body_template)
             .finish()
     }
 }
@@ -117,6 +725,42 @@ mod tests {
         assert!(debug_output.contains("max_body_size_bytes"));
     }
 
+    #[test]
+    fn unframed_responses_are_buffered_up_to_10mb_by_default() {
+        let config = OagwConfig::default();
+        assert_eq!(config.max_response_body_size_bytes, 10 * 1024 * 1024);
+        assert!(!config.stream_unframed_responses);
+
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"stream_unframed_responses": true}"#).unwrap();
+        assert!(config.stream_unframed_responses);
+    }
+
+    #[test]
+    fn error_body_template_defaults_to_json_content_type() {
+        assert_eq!(OagwConfig::default().error_body_template, None);
+
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"error_body_template":{"body":"{\"err\":\"{message}\"}"}}"#)
+                .unwrap();
+        let template = config.error_body_template.unwrap();
+        assert_eq!(template.body, r#"{"err":"{message}"}"#);
+        assert_eq!(template.content_type, "application/json");
+    }
+
+    #[test]
+    fn max_url_length_defaults_to_8192() {
+        let config = OagwConfig::default();
+        assert_eq!(config.max_url_length, 8192);
+    }
+
+    #[test]
+    fn max_query_params_is_unset_by_default() {
+        assert_eq!(OagwConfig::default().max_query_params, None);
+        let config: OagwConfig = serde_json::from_str(r#"{"max_query_params":50}"#).unwrap();
+        assert_eq!(config.max_query_params, Some(50));
+    }
+
     #[test]
     fn token_cache_ttl_defaults_to_300() {
         let config = OagwConfig::default();
@@ -128,4 +772,414 @@ mod tests {
         let config = OagwConfig::default();
         assert_eq!(config.token_cache_capacity, 10_000);
     }
+
+    #[test]
+    fn runtime_config_rejects_zero_limits() {
+        let config = RuntimeConfig::from(&OagwConfig::default());
+        assert!(config.validate().is_ok());
+        let zero_body = RuntimeConfig {
+            max_body_size_bytes: 0,
+            ..config.clone()
+        };
+        assert!(zero_body.validate().is_err());
+        let zero_timeout = RuntimeConfig {
+            request_timeout: Duration::ZERO,
+            ..config
+        };
+        assert!(zero_timeout.validate().is_err());
+    }
+
+    #[test]
+    fn pool_idle_timeout_defaults_to_90() {
+        let config = OagwConfig::default();
+        assert_eq!(config.pool_idle_timeout_secs, 90);
+    }
+
+    #[test]
+    fn global_concurrency_is_uncapped_by_default() {
+        let config = OagwConfig::default();
+        assert_eq!(config.max_global_concurrency, None);
+        assert_eq!(config.queue_timeout_ms, 0);
+    }
+
+    #[test]
+    fn global_concurrency_parses() {
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"max_global_concurrency":256,"queue_timeout_ms":50}"#)
+                .unwrap();
+        assert_eq!(config.max_global_concurrency, Some(256));
+        assert_eq!(config.queue_timeout_ms, 50);
+    }
+
+    #[test]
+    fn connections_per_host_are_uncapped_by_default() {
+        assert_eq!(OagwConfig::default().max_connections_per_host, None);
+        let config: OagwConfig = serde_json::from_str(r#"{"max_connections_per_host":8}"#).unwrap();
+        assert_eq!(config.max_connections_per_host, Some(8));
+    }
+
+    #[test]
+    fn upstream_concurrency_is_uncapped_by_default() {
+        assert_eq!(
+            OagwConfig::default().max_concurrent_requests_per_upstream,
+            None
+        );
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"max_concurrent_requests_per_upstream":4}"#).unwrap();
+        assert_eq!(config.max_concurrent_requests_per_upstream, Some(4));
+    }
+
+    #[test]
+    fn queue_wait_timeout_is_unset_by_default() {
+        assert_eq!(OagwConfig::default().queue_wait_timeout_ms, None);
+        let config: OagwConfig = serde_json::from_str(r#"{"queue_wait_timeout_ms":250}"#).unwrap();
+        assert_eq!(config.queue_wait_timeout_ms, Some(250));
+    }
+
+    #[test]
+    fn dns_cache_defaults_to_30s_without_overrides() {
+        let config = OagwConfig::default();
+        assert_eq!(config.dns_cache_ttl_secs, 30);
+        assert!(config.dns_override.is_empty());
+    }
+
+    #[test]
+    fn dns_override_parses() {
+        let config: OagwConfig = serde_json::from_str(
+            r#"{"dns_cache_ttl_secs":5,"dns_override":{"api.example.com":"10.0.0.7"}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.dns_cache_ttl_secs, 5);
+        assert_eq!(
+            config.dns_override.get("api.example.com"),
+            Some(&"10.0.0.7".parse::<IpAddr>().unwrap())
+        );
+    }
+
+    #[test]
+    fn srv_targets_default_priority_and_weight() {
+        assert!(OagwConfig::default().srv_targets.is_empty());
+        let config: OagwConfig = serde_json::from_str(
+            r#"{"srv_targets":{"api.internal":[
+                {"host":"a.internal","port":8443},
+                {"host":"b.internal","port":8443,"priority":10,"weight":5}
+            ]}}"#,
+        )
+        .unwrap();
+        let targets = &config.srv_targets["api.internal"];
+        assert_eq!((targets[0].priority, targets[0].weight), (0, 1));
+        assert_eq!((targets[1].priority, targets[1].weight), (10, 5));
+    }
+
+    #[test]
+    fn dns_retry_is_off_by_default() {
+        let config = OagwConfig::default();
+        assert_eq!(config.dns_retry_max, 0);
+        assert_eq!(config.dns_retry_backoff_ms, 100);
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"dns_retry_max":2,"dns_retry_backoff_ms":50}"#).unwrap();
+        assert_eq!(config.dns_retry_max, 2);
+        assert_eq!(config.dns_retry_backoff_ms, 50);
+    }
+
+    #[test]
+    fn connect_retry_is_off_by_default() {
+        let config = OagwConfig::default();
+        assert_eq!(config.connect_retry_max, 0);
+        assert_eq!(config.connect_retry_backoff_ms, 50);
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"connect_retry_max":3,"connect_retry_backoff_ms":20}"#)
+                .unwrap();
+        assert_eq!(config.connect_retry_max, 3);
+        assert_eq!(config.connect_retry_backoff_ms, 20);
+    }
+
+    #[test]
+    fn body_read_idle_timeout_is_off_by_default() {
+        assert_eq!(OagwConfig::default().body_read_idle_timeout_ms, None);
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"body_read_idle_timeout_ms":5000}"#).unwrap();
+        assert_eq!(config.body_read_idle_timeout_ms, Some(5000));
+    }
+
+    #[test]
+    fn stream_idle_timeout_is_off_by_default() {
+        let config = OagwConfig::default();
+        assert_eq!(config.stream_idle_timeout_ms, None);
+        assert!(
+            config
+                .stream_idle_terminal_event
+                .starts_with("event: error\n")
+        );
+    }
+
+    #[test]
+    fn max_concurrent_streams_is_unset_by_default() {
+        assert_eq!(OagwConfig::default().max_concurrent_streams, None);
+        let config: OagwConfig = serde_json::from_str(r#"{"max_concurrent_streams":100}"#).unwrap();
+        assert_eq!(config.max_concurrent_streams, Some(100));
+    }
+
+    #[test]
+    fn log_sampling_seed_parses() {
+        assert_eq!(OagwConfig::default().log_sampling_seed, None);
+        let config: OagwConfig = serde_json::from_str(r#"{"log_sampling_seed":42}"#).unwrap();
+        assert_eq!(config.log_sampling_seed, Some(42));
+    }
+
+    #[test]
+    fn recent_errors_capacity_defaults_to_50() {
+        assert_eq!(OagwConfig::default().recent_errors_capacity, 50);
+        let config: OagwConfig = serde_json::from_str(r#"{"recent_errors_capacity":0}"#).unwrap();
+        assert_eq!(config.recent_errors_capacity, 0);
+    }
+
+    #[test]
+    fn bodiless_method_body_parses() {
+        assert_eq!(
+            OagwConfig::default().bodiless_method_body,
+            BodilessMethodBody::Allow
+        );
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"bodiless_method_body":"reject"}"#).unwrap();
+        assert_eq!(config.bodiless_method_body, BodilessMethodBody::Reject);
+    }
+
+    #[test]
+    fn unmatched_route_parses_each_mode() {
+        assert_eq!(
+            OagwConfig::default().unmatched_route,
+            UnmatchedRouteBehavior::NotFound
+        );
+
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"unmatched_route":"not_found"}"#).unwrap();
+        assert_eq!(config.unmatched_route, UnmatchedRouteBehavior::NotFound);
+
+        let id = Uuid::new_v4();
+        let config: OagwConfig = serde_json::from_str(&format!(
+            r#"{{"unmatched_route":{{"catch_all_upstream_id":"{id}"}}}}"#
+        ))
+        .unwrap();
+        assert_eq!(
+            config.unmatched_route,
+            UnmatchedRouteBehavior::CatchAllUpstreamId(id)
+        );
+
+        let config: OagwConfig = serde_json::from_str(
+            r#"{"unmatched_route":{"static_response":{"status":404,"body":"nothing here"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.unmatched_route,
+            UnmatchedRouteBehavior::StaticResponse {
+                status: 404,
+                headers: HashMap::new(),
+                body: "nothing here".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn rate_limit_header_names_override_individually() {
+        assert_eq!(
+            OagwConfig::default().rate_limit_headers.remaining,
+            "X-RateLimit-Remaining"
+        );
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"rate_limit_headers":{"remaining":"RateLimit-Remaining"}}"#)
+                .unwrap();
+        assert_eq!(config.rate_limit_headers.limit, "X-RateLimit-Limit");
+        assert_eq!(config.rate_limit_headers.remaining, "RateLimit-Remaining");
+    }
+
+    #[test]
+    fn user_agent_defaults_to_gateway_version() {
+        assert_eq!(
+            OagwConfig::default().user_agent,
+            concat!("oagw/", env!("CARGO_PKG_VERSION"))
+        );
+        let config: OagwConfig = serde_json::from_str(r#"{"user_agent":"acme-gw/2"}"#).unwrap();
+        assert_eq!(config.user_agent, "acme-gw/2");
+    }
+
+    #[test]
+    fn credstore_unavailable_defaults_to_fail_closed() {
+        let config = OagwConfig::default();
+        assert_eq!(
+            config.credstore_unavailable,
+            CredStoreUnavailablePolicy::FailClosed
+        );
+    }
+
+    #[test]
+    fn credstore_unavailable_parses_snake_case() {
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"credstore_unavailable":"serve_stale"}"#).unwrap();
+        assert_eq!(
+            config.credstore_unavailable,
+            CredStoreUnavailablePolicy::ServeStale
+        );
+    }
+
+    #[test]
+    fn extra_hop_by_hop_headers_default_to_none() {
+        assert!(OagwConfig::default().extra_hop_by_hop_headers.is_empty());
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"extra_hop_by_hop_headers":["X-Trace-Hop"]}"#).unwrap();
+        assert_eq!(config.extra_hop_by_hop_headers, vec!["X-Trace-Hop"]);
+    }
+
+    #[test]
+    fn strip_inbound_headers_default_to_none() {
+        assert!(OagwConfig::default().strip_inbound_headers.is_empty());
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"strip_inbound_headers":["X-Debug"]}"#).unwrap();
+        assert_eq!(config.strip_inbound_headers, vec!["X-Debug"]);
+    }
+
+    #[test]
+    fn private_response_cache_is_off_by_default() {
+        assert!(!OagwConfig::default().private_response_cache);
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"private_response_cache":true}"#).unwrap();
+        assert!(config.private_response_cache);
+    }
+
+    #[test]
+    fn head_from_response_cache_is_on_by_default() {
+        assert!(OagwConfig::default().head_from_response_cache);
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"head_from_response_cache":false}"#).unwrap();
+        assert!(!config.head_from_response_cache);
+    }
+
+    #[test]
+    fn response_compression_is_off_by_default() {
+        let config = OagwConfig::default();
+        assert!(!config.compress_responses);
+        assert_eq!(config.compress_min_size_bytes, 1024);
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"compress_responses":true,"compress_min_size_bytes":256}"#)
+                .unwrap();
+        assert!(config.compress_responses);
+        assert_eq!(config.compress_min_size_bytes, 256);
+    }
+
+    #[test]
+    fn error_status_codes_default_to_5xx() {
+        let codes = OagwConfig::default().error_status_codes;
+        assert_eq!(codes.len(), 100);
+        assert!(codes.contains(&500) && codes.contains(&599) && !codes.contains(&404));
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"error_status_codes":[404,500,502]}"#).unwrap();
+        assert_eq!(config.error_status_codes, vec![404, 500, 502]);
+    }
+
+    #[test]
+    fn auth_config_overrides_are_disabled_by_default() {
+        assert!(OagwConfig::default().auth_config_override_keys.is_empty());
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"auth_config_override_keys":["secret_ref"]}"#).unwrap();
+        assert_eq!(config.auth_config_override_keys, vec!["secret_ref"]);
+    }
+
+    #[test]
+    fn env_and_file_secret_refs_are_disabled_by_default() {
+        let config = OagwConfig::default();
+        assert!(!config.env_secret_refs);
+        assert!(config.file_secret_dir.is_none());
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"env_secret_refs":true,"file_secret_dir":"/run/secrets"}"#)
+                .unwrap();
+        assert!(config.env_secret_refs);
+        assert_eq!(config.file_secret_dir.as_deref(), Some("/run/secrets"));
+    }
+
+    #[test]
+    fn disabled_auth_plugins_default_to_empty() {
+        assert!(OagwConfig::default().disabled_auth_plugins.is_empty());
+        let config: OagwConfig = serde_json::from_str(
+            r#"{"disabled_auth_plugins":["gts.x.core.oagw.auth_plugin.v1~x.core.oagw.noop.v1"]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.disabled_auth_plugins.len(), 1);
+    }
+
+    #[test]
+    fn forward_client_ip_is_off_by_default() {
+        let config = OagwConfig::default();
+        assert!(!config.forward_client_ip);
+        assert!(config.trusted_proxies.is_empty());
+        let config: OagwConfig = serde_json::from_str(
+            r#"{"forward_client_ip":true,"trusted_proxies":["10.0.0.1","::1"]}"#,
+        )
+        .unwrap();
+        assert!(config.forward_client_ip);
+        assert_eq!(
+            config.trusted_proxies,
+            vec![
+                "10.0.0.1".parse::<IpAddr>().unwrap(),
+                "::1".parse::<IpAddr>().unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn circuit_breaker_defaults() {
+        let config = OagwConfig::default();
+        assert_eq!(config.cb_failure_threshold, 5);
+        assert_eq!(config.cb_cooldown_secs, 30);
+        assert_eq!(config.cb_half_open_probes, 3);
+        let config: OagwConfig = serde_json::from_str(r#"{"cb_failure_threshold":0}"#).unwrap();
+        assert_eq!(config.cb_failure_threshold, 0);
+        assert_eq!(config.cb_cooldown_secs, 30);
+    }
+
+    #[test]
+    fn authz_failure_handling_defaults_to_fail_closed() {
+        let config = OagwConfig::default();
+        assert_eq!(config.authz_timeout_ms, 5000);
+        assert_eq!(config.authz_retries, 0);
+        assert_eq!(config.authz_on_error, AuthzErrorPolicy::Deny);
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"authz_timeout_ms":250,"authz_on_error":"allow"}"#).unwrap();
+        assert_eq!(config.authz_timeout_ms, 250);
+        assert_eq!(config.authz_on_error, AuthzErrorPolicy::Allow);
+    }
+
+    #[test]
+    fn authz_mode_defaults_to_required() {
+        assert_eq!(OagwConfig::default().authz_mode, AuthzMode::Required);
+        for (raw, mode) in [
+            ("required", AuthzMode::Required),
+            ("allow_all", AuthzMode::AllowAll),
+            ("deny_all", AuthzMode::DenyAll),
+        ] {
+            let config: OagwConfig =
+                serde_json::from_str(&format!(r#"{{"authz_mode":"{raw}"}}"#)).unwrap();
+            assert_eq!(config.authz_mode, mode);
+        }
+        assert!(serde_json::from_str::<OagwConfig>(r#"{"authz_mode":"maybe"}"#).is_err());
+    }
+
+    #[test]
+    fn tenant_quota_defaults() {
+        let config = OagwConfig::default();
+        assert_eq!(config.max_upstreams_per_tenant, 1000);
+        assert_eq!(config.max_routes_per_tenant, 10_000);
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"max_upstreams_per_tenant":0,"max_routes_per_tenant":50}"#)
+                .unwrap();
+        assert_eq!(config.max_upstreams_per_tenant, 0);
+        assert_eq!(config.max_routes_per_tenant, 50);
+    }
+
+    #[test]
+    fn clock_skew_correction_defaults_to_zero_and_accepts_negative() {
+        assert_eq!(OagwConfig::default().clock_skew_correction_secs, 0);
+        let config: OagwConfig =
+            serde_json::from_str(r#"{"clock_skew_correction_secs":-45}"#).unwrap();
+        assert_eq!(config.clock_skew_correction_secs, -45);
+    }
 }
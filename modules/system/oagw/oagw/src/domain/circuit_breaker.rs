@@ -0,0 +1,420 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::domain::clock::{Clock, SystemClock};
+use crate::domain::error::DomainError;
+use crate::domain::model::CircuitBreakerConfig;
+use dashmap::DashMap;
+use modkit_macros::domain_model;
+use uuid::Uuid;
+
+/// Thresholds a circuit is evaluated against: the gateway-wide `cb_*`
+/// settings, with any per-upstream overrides applied.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitSettings {
+    /// Consecutive failures that open the circuit; `0` disables the breaker.
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before admitting probes.
+    pub cooldown: Duration,
+    /// Requests admitted while half-open; all must succeed to close.
+    pub half_open_probes: u32,
+}
+
+impl CircuitSettings {
+    /// Apply an upstream's overrides on top of these defaults.
+    #[must_use]
+    pub fn with_overrides(self, overrides: Option<&CircuitBreakerConfig>) -> Self {
+        let Some(o) = overrides else {
+            return self;
+        };
+        Self {
+            failure_threshold: o.failure_threshold.unwrap_or(self.failure_threshold),
+            cooldown: o.cooldown_secs.map_or(self.cooldown, Duration::from_secs),
+            half_open_probes: o.half_open_probes.unwrap_or(self.half_open_probes),
+        }
+    }
+}
+
+/// How a proxied request went, from the circuit's point of view.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallOutcome {
+    Success,
+    /// Connection failure, timeout, or a 5xx gateway-class response.
+    Failure,
+    /// Neither: the request never reached the upstream or failed on the
+    /// client's side.
+    Ignored,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, Copy)]
+enum Circuit {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen { in_flight: u32, successes: u32 },
+}
+
+/// Observable state of one upstream's circuit.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitStatus {
+    Closed {
+        failures: u32,
+    },
+    /// Requests are rejected for roughly `retry_after_secs` more seconds.
+    Open {
+        retry_after_secs: u64,
+    },
+    HalfOpen,
+}
+
+/// Per-upstream circuit breakers (CLOSED → OPEN → HALF-OPEN → CLOSED).
+///
+/// State is local to this gateway instance.
+#[domain_model]
+pub struct CircuitBreaker {
+    circuits: DashMap<Uuid, Circuit>,
+    clock: Arc<dyn Clock>,
+}
+
+impl CircuitBreaker {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    /// Time cooldowns with `clock` instead of the system clock.
+    #[must_use]
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            circuits: DashMap::new(),
+            clock,
+        }
+    }
+
+    /// Admit a request to `upstream_id`, moving an open circuit whose
+    /// cooldown has elapsed to half-open.
+    ///
+    /// # Errors
+    /// Returns `DomainError::CircuitBreakerOpen` while the circuit is open or
+    /// all half-open probe slots are taken.
+    pub fn try_acquire(
+        &self,
+        upstream_id: Uuid,
+        settings: &CircuitSettings,
+        instance_uri: &str,
+    ) -> Result<(), DomainError> {
+        if settings.failure_threshold == 0 {
+            return Ok(());
+        }
+        let mut circuit = self
+            .circuits
+            .entry(upstream_id)
+            .or_insert(Circuit::Closed { failures: 0 });
+        self.admit(&mut circuit, upstream_id, settings, instance_uri)
+    }
+
+    /// What `try_acquire` would answer, without taking a half-open probe
+    /// slot or changing the circuit.
+    ///
+    /// # Errors
+    /// Returns `DomainError::CircuitBreakerOpen` when `try_acquire` would.
+    pub fn check(
+        &self,
+        upstream_id: Uuid,
+        settings: &CircuitSettings,
+        instance_uri: &str,
+    ) -> Result<(), DomainError> {
+        if settings.failure_threshold == 0 {
+            return Ok(());
+        }
+        let mut circuit = self
+            .circuits
+            .get(&upstream_id)
+            .map_or(Circuit::Closed { failures: 0 }, |c| *c);
+        self.admit(&mut circuit, upstream_id, settings, instance_uri)
+    }
+
+    fn admit(
+        &self,
+        circuit: &mut Circuit,
+        upstream_id: Uuid,
+        settings: &CircuitSettings,
+        instance_uri: &str,
+    ) -> Result<(), DomainError> {
+        let now = self.clock.instant();
+        if let Circuit::Open { until } = *circuit
+            && now >= until
+        {
+            *circuit = Circuit::HalfOpen {
+                in_flight: 0,
+                successes: 0,
+            };
+        }
+        let retry_after_secs = match circuit {
+            Circuit::Closed { .. } => return Ok(()),
+            Circuit::HalfOpen {
+                in_flight,
+                successes,
+            } if *in_flight + *successes < settings.half_open_probes => {
+                *in_flight += 1;
+                return Ok(());
+            }
+            Circuit::HalfOpen { .. } => 1,
+            Circuit::Open { until } => until.duration_since(now).as_secs_f64().ceil() as u64,
+        };
+        Err(DomainError::CircuitBreakerOpen {
+            detail: format!("circuit breaker open for upstream {upstream_id}"),
+            instance: instance_uri.to_string(),
+            retry_after_secs: retry_after_secs.max(1),
+        })
+    }
+
+    /// Current state of `upstream_id`'s circuit; closed if it never saw
+    /// traffic.
+    pub fn status(&self, upstream_id: Uuid) -> CircuitStatus {
+        match self.circuits.get(&upstream_id).as_deref() {
+            None => CircuitStatus::Closed { failures: 0 },
+            Some(Circuit::Closed { failures }) => CircuitStatus::Closed {
+                failures: *failures,
+            },
+            Some(Circuit::Open { until }) => CircuitStatus::Open {
+                retry_after_secs: until
+                    .saturating_duration_since(self.clock.instant())
+                    .as_secs_f64()
+                    .ceil() as u64,
+            },
+            Some(Circuit::HalfOpen { .. }) => CircuitStatus::HalfOpen,
+        }
+    }
+
+    /// Force `upstream_id`'s circuit closed with no recorded failures.
+    pub fn reset(&self, upstream_id: Uuid) {
+        self.circuits.remove(&upstream_id);
+    }
+
+    /// Record the outcome of a request admitted by `try_acquire`.
+    pub fn record(&self, upstream_id: Uuid, settings: &CircuitSettings, outcome: CallOutcome) {
+        if settings.failure_threshold == 0 {
+            return;
+        }
+        let Some(mut circuit) = self.circuits.get_mut(&upstream_id) else {
+            return;
+        };
+        let open = Circuit::Open {
+            until: self.clock.instant() + settings.cooldown,
+        };
+        *circuit = match (*circuit, outcome) {
+            (Circuit::Closed { .. }, CallOutcome::Success) => Circuit::Closed { failures: 0 },
+            (Circuit::Closed { failures }, CallOutcome::Failure) => {
+                if failures + 1 >= settings.failure_threshold {
+                    open
+                } else {
+                    Circuit::Closed {
+                        failures: failures + 1,
+                    }
+                }
+            }
+            (Circuit::HalfOpen { .. }, CallOutcome::Failure) => open,
+            (
+                Circuit::HalfOpen {
+                    in_flight,
+                    successes,
+                },
+                CallOutcome::Success,
+            ) => {
+                if successes + 1 >= settings.half_open_probes {
+                    Circuit::Closed { failures: 0 }
+                } else {
+                    Circuit::HalfOpen {
+                        in_flight: in_flight.saturating_sub(1),
+                        successes: successes + 1,
+                    }
+                }
+            }
+            (
+                Circuit::HalfOpen {
+                    in_flight,
+                    successes,
+                },
+                CallOutcome::Ignored,
+            ) => Circuit::HalfOpen {
+                in_flight: in_flight.saturating_sub(1),
+                successes,
+            },
+            (state, _) => state,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(failure_threshold: u32, cooldown: Duration) -> CircuitSettings {
+        CircuitSettings {
+            failure_threshold,
+            cooldown,
+            half_open_probes: 1,
+        }
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let cb = CircuitBreaker::new();
+        let id = Uuid::new_v4();
+        let s = settings(2, Duration::from_secs(30));
+
+        cb.try_acquire(id, &s, "/test").unwrap();
+        cb.record(id, &s, CallOutcome::Failure);
+        cb.try_acquire(id, &s, "/test").unwrap();
+        cb.record(id, &s, CallOutcome::Failure);
+
+        let err = cb.try_acquire(id, &s, "/test").unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::CircuitBreakerOpen {
+                retry_after_secs: 30,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn reset_closes_open_circuit_immediately() {
+        let cb = CircuitBreaker::new();
+        let id = Uuid::new_v4();
+        let s = settings(1, Duration::from_secs(30));
+
+        assert_eq!(cb.status(id), CircuitStatus::Closed { failures: 0 });
+        cb.try_acquire(id, &s, "/test").unwrap();
+        cb.record(id, &s, CallOutcome::Failure);
+        assert_eq!(
+            cb.status(id),
+            CircuitStatus::Open {
+                retry_after_secs: 30
+            }
+        );
+        assert!(cb.try_acquire(id, &s, "/test").is_err());
+
+        cb.reset(id);
+        assert_eq!(cb.status(id), CircuitStatus::Closed { failures: 0 });
+        cb.try_acquire(id, &s, "/test").unwrap();
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let cb = CircuitBreaker::new();
+        let id = Uuid::new_v4();
+        let s = settings(2, Duration::from_secs(30));
+
+        for outcome in [
+            CallOutcome::Failure,
+            CallOutcome::Success,
+            CallOutcome::Failure,
+        ] {
+            cb.try_acquire(id, &s, "/test").unwrap();
+            cb.record(id, &s, outcome);
+        }
+        assert!(cb.try_acquire(id, &s, "/test").is_ok());
+    }
+
+    #[test]
+    fn half_open_probe_closes_or_reopens() {
+        let cb = CircuitBreaker::new();
+        let id = Uuid::new_v4();
+        let s = settings(1, Duration::ZERO);
+
+        cb.try_acquire(id, &s, "/test").unwrap();
+        cb.record(id, &s, CallOutcome::Failure);
+
+        // Cooldown elapsed: one probe is admitted, a second is rejected.
+        cb.try_acquire(id, &s, "/test").unwrap();
+        assert!(cb.try_acquire(id, &s, "/test").is_err());
+        cb.record(id, &s, CallOutcome::Success);
+        assert!(cb.try_acquire(id, &s, "/test").is_ok());
+        cb.record(id, &s, CallOutcome::Failure);
+
+        // A failed probe reopens the circuit for a full cooldown.
+        let s = settings(1, Duration::from_secs(30));
+        cb.try_acquire(id, &s, "/test").unwrap();
+        cb.record(id, &s, CallOutcome::Failure);
+        assert!(
+            cb.try_acquire(id, &s, "/test")
+                .unwrap_err()
+                .to_string()
+                .contains("open")
+        );
+    }
+
+    #[test]
+    fn check_does_not_take_a_probe_slot() {
+        let cb = CircuitBreaker::new();
+        let id = Uuid::new_v4();
+        let s = settings(1, Duration::ZERO);
+
+        cb.try_acquire(id, &s, "/test").unwrap();
+        cb.record(id, &s, CallOutcome::Failure);
+
+        cb.check(id, &s, "/test").unwrap();
+        cb.check(id, &s, "/test").unwrap();
+        cb.try_acquire(id, &s, "/test").unwrap();
+        assert!(cb.check(id, &s, "/test").is_err());
+    }
+
+    #[test]
+    fn cooldown_elapses_on_clock_advance() {
+        let clock = crate::domain::test_support::MockClock::default();
+        let cb = CircuitBreaker::with_clock(Arc::new(clock.clone()));
+        let id = Uuid::new_v4();
+        let s = settings(1, Duration::from_secs(30));
+
+        cb.try_acquire(id, &s, "/test").unwrap();
+        cb.record(id, &s, CallOutcome::Failure);
+
+        clock.advance(Duration::from_secs(29));
+        assert_eq!(
+            cb.status(id),
+            CircuitStatus::Open {
+                retry_after_secs: 1
+            }
+        );
+        assert!(cb.try_acquire(id, &s, "/test").is_err());
+
+        clock.advance(Duration::from_secs(1));
+        cb.try_acquire(id, &s, "/test").unwrap();
+        assert_eq!(cb.status(id), CircuitStatus::HalfOpen);
+    }
+
+    #[test]
+    fn zero_threshold_disables_breaker() {
+        let cb = CircuitBreaker::new();
+        let id = Uuid::new_v4();
+        let s = settings(0, Duration::from_secs(30));
+        for _ in 0..10 {
+            cb.try_acquire(id, &s, "/test").unwrap();
+            cb.record(id, &s, CallOutcome::Failure);
+        }
+        assert!(cb.try_acquire(id, &s, "/test").is_ok());
+    }
+
+    #[test]
+    fn overrides_take_precedence_over_defaults() {
+        let defaults = CircuitSettings {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+            half_open_probes: 3,
+        };
+        assert_eq!(defaults.with_overrides(None), defaults);
+        let merged = defaults.with_overrides(Some(&CircuitBreakerConfig {
+            failure_threshold: Some(1),
+            cooldown_secs: None,
+            half_open_probes: Some(2),
+        }));
+        assert_eq!(merged.failure_threshold, 1);
+        assert_eq!(merged.cooldown, Duration::from_secs(30));
+        assert_eq!(merged.half_open_probes, 2);
+    }
+}
@@ -0,0 +1,29 @@
+//! Time source for the gateway's time-dependent state: TTL caches,
+//! circuit-breaker cooldowns, rate-limit refills and request signing read
+//! time through a [`Clock`] so that tests can move it deterministically.
+//! Timeouts run on tokio's timer, which tests pause and advance instead.
+
+use std::time::{Instant, SystemTime};
+
+/// Time source for the data plane, injectable for tests.
+pub trait Clock: Send + Sync {
+    /// Wall-clock time, for timestamps that leave the process (signing).
+    fn now(&self) -> SystemTime;
+
+    /// Monotonic time, for measuring elapsed durations (TTLs, cooldowns,
+    /// token refills).
+    fn instant(&self) -> Instant;
+}
+
+/// [`Clock`] backed by the local system clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::domain::error::DomainError;
+use dashmap::DashMap;
+use modkit_macros::domain_model;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, oneshot};
+use uuid::Uuid;
+
+/// Gateway-wide cap on in-flight proxy requests.
+///
+/// Requests beyond the cap wait up to `queue_timeout` for a free slot and
+/// are shed with `DomainError::GatewayOverloaded` once it elapses. A zero
+/// timeout sheds immediately.
+#[domain_model]
+pub struct ConcurrencyLimiter {
+    /// `None` when no cap is configured.
+    slots: Option<Arc<Semaphore>>,
+    queue_timeout: Duration,
+}
+
+impl ConcurrencyLimiter {
+    #[must_use]
+    pub fn new(max_in_flight: Option<usize>, queue_timeout: Duration) -> Self {
+        Self {
+            slots: max_in_flight.map(|n| Arc::new(Semaphore::new(n))),
+            queue_timeout,
+        }
+    }
+
+    /// A limiter that lets every request through.
+    #[must_use]
+    pub fn unlimited() -> Self {
+        Self::new(None, Duration::ZERO)
+    }
+
+    /// Reserve an in-flight slot. The slot is released when the returned
+    /// permit is dropped; `None` means no cap is configured.
+    ///
+    /// # Errors
+    /// Returns `DomainError::GatewayOverloaded` when no slot frees up within
+    /// the queue timeout.
+    pub async fn acquire(
+        &self,
+        instance_uri: &str,
+    ) -> Result<Option<OwnedSemaphorePermit>, DomainError> {
+        let Some(slots) = &self.slots else {
+            return Ok(None);
+        };
+        let permit = match slots.clone().try_acquire_owned() {
+            Ok(permit) => Some(permit),
+            Err(_) if self.queue_timeout.is_zero() => None,
+            Err(_) => tokio::time::timeout(self.queue_timeout, slots.clone().acquire_owned())
+                .await
+                .ok()
+                .and_then(Result::ok),
+        };
+        permit
+            .map(Some)
+            .ok_or_else(|| DomainError::GatewayOverloaded {
+                detail: "gateway is at its concurrent request limit".into(),
+                instance: instance_uri.to_string(),
+            })
+    }
+}
+
+/// Cap on in-flight requests to each upstream host (`host:port`).
+///
+/// Each in-flight request occupies one upstream connection, so this bounds
+/// the simultaneous connections to a host. Requests beyond the cap wait
+/// for a free slot; the caller bounds the wait.
+#[domain_model]
+pub struct HostConcurrencyLimiter {
+    /// `None` when no cap is configured.
+    max_per_host: Option<usize>,
+    hosts: DashMap<String, Arc<Semaphore>>,
+}
+
+impl HostConcurrencyLimiter {
+    #[must_use]
+    pub fn new(max_per_host: Option<usize>) -> Self {
+        Self {
+            max_per_host,
+            hosts: DashMap::new(),
+        }
+    }
+
+    /// A limiter that lets every request through.
+    #[must_use]
+    pub fn unlimited() -> Self {
+        Self::new(None)
+    }
+
+    /// Wait for a slot on `host`. The slot is released when the returned
+    /// permit is dropped; `None` means no cap is configured.
+    pub async fn acquire(&self, host: &str) -> Option<OwnedSemaphorePermit> {
+        let max = self.max_per_host?;
+        let slots = self
+            .hosts
+            .entry(host.to_string())
+            .or_insert_with(|| Arc::new(Semaphore::new(max)))
+            .clone();
+        slots.acquire_owned().await.ok()
+    }
+}
+
+/// Cap on in-flight requests to each upstream, shared fairly between
+/// tenants.
+///
+/// Requests beyond the cap queue per tenant, and a freed slot goes to the
+/// tenants with queued requests in turn, so a tenant with a deep backlog
+/// cannot starve one that sends a request now and then. Within a tenant,
+/// requests are served in arrival order. The caller bounds the wait.
+#[domain_model]
+pub struct UpstreamConcurrencyLimiter {
+    /// `None` when no cap is configured.
+    max_per_upstream: Option<usize>,
+    upstreams: DashMap<Uuid, Arc<FairQueue>>,
+}
+
+/// Slots of one upstream and the requests waiting for them.
+#[domain_model]
+struct FairQueue {
+    max: usize,
+    state: Mutex<FairQueueState>,
+}
+
+#[domain_model]
+#[derive(Default)]
+struct FairQueueState {
+    in_flight: usize,
+    /// Tenants with queued requests, next to be served first.
+    turns: VecDeque<Uuid>,
+    waiting: HashMap<Uuid, VecDeque<oneshot::Sender<UpstreamSlot>>>,
+}
+
+impl FairQueueState {
+    /// The next tenant's oldest waiter; the tenant goes to the back of the
+    /// line while it has more.
+    fn next_waiter(&mut self) -> Option<oneshot::Sender<UpstreamSlot>> {
+        let tenant = self.turns.pop_front()?;
+        let waiters = self.waiting.get_mut(&tenant)?;
+        let waiter = waiters.pop_front();
+        if waiters.is_empty() {
+            self.waiting.remove(&tenant);
+        } else {
+            self.turns.push_back(tenant);
+        }
+        waiter
+    }
+}
+
+/// An in-flight slot on an upstream, released (or handed to the next
+/// waiting request) on drop.
+#[domain_model]
+pub struct UpstreamSlot {
+    queue: Option<Arc<FairQueue>>,
+}
+
+impl Drop for UpstreamSlot {
+    fn drop(&mut self) {
+        let Some(queue) = self.queue.take() else {
+            return;
+        };
+        loop {
+            let waiter = {
+                let mut state = queue.state.lock().unwrap_or_else(|e| e.into_inner());
+                match state.next_waiter() {
+                    Some(waiter) => waiter,
+                    None => {
+                        state.in_flight -= 1;
+                        return;
+                    }
+                }
+            };
+            // Sent without the lock held: a waiter that gave up hands the
+            // slot back, and the next one is tried.
+            match waiter.send(Self {
+                queue: Some(queue.clone()),
+            }) {
+                Ok(()) => return,
+                Err(mut unclaimed) => unclaimed.queue = None,
+            }
+        }
+    }
+}
+
+impl UpstreamConcurrencyLimiter {
+    #[must_use]
+    pub fn new(max_per_upstream: Option<usize>) -> Self {
+        Self {
+            max_per_upstream,
+            upstreams: DashMap::new(),
+        }
+    }
+
+    /// A limiter that lets every request through.
+    #[must_use]
+    pub fn unlimited() -> Self {
+        Self::new(None)
+    }
+
+    /// Wait for a slot on `upstream_id` on behalf of `tenant_id`. `None`
+    /// means no cap is configured.
+    pub async fn acquire(&self, upstream_id: Uuid, tenant_id: Uuid) -> Option<UpstreamSlot> {
+        let max = self.max_per_upstream?;
+        let queue = self
+            .upstreams
+            .entry(upstream_id)
+            .or_insert_with(|| {
+                Arc::new(FairQueue {
+                    max,
+                    state: Mutex::default(),
+                })
+            })
+            .clone();
+        let granted = {
+            let mut state = queue.state.lock().unwrap_or_else(|e| e.into_inner());
+            // Queued requests go first, so a free slot is only taken
+            // directly when nobody waits.
+            if state.in_flight < queue.max && state.turns.is_empty() {
+                state.in_flight += 1;
+                None
+            } else {
+                let (tx, rx) = oneshot::channel();
+                let waiters = state.waiting.entry(tenant_id).or_default();
+                if waiters.is_empty() {
+                    state.turns.push_back(tenant_id);
+                }
+                waiters.push_back(tx);
+                Some(rx)
+            }
+        };
+        match granted {
+            None => Some(UpstreamSlot { queue: Some(queue) }),
+            Some(rx) => rx.await.ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn unlimited_never_sheds() {
+        let limiter = ConcurrencyLimiter::unlimited();
+        let permits: Vec<_> =
+            futures_util::future::join_all((0..100).map(|_| limiter.acquire("/test"))).await;
+        assert!(permits.iter().all(|p| matches!(p, Ok(None))));
+    }
+
+    #[tokio::test]
+    async fn sheds_immediately_beyond_cap_without_queue() {
+        let limiter = ConcurrencyLimiter::new(Some(2), Duration::ZERO);
+        let _a = limiter.acquire("/test").await.unwrap();
+        let _b = limiter.acquire("/test").await.unwrap();
+        let err = limiter.acquire("/test").await.unwrap_err();
+        assert!(matches!(err, DomainError::GatewayOverloaded { .. }));
+    }
+
+    #[tokio::test]
+    async fn releasing_a_permit_frees_a_slot() {
+        let limiter = ConcurrencyLimiter::new(Some(1), Duration::ZERO);
+        let held = limiter.acquire("/test").await.unwrap();
+        assert!(limiter.acquire("/test").await.is_err());
+        drop(held);
+        assert!(limiter.acquire("/test").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn queued_request_proceeds_when_slot_frees_in_time() {
+        let limiter = Arc::new(ConcurrencyLimiter::new(Some(1), Duration::from_secs(2)));
+        let held = limiter.acquire("/test").await.unwrap();
+
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire("/test").await.map(|p| p.is_some()) })
+        };
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        drop(held);
+
+        assert!(waiter.await.unwrap().unwrap());
+    }
+
+    #[tokio::test]
+    async fn host_limiter_queues_beyond_cap_per_host() {
+        let limiter = Arc::new(HostConcurrencyLimiter::new(Some(1)));
+        let held = limiter.acquire("a:443").await;
+        assert!(held.is_some());
+        // Other hosts have their own slots.
+        assert!(limiter.acquire("b:443").await.is_some());
+
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire("a:443").await.is_some() })
+        };
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!waiter.is_finished());
+        drop(held);
+        assert!(waiter.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn upstream_limiter_shares_slots_fairly_between_tenants() {
+        let limiter = Arc::new(UpstreamConcurrencyLimiter::new(Some(1)));
+        let upstream = Uuid::new_v4();
+        let (busy, quiet) = (Uuid::new_v4(), Uuid::new_v4());
+        let held = limiter.acquire(upstream, busy).await;
+        assert!(held.is_some());
+
+        // The busy tenant queues its whole backlog before the quiet one
+        // shows up.
+        let (served_tx, mut served) = tokio::sync::mpsc::unbounded_channel();
+        for tenant in std::iter::repeat_n(busy, 8).chain(std::iter::repeat_n(quiet, 4)) {
+            let limiter = limiter.clone();
+            let served_tx = served_tx.clone();
+            tokio::spawn(async move {
+                let _slot = limiter.acquire(upstream, tenant).await;
+                served_tx.send(tenant).unwrap();
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            });
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        drop(held);
+
+        let mut order = Vec::new();
+        for _ in 0..12 {
+            order.push(served.recv().await.unwrap());
+        }
+        let quiet_in_first_eight = order[..8].iter().filter(|t| **t == quiet).count();
+        assert_eq!(quiet_in_first_eight, 4, "{order:?}");
+    }
+
+    #[tokio::test]
+    async fn upstream_slot_of_abandoned_waiter_goes_to_the_next() {
+        let limiter = Arc::new(UpstreamConcurrencyLimiter::new(Some(1)));
+        let upstream = Uuid::new_v4();
+        let held = limiter.acquire(upstream, Uuid::new_v4()).await;
+
+        let abandoned = tokio::time::timeout(
+            Duration::from_millis(50),
+            limiter.acquire(upstream, Uuid::new_v4()),
+        )
+        .await;
+        assert!(abandoned.is_err());
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire(upstream, Uuid::new_v4()).await.is_some() })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        drop(held);
+        assert!(waiter.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn queued_request_is_shed_after_timeout() {
+        let limiter = ConcurrencyLimiter::new(Some(1), Duration::from_millis(200));
+        let _held = limiter.acquire("/test").await.unwrap();
+        let err = limiter.acquire("/test").await.unwrap_err();
+        assert!(matches!(err, DomainError::GatewayOverloaded { .. }));
+    }
+}
@@ -19,6 +19,50 @@ pub enum DomainError {
     #[error("upstream '{alias}' is disabled")]
     UpstreamDisabled { alias: String },
 
+    /// The upstream is in maintenance mode; traffic is answered by the gateway.
+    #[error("{detail}")]
+    UpstreamMaintenance {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
+    /// The upstream's circuit breaker is open; requests fail fast until the
+    /// cooldown elapses.
+    #[error("{detail}")]
+    CircuitBreakerOpen {
+        detail: String,
+        instance: String,
+        retry_after_secs: u64,
+    },
+
+    /// Every endpoint of a multi-endpoint upstream fails its health check.
+    #[error("{detail}")]
+    NoHealthyTarget {
+        detail: String,
+        instance: String,
+        /// Endpoints configured on the upstream.
+        total: usize,
+        /// Endpoints passing health checks when the request was selected.
+        healthy: usize,
+    },
+
+    /// The gateway-wide concurrent request limit is reached.
+    #[error("{detail}")]
+    GatewayOverloaded { detail: String, instance: String },
+
+    /// The auth plugin the request needs is disabled by the operator.
+    #[error("{detail}")]
+    PluginDisabled { detail: String, instance: String },
+
+    /// No AuthZ decision could be obtained and `authz_on_error` is `deny`.
+    #[error("{detail}")]
+    AuthzUnavailable {
+        detail: String,
+        instance: String,
+        retry_after_secs: u64,
+    },
+
     #[error("internal: {message}")]
     Internal { message: String },
 
@@ -37,11 +81,43 @@ pub enum DomainError {
     #[error("{detail}")]
     PayloadTooLarge { detail: String, instance: String },
 
+    #[error("{detail}")]
+    UnsupportedMediaType { detail: String, instance: String },
+
+    /// The request URL exceeds the configured maximum length.
+    #[error("{detail}")]
+    UriTooLong { detail: String, instance: String },
+
+    /// An outbound request header exceeds the upstream's
+    /// `max_header_size_bytes`.
+    #[error("{detail}")]
+    HeaderTooLarge { detail: String, instance: String },
+
+    /// The upstream does not accept the request method.
+    #[error("{detail}")]
+    MethodNotAllowed {
+        detail: String,
+        instance: String,
+        /// Methods the upstream accepts, for the `Allow` header.
+        allowed: Vec<String>,
+    },
+
+    /// The request body does not satisfy the route's `request_schema`.
+    #[error("{detail}")]
+    SchemaViolation {
+        detail: String,
+        instance: String,
+        /// Each violation as `(JSON pointer into the body, message)`.
+        violations: Vec<(String, String)>,
+    },
+
     #[error("{detail}")]
     RateLimitExceeded {
         detail: String,
         instance: String,
         retry_after_secs: Option<u64>,
+        /// Rate-limit status headers for the rejection, as `(name, value)`.
+        headers: Vec<(String, String)>,
     },
 
     #[error("{detail}")]
@@ -59,9 +135,18 @@ pub enum DomainError {
     #[error("{detail}")]
     RequestTimeout { detail: String, instance: String },
 
+    /// The client stopped sending its request body for longer than the
+    /// gateway's body read idle timeout.
+    #[error("{detail}")]
+    ClientTimeout { detail: String, instance: String },
+
     /// The request was denied by the authorization policy.
     #[error("access forbidden: {detail}")]
     Forbidden { detail: String },
+
+    /// The tenant already owns as many upstreams or routes as it may.
+    #[error("quota exceeded: {detail}")]
+    QuotaExceeded { detail: String },
 }
 
 impl DomainError {
@@ -106,6 +191,43 @@ impl DomainError {
             detail: detail.into(),
         }
     }
+
+    /// Short `snake_case` name of the error kind, e.g. for recording.
+    #[must_use]
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::NotFound { .. } => "not_found",
+            Self::Conflict { .. } => "conflict",
+            Self::Validation { .. } => "validation",
+            Self::UpstreamDisabled { .. } => "upstream_disabled",
+            Self::UpstreamMaintenance { .. } => "upstream_maintenance",
+            Self::CircuitBreakerOpen { .. } => "circuit_breaker_open",
+            Self::NoHealthyTarget { .. } => "no_healthy_target",
+            Self::GatewayOverloaded { .. } => "gateway_overloaded",
+            Self::PluginDisabled { .. } => "plugin_disabled",
+            Self::AuthzUnavailable { .. } => "authz_unavailable",
+            Self::Internal { .. } => "internal",
+            Self::MissingTargetHost { .. } => "missing_target_host",
+            Self::InvalidTargetHost { .. } => "invalid_target_host",
+            Self::UnknownTargetHost { .. } => "unknown_target_host",
+            Self::AuthenticationFailed { .. } => "authentication_failed",
+            Self::PayloadTooLarge { .. } => "payload_too_large",
+            Self::UnsupportedMediaType { .. } => "unsupported_media_type",
+            Self::UriTooLong { .. } => "uri_too_long",
+            Self::HeaderTooLarge { .. } => "header_too_large",
+            Self::MethodNotAllowed { .. } => "method_not_allowed",
+            Self::SchemaViolation { .. } => "schema_violation",
+            Self::RateLimitExceeded { .. } => "rate_limit_exceeded",
+            Self::SecretNotFound { .. } => "secret_not_found",
+            Self::DownstreamError { .. } => "downstream_error",
+            Self::ProtocolError { .. } => "protocol_error",
+            Self::ConnectionTimeout { .. } => "connection_timeout",
+            Self::RequestTimeout { .. } => "request_timeout",
+            Self::ClientTimeout { .. } => "client_timeout",
+            Self::Forbidden { .. } => "forbidden",
+            Self::QuotaExceeded { .. } => "quota_exceeded",
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -17,6 +17,7 @@ pub const PROXY_SCHEMA: &str = "gts.x.core.oagw.proxy.v1~";
 
 // -- Builtin protocol instances --
 pub const HTTP_PROTOCOL_ID: &str = "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1";
+pub const H2C_PROTOCOL_ID: &str = "gts.x.core.oagw.protocol.v1~x.core.oagw.h2c.v1";
 pub const GRPC_PROTOCOL_ID: &str = "gts.x.core.oagw.protocol.v1~x.core.oagw.grpc.v1";
 
 // -- Builtin auth plugin instances --
@@ -28,6 +29,14 @@ pub const OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID: &str =
     "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.oauth2_client_cred.v1";
 pub const OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID: &str =
     "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.oauth2_client_cred_basic.v1";
+pub const TOKEN_PASSTHROUGH_AUTH_PLUGIN_ID: &str =
+    "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.token_passthrough.v1";
+pub const REQUEST_TOKEN_AUTH_PLUGIN_ID: &str =
+    "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.request_token.v1";
+pub const ROTATING_APIKEY_AUTH_PLUGIN_ID: &str =
+    "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.rotating_apikey.v1";
+pub const WEBHOOK_SIGNATURE_AUTH_PLUGIN_ID: &str =
+    "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.webhook_signature.v1";
 
 // -- Builtin guard plugin instances --
 pub const TIMEOUT_GUARD_PLUGIN_ID: &str = "gts.x.core.oagw.guard_plugin.v1~x.core.oagw.timeout.v1";
@@ -0,0 +1,114 @@
+//! `{{ctx.<field>}}` placeholders in request header rule values, filled from
+//! the caller's `SecurityContext` (e.g. `X-Tenant-Id: {{ctx.subject_tenant_id}}`).
+
+use std::borrow::Cow;
+
+use modkit_security::SecurityContext;
+
+/// Context fields a header template may reference. The bearer token is
+/// deliberately absent: credentials reach upstreams through auth plugins.
+pub(crate) const CONTEXT_FIELDS: &[&str] = &["subject_id", "subject_tenant_id", "subject_type"];
+
+/// Check that every placeholder in `template` is closed and names a known
+/// context field.
+///
+/// # Errors
+/// A message naming the offending placeholder.
+pub(crate) fn validate(template: &str) -> Result<(), String> {
+    for field in placeholders(template) {
+        let field = field?;
+        if !CONTEXT_FIELDS.contains(&field) {
+            return Err(format!(
+                "unknown context field '{{{{ctx.{field}}}}}'; expected one of: {}",
+                CONTEXT_FIELDS.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Replace each placeholder in `template` with the value from `ctx`. A
+/// missing optional field (e.g. no subject type) renders as an empty string;
+/// unknown fields are left verbatim as they are rejected on write.
+pub(crate) fn render<'a>(template: &'a str, ctx: &SecurityContext) -> Cow<'a, str> {
+    if !template.contains("{{") {
+        return Cow::Borrowed(template);
+    }
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let raw = &rest[start..start + 2 + end + 2];
+        match after[..end].trim().strip_prefix("ctx.") {
+            Some("subject_id") => out.push_str(&ctx.subject_id().to_string()),
+            Some("subject_tenant_id") => out.push_str(&ctx.subject_tenant_id().to_string()),
+            Some("subject_type") => out.push_str(ctx.subject_type().unwrap_or_default()),
+            _ => out.push_str(raw),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+/// The field names referenced by `template`, or an error for a placeholder
+/// that is unterminated or not of the form `ctx.<field>`.
+fn placeholders(template: &str) -> impl Iterator<Item = Result<&str, String>> {
+    let mut rest = template;
+    std::iter::from_fn(move || {
+        let start = rest.find("{{")?;
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            rest = "";
+            return Some(Err(format!("unterminated placeholder in '{template}'")));
+        };
+        let inner = after[..end].trim();
+        rest = &after[end + 2..];
+        Some(inner.strip_prefix("ctx.").ok_or_else(|| {
+            format!("placeholder '{{{{{inner}}}}}' must reference a context field as 'ctx.<field>'")
+        }))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn render_substitutes_context_fields() {
+        let tenant = Uuid::new_v4();
+        let subject = Uuid::new_v4();
+        let ctx = SecurityContext::builder()
+            .subject_id(subject)
+            .subject_tenant_id(tenant)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            render("{{ctx.subject_tenant_id}}", &ctx),
+            tenant.to_string()
+        );
+        assert_eq!(
+            render("sub={{ ctx.subject_id }};type={{ctx.subject_type}}", &ctx),
+            format!("sub={subject};type=")
+        );
+        assert!(matches!(render("static", &ctx), Cow::Borrowed("static")));
+    }
+
+    #[test]
+    fn validate_rejects_unknown_and_malformed_placeholders() {
+        assert!(validate("plain").is_ok());
+        assert!(validate("t={{ctx.subject_tenant_id}},s={{ctx.subject_id}}").is_ok());
+
+        let err = validate("{{ctx.bearer_token}}").unwrap_err();
+        assert!(err.contains("unknown context field"), "{err}");
+        assert!(validate("{{subject_id}}").is_err());
+        assert!(validate("{{ctx.subject_id").is_err());
+    }
+}
@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use dashmap::DashMap;
+use modkit_macros::domain_model;
+use uuid::Uuid;
+
+use crate::domain::model::UpstreamLatency;
+
+/// Response times kept per upstream. Older samples are overwritten, so
+/// memory stays constant and the percentiles follow recent traffic.
+const WINDOW: usize = 1024;
+
+/// Ring buffer of the most recent response times, in microseconds.
+#[domain_model]
+#[derive(Debug)]
+struct Window {
+    samples: Vec<u64>,
+    next: usize,
+}
+
+impl Window {
+    fn push(&mut self, micros: u64) {
+        if self.samples.len() < WINDOW {
+            self.samples.push(micros);
+        } else {
+            self.samples[self.next] = micros;
+        }
+        self.next = (self.next + 1) % WINDOW;
+    }
+}
+
+/// Rolling per-upstream response time percentiles for the status endpoint.
+///
+/// State is local to this gateway instance.
+#[domain_model]
+pub struct LatencyTracker {
+    windows: DashMap<Uuid, Window>,
+}
+
+impl LatencyTracker {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            windows: DashMap::new(),
+        }
+    }
+
+    /// Record one response from `upstream_id` that took `elapsed`.
+    pub fn record(&self, upstream_id: Uuid, elapsed: Duration) {
+        let micros = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        self.windows
+            .entry(upstream_id)
+            .or_insert_with(|| Window {
+                samples: Vec::with_capacity(WINDOW),
+                next: 0,
+            })
+            .push(micros);
+    }
+
+    /// p50/p95/p99 over each upstream's window, ordered by upstream ID.
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<UpstreamLatency> {
+        let mut latencies: Vec<UpstreamLatency> = self
+            .windows
+            .iter()
+            .map(|entry| {
+                let mut sorted = entry.samples.clone();
+                sorted.sort_unstable();
+                UpstreamLatency {
+                    upstream_id: *entry.key(),
+                    samples: sorted.len(),
+                    p50_us: percentile(&sorted, 50),
+                    p95_us: percentile(&sorted, 95),
+                    p99_us: percentile(&sorted, 99),
+                }
+            })
+            .collect();
+        latencies.sort_by_key(|l| l.upstream_id);
+        latencies
+    }
+}
+
+impl Default for LatencyTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Nearest-rank percentile of a non-empty sorted slice.
+fn percentile(sorted: &[u64], p: usize) -> u64 {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn within(actual: u64, expected: u64, tolerance: u64) -> bool {
+        actual.abs_diff(expected) <= tolerance
+    }
+
+    #[test]
+    fn reports_percentiles_of_known_latencies() {
+        let tracker = LatencyTracker::new();
+        let id = Uuid::new_v4();
+        // 1..=1000 ms, recorded out of order.
+        for ms in (1..=1000u64).rev() {
+            tracker.record(id, Duration::from_millis(ms));
+        }
+
+        let [latency] = tracker.snapshot().try_into().unwrap();
+        assert_eq!(latency.upstream_id, id);
+        assert_eq!(latency.samples, 1000);
+        assert!(within(latency.p50_us, 500_000, 1_000), "{latency:?}");
+        assert!(within(latency.p95_us, 950_000, 1_000), "{latency:?}");
+        assert!(within(latency.p99_us, 990_000, 1_000), "{latency:?}");
+    }
+
+    #[test]
+    fn window_keeps_only_recent_samples() {
+        let tracker = LatencyTracker::new();
+        let id = Uuid::new_v4();
+        for _ in 0..WINDOW {
+            tracker.record(id, Duration::from_secs(5));
+        }
+        // A full window of fast responses displaces the slow ones.
+        for _ in 0..WINDOW {
+            tracker.record(id, Duration::from_millis(10));
+        }
+
+        let latency = &tracker.snapshot()[0];
+        assert_eq!(latency.samples, WINDOW);
+        assert_eq!(latency.p99_us, 10_000);
+    }
+
+    #[test]
+    fn upstreams_are_tracked_separately() {
+        let tracker = LatencyTracker::new();
+        let (fast, slow) = (Uuid::new_v4(), Uuid::new_v4());
+        for _ in 0..10 {
+            tracker.record(fast, Duration::from_millis(2));
+            tracker.record(slow, Duration::from_millis(200));
+        }
+
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        let p50 = |id| {
+            snapshot
+                .iter()
+                .find(|l| l.upstream_id == id)
+                .unwrap()
+                .p50_us
+        };
+        assert_eq!(p50(fast), 2_000);
+        assert_eq!(p50(slow), 200_000);
+    }
+}
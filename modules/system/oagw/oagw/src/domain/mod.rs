@@ -1,8 +1,15 @@
+pub(crate) mod circuit_breaker;
+pub(crate) mod clock;
+pub(crate) mod concurrency;
 pub(crate) mod error;
 pub(crate) mod gts_helpers;
+pub(crate) mod header_template;
+pub(crate) mod latency;
 pub(crate) mod model;
 pub(crate) mod plugin;
 pub(crate) mod rate_limit;
+pub(crate) mod readiness;
+pub(crate) mod recent_errors;
 pub(crate) mod repo;
 pub(crate) mod services;
 pub(crate) mod type_catalog;
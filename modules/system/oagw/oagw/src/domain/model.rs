@@ -1,8 +1,13 @@
 use std::collections::HashMap;
+use std::time::SystemTime;
 
 use modkit_macros::domain_model;
 use uuid::Uuid;
 
+use crate::domain::circuit_breaker::CircuitStatus;
+use crate::domain::gts_helpers::{GRPC_PROTOCOL_ID, H2C_PROTOCOL_ID};
+use crate::domain::rate_limit::RateLimitStatus;
+
 // ---------------------------------------------------------------------------
 // Shared enums
 // ---------------------------------------------------------------------------
@@ -31,6 +36,38 @@ pub enum Scheme {
     Grpc,
 }
 
+/// Wire protocol OAGW speaks to an upstream, derived from `Upstream::protocol`.
+///
+/// Accepts both the short names (`http1`, `h2c`, `grpc`) and the builtin
+/// protocol GTS identifiers. Unknown values fall back to HTTP/1.1.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UpstreamProtocol {
+    #[default]
+    Http1,
+    /// HTTP/2 with prior knowledge (no TLS/ALPN negotiation on cleartext).
+    H2c,
+    /// gRPC over HTTP/2: preserves `content-type: application/grpc` and trailers.
+    Grpc,
+}
+
+impl UpstreamProtocol {
+    #[must_use]
+    pub fn from_protocol(protocol: &str) -> Self {
+        match protocol {
+            "h2c" | H2C_PROTOCOL_ID => Self::H2c,
+            "grpc" | GRPC_PROTOCOL_ID => Self::Grpc,
+            _ => Self::Http1,
+        }
+    }
+
+    /// Whether the upstream is reached over HTTP/2.
+    #[must_use]
+    pub fn is_h2(self) -> bool {
+        matches!(self, Self::H2c | Self::Grpc)
+    }
+}
+
 #[domain_model]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Endpoint {
@@ -122,6 +159,25 @@ pub struct RateLimitConfig {
     pub cost: u32,
 }
 
+/// Rate limit that replaces an upstream's own limit for requests made by
+/// one tenant.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TenantRateLimit {
+    pub upstream_id: Uuid,
+    pub tenant_id: Uuid,
+    pub rate_limit: RateLimitConfig,
+}
+
+/// Auth applied to a tenant's requests through upstreams that configure
+/// none of their own.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TenantAuthDefault {
+    pub tenant_id: Uuid,
+    pub auth: AuthConfig,
+}
+
 #[domain_model]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum RateLimitAlgorithm {
@@ -173,6 +229,105 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+// ---------------------------------------------------------------------------
+// RetryOnBody
+// ---------------------------------------------------------------------------
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryOnBody {
+    pub matcher: BodyMatcher,
+    pub max_retries: u32,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub enum BodyMatcher {
+    JsonPointer {
+        pointer: String,
+        value: serde_json::Value,
+    },
+    Regex {
+        pattern: String,
+    },
+}
+
+// ---------------------------------------------------------------------------
+// ApiVersion
+// ---------------------------------------------------------------------------
+
+/// Version sent upstream in `header` (default `Api-Version`) and, when set,
+/// in the `query_param` query parameter.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiVersion {
+    pub value: String,
+    pub header: Option<String>,
+    pub query_param: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// AuthRule
+// ---------------------------------------------------------------------------
+
+/// Auth chosen for requests matching `condition`; `None` sends them without
+/// upstream auth. The first matching rule of a route wins.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthRule {
+    pub condition: AuthCondition,
+    pub auth: Option<AuthConfig>,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthCondition {
+    /// Request path (after the alias) is `prefix` or continues it with `/`.
+    PathPrefix { prefix: String },
+    /// Header `name` is present, and equals `value` when set.
+    Header { name: String, value: Option<String> },
+}
+
+// ---------------------------------------------------------------------------
+// BodyRewriteRule
+// ---------------------------------------------------------------------------
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BodyRewriteRule {
+    pub find: String,
+    pub replace: String,
+    pub regex: bool,
+    pub content_types: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// KeepaliveConfig
+// ---------------------------------------------------------------------------
+
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KeepaliveConfig {
+    pub tcp_idle_secs: Option<u64>,
+    pub tcp_interval_secs: Option<u64>,
+    pub tcp_probe_count: Option<u32>,
+    pub h2_ping_interval_secs: Option<u64>,
+}
+
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+/// Per-upstream circuit breaker overrides. Unset fields fall back to the
+/// gateway-wide `cb_*` settings.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CircuitBreakerConfig {
+    pub failure_threshold: Option<u32>,
+    pub cooldown_secs: Option<u64>,
+    pub half_open_probes: Option<u32>,
+}
+
 // ---------------------------------------------------------------------------
 // PluginsConfig
 // ---------------------------------------------------------------------------
@@ -198,6 +353,35 @@ pub enum HttpMethod {
     Patch,
 }
 
+impl HttpMethod {
+    /// Parse a request method, case-insensitively. Returns `None` for
+    /// methods routes cannot match.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_uppercase().as_str() {
+            // HEAD is served by GET routes (RFC 9110 §9.3.2); the data plane
+            // forwards it as HEAD and strips any body.
+            "GET" | "HEAD" => Some(Self::Get),
+            "POST" => Some(Self::Post),
+            "PUT" => Some(Self::Put),
+            "DELETE" => Some(Self::Delete),
+            "PATCH" => Some(Self::Patch),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Post => "POST",
+            Self::Put => "PUT",
+            Self::Delete => "DELETE",
+            Self::Patch => "PATCH",
+        }
+    }
+}
+
 #[domain_model]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum PathSuffixMode {
@@ -206,6 +390,16 @@ pub enum PathSuffixMode {
     Append,
 }
 
+/// How a trailing slash on the outbound path is treated.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingSlash {
+    #[default]
+    Preserve,
+    Strip,
+    Add,
+}
+
 #[domain_model]
 #[derive(Debug, Clone, PartialEq)]
 pub struct HttpMatch {
@@ -213,6 +407,9 @@ pub struct HttpMatch {
     pub path: String,
     pub query_allowlist: Vec<String>,
     pub path_suffix_mode: PathSuffixMode,
+    /// Condition on the request body. Routes that set it can only be matched
+    /// once the body has been buffered.
+    pub match_body: Option<BodyMatcher>,
 }
 
 #[domain_model]
@@ -229,6 +426,29 @@ pub struct MatchRules {
     pub grpc: Option<GrpcMatch>,
 }
 
+/// What a matched route does with the request.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum RouteKind {
+    /// Forward to the route's upstream.
+    #[default]
+    Proxy,
+    /// Answer locally with a fixed response. Static routes have no upstream
+    /// (`upstream_id` is nil) and are matched against the full proxy path.
+    Static {
+        status: u16,
+        headers: HashMap<String, String>,
+        body: String,
+    },
+}
+
+impl RouteKind {
+    #[must_use]
+    pub fn is_static(&self) -> bool {
+        matches!(self, Self::Static { .. })
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Domain entities
 // ---------------------------------------------------------------------------
@@ -245,6 +465,56 @@ pub struct Route {
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
+    pub skip_authz: bool,
+    pub timeout_secs: Option<u64>,
+    pub status_remap: HashMap<u16, u16>,
+    pub allowed_response_content_types: Vec<String>,
+    pub allowed_request_content_types: Vec<String>,
+    pub log_sample_rate: Option<f64>,
+    pub request_schema: Option<serde_json::Value>,
+    pub body_rewrite: Vec<BodyRewriteRule>,
+    pub auth_rules: Vec<AuthRule>,
+    pub strip_query_params: Vec<String>,
+    pub strip_inbound_headers: Vec<String>,
+    pub idempotency_ttl_secs: Option<u64>,
+    pub cache_responses: bool,
+    pub negative_cache_ttl_secs: Option<u64>,
+    pub response_header_allowlist: Option<Vec<String>>,
+    pub max_total_duration_ms: Option<u64>,
+    pub max_body_size_bytes: Option<u64>,
+    pub max_outbound_calls: Option<u32>,
+    pub coerce_content_type: Option<String>,
+    pub api_version: Option<ApiVersion>,
+    pub body_transformers: Vec<String>,
+    pub log_redact_paths: Vec<String>,
+    pub transform_hook_url: Option<String>,
+    pub deprecated: bool,
+    pub sunset: Option<String>,
+    pub kind: RouteKind,
+}
+
+/// Source of the key used to pin requests to one upstream endpoint, derived
+/// from `Upstream::sticky_by` (`header:<name>` or `tenant`).
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StickyBy {
+    /// Value of the named request header (lowercased name).
+    Header(String),
+    /// The calling tenant's ID.
+    Tenant,
+}
+
+impl StickyBy {
+    /// Parse a `sticky_by` value. Returns `None` for unknown forms.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        if value == "tenant" {
+            return Some(Self::Tenant);
+        }
+        let name = value.strip_prefix("header:")?.trim();
+        (!name.is_empty() && http::HeaderName::from_bytes(name.as_bytes()).is_ok())
+            .then(|| Self::Header(name.to_ascii_lowercase()))
+    }
 }
 
 #[domain_model]
@@ -261,6 +531,46 @@ pub struct Upstream {
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
     pub tags: Vec<String>,
+    pub maintenance: bool,
+    pub maintenance_message: Option<String>,
+    pub retry_on_body: Option<RetryOnBody>,
+    pub force_chunked: bool,
+    pub failover: Vec<Endpoint>,
+    pub timeout_secs: Option<u64>,
+    pub keepalive: Option<KeepaliveConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub user_agent: Option<String>,
+    pub sticky_by: Option<String>,
+    /// Methods proxied to this upstream; empty allows every method.
+    pub allowed_methods: Vec<HttpMethod>,
+    /// Trailing-slash policy applied to the outbound path.
+    pub trailing_slash: TrailingSlash,
+    /// Largest outbound header (name plus value) in bytes; `None` is no limit.
+    pub max_header_size_bytes: Option<u64>,
+    /// Lowest TLS version accepted from the upstream, `1.2` or `1.3`.
+    pub tls_min_version: Option<String>,
+    /// TLS cipher suites accepted from the upstream; empty accepts any.
+    pub tls_cipher_suites: Vec<String>,
+    /// Name the upstream certificate is verified against (and sent as SNI)
+    /// instead of the endpoint host.
+    pub tls_server_name: Option<String>,
+    /// Request header set to the milliseconds left of the request's time
+    /// budget when it is sent upstream.
+    pub budget_header: Option<String>,
+}
+
+impl Upstream {
+    /// Wire protocol used to reach this upstream.
+    #[must_use]
+    pub fn wire_protocol(&self) -> UpstreamProtocol {
+        UpstreamProtocol::from_protocol(&self.protocol)
+    }
+
+    /// Sticky key source for consistent-hash endpoint selection, if any.
+    #[must_use]
+    pub fn sticky_by(&self) -> Option<StickyBy> {
+        self.sticky_by.as_deref().and_then(StickyBy::parse)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -296,6 +606,23 @@ pub struct CreateUpstreamRequest {
     pub rate_limit: Option<RateLimitConfig>,
     pub tags: Vec<String>,
     pub enabled: bool,
+    pub maintenance: bool,
+    pub maintenance_message: Option<String>,
+    pub retry_on_body: Option<RetryOnBody>,
+    pub force_chunked: bool,
+    pub failover: Vec<Endpoint>,
+    pub timeout_secs: Option<u64>,
+    pub keepalive: Option<KeepaliveConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub user_agent: Option<String>,
+    pub sticky_by: Option<String>,
+    pub allowed_methods: Vec<HttpMethod>,
+    pub trailing_slash: TrailingSlash,
+    pub max_header_size_bytes: Option<u64>,
+    pub tls_min_version: Option<String>,
+    pub tls_cipher_suites: Vec<String>,
+    pub tls_server_name: Option<String>,
+    pub budget_header: Option<String>,
 }
 
 #[domain_model]
@@ -310,6 +637,23 @@ pub struct UpdateUpstreamRequest {
     pub rate_limit: Option<RateLimitConfig>,
     pub tags: Option<Vec<String>>,
     pub enabled: Option<bool>,
+    pub maintenance: Option<bool>,
+    pub maintenance_message: Option<String>,
+    pub retry_on_body: Option<RetryOnBody>,
+    pub force_chunked: Option<bool>,
+    pub failover: Option<Vec<Endpoint>>,
+    pub timeout_secs: Option<u64>,
+    pub keepalive: Option<KeepaliveConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub user_agent: Option<String>,
+    pub sticky_by: Option<String>,
+    pub allowed_methods: Option<Vec<HttpMethod>>,
+    pub trailing_slash: Option<TrailingSlash>,
+    pub max_header_size_bytes: Option<u64>,
+    pub tls_min_version: Option<String>,
+    pub tls_cipher_suites: Option<Vec<String>>,
+    pub tls_server_name: Option<String>,
+    pub budget_header: Option<String>,
 }
 
 #[domain_model]
@@ -322,6 +666,32 @@ pub struct CreateRouteRequest {
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
+    pub skip_authz: bool,
+    pub timeout_secs: Option<u64>,
+    pub status_remap: HashMap<u16, u16>,
+    pub allowed_response_content_types: Vec<String>,
+    pub allowed_request_content_types: Vec<String>,
+    pub log_sample_rate: Option<f64>,
+    pub request_schema: Option<serde_json::Value>,
+    pub body_rewrite: Vec<BodyRewriteRule>,
+    pub auth_rules: Vec<AuthRule>,
+    pub strip_query_params: Vec<String>,
+    pub strip_inbound_headers: Vec<String>,
+    pub idempotency_ttl_secs: Option<u64>,
+    pub cache_responses: bool,
+    pub negative_cache_ttl_secs: Option<u64>,
+    pub response_header_allowlist: Option<Vec<String>>,
+    pub max_total_duration_ms: Option<u64>,
+    pub max_body_size_bytes: Option<u64>,
+    pub max_outbound_calls: Option<u32>,
+    pub coerce_content_type: Option<String>,
+    pub api_version: Option<ApiVersion>,
+    pub body_transformers: Vec<String>,
+    pub log_redact_paths: Vec<String>,
+    pub transform_hook_url: Option<String>,
+    pub deprecated: bool,
+    pub sunset: Option<String>,
+    pub kind: RouteKind,
 }
 
 #[domain_model]
@@ -333,4 +703,219 @@ pub struct UpdateRouteRequest {
     pub tags: Option<Vec<String>>,
     pub priority: Option<i32>,
     pub enabled: Option<bool>,
+    pub skip_authz: Option<bool>,
+    pub timeout_secs: Option<u64>,
+    pub status_remap: Option<HashMap<u16, u16>>,
+    pub allowed_response_content_types: Option<Vec<String>>,
+    pub allowed_request_content_types: Option<Vec<String>>,
+    pub log_sample_rate: Option<f64>,
+    pub request_schema: Option<serde_json::Value>,
+    pub body_rewrite: Option<Vec<BodyRewriteRule>>,
+    pub auth_rules: Option<Vec<AuthRule>>,
+    pub strip_query_params: Option<Vec<String>>,
+    pub strip_inbound_headers: Option<Vec<String>>,
+    pub idempotency_ttl_secs: Option<u64>,
+    pub cache_responses: Option<bool>,
+    pub negative_cache_ttl_secs: Option<u64>,
+    pub response_header_allowlist: Option<Vec<String>>,
+    pub max_total_duration_ms: Option<u64>,
+    pub max_body_size_bytes: Option<u64>,
+    pub max_outbound_calls: Option<u32>,
+    pub coerce_content_type: Option<String>,
+    pub api_version: Option<ApiVersion>,
+    pub body_transformers: Option<Vec<String>>,
+    pub log_redact_paths: Option<Vec<String>>,
+    pub transform_hook_url: Option<String>,
+    pub deprecated: Option<bool>,
+    pub sunset: Option<String>,
+    pub kind: Option<RouteKind>,
+}
+
+// ---------------------------------------------------------------------------
+// Config validation
+// ---------------------------------------------------------------------------
+
+/// Upstreams and routes to be checked together without being created.
+#[domain_model]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigSnapshot {
+    pub upstreams: Vec<CreateUpstreamRequest>,
+    pub routes: Vec<CreateRouteRequest>,
+}
+
+/// A problem found in a [`ConfigSnapshot`]: the offending entry, e.g.
+/// `upstreams[2]` or `routes[0]`, and what is wrong with it.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    pub path: String,
+    pub detail: String,
+}
+
+// ---------------------------------------------------------------------------
+// Health
+// ---------------------------------------------------------------------------
+
+/// Liveness/readiness snapshot of the gateway.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GatewayStatus {
+    /// The process is up and serving.
+    pub live: bool,
+    /// All readiness checks passed; `reasons` is empty.
+    pub ready: bool,
+    /// Human-readable reasons the gateway is not ready.
+    pub reasons: Vec<String>,
+    /// Recent response times of each upstream that served traffic.
+    pub upstream_latencies: Vec<UpstreamLatency>,
+}
+
+/// Rolling response time percentiles of one upstream, over its most recent
+/// responses.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamLatency {
+    pub upstream_id: Uuid,
+    /// Responses the percentiles are computed over.
+    pub samples: usize,
+    pub p50_us: u64,
+    pub p95_us: u64,
+    pub p99_us: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Diagnostics
+// ---------------------------------------------------------------------------
+
+/// Outcome of a test connection against an upstream.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UpstreamTestResult {
+    /// The auth plugin resolved its credentials (`true` without auth).
+    pub credential_resolved: bool,
+    /// The auth plugin prepared the probe request (`true` without auth).
+    pub auth_applied: bool,
+    /// HTTP status the upstream answered the probe with.
+    pub upstream_status: Option<u16>,
+    /// Round-trip time of the probe request in milliseconds.
+    pub latency_ms: Option<u64>,
+    /// Why the test stopped short, if it did.
+    pub error: Option<String>,
+}
+
+/// Circuit-breaker and rate-limit state held for an upstream by this
+/// gateway instance.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamRuntimeState {
+    pub upstream_id: Uuid,
+    pub circuit: CircuitStatus,
+    /// Upstream-level buckets by key: `upstream:{id}` and the tenant
+    /// policy buckets `upstream:{id}:tenant:{tenant_id}`.
+    pub rate_limits: Vec<(String, RateLimitStatus)>,
+}
+
+/// A proxy request that failed inside the gateway.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentError {
+    pub at: SystemTime,
+    /// Unset when the request failed before its route was resolved.
+    pub upstream_id: Option<Uuid>,
+    pub route_id: Option<Uuid>,
+    /// Kind of error, e.g. `connection_timeout` (see
+    /// [`DomainError::category`](crate::domain::error::DomainError::category)).
+    pub category: &'static str,
+    /// Error message with credentials, query strings and URL userinfo
+    /// masked.
+    pub message: String,
+}
+
+/// Entries evicted from each cache held by this gateway instance.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FlushedCaches {
+    /// Cached upstream responses, including idempotency replays.
+    pub response_cache: usize,
+    /// Tokens cached by auth plugins.
+    pub token_cache: usize,
+}
+
+/// Configuration layer an effective setting was taken from.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// The route itself.
+    Route,
+    /// The upstream the caller's alias resolves to.
+    Upstream,
+    /// An ancestor tenant's upstream with the same alias.
+    Ancestor,
+    /// A tenant rate limit set on the upstream for the calling tenant.
+    TenantPolicy,
+    /// The gateway-wide module default.
+    Gateway,
+}
+
+/// An effective setting and the layer it came from.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sourced<T> {
+    pub value: T,
+    pub source: ConfigSource,
+}
+
+/// Fully-resolved configuration the proxy applies to requests matching a
+/// route, as seen by the calling tenant.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteDescription {
+    pub route: Route,
+    /// Upstream the route's alias resolves to for the caller.
+    pub upstream_id: Uuid,
+    pub timeout_secs: Sourced<u64>,
+    pub user_agent: Sourced<String>,
+    pub auth: Option<Sourced<AuthConfig>>,
+    pub headers: Option<Sourced<HeadersConfig>>,
+    /// Limit of the upstream bucket; a route limit tighter than the
+    /// upstream's is reported as coming from the route.
+    pub rate_limit: Option<Sourced<RateLimitConfig>>,
+    pub circuit_failure_threshold: Sourced<u32>,
+    pub circuit_cooldown_secs: Sourced<u64>,
+    pub circuit_half_open_probes: Sourced<u32>,
+}
+
+/// AuthZ decision recorded in a [`RequestTrace`].
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceAuthz {
+    Allowed,
+    Denied,
+    /// Not evaluated: the route is public or `authz_mode` is `allow_all`.
+    Skipped,
+}
+
+/// What each stage of the proxy pipeline did with one traced request.
+/// Stages the request did not reach are left empty.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RequestTrace {
+    pub route_id: Option<Uuid>,
+    pub upstream_id: Option<Uuid>,
+    /// `host:port` the request was addressed to.
+    pub endpoint: Option<String>,
+    pub authz: Option<TraceAuthz>,
+    /// Auth plugins run, by plugin type.
+    pub plugins: Vec<String>,
+    /// Transforms applied, in order: `request_body_transformers`,
+    /// `transform_hook`, `header_rules`, `status_remap`, `body_rewrite`,
+    /// `response_body_transformers`, `response_header_allowlist`.
+    pub transforms: Vec<String>,
+    /// The mock response stood in for the upstream.
+    pub upstream_called: bool,
+    /// Status of the response the client would get; unset when the trace
+    /// ended before sending or the request failed.
+    pub status: Option<u16>,
+    /// Why the request failed, if it did.
+    pub error: Option<String>,
 }
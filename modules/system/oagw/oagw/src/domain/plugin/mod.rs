@@ -1,8 +1,11 @@
 use std::collections::HashMap;
+use std::time::SystemTime;
 
 use async_trait::async_trait;
+use bytes::Bytes;
 use modkit_macros::domain_model;
 use modkit_security::SecurityContext;
+use uuid::Uuid;
 
 // ---------------------------------------------------------------------------
 // Plugin errors
@@ -15,7 +18,6 @@ pub enum PluginError {
     #[error("secret not found: {0}")]
     SecretNotFound(String),
     #[error("authentication failed: {0}")]
-    #[allow(dead_code)] // Part of plugin trait API; no current plugin constructs this.
     AuthFailed(String),
     #[error("request rejected: {0}")]
     #[allow(dead_code)] // Part of plugin trait API; no current plugin constructs this.
@@ -24,21 +26,284 @@ pub enum PluginError {
     InvalidConfig(String),
     #[error("plugin error: {0}")]
     Internal(String),
+    #[error("auth plugin is disabled: {0}")]
+    Disabled(String),
+}
+
+// ---------------------------------------------------------------------------
+// Secret references
+// ---------------------------------------------------------------------------
+
+/// Placeholder in a `secret_ref` that is replaced with the calling tenant's ID.
+pub const TENANT_PLACEHOLDER: &str = "{tenant}";
+
+/// Turn a configured `secret_ref` into a bare credstore key for `tenant_id`.
+///
+/// The `cred://` scheme is stripped. Templated references have
+/// [`TENANT_PLACEHOLDER`] substituted and their `/` separators flattened to
+/// `-`, since credstore keys are flat: `cred://{tenant}/openai-key` becomes
+/// `<tenant-id>-openai-key`. Plain references are returned unchanged.
+#[must_use]
+pub fn resolve_secret_ref(secret_ref: &str, tenant_id: Uuid) -> String {
+    let bare = secret_ref.strip_prefix("cred://").unwrap_or(secret_ref);
+    if !bare.contains(TENANT_PLACEHOLDER) {
+        return bare.to_string();
+    }
+    bare.replace(TENANT_PLACEHOLDER, &tenant_id.to_string())
+        .replace('/', "-")
+}
+
+/// Pick `field` out of a secret that stores JSON credentials, e.g.
+/// `client_secret` from `{"client_id": "...", "client_secret": "..."}`.
+/// String fields are returned as-is; numbers and booleans in their JSON form.
+///
+/// # Errors
+/// `Internal` when the secret is not a JSON object or the field is not a
+/// scalar; `SecretNotFound` (naming `secret_ref#field`) when it is absent.
+pub fn select_secret_field(
+    secret: &str,
+    secret_ref: &str,
+    field: &str,
+) -> Result<String, PluginError> {
+    let serde_json::Value::Object(mut fields) = serde_json::from_str(secret)
+        .map_err(|_| PluginError::Internal(format!("secret '{secret_ref}' is not valid JSON")))?
+    else {
+        return Err(PluginError::Internal(format!(
+            "secret '{secret_ref}' is not a JSON object"
+        )));
+    };
+    match fields.remove(field) {
+        None | Some(serde_json::Value::Null) => {
+            Err(PluginError::SecretNotFound(format!("{secret_ref}#{field}")))
+        }
+        Some(serde_json::Value::String(value)) => Ok(value),
+        Some(value @ (serde_json::Value::Number(_) | serde_json::Value::Bool(_))) => {
+            Ok(value.to_string())
+        }
+        Some(_) => Err(PluginError::Internal(format!(
+            "field '{field}' of secret '{secret_ref}' is not a scalar value"
+        ))),
+    }
 }
 
 // ---------------------------------------------------------------------------
 // Auth plugin
 // ---------------------------------------------------------------------------
 
-/// Request context passed to an auth plugin for header injection.
+/// Ordered outbound request headers handed to an auth plugin.
+///
+/// Entries keep their order so signing plugins can canonicalize headers
+/// reproducibly, and a name may carry several values. Names compare
+/// case-insensitively.
+#[domain_model]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuthHeaders {
+    entries: Vec<(String, String)>,
+}
+
+#[allow(dead_code)] // Part of plugin API; built-in plugins use only a subset.
+impl AuthHeaders {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `name` to a single value. The first existing entry is updated in
+    /// place and any further values are dropped; a new name is appended.
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        let value = value.into();
+        match self
+            .entries
+            .iter()
+            .position(|(n, _)| n.eq_ignore_ascii_case(&name))
+        {
+            Some(pos) => {
+                self.entries[pos].1 = value;
+                let mut index = 0;
+                self.entries.retain(|(n, _)| {
+                    let keep = index <= pos || !n.eq_ignore_ascii_case(&name);
+                    index += 1;
+                    keep
+                });
+            }
+            None => self.entries.push((name, value)),
+        }
+    }
+
+    /// Add a value for `name`, keeping any existing ones.
+    pub fn append(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.entries.push((name.into(), value.into()));
+    }
+
+    /// First value of `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// All values of `name`, in order.
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.entries
+            .iter()
+            .filter(move |(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[must_use]
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Remove every value of `name`.
+    pub fn remove(&mut self, name: &str) {
+        self.entries.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+    }
+
+    /// All entries, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(n, v)| (n.as_str(), v.as_str()))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Lowercased names whose values differ from those in `before`, i.e.
+    /// that were set, changed or removed since; in first-seen order.
+    #[must_use]
+    pub fn changed_since(&self, before: &AuthHeaders) -> Vec<String> {
+        let mut changed: Vec<String> = Vec::new();
+        for (name, _) in self.entries.iter().chain(&before.entries) {
+            let name = name.to_ascii_lowercase();
+            if !changed.contains(&name) && !self.get_all(&name).eq(before.get_all(&name)) {
+                changed.push(name);
+            }
+        }
+        changed
+    }
+}
+
+impl FromIterator<(String, String)> for AuthHeaders {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// An outbound header set, changed or removed by an auth plugin, recorded so
+/// that the data plane can tell which plugin produced which header.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderProvenance {
+    pub plugin_id: String,
+    /// Lowercased header name.
+    pub header: String,
+}
+
+/// Request context passed to an auth plugin for credential injection.
 #[domain_model]
 pub struct AuthContext {
     /// Outbound request headers (modified in-place by the plugin).
-    pub headers: HashMap<String, String>,
+    pub headers: AuthHeaders,
     /// Plugin-specific configuration key/value pairs.
     pub config: HashMap<String, String>,
     /// Security context of the calling subject.
     pub security_context: SecurityContext,
+    /// Set by a plugin that answers the request itself (e.g. a 302 to a
+    /// login page). The data plane returns it without calling the upstream;
+    /// header-injecting plugins leave it `None`.
+    pub early_response: Option<EarlyResponse>,
+    /// Fields to set in the outbound JSON request body, as
+    /// `(JSON pointer, value)` pairs. Setting any forces the data plane to
+    /// buffer the body; header-injecting plugins leave it empty.
+    pub body_fields: Vec<(String, String)>,
+    /// Query parameters to set on the outbound URL, as `(name, value)`
+    /// pairs. Each replaces a client-sent parameter of the same name;
+    /// header-injecting plugins leave it empty.
+    pub query: Vec<(String, String)>,
+    /// Method of the outbound request, uppercase (`GET`, `POST`, ...).
+    pub method: String,
+    /// Path and query of the outbound request as sent to the upstream
+    /// (route path joined with the request's suffix, stripped params removed).
+    pub path: String,
+    /// The full request body when the data plane holds it buffered, which
+    /// it always does for plugins whose [`AuthPlugin::requires_body`] is
+    /// true. `None` while the body is still streaming.
+    pub body: Option<Bytes>,
+    /// Trusted current time: the gateway clock adjusted by the configured
+    /// `clock_skew_correction_secs`. Plugins that sign timestamps (SigV4,
+    /// HMAC) must use this rather than `SystemTime::now()`.
+    pub now: SystemTime,
+    /// Set by a plugin that picks among several credentials, naming the one
+    /// it injected. The data plane reports the upstream's status for it
+    /// through [`AuthPlugin::upstream_responded`].
+    pub credential: Option<String>,
+}
+
+/// Response produced by an auth plugin in place of the upstream response.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EarlyResponse {
+    pub status: u16,
+    pub headers: AuthHeaders,
+    pub body: Vec<u8>,
+}
+
+// ---------------------------------------------------------------------------
+// Config schema
+// ---------------------------------------------------------------------------
+
+/// How a plugin interprets a configuration value. Values always arrive as
+/// strings in `AuthContext::config`.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigValueType {
+    String,
+    /// A `cred://` secret reference, optionally templated with `{tenant}`.
+    SecretRef,
+    Url,
+    /// `true` or `false`.
+    Bool,
+}
+
+/// One key accepted in a plugin's configuration.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigKey {
+    pub name: &'static str,
+    pub value_type: ConfigValueType,
+    pub required: bool,
+}
+
+impl ConfigKey {
+    #[must_use]
+    pub const fn required(name: &'static str, value_type: ConfigValueType) -> Self {
+        Self {
+            name,
+            value_type,
+            required: true,
+        }
+    }
+
+    #[must_use]
+    pub const fn optional(name: &'static str, value_type: ConfigValueType) -> Self {
+        Self {
+            name,
+            value_type,
+            required: false,
+        }
+    }
 }
 
 /// Trait for outbound authentication plugins.
@@ -48,5 +313,162 @@ pub struct AuthContext {
 #[async_trait]
 pub trait AuthPlugin: Send + Sync {
     /// Apply authentication to the outbound request context.
+    ///
+    /// Changes apply all together, and only if this returns `Ok`: a plugin
+    /// may set several headers and still fail afterwards without any of
+    /// them reaching the upstream.
     async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError>;
+
+    /// Configuration keys the plugin understands. Plugins without
+    /// configuration keep the default.
+    fn config_schema(&self) -> &'static [ConfigKey] {
+        &[]
+    }
+
+    /// Whether the plugin needs the full request body, e.g. to sign it. The
+    /// data plane then buffers streaming bodies (bounded by the maximum body
+    /// size) and passes them in [`AuthContext::body`]; other plugins leave
+    /// request bodies streaming.
+    fn requires_body(&self) -> bool {
+        false
+    }
+
+    /// Report whether the plugin can currently serve requests. Feeds the
+    /// module's readiness signal; stateless plugins keep the default.
+    async fn health(&self) -> Result<(), PluginError> {
+        Ok(())
+    }
+
+    /// Told the upstream's response status for a request the plugin
+    /// authenticated with [`AuthContext::credential`]. Plugins that leave
+    /// it unset keep the default.
+    fn upstream_responded(&self, _credential: &str, _status: u16) {}
+
+    /// Drop everything the plugin caches (e.g. fetched tokens) and return
+    /// the number of entries dropped. Plugins without a cache keep the
+    /// default.
+    fn clear_cache(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::AuthContextBuilder;
+
+    #[test]
+    fn auth_headers_iterate_in_insertion_order() {
+        let mut headers = AuthHeaders::new();
+        for name in ["x-date", "host", "content-type", "accept", "x-amz-content"] {
+            headers.insert(name, "v");
+        }
+        headers.insert("authorization", "sig");
+        // Overwriting an existing name keeps its position.
+        headers.insert("Host", "api.example.com");
+
+        let names: Vec<&str> = headers.iter().map(|(n, _)| n).collect();
+        assert_eq!(
+            names,
+            [
+                "x-date",
+                "host",
+                "content-type",
+                "accept",
+                "x-amz-content",
+                "authorization"
+            ]
+        );
+        assert_eq!(headers.get("host"), Some("api.example.com"));
+    }
+
+    #[test]
+    fn auth_headers_keep_duplicate_values() {
+        let mut headers = AuthHeaders::new();
+        headers.append("accept", "application/json");
+        headers.append("x-trace", "1");
+        headers.append("Accept", "text/plain");
+
+        let accept: Vec<&str> = headers.get_all("accept").collect();
+        assert_eq!(accept, ["application/json", "text/plain"]);
+        assert_eq!(headers.get("accept"), Some("application/json"));
+        assert_eq!(headers.len(), 3);
+
+        headers.insert("accept", "*/*");
+        assert_eq!(headers.get_all("accept").collect::<Vec<_>>(), ["*/*"]);
+        assert_eq!(headers.len(), 2);
+
+        headers.remove("ACCEPT");
+        assert!(!headers.contains_key("accept"));
+    }
+
+    #[test]
+    fn auth_headers_changed_since_lists_set_changed_and_removed_names() {
+        let mut before = AuthHeaders::new();
+        before.insert("Accept", "application/json");
+        before.insert("x-trace", "1");
+        before.insert("x-stale", "old");
+
+        let mut after = before.clone();
+        after.insert("Authorization", "Bearer t");
+        after.insert("x-trace", "2");
+        after.remove("x-stale");
+        after.insert("accept", "application/json");
+
+        assert_eq!(
+            after.changed_since(&before),
+            ["x-trace", "authorization", "x-stale"]
+        );
+        assert!(before.changed_since(&before).is_empty());
+    }
+
+    /// Signs `method`, `path`, timestamp and body length into `x-signature`.
+    struct StringToSignPlugin;
+
+    #[async_trait]
+    impl AuthPlugin for StringToSignPlugin {
+        async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+            let key = ctx
+                .config
+                .get("key_id")
+                .ok_or_else(|| PluginError::InvalidConfig("missing key_id".into()))?;
+            let ts = ctx
+                .now
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_err(|e| PluginError::Internal(e.to_string()))?
+                .as_secs();
+            let body_len = ctx.body.as_ref().map_or(0, Bytes::len);
+            let content_type = ctx.headers.get("content-type").unwrap_or("");
+            let signature = format!(
+                "{key}:{}\n{}\n{ts}\n{content_type}\n{body_len}",
+                ctx.method, ctx.path
+            );
+            ctx.headers.insert("x-signature", signature);
+            Ok(())
+        }
+
+        fn requires_body(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn auth_context_builder_feeds_signing_plugin() {
+        let now = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(HashMap::from([("key_id".into(), "k1".into())]))
+            .with_header("content-type", "application/json")
+            .with_method("POST")
+            .with_path("/v1/chat?stream=true")
+            .with_body(&b"{\"a\":1}"[..])
+            .with_now(now)
+            .build();
+
+        StringToSignPlugin.authenticate(&mut ctx).await.unwrap();
+
+        assert_eq!(
+            ctx.headers.get("x-signature"),
+            Some("k1:POST\n/v1/chat?stream=true\n1700000000\napplication/json\n7")
+        );
+    }
 }
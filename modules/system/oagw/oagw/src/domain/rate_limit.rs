@@ -1,6 +1,8 @@
 use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::Instant;
 
+use crate::domain::clock::{Clock, SystemClock};
 use crate::domain::error::DomainError;
 use crate::domain::model::{RateLimitConfig, Window};
 use dashmap::DashMap;
@@ -9,9 +11,24 @@ use modkit_macros::domain_model;
 #[domain_model]
 pub struct RateLimiter {
     buckets: DashMap<String, TokenBucket>,
+    clock: Arc<dyn Clock>,
 }
 
+/// Bucket state after a consume attempt, reported to clients in
+/// rate-limit headers.
 #[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    /// Bucket capacity: the burst size, or the sustained rate without one.
+    pub limit: u64,
+    /// Whole tokens left in the bucket.
+    pub remaining: u64,
+    /// Seconds until the bucket is full again.
+    pub reset_secs: u64,
+}
+
+#[domain_model]
+#[derive(Clone)]
 struct TokenBucket {
     capacity: f64,
     tokens: f64,
@@ -20,7 +37,7 @@ struct TokenBucket {
 }
 
 impl TokenBucket {
-    fn new(config: &RateLimitConfig) -> Self {
+    fn new(config: &RateLimitConfig, now: Instant) -> Self {
         let capacity = config
             .burst
             .as_ref()
@@ -31,19 +48,18 @@ impl TokenBucket {
             capacity,
             tokens: capacity,
             refill_rate,
-            last_refill: Instant::now(),
+            last_refill: now,
         }
     }
 
-    fn refill(&mut self) {
-        let now = Instant::now();
+    fn refill(&mut self, now: Instant) {
         let elapsed = now.duration_since(self.last_refill).as_secs_f64();
         self.tokens = (self.tokens + elapsed * self.refill_rate).min(self.capacity);
         self.last_refill = now;
     }
 
-    fn try_consume(&mut self, cost: f64) -> bool {
-        self.refill();
+    fn try_consume(&mut self, cost: f64, now: Instant) -> bool {
+        self.refill(now);
         if self.tokens >= cost {
             self.tokens -= cost;
             true
@@ -62,6 +78,19 @@ impl TokenBucket {
         }
         (needed / self.refill_rate).ceil() as u64
     }
+
+    fn status(&self) -> RateLimitStatus {
+        let reset_secs = if self.refill_rate > 0.0 {
+            ((self.capacity - self.tokens).max(0.0) / self.refill_rate).ceil() as u64
+        } else {
+            0
+        };
+        RateLimitStatus {
+            limit: self.capacity as u64,
+            remaining: self.tokens.max(0.0).floor() as u64,
+            reset_secs,
+        }
+    }
 }
 
 fn window_to_secs(window: &Window) -> f64 {
@@ -76,8 +105,15 @@ fn window_to_secs(window: &Window) -> f64 {
 impl RateLimiter {
     #[must_use]
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    /// Refill buckets by `clock` instead of the system clock.
+    #[must_use]
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
         Self {
             buckets: DashMap::new(),
+            clock,
         }
     }
 
@@ -95,7 +131,30 @@ impl RateLimiter {
         self.buckets.remove(key);
     }
 
-    /// Try to consume tokens for the given key.
+    /// Current state of the bucket for `key`; `None` before its first use.
+    pub fn status(&self, key: &str) -> Option<RateLimitStatus> {
+        self.buckets.get(key).map(|bucket| bucket.status())
+    }
+
+    /// Current state of every bucket whose key starts with `prefix`, sorted
+    /// by key. Buckets are refilled first so the state is up to date.
+    pub fn statuses(&self, prefix: &str) -> Vec<(String, RateLimitStatus)> {
+        let now = self.clock.instant();
+        let mut statuses: Vec<_> = self
+            .buckets
+            .iter_mut()
+            .filter(|entry| entry.key().starts_with(prefix))
+            .map(|mut entry| {
+                entry.refill(now);
+                (entry.key().clone(), entry.status())
+            })
+            .collect();
+        statuses.sort_by(|a, b| a.0.cmp(&b.0));
+        statuses
+    }
+
+    /// Try to consume tokens for the given key, returning the bucket state
+    /// afterwards.
     ///
     /// # Errors
     /// Returns `DomainError::RateLimitExceeded` with Retry-After seconds when exhausted.
@@ -104,23 +163,52 @@ impl RateLimiter {
         key: &str,
         config: &RateLimitConfig,
         instance_uri: &str,
-    ) -> Result<(), DomainError> {
-        let cost = config.cost as f64;
+    ) -> Result<RateLimitStatus, DomainError> {
+        let now = self.clock.instant();
         let mut bucket = self
             .buckets
             .entry(key.to_string())
-            .or_insert_with(|| TokenBucket::new(config));
+            .or_insert_with(|| TokenBucket::new(config, now));
+        consume(&mut bucket, key, config, instance_uri, now)
+    }
 
-        if bucket.try_consume(cost) {
-            Ok(())
-        } else {
-            let retry_after = bucket.retry_after_secs(cost);
-            Err(DomainError::RateLimitExceeded {
-                detail: format!("rate limit exceeded for key: {key}"),
-                instance: instance_uri.to_string(),
-                retry_after_secs: Some(retry_after),
-            })
-        }
+    /// What `try_consume` would answer, without taking tokens.
+    ///
+    /// # Errors
+    /// Returns `DomainError::RateLimitExceeded` when `try_consume` would.
+    pub fn check(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+        instance_uri: &str,
+    ) -> Result<RateLimitStatus, DomainError> {
+        let now = self.clock.instant();
+        let mut bucket = self
+            .buckets
+            .get(key)
+            .map_or_else(|| TokenBucket::new(config, now), |b| b.value().clone());
+        consume(&mut bucket, key, config, instance_uri, now)
+    }
+}
+
+fn consume(
+    bucket: &mut TokenBucket,
+    key: &str,
+    config: &RateLimitConfig,
+    instance_uri: &str,
+    now: Instant,
+) -> Result<RateLimitStatus, DomainError> {
+    let cost = config.cost as f64;
+    if bucket.try_consume(cost, now) {
+        Ok(bucket.status())
+    } else {
+        let retry_after = bucket.retry_after_secs(cost);
+        Err(DomainError::RateLimitExceeded {
+            detail: format!("rate limit exceeded for key: {key}"),
+            instance: instance_uri.to_string(),
+            retry_after_secs: Some(retry_after),
+            headers: Vec::new(),
+        })
     }
 }
 
@@ -129,6 +217,7 @@ mod tests {
     use crate::domain::model::{
         BurstConfig, RateLimitAlgorithm, RateLimitScope, RateLimitStrategy, SustainedRate,
     };
+    use uuid::Uuid;
 
     use super::*;
 
@@ -163,6 +252,20 @@ mod tests {
         assert!(matches!(err, DomainError::RateLimitExceeded { .. }));
     }
 
+    #[test]
+    fn check_does_not_consume() {
+        let limiter = RateLimiter::new();
+        let config = make_config(1, Window::Minute, None);
+        assert_eq!(
+            limiter.check("test", &config, "/test").unwrap().remaining,
+            0
+        );
+        assert!(limiter.status("test").is_none());
+        assert!(limiter.try_consume("test", &config, "/test").is_ok());
+        let err = limiter.check("test", &config, "/test").unwrap_err();
+        assert!(matches!(err, DomainError::RateLimitExceeded { .. }));
+    }
+
     #[test]
     fn retry_after_is_calculated() {
         let limiter = RateLimiter::new();
@@ -190,6 +293,25 @@ mod tests {
         assert!(limiter.try_consume("test", &config, "/test").is_err());
     }
 
+    #[test]
+    fn status_tracks_remaining_tokens() {
+        let limiter = RateLimiter::new();
+        let config = make_config(1, Window::Minute, Some(3));
+        assert_eq!(limiter.status("test"), None);
+
+        let status = limiter.try_consume("test", &config, "/test").unwrap();
+        assert_eq!(status.limit, 3);
+        assert_eq!(status.remaining, 2);
+        assert!(status.reset_secs > 0 && status.reset_secs <= 60);
+
+        limiter.try_consume("test", &config, "/test").unwrap();
+        limiter.try_consume("test", &config, "/test").unwrap();
+        assert!(limiter.try_consume("test", &config, "/test").is_err());
+        let status = limiter.status("test").unwrap();
+        assert_eq!(status.remaining, 0);
+        assert!(status.reset_secs > 120 && status.reset_secs <= 180);
+    }
+
     #[test]
     fn separate_keys_independent() {
         let limiter = RateLimiter::new();
@@ -232,6 +354,31 @@ mod tests {
         assert!(limiter.buckets.contains_key("route:bbb"));
     }
 
+    #[test]
+    fn statuses_lists_buckets_by_prefix() {
+        let limiter = RateLimiter::new();
+        let config = make_config(5, Window::Minute, None);
+        let upstream = Uuid::new_v4();
+        limiter
+            .try_consume(&format!("upstream:{upstream}"), &config, "/test")
+            .unwrap();
+        limiter
+            .try_consume(&format!("upstream:{upstream}:tenant:t1"), &config, "/test")
+            .unwrap();
+        limiter.try_consume("route:r1", &config, "/test").unwrap();
+
+        let statuses = limiter.statuses(&format!("upstream:{upstream}"));
+        let keys: Vec<&str> = statuses.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                format!("upstream:{upstream}"),
+                format!("upstream:{upstream}:tenant:t1")
+            ]
+        );
+        assert_eq!(statuses[0].1.remaining, 4);
+    }
+
     #[test]
     fn remove_key_noop_for_missing_key() {
         let limiter = RateLimiter::new();
@@ -0,0 +1,34 @@
+//! Startup milestones that gate the module's readiness signal.
+//!
+//! Liveness only means the process is up. Readiness additionally requires the
+//! auth plugins to be initialized and the provisioned upstreams/routes to be
+//! loaded into the repositories; both are flipped once by the module
+//! lifecycle and never reset.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Debug, Default)]
+pub(crate) struct ReadinessState {
+    plugins_initialized: AtomicBool,
+    repos_loaded: AtomicBool,
+}
+
+impl ReadinessState {
+    /// Called once the Data Plane (and its auth plugin registry) is built.
+    pub(crate) fn mark_plugins_initialized(&self) {
+        self.plugins_initialized.store(true, Ordering::Release);
+    }
+
+    /// Called once provisioned upstreams and routes are materialized.
+    pub(crate) fn mark_repos_loaded(&self) {
+        self.repos_loaded.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn plugins_initialized(&self) -> bool {
+        self.plugins_initialized.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn repos_loaded(&self) -> bool {
+        self.repos_loaded.load(Ordering::Acquire)
+    }
+}
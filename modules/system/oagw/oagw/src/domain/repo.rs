@@ -1,4 +1,4 @@
-use crate::domain::model::{ListQuery, Route, Upstream};
+use crate::domain::model::{ListQuery, Route, TenantAuthDefault, TenantRateLimit, Upstream};
 use async_trait::async_trait;
 use modkit_macros::domain_model;
 use uuid::Uuid;
@@ -49,12 +49,18 @@ pub trait UpstreamRepository: Send + Sync {
 
     /// Delete an upstream. Returns NotFound if it does not exist.
     async fn delete(&self, tenant_id: Uuid, id: Uuid) -> Result<(), RepositoryError>;
+
+    /// Number of upstreams owned by a tenant.
+    async fn count(&self, tenant_id: Uuid) -> Result<usize, RepositoryError>;
 }
 
 /// Repository trait for route persistence.
 #[async_trait]
 pub trait RouteRepository: Send + Sync {
-    /// Insert a new route.
+    /// Insert a new route. Returns Conflict if it is enabled and an enabled
+    /// route of the tenant under the same upstream has the same priority,
+    /// path, body condition and at least one of its methods: neither would
+    /// be more specific, so the winner would be arbitrary.
     async fn create(&self, route: Route) -> Result<Route, RepositoryError>;
 
     /// Get a route by id, scoped to a tenant.
@@ -68,17 +74,25 @@ pub trait RouteRepository: Send + Sync {
         query: &ListQuery,
     ) -> Result<Vec<Route>, RepositoryError>;
 
-    /// Find the best matching route for a given method and path.
-    /// Match criteria: enabled=true, method matches, longest path prefix, highest priority.
+    /// Find the best matching route for a given method, path and body.
+    /// Match criteria: enabled=true, method matches, body condition holds,
+    /// highest priority, then longest path prefix, then routes with a body
+    /// condition, then the earliest created.
+    ///
+    /// `body` is `None` when the request body has not been buffered; body
+    /// conditions are then assumed to hold, so a winning route that carries
+    /// one must be re-checked against the buffered body.
     async fn find_matching(
         &self,
         tenant_id: Uuid,
         upstream_id: Uuid,
         method: &str,
         path: &str,
+        body: Option<&[u8]>,
     ) -> Result<Route, RepositoryError>;
 
-    /// Update an existing route.
+    /// Update an existing route. Returns Conflict under the same rule as
+    /// [`create`](Self::create).
     async fn update(&self, route: Route) -> Result<Route, RepositoryError>;
 
     /// Delete a route.
@@ -90,4 +104,43 @@ pub trait RouteRepository: Send + Sync {
         tenant_id: Uuid,
         upstream_id: Uuid,
     ) -> Result<u64, RepositoryError>;
+
+    /// Number of routes owned by a tenant, across all its upstreams.
+    async fn count(&self, tenant_id: Uuid) -> Result<usize, RepositoryError>;
+}
+
+/// Repository trait for tenant-scoped rate limit policies.
+#[async_trait]
+pub trait TenantRateLimitRepository: Send + Sync {
+    /// Insert or replace the policy for `(upstream_id, tenant_id)`.
+    async fn upsert(&self, limit: TenantRateLimit) -> Result<TenantRateLimit, RepositoryError>;
+
+    /// Get the policy a tenant has on an upstream.
+    async fn get(
+        &self,
+        upstream_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<TenantRateLimit, RepositoryError>;
+
+    /// Delete the policy a tenant has on an upstream.
+    async fn delete(&self, upstream_id: Uuid, tenant_id: Uuid) -> Result<(), RepositoryError>;
+
+    /// Delete every policy attached to an upstream. Returns the count deleted.
+    async fn delete_by_upstream(&self, upstream_id: Uuid) -> Result<u64, RepositoryError>;
+}
+
+/// Repository trait for tenant-level default auth.
+#[async_trait]
+pub trait TenantAuthDefaultRepository: Send + Sync {
+    /// Insert or replace the tenant's default auth.
+    async fn upsert(
+        &self,
+        default: TenantAuthDefault,
+    ) -> Result<TenantAuthDefault, RepositoryError>;
+
+    /// Get a tenant's default auth.
+    async fn get(&self, tenant_id: Uuid) -> Result<TenantAuthDefault, RepositoryError>;
+
+    /// Delete a tenant's default auth.
+    async fn delete(&self, tenant_id: Uuid) -> Result<(), RepositoryError>;
 }
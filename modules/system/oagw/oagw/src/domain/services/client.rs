@@ -9,6 +9,7 @@ use oagw_sdk::error::ServiceGatewayError;
 use uuid::Uuid;
 
 use super::{ControlPlaneService, DataPlaneService};
+use crate::domain::circuit_breaker::CircuitStatus;
 use crate::domain::error::DomainError;
 use crate::domain::model;
 
@@ -38,6 +39,22 @@ impl ServiceGatewayClientV1 for ServiceGatewayClientV1Facade {
         result.map(upstream_to_sdk).map_err(domain_err_to_sdk)
     }
 
+    async fn create_upstreams(
+        &self,
+        ctx: SecurityContext,
+        reqs: Vec<oagw_sdk::CreateUpstreamRequest>,
+    ) -> Result<Vec<oagw_sdk::Upstream>, ServiceGatewayError> {
+        let internal_reqs = reqs
+            .into_iter()
+            .map(sdk_create_upstream_to_domain)
+            .collect();
+        self.cp
+            .create_upstreams(&ctx, internal_reqs)
+            .await
+            .map(|ups| ups.into_iter().map(upstream_to_sdk).collect())
+            .map_err(domain_err_to_sdk)
+    }
+
     async fn get_upstream(
         &self,
         ctx: SecurityContext,
@@ -85,8 +102,9 @@ impl ServiceGatewayClientV1 for ServiceGatewayClientV1Facade {
         ctx: SecurityContext,
         id: Uuid,
     ) -> Result<(), ServiceGatewayError> {
+        // The SDK has no cascade flag; keep its delete-with-routes semantics.
         self.cp
-            .delete_upstream(&ctx, id)
+            .delete_upstream(&ctx, id, true)
             .await
             .map_err(domain_err_to_sdk)
     }
@@ -104,6 +122,19 @@ impl ServiceGatewayClientV1 for ServiceGatewayClientV1Facade {
             .map_err(domain_err_to_sdk)
     }
 
+    async fn create_routes(
+        &self,
+        ctx: SecurityContext,
+        reqs: Vec<oagw_sdk::CreateRouteRequest>,
+    ) -> Result<Vec<oagw_sdk::Route>, ServiceGatewayError> {
+        let internal_reqs = reqs.into_iter().map(sdk_create_route_to_domain).collect();
+        self.cp
+            .create_routes(&ctx, internal_reqs)
+            .await
+            .map(|routes| routes.into_iter().map(route_to_sdk).collect())
+            .map_err(domain_err_to_sdk)
+    }
+
     async fn get_route(
         &self,
         ctx: SecurityContext,
@@ -158,6 +189,38 @@ impl ServiceGatewayClientV1 for ServiceGatewayClientV1Facade {
             .map_err(domain_err_to_sdk)
     }
 
+    async fn validate_config(
+        &self,
+        ctx: SecurityContext,
+        snapshot: oagw_sdk::ConfigSnapshot,
+    ) -> Result<Vec<oagw_sdk::ConfigProblem>, ServiceGatewayError> {
+        let snapshot = model::ConfigSnapshot {
+            upstreams: snapshot
+                .upstreams
+                .into_iter()
+                .map(sdk_create_upstream_to_domain)
+                .collect(),
+            routes: snapshot
+                .routes
+                .into_iter()
+                .map(sdk_create_route_to_domain)
+                .collect(),
+        };
+        self.cp
+            .validate_config(&ctx, snapshot)
+            .await
+            .map(|problems| {
+                problems
+                    .into_iter()
+                    .map(|p| oagw_sdk::ConfigProblem {
+                        path: p.path,
+                        detail: p.detail,
+                    })
+                    .collect()
+            })
+            .map_err(domain_err_to_sdk)
+    }
+
     async fn resolve_proxy_target(
         &self,
         ctx: SecurityContext,
@@ -166,7 +229,7 @@ impl ServiceGatewayClientV1 for ServiceGatewayClientV1Facade {
         path: &str,
     ) -> Result<(oagw_sdk::Upstream, oagw_sdk::Route), ServiceGatewayError> {
         self.cp
-            .resolve_proxy_target(&ctx, alias, method, path)
+            .resolve_proxy_target(&ctx, alias, method, path, None)
             .await
             .map(|(u, r)| (upstream_to_sdk(u), route_to_sdk(r)))
             .map_err(domain_err_to_sdk)
@@ -182,6 +245,157 @@ impl ServiceGatewayClientV1 for ServiceGatewayClientV1Facade {
             .await
             .map_err(domain_err_to_sdk)
     }
+
+    async fn status(&self) -> oagw_sdk::GatewayStatus {
+        let s = self.dp.status().await;
+        oagw_sdk::GatewayStatus {
+            live: s.live,
+            ready: s.ready,
+            reasons: s.reasons,
+            upstream_latencies: s
+                .upstream_latencies
+                .into_iter()
+                .map(|l| oagw_sdk::UpstreamLatency {
+                    upstream_id: l.upstream_id,
+                    samples: l.samples,
+                    p50_us: l.p50_us,
+                    p95_us: l.p95_us,
+                    p99_us: l.p99_us,
+                })
+                .collect(),
+        }
+    }
+
+    fn version_info(&self) -> oagw_sdk::VersionInfo {
+        let mut features = Vec::new();
+        if cfg!(feature = "test-utils") {
+            features.push("test-utils".to_string());
+        }
+        oagw_sdk::VersionInfo {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_sha: option_env!("OAGW_GIT_SHA")
+                .filter(|sha| !sha.is_empty())
+                .map(str::to_string),
+            features,
+        }
+    }
+
+    async fn test_upstream(
+        &self,
+        ctx: SecurityContext,
+        id: Uuid,
+    ) -> Result<oagw_sdk::UpstreamTestResult, ServiceGatewayError> {
+        let upstream = self
+            .cp
+            .get_upstream(&ctx, id)
+            .await
+            .map_err(domain_err_to_sdk)?;
+        let r = self.dp.test_upstream(&ctx, &upstream).await;
+        Ok(oagw_sdk::UpstreamTestResult {
+            credential_resolved: r.credential_resolved,
+            auth_applied: r.auth_applied,
+            upstream_status: r.upstream_status,
+            latency_ms: r.latency_ms,
+            error: r.error,
+        })
+    }
+
+    async fn describe_route(
+        &self,
+        ctx: SecurityContext,
+        id: Uuid,
+    ) -> Result<oagw_sdk::RouteDescription, ServiceGatewayError> {
+        self.dp
+            .describe_route(&ctx, id)
+            .await
+            .map(route_description_to_sdk)
+            .map_err(domain_err_to_sdk)
+    }
+
+    async fn trace_request(
+        &self,
+        ctx: SecurityContext,
+        req: http::Request<Body>,
+        mock_response: Option<http::Response<Body>>,
+    ) -> oagw_sdk::RequestTrace {
+        request_trace_to_sdk(self.dp.trace_request(ctx, req, mock_response).await)
+    }
+
+    async fn list_runtime_states(
+        &self,
+        ctx: SecurityContext,
+        query: &oagw_sdk::ListQuery,
+    ) -> Result<Vec<oagw_sdk::UpstreamRuntimeState>, ServiceGatewayError> {
+        let q = model::ListQuery {
+            top: query.top,
+            skip: query.skip,
+        };
+        let upstreams = self
+            .cp
+            .list_upstreams(&ctx, &q)
+            .await
+            .map_err(domain_err_to_sdk)?;
+        Ok(upstreams
+            .iter()
+            .map(|u| runtime_state_to_sdk(self.dp.runtime_state(u.id)))
+            .collect())
+    }
+
+    async fn reset_circuit_breaker(
+        &self,
+        ctx: SecurityContext,
+        upstream_id: Uuid,
+    ) -> Result<(), ServiceGatewayError> {
+        self.cp
+            .get_upstream(&ctx, upstream_id)
+            .await
+            .map_err(domain_err_to_sdk)?;
+        self.dp.reset_circuit_breaker(upstream_id);
+        tracing::info!(%upstream_id, "circuit breaker reset");
+        Ok(())
+    }
+
+    async fn reset_rate_limit(
+        &self,
+        ctx: SecurityContext,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+    ) -> Result<(), ServiceGatewayError> {
+        self.cp
+            .get_upstream(&ctx, upstream_id)
+            .await
+            .map_err(domain_err_to_sdk)?;
+        self.dp.reset_rate_limit(tenant_id, upstream_id);
+        tracing::info!(%tenant_id, %upstream_id, "rate limit reset");
+        Ok(())
+    }
+
+    fn recent_errors(&self) -> Vec<oagw_sdk::RecentError> {
+        self.dp
+            .recent_errors()
+            .into_iter()
+            .map(|e| oagw_sdk::RecentError {
+                at: e.at,
+                upstream_id: e.upstream_id,
+                route_id: e.route_id,
+                category: e.category.to_string(),
+                message: e.message,
+            })
+            .collect()
+    }
+
+    async fn flush_all_caches(&self) -> oagw_sdk::FlushedCaches {
+        let flushed = self.dp.flush_caches().await;
+        tracing::info!(
+            response_cache = flushed.response_cache,
+            token_cache = flushed.token_cache,
+            "caches flushed"
+        );
+        oagw_sdk::FlushedCaches {
+            response_cache: flushed.response_cache,
+            token_cache: flushed.token_cache,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -205,6 +419,50 @@ fn domain_err_to_sdk(err: DomainError) -> ServiceGatewayError {
             detail: format!("upstream '{alias}' is disabled"),
             instance: String::new(),
         },
+        DomainError::UpstreamMaintenance {
+            detail,
+            instance,
+            retry_after_secs,
+        } => ServiceGatewayError::UpstreamMaintenance {
+            detail,
+            instance,
+            retry_after_secs,
+        },
+        DomainError::CircuitBreakerOpen {
+            detail,
+            instance,
+            retry_after_secs,
+        } => ServiceGatewayError::CircuitBreakerOpen {
+            detail,
+            instance,
+            retry_after_secs,
+        },
+        DomainError::NoHealthyTarget {
+            detail,
+            instance,
+            total,
+            healthy,
+        } => ServiceGatewayError::NoHealthyTarget {
+            detail,
+            instance,
+            total,
+            healthy,
+        },
+        DomainError::GatewayOverloaded { detail, instance } => {
+            ServiceGatewayError::GatewayOverloaded { detail, instance }
+        }
+        DomainError::PluginDisabled { detail, instance } => {
+            ServiceGatewayError::PluginDisabled { detail, instance }
+        }
+        DomainError::AuthzUnavailable {
+            detail,
+            instance,
+            retry_after_secs,
+        } => ServiceGatewayError::AuthzUnavailable {
+            detail,
+            instance,
+            retry_after_secs,
+        },
         DomainError::Internal { message } => ServiceGatewayError::DownstreamError {
             detail: message,
             instance: String::new(),
@@ -224,10 +482,32 @@ fn domain_err_to_sdk(err: DomainError) -> ServiceGatewayError {
         DomainError::PayloadTooLarge { detail, instance } => {
             ServiceGatewayError::PayloadTooLarge { detail, instance }
         }
+        DomainError::UnsupportedMediaType { detail, instance } => {
+            ServiceGatewayError::UnsupportedMediaType { detail, instance }
+        }
+        DomainError::UriTooLong { detail, instance } => {
+            ServiceGatewayError::UriTooLong { detail, instance }
+        }
+        DomainError::HeaderTooLarge { detail, instance } => {
+            ServiceGatewayError::HeaderTooLarge { detail, instance }
+        }
+        DomainError::MethodNotAllowed {
+            detail,
+            instance,
+            allowed,
+        } => ServiceGatewayError::MethodNotAllowed {
+            detail,
+            instance,
+            allowed,
+        },
+        DomainError::SchemaViolation {
+            detail, instance, ..
+        } => ServiceGatewayError::ValidationError { detail, instance },
         DomainError::RateLimitExceeded {
             detail,
             instance,
             retry_after_secs,
+            ..
         } => ServiceGatewayError::RateLimitExceeded {
             detail,
             instance,
@@ -242,6 +522,9 @@ fn domain_err_to_sdk(err: DomainError) -> ServiceGatewayError {
         DomainError::ProtocolError { detail, instance } => {
             ServiceGatewayError::ProtocolError { detail, instance }
         }
+        DomainError::ClientTimeout { detail, instance } => {
+            ServiceGatewayError::ClientTimeout { detail, instance }
+        }
         DomainError::ConnectionTimeout { detail, instance } => {
             ServiceGatewayError::ConnectionTimeout { detail, instance }
         }
@@ -249,6 +532,7 @@ fn domain_err_to_sdk(err: DomainError) -> ServiceGatewayError {
             ServiceGatewayError::RequestTimeout { detail, instance }
         }
         DomainError::Forbidden { detail } => ServiceGatewayError::Forbidden { detail },
+        DomainError::QuotaExceeded { detail } => ServiceGatewayError::QuotaExceeded { detail },
     }
 }
 
@@ -269,6 +553,33 @@ fn sdk_create_upstream_to_domain(
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
         tags: req.tags().to_vec(),
         enabled: req.enabled(),
+        maintenance: req.maintenance(),
+        maintenance_message: req.maintenance_message().map(|s| s.to_string()),
+        retry_on_body: req.retry_on_body().cloned().map(retry_on_body_to_domain),
+        force_chunked: req.force_chunked(),
+        failover: req
+            .failover()
+            .iter()
+            .cloned()
+            .map(endpoint_to_domain)
+            .collect(),
+        timeout_secs: req.timeout_secs(),
+        keepalive: req.keepalive().map(keepalive_to_domain),
+        circuit_breaker: req.circuit_breaker().map(circuit_breaker_to_domain),
+        user_agent: req.user_agent().map(str::to_string),
+        sticky_by: req.sticky_by().map(str::to_string),
+        allowed_methods: req
+            .allowed_methods()
+            .iter()
+            .copied()
+            .map(http_method_to_domain)
+            .collect(),
+        trailing_slash: trailing_slash_to_domain(req.trailing_slash()),
+        max_header_size_bytes: req.max_header_size_bytes(),
+        tls_min_version: req.tls_min_version().map(str::to_string),
+        tls_cipher_suites: req.tls_cipher_suites().to_vec(),
+        tls_server_name: req.tls_server_name().map(str::to_string),
+        budget_header: req.budget_header().map(str::to_string),
     }
 }
 
@@ -285,6 +596,27 @@ fn sdk_update_upstream_to_domain(
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
         tags: req.tags().map(|s| s.to_vec()),
         enabled: req.enabled(),
+        maintenance: req.maintenance(),
+        maintenance_message: req.maintenance_message().map(|s| s.to_string()),
+        retry_on_body: req.retry_on_body().cloned().map(retry_on_body_to_domain),
+        force_chunked: req.force_chunked(),
+        failover: req
+            .failover()
+            .map(|eps| eps.iter().cloned().map(endpoint_to_domain).collect()),
+        timeout_secs: req.timeout_secs(),
+        keepalive: req.keepalive().map(keepalive_to_domain),
+        circuit_breaker: req.circuit_breaker().map(circuit_breaker_to_domain),
+        user_agent: req.user_agent().map(str::to_string),
+        sticky_by: req.sticky_by().map(str::to_string),
+        allowed_methods: req
+            .allowed_methods()
+            .map(|ms| ms.iter().copied().map(http_method_to_domain).collect()),
+        trailing_slash: req.trailing_slash().map(trailing_slash_to_domain),
+        max_header_size_bytes: req.max_header_size_bytes(),
+        tls_min_version: req.tls_min_version().map(str::to_string),
+        tls_cipher_suites: req.tls_cipher_suites().map(<[String]>::to_vec),
+        tls_server_name: req.tls_server_name().map(str::to_string),
+        budget_header: req.budget_header().map(str::to_string),
     }
 }
 
@@ -297,6 +629,42 @@ fn sdk_create_route_to_domain(req: oagw_sdk::CreateRouteRequest) -> model::Creat
         tags: req.tags().to_vec(),
         priority: req.priority(),
         enabled: req.enabled(),
+        skip_authz: req.skip_authz(),
+        timeout_secs: req.timeout_secs(),
+        status_remap: req.status_remap().clone(),
+        allowed_response_content_types: req.allowed_response_content_types().to_vec(),
+        allowed_request_content_types: req.allowed_request_content_types().to_vec(),
+        log_sample_rate: req.log_sample_rate(),
+        request_schema: req.request_schema().cloned(),
+        body_rewrite: req
+            .body_rewrite()
+            .iter()
+            .cloned()
+            .map(body_rewrite_to_domain)
+            .collect(),
+        auth_rules: req
+            .auth_rules()
+            .iter()
+            .cloned()
+            .map(auth_rule_to_domain)
+            .collect(),
+        strip_query_params: req.strip_query_params().to_vec(),
+        strip_inbound_headers: req.strip_inbound_headers().to_vec(),
+        idempotency_ttl_secs: req.idempotency_ttl_secs(),
+        cache_responses: req.cache_responses(),
+        negative_cache_ttl_secs: req.negative_cache_ttl_secs(),
+        response_header_allowlist: req.response_header_allowlist().map(<[String]>::to_vec),
+        max_total_duration_ms: req.max_total_duration_ms(),
+        max_body_size_bytes: req.max_body_size_bytes(),
+        max_outbound_calls: req.max_outbound_calls(),
+        coerce_content_type: req.coerce_content_type().map(str::to_owned),
+        api_version: req.api_version().cloned().map(api_version_to_domain),
+        body_transformers: req.body_transformers().to_vec(),
+        log_redact_paths: req.log_redact_paths().to_vec(),
+        transform_hook_url: req.transform_hook_url().map(str::to_owned),
+        deprecated: req.deprecated(),
+        sunset: req.sunset().map(str::to_owned),
+        kind: route_kind_to_domain(req.kind().clone()),
     }
 }
 
@@ -308,6 +676,38 @@ fn sdk_update_route_to_domain(req: oagw_sdk::UpdateRouteRequest) -> model::Updat
         tags: req.tags().map(|s| s.to_vec()),
         priority: req.priority(),
         enabled: req.enabled(),
+        skip_authz: req.skip_authz(),
+        timeout_secs: req.timeout_secs(),
+        status_remap: req.status_remap().cloned(),
+        allowed_response_content_types: req
+            .allowed_response_content_types()
+            .map(<[String]>::to_vec),
+        allowed_request_content_types: req.allowed_request_content_types().map(<[String]>::to_vec),
+        log_sample_rate: req.log_sample_rate(),
+        request_schema: req.request_schema().cloned(),
+        body_rewrite: req
+            .body_rewrite()
+            .map(|rules| rules.iter().cloned().map(body_rewrite_to_domain).collect()),
+        auth_rules: req
+            .auth_rules()
+            .map(|rules| rules.iter().cloned().map(auth_rule_to_domain).collect()),
+        strip_query_params: req.strip_query_params().map(<[String]>::to_vec),
+        strip_inbound_headers: req.strip_inbound_headers().map(<[String]>::to_vec),
+        idempotency_ttl_secs: req.idempotency_ttl_secs(),
+        cache_responses: req.cache_responses(),
+        negative_cache_ttl_secs: req.negative_cache_ttl_secs(),
+        response_header_allowlist: req.response_header_allowlist().map(<[String]>::to_vec),
+        max_total_duration_ms: req.max_total_duration_ms(),
+        max_body_size_bytes: req.max_body_size_bytes(),
+        max_outbound_calls: req.max_outbound_calls(),
+        coerce_content_type: req.coerce_content_type().map(str::to_owned),
+        api_version: req.api_version().cloned().map(api_version_to_domain),
+        body_transformers: req.body_transformers().map(<[String]>::to_vec),
+        log_redact_paths: req.log_redact_paths().map(<[String]>::to_vec),
+        transform_hook_url: req.transform_hook_url().map(str::to_owned),
+        deprecated: req.deprecated(),
+        sunset: req.sunset().map(str::to_owned),
+        kind: req.kind().cloned().map(route_kind_to_domain),
     }
 }
 
@@ -397,6 +797,70 @@ fn window_to_domain(v: oagw_sdk::Window) -> model::Window {
     }
 }
 
+fn body_matcher_to_domain(v: oagw_sdk::BodyMatcher) -> model::BodyMatcher {
+    match v {
+        oagw_sdk::BodyMatcher::JsonPointer { pointer, value } => {
+            model::BodyMatcher::JsonPointer { pointer, value }
+        }
+        oagw_sdk::BodyMatcher::Regex { pattern } => model::BodyMatcher::Regex { pattern },
+    }
+}
+
+fn retry_on_body_to_domain(v: oagw_sdk::RetryOnBody) -> model::RetryOnBody {
+    model::RetryOnBody {
+        matcher: body_matcher_to_domain(v.matcher),
+        max_retries: v.max_retries,
+    }
+}
+
+fn api_version_to_domain(v: oagw_sdk::ApiVersion) -> model::ApiVersion {
+    model::ApiVersion {
+        value: v.value,
+        header: v.header,
+        query_param: v.query_param,
+    }
+}
+
+fn auth_rule_to_domain(v: oagw_sdk::AuthRule) -> model::AuthRule {
+    model::AuthRule {
+        condition: match v.condition {
+            oagw_sdk::AuthCondition::PathPrefix { prefix } => {
+                model::AuthCondition::PathPrefix { prefix }
+            }
+            oagw_sdk::AuthCondition::Header { name, value } => {
+                model::AuthCondition::Header { name, value }
+            }
+        },
+        auth: v.auth.map(auth_config_to_domain),
+    }
+}
+
+fn body_rewrite_to_domain(v: oagw_sdk::BodyRewriteRule) -> model::BodyRewriteRule {
+    model::BodyRewriteRule {
+        find: v.find,
+        replace: v.replace,
+        regex: v.regex,
+        content_types: v.content_types,
+    }
+}
+
+fn keepalive_to_domain(v: &oagw_sdk::KeepaliveConfig) -> model::KeepaliveConfig {
+    model::KeepaliveConfig {
+        tcp_idle_secs: v.tcp_idle_secs,
+        tcp_interval_secs: v.tcp_interval_secs,
+        tcp_probe_count: v.tcp_probe_count,
+        h2_ping_interval_secs: v.h2_ping_interval_secs,
+    }
+}
+
+fn circuit_breaker_to_domain(v: &oagw_sdk::CircuitBreakerConfig) -> model::CircuitBreakerConfig {
+    model::CircuitBreakerConfig {
+        failure_threshold: v.failure_threshold,
+        cooldown_secs: v.cooldown_secs,
+        half_open_probes: v.half_open_probes,
+    }
+}
+
 fn rate_limit_config_to_domain(v: oagw_sdk::RateLimitConfig) -> model::RateLimitConfig {
     model::RateLimitConfig {
         sharing: sharing_mode_to_domain(v.sharing),
@@ -444,6 +908,24 @@ fn http_method_to_domain(v: oagw_sdk::HttpMethod) -> model::HttpMethod {
     }
 }
 
+fn http_method_to_sdk(v: model::HttpMethod) -> oagw_sdk::HttpMethod {
+    match v {
+        model::HttpMethod::Get => oagw_sdk::HttpMethod::Get,
+        model::HttpMethod::Post => oagw_sdk::HttpMethod::Post,
+        model::HttpMethod::Put => oagw_sdk::HttpMethod::Put,
+        model::HttpMethod::Delete => oagw_sdk::HttpMethod::Delete,
+        model::HttpMethod::Patch => oagw_sdk::HttpMethod::Patch,
+    }
+}
+
+fn trailing_slash_to_domain(v: oagw_sdk::TrailingSlash) -> model::TrailingSlash {
+    match v {
+        oagw_sdk::TrailingSlash::Preserve => model::TrailingSlash::Preserve,
+        oagw_sdk::TrailingSlash::Strip => model::TrailingSlash::Strip,
+        oagw_sdk::TrailingSlash::Add => model::TrailingSlash::Add,
+    }
+}
+
 fn http_match_to_domain(v: oagw_sdk::HttpMatch) -> model::HttpMatch {
     model::HttpMatch {
         methods: v.methods.into_iter().map(http_method_to_domain).collect(),
@@ -453,6 +935,7 @@ fn http_match_to_domain(v: oagw_sdk::HttpMatch) -> model::HttpMatch {
             oagw_sdk::PathSuffixMode::Disabled => model::PathSuffixMode::Disabled,
             oagw_sdk::PathSuffixMode::Append => model::PathSuffixMode::Append,
         },
+        match_body: v.match_body.map(body_matcher_to_domain),
     }
 }
 
@@ -470,6 +953,21 @@ fn match_rules_to_domain(v: oagw_sdk::MatchRules) -> model::MatchRules {
     }
 }
 
+fn route_kind_to_domain(v: oagw_sdk::RouteKind) -> model::RouteKind {
+    match v {
+        oagw_sdk::RouteKind::Proxy => model::RouteKind::Proxy,
+        oagw_sdk::RouteKind::Static {
+            status,
+            headers,
+            body,
+        } => model::RouteKind::Static {
+            status,
+            headers,
+            body,
+        },
+    }
+}
+
 // ---------------------------------------------------------------------------
 // domain value types → SDK value types
 // ---------------------------------------------------------------------------
@@ -492,7 +990,15 @@ fn scheme_to_sdk(v: model::Scheme) -> oagw_sdk::Scheme {
     }
 }
 
-fn upstream_to_sdk(u: model::Upstream) -> oagw_sdk::Upstream {
+fn endpoint_to_sdk(e: model::Endpoint) -> oagw_sdk::Endpoint {
+    oagw_sdk::Endpoint {
+        scheme: scheme_to_sdk(e.scheme),
+        host: e.host,
+        port: e.port,
+    }
+}
+
+pub(super) fn upstream_to_sdk(u: model::Upstream) -> oagw_sdk::Upstream {
     oagw_sdk::Upstream {
         id: u.id,
         tenant_id: u.tenant_id,
@@ -502,71 +1008,171 @@ fn upstream_to_sdk(u: model::Upstream) -> oagw_sdk::Upstream {
                 .server
                 .endpoints
                 .into_iter()
-                .map(|e| oagw_sdk::Endpoint {
-                    scheme: scheme_to_sdk(e.scheme),
-                    host: e.host,
-                    port: e.port,
-                })
+                .map(endpoint_to_sdk)
                 .collect(),
         },
         protocol: u.protocol,
         enabled: u.enabled,
-        auth: u.auth.map(|a| oagw_sdk::AuthConfig {
-            plugin_type: a.plugin_type,
-            sharing: sharing_mode_to_sdk(a.sharing),
-            config: a.config,
-        }),
-        headers: u.headers.map(|h| oagw_sdk::HeadersConfig {
-            request: h.request.map(|r| oagw_sdk::RequestHeaderRules {
-                set: r.set,
-                add: r.add,
-                remove: r.remove,
-                passthrough: match r.passthrough {
-                    model::PassthroughMode::None => oagw_sdk::PassthroughMode::None,
-                    model::PassthroughMode::Allowlist => oagw_sdk::PassthroughMode::Allowlist,
-                    model::PassthroughMode::All => oagw_sdk::PassthroughMode::All,
-                },
-                passthrough_allowlist: r.passthrough_allowlist,
-            }),
-            response: h.response.map(|r| oagw_sdk::ResponseHeaderRules {
-                set: r.set,
-                add: r.add,
-                remove: r.remove,
-            }),
-        }),
+        auth: u.auth.map(auth_config_to_sdk),
+        headers: u.headers.map(headers_config_to_sdk),
         plugins: u.plugins.map(|p| oagw_sdk::PluginsConfig {
             sharing: sharing_mode_to_sdk(p.sharing),
             items: p.items,
         }),
         rate_limit: u.rate_limit.map(rate_limit_config_to_sdk),
         tags: u.tags,
+        maintenance: u.maintenance,
+        maintenance_message: u.maintenance_message,
+        retry_on_body: u.retry_on_body.map(retry_on_body_to_sdk),
+        force_chunked: u.force_chunked,
+        failover: u.failover.into_iter().map(endpoint_to_sdk).collect(),
+        timeout_secs: u.timeout_secs,
+        keepalive: u.keepalive.map(|k| oagw_sdk::KeepaliveConfig {
+            tcp_idle_secs: k.tcp_idle_secs,
+            tcp_interval_secs: k.tcp_interval_secs,
+            tcp_probe_count: k.tcp_probe_count,
+            h2_ping_interval_secs: k.h2_ping_interval_secs,
+        }),
+        circuit_breaker: u.circuit_breaker.map(|c| oagw_sdk::CircuitBreakerConfig {
+            failure_threshold: c.failure_threshold,
+            cooldown_secs: c.cooldown_secs,
+            half_open_probes: c.half_open_probes,
+        }),
+        user_agent: u.user_agent,
+        sticky_by: u.sticky_by,
+        allowed_methods: u
+            .allowed_methods
+            .into_iter()
+            .map(http_method_to_sdk)
+            .collect(),
+        trailing_slash: match u.trailing_slash {
+            model::TrailingSlash::Preserve => oagw_sdk::TrailingSlash::Preserve,
+            model::TrailingSlash::Strip => oagw_sdk::TrailingSlash::Strip,
+            model::TrailingSlash::Add => oagw_sdk::TrailingSlash::Add,
+        },
+        max_header_size_bytes: u.max_header_size_bytes,
+        tls_min_version: u.tls_min_version,
+        tls_cipher_suites: u.tls_cipher_suites,
+        tls_server_name: u.tls_server_name,
+        budget_header: u.budget_header,
     }
 }
 
-fn route_to_sdk(r: model::Route) -> oagw_sdk::Route {
+fn auth_config_to_sdk(a: model::AuthConfig) -> oagw_sdk::AuthConfig {
+    oagw_sdk::AuthConfig {
+        plugin_type: a.plugin_type,
+        sharing: sharing_mode_to_sdk(a.sharing),
+        config: a.config,
+    }
+}
+
+fn headers_config_to_sdk(h: model::HeadersConfig) -> oagw_sdk::HeadersConfig {
+    oagw_sdk::HeadersConfig {
+        request: h.request.map(|r| oagw_sdk::RequestHeaderRules {
+            set: r.set,
+            add: r.add,
+            remove: r.remove,
+            passthrough: match r.passthrough {
+                model::PassthroughMode::None => oagw_sdk::PassthroughMode::None,
+                model::PassthroughMode::Allowlist => oagw_sdk::PassthroughMode::Allowlist,
+                model::PassthroughMode::All => oagw_sdk::PassthroughMode::All,
+            },
+            passthrough_allowlist: r.passthrough_allowlist,
+        }),
+        response: h.response.map(|r| oagw_sdk::ResponseHeaderRules {
+            set: r.set,
+            add: r.add,
+            remove: r.remove,
+        }),
+    }
+}
+
+fn sourced_to_sdk<T, U>(s: model::Sourced<T>, f: impl FnOnce(T) -> U) -> oagw_sdk::Sourced<U> {
+    oagw_sdk::Sourced {
+        value: f(s.value),
+        source: match s.source {
+            model::ConfigSource::Route => oagw_sdk::ConfigSource::Route,
+            model::ConfigSource::Upstream => oagw_sdk::ConfigSource::Upstream,
+            model::ConfigSource::Ancestor => oagw_sdk::ConfigSource::Ancestor,
+            model::ConfigSource::TenantPolicy => oagw_sdk::ConfigSource::TenantPolicy,
+            model::ConfigSource::Gateway => oagw_sdk::ConfigSource::Gateway,
+        },
+    }
+}
+
+fn route_description_to_sdk(d: model::RouteDescription) -> oagw_sdk::RouteDescription {
+    oagw_sdk::RouteDescription {
+        route: route_to_sdk(d.route),
+        upstream_id: d.upstream_id,
+        timeout_secs: sourced_to_sdk(d.timeout_secs, |v| v),
+        user_agent: sourced_to_sdk(d.user_agent, |v| v),
+        auth: d.auth.map(|a| sourced_to_sdk(a, auth_config_to_sdk)),
+        headers: d.headers.map(|h| sourced_to_sdk(h, headers_config_to_sdk)),
+        rate_limit: d
+            .rate_limit
+            .map(|rl| sourced_to_sdk(rl, rate_limit_config_to_sdk)),
+        circuit_failure_threshold: sourced_to_sdk(d.circuit_failure_threshold, |v| v),
+        circuit_cooldown_secs: sourced_to_sdk(d.circuit_cooldown_secs, |v| v),
+        circuit_half_open_probes: sourced_to_sdk(d.circuit_half_open_probes, |v| v),
+    }
+}
+
+fn request_trace_to_sdk(t: model::RequestTrace) -> oagw_sdk::RequestTrace {
+    oagw_sdk::RequestTrace {
+        route_id: t.route_id,
+        upstream_id: t.upstream_id,
+        endpoint: t.endpoint,
+        authz: t.authz.map(|a| match a {
+            model::TraceAuthz::Allowed => oagw_sdk::TraceAuthz::Allowed,
+            model::TraceAuthz::Denied => oagw_sdk::TraceAuthz::Denied,
+            model::TraceAuthz::Skipped => oagw_sdk::TraceAuthz::Skipped,
+        }),
+        plugins: t.plugins,
+        transforms: t.transforms,
+        upstream_called: t.upstream_called,
+        status: t.status,
+        error: t.error,
+    }
+}
+
+fn runtime_state_to_sdk(s: model::UpstreamRuntimeState) -> oagw_sdk::UpstreamRuntimeState {
+    oagw_sdk::UpstreamRuntimeState {
+        upstream_id: s.upstream_id,
+        circuit: match s.circuit {
+            CircuitStatus::Closed { failures } => oagw_sdk::CircuitStatus::Closed { failures },
+            CircuitStatus::Open { retry_after_secs } => {
+                oagw_sdk::CircuitStatus::Open { retry_after_secs }
+            }
+            CircuitStatus::HalfOpen => oagw_sdk::CircuitStatus::HalfOpen,
+        },
+        rate_limits: s
+            .rate_limits
+            .into_iter()
+            .map(|(key, status)| oagw_sdk::RateLimitBucketState {
+                key,
+                limit: status.limit,
+                remaining: status.remaining,
+                reset_secs: status.reset_secs,
+            })
+            .collect(),
+    }
+}
+
+pub(super) fn route_to_sdk(r: model::Route) -> oagw_sdk::Route {
     oagw_sdk::Route {
         id: r.id,
         tenant_id: r.tenant_id,
         upstream_id: r.upstream_id,
         match_rules: oagw_sdk::MatchRules {
             http: r.match_rules.http.map(|h| oagw_sdk::HttpMatch {
-                methods: h
-                    .methods
-                    .into_iter()
-                    .map(|m| match m {
-                        model::HttpMethod::Get => oagw_sdk::HttpMethod::Get,
-                        model::HttpMethod::Post => oagw_sdk::HttpMethod::Post,
-                        model::HttpMethod::Put => oagw_sdk::HttpMethod::Put,
-                        model::HttpMethod::Delete => oagw_sdk::HttpMethod::Delete,
-                        model::HttpMethod::Patch => oagw_sdk::HttpMethod::Patch,
-                    })
-                    .collect(),
+                methods: h.methods.into_iter().map(http_method_to_sdk).collect(),
                 path: h.path,
                 query_allowlist: h.query_allowlist,
                 path_suffix_mode: match h.path_suffix_mode {
                     model::PathSuffixMode::Disabled => oagw_sdk::PathSuffixMode::Disabled,
                     model::PathSuffixMode::Append => oagw_sdk::PathSuffixMode::Append,
                 },
+                match_body: h.match_body.map(body_matcher_to_sdk),
             }),
             grpc: r.match_rules.grpc.map(|g| oagw_sdk::GrpcMatch {
                 service: g.service,
@@ -581,6 +1187,98 @@ fn route_to_sdk(r: model::Route) -> oagw_sdk::Route {
         tags: r.tags,
         priority: r.priority,
         enabled: r.enabled,
+        skip_authz: r.skip_authz,
+        timeout_secs: r.timeout_secs,
+        status_remap: r.status_remap,
+        allowed_response_content_types: r.allowed_response_content_types,
+        allowed_request_content_types: r.allowed_request_content_types,
+        log_sample_rate: r.log_sample_rate,
+        request_schema: r.request_schema,
+        body_rewrite: r
+            .body_rewrite
+            .into_iter()
+            .map(body_rewrite_to_sdk)
+            .collect(),
+        auth_rules: r.auth_rules.into_iter().map(auth_rule_to_sdk).collect(),
+        strip_query_params: r.strip_query_params,
+        strip_inbound_headers: r.strip_inbound_headers,
+        idempotency_ttl_secs: r.idempotency_ttl_secs,
+        cache_responses: r.cache_responses,
+        negative_cache_ttl_secs: r.negative_cache_ttl_secs,
+        response_header_allowlist: r.response_header_allowlist,
+        max_total_duration_ms: r.max_total_duration_ms,
+        max_body_size_bytes: r.max_body_size_bytes,
+        max_outbound_calls: r.max_outbound_calls,
+        coerce_content_type: r.coerce_content_type,
+        api_version: r.api_version.map(api_version_to_sdk),
+        body_transformers: r.body_transformers,
+        log_redact_paths: r.log_redact_paths,
+        transform_hook_url: r.transform_hook_url,
+        deprecated: r.deprecated,
+        sunset: r.sunset,
+        kind: route_kind_to_sdk(r.kind),
+    }
+}
+
+fn route_kind_to_sdk(v: model::RouteKind) -> oagw_sdk::RouteKind {
+    match v {
+        model::RouteKind::Proxy => oagw_sdk::RouteKind::Proxy,
+        model::RouteKind::Static {
+            status,
+            headers,
+            body,
+        } => oagw_sdk::RouteKind::Static {
+            status,
+            headers,
+            body,
+        },
+    }
+}
+
+fn body_matcher_to_sdk(v: model::BodyMatcher) -> oagw_sdk::BodyMatcher {
+    match v {
+        model::BodyMatcher::JsonPointer { pointer, value } => {
+            oagw_sdk::BodyMatcher::JsonPointer { pointer, value }
+        }
+        model::BodyMatcher::Regex { pattern } => oagw_sdk::BodyMatcher::Regex { pattern },
+    }
+}
+
+fn api_version_to_sdk(v: model::ApiVersion) -> oagw_sdk::ApiVersion {
+    oagw_sdk::ApiVersion {
+        value: v.value,
+        header: v.header,
+        query_param: v.query_param,
+    }
+}
+
+fn auth_rule_to_sdk(v: model::AuthRule) -> oagw_sdk::AuthRule {
+    oagw_sdk::AuthRule {
+        condition: match v.condition {
+            model::AuthCondition::PathPrefix { prefix } => {
+                oagw_sdk::AuthCondition::PathPrefix { prefix }
+            }
+            model::AuthCondition::Header { name, value } => {
+                oagw_sdk::AuthCondition::Header { name, value }
+            }
+        },
+        auth: v.auth.map(auth_config_to_sdk),
+    }
+}
+
+fn body_rewrite_to_sdk(v: model::BodyRewriteRule) -> oagw_sdk::BodyRewriteRule {
+    oagw_sdk::BodyRewriteRule {
+        find: v.find,
+        replace: v.replace,
+        regex: v.regex,
+        content_types: v.content_types,
+    }
+}
+
+fn retry_on_body_to_sdk(v: model::RetryOnBody) -> oagw_sdk::RetryOnBody {
+    oagw_sdk::RetryOnBody {
+        matcher: body_matcher_to_sdk(v.matcher),
+        max_retries: v.max_retries,
     }
 }
 
@@ -680,6 +1378,23 @@ mod tests {
             plugins: None,
             rate_limit: None,
             tags: vec![],
+            maintenance: false,
+            maintenance_message: None,
+            retry_on_body: None,
+            force_chunked: false,
+            failover: vec![],
+            timeout_secs: None,
+            keepalive: None,
+            circuit_breaker: None,
+            user_agent: None,
+            sticky_by: None,
+            allowed_methods: vec![],
+            trailing_slash: model::TrailingSlash::default(),
+            max_header_size_bytes: None,
+            tls_min_version: None,
+            tls_cipher_suites: vec![],
+            tls_server_name: None,
+            budget_header: None,
         };
 
         let sdk = upstream_to_sdk(domain_upstream);
@@ -722,6 +1437,7 @@ mod tests {
             detail: "too fast".into(),
             instance: "/api".into(),
             retry_after_secs: Some(30),
+            headers: Vec::new(),
         };
         let sdk_err = domain_err_to_sdk(err);
         match sdk_err {
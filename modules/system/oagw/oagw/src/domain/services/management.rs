@@ -1,14 +1,25 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::ControlPlaneService;
+use super::client::{route_to_sdk, upstream_to_sdk};
 use std::net::IpAddr;
 
 use crate::domain::error::DomainError;
+use crate::domain::gts_helpers::APIKEY_AUTH_PLUGIN_ID;
+use crate::domain::header_template;
 use crate::domain::model::{
-    CreateRouteRequest, CreateUpstreamRequest, Endpoint, ListQuery, Route, UpdateRouteRequest,
+    ApiVersion, AuthCondition, AuthConfig, AuthRule, BodyMatcher, BodyRewriteRule,
+    CircuitBreakerConfig, ConfigProblem, ConfigSnapshot, CreateRouteRequest, CreateUpstreamRequest,
+    Endpoint, HeadersConfig, KeepaliveConfig, ListQuery, MatchRules, RateLimitConfig, RetryOnBody,
+    Route, RouteKind, StickyBy, TenantAuthDefault, TenantRateLimit, UpdateRouteRequest,
     UpdateUpstreamRequest, Upstream,
 };
-use crate::domain::repo::{RouteRepository, UpstreamRepository};
+use crate::domain::plugin::resolve_secret_ref;
+use crate::domain::repo::{
+    RepositoryError, RouteRepository, TenantAuthDefaultRepository, TenantRateLimitRepository,
+    UpstreamRepository,
+};
 
 use async_trait::async_trait;
 use authz_resolver_sdk::PolicyEnforcer;
@@ -16,6 +27,7 @@ use authz_resolver_sdk::pep::{AccessRequest, ResourceType};
 use credstore_sdk::CredStoreClientV1;
 use modkit_macros::domain_model;
 use modkit_security::SecurityContext;
+use oagw_sdk::{AuditAction, AuditEntityType, AuditEvent, AuditSink, AuditSnapshot};
 use tenant_resolver_sdk::TenantResolverClient;
 use uuid::Uuid;
 
@@ -33,14 +45,27 @@ mod bind_actions {
     pub const ADD_PLUGINS: &str = "add_plugins";
 }
 
+/// Default for `max_upstreams_per_tenant`.
+const MAX_UPSTREAMS_PER_TENANT: usize = 1000;
+
+/// Default for `max_routes_per_tenant`.
+const MAX_ROUTES_PER_TENANT: usize = 10_000;
+
 /// Control Plane service implementation backed by in-memory repositories.
 #[domain_model]
 pub(crate) struct ControlPlaneServiceImpl {
     upstreams: Arc<dyn UpstreamRepository>,
     routes: Arc<dyn RouteRepository>,
+    tenant_rate_limits: Arc<dyn TenantRateLimitRepository>,
+    tenant_auth_defaults: Arc<dyn TenantAuthDefaultRepository>,
     tenant_resolver: Arc<dyn TenantResolverClient>,
     policy_enforcer: PolicyEnforcer,
     credstore: Arc<dyn CredStoreClientV1>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    /// Upstreams a tenant may own; `0` means unlimited.
+    max_upstreams_per_tenant: usize,
+    /// Routes a tenant may own; `0` means unlimited.
+    max_routes_per_tenant: usize,
 }
 
 impl ControlPlaneServiceImpl {
@@ -48,6 +73,8 @@ impl ControlPlaneServiceImpl {
     pub(crate) fn new(
         upstreams: Arc<dyn UpstreamRepository>,
         routes: Arc<dyn RouteRepository>,
+        tenant_rate_limits: Arc<dyn TenantRateLimitRepository>,
+        tenant_auth_defaults: Arc<dyn TenantAuthDefaultRepository>,
         tenant_resolver: Arc<dyn TenantResolverClient>,
         policy_enforcer: PolicyEnforcer,
         credstore: Arc<dyn CredStoreClientV1>,
@@ -55,11 +82,32 @@ impl ControlPlaneServiceImpl {
         Self {
             upstreams,
             routes,
+            tenant_rate_limits,
+            tenant_auth_defaults,
             tenant_resolver,
             policy_enforcer,
             credstore,
+            audit_sink: None,
+            max_upstreams_per_tenant: MAX_UPSTREAMS_PER_TENANT,
+            max_routes_per_tenant: MAX_ROUTES_PER_TENANT,
         }
     }
+
+    /// Emit an [`AuditEvent`] for every upstream/route create, update, and delete.
+    #[must_use]
+    pub(crate) fn with_audit_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(sink);
+        self
+    }
+
+    /// Cap how many upstreams and routes each tenant may own (`0` for no
+    /// limit). Creates past a cap fail with `QuotaExceeded`.
+    #[must_use]
+    pub(crate) fn with_tenant_quotas(mut self, max_upstreams: usize, max_routes: usize) -> Self {
+        self.max_upstreams_per_tenant = max_upstreams;
+        self.max_routes_per_tenant = max_routes;
+        self
+    }
 }
 
 // ===========================================================================
@@ -75,51 +123,63 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         ctx: &SecurityContext,
         req: CreateUpstreamRequest,
     ) -> Result<Upstream, DomainError> {
-        validate_endpoints(&req.server.endpoints)?;
-
-        let tenant_id = ctx.subject_tenant_id();
-        let id = Uuid::new_v4();
-
-        let upstream = Upstream {
-            id,
-            tenant_id,
-            alias: String::new(),
-            server: req.server.clone(),
-            protocol: req.protocol.clone(),
-            enabled: req.enabled,
-            auth: req.auth.clone(),
-            headers: req.headers.clone(),
-            plugins: req.plugins.clone(),
-            rate_limit: req.rate_limit.clone(),
-            tags: req.tags.clone(),
-        };
-
-        let alias = req
-            .alias
-            .clone()
-            .unwrap_or_else(|| generate_alias(&upstream));
-
-        validate_alias(&alias)?;
-
-        // Check if an ancestor tenant has an upstream with this alias.
-        // If so, this is a "bind" operation requiring ancestor bind validation.
-        self.validate_ancestor_bind(
-            ctx,
-            &alias,
-            &BindOverrides {
-                auth: req.auth.as_ref(),
-                rate_limit: req.rate_limit.as_ref(),
-                plugins: req.plugins.as_ref(),
-            },
-        )
-        .await?;
-
-        let upstream = Upstream { alias, ..upstream };
-
-        self.upstreams
+        let upstream = self.prepare_upstream(ctx, req).await?;
+        self.check_upstream_quota(ctx, 1).await?;
+        let created = self
+            .upstreams
             .create(upstream)
             .await
-            .map_err(DomainError::from)
+            .map_err(DomainError::from)?;
+        self.audit_upstream_created(ctx, &created).await;
+        Ok(created)
+    }
+
+    async fn create_upstreams(
+        &self,
+        ctx: &SecurityContext,
+        reqs: Vec<CreateUpstreamRequest>,
+    ) -> Result<Vec<Upstream>, DomainError> {
+        let mut prepared = Vec::with_capacity(reqs.len());
+        for (index, req) in reqs.into_iter().enumerate() {
+            let upstream = self
+                .prepare_upstream(ctx, req)
+                .await
+                .map_err(|e| batch_item_error(index, e))?;
+            if let Some(dup) = prepared
+                .iter()
+                .position(|u: &Upstream| u.alias == upstream.alias)
+            {
+                return Err(batch_item_error(
+                    index,
+                    DomainError::conflict(format!(
+                        "alias '{}' is already used by item {dup}",
+                        upstream.alias
+                    )),
+                ));
+            }
+            prepared.push(upstream);
+        }
+        self.check_upstream_quota(ctx, prepared.len()).await?;
+
+        let tenant_id = ctx.subject_tenant_id();
+        let mut created = Vec::with_capacity(prepared.len());
+        for (index, upstream) in prepared.into_iter().enumerate() {
+            match self.upstreams.create(upstream).await {
+                Ok(u) => created.push(u),
+                Err(e) => {
+                    for u in &created {
+                        if let Err(e) = self.upstreams.delete(tenant_id, u.id).await {
+                            tracing::error!(upstream_id = %u.id, error = %e, "batch rollback failed");
+                        }
+                    }
+                    return Err(batch_item_error(index, DomainError::from(e)));
+                }
+            }
+        }
+        for u in &created {
+            self.audit_upstream_created(ctx, u).await;
+        }
+        Ok(created)
     }
 
     async fn get_upstream(&self, ctx: &SecurityContext, id: Uuid) -> Result<Upstream, DomainError> {
@@ -154,6 +214,7 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             .get_by_id(tenant_id, id)
             .await
             .map_err(|_| DomainError::not_found("upstream", id))?;
+        let before = existing.clone();
 
         // Apply partial update.
         if let Some(server) = req.server {
@@ -199,9 +260,11 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             existing.alias = alias;
         }
         if let Some(auth) = req.auth {
+            validate_auth(Some(&auth))?;
             existing.auth = Some(auth);
         }
         if let Some(headers) = req.headers {
+            validate_headers(Some(&headers))?;
             existing.headers = Some(headers);
         }
         if let Some(plugins) = req.plugins {
@@ -216,24 +279,152 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         if let Some(enabled) = req.enabled {
             existing.enabled = enabled;
         }
+        if let Some(maintenance) = req.maintenance {
+            existing.maintenance = maintenance;
+            // Leaving maintenance drops the stale message unless a new one is supplied.
+            if !maintenance {
+                existing.maintenance_message = None;
+            }
+        }
+        if let Some(message) = req.maintenance_message {
+            existing.maintenance_message = Some(message);
+        }
+        if let Some(force_chunked) = req.force_chunked {
+            existing.force_chunked = force_chunked;
+        }
+        if let Some(failover) = req.failover {
+            validate_failover(&existing.server.endpoints, &failover)?;
+            existing.failover = failover;
+        }
+        if let Some(retry_on_body) = req.retry_on_body {
+            validate_retry_on_body(Some(&retry_on_body))?;
+            existing.retry_on_body = Some(retry_on_body);
+        }
+        if let Some(timeout_secs) = req.timeout_secs {
+            validate_timeout_secs(Some(timeout_secs))?;
+            existing.timeout_secs = Some(timeout_secs);
+        }
+        if let Some(keepalive) = req.keepalive {
+            validate_keepalive(Some(&keepalive))?;
+            existing.keepalive = Some(keepalive);
+        }
+        if let Some(circuit_breaker) = req.circuit_breaker {
+            validate_circuit_breaker(Some(&circuit_breaker))?;
+            existing.circuit_breaker = Some(circuit_breaker);
+        }
+        if let Some(user_agent) = req.user_agent {
+            validate_user_agent(Some(&user_agent))?;
+            existing.user_agent = Some(user_agent);
+        }
+        if let Some(sticky_by) = req.sticky_by {
+            validate_sticky_by(Some(&sticky_by))?;
+            existing.sticky_by = Some(sticky_by);
+        }
+        if let Some(allowed_methods) = req.allowed_methods {
+            existing.allowed_methods = allowed_methods;
+        }
+        if let Some(trailing_slash) = req.trailing_slash {
+            existing.trailing_slash = trailing_slash;
+        }
+        if let Some(max_header_size_bytes) = req.max_header_size_bytes {
+            validate_max_header_size(Some(max_header_size_bytes))?;
+            existing.max_header_size_bytes = Some(max_header_size_bytes);
+        }
+        if let Some(tls_min_version) = req.tls_min_version {
+            validate_tls_min_version(Some(&tls_min_version))?;
+            existing.tls_min_version = Some(tls_min_version);
+        }
+        if let Some(tls_cipher_suites) = req.tls_cipher_suites {
+            validate_tls_cipher_suites(&tls_cipher_suites)?;
+            existing.tls_cipher_suites = tls_cipher_suites;
+        }
+        if let Some(tls_server_name) = req.tls_server_name {
+            validate_tls_server_name(Some(&tls_server_name))?;
+            existing.tls_server_name = Some(tls_server_name);
+        }
+        if let Some(budget_header) = req.budget_header {
+            validate_budget_header(Some(&budget_header))?;
+            existing.budget_header = Some(budget_header);
+        }
 
-        self.upstreams
+        let updated = self
+            .upstreams
             .update(existing)
             .await
-            .map_err(DomainError::from)
+            .map_err(DomainError::from)?;
+        self.audit(
+            ctx,
+            AuditAction::Update,
+            AuditEntityType::Upstream,
+            id,
+            Some(AuditSnapshot::Upstream(Box::new(upstream_to_sdk(before)))),
+            Some(AuditSnapshot::Upstream(Box::new(upstream_to_sdk(
+                updated.clone(),
+            )))),
+        )
+        .await;
+        Ok(updated)
     }
 
-    async fn delete_upstream(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError> {
+    async fn delete_upstream(
+        &self,
+        ctx: &SecurityContext,
+        id: Uuid,
+        cascade: bool,
+    ) -> Result<(), DomainError> {
         let tenant_id = ctx.subject_tenant_id();
-        // Cascade delete routes before removing the upstream.
+        let before = self
+            .upstreams
+            .get_by_id(tenant_id, id)
+            .await
+            .map_err(|_| DomainError::not_found("upstream", id))?;
+        let dependents = self
+            .routes
+            .list_by_upstream(
+                tenant_id,
+                id,
+                &ListQuery {
+                    top: u32::MAX,
+                    skip: 0,
+                },
+            )
+            .await
+            .map_err(DomainError::from)?;
+        if !dependents.is_empty() && !cascade {
+            let ids: Vec<String> = dependents.iter().map(|r| r.id.to_string()).collect();
+            return Err(DomainError::conflict(format!(
+                "upstream {id} is referenced by routes [{}]; delete them first or retry with cascade",
+                ids.join(", ")
+            )));
+        }
+
         self.routes
             .delete_by_upstream(tenant_id, id)
             .await
             .map_err(DomainError::from)?;
-        self.upstreams
-            .delete(tenant_id, id)
-            .await
-            .map_err(|_| DomainError::not_found("upstream", id))
+        if let Err(e) = self.upstreams.delete(tenant_id, id).await {
+            // Restore the routes so a failed delete leaves no orphans behind.
+            for route in dependents {
+                if let Err(restore_err) = self.routes.create(route).await {
+                    tracing::error!(upstream_id = %id, error = %restore_err, "failed to restore route after aborted upstream delete");
+                }
+            }
+            tracing::debug!(upstream_id = %id, error = %e, "upstream delete failed");
+            return Err(DomainError::not_found("upstream", id));
+        }
+        if let Err(e) = self.tenant_rate_limits.delete_by_upstream(id).await {
+            tracing::warn!(upstream_id = %id, error = %e, "failed to delete tenant rate limits of deleted upstream");
+        }
+        self.audit(
+            ctx,
+            AuditAction::Delete,
+            AuditEntityType::Upstream,
+            id,
+            Some(AuditSnapshot::Upstream(Box::new(upstream_to_sdk(before)))),
+            None,
+        )
+        .await;
+        Ok(())
     }
 
     // -- Route CRUD --
@@ -243,31 +434,50 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         ctx: &SecurityContext,
         req: CreateRouteRequest,
     ) -> Result<Route, DomainError> {
-        let tenant_id = ctx.subject_tenant_id();
-        // Validate that the upstream exists and belongs to this tenant.
-        self.upstreams
-            .get_by_id(tenant_id, req.upstream_id)
-            .await
-            .map_err(|_| {
-                DomainError::validation(format!(
-                    "upstream '{}' not found for this tenant",
-                    req.upstream_id
-                ))
-            })?;
+        let route = self.prepare_route(ctx, req).await?;
+        self.check_route_quota(ctx, 1).await?;
+        log_auth_bypass(&route);
 
-        let route = Route {
-            id: Uuid::new_v4(),
-            tenant_id,
-            upstream_id: req.upstream_id,
-            match_rules: req.match_rules,
-            plugins: req.plugins,
-            rate_limit: req.rate_limit,
-            tags: req.tags,
-            priority: req.priority,
-            enabled: req.enabled,
-        };
+        let created = self.routes.create(route).await.map_err(DomainError::from)?;
+        self.audit_route_created(ctx, &created).await;
+        Ok(created)
+    }
+
+    async fn create_routes(
+        &self,
+        ctx: &SecurityContext,
+        reqs: Vec<CreateRouteRequest>,
+    ) -> Result<Vec<Route>, DomainError> {
+        let mut prepared = Vec::with_capacity(reqs.len());
+        for (index, req) in reqs.into_iter().enumerate() {
+            let route = self
+                .prepare_route(ctx, req)
+                .await
+                .map_err(|e| batch_item_error(index, e))?;
+            prepared.push(route);
+        }
+        self.check_route_quota(ctx, prepared.len()).await?;
 
-        self.routes.create(route).await.map_err(DomainError::from)
+        let tenant_id = ctx.subject_tenant_id();
+        let mut created = Vec::with_capacity(prepared.len());
+        for (index, route) in prepared.into_iter().enumerate() {
+            log_auth_bypass(&route);
+            match self.routes.create(route).await {
+                Ok(r) => created.push(r),
+                Err(e) => {
+                    for r in &created {
+                        if let Err(e) = self.routes.delete(tenant_id, r.id).await {
+                            tracing::error!(route_id = %r.id, error = %e, "batch rollback failed");
+                        }
+                    }
+                    return Err(batch_item_error(index, DomainError::from(e)));
+                }
+            }
+        }
+        for r in &created {
+            self.audit_route_created(ctx, r).await;
+        }
+        Ok(created)
     }
 
     async fn get_route(&self, ctx: &SecurityContext, id: Uuid) -> Result<Route, DomainError> {
@@ -303,8 +513,10 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             .get_by_id(tenant_id, id)
             .await
             .map_err(|_| DomainError::not_found("route", id))?;
+        let before = existing.clone();
 
         if let Some(match_rules) = req.match_rules {
+            validate_match_body(&match_rules)?;
             existing.match_rules = match_rules;
         }
         if let Some(plugins) = req.plugins {
@@ -322,70 +534,655 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         if let Some(enabled) = req.enabled {
             existing.enabled = enabled;
         }
+        if let Some(skip_authz) = req.skip_authz {
+            existing.skip_authz = skip_authz;
+        }
+        if let Some(timeout_secs) = req.timeout_secs {
+            validate_timeout_secs(Some(timeout_secs))?;
+            existing.timeout_secs = Some(timeout_secs);
+        }
+        if let Some(status_remap) = req.status_remap {
+            validate_status_remap(&status_remap)?;
+            existing.status_remap = status_remap;
+        }
+        if let Some(types) = req.allowed_request_content_types {
+            validate_content_types(&types)?;
+            existing.allowed_request_content_types = types;
+        }
+        if let Some(types) = req.allowed_response_content_types {
+            validate_content_types(&types)?;
+            existing.allowed_response_content_types = types;
+        }
+        if let Some(rate) = req.log_sample_rate {
+            validate_log_sample_rate(Some(rate))?;
+            existing.log_sample_rate = Some(rate);
+        }
+        if let Some(schema) = req.request_schema {
+            validate_request_schema(Some(&schema))?;
+            existing.request_schema = Some(schema);
+        }
+        if let Some(rules) = req.body_rewrite {
+            validate_body_rewrite(&rules)?;
+            existing.body_rewrite = rules;
+        }
+        if let Some(rules) = req.auth_rules {
+            validate_auth_rules(&rules)?;
+            existing.auth_rules = rules;
+        }
+        if let Some(params) = req.strip_query_params {
+            validate_strip_query_params(&params)?;
+            existing.strip_query_params = params;
+        }
+        if let Some(names) = req.strip_inbound_headers {
+            validate_strip_inbound_headers(&names)?;
+            existing.strip_inbound_headers = names;
+        }
+        if let Some(secs) = req.idempotency_ttl_secs {
+            validate_idempotency_ttl(Some(secs))?;
+            existing.idempotency_ttl_secs = Some(secs);
+        }
+        if let Some(cache_responses) = req.cache_responses {
+            existing.cache_responses = cache_responses;
+        }
+        if let Some(secs) = req.negative_cache_ttl_secs {
+            validate_negative_cache_ttl(Some(secs))?;
+            existing.negative_cache_ttl_secs = Some(secs);
+        }
+        if let Some(names) = req.response_header_allowlist {
+            validate_response_header_allowlist(Some(&names))?;
+            existing.response_header_allowlist = Some(names);
+        }
+        if let Some(ms) = req.max_total_duration_ms {
+            validate_max_total_duration(Some(ms))?;
+            existing.max_total_duration_ms = Some(ms);
+        }
+        if let Some(bytes) = req.max_body_size_bytes {
+            validate_route_max_body_size(Some(bytes))?;
+            existing.max_body_size_bytes = Some(bytes);
+        }
+        if let Some(calls) = req.max_outbound_calls {
+            validate_max_outbound_calls(Some(calls))?;
+            existing.max_outbound_calls = Some(calls);
+        }
+        if let Some(content_type) = req.coerce_content_type {
+            validate_coerce_content_type(Some(&content_type))?;
+            existing.coerce_content_type = Some(content_type);
+        }
+        if let Some(version) = req.api_version {
+            validate_api_version(Some(&version))?;
+            existing.api_version = Some(version);
+        }
+        if let Some(names) = req.body_transformers {
+            validate_body_transformers(&names)?;
+            existing.body_transformers = names;
+        }
+        if let Some(paths) = req.log_redact_paths {
+            validate_log_redact_paths(&paths)?;
+            existing.log_redact_paths = paths;
+        }
+        if let Some(url) = req.transform_hook_url {
+            validate_transform_hook_url(Some(&url))?;
+            existing.transform_hook_url = Some(url);
+        }
+        if let Some(deprecated) = req.deprecated {
+            existing.deprecated = deprecated;
+        }
+        if let Some(sunset) = req.sunset {
+            validate_sunset(Some(&sunset))?;
+            existing.sunset = Some(sunset);
+        }
+        if let Some(kind) = req.kind {
+            existing.kind = kind;
+        }
+        validate_route_kind(&existing.kind, existing.upstream_id, &existing.match_rules)?;
+        log_auth_bypass(&existing);
 
-        self.routes
+        let updated = self
+            .routes
             .update(existing)
             .await
-            .map_err(DomainError::from)
+            .map_err(DomainError::from)?;
+        self.audit(
+            ctx,
+            AuditAction::Update,
+            AuditEntityType::Route,
+            id,
+            Some(AuditSnapshot::Route(Box::new(route_to_sdk(before)))),
+            Some(AuditSnapshot::Route(Box::new(route_to_sdk(
+                updated.clone(),
+            )))),
+        )
+        .await;
+        Ok(updated)
     }
 
     async fn delete_route(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError> {
         let tenant_id = ctx.subject_tenant_id();
+        let before = self
+            .routes
+            .get_by_id(tenant_id, id)
+            .await
+            .map_err(|_| DomainError::not_found("route", id))?;
         self.routes
             .delete(tenant_id, id)
             .await
-            .map_err(|_| DomainError::not_found("route", id))
+            .map_err(|_| DomainError::not_found("route", id))?;
+        self.audit(
+            ctx,
+            AuditAction::Delete,
+            AuditEntityType::Route,
+            id,
+            Some(AuditSnapshot::Route(Box::new(route_to_sdk(before)))),
+            None,
+        )
+        .await;
+        Ok(())
     }
 
-    // -- Resolution --
+    // -- Validation --
 
-    async fn resolve_proxy_target(
+    async fn validate_config(
         &self,
         ctx: &SecurityContext,
-        alias: &str,
-        method: &str,
-        path: &str,
-    ) -> Result<(Upstream, Route), DomainError> {
-        let tenant_chain = self.build_tenant_chain(ctx).await?;
-        let (effective, route) = self
-            .resolve_alias(ctx, &tenant_chain, alias, Some((method, path)))
-            .await?;
-        Ok((
-            effective,
-            route.expect("route always present when method+path provided"),
-        ))
+        snapshot: ConfigSnapshot,
+    ) -> Result<Vec<ConfigProblem>, DomainError> {
+        let tenant_id = ctx.subject_tenant_id();
+        let mut problems = Vec::new();
+
+        let mut aliases: Vec<String> = Vec::new();
+        for (index, req) in snapshot.upstreams.iter().enumerate() {
+            let path = format!("upstreams[{index}]");
+            let errors = upstream_request_errors(req);
+            if !errors.is_empty() {
+                for err in errors {
+                    problems.push(config_problem(&path, err)?);
+                }
+                continue;
+            }
+            // Alias and ancestor bind checks need the tenant hierarchy.
+            let upstream = match self.prepare_upstream(ctx, req.clone()).await {
+                Ok(upstream) => upstream,
+                Err(err) => {
+                    problems.push(config_problem(&path, err)?);
+                    continue;
+                }
+            };
+            if let Some(dup) = aliases.iter().position(|a| *a == upstream.alias) {
+                problems.push(ConfigProblem {
+                    path,
+                    detail: format!(
+                        "alias '{}' is already used by upstreams[{dup}]",
+                        upstream.alias
+                    ),
+                });
+            } else if let Ok(existing) = self
+                .upstreams
+                .get_by_alias(tenant_id, &upstream.alias)
+                .await
+            {
+                problems.push(ConfigProblem {
+                    path,
+                    detail: format!(
+                        "alias '{}' is already used by upstream {}",
+                        upstream.alias, existing.id
+                    ),
+                });
+            }
+            aliases.push(upstream.alias);
+        }
+        if let Err(err) = self
+            .check_upstream_quota(ctx, snapshot.upstreams.len())
+            .await
+        {
+            problems.push(config_problem("upstreams", err)?);
+        }
+
+        for (index, req) in snapshot.routes.iter().enumerate() {
+            let path = format!("routes[{index}]");
+            let errors = route_request_errors(req);
+            if !errors.is_empty() {
+                for err in errors {
+                    problems.push(config_problem(&path, err)?);
+                }
+                continue;
+            }
+            if let Err(err) = self.prepare_route(ctx, req.clone()).await {
+                problems.push(config_problem(&path, err)?);
+            }
+        }
+        if let Err(err) = self.check_route_quota(ctx, snapshot.routes.len()).await {
+            problems.push(config_problem("routes", err)?);
+        }
+
+        Ok(problems)
     }
-}
 
-// ===========================================================================
-// Private helpers on ControlPlaneServiceImpl
-// ===========================================================================
+    // -- Tenant rate limits --
 
-impl ControlPlaneServiceImpl {
-    /// Validate bind constraints against the **closest** ancestor with a matching
-    /// alias. Delegates to [`validate_bind_constraints`] for policy permissions,
-    /// sharing mode enforcement, and `secret_ref` accessibility.
-    ///
-    /// Only the closest ancestor is checked — not the entire chain. This is
-    /// intentional: permission to bind is granted by the immediate owner of
-    /// the alias. Grandparent `enforce` constraints still propagate at runtime
-    /// through [`compute_effective_config`], which merges the full chain
-    /// root → descendant.
-    ///
-    /// No-op if no ancestor has the alias (fresh upstream, no bind needed).
-    async fn validate_ancestor_bind(
+    async fn set_tenant_rate_limit(
         &self,
         ctx: &SecurityContext,
-        alias: &str,
-        overrides: &BindOverrides<'_>,
-    ) -> Result<(), DomainError> {
-        let tenant_chain = self.build_tenant_chain(ctx).await?;
-
-        for &ancestor_tid in &tenant_chain[1..] {
-            if let Ok(ancestor_upstream) = self.upstreams.get_by_alias(ancestor_tid, alias).await {
-                validate_bind_constraints(
-                    &self.policy_enforcer,
-                    self.credstore.as_ref(),
+        upstream_id: Uuid,
+        tenant_id: Uuid,
+        rate_limit: RateLimitConfig,
+    ) -> Result<TenantRateLimit, DomainError> {
+        self.upstreams
+            .get_by_id(ctx.subject_tenant_id(), upstream_id)
+            .await
+            .map_err(|_| DomainError::not_found("upstream", upstream_id))?;
+        self.tenant_rate_limits
+            .upsert(TenantRateLimit {
+                upstream_id,
+                tenant_id,
+                rate_limit,
+            })
+            .await
+            .map_err(DomainError::from)
+    }
+
+    async fn get_tenant_rate_limit(
+        &self,
+        ctx: &SecurityContext,
+        upstream_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<TenantRateLimit, DomainError> {
+        self.upstreams
+            .get_by_id(ctx.subject_tenant_id(), upstream_id)
+            .await
+            .map_err(|_| DomainError::not_found("upstream", upstream_id))?;
+        self.tenant_rate_limits
+            .get(upstream_id, tenant_id)
+            .await
+            .map_err(DomainError::from)
+    }
+
+    async fn delete_tenant_rate_limit(
+        &self,
+        ctx: &SecurityContext,
+        upstream_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<(), DomainError> {
+        self.upstreams
+            .get_by_id(ctx.subject_tenant_id(), upstream_id)
+            .await
+            .map_err(|_| DomainError::not_found("upstream", upstream_id))?;
+        self.tenant_rate_limits
+            .delete(upstream_id, tenant_id)
+            .await
+            .map_err(DomainError::from)
+    }
+
+    // -- Tenant default auth --
+
+    async fn set_tenant_default_auth(
+        &self,
+        ctx: &SecurityContext,
+        auth: AuthConfig,
+    ) -> Result<TenantAuthDefault, DomainError> {
+        validate_auth(Some(&auth))?;
+        self.tenant_auth_defaults
+            .upsert(TenantAuthDefault {
+                tenant_id: ctx.subject_tenant_id(),
+                auth,
+            })
+            .await
+            .map_err(DomainError::from)
+    }
+
+    async fn get_tenant_default_auth(
+        &self,
+        ctx: &SecurityContext,
+    ) -> Result<TenantAuthDefault, DomainError> {
+        self.tenant_auth_defaults
+            .get(ctx.subject_tenant_id())
+            .await
+            .map_err(DomainError::from)
+    }
+
+    async fn delete_tenant_default_auth(&self, ctx: &SecurityContext) -> Result<(), DomainError> {
+        self.tenant_auth_defaults
+            .delete(ctx.subject_tenant_id())
+            .await
+            .map_err(DomainError::from)
+    }
+
+    // -- Resolution --
+
+    async fn resolve_proxy_target(
+        &self,
+        ctx: &SecurityContext,
+        alias: &str,
+        method: &str,
+        path: &str,
+        body: Option<&[u8]>,
+    ) -> Result<(Upstream, Route), DomainError> {
+        let tenant_chain = self.build_tenant_chain(ctx).await?;
+        let (effective, route) = self
+            .resolve_alias(ctx, &tenant_chain, alias, Some((method, path, body)))
+            .await?;
+        Ok((
+            effective,
+            route.expect("route always present when method+path provided"),
+        ))
+    }
+
+    async fn resolve_static_route(
+        &self,
+        ctx: &SecurityContext,
+        method: &str,
+        path: &str,
+    ) -> Result<Option<Route>, DomainError> {
+        // Static routes carry no upstream, so they are indexed under the nil id.
+        match self
+            .routes
+            .find_matching(ctx.subject_tenant_id(), Uuid::nil(), method, path, None)
+            .await
+        {
+            Ok(route) => Ok(route.kind.is_static().then_some(route)),
+            Err(RepositoryError::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn resolve_tenant_rate_limit(
+        &self,
+        ctx: &SecurityContext,
+        upstream_id: Uuid,
+    ) -> Result<Option<TenantRateLimit>, DomainError> {
+        match self
+            .tenant_rate_limits
+            .get(upstream_id, ctx.subject_tenant_id())
+            .await
+        {
+            Ok(limit) => Ok(Some(limit)),
+            Err(RepositoryError::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn resolve_tenant_default_auth(
+        &self,
+        ctx: &SecurityContext,
+    ) -> Result<Option<AuthConfig>, DomainError> {
+        match self.tenant_auth_defaults.get(ctx.subject_tenant_id()).await {
+            Ok(default) => Ok(Some(default.auth)),
+            Err(RepositoryError::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn resolve_route_config(
+        &self,
+        ctx: &SecurityContext,
+        route_id: Uuid,
+    ) -> Result<(Upstream, Upstream, Route), DomainError> {
+        let route = self.get_route(ctx, route_id).await?;
+        let tenant_chain = self.build_tenant_chain(ctx).await?;
+
+        // The route may be attached to an ancestor's upstream; only its alias
+        // matters, since that is what the caller's requests resolve.
+        let mut alias = None;
+        for &tid in &tenant_chain {
+            if let Ok(upstream) = self.upstreams.get_by_id(tid, route.upstream_id).await {
+                alias = Some(upstream.alias);
+                break;
+            }
+        }
+        let alias = alias.ok_or_else(|| DomainError::not_found("upstream", route.upstream_id))?;
+
+        let (inherited, _) = self.resolve_alias(ctx, &tenant_chain, &alias, None).await?;
+        let selected = self
+            .upstreams
+            .get_by_id(inherited.tenant_id, inherited.id)
+            .await?;
+        let effective = compute_effective_config(std::slice::from_ref(&inherited), Some(&route));
+        Ok((selected, effective, route))
+    }
+}
+
+// ===========================================================================
+// Private helpers on ControlPlaneServiceImpl
+// ===========================================================================
+
+/// Method, path and (when buffered) body of a request to resolve a route for.
+type RouteRequest<'a> = (&'a str, &'a str, Option<&'a [u8]>);
+
+impl ControlPlaneServiceImpl {
+    /// Forward a configuration change to the audit sink, if one is configured.
+    async fn audit(
+        &self,
+        ctx: &SecurityContext,
+        action: AuditAction,
+        entity_type: AuditEntityType,
+        entity_id: Uuid,
+        before: Option<AuditSnapshot>,
+        after: Option<AuditSnapshot>,
+    ) {
+        let Some(sink) = &self.audit_sink else {
+            return;
+        };
+        sink.record(AuditEvent {
+            subject_id: ctx.subject_id(),
+            subject_tenant_id: ctx.subject_tenant_id(),
+            action,
+            entity_type,
+            entity_id,
+            before,
+            after,
+        })
+        .await;
+    }
+
+    /// Reject creating `adding` upstreams that would take the caller's
+    /// tenant past `max_upstreams_per_tenant`.
+    async fn check_upstream_quota(
+        &self,
+        ctx: &SecurityContext,
+        adding: usize,
+    ) -> Result<(), DomainError> {
+        if self.max_upstreams_per_tenant == 0 {
+            return Ok(());
+        }
+        let owned = self.upstreams.count(ctx.subject_tenant_id()).await?;
+        check_quota("upstreams", self.max_upstreams_per_tenant, owned, adding)
+    }
+
+    /// Route counterpart of [`Self::check_upstream_quota`].
+    async fn check_route_quota(
+        &self,
+        ctx: &SecurityContext,
+        adding: usize,
+    ) -> Result<(), DomainError> {
+        if self.max_routes_per_tenant == 0 {
+            return Ok(());
+        }
+        let owned = self.routes.count(ctx.subject_tenant_id()).await?;
+        check_quota("routes", self.max_routes_per_tenant, owned, adding)
+    }
+
+    /// Validate a create request and build the upstream it describes,
+    /// including the alias and ancestor bind checks. Nothing is stored.
+    async fn prepare_upstream(
+        &self,
+        ctx: &SecurityContext,
+        req: CreateUpstreamRequest,
+    ) -> Result<Upstream, DomainError> {
+        if let Some(err) = upstream_request_errors(&req).into_iter().next() {
+            return Err(err);
+        }
+
+        let tenant_id = ctx.subject_tenant_id();
+        let id = Uuid::new_v4();
+
+        let upstream = Upstream {
+            id,
+            tenant_id,
+            alias: String::new(),
+            server: req.server.clone(),
+            protocol: req.protocol.clone(),
+            enabled: req.enabled,
+            auth: req.auth.clone(),
+            headers: req.headers.clone(),
+            plugins: req.plugins.clone(),
+            rate_limit: req.rate_limit.clone(),
+            tags: req.tags.clone(),
+            maintenance: req.maintenance,
+            maintenance_message: req.maintenance_message.clone(),
+            retry_on_body: req.retry_on_body.clone(),
+            force_chunked: req.force_chunked,
+            failover: req.failover.clone(),
+            timeout_secs: req.timeout_secs,
+            keepalive: req.keepalive,
+            circuit_breaker: req.circuit_breaker,
+            user_agent: req.user_agent.clone(),
+            sticky_by: req.sticky_by.clone(),
+            allowed_methods: req.allowed_methods.clone(),
+            trailing_slash: req.trailing_slash,
+            max_header_size_bytes: req.max_header_size_bytes,
+            tls_min_version: req.tls_min_version.clone(),
+            tls_cipher_suites: req.tls_cipher_suites.clone(),
+            tls_server_name: req.tls_server_name.clone(),
+            budget_header: req.budget_header.clone(),
+        };
+
+        let alias = req
+            .alias
+            .clone()
+            .unwrap_or_else(|| generate_alias(&upstream));
+
+        validate_alias(&alias)?;
+
+        // Check if an ancestor tenant has an upstream with this alias.
+        // If so, this is a "bind" operation requiring ancestor bind validation.
+        self.validate_ancestor_bind(
+            ctx,
+            &alias,
+            &BindOverrides {
+                auth: req.auth.as_ref(),
+                rate_limit: req.rate_limit.as_ref(),
+                plugins: req.plugins.as_ref(),
+            },
+        )
+        .await?;
+
+        Ok(Upstream { alias, ..upstream })
+    }
+
+    async fn audit_upstream_created(&self, ctx: &SecurityContext, created: &Upstream) {
+        self.audit(
+            ctx,
+            AuditAction::Create,
+            AuditEntityType::Upstream,
+            created.id,
+            None,
+            Some(AuditSnapshot::Upstream(Box::new(upstream_to_sdk(
+                created.clone(),
+            )))),
+        )
+        .await;
+    }
+
+    /// Validate a create request and build the route it describes. Nothing
+    /// is stored.
+    async fn prepare_route(
+        &self,
+        ctx: &SecurityContext,
+        req: CreateRouteRequest,
+    ) -> Result<Route, DomainError> {
+        if let Some(err) = route_request_errors(&req).into_iter().next() {
+            return Err(err);
+        }
+        let tenant_id = ctx.subject_tenant_id();
+        // Validate that the upstream exists and belongs to this tenant.
+        if !req.kind.is_static() {
+            self.upstreams
+                .get_by_id(tenant_id, req.upstream_id)
+                .await
+                .map_err(|_| {
+                    DomainError::validation(format!(
+                        "upstream '{}' not found for this tenant",
+                        req.upstream_id
+                    ))
+                })?;
+        }
+
+        Ok(Route {
+            id: Uuid::new_v4(),
+            tenant_id,
+            upstream_id: req.upstream_id,
+            match_rules: req.match_rules,
+            plugins: req.plugins,
+            rate_limit: req.rate_limit,
+            tags: req.tags,
+            priority: req.priority,
+            enabled: req.enabled,
+            skip_authz: req.skip_authz,
+            timeout_secs: req.timeout_secs,
+            status_remap: req.status_remap,
+            allowed_response_content_types: req.allowed_response_content_types,
+            allowed_request_content_types: req.allowed_request_content_types,
+            log_sample_rate: req.log_sample_rate,
+            request_schema: req.request_schema,
+            body_rewrite: req.body_rewrite,
+            auth_rules: req.auth_rules,
+            strip_query_params: req.strip_query_params,
+            strip_inbound_headers: req.strip_inbound_headers,
+            idempotency_ttl_secs: req.idempotency_ttl_secs,
+            cache_responses: req.cache_responses,
+            negative_cache_ttl_secs: req.negative_cache_ttl_secs,
+            response_header_allowlist: req.response_header_allowlist.clone(),
+            max_total_duration_ms: req.max_total_duration_ms,
+            max_body_size_bytes: req.max_body_size_bytes,
+            max_outbound_calls: req.max_outbound_calls,
+            coerce_content_type: req.coerce_content_type,
+            api_version: req.api_version,
+            body_transformers: req.body_transformers,
+            log_redact_paths: req.log_redact_paths,
+            transform_hook_url: req.transform_hook_url,
+            deprecated: req.deprecated,
+            sunset: req.sunset,
+            kind: req.kind,
+        })
+    }
+
+    async fn audit_route_created(&self, ctx: &SecurityContext, created: &Route) {
+        self.audit(
+            ctx,
+            AuditAction::Create,
+            AuditEntityType::Route,
+            created.id,
+            None,
+            Some(AuditSnapshot::Route(Box::new(route_to_sdk(
+                created.clone(),
+            )))),
+        )
+        .await;
+    }
+
+    /// Validate bind constraints against the **closest** ancestor with a matching
+    /// alias. Delegates to [`validate_bind_constraints`] for policy permissions,
+    /// sharing mode enforcement, and `secret_ref` accessibility.
+    ///
+    /// Only the closest ancestor is checked — not the entire chain. This is
+    /// intentional: permission to bind is granted by the immediate owner of
+    /// the alias. Grandparent `enforce` constraints still propagate at runtime
+    /// through [`compute_effective_config`], which merges the full chain
+    /// root → descendant.
+    ///
+    /// No-op if no ancestor has the alias (fresh upstream, no bind needed).
+    async fn validate_ancestor_bind(
+        &self,
+        ctx: &SecurityContext,
+        alias: &str,
+        overrides: &BindOverrides<'_>,
+    ) -> Result<(), DomainError> {
+        let tenant_chain = self.build_tenant_chain(ctx).await?;
+
+        for &ancestor_tid in &tenant_chain[1..] {
+            if let Ok(ancestor_upstream) = self.upstreams.get_by_alias(ancestor_tid, alias).await {
+                validate_bind_constraints(
+                    &self.policy_enforcer,
+                    self.credstore.as_ref(),
                     ctx,
                     &ancestor_upstream,
                     overrides,
@@ -431,7 +1228,7 @@ impl ControlPlaneServiceImpl {
     /// upstreams in one pass. The winning (closest enabled) upstream is selected
     /// and ancestors above it form the merge chain — no second pass needed.
     ///
-    /// When `method_path` is `Some((method, path))`, a route is also resolved
+    /// When `request` is `Some((method, path, body))`, a route is also resolved
     /// across the tenant chain (searching by each ancestor upstream ID) and
     /// folded into the effective config via `compute_effective_config`.
     pub(crate) async fn resolve_alias(
@@ -439,7 +1236,7 @@ impl ControlPlaneServiceImpl {
         ctx: &SecurityContext,
         tenant_chain: &[Uuid],
         alias: &str,
-        method_path: Option<(&str, &str)>,
+        request: Option<RouteRequest<'_>>,
     ) -> Result<(Upstream, Option<Route>), DomainError> {
         let tenant_id = ctx.subject_tenant_id();
 
@@ -482,7 +1279,7 @@ impl ControlPlaneServiceImpl {
         // Resolve route if method+path provided.
         // Search by each upstream ID in the chain — routes may be attached to
         // the selected upstream or any ancestor upstream.
-        let route = if let Some((method, path)) = method_path {
+        let route = if let Some((method, path, body)) = request {
             let mut route_found: Option<Route> = None;
 
             // Try selected upstream's ID first (most specific).
@@ -492,6 +1289,7 @@ impl ControlPlaneServiceImpl {
                 selected_upstream.id,
                 method,
                 path,
+                body,
             )
             .await
             {
@@ -507,6 +1305,7 @@ impl ControlPlaneServiceImpl {
                         ancestor.id,
                         method,
                         path,
+                        body,
                     )
                     .await
                     {
@@ -547,9 +1346,13 @@ impl ControlPlaneServiceImpl {
         upstream_id: Uuid,
         method: &str,
         path: &str,
+        body: Option<&[u8]>,
     ) -> Result<Route, DomainError> {
         for &tid in tenant_chain {
-            if let Ok(route) = routes.find_matching(tid, upstream_id, method, path).await {
+            if let Ok(route) = routes
+                .find_matching(tid, upstream_id, method, path, body)
+                .await
+            {
                 return Ok(route);
             }
         }
@@ -561,6 +1364,19 @@ impl ControlPlaneServiceImpl {
 // Free functions — validation, permissions, visibility, config merge, alias
 // ===========================================================================
 
+/// Fail when owning `owned + adding` of `kind` would exceed `limit`.
+fn check_quota(kind: &str, limit: usize, owned: usize, adding: usize) -> Result<(), DomainError> {
+    if owned.saturating_add(adding) <= limit {
+        return Ok(());
+    }
+    Err(DomainError::QuotaExceeded {
+        detail: format!(
+            "tenant may own at most {limit} {kind} and already owns {owned}; \
+             cannot create {adding} more"
+        ),
+    })
+}
+
 /// Validate the endpoint list for a server configuration.
 ///
 /// Rules:
@@ -629,6 +1445,31 @@ fn validate_endpoints(endpoints: &[Endpoint]) -> Result<(), DomainError> {
     Ok(())
 }
 
+/// Validate failover targets: each must be a usable endpoint and share the
+/// primary pool's scheme. Ports may differ (e.g. a secondary region).
+fn validate_failover(primary: &[Endpoint], failover: &[Endpoint]) -> Result<(), DomainError> {
+    for (i, ep) in failover.iter().enumerate() {
+        if strip_brackets(&ep.host)
+            .parse::<std::net::Ipv6Addr>()
+            .is_ok()
+        {
+            return Err(DomainError::validation(format!(
+                "failover[{i}] uses IPv6 address '{}'; IPv6 endpoints are not yet supported",
+                ep.host
+            )));
+        }
+        if let Some(first) = primary.first()
+            && ep.scheme != first.scheme
+        {
+            return Err(DomainError::validation(format!(
+                "failover[{i}] scheme {:?} differs from endpoint[0] scheme {:?}",
+                ep.scheme, first.scheme
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Maximum length for an upstream alias.
 const MAX_ALIAS_LENGTH: usize = 253;
 
@@ -653,12 +1494,625 @@ fn validate_alias(alias: &str) -> Result<(), DomainError> {
     Ok(())
 }
 
-/// Strip surrounding `[` and `]` from a host string so that bracketed IPv6
-/// literals (e.g. `[2001:db8::1]`) can be parsed by `Ipv6Addr` / `IpAddr`.
-fn strip_brackets(host: &str) -> &str {
-    host.strip_prefix('[')
-        .and_then(|s| s.strip_suffix(']'))
-        .unwrap_or(host)
+/// Reject API key auth configured with both a `secret_ref` and an inline
+/// `value`.
+fn validate_auth(auth: Option<&AuthConfig>) -> Result<(), DomainError> {
+    let Some(auth) = auth else {
+        return Ok(());
+    };
+    if auth.plugin_type == APIKEY_AUTH_PLUGIN_ID
+        && let Some(config) = &auth.config
+        && config.contains_key("secret_ref")
+        && config.contains_key("value")
+    {
+        return Err(DomainError::validation(
+            "auth.config: secret_ref and value are mutually exclusive",
+        ));
+    }
+    Ok(())
+}
+
+/// Reject a zero proxy timeout; `None` means "inherit the next level".
+fn validate_timeout_secs(timeout_secs: Option<u64>) -> Result<(), DomainError> {
+    if timeout_secs == Some(0) {
+        return Err(DomainError::validation(
+            "timeout_secs must be greater than 0",
+        ));
+    }
+    Ok(())
+}
+
+/// Reject status remaps whose source or target is not a valid HTTP status.
+fn validate_status_remap(remap: &HashMap<u16, u16>) -> Result<(), DomainError> {
+    for (&from, &to) in remap {
+        for code in [from, to] {
+            if !(100..=599).contains(&code) {
+                return Err(DomainError::validation(format!(
+                    "status_remap: {code} is not a valid HTTP status code"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Static routes answer locally: they must not reference an upstream, need
+/// HTTP match rules, and must describe a well-formed response. Proxy routes
+/// must reference an upstream.
+fn validate_route_kind(
+    kind: &RouteKind,
+    upstream_id: Uuid,
+    match_rules: &MatchRules,
+) -> Result<(), DomainError> {
+    let RouteKind::Static {
+        status, headers, ..
+    } = kind
+    else {
+        if upstream_id.is_nil() {
+            return Err(DomainError::validation(
+                "proxy routes must reference an upstream",
+            ));
+        }
+        return Ok(());
+    };
+    if !upstream_id.is_nil() {
+        return Err(DomainError::validation(
+            "static routes must not reference an upstream",
+        ));
+    }
+    let Some(http_match) = &match_rules.http else {
+        return Err(DomainError::validation(
+            "static routes require HTTP match rules",
+        ));
+    };
+    // Static routes are matched before the request body is read.
+    if http_match.match_body.is_some() {
+        return Err(DomainError::validation(
+            "static routes cannot match on the request body",
+        ));
+    }
+    if !(100..=599).contains(status) {
+        return Err(DomainError::validation(format!(
+            "static response status {status} is not a valid HTTP status code"
+        )));
+    }
+    for (name, value) in headers {
+        if http::HeaderName::from_bytes(name.as_bytes()).is_err()
+            || http::HeaderValue::from_str(value).is_err()
+        {
+            return Err(DomainError::validation(format!(
+                "static response header '{name}' is not a valid HTTP header"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reject allowlist entries that are not bare `type/subtype` media types.
+fn validate_content_types(types: &[String]) -> Result<(), DomainError> {
+    for t in types {
+        let valid = t.split_once('/').is_some_and(|(ty, sub)| {
+            !ty.trim().is_empty() && !sub.trim().is_empty() && !sub.contains(['/', ';'])
+        });
+        if !valid {
+            return Err(DomainError::validation(format!(
+                "'{t}' is not a media type of the form type/subtype"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_log_sample_rate(rate: Option<f64>) -> Result<(), DomainError> {
+    if let Some(rate) = rate
+        && !(0.0..=1.0).contains(&rate)
+    {
+        return Err(DomainError::validation(
+            "log_sample_rate must be between 0.0 and 1.0",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_request_schema(schema: Option<&serde_json::Value>) -> Result<(), DomainError> {
+    if let Some(schema) = schema
+        && let Err(e) = jsonschema::validator_for(schema)
+    {
+        return Err(DomainError::validation(format!(
+            "request_schema is not a valid JSON Schema: {e}"
+        )));
+    }
+    Ok(())
+}
+
+/// Reject auth rules whose condition can never match a request, and check
+/// each rule's auth like an upstream's.
+fn validate_auth_rules(rules: &[AuthRule]) -> Result<(), DomainError> {
+    for (i, rule) in rules.iter().enumerate() {
+        match &rule.condition {
+            AuthCondition::PathPrefix { prefix } if !prefix.starts_with('/') => {
+                return Err(DomainError::validation(format!(
+                    "auth_rules[{i}].condition.prefix must start with '/'"
+                )));
+            }
+            AuthCondition::Header { name, .. }
+                if http::HeaderName::from_bytes(name.as_bytes()).is_err() =>
+            {
+                return Err(DomainError::validation(format!(
+                    "auth_rules[{i}].condition.name is not a valid header name: '{name}'"
+                )));
+            }
+            _ => {}
+        }
+        validate_auth(rule.auth.as_ref())?;
+    }
+    Ok(())
+}
+
+/// Reject rewrite rules with nothing to find, an invalid pattern, or a
+/// malformed content type.
+fn validate_body_rewrite(rules: &[BodyRewriteRule]) -> Result<(), DomainError> {
+    for (i, rule) in rules.iter().enumerate() {
+        if rule.find.is_empty() {
+            return Err(DomainError::validation(format!(
+                "body_rewrite[{i}].find must not be empty"
+            )));
+        }
+        if rule.regex
+            && let Err(e) = regex::Regex::new(&rule.find)
+        {
+            return Err(DomainError::validation(format!(
+                "body_rewrite[{i}].find is not a valid regex: {e}"
+            )));
+        }
+        validate_content_types(&rule.content_types)?;
+    }
+    Ok(())
+}
+
+/// Reject blank query parameter names in `strip_query_params`.
+fn validate_strip_query_params(params: &[String]) -> Result<(), DomainError> {
+    if let Some(i) = params.iter().position(|p| p.trim().is_empty()) {
+        return Err(DomainError::validation(format!(
+            "strip_query_params[{i}] must not be empty"
+        )));
+    }
+    Ok(())
+}
+
+/// Reject `response_header_allowlist` entries that are not header names.
+fn validate_response_header_allowlist(names: Option<&[String]>) -> Result<(), DomainError> {
+    for (i, name) in names.unwrap_or_default().iter().enumerate() {
+        if http::HeaderName::from_bytes(name.as_bytes()).is_err() {
+            return Err(DomainError::validation(format!(
+                "response_header_allowlist[{i}] is not a valid header name"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reject invalid header names in a route's `strip_inbound_headers`.
+fn validate_strip_inbound_headers(names: &[String]) -> Result<(), DomainError> {
+    for (i, name) in names.iter().enumerate() {
+        if http::HeaderName::from_bytes(name.as_bytes()).is_err() {
+            return Err(DomainError::validation(format!(
+                "strip_inbound_headers[{i}] is not a valid header name"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Longest time a response is kept for idempotency-key replay.
+const MAX_IDEMPOTENCY_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Validate a route's `idempotency_ttl_secs`: 1 second to 24 hours.
+fn validate_idempotency_ttl(secs: Option<u64>) -> Result<(), DomainError> {
+    if let Some(secs) = secs
+        && !(1..=MAX_IDEMPOTENCY_TTL_SECS).contains(&secs)
+    {
+        return Err(DomainError::validation(format!(
+            "idempotency_ttl_secs must be between 1 and {MAX_IDEMPOTENCY_TTL_SECS}"
+        )));
+    }
+    Ok(())
+}
+
+/// Longest time a 404/410 response is cached, so that a resource created
+/// upstream is not hidden for long.
+const MAX_NEGATIVE_CACHE_TTL_SECS: u64 = 5 * 60;
+
+/// Validate a route's `negative_cache_ttl_secs`: 1 second to 5 minutes.
+fn validate_negative_cache_ttl(secs: Option<u64>) -> Result<(), DomainError> {
+    if let Some(secs) = secs
+        && !(1..=MAX_NEGATIVE_CACHE_TTL_SECS).contains(&secs)
+    {
+        return Err(DomainError::validation(format!(
+            "negative_cache_ttl_secs must be between 1 and {MAX_NEGATIVE_CACHE_TTL_SECS}"
+        )));
+    }
+    Ok(())
+}
+
+/// A route's `max_total_duration_ms` SLA must leave time for a response.
+fn validate_max_total_duration(ms: Option<u64>) -> Result<(), DomainError> {
+    if ms == Some(0) {
+        return Err(DomainError::validation(
+            "max_total_duration_ms must be greater than 0",
+        ));
+    }
+    Ok(())
+}
+
+/// A route's `max_body_size_bytes` of 0 would reject every request body.
+fn validate_route_max_body_size(bytes: Option<u64>) -> Result<(), DomainError> {
+    if bytes == Some(0) {
+        return Err(DomainError::validation(
+            "max_body_size_bytes must be greater than 0",
+        ));
+    }
+    Ok(())
+}
+
+/// A route's `max_outbound_calls` of 0 would fail every request.
+fn validate_max_outbound_calls(calls: Option<u32>) -> Result<(), DomainError> {
+    if calls == Some(0) {
+        return Err(DomainError::validation(
+            "max_outbound_calls must be greater than 0",
+        ));
+    }
+    Ok(())
+}
+
+/// Content types a route may force onto outbound requests.
+const COERCIBLE_CONTENT_TYPES: &[&str] = &[
+    "application/json",
+    "application/xml",
+    "application/x-www-form-urlencoded",
+    "application/octet-stream",
+    "text/plain",
+    "text/xml",
+];
+
+/// `coerce_content_type` must name one of [`COERCIBLE_CONTENT_TYPES`],
+/// optionally with parameters such as `charset`.
+fn validate_coerce_content_type(content_type: Option<&str>) -> Result<(), DomainError> {
+    let Some(content_type) = content_type else {
+        return Ok(());
+    };
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    if !COERCIBLE_CONTENT_TYPES
+        .iter()
+        .any(|t| t.eq_ignore_ascii_case(media_type))
+        || http::HeaderValue::from_str(content_type).is_err()
+    {
+        return Err(DomainError::validation(format!(
+            "coerce_content_type '{content_type}' must be one of: {}",
+            COERCIBLE_CONTENT_TYPES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// The version must be a valid header value, sent under a valid header name
+/// and, when set, a non-blank query parameter.
+fn validate_api_version(version: Option<&ApiVersion>) -> Result<(), DomainError> {
+    let Some(version) = version else {
+        return Ok(());
+    };
+    if version.value.trim().is_empty() || http::HeaderValue::from_str(&version.value).is_err() {
+        return Err(DomainError::validation(format!(
+            "api_version.value is not a valid header value: '{}'",
+            version.value
+        )));
+    }
+    if let Some(name) = &version.header
+        && http::HeaderName::from_bytes(name.as_bytes()).is_err()
+    {
+        return Err(DomainError::validation(format!(
+            "api_version.header is not a valid header name: '{name}'"
+        )));
+    }
+    if version
+        .query_param
+        .as_ref()
+        .is_some_and(|p| p.trim().is_empty())
+    {
+        return Err(DomainError::validation(
+            "api_version.query_param must not be empty",
+        ));
+    }
+    Ok(())
+}
+
+/// Reject blank and repeated names in `body_transformers`. Whether a name is
+/// registered is only known to the proxy, which fails requests naming an
+/// unknown transformer.
+fn validate_body_transformers(names: &[String]) -> Result<(), DomainError> {
+    for (i, name) in names.iter().enumerate() {
+        if name.trim().is_empty() {
+            return Err(DomainError::validation(format!(
+                "body_transformers[{i}] must not be empty"
+            )));
+        }
+        if names[..i].contains(name) {
+            return Err(DomainError::validation(format!(
+                "body_transformers[{i}]: '{name}' is listed more than once"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reject `log_redact_paths` entries that are not JSON pointers to a field;
+/// the whole body (`""`) cannot be redacted.
+fn validate_log_redact_paths(paths: &[String]) -> Result<(), DomainError> {
+    for (i, path) in paths.iter().enumerate() {
+        if !path.starts_with('/') {
+            return Err(DomainError::validation(format!(
+                "log_redact_paths[{i}]: '{path}' must start with '/'"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// `transform_hook_url` must be an absolute `http`/`https` URL. Whether
+/// plain HTTP is allowed is decided at proxy time, as for endpoints.
+fn validate_transform_hook_url(url: Option<&str>) -> Result<(), DomainError> {
+    let Some(url) = url else {
+        return Ok(());
+    };
+    match url::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") && parsed.has_host() => Ok(()),
+        _ => Err(DomainError::validation(format!(
+            "transform_hook_url '{url}' must be an absolute http or https URL"
+        ))),
+    }
+}
+
+/// `sunset` is sent verbatim as the `Sunset` header, so it must be an
+/// HTTP-date (RFC 9110 §5.6.7).
+fn validate_sunset(sunset: Option<&str>) -> Result<(), DomainError> {
+    let Some(sunset) = sunset else {
+        return Ok(());
+    };
+    httpdate::parse_http_date(sunset).map(drop).map_err(|_| {
+        DomainError::validation(format!(
+            "sunset '{sunset}' must be an HTTP-date, e.g. 'Sat, 01 Nov 2025 00:00:00 GMT'"
+        ))
+    })
+}
+
+/// Maximum number of body-triggered retries per request.
+const MAX_BODY_RETRIES: u32 = 5;
+
+/// Reject a body retry condition that could never match or would retry
+/// unboundedly.
+fn validate_retry_on_body(retry: Option<&RetryOnBody>) -> Result<(), DomainError> {
+    let Some(retry) = retry else {
+        return Ok(());
+    };
+    if !(1..=MAX_BODY_RETRIES).contains(&retry.max_retries) {
+        return Err(DomainError::validation(format!(
+            "retry_on_body.max_retries must be between 1 and {MAX_BODY_RETRIES}"
+        )));
+    }
+    validate_body_matcher("retry_on_body.match", &retry.matcher)
+}
+
+/// Reject keep-alive settings that would probe continuously or never.
+fn validate_keepalive(keepalive: Option<&KeepaliveConfig>) -> Result<(), DomainError> {
+    let Some(keepalive) = keepalive else {
+        return Ok(());
+    };
+    for (field, value) in [
+        ("tcp_idle_secs", keepalive.tcp_idle_secs),
+        ("tcp_interval_secs", keepalive.tcp_interval_secs),
+        ("tcp_probe_count", keepalive.tcp_probe_count.map(u64::from)),
+        ("h2_ping_interval_secs", keepalive.h2_ping_interval_secs),
+    ] {
+        if value == Some(0) {
+            return Err(DomainError::validation(format!(
+                "keepalive.{field} must be greater than 0"
+            )));
+        }
+    }
+    if keepalive.tcp_idle_secs.is_none()
+        && (keepalive.tcp_interval_secs.is_some() || keepalive.tcp_probe_count.is_some())
+    {
+        return Err(DomainError::validation(
+            "keepalive.tcp_interval_secs and tcp_probe_count require tcp_idle_secs",
+        ));
+    }
+    Ok(())
+}
+
+/// Reject circuit breaker overrides that would never open, never probe, or
+/// never leave the open state.
+fn validate_circuit_breaker(cb: Option<&CircuitBreakerConfig>) -> Result<(), DomainError> {
+    let Some(cb) = cb else {
+        return Ok(());
+    };
+    for (field, value) in [
+        ("failure_threshold", cb.failure_threshold.map(u64::from)),
+        ("cooldown_secs", cb.cooldown_secs),
+        ("half_open_probes", cb.half_open_probes.map(u64::from)),
+    ] {
+        if value == Some(0) {
+            return Err(DomainError::validation(format!(
+                "circuit_breaker.{field} must be greater than 0"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reject a `User-Agent` that is empty or not a valid header value.
+fn validate_user_agent(user_agent: Option<&str>) -> Result<(), DomainError> {
+    if let Some(ua) = user_agent
+        && (ua.trim().is_empty() || !ua.bytes().all(|b| b == b'\t' || (0x20..0x7f).contains(&b)))
+    {
+        return Err(DomainError::validation(
+            "user_agent must be non-empty printable ASCII",
+        ));
+    }
+    Ok(())
+}
+
+/// Reject `sticky_by` values other than `tenant` or `header:<valid-name>`.
+fn validate_sticky_by(sticky_by: Option<&str>) -> Result<(), DomainError> {
+    if let Some(v) = sticky_by
+        && StickyBy::parse(v).is_none()
+    {
+        return Err(DomainError::validation(
+            "sticky_by must be 'tenant' or 'header:<name>'",
+        ));
+    }
+    Ok(())
+}
+
+/// An upstream's `max_header_size_bytes` of 0 would reject every request.
+fn validate_max_header_size(bytes: Option<u64>) -> Result<(), DomainError> {
+    if bytes == Some(0) {
+        return Err(DomainError::validation(
+            "max_header_size_bytes must be greater than 0",
+        ));
+    }
+    Ok(())
+}
+
+/// TLS versions an upstream's `tls_min_version` may name.
+const TLS_VERSIONS: &[&str] = &["1.2", "1.3"];
+
+/// Cipher suites the gateway's TLS client can negotiate.
+const TLS_CIPHER_SUITES: &[&str] = &[
+    "TLS13_AES_256_GCM_SHA384",
+    "TLS13_AES_128_GCM_SHA256",
+    "TLS13_CHACHA20_POLY1305_SHA256",
+    "TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384",
+    "TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256",
+    "TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256",
+    "TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384",
+    "TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256",
+    "TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256",
+];
+
+fn validate_tls_min_version(version: Option<&str>) -> Result<(), DomainError> {
+    match version {
+        Some(v) if !TLS_VERSIONS.contains(&v) => Err(DomainError::validation(format!(
+            "tls_min_version must be one of {}, got '{v}'",
+            TLS_VERSIONS.join(", ")
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn validate_tls_cipher_suites(suites: &[String]) -> Result<(), DomainError> {
+    match suites
+        .iter()
+        .find(|s| !TLS_CIPHER_SUITES.contains(&s.as_str()))
+    {
+        Some(s) => Err(DomainError::validation(format!(
+            "tls_cipher_suites: unsupported cipher suite '{s}'"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// The certificate name must be a DNS hostname: verifying against an IP
+/// literal is what the endpoint host already does.
+fn validate_tls_server_name(name: Option<&str>) -> Result<(), DomainError> {
+    let Some(name) = name else {
+        return Ok(());
+    };
+    let valid = name.len() <= 253
+        && name.parse::<IpAddr>().is_err()
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(DomainError::validation(format!(
+            "tls_server_name must be a DNS hostname, got '{name}'"
+        )))
+    }
+}
+
+/// An upstream's `budget_header` must be a valid header name.
+fn validate_budget_header(name: Option<&str>) -> Result<(), DomainError> {
+    if let Some(name) = name
+        && http::HeaderName::from_bytes(name.as_bytes()).is_err()
+    {
+        return Err(DomainError::validation(format!(
+            "budget_header is not a valid header name: '{name}'"
+        )));
+    }
+    Ok(())
+}
+
+/// Reject request header values whose `{{ctx.<field>}}` placeholders are
+/// malformed or name an unknown context field.
+fn validate_headers(headers: Option<&HeadersConfig>) -> Result<(), DomainError> {
+    let Some(rules) = headers.and_then(|h| h.request.as_ref()) else {
+        return Ok(());
+    };
+    for (kind, map) in [("set", &rules.set), ("add", &rules.add)] {
+        for (name, value) in map {
+            header_template::validate(value).map_err(|e| {
+                DomainError::validation(format!("headers.request.{kind}.{name}: {e}"))
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Reject a route body condition that could never match.
+fn validate_match_body(match_rules: &MatchRules) -> Result<(), DomainError> {
+    match match_rules
+        .http
+        .as_ref()
+        .and_then(|h| h.match_body.as_ref())
+    {
+        Some(matcher) => validate_body_matcher("match.http.match_body", matcher),
+        None => Ok(()),
+    }
+}
+
+/// Reject a malformed JSON pointer or regex; `field` names the matcher in
+/// error messages.
+fn validate_body_matcher(field: &str, matcher: &BodyMatcher) -> Result<(), DomainError> {
+    match matcher {
+        BodyMatcher::JsonPointer { pointer, .. } => {
+            if !pointer.is_empty() && !pointer.starts_with('/') {
+                return Err(DomainError::validation(format!(
+                    "{field}.pointer must be empty or start with '/'"
+                )));
+            }
+        }
+        BodyMatcher::Regex { pattern } => {
+            if let Err(e) = regex::Regex::new(pattern) {
+                return Err(DomainError::validation(format!(
+                    "{field}.pattern is not a valid regex: {e}"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Strip surrounding `[` and `]` from a host string so that bracketed IPv6
+/// literals (e.g. `[2001:db8::1]`) can be parsed by `Ipv6Addr` / `IpAddr`.
+fn strip_brackets(host: &str) -> &str {
+    host.strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .unwrap_or(host)
 }
 
 /// Generate an alias from the upstream's server endpoints.
@@ -672,6 +2126,104 @@ fn generate_alias(upstream: &Upstream) -> String {
     endpoints[0].alias_contribution()
 }
 
+/// Every failed self-contained check of an upstream create request, in the
+/// order [`ControlPlaneServiceImpl::prepare_upstream`] reports them.
+fn upstream_request_errors(req: &CreateUpstreamRequest) -> Vec<DomainError> {
+    [
+        validate_endpoints(&req.server.endpoints),
+        validate_failover(&req.server.endpoints, &req.failover),
+        validate_timeout_secs(req.timeout_secs),
+        validate_retry_on_body(req.retry_on_body.as_ref()),
+        validate_keepalive(req.keepalive.as_ref()),
+        validate_circuit_breaker(req.circuit_breaker.as_ref()),
+        validate_user_agent(req.user_agent.as_deref()),
+        validate_sticky_by(req.sticky_by.as_deref()),
+        validate_max_header_size(req.max_header_size_bytes),
+        validate_tls_min_version(req.tls_min_version.as_deref()),
+        validate_tls_cipher_suites(&req.tls_cipher_suites),
+        validate_tls_server_name(req.tls_server_name.as_deref()),
+        validate_budget_header(req.budget_header.as_deref()),
+        validate_headers(req.headers.as_ref()),
+        validate_auth(req.auth.as_ref()),
+    ]
+    .into_iter()
+    .filter_map(Result::err)
+    .collect()
+}
+
+/// Route counterpart of [`upstream_request_errors`].
+fn route_request_errors(req: &CreateRouteRequest) -> Vec<DomainError> {
+    [
+        validate_timeout_secs(req.timeout_secs),
+        validate_status_remap(&req.status_remap),
+        validate_content_types(&req.allowed_request_content_types),
+        validate_content_types(&req.allowed_response_content_types),
+        validate_log_sample_rate(req.log_sample_rate),
+        validate_request_schema(req.request_schema.as_ref()),
+        validate_body_rewrite(&req.body_rewrite),
+        validate_auth_rules(&req.auth_rules),
+        validate_strip_query_params(&req.strip_query_params),
+        validate_strip_inbound_headers(&req.strip_inbound_headers),
+        validate_idempotency_ttl(req.idempotency_ttl_secs),
+        validate_negative_cache_ttl(req.negative_cache_ttl_secs),
+        validate_response_header_allowlist(req.response_header_allowlist.as_deref()),
+        validate_max_total_duration(req.max_total_duration_ms),
+        validate_route_max_body_size(req.max_body_size_bytes),
+        validate_max_outbound_calls(req.max_outbound_calls),
+        validate_coerce_content_type(req.coerce_content_type.as_deref()),
+        validate_api_version(req.api_version.as_ref()),
+        validate_body_transformers(&req.body_transformers),
+        validate_log_redact_paths(&req.log_redact_paths),
+        validate_transform_hook_url(req.transform_hook_url.as_deref()),
+        validate_sunset(req.sunset.as_deref()),
+        validate_route_kind(&req.kind, req.upstream_id, &req.match_rules),
+        validate_match_body(&req.match_rules),
+    ]
+    .into_iter()
+    .filter_map(Result::err)
+    .collect()
+}
+
+/// Report `err` as a problem at `path` of a validated snapshot. Storage
+/// failures are not configuration problems and are passed through.
+fn config_problem(path: &str, err: DomainError) -> Result<ConfigProblem, DomainError> {
+    let detail = match err {
+        DomainError::Internal { .. } => return Err(err),
+        DomainError::Validation { detail, .. } | DomainError::Conflict { detail } => detail,
+        other => other.to_string(),
+    };
+    Ok(ConfigProblem {
+        path: path.to_string(),
+        detail,
+    })
+}
+
+/// Attribute a batch failure to the item at `index`.
+fn batch_item_error(index: usize, err: DomainError) -> DomainError {
+    match err {
+        DomainError::Validation { detail, instance } => DomainError::Validation {
+            detail: format!("item {index}: {detail}"),
+            instance,
+        },
+        DomainError::Conflict { detail } => DomainError::Conflict {
+            detail: format!("item {index}: {detail}"),
+        },
+        other => other,
+    }
+}
+
+/// Warn whenever a route is saved with an auth bypass so that public routes
+/// are always visible in the audit trail.
+fn log_auth_bypass(route: &Route) {
+    if route.skip_authz {
+        tracing::warn!(
+            route_id = %route.id,
+            upstream_id = %route.upstream_id,
+            "route configured with auth bypass"
+        );
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Ancestor bind validation
 // ---------------------------------------------------------------------------
@@ -751,7 +2303,14 @@ async fn validate_bind_constraints(
                 if let Some(ref config) = auth_override.config
                     && let Some(raw_ref) = config.get("secret_ref")
                 {
-                    validate_secret_ref_accessible(credstore, ctx, raw_ref).await?;
+                    // A missing per-tenant secret is fine when a default is configured.
+                    let primary = validate_secret_ref_accessible(credstore, ctx, raw_ref).await;
+                    match (primary, config.get("default_secret_ref")) {
+                        (Err(DomainError::Validation { .. }), Some(default_ref)) => {
+                            validate_secret_ref_accessible(credstore, ctx, default_ref).await?;
+                        }
+                        (result, _) => result?,
+                    }
                 }
             }
         }
@@ -822,7 +2381,7 @@ async fn validate_secret_ref_accessible(
     ctx: &SecurityContext,
     raw_ref: &str,
 ) -> Result<(), DomainError> {
-    let bare = raw_ref.strip_prefix("cred://").unwrap_or(raw_ref);
+    let bare = resolve_secret_ref(raw_ref, ctx.subject_tenant_id());
     let key = credstore_sdk::SecretRef::new(bare)
         .map_err(|e| DomainError::validation(format!("invalid secret_ref '{raw_ref}': {e}")))?;
 
@@ -923,13 +2482,30 @@ pub(crate) fn compute_effective_config(
             }
         }
 
-        // Server, protocol, enabled, alias: always use the selected upstream's values.
+        // Server, protocol, enabled, maintenance, retry, framing, timeout, keep-alive, circuit breaker, stickiness, allowed methods, trailing slash, header size limit, alias: always use the selected upstream's values.
         effective.id = layer.id;
         effective.tenant_id = layer.tenant_id;
         effective.alias = layer.alias.clone();
         effective.server = layer.server.clone();
         effective.protocol = layer.protocol.clone();
         effective.enabled = layer.enabled;
+        effective.maintenance = layer.maintenance;
+        effective.maintenance_message = layer.maintenance_message.clone();
+        effective.retry_on_body = layer.retry_on_body.clone();
+        effective.force_chunked = layer.force_chunked;
+        effective.failover = layer.failover.clone();
+        effective.timeout_secs = layer.timeout_secs;
+        effective.keepalive = layer.keepalive;
+        effective.circuit_breaker = layer.circuit_breaker;
+        effective.user_agent = layer.user_agent.clone();
+        effective.sticky_by = layer.sticky_by.clone();
+        effective.allowed_methods = layer.allowed_methods.clone();
+        effective.trailing_slash = layer.trailing_slash;
+        effective.max_header_size_bytes = layer.max_header_size_bytes;
+        effective.tls_min_version = layer.tls_min_version.clone();
+        effective.tls_cipher_suites = layer.tls_cipher_suites.clone();
+        effective.tls_server_name = layer.tls_server_name.clone();
+        effective.budget_header = layer.budget_header.clone();
         effective.headers = layer.headers.clone().or(effective.headers);
     }
 
@@ -1141,19 +2717,24 @@ mod tests {
     use std::sync::Arc;
 
     use crate::domain::model::{
-        Endpoint, HttpMatch, HttpMethod, MatchRules, PathSuffixMode, Scheme, Server,
+        Endpoint, HttpMatch, HttpMethod, MatchRules, PathSuffixMode, Scheme, Server, TrailingSlash,
     };
 
     use super::*;
     use crate::domain::test_support::{
         MockCredStoreClient, MockTenantResolverClient, allow_all_enforcer,
     };
-    use crate::infra::storage::{InMemoryRouteRepo, InMemoryUpstreamRepo};
+    use crate::infra::storage::{
+        InMemoryRouteRepo, InMemoryTenantAuthDefaultRepo, InMemoryTenantRateLimitRepo,
+        InMemoryUpstreamRepo,
+    };
 
     fn make_service() -> ControlPlaneServiceImpl {
         ControlPlaneServiceImpl::new(
             Arc::new(InMemoryUpstreamRepo::new()),
             Arc::new(InMemoryRouteRepo::new()),
+            Arc::new(InMemoryTenantRateLimitRepo::new()),
+            Arc::new(InMemoryTenantAuthDefaultRepo::new()),
             Arc::new(MockTenantResolverClient::single_tenant()),
             allow_all_enforcer(),
             Arc::new(MockCredStoreClient::empty()),
@@ -1164,6 +2745,8 @@ mod tests {
         ControlPlaneServiceImpl::new(
             Arc::new(InMemoryUpstreamRepo::new()),
             Arc::new(InMemoryRouteRepo::new()),
+            Arc::new(InMemoryTenantRateLimitRepo::new()),
+            Arc::new(InMemoryTenantAuthDefaultRepo::new()),
             Arc::new(resolver),
             allow_all_enforcer(),
             Arc::new(MockCredStoreClient::empty()),
@@ -1177,6 +2760,8 @@ mod tests {
         ControlPlaneServiceImpl::new(
             Arc::new(InMemoryUpstreamRepo::new()),
             Arc::new(InMemoryRouteRepo::new()),
+            Arc::new(InMemoryTenantRateLimitRepo::new()),
+            Arc::new(InMemoryTenantAuthDefaultRepo::new()),
             Arc::new(resolver),
             allow_all_enforcer(),
             Arc::new(MockCredStoreClient::with_secrets(creds)),
@@ -1208,6 +2793,23 @@ mod tests {
             rate_limit: None,
             tags: vec![],
             enabled: true,
+            maintenance: false,
+            maintenance_message: None,
+            retry_on_body: None,
+            force_chunked: false,
+            failover: vec![],
+            timeout_secs: None,
+            keepalive: None,
+            circuit_breaker: None,
+            user_agent: None,
+            sticky_by: None,
+            allowed_methods: vec![],
+            trailing_slash: TrailingSlash::default(),
+            max_header_size_bytes: None,
+            tls_min_version: None,
+            tls_cipher_suites: vec![],
+            tls_server_name: None,
+            budget_header: None,
         }
     }
 
@@ -1220,6 +2822,7 @@ mod tests {
                     path: "/v1/chat/completions".into(),
                     query_allowlist: vec![],
                     path_suffix_mode: PathSuffixMode::Append,
+                    match_body: None,
                 }),
                 grpc: None,
             },
@@ -1228,6 +2831,32 @@ mod tests {
             tags: vec![],
             priority: 0,
             enabled: true,
+            skip_authz: false,
+            timeout_secs: None,
+            status_remap: HashMap::new(),
+            allowed_response_content_types: vec![],
+            allowed_request_content_types: vec![],
+            log_sample_rate: None,
+            request_schema: None,
+            body_rewrite: vec![],
+            auth_rules: vec![],
+            strip_query_params: vec![],
+            strip_inbound_headers: vec![],
+            idempotency_ttl_secs: None,
+            cache_responses: false,
+            negative_cache_ttl_secs: None,
+            response_header_allowlist: None,
+            max_total_duration_ms: None,
+            max_body_size_bytes: None,
+            max_outbound_calls: None,
+            coerce_content_type: None,
+            api_version: None,
+            body_transformers: vec![],
+            log_redact_paths: vec![],
+            transform_hook_url: None,
+            deprecated: false,
+            sunset: None,
+            kind: RouteKind::Proxy,
         }
     }
 
@@ -1271,7 +2900,7 @@ mod tests {
         assert_eq!(list.len(), 1);
 
         // Delete
-        svc.delete_upstream(&ctx, u.id).await.unwrap();
+        svc.delete_upstream(&ctx, u.id, false).await.unwrap();
         assert!(svc.get_upstream(&ctx, u.id).await.is_err());
     }
 
@@ -1305,6 +2934,23 @@ mod tests {
             rate_limit: None,
             tags: vec![],
             enabled: true,
+            maintenance: false,
+            maintenance_message: None,
+            retry_on_body: None,
+            force_chunked: false,
+            failover: vec![],
+            timeout_secs: None,
+            keepalive: None,
+            circuit_breaker: None,
+            user_agent: None,
+            sticky_by: None,
+            allowed_methods: vec![],
+            trailing_slash: TrailingSlash::default(),
+            max_header_size_bytes: None,
+            tls_min_version: None,
+            tls_cipher_suites: vec![],
+            tls_server_name: None,
+            budget_header: None,
         };
         let u2 = svc.create_upstream(&ctx, req).await.unwrap();
         assert_eq!(u2.alias, "api.openai.com:8443");
@@ -1443,17 +3089,228 @@ mod tests {
         let tenant = Uuid::new_v4();
         let ctx = test_ctx(tenant);
 
-        let chain = svc.build_tenant_chain(&ctx).await.unwrap();
+        let chain = svc.build_tenant_chain(&ctx).await.unwrap();
+        let err = svc
+            .resolve_alias(&ctx, &chain, "nonexistent", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::NotFound { .. }));
+    }
+
+    #[tokio::test]
+    async fn route_matching_through_cp() {
+        let svc = make_service();
+        let tenant = Uuid::new_v4();
+        let ctx = test_ctx(tenant);
+
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+        let r = svc
+            .create_route(&ctx, make_create_route(u.id))
+            .await
+            .unwrap();
+
+        let chain = svc.build_tenant_chain(&ctx).await.unwrap();
+        let matched = ControlPlaneServiceImpl::find_route_in_chain(
+            &*svc.routes,
+            &chain,
+            u.id,
+            "POST",
+            "/v1/chat/completions",
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(matched.id, r.id);
+    }
+
+    #[tokio::test]
+    async fn route_matching_no_match_returns_404() {
+        let svc = make_service();
+        let tenant = Uuid::new_v4();
+        let ctx = test_ctx(tenant);
+
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+
+        let chain = svc.build_tenant_chain(&ctx).await.unwrap();
+        let err = ControlPlaneServiceImpl::find_route_in_chain(
+            &*svc.routes,
+            &chain,
+            u.id,
+            "GET",
+            "/v1/unknown",
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, DomainError::NotFound { .. }));
+    }
+
+    #[tokio::test]
+    async fn header_templates_reject_unknown_context_fields() {
+        use crate::domain::model::RequestHeaderRules;
+
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let headers = |value: &str| HeadersConfig {
+            request: Some(RequestHeaderRules {
+                set: HashMap::from([("X-Caller".into(), value.into())]),
+                ..Default::default()
+            }),
+            response: None,
+        };
+
+        let mut req = make_create_upstream(Some("templated"));
+        req.headers = Some(headers("{{ctx.bearer_token}}"));
+        let err = svc.create_upstream(&ctx, req).await.unwrap_err();
+        assert!(
+            matches!(err, DomainError::Validation { ref detail, .. } if detail.contains("headers.request.set.X-Caller")),
+            "{err:?}"
+        );
+
+        let mut req = make_create_upstream(Some("templated"));
+        req.headers = Some(headers("{{ctx.subject_tenant_id}}"));
+        let u = svc.create_upstream(&ctx, req).await.unwrap();
+        let err = svc
+            .update_upstream(
+                &ctx,
+                u.id,
+                UpdateUpstreamRequest {
+                    headers: Some(headers("{{ctx.tenant}}")),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }), "{err:?}");
+    }
+
+    // -- Tenant quota tests --
+
+    #[tokio::test]
+    async fn upstream_quota_rejects_creates_until_one_is_deleted() {
+        let svc = make_service().with_tenant_quotas(2, 0);
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let first = svc
+            .create_upstream(&ctx, make_create_upstream(Some("a")))
+            .await
+            .unwrap();
+        svc.create_upstream(&ctx, make_create_upstream(Some("b")))
+            .await
+            .unwrap();
+        let err = svc
+            .create_upstream(&ctx, make_create_upstream(Some("c")))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::QuotaExceeded { .. }), "{err:?}");
+
+        // Other tenants have their own quota.
+        let other = test_ctx(Uuid::new_v4());
+        svc.create_upstream(&other, make_create_upstream(Some("c")))
+            .await
+            .unwrap();
+
+        svc.delete_upstream(&ctx, first.id, false).await.unwrap();
+        svc.create_upstream(&ctx, make_create_upstream(Some("c")))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn duplicate_route_conflict() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+        svc.create_route(&ctx, make_create_route(u.id))
+            .await
+            .unwrap();
+
+        let err = svc
+            .create_route(&ctx, make_create_route(u.id))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Conflict { .. }), "{err:?}");
+        let err = svc
+            .create_routes(
+                &ctx,
+                vec![
+                    CreateRouteRequest {
+                        priority: 1,
+                        ..make_create_route(u.id)
+                    },
+                    CreateRouteRequest {
+                        priority: 1,
+                        ..make_create_route(u.id)
+                    },
+                ],
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Conflict { .. }), "{err:?}");
+        let routes = svc
+            .list_routes(&ctx, u.id, &ListQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(routes.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn route_quota_counts_batches_and_frees_on_delete() {
+        let svc = make_service().with_tenant_quotas(0, 2);
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("quota")))
+            .await
+            .unwrap();
+        // Distinct priorities keep the routes from being duplicates.
+        let route = |priority| CreateRouteRequest {
+            priority,
+            ..make_create_route(u.id)
+        };
+
+        let first = svc.create_route(&ctx, route(0)).await.unwrap();
         let err = svc
-            .resolve_alias(&ctx, &chain, "nonexistent", None)
+            .create_routes(&ctx, vec![route(1), route(2)])
             .await
             .unwrap_err();
-        assert!(matches!(err, DomainError::NotFound { .. }));
+        assert!(matches!(err, DomainError::QuotaExceeded { .. }), "{err:?}");
+        svc.create_route(&ctx, route(1)).await.unwrap();
+        assert!(matches!(
+            svc.create_route(&ctx, route(2)).await,
+            Err(DomainError::QuotaExceeded { .. })
+        ));
+
+        svc.delete_route(&ctx, first.id).await.unwrap();
+        svc.create_route(&ctx, route(2)).await.unwrap();
+    }
+
+    // -- Audit trail tests --
+
+    #[derive(Default)]
+    struct CapturingAuditSink {
+        events: std::sync::Mutex<Vec<AuditEvent>>,
+    }
+
+    #[async_trait]
+    impl AuditSink for CapturingAuditSink {
+        async fn record(&self, event: AuditEvent) {
+            self.events.lock().unwrap().push(event);
+        }
     }
 
     #[tokio::test]
-    async fn route_matching_through_cp() {
-        let svc = make_service();
+    async fn audit_sink_records_create_and_update() {
+        let sink = Arc::new(CapturingAuditSink::default());
+        let svc = make_service().with_audit_sink(sink.clone());
         let tenant = Uuid::new_v4();
         let ctx = test_ctx(tenant);
 
@@ -1461,46 +3318,71 @@ mod tests {
             .create_upstream(&ctx, make_create_upstream(Some("openai")))
             .await
             .unwrap();
-        let r = svc
-            .create_route(&ctx, make_create_route(u.id))
-            .await
-            .unwrap();
+        {
+            let events = sink.events.lock().unwrap();
+            assert_eq!(events.len(), 1);
+            let ev = &events[0];
+            assert_eq!(ev.subject_id, ctx.subject_id());
+            assert_eq!(ev.subject_tenant_id, tenant);
+            assert_eq!(ev.action, AuditAction::Create);
+            assert_eq!(ev.entity_type, AuditEntityType::Upstream);
+            assert_eq!(ev.entity_id, u.id);
+            assert!(ev.before.is_none());
+            assert!(
+                matches!(&ev.after, Some(AuditSnapshot::Upstream(after)) if after.alias == "openai")
+            );
+        }
 
-        let chain = svc.build_tenant_chain(&ctx).await.unwrap();
-        let matched = ControlPlaneServiceImpl::find_route_in_chain(
-            &*svc.routes,
-            &chain,
+        svc.update_upstream(
+            &ctx,
             u.id,
-            "POST",
-            "/v1/chat/completions",
+            UpdateUpstreamRequest {
+                alias: Some("openai-v2".into()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
-        assert_eq!(matched.id, r.id);
+
+        let events = sink.events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        let ev = &events[1];
+        assert_eq!(ev.subject_id, ctx.subject_id());
+        assert_eq!(ev.action, AuditAction::Update);
+        assert_eq!(ev.entity_type, AuditEntityType::Upstream);
+        assert_eq!(ev.entity_id, u.id);
+        assert!(
+            matches!(&ev.before, Some(AuditSnapshot::Upstream(before)) if before.alias == "openai")
+        );
+        assert!(
+            matches!(&ev.after, Some(AuditSnapshot::Upstream(after)) if after.alias == "openai-v2")
+        );
     }
 
     #[tokio::test]
-    async fn route_matching_no_match_returns_404() {
-        let svc = make_service();
-        let tenant = Uuid::new_v4();
-        let ctx = test_ctx(tenant);
+    async fn audit_sink_records_route_delete_with_before_snapshot() {
+        let sink = Arc::new(CapturingAuditSink::default());
+        let svc = make_service().with_audit_sink(sink.clone());
+        let ctx = test_ctx(Uuid::new_v4());
 
         let u = svc
             .create_upstream(&ctx, make_create_upstream(Some("openai")))
             .await
             .unwrap();
-
-        let chain = svc.build_tenant_chain(&ctx).await.unwrap();
-        let err = ControlPlaneServiceImpl::find_route_in_chain(
-            &*svc.routes,
-            &chain,
-            u.id,
-            "GET",
-            "/v1/unknown",
-        )
-        .await
-        .unwrap_err();
-        assert!(matches!(err, DomainError::NotFound { .. }));
+        let r = svc
+            .create_route(&ctx, make_create_route(u.id))
+            .await
+            .unwrap();
+        svc.delete_route(&ctx, r.id).await.unwrap();
+
+        let events = sink.events.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        let ev = &events[2];
+        assert_eq!(ev.action, AuditAction::Delete);
+        assert_eq!(ev.entity_type, AuditEntityType::Route);
+        assert_eq!(ev.entity_id, r.id);
+        assert!(matches!(&ev.before, Some(AuditSnapshot::Route(before)) if before.id == r.id));
+        assert!(ev.after.is_none());
     }
 
     // -- validate_endpoints tests --
@@ -1581,85 +3463,479 @@ mod tests {
     }
 
     #[test]
-    fn validate_endpoints_accepts_all_hostname() {
-        let endpoints = vec![
-            Endpoint {
-                scheme: Scheme::Https,
-                host: "a.example.com".into(),
-                port: 443,
-            },
-            Endpoint {
-                scheme: Scheme::Https,
-                host: "b.example.com".into(),
-                port: 443,
-            },
-        ];
-        assert!(validate_endpoints(&endpoints).is_ok());
+    fn validate_endpoints_accepts_all_hostname() {
+        let endpoints = vec![
+            Endpoint {
+                scheme: Scheme::Https,
+                host: "a.example.com".into(),
+                port: 443,
+            },
+            Endpoint {
+                scheme: Scheme::Https,
+                host: "b.example.com".into(),
+                port: 443,
+            },
+        ];
+        assert!(validate_endpoints(&endpoints).is_ok());
+    }
+
+    #[test]
+    fn validate_endpoints_rejects_mixed_ports() {
+        let endpoints = vec![
+            Endpoint {
+                scheme: Scheme::Https,
+                host: "a.example.com".into(),
+                port: 443,
+            },
+            Endpoint {
+                scheme: Scheme::Https,
+                host: "b.example.com".into(),
+                port: 8443,
+            },
+        ];
+        let err = validate_endpoints(&endpoints).unwrap_err();
+        assert!(
+            err.to_string().contains("port"),
+            "expected port error, got: {err}"
+        );
+    }
+
+    #[test]
+    fn validate_endpoints_accepts_single() {
+        let endpoints = vec![Endpoint {
+            scheme: Scheme::Https,
+            host: "api.openai.com".into(),
+            port: 443,
+        }];
+        assert!(validate_endpoints(&endpoints).is_ok());
+    }
+
+    #[test]
+    fn validate_endpoints_rejects_ipv6() {
+        let endpoints = vec![Endpoint {
+            scheme: Scheme::Https,
+            host: "::1".into(),
+            port: 443,
+        }];
+        let err = validate_endpoints(&endpoints).unwrap_err();
+        match err {
+            DomainError::Validation { detail, .. } => {
+                assert!(
+                    detail.contains("IPv6"),
+                    "expected IPv6 error, got: {detail}"
+                );
+                assert!(
+                    detail.contains("not yet supported"),
+                    "expected 'not yet supported', got: {detail}"
+                );
+            }
+            _ => panic!("expected Validation, got: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn validate_endpoints_rejects_ipv6_full_address() {
+        let endpoints = vec![Endpoint {
+            scheme: Scheme::Https,
+            host: "2001:db8::1".into(),
+            port: 8443,
+        }];
+        let err = validate_endpoints(&endpoints).unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }));
+    }
+
+    #[test]
+    fn validate_retry_on_body_rejects_bad_config() {
+        let retry = |matcher, max_retries| RetryOnBody {
+            matcher,
+            max_retries,
+        };
+        let pointer = |p: &str| BodyMatcher::JsonPointer {
+            pointer: p.into(),
+            value: serde_json::json!(true),
+        };
+
+        assert!(validate_retry_on_body(None).is_ok());
+        assert!(validate_retry_on_body(Some(&retry(pointer("/error"), 3))).is_ok());
+        assert!(validate_retry_on_body(Some(&retry(pointer("error"), 3))).is_err());
+        assert!(validate_retry_on_body(Some(&retry(pointer("/error"), 0))).is_err());
+        assert!(validate_retry_on_body(Some(&retry(pointer("/error"), 6))).is_err());
+        let bad_regex = BodyMatcher::Regex {
+            pattern: "(".into(),
+        };
+        assert!(validate_retry_on_body(Some(&retry(bad_regex, 1))).is_err());
+    }
+
+    #[test]
+    fn validate_keepalive_rejects_bad_config() {
+        let tcp = KeepaliveConfig {
+            tcp_idle_secs: Some(30),
+            tcp_interval_secs: Some(10),
+            tcp_probe_count: Some(3),
+            h2_ping_interval_secs: None,
+        };
+
+        assert!(validate_keepalive(None).is_ok());
+        assert!(validate_keepalive(Some(&tcp)).is_ok());
+        assert!(
+            validate_keepalive(Some(&KeepaliveConfig {
+                h2_ping_interval_secs: Some(20),
+                ..Default::default()
+            }))
+            .is_ok()
+        );
+        assert!(
+            validate_keepalive(Some(&KeepaliveConfig {
+                tcp_probe_count: Some(0),
+                ..tcp
+            }))
+            .is_err()
+        );
+        assert!(
+            validate_keepalive(Some(&KeepaliveConfig {
+                tcp_idle_secs: None,
+                ..tcp
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn validate_tls_policy_rejects_unknown_values() {
+        assert!(validate_tls_min_version(None).is_ok());
+        assert!(validate_tls_min_version(Some("1.3")).is_ok());
+        for bad in ["1.1", "TLSv1.3", "1.4", ""] {
+            let err = validate_tls_min_version(Some(bad)).unwrap_err();
+            assert!(
+                matches!(&err, DomainError::Validation { detail, .. } if detail.contains("tls_min_version")),
+                "{bad}: {err:?}"
+            );
+        }
+
+        assert!(validate_tls_cipher_suites(&[]).is_ok());
+        assert!(validate_tls_cipher_suites(&["TLS13_AES_256_GCM_SHA384".into()]).is_ok());
+        assert!(validate_tls_cipher_suites(&["TLS_RSA_WITH_RC4_128_SHA".into()]).is_err());
+    }
+
+    #[test]
+    fn validate_tls_server_name_accepts_hostnames_only() {
+        assert!(validate_tls_server_name(None).is_ok());
+        assert!(validate_tls_server_name(Some("api.example.com")).is_ok());
+        for bad in [
+            "",
+            "10.0.0.1",
+            "api.example.com:443",
+            "https://api",
+            "-a.example",
+            "a..b",
+        ] {
+            assert!(validate_tls_server_name(Some(bad)).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn validate_budget_header_rejects_invalid_names() {
+        assert!(validate_budget_header(None).is_ok());
+        assert!(validate_budget_header(Some("X-Timeout-Ms")).is_ok());
+        assert!(validate_budget_header(Some("")).is_err());
+        assert!(validate_budget_header(Some("x timeout")).is_err());
+    }
+
+    #[test]
+    fn validate_circuit_breaker_rejects_zero_values() {
+        assert!(validate_circuit_breaker(None).is_ok());
+        assert!(
+            validate_circuit_breaker(Some(&CircuitBreakerConfig {
+                failure_threshold: Some(2),
+                ..Default::default()
+            }))
+            .is_ok()
+        );
+        assert!(
+            validate_circuit_breaker(Some(&CircuitBreakerConfig {
+                cooldown_secs: Some(0),
+                ..Default::default()
+            }))
+            .is_err()
+        );
+        assert!(
+            validate_circuit_breaker(Some(&CircuitBreakerConfig {
+                half_open_probes: Some(0),
+                ..Default::default()
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn validate_user_agent_rejects_bad_values() {
+        assert!(validate_user_agent(None).is_ok());
+        assert!(validate_user_agent(Some("acme-bot/1.0 (+https://acme.example)")).is_ok());
+        assert!(validate_user_agent(Some("  ")).is_err());
+        assert!(validate_user_agent(Some("bot\r\nx-injected: 1")).is_err());
+    }
+
+    #[test]
+    fn validate_sticky_by_accepts_tenant_and_header() {
+        assert!(validate_sticky_by(None).is_ok());
+        assert!(validate_sticky_by(Some("tenant")).is_ok());
+        assert!(validate_sticky_by(Some("header:X-Session-Id")).is_ok());
+        assert!(validate_sticky_by(Some("header:")).is_err());
+        assert!(validate_sticky_by(Some("header:bad name")).is_err());
+        assert!(validate_sticky_by(Some("cookie:sid")).is_err());
+    }
+
+    #[test]
+    fn validate_max_header_size_rejects_zero() {
+        assert!(validate_max_header_size(None).is_ok());
+        assert!(validate_max_header_size(Some(8192)).is_ok());
+        assert!(validate_max_header_size(Some(0)).is_err());
+    }
+
+    #[test]
+    fn validate_match_body_rejects_bad_matcher() {
+        let rules = |match_body| MatchRules {
+            http: Some(HttpMatch {
+                methods: vec![HttpMethod::Post],
+                path: "/v1/chat".into(),
+                query_allowlist: vec![],
+                path_suffix_mode: PathSuffixMode::Append,
+                match_body,
+            }),
+            grpc: None,
+        };
+        let pointer = |p: &str| BodyMatcher::JsonPointer {
+            pointer: p.into(),
+            value: serde_json::json!("gpt-4"),
+        };
+
+        assert!(validate_match_body(&rules(None)).is_ok());
+        assert!(validate_match_body(&rules(Some(pointer("/model")))).is_ok());
+        assert!(validate_match_body(&rules(Some(pointer("model")))).is_err());
+        let bad_regex = BodyMatcher::Regex {
+            pattern: "(".into(),
+        };
+        assert!(validate_match_body(&rules(Some(bad_regex))).is_err());
+
+        let static_kind = RouteKind::Static {
+            status: 200,
+            headers: HashMap::new(),
+            body: String::new(),
+        };
+        assert!(
+            validate_route_kind(&static_kind, Uuid::nil(), &rules(Some(pointer("/model"))))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn validate_auth_rejects_secret_ref_with_inline_value() {
+        let apikey = |pairs: &[(&str, &str)]| AuthConfig {
+            plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+            sharing: crate::domain::model::SharingMode::Private,
+            config: Some(
+                pairs
+                    .iter()
+                    .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                    .collect(),
+            ),
+        };
+
+        assert!(validate_auth(None).is_ok());
+        assert!(validate_auth(Some(&apikey(&[("secret_ref", "cred://key")]))).is_ok());
+        assert!(validate_auth(Some(&apikey(&[("value", "sk-inline")]))).is_ok());
+        let err = validate_auth(Some(&apikey(&[
+            ("secret_ref", "cred://key"),
+            ("value", "sk-inline"),
+        ])))
+        .unwrap_err();
+        assert!(
+            matches!(err, DomainError::Validation { ref detail, .. } if detail.contains("mutually exclusive"))
+        );
+    }
+
+    #[test]
+    fn validate_log_sample_rate_accepts_unit_interval() {
+        assert!(validate_log_sample_rate(None).is_ok());
+        assert!(validate_log_sample_rate(Some(0.0)).is_ok());
+        assert!(validate_log_sample_rate(Some(0.25)).is_ok());
+        assert!(validate_log_sample_rate(Some(1.0)).is_ok());
+        assert!(validate_log_sample_rate(Some(-0.1)).is_err());
+        assert!(validate_log_sample_rate(Some(1.5)).is_err());
+        assert!(validate_log_sample_rate(Some(f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn validate_response_header_allowlist_rejects_invalid_names() {
+        assert!(validate_response_header_allowlist(None).is_ok());
+        assert!(validate_response_header_allowlist(Some(&[])).is_ok());
+        assert!(
+            validate_response_header_allowlist(Some(&["ETag".into(), "x-request-id".into()]))
+                .is_ok()
+        );
+        assert!(validate_response_header_allowlist(Some(&["x id".into()])).is_err());
+        assert!(validate_response_header_allowlist(Some(&["".into()])).is_err());
+    }
+
+    #[test]
+    fn validate_strip_query_params_rejects_blank_names() {
+        assert!(validate_strip_query_params(&[]).is_ok());
+        assert!(validate_strip_query_params(&["debug".into(), "utm_source".into()]).is_ok());
+        assert!(validate_strip_query_params(&["debug".into(), " ".into()]).is_err());
+    }
+
+    #[test]
+    fn validate_strip_inbound_headers_rejects_invalid_names() {
+        assert!(validate_strip_inbound_headers(&[]).is_ok());
+        assert!(validate_strip_inbound_headers(&["Cookie".into(), "x-debug".into()]).is_ok());
+        assert!(validate_strip_inbound_headers(&["x debug".into()]).is_err());
+        assert!(validate_strip_inbound_headers(&["".into()]).is_err());
+    }
+
+    #[test]
+    fn validate_idempotency_ttl_accepts_up_to_a_day() {
+        assert!(validate_idempotency_ttl(None).is_ok());
+        assert!(validate_idempotency_ttl(Some(1)).is_ok());
+        assert!(validate_idempotency_ttl(Some(86_400)).is_ok());
+        assert!(validate_idempotency_ttl(Some(0)).is_err());
+        assert!(validate_idempotency_ttl(Some(86_401)).is_err());
+    }
+
+    #[test]
+    fn validate_negative_cache_ttl_accepts_up_to_five_minutes() {
+        assert!(validate_negative_cache_ttl(None).is_ok());
+        assert!(validate_negative_cache_ttl(Some(1)).is_ok());
+        assert!(validate_negative_cache_ttl(Some(300)).is_ok());
+        assert!(validate_negative_cache_ttl(Some(0)).is_err());
+        assert!(validate_negative_cache_ttl(Some(301)).is_err());
+    }
+
+    #[test]
+    fn validate_max_total_duration_rejects_zero() {
+        assert!(validate_max_total_duration(None).is_ok());
+        assert!(validate_max_total_duration(Some(250)).is_ok());
+        assert!(validate_max_total_duration(Some(0)).is_err());
+    }
+
+    #[test]
+    fn validate_route_max_body_size_rejects_zero() {
+        assert!(validate_route_max_body_size(None).is_ok());
+        assert!(validate_route_max_body_size(Some(1024)).is_ok());
+        assert!(validate_route_max_body_size(Some(0)).is_err());
+    }
+
+    #[test]
+    fn validate_max_outbound_calls_rejects_zero() {
+        assert!(validate_max_outbound_calls(None).is_ok());
+        assert!(validate_max_outbound_calls(Some(1)).is_ok());
+        assert!(validate_max_outbound_calls(Some(0)).is_err());
+    }
+
+    #[test]
+    fn validate_api_version_rejects_bad_values() {
+        let version = |value: &str, header: Option<&str>, query_param: Option<&str>| ApiVersion {
+            value: value.into(),
+            header: header.map(Into::into),
+            query_param: query_param.map(Into::into),
+        };
+        assert!(validate_api_version(None).is_ok());
+        assert!(validate_api_version(Some(&version("2024-06-01", None, None))).is_ok());
+        assert!(
+            validate_api_version(Some(&version(
+                "2024-06-01",
+                Some("x-ms-version"),
+                Some("api-version")
+            )))
+            .is_ok()
+        );
+        assert!(validate_api_version(Some(&version(" ", None, None))).is_err());
+        assert!(validate_api_version(Some(&version("v1\n", None, None))).is_err());
+        assert!(validate_api_version(Some(&version("v1", Some("bad header"), None))).is_err());
+        assert!(validate_api_version(Some(&version("v1", None, Some("")))).is_err());
+    }
+
+    #[test]
+    fn validate_coerce_content_type_accepts_allowlisted_types_only() {
+        assert!(validate_coerce_content_type(None).is_ok());
+        assert!(validate_coerce_content_type(Some("application/json")).is_ok());
+        assert!(validate_coerce_content_type(Some("Text/Plain; charset=utf-8")).is_ok());
+        assert!(validate_coerce_content_type(Some("image/png")).is_err());
+        assert!(validate_coerce_content_type(Some("application/json\r\nx: y")).is_err());
+    }
+
+    #[test]
+    fn validate_body_transformers_rejects_blank_and_repeated_names() {
+        assert!(validate_body_transformers(&[]).is_ok());
+        assert!(validate_body_transformers(&["minify".into(), "redact-pii".into()]).is_ok());
+        assert!(validate_body_transformers(&[" ".into()]).is_err());
+        assert!(validate_body_transformers(&["minify".into(), "minify".into()]).is_err());
+    }
+
+    #[test]
+    fn validate_log_redact_paths_requires_field_pointers() {
+        assert!(validate_log_redact_paths(&[]).is_ok());
+        assert!(validate_log_redact_paths(&["/password".into(), "/users/*/token".into()]).is_ok());
+        assert!(validate_log_redact_paths(&["password".into()]).is_err());
+        assert!(validate_log_redact_paths(&[String::new()]).is_err());
+    }
+
+    #[test]
+    fn validate_transform_hook_url_requires_absolute_http_url() {
+        assert!(validate_transform_hook_url(None).is_ok());
+        assert!(validate_transform_hook_url(Some("https://hooks.internal/transform")).is_ok());
+        assert!(validate_transform_hook_url(Some("http://127.0.0.1:9000/t")).is_ok());
+        assert!(validate_transform_hook_url(Some("/transform")).is_err());
+        assert!(validate_transform_hook_url(Some("ftp://hooks.internal/t")).is_err());
     }
 
     #[test]
-    fn validate_endpoints_rejects_mixed_ports() {
-        let endpoints = vec![
-            Endpoint {
-                scheme: Scheme::Https,
-                host: "a.example.com".into(),
-                port: 443,
-            },
-            Endpoint {
-                scheme: Scheme::Https,
-                host: "b.example.com".into(),
-                port: 8443,
-            },
-        ];
-        let err = validate_endpoints(&endpoints).unwrap_err();
-        assert!(
-            err.to_string().contains("port"),
-            "expected port error, got: {err}"
-        );
+    fn validate_sunset_requires_http_date() {
+        assert!(validate_sunset(None).is_ok());
+        assert!(validate_sunset(Some("Sat, 01 Nov 2025 00:00:00 GMT")).is_ok());
+        assert!(validate_sunset(Some("2025-11-01")).is_err());
+        assert!(validate_sunset(Some("")).is_err());
     }
 
     #[test]
-    fn validate_endpoints_accepts_single() {
-        let endpoints = vec![Endpoint {
-            scheme: Scheme::Https,
-            host: "api.openai.com".into(),
-            port: 443,
-        }];
-        assert!(validate_endpoints(&endpoints).is_ok());
+    fn validate_body_rewrite_rejects_bad_rules() {
+        let rule = |find: &str, regex: bool| BodyRewriteRule {
+            find: find.into(),
+            replace: "x".into(),
+            regex,
+            content_types: vec![],
+        };
+        assert!(validate_body_rewrite(&[]).is_ok());
+        assert!(
+            validate_body_rewrite(&[rule("http://internal", false), rule(r"v\d+", true)]).is_ok()
+        );
+        assert!(validate_body_rewrite(&[rule("", false)]).is_err());
+        assert!(validate_body_rewrite(&[rule("(", true)]).is_err());
+        assert!(validate_body_rewrite(&[rule("(", false)]).is_ok());
     }
 
     #[test]
-    fn validate_endpoints_rejects_ipv6() {
-        let endpoints = vec![Endpoint {
-            scheme: Scheme::Https,
-            host: "::1".into(),
-            port: 443,
-        }];
-        let err = validate_endpoints(&endpoints).unwrap_err();
-        match err {
-            DomainError::Validation { detail, .. } => {
-                assert!(
-                    detail.contains("IPv6"),
-                    "expected IPv6 error, got: {detail}"
-                );
-                assert!(
-                    detail.contains("not yet supported"),
-                    "expected 'not yet supported', got: {detail}"
-                );
-            }
-            _ => panic!("expected Validation, got: {err:?}"),
-        }
+    fn validate_auth_rules_rejects_bad_conditions() {
+        let rule = |condition| AuthRule {
+            condition,
+            auth: None,
+        };
+        let prefix = |p: &str| AuthCondition::PathPrefix { prefix: p.into() };
+        let header = |name: &str| AuthCondition::Header {
+            name: name.into(),
+            value: None,
+        };
+        assert!(validate_auth_rules(&[]).is_ok());
+        assert!(validate_auth_rules(&[rule(prefix("/admin")), rule(header("x-tier"))]).is_ok());
+        assert!(validate_auth_rules(&[rule(prefix("admin"))]).is_err());
+        assert!(validate_auth_rules(&[rule(header("bad header"))]).is_err());
     }
 
     #[test]
-    fn validate_endpoints_rejects_ipv6_full_address() {
-        let endpoints = vec![Endpoint {
-            scheme: Scheme::Https,
-            host: "2001:db8::1".into(),
-            port: 8443,
-        }];
-        let err = validate_endpoints(&endpoints).unwrap_err();
-        assert!(matches!(err, DomainError::Validation { .. }));
+    fn validate_request_schema_rejects_invalid_schema() {
+        assert!(validate_request_schema(None).is_ok());
+        assert!(validate_request_schema(Some(&serde_json::json!({"type": "object"}))).is_ok());
+        assert!(validate_request_schema(Some(&serde_json::json!({"type": "nope"}))).is_err());
+        assert!(validate_request_schema(Some(&serde_json::json!({"minimum": "x"}))).is_err());
     }
 
     #[test]
@@ -1685,6 +3961,165 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn batch_create_upstreams_and_routes() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let ups = svc
+            .create_upstreams(
+                &ctx,
+                vec![
+                    make_create_upstream(Some("openai")),
+                    make_create_upstream(Some("anthropic")),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(ups.len(), 2);
+        assert_eq!(ups[0].alias, "openai");
+        assert_eq!(ups[1].alias, "anthropic");
+
+        let routes = svc
+            .create_routes(
+                &ctx,
+                vec![make_create_route(ups[0].id), make_create_route(ups[1].id)],
+            )
+            .await
+            .unwrap();
+        assert_eq!(routes.len(), 2);
+        for r in &routes {
+            assert!(svc.get_route(&ctx, r.id).await.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn batch_create_rolls_back_on_invalid_item() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let mut invalid = make_create_upstream(Some("broken"));
+        invalid.timeout_secs = Some(0);
+        let err = svc
+            .create_upstreams(&ctx, vec![make_create_upstream(Some("openai")), invalid])
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, DomainError::Validation { detail, .. } if detail.starts_with("item 1:"))
+        );
+
+        // Store-time conflict on a later item undoes the earlier ones.
+        svc.create_upstream(&ctx, make_create_upstream(Some("taken")))
+            .await
+            .unwrap();
+        let err = svc
+            .create_upstreams(
+                &ctx,
+                vec![
+                    make_create_upstream(Some("openai")),
+                    make_create_upstream(Some("taken")),
+                ],
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Conflict { .. }));
+
+        let listed = svc
+            .list_upstreams(&ctx, &ListQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].alias, "taken");
+
+        let err = svc
+            .create_routes(
+                &ctx,
+                vec![
+                    make_create_route(listed[0].id),
+                    make_create_route(Uuid::new_v4()),
+                ],
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }));
+        let routes = svc
+            .list_routes(&ctx, listed[0].id, &ListQuery::default())
+            .await
+            .unwrap();
+        assert!(routes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn validate_config_reports_every_problem() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        svc.create_upstream(&ctx, make_create_upstream(Some("taken")))
+            .await
+            .unwrap();
+
+        let mut broken = make_create_upstream(Some("broken"));
+        broken.server.endpoints.clear();
+        broken.timeout_secs = Some(0);
+        let snapshot = ConfigSnapshot {
+            upstreams: vec![
+                broken,
+                make_create_upstream(Some("openai")),
+                make_create_upstream(Some("openai")),
+                make_create_upstream(Some("taken")),
+            ],
+            routes: vec![make_create_route(Uuid::new_v4())],
+        };
+
+        let problems = svc.validate_config(&ctx, snapshot).await.unwrap();
+        let paths: Vec<&str> = problems.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "upstreams[0]",
+                "upstreams[0]",
+                "upstreams[2]",
+                "upstreams[3]",
+                "routes[0]"
+            ]
+        );
+        assert!(problems[2].detail.contains("upstreams[1]"), "{problems:?}");
+
+        // Dry run: nothing besides the pre-existing upstream was stored.
+        let listed = svc
+            .list_upstreams(&ctx, &ListQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn validate_config_accepts_valid_snapshot() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let existing = svc
+            .create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+
+        let snapshot = ConfigSnapshot {
+            upstreams: vec![make_create_upstream(Some("anthropic"))],
+            routes: vec![make_create_route(existing.id)],
+        };
+        let problems = svc.validate_config(&ctx, snapshot).await.unwrap();
+        assert!(problems.is_empty(), "unexpected problems: {problems:?}");
+
+        let listed = svc
+            .list_upstreams(&ctx, &ListQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+        let routes = svc
+            .list_routes(&ctx, existing.id, &ListQuery::default())
+            .await
+            .unwrap();
+        assert!(routes.is_empty());
+    }
+
     #[tokio::test]
     async fn delete_upstream_cascades_routes() {
         let svc = make_service();
@@ -1700,12 +4135,182 @@ mod tests {
             .await
             .unwrap();
 
-        svc.delete_upstream(&ctx, u.id).await.unwrap();
+        svc.delete_upstream(&ctx, u.id, true).await.unwrap();
 
         // Route should be gone.
         assert!(svc.get_route(&ctx, r.id).await.is_err());
     }
 
+    #[tokio::test]
+    async fn tenant_rate_limit_crud_requires_upstream_owner() {
+        let svc = make_service();
+        let owner = test_ctx(Uuid::new_v4());
+        let other = test_ctx(Uuid::new_v4());
+        let tenant = Uuid::new_v4();
+        let u = svc
+            .create_upstream(&owner, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+        let limit = make_rate_limit(SharingMode::Private, 5, Window::Minute);
+
+        let err = svc
+            .set_tenant_rate_limit(&other, u.id, tenant, limit.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::NotFound { .. }));
+
+        svc.set_tenant_rate_limit(&owner, u.id, tenant, limit.clone())
+            .await
+            .unwrap();
+        let got = svc
+            .get_tenant_rate_limit(&owner, u.id, tenant)
+            .await
+            .unwrap();
+        assert_eq!(got.rate_limit, limit);
+        assert!(
+            svc.get_tenant_rate_limit(&other, u.id, tenant)
+                .await
+                .is_err()
+        );
+
+        // The data plane sees the policy only for the tenant it names.
+        let as_tenant = test_ctx(tenant);
+        assert!(
+            svc.resolve_tenant_rate_limit(&as_tenant, u.id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            svc.resolve_tenant_rate_limit(&owner, u.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        svc.delete_tenant_rate_limit(&owner, u.id, tenant)
+            .await
+            .unwrap();
+        assert!(
+            svc.resolve_tenant_rate_limit(&as_tenant, u.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // Deleting the upstream drops its policies.
+        svc.set_tenant_rate_limit(&owner, u.id, tenant, limit)
+            .await
+            .unwrap();
+        svc.delete_upstream(&owner, u.id, false).await.unwrap();
+        assert!(
+            svc.resolve_tenant_rate_limit(&as_tenant, u.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn tenant_default_auth_is_scoped_to_the_callers_tenant() {
+        let svc = make_service();
+        let (tenant, other) = (test_ctx(Uuid::new_v4()), test_ctx(Uuid::new_v4()));
+        let auth = AuthConfig {
+            plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+            sharing: SharingMode::Private,
+            config: Some(HashMap::from([(
+                "secret_ref".to_string(),
+                "cred://{tenant}/key".to_string(),
+            )])),
+        };
+
+        let stored = svc
+            .set_tenant_default_auth(&tenant, auth.clone())
+            .await
+            .unwrap();
+        assert_eq!(stored.tenant_id, tenant.subject_tenant_id());
+        assert_eq!(
+            svc.get_tenant_default_auth(&tenant).await.unwrap().auth,
+            auth
+        );
+        assert_eq!(
+            svc.resolve_tenant_default_auth(&tenant).await.unwrap(),
+            Some(auth.clone())
+        );
+        assert!(matches!(
+            svc.get_tenant_default_auth(&other).await.unwrap_err(),
+            DomainError::NotFound { .. }
+        ));
+        assert!(
+            svc.resolve_tenant_default_auth(&other)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let mut both = auth;
+        both.config
+            .as_mut()
+            .unwrap()
+            .insert("value".into(), "sk-inline".into());
+        let err = svc
+            .set_tenant_default_auth(&tenant, both)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }), "{err:?}");
+
+        svc.delete_tenant_default_auth(&tenant).await.unwrap();
+        assert!(
+            svc.resolve_tenant_default_auth(&tenant)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(svc.delete_tenant_default_auth(&tenant).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn delete_upstream_without_cascade_lists_blocking_routes() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+        let r = svc
+            .create_route(&ctx, make_create_route(u.id))
+            .await
+            .unwrap();
+
+        let err = svc.delete_upstream(&ctx, u.id, false).await.unwrap_err();
+        match err {
+            DomainError::Conflict { detail } => assert!(
+                detail.contains(&r.id.to_string()),
+                "expected blocking route id in: {detail}"
+            ),
+            other => panic!("expected Conflict, got: {other:?}"),
+        }
+
+        // Nothing was removed.
+        assert!(svc.get_upstream(&ctx, u.id).await.is_ok());
+        assert!(svc.get_route(&ctx, r.id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn delete_upstream_without_routes_needs_no_cascade() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+
+        svc.delete_upstream(&ctx, u.id, false).await.unwrap();
+        assert!(svc.get_upstream(&ctx, u.id).await.is_err());
+    }
+
     // -- Alias resolution tests --
 
     #[tokio::test]
@@ -1955,6 +4560,23 @@ mod tests {
             plugins,
             rate_limit,
             tags,
+            maintenance: false,
+            maintenance_message: None,
+            retry_on_body: None,
+            force_chunked: false,
+            failover: vec![],
+            timeout_secs: None,
+            keepalive: None,
+            circuit_breaker: None,
+            user_agent: None,
+            sticky_by: None,
+            allowed_methods: vec![],
+            trailing_slash: TrailingSlash::default(),
+            max_header_size_bytes: None,
+            tls_min_version: None,
+            tls_cipher_suites: vec![],
+            tls_server_name: None,
+            budget_header: None,
         }
     }
 
@@ -2159,6 +4781,32 @@ mod tests {
             tags: vec![],
             priority: 0,
             enabled: true,
+            skip_authz: false,
+            timeout_secs: None,
+            status_remap: HashMap::new(),
+            allowed_response_content_types: vec![],
+            allowed_request_content_types: vec![],
+            log_sample_rate: None,
+            request_schema: None,
+            body_rewrite: vec![],
+            auth_rules: vec![],
+            strip_query_params: vec![],
+            strip_inbound_headers: vec![],
+            idempotency_ttl_secs: None,
+            cache_responses: false,
+            negative_cache_ttl_secs: None,
+            response_header_allowlist: None,
+            max_total_duration_ms: None,
+            max_body_size_bytes: None,
+            max_outbound_calls: None,
+            coerce_content_type: None,
+            api_version: None,
+            body_transformers: vec![],
+            log_redact_paths: vec![],
+            transform_hook_url: None,
+            deprecated: false,
+            sunset: None,
+            kind: RouteKind::Proxy,
         };
 
         let effective = compute_effective_config(&[u], Some(&route));
@@ -2444,6 +5092,37 @@ mod tests {
         assert_eq!(child_upstream.alias, "openai");
     }
 
+    #[tokio::test]
+    async fn bind_templated_secret_ref_falls_back_to_default() {
+        let root = Uuid::new_v4();
+        let child = Uuid::new_v4();
+        let resolver = MockTenantResolverClient::with_hierarchy(vec![root, child]);
+        let svc = make_service_with_resolver_and_creds(
+            resolver,
+            vec![("shared-key".into(), "secret-value".into())],
+        );
+
+        let root_ctx = test_ctx(root);
+        let mut root_req = make_create_upstream(Some("openai"));
+        root_req.auth = Some(AuthConfig {
+            plugin_type: "apikey".into(),
+            sharing: SharingMode::Inherit,
+            config: None,
+        });
+        svc.create_upstream(&root_ctx, root_req).await.unwrap();
+
+        // No `<child>-openai-key` secret exists, but the default is accessible.
+        let child_ctx = test_ctx(child);
+        let mut child_req = make_create_upstream(Some("openai"));
+        let mut auth = auth_with_secret_ref("cred://{tenant}/openai-key");
+        auth.config
+            .as_mut()
+            .unwrap()
+            .insert("default_secret_ref".into(), "cred://shared-key".into());
+        child_req.auth = Some(auth);
+        svc.create_upstream(&child_ctx, child_req).await.unwrap();
+    }
+
     // -- Update upstream bind validation tests --
 
     #[tokio::test]
@@ -2650,6 +5329,7 @@ mod tests {
                     methods: vec![HttpMethod::Post],
                     query_allowlist: vec![],
                     path_suffix_mode: PathSuffixMode::default(),
+                    match_body: None,
                 }),
                 grpc: None,
             },
@@ -2658,6 +5338,32 @@ mod tests {
             tags: vec![],
             priority: 0,
             enabled: true,
+            skip_authz: false,
+            timeout_secs: None,
+            status_remap: HashMap::new(),
+            allowed_response_content_types: vec![],
+            allowed_request_content_types: vec![],
+            log_sample_rate: None,
+            request_schema: None,
+            body_rewrite: vec![],
+            auth_rules: vec![],
+            strip_query_params: vec![],
+            strip_inbound_headers: vec![],
+            idempotency_ttl_secs: None,
+            cache_responses: false,
+            negative_cache_ttl_secs: None,
+            response_header_allowlist: None,
+            max_total_duration_ms: None,
+            max_body_size_bytes: None,
+            max_outbound_calls: None,
+            coerce_content_type: None,
+            api_version: None,
+            body_transformers: vec![],
+            log_redact_paths: vec![],
+            transform_hook_url: None,
+            deprecated: false,
+            sunset: None,
+            kind: RouteKind::Proxy,
         };
         let root_route = svc.create_route(&root_ctx, route_req).await.unwrap();
 
@@ -2671,7 +5377,7 @@ mod tests {
         // Child resolves proxy target — should find the route defined on
         // the root's upstream ID, not the child's.
         let (effective, route) = svc
-            .resolve_proxy_target(&child_ctx, "openai", "POST", "/v1/chat")
+            .resolve_proxy_target(&child_ctx, "openai", "POST", "/v1/chat", None)
             .await
             .unwrap();
 
@@ -2707,6 +5413,7 @@ mod tests {
                     methods: vec![HttpMethod::Post],
                     query_allowlist: vec![],
                     path_suffix_mode: PathSuffixMode::default(),
+                    match_body: None,
                 }),
                 grpc: None,
             },
@@ -2715,6 +5422,32 @@ mod tests {
             tags: vec![],
             priority: 0,
             enabled: true,
+            skip_authz: false,
+            timeout_secs: None,
+            status_remap: HashMap::new(),
+            allowed_response_content_types: vec![],
+            allowed_request_content_types: vec![],
+            log_sample_rate: None,
+            request_schema: None,
+            body_rewrite: vec![],
+            auth_rules: vec![],
+            strip_query_params: vec![],
+            strip_inbound_headers: vec![],
+            idempotency_ttl_secs: None,
+            cache_responses: false,
+            negative_cache_ttl_secs: None,
+            response_header_allowlist: None,
+            max_total_duration_ms: None,
+            max_body_size_bytes: None,
+            max_outbound_calls: None,
+            coerce_content_type: None,
+            api_version: None,
+            body_transformers: vec![],
+            log_redact_paths: vec![],
+            transform_hook_url: None,
+            deprecated: false,
+            sunset: None,
+            kind: RouteKind::Proxy,
         };
         svc.create_route(&root_ctx, root_route_req).await.unwrap();
 
@@ -2734,6 +5467,7 @@ mod tests {
                     methods: vec![HttpMethod::Post],
                     query_allowlist: vec![],
                     path_suffix_mode: PathSuffixMode::default(),
+                    match_body: None,
                 }),
                 grpc: None,
             },
@@ -2742,12 +5476,38 @@ mod tests {
             tags: vec![],
             priority: 0,
             enabled: true,
+            skip_authz: false,
+            timeout_secs: None,
+            status_remap: HashMap::new(),
+            allowed_response_content_types: vec![],
+            allowed_request_content_types: vec![],
+            log_sample_rate: None,
+            request_schema: None,
+            body_rewrite: vec![],
+            auth_rules: vec![],
+            strip_query_params: vec![],
+            strip_inbound_headers: vec![],
+            idempotency_ttl_secs: None,
+            cache_responses: false,
+            negative_cache_ttl_secs: None,
+            response_header_allowlist: None,
+            max_total_duration_ms: None,
+            max_body_size_bytes: None,
+            max_outbound_calls: None,
+            coerce_content_type: None,
+            api_version: None,
+            body_transformers: vec![],
+            log_redact_paths: vec![],
+            transform_hook_url: None,
+            deprecated: false,
+            sunset: None,
+            kind: RouteKind::Proxy,
         };
         let child_route = svc.create_route(&child_ctx, child_route_req).await.unwrap();
 
         // Child resolves — should prefer its own route (child upstream ID checked first).
         let (_effective, route) = svc
-            .resolve_proxy_target(&child_ctx, "openai", "POST", "/v1/chat")
+            .resolve_proxy_target(&child_ctx, "openai", "POST", "/v1/chat", None)
             .await
             .unwrap();
 
@@ -2807,6 +5567,32 @@ mod tests {
             tags: vec![],
             priority: 0,
             enabled: true,
+            skip_authz: false,
+            timeout_secs: None,
+            status_remap: HashMap::new(),
+            allowed_response_content_types: vec![],
+            allowed_request_content_types: vec![],
+            log_sample_rate: None,
+            request_schema: None,
+            body_rewrite: vec![],
+            auth_rules: vec![],
+            strip_query_params: vec![],
+            strip_inbound_headers: vec![],
+            idempotency_ttl_secs: None,
+            cache_responses: false,
+            negative_cache_ttl_secs: None,
+            response_header_allowlist: None,
+            max_total_duration_ms: None,
+            max_body_size_bytes: None,
+            max_outbound_calls: None,
+            coerce_content_type: None,
+            api_version: None,
+            body_transformers: vec![],
+            log_redact_paths: vec![],
+            transform_hook_url: None,
+            deprecated: false,
+            sunset: None,
+            kind: RouteKind::Proxy,
         };
 
         let effective = compute_effective_config(&[u], Some(&route));
@@ -2834,6 +5620,32 @@ mod tests {
             tags: vec![],
             priority: 0,
             enabled: true,
+            skip_authz: false,
+            timeout_secs: None,
+            status_remap: HashMap::new(),
+            allowed_response_content_types: vec![],
+            allowed_request_content_types: vec![],
+            log_sample_rate: None,
+            request_schema: None,
+            body_rewrite: vec![],
+            auth_rules: vec![],
+            strip_query_params: vec![],
+            strip_inbound_headers: vec![],
+            idempotency_ttl_secs: None,
+            cache_responses: false,
+            negative_cache_ttl_secs: None,
+            response_header_allowlist: None,
+            max_total_duration_ms: None,
+            max_body_size_bytes: None,
+            max_outbound_calls: None,
+            coerce_content_type: None,
+            api_version: None,
+            body_transformers: vec![],
+            log_redact_paths: vec![],
+            transform_hook_url: None,
+            deprecated: false,
+            sunset: None,
+            kind: RouteKind::Proxy,
         };
 
         let effective = compute_effective_config(&[u], Some(&route));
@@ -11,8 +11,10 @@ use uuid::Uuid;
 
 use crate::domain::error::DomainError;
 use crate::domain::model::{
-    CreateRouteRequest, CreateUpstreamRequest, Endpoint, ListQuery, Route, UpdateRouteRequest,
-    UpdateUpstreamRequest, Upstream,
+    AuthConfig, ConfigProblem, ConfigSnapshot, CreateRouteRequest, CreateUpstreamRequest, Endpoint,
+    FlushedCaches, GatewayStatus, ListQuery, RateLimitConfig, RecentError, RequestTrace, Route,
+    RouteDescription, TenantAuthDefault, TenantRateLimit, UpdateRouteRequest,
+    UpdateUpstreamRequest, Upstream, UpstreamRuntimeState, UpstreamTestResult,
 };
 
 /// Internal Control Plane service trait — configuration management and resolution.
@@ -26,6 +28,15 @@ pub(crate) trait ControlPlaneService: Send + Sync {
         req: CreateUpstreamRequest,
     ) -> Result<Upstream, DomainError>;
 
+    /// Create several upstreams all-or-nothing. Every item is validated
+    /// before any is stored; if one fails, none are kept and the error names
+    /// the failing item's index.
+    async fn create_upstreams(
+        &self,
+        ctx: &SecurityContext,
+        reqs: Vec<CreateUpstreamRequest>,
+    ) -> Result<Vec<Upstream>, DomainError>;
+
     async fn get_upstream(&self, ctx: &SecurityContext, id: Uuid) -> Result<Upstream, DomainError>;
 
     async fn list_upstreams(
@@ -41,7 +52,15 @@ pub(crate) trait ControlPlaneService: Send + Sync {
         req: UpdateUpstreamRequest,
     ) -> Result<Upstream, DomainError>;
 
-    async fn delete_upstream(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError>;
+    /// Delete an upstream. Dependent routes are deleted along with it when
+    /// `cascade` is set; otherwise their presence rejects the delete with a
+    /// conflict listing the blocking route IDs.
+    async fn delete_upstream(
+        &self,
+        ctx: &SecurityContext,
+        id: Uuid,
+        cascade: bool,
+    ) -> Result<(), DomainError>;
 
     // -- Route CRUD --
 
@@ -51,6 +70,13 @@ pub(crate) trait ControlPlaneService: Send + Sync {
         req: CreateRouteRequest,
     ) -> Result<Route, DomainError>;
 
+    /// Create several routes all-or-nothing, like [`Self::create_upstreams`].
+    async fn create_routes(
+        &self,
+        ctx: &SecurityContext,
+        reqs: Vec<CreateRouteRequest>,
+    ) -> Result<Vec<Route>, DomainError>;
+
     async fn get_route(&self, ctx: &SecurityContext, id: Uuid) -> Result<Route, DomainError>;
 
     async fn list_routes(
@@ -69,6 +95,61 @@ pub(crate) trait ControlPlaneService: Send + Sync {
 
     async fn delete_route(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError>;
 
+    // -- Validation --
+
+    /// Check a snapshot as if its upstreams and then its routes were
+    /// created, and return every problem found. Nothing is stored; an error
+    /// means the check itself could not run.
+    async fn validate_config(
+        &self,
+        ctx: &SecurityContext,
+        snapshot: ConfigSnapshot,
+    ) -> Result<Vec<ConfigProblem>, DomainError>;
+
+    // -- Tenant rate limits --
+
+    /// Set the rate limit applied to `tenant_id`'s requests through one of
+    /// the caller's upstreams, replacing the upstream's own limit for that
+    /// tenant. An existing policy for the pair is overwritten.
+    async fn set_tenant_rate_limit(
+        &self,
+        ctx: &SecurityContext,
+        upstream_id: Uuid,
+        tenant_id: Uuid,
+        rate_limit: RateLimitConfig,
+    ) -> Result<TenantRateLimit, DomainError>;
+
+    async fn get_tenant_rate_limit(
+        &self,
+        ctx: &SecurityContext,
+        upstream_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<TenantRateLimit, DomainError>;
+
+    async fn delete_tenant_rate_limit(
+        &self,
+        ctx: &SecurityContext,
+        upstream_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<(), DomainError>;
+
+    // -- Tenant default auth --
+
+    /// Set the auth applied to the caller's tenant's requests through
+    /// upstreams that configure none, replacing any previous default.
+    async fn set_tenant_default_auth(
+        &self,
+        ctx: &SecurityContext,
+        auth: AuthConfig,
+    ) -> Result<TenantAuthDefault, DomainError>;
+
+    async fn get_tenant_default_auth(
+        &self,
+        ctx: &SecurityContext,
+    ) -> Result<TenantAuthDefault, DomainError>;
+
+    async fn delete_tenant_default_auth(&self, ctx: &SecurityContext) -> Result<(), DomainError>;
+
     // -- Resolution --
 
     /// Combined upstream + route resolution for the proxy hot path.
@@ -76,13 +157,54 @@ pub(crate) trait ControlPlaneService: Send + Sync {
     /// Single `get_ancestors` call, correct multi-ID route matching across
     /// ancestor upstreams, and full effective config merge including route
     /// overrides.
+    ///
+    /// `body` is the buffered request body, or `None` when it has not been
+    /// buffered; see [`RouteRepository::find_matching`] for how route body
+    /// conditions are treated then.
+    ///
+    /// [`RouteRepository::find_matching`]: crate::domain::repo::RouteRepository::find_matching
     async fn resolve_proxy_target(
         &self,
         ctx: &SecurityContext,
         alias: &str,
         method: &str,
         path: &str,
+        body: Option<&[u8]>,
     ) -> Result<(Upstream, Route), DomainError>;
+
+    /// Find an enabled static-response route in the caller's tenant whose
+    /// match path prefixes `path` (the full proxy path, alias segment
+    /// included). Static routes are not inherited by descendant tenants.
+    async fn resolve_static_route(
+        &self,
+        ctx: &SecurityContext,
+        method: &str,
+        path: &str,
+    ) -> Result<Option<Route>, DomainError>;
+
+    /// Policy the calling tenant is subject to on the effective upstream
+    /// `upstream_id`, if its owner set one.
+    async fn resolve_tenant_rate_limit(
+        &self,
+        ctx: &SecurityContext,
+        upstream_id: Uuid,
+    ) -> Result<Option<TenantRateLimit>, DomainError>;
+
+    /// Default auth of the calling tenant, if it set one.
+    async fn resolve_tenant_default_auth(
+        &self,
+        ctx: &SecurityContext,
+    ) -> Result<Option<AuthConfig>, DomainError>;
+
+    /// Resolve the configuration layers behind route `route_id` for the
+    /// caller: the upstream its alias resolves to, as stored, and the
+    /// effective upstream after the hierarchy merge and route overrides.
+    /// Returns `(selected, effective, route)`.
+    async fn resolve_route_config(
+        &self,
+        ctx: &SecurityContext,
+        route_id: Uuid,
+    ) -> Result<(Upstream, Upstream, Route), DomainError>;
 }
 
 /// Internal Data Plane service trait — proxy orchestration and plugin execution.
@@ -96,6 +218,56 @@ pub(crate) trait DataPlaneService: Send + Sync {
 
     /// Remove a rate-limit bucket by key (e.g. `"upstream:{id}"` or `"route:{id}"`).
     fn remove_rate_limit_key(&self, key: &str);
+
+    /// Circuit-breaker and upstream rate-limit state for `upstream_id`.
+    fn runtime_state(&self, upstream_id: Uuid) -> UpstreamRuntimeState;
+
+    /// Force `upstream_id`'s circuit breaker closed.
+    fn reset_circuit_breaker(&self, upstream_id: Uuid);
+
+    /// Refill the upstream buckets that limit `tenant_id`'s traffic to
+    /// `upstream_id`: its tenant policy bucket and the upstream's own
+    /// bucket, which is shared by tenants without a policy.
+    fn reset_rate_limit(&self, tenant_id: Uuid, upstream_id: Uuid);
+
+    /// The last proxy requests that failed inside the gateway, oldest first.
+    fn recent_errors(&self) -> Vec<RecentError>;
+
+    /// Empty the response cache and the auth plugin token caches.
+    async fn flush_caches(&self) -> FlushedCaches;
+
+    /// Liveness and readiness of the gateway (for Kubernetes probes).
+    async fn status(&self) -> GatewayStatus;
+
+    /// Resolve `upstream`'s credentials, run its auth plugin and send a
+    /// `GET /` probe to one of its endpoints. Failures are reported in the
+    /// result rather than as errors.
+    async fn test_upstream(&self, ctx: &SecurityContext, upstream: &Upstream)
+    -> UpstreamTestResult;
+
+    /// Effective configuration of route `route_id` for the caller, with the
+    /// layer each setting comes from.
+    async fn describe_route(
+        &self,
+        ctx: &SecurityContext,
+        route_id: Uuid,
+    ) -> Result<RouteDescription, DomainError>;
+
+    /// Run `req` through the proxy pipeline for debugging and record what
+    /// each stage did. The upstream is never contacted: `mock_response`
+    /// takes its place in the response stages, and without one the trace
+    /// ends once the request is ready to send. A failure ends the trace and
+    /// is reported in it.
+    async fn trace_request(
+        &self,
+        ctx: SecurityContext,
+        req: http::Request<Body>,
+        mock_response: Option<http::Response<Body>>,
+    ) -> RequestTrace;
+
+    /// Flush state held for external backends (e.g. pending response cache
+    /// writes). Called once on graceful shutdown.
+    async fn shutdown(&self);
 }
 
 /// Endpoint selection abstraction for multi-endpoint load balancing.
@@ -107,6 +279,19 @@ pub(crate) trait EndpointSelector: Send + Sync {
     /// Returns `None` if all backends are unhealthy or the endpoint list is empty.
     async fn select(&self, upstream_id: Uuid, endpoints: &[Endpoint]) -> Option<Endpoint>;
 
+    /// Select a healthy endpoint by consistent hashing of `key`, so the same
+    /// key keeps landing on the same endpoint while it stays healthy.
+    async fn select_sticky(
+        &self,
+        upstream_id: Uuid,
+        endpoints: &[Endpoint],
+        key: &[u8],
+    ) -> Option<Endpoint>;
+
+    /// How many of the upstream's endpoints currently pass health checks.
+    /// Zero when the upstream has no load-balancing state yet.
+    fn healthy_count(&self, upstream_id: Uuid) -> usize;
+
     /// Invalidate cached state for the given upstream (called on CRUD).
     fn invalidate(&self, upstream_id: Uuid);
 }
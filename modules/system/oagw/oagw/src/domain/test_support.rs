@@ -1,20 +1,24 @@
 //! Test utilities for CP and DP integration tests.
 
 use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 
 use async_trait::async_trait;
 use authz_resolver_sdk::{
     AuthZResolverClient, AuthZResolverError, EvaluationRequest, EvaluationResponse,
     EvaluationResponseContext, PolicyEnforcer,
 };
+#[cfg(test)]
+use bytes::Bytes;
 use credstore_sdk::{
     CredStoreClientV1, CredStoreError, GetSecretResponse, SecretRef, SecretValue, SharingMode,
 };
 use modkit::client_hub::ClientHub;
 use modkit_security::SecurityContext;
 use oagw_sdk::api::ServiceGatewayClientV1;
+use oagw_sdk::{MetricsSink, ResponseCache};
 use tenant_resolver_sdk::{
     GetAncestorsOptions, GetAncestorsResponse, GetDescendantsOptions, GetDescendantsResponse,
     GetTenantsOptions, IsAncestorOptions, TenantId, TenantInfo, TenantRef, TenantResolverClient,
@@ -23,12 +27,19 @@ use tenant_resolver_sdk::{
 use uuid::Uuid;
 
 use crate::config::TokenCacheConfig;
+use crate::domain::clock::Clock;
+#[cfg(test)]
+use crate::domain::plugin::{AuthContext, AuthHeaders};
 use crate::domain::services::{
     ControlPlaneService, ControlPlaneServiceImpl, DataPlaneService, EndpointSelector,
     ServiceGatewayClientV1Facade,
 };
 use crate::infra::proxy::DataPlaneServiceImpl;
-use crate::infra::storage::{InMemoryRouteRepo, InMemoryUpstreamRepo};
+use crate::infra::proxy::dns_cache::DnsCache;
+use crate::infra::storage::{
+    InMemoryRouteRepo, InMemoryTenantAuthDefaultRepo, InMemoryTenantRateLimitRepo,
+    InMemoryUpstreamRepo,
+};
 
 /// Build an allow-all `PolicyEnforcer` for tests.
 pub fn allow_all_enforcer() -> PolicyEnforcer {
@@ -127,6 +138,254 @@ impl AuthZResolverClient for CapturingAuthZResolverClient {
     }
 }
 
+/// A request as the data plane sent it upstream, after auth plugins and
+/// header rules ran. Internal `x-oagw-*` bridge headers are left out.
+#[derive(Debug, Clone)]
+pub struct OutboundRequest {
+    pub method: http::Method,
+    pub url: String,
+    pub headers: http::HeaderMap,
+}
+
+/// Handle to the outbound requests of a DP built with
+/// [`TestDpBuilder::with_outbound_capture`].
+#[derive(Clone, Default)]
+pub struct OutboundCapture {
+    requests: Arc<Mutex<Vec<OutboundRequest>>>,
+}
+
+impl OutboundCapture {
+    pub(crate) fn record(&self, method: &http::Method, url: &str, headers: &http::HeaderMap) {
+        let mut headers = headers.clone();
+        crate::infra::proxy::headers::strip_internal_headers(&mut headers);
+        self.requests.lock().unwrap().push(OutboundRequest {
+            method: method.clone(),
+            url: url.to_string(),
+            headers,
+        });
+    }
+
+    /// Return a snapshot of all requests sent upstream, in order.
+    pub fn captured(&self) -> Vec<OutboundRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// A recorded metric call: `(name, value, labels)`.
+#[cfg(test)]
+type RecordedMetric = (&'static str, f64, Vec<(String, String)>);
+
+/// [`MetricsSink`] keeping every counter, histogram and gauge call.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct RecordingMetricsSink {
+    calls: Mutex<Vec<RecordedMetric>>,
+}
+
+#[cfg(test)]
+impl RecordingMetricsSink {
+    fn record(
+        &self,
+        name: &'static str,
+        value: f64,
+        labels: &[oagw_sdk::metrics::MetricLabel<'_>],
+    ) {
+        let labels = labels
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        self.calls.lock().unwrap().push((name, value, labels));
+    }
+
+    /// Values recorded under `name` whose labels include all of `labels`.
+    pub(crate) fn values(&self, name: &str, labels: &[(&str, &str)]) -> Vec<f64> {
+        self.calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(n, _, recorded)| {
+                *n == name
+                    && labels
+                        .iter()
+                        .all(|(k, v)| recorded.iter().any(|(rk, rv)| rk == k && rv == v))
+            })
+            .map(|(_, value, _)| *value)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::cast_precision_loss)]
+impl MetricsSink for RecordingMetricsSink {
+    fn increment_counter(
+        &self,
+        name: &'static str,
+        value: u64,
+        labels: &[oagw_sdk::metrics::MetricLabel<'_>],
+    ) {
+        self.record(name, value as f64, labels);
+    }
+
+    fn record_histogram(
+        &self,
+        name: &'static str,
+        value: f64,
+        labels: &[oagw_sdk::metrics::MetricLabel<'_>],
+    ) {
+        self.record(name, value, labels);
+    }
+
+    fn record_gauge(
+        &self,
+        name: &'static str,
+        value: u64,
+        labels: &[oagw_sdk::metrics::MetricLabel<'_>],
+    ) {
+        self.record(name, value as f64, labels);
+    }
+}
+
+/// [`Clock`] that only moves when [`MockClock::advance`] is called, so TTLs
+/// and cooldowns can be crossed without sleeping. Clones share the time.
+#[derive(Clone)]
+pub struct MockClock {
+    wall_start: SystemTime,
+    start: Instant,
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl MockClock {
+    /// A clock reading `wall_start` as the wall-clock time.
+    #[must_use]
+    pub fn starting_at(wall_start: SystemTime) -> Self {
+        Self {
+            wall_start,
+            start: Instant::now(),
+            elapsed: Arc::new(Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    /// Move both wall-clock and monotonic time forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        *self.elapsed.lock().unwrap() += by;
+    }
+
+    fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::starting_at(SystemTime::now())
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        self.wall_start + self.elapsed()
+    }
+
+    fn instant(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+}
+
+/// Builder for an [`AuthContext`] in auth plugin tests. Defaults to a
+/// fresh tenant and subject, no config or headers, `GET /`, no body and the
+/// current time.
+#[cfg(test)]
+pub struct AuthContextBuilder {
+    ctx: AuthContext,
+}
+
+#[cfg(test)]
+impl AuthContextBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            ctx: AuthContext {
+                headers: AuthHeaders::new(),
+                config: HashMap::new(),
+                security_context: SecurityContext::builder()
+                    .subject_tenant_id(Uuid::new_v4())
+                    .subject_id(Uuid::new_v4())
+                    .build()
+                    .expect("test security context"),
+                early_response: None,
+                body_fields: Vec::new(),
+                query: Vec::new(),
+                method: "GET".into(),
+                path: "/".into(),
+                body: None,
+                now: SystemTime::now(),
+                credential: None,
+            },
+        }
+    }
+
+    /// Set the plugin configuration.
+    #[must_use]
+    pub fn with_config(mut self, config: HashMap<String, String>) -> Self {
+        self.ctx.config = config;
+        self
+    }
+
+    /// Add an outbound request header.
+    #[must_use]
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.ctx.headers.insert(name, value);
+        self
+    }
+
+    /// Override the security context (e.g. to share a tenant across calls).
+    #[must_use]
+    pub fn with_security_context(mut self, security_context: SecurityContext) -> Self {
+        self.ctx.security_context = security_context;
+        self
+    }
+
+    /// Set the outbound method.
+    #[must_use]
+    pub fn with_method(mut self, method: &str) -> Self {
+        self.ctx.method = method.into();
+        self
+    }
+
+    /// Set the outbound path and query.
+    #[must_use]
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.ctx.path = path.into();
+        self
+    }
+
+    /// Set the buffered request body.
+    #[must_use]
+    pub fn with_body(mut self, body: impl Into<Bytes>) -> Self {
+        self.ctx.body = Some(body.into());
+        self
+    }
+
+    /// Fix the trusted current time.
+    #[must_use]
+    pub fn with_now(mut self, now: SystemTime) -> Self {
+        self.ctx.now = now;
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> AuthContext {
+        self.ctx
+    }
+}
+
+#[cfg(test)]
+impl Default for AuthContextBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Mock `CredStoreClientV1` for tests. Stores secrets in memory keyed by
 /// the bare secret name (without `cred://` prefix).
 pub struct MockCredStoreClient {
@@ -406,6 +665,8 @@ impl TestCpBuilder {
         let cp: Arc<dyn ControlPlaneService> = Arc::new(ControlPlaneServiceImpl::new(
             upstream_repo,
             route_repo,
+            Arc::new(InMemoryTenantRateLimitRepo::new()),
+            Arc::new(InMemoryTenantAuthDefaultRepo::new()),
             tenant_resolver,
             allow_all_enforcer(),
             credstore,
@@ -429,10 +690,19 @@ pub struct TestDpBuilder {
     request_timeout: Option<Duration>,
     authz_client: Option<Arc<dyn AuthZResolverClient>>,
     backend_selector: Option<Arc<dyn EndpointSelector>>,
+    runtime_config: Option<crate::config::SharedRuntimeConfig>,
     max_body_size: Option<usize>,
+    max_url_length: Option<usize>,
     skip_upstream_tls_verify: bool,
     token_http_config: Option<modkit_http::HttpClientConfig>,
     token_cache_config: TokenCacheConfig,
+    response_cache: Option<Arc<dyn ResponseCache>>,
+    metrics_sink: Option<Arc<dyn MetricsSink>>,
+    global_concurrency: Option<(usize, Duration)>,
+    dns_override: HashMap<String, IpAddr>,
+    stream_idle_timeout: Option<Duration>,
+    outbound_capture: Option<OutboundCapture>,
+    clock: Option<MockClock>,
 }
 
 impl TestDpBuilder {
@@ -442,10 +712,19 @@ impl TestDpBuilder {
             request_timeout: None,
             authz_client: None,
             backend_selector: None,
+            runtime_config: None,
             max_body_size: None,
+            max_url_length: None,
             skip_upstream_tls_verify: false,
             token_http_config: None,
             token_cache_config: TokenCacheConfig::default(),
+            response_cache: None,
+            metrics_sink: None,
+            global_concurrency: None,
+            dns_override: HashMap::new(),
+            stream_idle_timeout: None,
+            outbound_capture: None,
+            clock: None,
         }
     }
 
@@ -470,6 +749,24 @@ impl TestDpBuilder {
         self
     }
 
+    /// Override the maximum request URL length (useful for URL-limit tests).
+    #[must_use]
+    pub fn with_max_url_length(mut self, len: usize) -> Self {
+        self.max_url_length = Some(len);
+        self
+    }
+
+    /// Cap in-flight proxy requests (useful for load-shedding tests).
+    #[must_use]
+    pub fn with_global_concurrency(
+        mut self,
+        max_in_flight: usize,
+        queue_timeout: Duration,
+    ) -> Self {
+        self.global_concurrency = Some((max_in_flight, queue_timeout));
+        self
+    }
+
     /// Skip upstream TLS certificate verification. **Test use only.**
     #[must_use]
     pub fn with_skip_upstream_tls_verify(mut self, allow: bool) -> Self {
@@ -477,6 +774,21 @@ impl TestDpBuilder {
         self
     }
 
+    /// End stalled SSE responses with the default terminal event (useful for
+    /// streaming tests).
+    #[must_use]
+    pub fn with_stream_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.stream_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Pin hostnames to fixed addresses (useful for hostname upstreams).
+    #[must_use]
+    pub fn with_dns_override(mut self, overrides: HashMap<String, IpAddr>) -> Self {
+        self.dns_override = overrides;
+        self
+    }
+
     /// Inject a shared `EndpointSelector` so callers can hold the same
     /// instance that the DP service uses (e.g. for `invalidate()` calls).
     #[must_use]
@@ -485,6 +797,17 @@ impl TestDpBuilder {
         self
     }
 
+    /// Read limits from a shared runtime config so callers can update them
+    /// through the same handle (e.g. an `AppState`).
+    #[must_use]
+    pub(crate) fn with_runtime_config(
+        mut self,
+        runtime: crate::config::SharedRuntimeConfig,
+    ) -> Self {
+        self.runtime_config = Some(runtime);
+        self
+    }
+
     /// Override the HTTP client config for OAuth2 token endpoints.
     /// Pass `HttpClientConfig::for_testing()` to allow plain HTTP in tests.
     #[must_use]
@@ -500,6 +823,37 @@ impl TestDpBuilder {
         self
     }
 
+    /// Override the response cache backend (useful for cache tests).
+    #[must_use]
+    pub fn with_response_cache(mut self, cache: Arc<dyn ResponseCache>) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
+    /// Record metrics through `sink` (useful for metrics tests).
+    #[must_use]
+    pub fn with_metrics_sink(mut self, sink: Arc<dyn MetricsSink>) -> Self {
+        self.metrics_sink = Some(sink);
+        self
+    }
+
+    /// Record every request the DP sends upstream; the returned handle
+    /// yields them (useful for asserting outbound headers).
+    #[must_use]
+    pub fn with_outbound_capture(mut self) -> (Self, OutboundCapture) {
+        let capture = OutboundCapture::default();
+        self.outbound_capture = Some(capture.clone());
+        (self, capture)
+    }
+
+    /// Read time from `clock`: auth plugin signing time, circuit-breaker
+    /// cooldowns and rate-limit refills follow [`MockClock::advance`].
+    #[must_use]
+    pub fn with_clock(mut self, clock: MockClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
     /// Fetch `CredStoreClientV1` from the hub, create a DP service with
     /// the given CP, and return the trait object.
     pub(crate) fn build_and_register(
@@ -517,11 +871,13 @@ impl TestDpBuilder {
         let policy_enforcer = PolicyEnforcer::new(authz_client);
 
         let server_conf = Arc::new(pingora_core::server::configuration::ServerConf::default());
+        let dns_cache = Arc::new(DnsCache::default().with_overrides(self.dns_override));
         let pingora_proxy = crate::infra::proxy::pingora_proxy::PingoraProxy::new(
             Duration::from_secs(10),
             Duration::from_secs(30),
         )
-        .with_skip_upstream_tls_verify(self.skip_upstream_tls_verify);
+        .with_skip_upstream_tls_verify(self.skip_upstream_tls_verify)
+        .with_dns_cache(dns_cache.clone());
         let proxy = Arc::new(crate::infra::proxy::pingora_proxy::new_http_proxy(
             &server_conf,
             pingora_proxy,
@@ -529,7 +885,10 @@ impl TestDpBuilder {
 
         let backend_selector: Arc<dyn EndpointSelector> =
             self.backend_selector.unwrap_or_else(|| {
-                Arc::new(crate::infra::proxy::pingora_proxy::PingoraEndpointSelector::new())
+                Arc::new(
+                    crate::infra::proxy::pingora_proxy::PingoraEndpointSelector::new()
+                        .with_dns_cache(dns_cache),
+                )
             });
 
         let mut svc = DataPlaneServiceImpl::new(
@@ -542,12 +901,39 @@ impl TestDpBuilder {
             proxy,
         )
         .with_allow_http_upstream(true);
+        if let Some(runtime) = self.runtime_config {
+            svc = svc.with_runtime_config(runtime);
+        }
         if let Some(timeout) = self.request_timeout {
             svc = svc.with_request_timeout(timeout);
         }
         if let Some(size) = self.max_body_size {
             svc = svc.with_max_body_size(size);
         }
+        if let Some(len) = self.max_url_length {
+            svc = svc.with_max_url_length(len);
+        }
+        if let Some(cache) = self.response_cache {
+            svc = svc.with_response_cache(cache);
+        }
+        if let Some(sink) = self.metrics_sink {
+            svc = svc.with_metrics(crate::infra::metrics::OagwMetrics::with_sink(sink));
+        }
+        if let Some((max_in_flight, queue_timeout)) = self.global_concurrency {
+            svc = svc.with_global_concurrency(Some(max_in_flight), queue_timeout);
+        }
+        if let Some(timeout) = self.stream_idle_timeout {
+            svc = svc.with_stream_idle_timeout(
+                Some(timeout),
+                crate::infra::proxy::stream_idle::DEFAULT_TERMINAL_EVENT,
+            );
+        }
+        if let Some(capture) = self.outbound_capture {
+            svc = svc.with_outbound_capture(capture);
+        }
+        if let Some(clock) = self.clock {
+            svc = svc.with_clock(Arc::new(clock));
+        }
 
         Arc::new(svc)
     }
@@ -578,9 +964,19 @@ pub fn build_test_app_state(
 ) -> TestAppState {
     let backend_selector: Arc<dyn EndpointSelector> =
         Arc::new(crate::infra::proxy::pingora_proxy::PingoraEndpointSelector::new());
+    // The DP builder's limits are written into this handle, so the handler
+    // and the DP agree and `update_runtime_config` reaches both.
+    let config = Arc::new(arc_swap::ArcSwap::from_pointee(
+        crate::config::RuntimeConfig {
+            request_timeout: Duration::from_secs(30),
+            max_body_size_bytes: 100 * 1024 * 1024, // 100 MB default for tests
+            error_body_template: None,
+        },
+    ));
     let cp = cp_builder.build_and_register(hub);
     let dp = dp_builder
         .with_backend_selector(backend_selector.clone())
+        .with_runtime_config(config.clone())
         .build_and_register(hub, cp.clone());
     let facade: Arc<dyn ServiceGatewayClientV1> =
         Arc::new(ServiceGatewayClientV1Facade::new(cp.clone(), dp.clone()));
@@ -590,9 +986,7 @@ pub fn build_test_app_state(
             cp,
             dp,
             backend_selector,
-            config: crate::config::RuntimeConfig {
-                max_body_size_bytes: 100 * 1024 * 1024, // 100 MB default for tests
-            },
+            config,
         },
         facade,
     }
@@ -611,3 +1005,78 @@ pub fn build_test_gateway(
     hub.register::<dyn ServiceGatewayClientV1>(oagw.clone());
     oagw
 }
+
+/// HTTP/1.1 client returned by [`spawn_test_gateway`].
+pub type TestHttpClient = hyper_util::client::legacy::Client<
+    hyper_util::client::legacy::connect::HttpConnector,
+    axum::body::Body,
+>;
+
+/// Gateway served over real HTTP by [`spawn_test_gateway`]. The server stops
+/// when this is dropped.
+pub struct TestGateway {
+    /// `http://127.0.0.1:<port>`, without a trailing slash.
+    pub base_url: String,
+    pub client: TestHttpClient,
+    /// Facade over the same state, for creating upstreams and routes.
+    pub facade: Arc<dyn ServiceGatewayClientV1>,
+    /// Security context every request through the router runs as.
+    pub ctx: SecurityContext,
+    server: tokio::task::JoinHandle<()>,
+}
+
+impl TestGateway {
+    /// Absolute URL of `path`, e.g. `/oagw/v1/proxy/openai/v1/models`.
+    #[must_use]
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+}
+
+impl Drop for TestGateway {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// Serve the real REST router over [`build_test_app_state`]'s `AppState` on
+/// an ephemeral local port, so that tests exercise the full HTTP path
+/// (body limits, error envelopes, client address) rather than a facade call.
+///
+/// # Panics
+/// Panics if no local port can be bound.
+pub async fn spawn_test_gateway(
+    hub: &ClientHub,
+    cp_builder: TestCpBuilder,
+    dp_builder: TestDpBuilder,
+) -> TestGateway {
+    let app_state = build_test_app_state(hub, cp_builder, dp_builder);
+    let ctx = SecurityContext::builder()
+        .subject_tenant_id(Uuid::new_v4())
+        .subject_id(Uuid::new_v4())
+        .build()
+        .expect("test security context");
+    let router = crate::api::rest::routes::test_router(app_state.state, ctx.clone());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("failed to bind test gateway");
+    let addr = listener.local_addr().expect("failed to get local addr");
+    let server = tokio::spawn(async move {
+        axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .expect("test gateway server error");
+    });
+
+    TestGateway {
+        base_url: format!("http://{addr}"),
+        client: hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
+            .build_http(),
+        facade: app_state.facade,
+        ctx,
+        server,
+    }
+}
@@ -1,6 +1,6 @@
 //! Centralized catalog of all OAGW GTS entities for Types Registry registration.
 //!
-//! Returns all 20 entities (7 schemas + 13 instances) in a single batch,
+//! Returns all 22 entities (7 schemas + 15 instances) in a single batch,
 //! ready for `TypesRegistryClient::register()`.
 
 use serde_json::{Value, json};
@@ -28,7 +28,7 @@ fn instance_entity(gts_id: &str, description: &str) -> Value {
     })
 }
 
-/// Returns all OAGW GTS entities (7 schemas + 13 instances) for batch registration.
+/// Returns all OAGW GTS entities (7 schemas + 15 instances) for batch registration.
 pub fn oagw_gts_entities() -> Vec<Value> {
     vec![
         // -- Schemas (7) --
@@ -39,10 +39,14 @@ pub fn oagw_gts_entities() -> Vec<Value> {
         schema_entity(GUARD_PLUGIN_SCHEMA, "Guard plugin category"),
         schema_entity(TRANSFORM_PLUGIN_SCHEMA, "Transform plugin category"),
         schema_entity(PROXY_SCHEMA, "Proxy API (permissions)"),
-        // -- Protocol instances (2) --
+        // -- Protocol instances (3) --
         instance_entity(HTTP_PROTOCOL_ID, "HTTP protocol"),
+        instance_entity(
+            H2C_PROTOCOL_ID,
+            "HTTP/2 cleartext (prior knowledge) protocol",
+        ),
         instance_entity(GRPC_PROTOCOL_ID, "gRPC protocol"),
-        // -- Auth plugin instances (6) --
+        // -- Auth plugin instances (10) --
         instance_entity(NOOP_AUTH_PLUGIN_ID, "No-op (passthrough) auth"),
         instance_entity(APIKEY_AUTH_PLUGIN_ID, "API key injection"),
         instance_entity(BASIC_AUTH_PLUGIN_ID, "HTTP Basic auth"),
@@ -55,6 +59,16 @@ pub fn oagw_gts_entities() -> Vec<Value> {
             OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID,
             "OAuth2 client credentials (Basic)",
         ),
+        instance_entity(TOKEN_PASSTHROUGH_AUTH_PLUGIN_ID, "Caller token passthrough"),
+        instance_entity(REQUEST_TOKEN_AUTH_PLUGIN_ID, "Per-request token injection"),
+        instance_entity(
+            ROTATING_APIKEY_AUTH_PLUGIN_ID,
+            "API key rotation across several keys",
+        ),
+        instance_entity(
+            WEBHOOK_SIGNATURE_AUTH_PLUGIN_ID,
+            "Inbound webhook signature verification",
+        ),
         // -- Guard plugin instances (2) --
         instance_entity(TIMEOUT_GUARD_PLUGIN_ID, "Request timeout"),
         instance_entity(CORS_GUARD_PLUGIN_ID, "CORS handling"),
@@ -75,12 +89,12 @@ mod tests {
     }
 
     #[test]
-    fn catalog_returns_exactly_20_entities() {
+    fn catalog_returns_exactly_25_entities() {
         let entities = oagw_gts_entities();
         assert_eq!(
             entities.len(),
-            20,
-            "expected 20 entities (7 schemas + 13 instances)"
+            25,
+            "expected 25 entities (7 schemas + 18 instances)"
         );
     }
 
@@ -112,7 +126,7 @@ mod tests {
     }
 
     #[test]
-    fn seven_schemas_and_thirteen_instances() {
+    fn seven_schemas_and_fifteen_instances() {
         let entities = oagw_gts_entities();
         let schemas: Vec<_> = entities
             .iter()
@@ -124,7 +138,7 @@ mod tests {
             .collect();
 
         assert_eq!(schemas.len(), 7, "expected 7 schemas");
-        assert_eq!(instances.len(), 13, "expected 13 instances");
+        assert_eq!(instances.len(), 18, "expected 18 instances");
     }
 
     #[test]
@@ -0,0 +1,233 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use arc_swap::ArcSwap;
+use oagw_sdk::metrics::{MetricLabel, MetricsSink};
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Gauge, Histogram, Meter};
+use uuid::Uuid;
+
+pub(crate) use oagw_sdk::metrics::{
+    AUTH_PLUGIN_DURATION, BODY_LIMIT_DECLARED_SIZE, BODY_LIMIT_EXCEEDED, CACHE_LOOKUPS, CACHE_SIZE,
+    PROXY_RESPONSES,
+};
+
+/// A request rejected with 413 because its body exceeds `max_body_size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLimitExceeded {
+    pub route_id: Uuid,
+    /// `Content-Length` when the client sent one, otherwise the bytes
+    /// received before the limit tripped.
+    pub declared_len: usize,
+    pub limit: usize,
+}
+
+/// Response statuses labeled `error` in metrics and logs; every `5xx` by
+/// default.
+#[derive(Debug, Clone)]
+pub struct ErrorStatusCodes(HashSet<u16>);
+
+impl ErrorStatusCodes {
+    #[must_use]
+    pub fn new(codes: impl IntoIterator<Item = u16>) -> Self {
+        Self(codes.into_iter().collect())
+    }
+
+    /// `"error"` or `"success"` for a response with `status`.
+    #[must_use]
+    pub fn result_label(&self, status: http::StatusCode) -> &'static str {
+        if self.0.contains(&status.as_u16()) {
+            "error"
+        } else {
+            "success"
+        }
+    }
+}
+
+impl Default for ErrorStatusCodes {
+    fn default() -> Self {
+        Self::new(500..=599)
+    }
+}
+
+/// Metrics recorded by the data plane, delivered to a [`MetricsSink`].
+///
+/// Clones share the sink, so handing a clone to a component built before the
+/// sink is chosen (e.g. a built-in auth plugin) is fine: [`Self::redirect`]
+/// reaches every clone.
+#[derive(Clone)]
+pub struct OagwMetrics {
+    sink: Arc<ArcSwap<Arc<dyn MetricsSink>>>,
+}
+
+impl OagwMetrics {
+    /// Record on OpenTelemetry instruments created from `meter`.
+    #[must_use]
+    pub fn new(meter: &Meter) -> Self {
+        Self::with_sink(Arc::new(OtelMetricsSink::new(meter)))
+    }
+
+    /// Record through a custom sink (e.g. one registered in the `ClientHub`).
+    #[must_use]
+    pub fn with_sink(sink: Arc<dyn MetricsSink>) -> Self {
+        Self {
+            sink: Arc::new(ArcSwap::from_pointee(sink)),
+        }
+    }
+
+    /// Deliver this handle's metrics, and those of all its clones, to the
+    /// sink of `other` from now on.
+    pub fn redirect(&self, other: &OagwMetrics) {
+        self.sink.store(other.sink.load_full());
+    }
+
+    fn sink(&self) -> Arc<Arc<dyn MetricsSink>> {
+        self.sink.load_full()
+    }
+
+    /// Record one lookup in the named cache.
+    pub fn record_cache_lookup(&self, cache: &str, hit: bool) {
+        self.sink().increment_counter(
+            CACHE_LOOKUPS,
+            1,
+            &[
+                ("cache", cache),
+                ("result", if hit { "hit" } else { "miss" }),
+            ],
+        );
+    }
+
+    /// Record the number of entries currently held by the named cache.
+    pub fn record_cache_size(&self, cache: &str, entries: usize) {
+        self.sink()
+            .record_gauge(CACHE_SIZE, entries as u64, &[("cache", cache)]);
+    }
+
+    /// Record one `authenticate` call, labeled by plugin ID and outcome.
+    pub fn record_auth_plugin(&self, plugin_id: &str, success: bool, elapsed: Duration) {
+        self.sink().record_histogram(
+            AUTH_PLUGIN_DURATION,
+            elapsed.as_secs_f64(),
+            &[
+                ("plugin_id", plugin_id),
+                ("result", if success { "success" } else { "failure" }),
+            ],
+        );
+    }
+
+    /// Record a proxied response, labeled by upstream, status, and whether
+    /// the status counts as an error.
+    pub fn record_proxy_response(&self, upstream_id: Uuid, status: http::StatusCode, result: &str) {
+        let upstream_id = upstream_id.to_string();
+        self.sink().increment_counter(
+            PROXY_RESPONSES,
+            1,
+            &[
+                ("upstream_id", upstream_id.as_str()),
+                ("status", status.as_str()),
+                ("result", result),
+            ],
+        );
+    }
+
+    /// Record a body-size rejection, labeled by route.
+    pub fn record_body_limit_exceeded(&self, event: BodyLimitExceeded) {
+        tracing::info!(
+            route_id = %event.route_id,
+            declared_len = event.declared_len,
+            limit = event.limit,
+            "request body limit exceeded"
+        );
+        let route_id = event.route_id.to_string();
+        let labels = [("route_id", route_id.as_str())];
+        let sink = self.sink();
+        sink.increment_counter(BODY_LIMIT_EXCEEDED, 1, &labels);
+        sink.record_histogram(BODY_LIMIT_DECLARED_SIZE, event.declared_len as f64, &labels);
+    }
+}
+
+/// Default sink: OpenTelemetry instruments, exported by the host process
+/// (Prometheus in standard deployments).
+pub struct OtelMetricsSink {
+    meter: Meter,
+    auth_plugin_duration: Histogram<f64>,
+    body_limit_exceeded: Counter<u64>,
+    body_limit_declared_size: Histogram<u64>,
+    proxy_responses: Counter<u64>,
+    cache_lookups: Counter<u64>,
+    cache_size: Gauge<u64>,
+}
+
+impl OtelMetricsSink {
+    #[must_use]
+    pub fn new(meter: &Meter) -> Self {
+        Self {
+            meter: meter.clone(),
+            auth_plugin_duration: meter
+                .f64_histogram(AUTH_PLUGIN_DURATION)
+                .with_description("Latency of outbound auth plugin execution")
+                .with_unit("s")
+                .build(),
+            body_limit_exceeded: meter
+                .u64_counter(BODY_LIMIT_EXCEEDED)
+                .with_description("Requests rejected for exceeding the body size limit")
+                .build(),
+            body_limit_declared_size: meter
+                .u64_histogram(BODY_LIMIT_DECLARED_SIZE)
+                .with_description("Declared size of request bodies rejected by the size limit")
+                .with_unit("By")
+                .build(),
+            proxy_responses: meter
+                .u64_counter(PROXY_RESPONSES)
+                .with_description("Proxied responses by upstream, status, and result")
+                .build(),
+            cache_lookups: meter
+                .u64_counter(CACHE_LOOKUPS)
+                .with_description("Cache lookups by cache and result")
+                .build(),
+            cache_size: meter
+                .u64_gauge(CACHE_SIZE)
+                .with_description("Entries held by a cache")
+                .build(),
+        }
+    }
+}
+
+fn key_values(labels: &[MetricLabel<'_>]) -> Vec<KeyValue> {
+    labels
+        .iter()
+        .map(|(key, value)| KeyValue::new(*key, (*value).to_owned()))
+        .collect()
+}
+
+impl MetricsSink for OtelMetricsSink {
+    fn increment_counter(&self, name: &'static str, value: u64, labels: &[MetricLabel<'_>]) {
+        let attrs = key_values(labels);
+        match name {
+            BODY_LIMIT_EXCEEDED => self.body_limit_exceeded.add(value, &attrs),
+            PROXY_RESPONSES => self.proxy_responses.add(value, &attrs),
+            CACHE_LOOKUPS => self.cache_lookups.add(value, &attrs),
+            _ => self.meter.u64_counter(name).build().add(value, &attrs),
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn record_histogram(&self, name: &'static str, value: f64, labels: &[MetricLabel<'_>]) {
+        let attrs = key_values(labels);
+        match name {
+            AUTH_PLUGIN_DURATION => self.auth_plugin_duration.record(value, &attrs),
+            // Byte counts are whole numbers; keep the integer instrument.
+            BODY_LIMIT_DECLARED_SIZE => self.body_limit_declared_size.record(value as u64, &attrs),
+            _ => self.meter.f64_histogram(name).build().record(value, &attrs),
+        }
+    }
+
+    fn record_gauge(&self, name: &'static str, value: u64, labels: &[MetricLabel<'_>]) {
+        let attrs = key_values(labels);
+        match name {
+            CACHE_SIZE => self.cache_size.record(value, &attrs),
+            _ => self.meter.u64_gauge(name).build().record(value, &attrs),
+        }
+    }
+}
@@ -1,4 +1,6 @@
+pub(crate) mod metrics;
 pub(crate) mod plugin;
 pub(crate) mod proxy;
+pub(crate) mod static_authz;
 pub(crate) mod storage;
 pub(crate) mod type_provisioning;
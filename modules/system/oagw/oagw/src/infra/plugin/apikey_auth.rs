@@ -1,32 +1,152 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use async_trait::async_trait;
-use credstore_sdk::{CredStoreClientV1, SecretRef};
-use serde::Deserialize;
-
-use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
+use credstore_sdk::CredStoreClientV1;
+use modkit::client_hub::ClientHub;
+use serde::{Deserialize, Deserializer};
+
+use super::secret_resolver::SecretResolvers;
+use crate::domain::plugin::{
+    AuthContext, AuthPlugin, ConfigKey, ConfigValueType, PluginError, select_secret_field,
+};
+
+/// Keys accepted in the API key plugin's configuration; see [`ApiKeyConfig`].
+/// Exactly one of `secret_ref` and `value` must be set, and exactly one of
+/// `header` and `body_pointer`; `secret_field` only applies to `secret_ref`.
+const CONFIG_SCHEMA: &[ConfigKey] = &[
+    ConfigKey::optional("header", ConfigValueType::String),
+    ConfigKey::optional("body_pointer", ConfigValueType::String),
+    ConfigKey::optional("prefix", ConfigValueType::String),
+    ConfigKey::optional("secret_ref", ConfigValueType::SecretRef),
+    ConfigKey::optional("default_secret_ref", ConfigValueType::SecretRef),
+    ConfigKey::optional("secret_field", ConfigValueType::String),
+    ConfigKey::optional("value", ConfigValueType::String),
+    ConfigKey::optional("reject_empty_secret", ConfigValueType::Bool),
+];
 
 /// Configuration for the API key auth plugin.
 #[derive(Debug, Deserialize)]
 struct ApiKeyConfig {
     /// Header name to set (e.g. "Authorization", "X-API-Key").
-    header: String,
+    #[serde(default)]
+    header: Option<String>,
+    /// JSON pointer of the request body field to set instead of a header
+    /// (e.g. "/auth/api_key"). The request body must be a JSON object.
+    #[serde(default)]
+    body_pointer: Option<String>,
     /// Prefix prepended to the secret value (e.g. "Bearer ").
     #[serde(default)]
     prefix: String,
-    /// Secret reference to resolve (e.g. "cred://openai-key").
-    secret_ref: String,
+    /// Secret reference to resolve (e.g. "cred://openai-key"). May contain
+    /// `{tenant}` to select a per-tenant secret (e.g. "cred://{tenant}/openai-key").
+    /// Other schemes (e.g. "env://OPENAI_KEY") need a registered resolver.
+    #[serde(default)]
+    secret_ref: Option<String>,
+    /// Reference used when the tenant-specific secret does not exist.
+    #[serde(default)]
+    default_secret_ref: Option<String>,
+    /// Field to extract when the secret holds a JSON object (e.g.
+    /// "client_secret"); the whole secret is used when unset.
+    #[serde(default)]
+    secret_field: Option<String>,
+    /// Literal key stored in the upstream configuration, bypassing the
+    /// credential store. Insecure; intended for quick setups and tests.
+    #[serde(default)]
+    value: Option<String>,
+    /// Fail with `SecretNotFound` when the resolved secret is empty or
+    /// whitespace-only instead of injecting an empty credential.
+    /// Default: true.
+    #[serde(
+        default = "default_reject_empty_secret",
+        deserialize_with = "bool_from_str"
+    )]
+    reject_empty_secret: bool,
+}
+
+fn default_reject_empty_secret() -> bool {
+    true
+}
+
+/// Plugin configuration values arrive as strings.
+fn bool_from_str<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    value
+        .parse()
+        .map_err(|_| serde::de::Error::custom(format!("expected true or false, got '{value}'")))
 }
 
 /// Auth plugin that resolves a secret reference and injects it as a header value.
 pub struct ApiKeyAuthPlugin {
-    credstore: Arc<dyn CredStoreClientV1>,
+    secrets: SecretResolvers,
+    /// Set once the inline-`value` warning has been logged.
+    inline_value_warned: AtomicBool,
 }
 
 impl ApiKeyAuthPlugin {
     #[must_use]
     pub fn new(credstore: Arc<dyn CredStoreClientV1>) -> Self {
-        Self { credstore }
+        Self {
+            secrets: SecretResolvers::new(credstore),
+            inline_value_warned: AtomicBool::new(false),
+        }
+    }
+
+    /// Resolve secret references with schemes other than `cred://` through
+    /// the `SecretResolver`s registered in `hub`.
+    #[must_use]
+    pub fn with_secret_resolvers(mut self, hub: Arc<ClientHub>) -> Self {
+        self.secrets = self.secrets.with_hub(hub);
+        self
+    }
+
+    /// Resolve `secret_ref` for the calling tenant; `None` when absent.
+    async fn lookup(
+        &self,
+        ctx: &AuthContext,
+        secret_ref: &str,
+    ) -> Result<Option<String>, PluginError> {
+        self.secrets
+            .resolve(&ctx.security_context, secret_ref)
+            .await
+    }
+
+    /// Resolve the key by its reference scheme, falling back to
+    /// `default_secret_ref` when the primary secret does not exist, and pick
+    /// `secret_field` out of it when set. With `reject_empty`, a blank key
+    /// counts as missing.
+    async fn resolve_secret(
+        &self,
+        ctx: &AuthContext,
+        secret_ref: &str,
+        default_secret_ref: Option<&str>,
+        secret_field: Option<&str>,
+        reject_empty: bool,
+    ) -> Result<String, PluginError> {
+        let (resolved_ref, secret) = match self.lookup(ctx, secret_ref).await? {
+            Some(secret) => (secret_ref, secret),
+            None => {
+                let Some(default_ref) = default_secret_ref else {
+                    return Err(PluginError::SecretNotFound(secret_ref.to_string()));
+                };
+                let secret = self
+                    .lookup(ctx, default_ref)
+                    .await?
+                    .ok_or_else(|| PluginError::SecretNotFound(default_ref.to_string()))?;
+                (default_ref, secret)
+            }
+        };
+
+        let secret = match secret_field {
+            Some(field) => select_secret_field(&secret, resolved_ref, field)?,
+            None => secret,
+        };
+        if reject_empty && secret.trim().is_empty() {
+            return Err(PluginError::SecretNotFound(format!(
+                "{resolved_ref} (empty value)"
+            )));
+        }
+        Ok(secret)
     }
 }
 
@@ -38,30 +158,58 @@ impl AuthPlugin for ApiKeyAuthPlugin {
                 .map_err(|e| PluginError::Internal(format!("invalid apikey auth config: {e}")))?,
         )
         .map_err(|e| PluginError::Internal(format!("invalid apikey auth config: {e}")))?;
-
-        let raw_ref = config
-            .secret_ref
-            .strip_prefix("cred://")
-            .unwrap_or(&config.secret_ref);
-        let key = SecretRef::new(raw_ref)
-            .map_err(|e| PluginError::Internal(format!("invalid secret ref '{raw_ref}': {e}")))?;
-
-        let response = self
-            .credstore
-            .get(&ctx.security_context, &key)
-            .await
-            .map_err(|e| PluginError::Internal(format!("credstore error: {e}")))?
-            .ok_or_else(|| PluginError::SecretNotFound(config.secret_ref.clone()))?;
-
-        let secret_str = std::str::from_utf8(response.value.as_bytes())
-            .map_err(|_| PluginError::Internal("secret value is not valid UTF-8".into()))?
-            .to_string();
+        if config.header.is_some() == config.body_pointer.is_some() {
+            return Err(PluginError::InvalidConfig(
+                "exactly one of header or body_pointer is required".into(),
+            ));
+        }
+        let secret_str = match (config.value, config.secret_ref.as_deref()) {
+            (Some(_), Some(_)) => {
+                return Err(PluginError::InvalidConfig(
+                    "secret_ref and value are mutually exclusive".into(),
+                ));
+            }
+            (None, None) => {
+                return Err(PluginError::InvalidConfig(
+                    "one of secret_ref or value is required".into(),
+                ));
+            }
+            (Some(value), None) => {
+                if !self.inline_value_warned.swap(true, Ordering::Relaxed) {
+                    tracing::warn!(
+                        header = ?config.header,
+                        body_pointer = ?config.body_pointer,
+                        "apikey auth is using an inline `value` instead of a secret_ref; \
+                         this is insecure and intended for testing only"
+                    );
+                }
+                value
+            }
+            (None, Some(secret_ref)) => {
+                self.resolve_secret(
+                    ctx,
+                    secret_ref,
+                    config.default_secret_ref.as_deref(),
+                    config.secret_field.as_deref(),
+                    config.reject_empty_secret,
+                )
+                .await?
+            }
+        };
 
         let value = format!("{}{}", config.prefix, secret_str);
-        ctx.headers.insert(config.header.to_lowercase(), value);
+        if let Some(header) = config.header {
+            ctx.headers.insert(header.to_lowercase(), value);
+        } else if let Some(pointer) = config.body_pointer {
+            ctx.body_fields.push((pointer, value));
+        }
 
         Ok(())
     }
+
+    fn config_schema(&self) -> &'static [ConfigKey] {
+        CONFIG_SCHEMA
+    }
 }
 
 #[cfg(test)]
@@ -72,11 +220,16 @@ mod tests {
     use credstore_sdk::{
         CredStoreClientV1, CredStoreError, GetSecretResponse, SecretRef, SecretValue, SharingMode,
     };
+    use modkit::client_hub::ClientScope;
     use modkit_security::SecurityContext;
+    use oagw_sdk::SecretResolver;
     use uuid::Uuid;
 
     use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
-    use crate::domain::test_support::{FailingCredStoreClient, MockCredStoreClient};
+    use crate::domain::test_support::{
+        AuthContextBuilder, FailingCredStoreClient, MockCredStoreClient,
+    };
+    use crate::infra::plugin::secret_resolver::{EnvSecretResolver, FileSecretResolver};
 
     use super::*;
 
@@ -88,22 +241,6 @@ mod tests {
         ])
     }
 
-    fn test_security_context() -> SecurityContext {
-        SecurityContext::builder()
-            .subject_tenant_id(Uuid::new_v4())
-            .subject_id(Uuid::new_v4())
-            .build()
-            .expect("test security context")
-    }
-
-    fn make_auth_ctx(config: HashMap<String, String>) -> AuthContext {
-        AuthContext {
-            headers: HashMap::new(),
-            config,
-            security_context: test_security_context(),
-        }
-    }
-
     #[tokio::test]
     async fn injects_bearer_token() {
         let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
@@ -112,7 +249,9 @@ mod tests {
         )]));
         let plugin = ApiKeyAuthPlugin::new(credstore);
 
-        let mut ctx = make_auth_ctx(make_config("authorization", "Bearer ", "cred://openai-key"));
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(make_config("authorization", "Bearer ", "cred://openai-key"))
+            .build();
 
         plugin.authenticate(&mut ctx).await.unwrap();
         assert_eq!(
@@ -121,6 +260,58 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn empty_secret_rejected_by_default() {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "blank-key".into(),
+            " \n".into(),
+        )]));
+        let plugin = ApiKeyAuthPlugin::new(credstore);
+
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(make_config("authorization", "Bearer ", "cred://blank-key"))
+            .build();
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(
+            matches!(err, PluginError::SecretNotFound(ref r) if r == "cred://blank-key (empty value)"),
+            "{err:?}"
+        );
+        assert!(!ctx.headers.contains_key("authorization"));
+    }
+
+    #[tokio::test]
+    async fn empty_secret_injected_when_rejection_disabled() {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "blank-key".into(),
+            String::new(),
+        )]));
+        let plugin = ApiKeyAuthPlugin::new(credstore);
+
+        let mut config = make_config("authorization", "Bearer ", "cred://blank-key");
+        config.insert("reject_empty_secret".into(), "false".into());
+        let mut ctx = AuthContextBuilder::new().with_config(config).build();
+
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(ctx.headers.get("authorization"), Some("Bearer "));
+    }
+
+    #[tokio::test]
+    async fn non_empty_secret_passes_with_rejection_enabled() {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "openai-key".into(),
+            "sk-abc123".into(),
+        )]));
+        let plugin = ApiKeyAuthPlugin::new(credstore);
+
+        let mut config = make_config("authorization", "Bearer ", "cred://openai-key");
+        config.insert("reject_empty_secret".into(), "true".into());
+        let mut ctx = AuthContextBuilder::new().with_config(config).build();
+
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(ctx.headers.get("authorization"), Some("Bearer sk-abc123"));
+    }
+
     #[tokio::test]
     async fn injects_custom_header_no_prefix() {
         let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
@@ -129,7 +320,9 @@ mod tests {
         )]));
         let plugin = ApiKeyAuthPlugin::new(credstore);
 
-        let mut ctx = make_auth_ctx(make_config("x-api-key", "", "cred://custom-key"));
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(make_config("x-api-key", "", "cred://custom-key"))
+            .build();
 
         plugin.authenticate(&mut ctx).await.unwrap();
         assert_eq!(ctx.headers.get("x-api-key").unwrap(), "my-secret-key");
@@ -144,7 +337,9 @@ mod tests {
         let plugin = ApiKeyAuthPlugin::new(credstore);
 
         // With cred:// prefix
-        let mut ctx = make_auth_ctx(make_config("x-api-key", "", "cred://my-key"));
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(make_config("x-api-key", "", "cred://my-key"))
+            .build();
         plugin.authenticate(&mut ctx).await.unwrap();
         assert_eq!(ctx.headers.get("x-api-key").unwrap(), "secret-value");
     }
@@ -158,7 +353,9 @@ mod tests {
         let plugin = ApiKeyAuthPlugin::new(credstore);
 
         // Without cred:// prefix
-        let mut ctx = make_auth_ctx(make_config("x-api-key", "", "plain-key"));
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(make_config("x-api-key", "", "plain-key"))
+            .build();
         plugin.authenticate(&mut ctx).await.unwrap();
         assert_eq!(ctx.headers.get("x-api-key").unwrap(), "plain-value");
     }
@@ -168,16 +365,209 @@ mod tests {
         let credstore = Arc::new(MockCredStoreClient::empty());
         let plugin = ApiKeyAuthPlugin::new(credstore);
 
-        let mut ctx = make_auth_ctx(make_config("authorization", "Bearer ", "cred://missing"));
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(make_config("authorization", "Bearer ", "cred://missing"))
+            .build();
 
         let err = plugin.authenticate(&mut ctx).await.unwrap_err();
         assert!(matches!(err, PluginError::SecretNotFound(_)));
     }
 
+    fn auth_ctx_for_tenant(config: HashMap<String, String>, tenant_id: Uuid) -> AuthContext {
+        AuthContextBuilder::new()
+            .with_config(config)
+            .with_security_context(
+                SecurityContext::builder()
+                    .subject_tenant_id(tenant_id)
+                    .subject_id(Uuid::new_v4())
+                    .build()
+                    .expect("test security context"),
+            )
+            .build()
+    }
+
+    #[tokio::test]
+    async fn tenant_templated_ref_resolves_per_tenant() {
+        let (tenant_a, tenant_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![
+            (format!("{tenant_a}-openai-key"), "sk-tenant-a".into()),
+            (format!("{tenant_b}-openai-key"), "sk-tenant-b".into()),
+        ]));
+        let plugin = ApiKeyAuthPlugin::new(credstore);
+        let config = make_config("authorization", "Bearer ", "cred://{tenant}/openai-key");
+
+        let mut ctx_a = auth_ctx_for_tenant(config.clone(), tenant_a);
+        plugin.authenticate(&mut ctx_a).await.unwrap();
+        let mut ctx_b = auth_ctx_for_tenant(config, tenant_b);
+        plugin.authenticate(&mut ctx_b).await.unwrap();
+
+        assert_eq!(
+            ctx_a.headers.get("authorization").unwrap(),
+            "Bearer sk-tenant-a"
+        );
+        assert_eq!(
+            ctx_b.headers.get("authorization").unwrap(),
+            "Bearer sk-tenant-b"
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_tenant_secret_falls_back_to_default_ref() {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "openai-key".into(),
+            "sk-shared".into(),
+        )]));
+        let plugin = ApiKeyAuthPlugin::new(credstore);
+        let mut config = make_config("authorization", "Bearer ", "cred://{tenant}/openai-key");
+        config.insert("default_secret_ref".into(), "cred://openai-key".into());
+
+        let mut ctx = auth_ctx_for_tenant(config, Uuid::new_v4());
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(
+            ctx.headers.get("authorization").unwrap(),
+            "Bearer sk-shared"
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_tenant_secret_without_default_returns_not_found() {
+        let credstore = Arc::new(MockCredStoreClient::empty());
+        let plugin = ApiKeyAuthPlugin::new(credstore);
+
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(make_config(
+                "authorization",
+                "Bearer ",
+                "cred://{tenant}/openai-key",
+            ))
+            .build();
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::SecretNotFound(r) if r == "cred://{tenant}/openai-key"));
+    }
+
+    fn json_secret_config(field: &str) -> HashMap<String, String> {
+        let mut config = make_config("authorization", "Bearer ", "cred://oauth-client");
+        config.insert("secret_field".into(), field.into());
+        config
+    }
+
+    #[tokio::test]
+    async fn secret_field_extracts_value_from_json_secret() {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "oauth-client".into(),
+            r#"{"client_id":"app-1","client_secret":"s3cr3t"}"#.into(),
+        )]));
+        let plugin = ApiKeyAuthPlugin::new(credstore);
+
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(json_secret_config("client_secret"))
+            .build();
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(ctx.headers.get("authorization").unwrap(), "Bearer s3cr3t");
+    }
+
+    #[tokio::test]
+    async fn secret_field_missing_returns_not_found() {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "oauth-client".into(),
+            r#"{"client_id":"app-1"}"#.into(),
+        )]));
+        let plugin = ApiKeyAuthPlugin::new(credstore);
+
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(json_secret_config("client_secret"))
+            .build();
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(
+            matches!(err, PluginError::SecretNotFound(r) if r == "cred://oauth-client#client_secret")
+        );
+    }
+
+    #[tokio::test]
+    async fn secret_field_on_non_json_secret_returns_internal() {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "oauth-client".into(),
+            "plain-value".into(),
+        )]));
+        let plugin = ApiKeyAuthPlugin::new(credstore);
+
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(json_secret_config("client_secret"))
+            .build();
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::Internal(_)));
+    }
+
+    #[tokio::test]
+    async fn injects_inline_value_without_credstore() {
+        let plugin = ApiKeyAuthPlugin::new(Arc::new(FailingCredStoreClient));
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(HashMap::from([
+                ("header".into(), "authorization".into()),
+                ("prefix".into(), "Bearer ".into()),
+                ("value".into(), "sk-inline".into()),
+            ]))
+            .build();
+
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(
+            ctx.headers.get("authorization").unwrap(),
+            "Bearer sk-inline"
+        );
+    }
+
+    #[tokio::test]
+    async fn secret_ref_and_inline_value_are_mutually_exclusive() {
+        let plugin = ApiKeyAuthPlugin::new(Arc::new(MockCredStoreClient::empty()));
+        let mut config = make_config("authorization", "Bearer ", "cred://openai-key");
+        config.insert("value".into(), "sk-inline".into());
+        let mut ctx = AuthContextBuilder::new().with_config(config).build();
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(
+            matches!(err, PluginError::InvalidConfig(ref msg) if msg.contains("mutually exclusive"))
+        );
+    }
+
+    #[tokio::test]
+    async fn body_pointer_targets_request_body_instead_of_header() {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "odd-key".into(),
+            "sk-body".into(),
+        )]));
+        let plugin = ApiKeyAuthPlugin::new(credstore);
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(HashMap::from([
+                ("body_pointer".into(), "/auth/api_key".into()),
+                ("secret_ref".into(), "cred://odd-key".into()),
+            ]))
+            .build();
+
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert!(ctx.headers.is_empty());
+        assert_eq!(
+            ctx.body_fields,
+            [("/auth/api_key".to_string(), "sk-body".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn header_and_body_pointer_are_mutually_exclusive() {
+        let plugin = ApiKeyAuthPlugin::new(Arc::new(MockCredStoreClient::empty()));
+        let mut config = make_config("authorization", "", "cred://openai-key");
+        config.insert("body_pointer".into(), "/api_key".into());
+        let mut ctx = AuthContextBuilder::new().with_config(config).build();
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::InvalidConfig(_)));
+    }
+
     #[tokio::test]
     async fn credstore_error_maps_to_internal() {
         let plugin = ApiKeyAuthPlugin::new(Arc::new(FailingCredStoreClient));
-        let mut ctx = make_auth_ctx(make_config("authorization", "Bearer ", "cred://some-key"));
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(make_config("authorization", "Bearer ", "cred://some-key"))
+            .build();
 
         let err = plugin.authenticate(&mut ctx).await.unwrap_err();
         assert!(matches!(err, PluginError::Internal(_)));
@@ -204,9 +594,86 @@ mod tests {
         }
 
         let plugin = ApiKeyAuthPlugin::new(Arc::new(Utf8ErrorCredStore));
-        let mut ctx = make_auth_ctx(make_config("authorization", "", "cred://bad-utf8"));
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(make_config("authorization", "", "cred://bad-utf8"))
+            .build();
 
         let err = plugin.authenticate(&mut ctx).await.unwrap_err();
         assert!(matches!(err, PluginError::Internal(_)));
     }
+
+    fn plugin_with_resolvers(hub: ClientHub) -> ApiKeyAuthPlugin {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "openai-key".into(),
+            "sk-cred".into(),
+        )]));
+        ApiKeyAuthPlugin::new(credstore).with_secret_resolvers(Arc::new(hub))
+    }
+
+    #[tokio::test]
+    async fn env_secret_ref_resolves_through_registered_resolver() {
+        let hub = ClientHub::new();
+        hub.register_scoped::<dyn SecretResolver>(
+            ClientScope::new("env"),
+            Arc::new(EnvSecretResolver),
+        );
+        let plugin = plugin_with_resolvers(hub);
+        // Cargo sets this for test binaries.
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(make_config("x-api-key", "", "env://CARGO_MANIFEST_DIR"))
+            .build();
+
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(
+            ctx.headers.get("x-api-key").unwrap(),
+            env!("CARGO_MANIFEST_DIR")
+        );
+    }
+
+    #[tokio::test]
+    async fn file_secret_ref_resolves_through_registered_resolver() {
+        let dir = std::env::temp_dir().join(format!("oagw-apikey-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("openai-key"), "sk-file\n").unwrap();
+        let hub = ClientHub::new();
+        hub.register_scoped::<dyn SecretResolver>(
+            ClientScope::new("file"),
+            Arc::new(FileSecretResolver::new(&dir).unwrap()),
+        );
+        let plugin = plugin_with_resolvers(hub);
+        let secret_ref = format!("file://{}", dir.join("openai-key").display());
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(make_config("authorization", "Bearer ", &secret_ref))
+            .build();
+
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(ctx.headers.get("authorization").unwrap(), "Bearer sk-file");
+    }
+
+    #[tokio::test]
+    async fn cred_secret_ref_still_uses_credstore_with_resolvers() {
+        let hub = ClientHub::new();
+        hub.register_scoped::<dyn SecretResolver>(
+            ClientScope::new("env"),
+            Arc::new(EnvSecretResolver),
+        );
+        let plugin = plugin_with_resolvers(hub);
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(make_config("authorization", "Bearer ", "cred://openai-key"))
+            .build();
+
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(ctx.headers.get("authorization").unwrap(), "Bearer sk-cred");
+    }
+
+    #[tokio::test]
+    async fn unregistered_secret_scheme_is_invalid_config() {
+        let plugin = ApiKeyAuthPlugin::new(Arc::new(MockCredStoreClient::empty()));
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(make_config("authorization", "", "env://OPENAI_KEY"))
+            .build();
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::InvalidConfig(_)), "{err}");
+    }
 }
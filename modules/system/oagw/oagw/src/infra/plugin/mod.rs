@@ -2,5 +2,11 @@ pub(crate) mod apikey_auth;
 pub(crate) mod noop_auth;
 pub(crate) mod oauth2_client_cred_auth;
 pub(crate) mod registry;
+pub(crate) mod replay_cache;
+pub(crate) mod request_token_auth;
+pub(crate) mod rotating_apikey_auth;
+pub(crate) mod secret_resolver;
+pub(crate) mod token_passthrough_auth;
+pub(crate) mod webhook_signature_auth;
 
 pub(crate) use registry::AuthPluginRegistry;
@@ -18,13 +18,15 @@ mod tests {
     use modkit_security::SecurityContext;
     use uuid::Uuid;
 
+    use crate::domain::plugin::AuthHeaders;
+
     use super::*;
 
     #[tokio::test]
     async fn noop_leaves_headers_unchanged() {
         let plugin = NoopAuthPlugin;
-        let mut headers = HashMap::new();
-        headers.insert("x-existing".to_string(), "value".to_string());
+        let mut headers = AuthHeaders::new();
+        headers.insert("x-existing", "value");
 
         let mut ctx = AuthContext {
             headers: headers.clone(),
@@ -34,6 +36,14 @@ mod tests {
                 .subject_id(Uuid::nil())
                 .build()
                 .unwrap(),
+            early_response: None,
+            body_fields: Vec::new(),
+            query: Vec::new(),
+            method: "GET".into(),
+            path: "/".into(),
+            body: None,
+            now: std::time::SystemTime::now(),
+            credential: None,
         };
 
         plugin.authenticate(&mut ctx).await.unwrap();
@@ -1,6 +1,6 @@
 use credstore_sdk::{CredStoreClientV1, SecretRef};
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use modkit_auth::oauth2::types::{ClientAuthMethod, SecretString};
@@ -8,13 +8,26 @@ use modkit_auth::oauth2::{OAuthClientConfig, fetch_token};
 use pingora_memory_cache::MemoryCache;
 use url::Url;
 
-use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
+use crate::config::CredStoreUnavailablePolicy;
+use crate::domain::plugin::{AuthContext, AuthPlugin, ConfigKey, ConfigValueType, PluginError};
+use crate::infra::metrics::OagwMetrics;
 
 /// Safety margin subtracted from the IdP-reported `expires_in` when computing
 /// cache TTL.  Prevents serving a token that is about to expire while the
 /// upstream request is still in flight.
 const TOKEN_EXPIRY_SAFETY_MARGIN: Duration = Duration::from_secs(30);
 
+/// Keys accepted in the plugin configuration. Exactly one of
+/// `token_endpoint` and `issuer_url` must be set; `scopes` is
+/// space-separated.
+const CONFIG_SCHEMA: &[ConfigKey] = &[
+    ConfigKey::optional("token_endpoint", ConfigValueType::Url),
+    ConfigKey::optional("issuer_url", ConfigValueType::Url),
+    ConfigKey::required("client_id_ref", ConfigValueType::SecretRef),
+    ConfigKey::required("client_secret_ref", ConfigValueType::SecretRef),
+    ConfigKey::optional("scopes", ConfigValueType::String),
+];
+
 /// Parsed configuration from `AuthContext::config`.
 struct OAuth2PluginConfig {
     token_endpoint: Option<Url>,
@@ -88,6 +101,21 @@ struct CachedToken {
     token: SecretString,
 }
 
+/// Failure resolving a `cred://` reference. `Unavailable` marks a credstore
+/// outage, which `ServeStale` may bridge with an expired cached token.
+enum SecretResolveError {
+    Unavailable(PluginError),
+    Other(PluginError),
+}
+
+impl From<SecretResolveError> for PluginError {
+    fn from(e: SecretResolveError) -> Self {
+        match e {
+            SecretResolveError::Unavailable(e) | SecretResolveError::Other(e) => e,
+        }
+    }
+}
+
 fn build_cache_key(ctx: &AuthContext, auth_method: ClientAuthMethod) -> String {
     format!(
         "{}:{}:{}:{}",
@@ -123,7 +151,13 @@ pub struct OAuth2ClientCredAuthPlugin {
     auth_method: ClientAuthMethod,
     http_config: Option<modkit_http::HttpClientConfig>,
     cache: MemoryCache<String, CachedToken>,
+    cache_capacity: usize,
+    /// Keys cached since the last clear, which `MemoryCache` cannot list.
+    /// May include keys it has since evicted.
+    cache_keys: Mutex<HashSet<String>>,
     cache_ttl: Duration,
+    credstore_unavailable: CredStoreUnavailablePolicy,
+    metrics: OagwMetrics,
 }
 
 impl OAuth2ClientCredAuthPlugin {
@@ -139,7 +173,11 @@ impl OAuth2ClientCredAuthPlugin {
             auth_method,
             http_config: None,
             cache: MemoryCache::new(cache_capacity),
+            cache_capacity,
+            cache_keys: Mutex::new(HashSet::new()),
             cache_ttl,
+            credstore_unavailable: CredStoreUnavailablePolicy::default(),
+            metrics: OagwMetrics::new(&opentelemetry::global::meter("oagw")),
         }
     }
 
@@ -150,24 +188,92 @@ impl OAuth2ClientCredAuthPlugin {
         self
     }
 
+    /// Set the behaviour when the credstore is unreachable.
+    #[must_use]
+    pub(crate) fn with_credstore_unavailable(mut self, policy: CredStoreUnavailablePolicy) -> Self {
+        self.credstore_unavailable = policy;
+        self
+    }
+
+    /// Record token cache hits and misses on `metrics`.
+    #[must_use]
+    pub(crate) fn with_metrics(mut self, metrics: OagwMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Whether `key` holds a token, fresh or expired.
+    fn is_cached(&self, key: &str) -> bool {
+        let (entry, _status) = self.cache.get_stale(key);
+        entry.is_some_and(|e| e.key == key)
+    }
+
+    /// Cache `token` under `key` for `ttl`.
+    fn store_token(&self, key: &str, token: SecretString, ttl: Duration) {
+        {
+            let mut keys = self.cache_keys.lock().unwrap_or_else(|e| e.into_inner());
+            // Forget evicted keys once they outnumber the cache capacity.
+            if keys.len() >= self.cache_capacity.saturating_mul(2) {
+                keys.retain(|k| self.is_cached(k));
+            }
+            keys.insert(key.to_string());
+        }
+        self.cache.put(
+            key,
+            CachedToken {
+                key: key.to_string(),
+                token,
+            },
+            Some(ttl),
+        );
+    }
+
     /// Resolve a `cred://` reference to its plaintext UTF-8 value.
     async fn resolve_secret(
         &self,
         security_context: &modkit_security::SecurityContext,
         cred_ref: &str,
-    ) -> Result<String, PluginError> {
+    ) -> Result<String, SecretResolveError> {
         let raw = cred_ref.strip_prefix("cred://").unwrap_or(cred_ref);
-        let secret_ref = SecretRef::new(raw)
-            .map_err(|e| PluginError::Internal(format!("invalid secret ref '{raw}': {e}")))?;
+        let secret_ref = SecretRef::new(raw).map_err(|e| {
+            SecretResolveError::Other(PluginError::Internal(format!(
+                "invalid secret ref '{raw}': {e}"
+            )))
+        })?;
         let response = self
             .credstore
             .get(security_context, &secret_ref)
             .await
-            .map_err(|e| PluginError::Internal(format!("credstore error: {e}")))?
-            .ok_or_else(|| PluginError::SecretNotFound(cred_ref.to_owned()))?;
+            .map_err(|e| {
+                SecretResolveError::Unavailable(PluginError::Internal(format!(
+                    "credstore error: {e}"
+                )))
+            })?
+            .ok_or_else(|| {
+                SecretResolveError::Other(PluginError::SecretNotFound(cred_ref.to_owned()))
+            })?;
         std::str::from_utf8(response.value.as_bytes())
             .map(str::to_owned)
-            .map_err(|_| PluginError::Internal(format!("secret '{cred_ref}' is not valid UTF-8")))
+            .map_err(|_| {
+                SecretResolveError::Other(PluginError::Internal(format!(
+                    "secret '{cred_ref}' is not valid UTF-8"
+                )))
+            })
+    }
+
+    /// Resolve both client credentials referenced by the plugin config.
+    async fn resolve_credentials(
+        &self,
+        security_context: &modkit_security::SecurityContext,
+        config: &OAuth2PluginConfig,
+    ) -> Result<(String, String), SecretResolveError> {
+        let client_id = self
+            .resolve_secret(security_context, &config.client_id_ref)
+            .await?;
+        let client_secret = self
+            .resolve_secret(security_context, &config.client_secret_ref)
+            .await?;
+        Ok((client_id, client_secret))
     }
 }
 
@@ -178,26 +284,37 @@ impl AuthPlugin for OAuth2ClientCredAuthPlugin {
         let key = build_cache_key(ctx, self.auth_method);
 
         // Cache hit — verify key matches to prevent hash-collision leakage.
+        // A hash collision is treated as a miss.
         let (cached, _status) = self.cache.get(&key);
-        if let Some(entry) = cached
-            && entry.key == key
-        {
-            ctx.headers.insert(
-                "authorization".into(),
-                format!("Bearer {}", entry.token.expose()),
-            );
+        let cached = cached.filter(|entry| entry.key == key);
+        self.metrics.record_cache_lookup("token", cached.is_some());
+        if let Some(entry) = cached {
+            ctx.headers
+                .insert("authorization", format!("Bearer {}", entry.token.expose()));
             return Ok(());
-
-            // Hash collision — treat as miss, do not use this entry.
         }
 
         // Cache miss — resolve credentials and fetch token.
-        let client_id_str = self
-            .resolve_secret(&ctx.security_context, &config.client_id_ref)
-            .await?;
-        let client_secret_str = self
-            .resolve_secret(&ctx.security_context, &config.client_secret_ref)
-            .await?;
+        let (client_id_str, client_secret_str) = match self
+            .resolve_credentials(&ctx.security_context, &config)
+            .await
+        {
+            Ok(creds) => creds,
+            Err(SecretResolveError::Unavailable(e))
+                if self.credstore_unavailable == CredStoreUnavailablePolicy::ServeStale =>
+            {
+                // Credstore outage — fall back to an expired token, if any.
+                let (stale, _status) = self.cache.get_stale(&key);
+                let Some(entry) = stale.filter(|entry| entry.key == key) else {
+                    return Err(e);
+                };
+                tracing::warn!(error = %e, "credstore unavailable, serving stale OAuth2 token");
+                ctx.headers
+                    .insert("authorization", format!("Bearer {}", entry.token.expose()));
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
 
         let mut oauth_config = OAuthClientConfig {
             token_endpoint: config.token_endpoint,
@@ -223,22 +340,31 @@ impl AuthPlugin for OAuth2ClientCredAuthPlugin {
         );
 
         // Cache with key for verification — ZeroizeOnDrop fires on eviction.
-        self.cache.put(
-            &key,
-            CachedToken {
-                key: key.clone(),
-                token: fetched.bearer.clone(),
-            },
-            Some(ttl),
-        );
+        self.store_token(&key, fetched.bearer.clone(), ttl);
 
         ctx.headers.insert(
-            "authorization".into(),
+            "authorization",
             format!("Bearer {}", fetched.bearer.expose()),
         );
 
         Ok(())
     }
+
+    fn config_schema(&self) -> &'static [ConfigKey] {
+        CONFIG_SCHEMA
+    }
+
+    fn clear_cache(&self) -> usize {
+        let keys = std::mem::take(&mut *self.cache_keys.lock().unwrap_or_else(|e| e.into_inner()));
+        let mut removed = 0;
+        for key in keys {
+            if self.is_cached(&key) {
+                removed += 1;
+            }
+            self.cache.remove(&key);
+        }
+        removed
+    }
 }
 
 #[cfg(test)]
@@ -255,7 +381,7 @@ mod tests {
         CredStoreClientV1, CredStoreError, GetSecretResponse, SecretRef, SecretValue, SharingMode,
     };
 
-    use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
+    use crate::domain::plugin::{AuthContext, AuthHeaders, AuthPlugin, PluginError};
     use crate::domain::test_support::{FailingCredStoreClient, MockCredStoreClient};
 
     use super::*;
@@ -299,9 +425,17 @@ mod tests {
 
     fn make_auth_ctx(config: HashMap<String, String>) -> AuthContext {
         AuthContext {
-            headers: HashMap::new(),
+            headers: AuthHeaders::new(),
             config,
             security_context: test_security_context(),
+            early_response: None,
+            body_fields: Vec::new(),
+            query: Vec::new(),
+            method: "GET".into(),
+            path: "/".into(),
+            body: None,
+            now: std::time::SystemTime::now(),
+            credential: None,
         }
     }
 
@@ -675,9 +809,17 @@ mod tests {
     /// Helper that creates an `AuthContext` with an explicit `SecurityContext`.
     fn make_auth_ctx_with_sc(config: HashMap<String, String>, sc: SecurityContext) -> AuthContext {
         AuthContext {
-            headers: HashMap::new(),
+            headers: AuthHeaders::new(),
             config,
             security_context: sc,
+            early_response: None,
+            body_fields: Vec::new(),
+            query: Vec::new(),
+            method: "GET".into(),
+            path: "/".into(),
+            body: None,
+            now: std::time::SystemTime::now(),
+            credential: None,
         }
     }
 
@@ -715,6 +857,40 @@ mod tests {
         mock.assert_calls(1);
     }
 
+    #[tokio::test]
+    async fn token_cache_lookups_are_recorded() {
+        use crate::domain::test_support::RecordingMetricsSink;
+        use crate::infra::metrics::CACHE_LOOKUPS;
+
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(default_creds()));
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/token");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(mock_token_response("cached-token", 3600));
+        });
+        let sink = Arc::new(RecordingMetricsSink::default());
+        let plugin = make_plugin(credstore).with_metrics(OagwMetrics::with_sink(sink.clone()));
+        let config = make_config(&server);
+        let sc = test_security_context();
+
+        for _ in 0..3 {
+            let mut ctx = make_auth_ctx_with_sc(config.clone(), sc.clone());
+            plugin.authenticate(&mut ctx).await.unwrap();
+        }
+
+        let token = ("cache", "token");
+        assert_eq!(
+            sink.values(CACHE_LOOKUPS, &[token, ("result", "miss")]),
+            [1.0]
+        );
+        assert_eq!(
+            sink.values(CACHE_LOOKUPS, &[token, ("result", "hit")]),
+            [1.0, 1.0]
+        );
+    }
+
     #[tokio::test]
     async fn different_subject_id_gets_separate_cache_entry() {
         let credstore = Arc::new(MockCredStoreClient::with_secrets(default_creds()));
@@ -910,6 +1086,33 @@ mod tests {
         ok_mock.assert_calls(1);
     }
 
+    #[tokio::test]
+    async fn cleared_cache_fetches_a_new_token() {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(default_creds()));
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/token");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(mock_token_response("fresh-token", 3600));
+        });
+
+        let plugin = make_plugin(credstore);
+        let config = make_config(&server);
+        for _ in 0..2 {
+            let mut ctx = make_auth_ctx(config.clone());
+            plugin.authenticate(&mut ctx).await.unwrap();
+        }
+        mock.assert_calls(2);
+
+        assert_eq!(plugin.clear_cache(), 2);
+        assert_eq!(plugin.clear_cache(), 0);
+
+        let mut ctx = make_auth_ctx(config);
+        plugin.authenticate(&mut ctx).await.unwrap();
+        mock.assert_calls(3);
+    }
+
     // -----------------------------------------------------------------------
     // Group 8: expires_in-aware cache TTL
     // -----------------------------------------------------------------------
@@ -1055,4 +1258,70 @@ mod tests {
             );
         }
     }
+
+    // -----------------------------------------------------------------------
+    // Group 10: Credstore outage policy
+    // -----------------------------------------------------------------------
+
+    /// Seed the plugin cache with an already-expired token for `ctx`.
+    async fn seed_expired_token(plugin: &OAuth2ClientCredAuthPlugin, ctx: &AuthContext) {
+        let key = build_cache_key(ctx, ClientAuthMethod::Form);
+        plugin.store_token(
+            &key,
+            SecretString::new("stale-token"),
+            Duration::from_millis(1),
+        );
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    #[tokio::test]
+    async fn serve_stale_uses_expired_token_when_credstore_down() {
+        let server = MockServer::start();
+        let plugin = make_plugin(Arc::new(FailingCredStoreClient))
+            .with_credstore_unavailable(CredStoreUnavailablePolicy::ServeStale);
+        let mut ctx = make_auth_ctx(make_config(&server));
+        seed_expired_token(&plugin, &ctx).await;
+
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(
+            ctx.headers.get("authorization").unwrap(),
+            "Bearer stale-token"
+        );
+    }
+
+    #[tokio::test]
+    async fn serve_stale_without_warm_cache_rejects() {
+        let server = MockServer::start();
+        let plugin = make_plugin(Arc::new(FailingCredStoreClient))
+            .with_credstore_unavailable(CredStoreUnavailablePolicy::ServeStale);
+        let mut ctx = make_auth_ctx(make_config(&server));
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::Internal(ref msg) if msg.contains("credstore error")));
+    }
+
+    #[tokio::test]
+    async fn fail_closed_rejects_even_with_warm_cache() {
+        let server = MockServer::start();
+        let plugin = make_plugin(Arc::new(FailingCredStoreClient))
+            .with_credstore_unavailable(CredStoreUnavailablePolicy::FailClosed);
+        let mut ctx = make_auth_ctx(make_config(&server));
+        seed_expired_token(&plugin, &ctx).await;
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::Internal(ref msg) if msg.contains("credstore error")));
+        assert!(!ctx.headers.contains_key("authorization"));
+    }
+
+    #[tokio::test]
+    async fn serve_stale_does_not_mask_missing_secret() {
+        let server = MockServer::start();
+        let plugin = make_plugin(Arc::new(MockCredStoreClient::empty()))
+            .with_credstore_unavailable(CredStoreUnavailablePolicy::ServeStale);
+        let mut ctx = make_auth_ctx(make_config(&server));
+        seed_expired_token(&plugin, &ctx).await;
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::SecretNotFound(_)));
+    }
 }
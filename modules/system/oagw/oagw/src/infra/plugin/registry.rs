@@ -1,39 +1,73 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use modkit_auth::oauth2::types::ClientAuthMethod;
 
 use crate::config::TokenCacheConfig;
-use crate::domain::plugin::{AuthPlugin, PluginError};
+use crate::domain::plugin::{AuthPlugin, ConfigKey, PluginError};
+use crate::infra::metrics::OagwMetrics;
 use credstore_sdk::CredStoreClientV1;
 
 use super::apikey_auth::ApiKeyAuthPlugin;
 use super::noop_auth::NoopAuthPlugin;
 use super::oauth2_client_cred_auth::OAuth2ClientCredAuthPlugin;
+use super::request_token_auth::RequestTokenAuthPlugin;
+use super::rotating_apikey_auth::RotatingApiKeyAuthPlugin;
+use super::token_passthrough_auth::TokenPassthroughAuthPlugin;
+use super::webhook_signature_auth::WebhookSignatureAuthPlugin;
 use crate::domain::gts_helpers::{
     APIKEY_AUTH_PLUGIN_ID, NOOP_AUTH_PLUGIN_ID, OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID,
-    OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID,
+    OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID, REQUEST_TOKEN_AUTH_PLUGIN_ID,
+    ROTATING_APIKEY_AUTH_PLUGIN_ID, TOKEN_PASSTHROUGH_AUTH_PLUGIN_ID,
+    WEBHOOK_SIGNATURE_AUTH_PLUGIN_ID,
 };
 
+/// A registered plugin and the configuration keys it accepts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginInfo {
+    pub id: String,
+    pub config_schema: &'static [ConfigKey],
+}
+
 /// Registry that resolves auth plugin GTS identifiers to plugin implementations.
 pub struct AuthPluginRegistry {
     plugins: HashMap<String, Arc<dyn AuthPlugin>>,
+    /// Registered plugins switched off by the operator.
+    disabled: HashSet<String>,
 }
 
 impl AuthPluginRegistry {
-    /// Create a registry with the built-in plugins (apikey, noop, oauth2 CC).
+    /// Create a registry with the built-in plugins (apikey, noop, oauth2 CC,
+    /// token passthrough, request token, rotating API key, webhook signature).
+    /// OAuth2 token cache lookups are recorded on `metrics`.
     #[must_use]
     pub fn with_builtins(
         credstore: Arc<dyn CredStoreClientV1>,
         token_http_config: Option<modkit_http::HttpClientConfig>,
         token_cache_config: TokenCacheConfig,
+        metrics: &OagwMetrics,
     ) -> Self {
-        let mut plugins: HashMap<String, Arc<dyn AuthPlugin>> = HashMap::new();
-        plugins.insert(
-            APIKEY_AUTH_PLUGIN_ID.to_string(),
+        let mut registry = Self {
+            plugins: HashMap::new(),
+            disabled: HashSet::new(),
+        };
+        registry.register(
+            APIKEY_AUTH_PLUGIN_ID,
             Arc::new(ApiKeyAuthPlugin::new(credstore.clone())),
         );
-        plugins.insert(NOOP_AUTH_PLUGIN_ID.to_string(), Arc::new(NoopAuthPlugin));
+        registry.register(NOOP_AUTH_PLUGIN_ID, Arc::new(NoopAuthPlugin));
+        registry.register(
+            REQUEST_TOKEN_AUTH_PLUGIN_ID,
+            Arc::new(RequestTokenAuthPlugin::new(credstore.clone())),
+        );
+        registry.register(
+            ROTATING_APIKEY_AUTH_PLUGIN_ID,
+            Arc::new(RotatingApiKeyAuthPlugin::new(credstore.clone())),
+        );
+        registry.register(
+            WEBHOOK_SIGNATURE_AUTH_PLUGIN_ID,
+            Arc::new(WebhookSignatureAuthPlugin::new(credstore.clone())),
+        );
 
         let mut form_plugin = OAuth2ClientCredAuthPlugin::new(
             credstore.clone(),
@@ -47,38 +81,107 @@ impl AuthPluginRegistry {
             token_cache_config.ttl,
             token_cache_config.capacity,
         );
+        let mut passthrough_plugin = TokenPassthroughAuthPlugin::new();
         if let Some(ref cfg) = token_http_config {
             form_plugin = form_plugin.with_http_config(cfg.clone());
             basic_plugin = basic_plugin.with_http_config(cfg.clone());
+            passthrough_plugin = passthrough_plugin.with_http_config(cfg.clone());
         }
+        form_plugin = form_plugin
+            .with_credstore_unavailable(token_cache_config.credstore_unavailable)
+            .with_metrics(metrics.clone());
+        basic_plugin = basic_plugin
+            .with_credstore_unavailable(token_cache_config.credstore_unavailable)
+            .with_metrics(metrics.clone());
 
-        plugins.insert(
-            OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID.to_string(),
-            Arc::new(form_plugin),
-        );
-        plugins.insert(
-            OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID.to_string(),
+        registry.register(OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID, Arc::new(form_plugin));
+        registry.register(
+            OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID,
             Arc::new(basic_plugin),
         );
-        Self { plugins }
+        registry.register(
+            TOKEN_PASSTHROUGH_AUTH_PLUGIN_ID,
+            Arc::new(passthrough_plugin),
+        );
+        registry
+    }
+
+    /// Register (or replace) the plugin served under `plugin_id`.
+    pub fn register(&mut self, plugin_id: impl Into<String>, plugin: Arc<dyn AuthPlugin>) {
+        self.plugins.insert(plugin_id.into(), plugin);
+    }
+
+    /// Enable or disable the plugin served under `plugin_id`. Routes that
+    /// use a disabled plugin fail instead of proxying without auth.
+    pub fn set_enabled(&mut self, plugin_id: &str, enabled: bool) {
+        if enabled {
+            self.disabled.remove(plugin_id);
+        } else {
+            self.disabled.insert(plugin_id.to_string());
+        }
     }
 
     /// Resolve a plugin by its GTS identifier.
     ///
     /// # Errors
-    /// Returns `PluginError::Internal` if the plugin is not registered.
+    /// Returns `PluginError::Disabled` if the plugin is disabled and
+    /// `PluginError::Internal` if it is not registered.
     pub fn resolve(&self, plugin_id: &str) -> Result<Arc<dyn AuthPlugin>, PluginError> {
+        if self.disabled.contains(plugin_id) {
+            return Err(PluginError::Disabled(plugin_id.to_string()));
+        }
         self.plugins
             .get(plugin_id)
             .cloned()
             .ok_or_else(|| PluginError::Internal(format!("unknown auth plugin: {plugin_id}")))
     }
+
+    /// List the registered plugins with their config schemas, ordered by ID.
+    #[must_use]
+    #[allow(dead_code)] // Operator-facing discovery; not yet exposed over REST.
+    pub fn list_plugins(&self) -> Vec<PluginInfo> {
+        let mut plugins: Vec<PluginInfo> = self
+            .plugins
+            .iter()
+            .map(|(id, plugin)| PluginInfo {
+                id: id.clone(),
+                config_schema: plugin.config_schema(),
+            })
+            .collect();
+        plugins.sort_by(|a, b| a.id.cmp(&b.id));
+        plugins
+    }
+
+    /// Clear the caches of every registered plugin, enabled or not, and
+    /// return the total number of entries dropped.
+    #[must_use]
+    pub fn clear_caches(&self) -> usize {
+        self.plugins.values().map(|p| p.clear_cache()).sum()
+    }
+
+    /// Check the health of every enabled plugin.
+    ///
+    /// # Errors
+    /// Returns the first failure, prefixed with the plugin's GTS identifier.
+    pub async fn health(&self) -> Result<(), PluginError> {
+        for (plugin_id, plugin) in &self.plugins {
+            if self.disabled.contains(plugin_id) {
+                continue;
+            }
+            plugin
+                .health()
+                .await
+                .map_err(|e| PluginError::Internal(format!("{plugin_id}: {e}")))?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
+    use crate::domain::plugin::ConfigValueType;
     use crate::domain::test_support::MockCredStoreClient;
 
     use super::*;
@@ -88,6 +191,7 @@ mod tests {
             Arc::new(MockCredStoreClient::empty()),
             None,
             TokenCacheConfig::default(),
+            &OagwMetrics::new(&opentelemetry::global::meter("oagw")),
         )
     }
 
@@ -119,6 +223,98 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolves_token_passthrough_plugin() {
+        let registry = make_registry();
+        assert!(registry.resolve(TOKEN_PASSTHROUGH_AUTH_PLUGIN_ID).is_ok());
+    }
+
+    #[test]
+    fn resolves_request_token_plugin() {
+        let registry = make_registry();
+        assert!(registry.resolve(REQUEST_TOKEN_AUTH_PLUGIN_ID).is_ok());
+    }
+
+    #[test]
+    fn resolves_rotating_apikey_plugin() {
+        let registry = make_registry();
+        assert!(registry.resolve(ROTATING_APIKEY_AUTH_PLUGIN_ID).is_ok());
+    }
+
+    #[test]
+    fn resolves_webhook_signature_plugin() {
+        let registry = make_registry();
+        assert!(registry.resolve(WEBHOOK_SIGNATURE_AUTH_PLUGIN_ID).is_ok());
+    }
+
+    #[test]
+    fn list_plugins_reports_ids_and_schemas() {
+        let registry = make_registry();
+        let plugins = registry.list_plugins();
+
+        let ids: Vec<&str> = plugins.iter().map(|p| p.id.as_str()).collect();
+        let mut expected = vec![
+            APIKEY_AUTH_PLUGIN_ID,
+            NOOP_AUTH_PLUGIN_ID,
+            OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID,
+            OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID,
+            TOKEN_PASSTHROUGH_AUTH_PLUGIN_ID,
+            REQUEST_TOKEN_AUTH_PLUGIN_ID,
+            ROTATING_APIKEY_AUTH_PLUGIN_ID,
+            WEBHOOK_SIGNATURE_AUTH_PLUGIN_ID,
+        ];
+        expected.sort_unstable();
+        assert_eq!(ids, expected);
+
+        let apikey = plugins
+            .iter()
+            .find(|p| p.id == APIKEY_AUTH_PLUGIN_ID)
+            .unwrap();
+        let keys: Vec<(&str, ConfigValueType, bool)> = apikey
+            .config_schema
+            .iter()
+            .map(|k| (k.name, k.value_type, k.required))
+            .collect();
+        assert!(keys.contains(&("header", ConfigValueType::String, false)));
+        assert!(keys.contains(&("body_pointer", ConfigValueType::String, false)));
+        assert!(keys.contains(&("prefix", ConfigValueType::String, false)));
+        assert!(keys.contains(&("secret_ref", ConfigValueType::SecretRef, false)));
+        assert!(keys.contains(&("secret_field", ConfigValueType::String, false)));
+        assert!(keys.contains(&("value", ConfigValueType::String, false)));
+        assert!(keys.contains(&("reject_empty_secret", ConfigValueType::Bool, false)));
+
+        let noop = plugins
+            .iter()
+            .find(|p| p.id == NOOP_AUTH_PLUGIN_ID)
+            .unwrap();
+        assert!(noop.config_schema.is_empty());
+
+        let oauth2 = plugins
+            .iter()
+            .find(|p| p.id == OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID)
+            .unwrap();
+        assert!(
+            oauth2
+                .config_schema
+                .iter()
+                .any(|k| k.name == "client_secret_ref" && k.required)
+        );
+    }
+
+    #[test]
+    fn disabled_plugin_does_not_resolve_until_reenabled() {
+        let mut registry = make_registry();
+        registry.set_enabled(APIKEY_AUTH_PLUGIN_ID, false);
+        assert!(matches!(
+            registry.resolve(APIKEY_AUTH_PLUGIN_ID),
+            Err(PluginError::Disabled(ref id)) if id == APIKEY_AUTH_PLUGIN_ID
+        ));
+        assert!(registry.resolve(NOOP_AUTH_PLUGIN_ID).is_ok());
+
+        registry.set_enabled(APIKEY_AUTH_PLUGIN_ID, true);
+        assert!(registry.resolve(APIKEY_AUTH_PLUGIN_ID).is_ok());
+    }
+
     #[test]
     fn unknown_plugin_returns_error() {
         let registry = make_registry();
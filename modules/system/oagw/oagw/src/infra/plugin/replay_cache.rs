@@ -0,0 +1,190 @@
+//! Replay protection for signed inbound requests.
+//!
+//! A signature is accepted once: its timestamp must lie within the tolerance
+//! window, and the same signature (or nonce) seen again before it expires is
+//! rejected with [`PluginError::AuthFailed`], which the data plane surfaces
+//! as 401. Used by the webhook signature plugin.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::domain::clock::{Clock, SystemClock};
+use crate::domain::plugin::PluginError;
+
+/// Default number of signatures remembered at once.
+pub(crate) const DEFAULT_REPLAY_CACHE_CAPACITY: usize = 10_000;
+
+/// Bounded, TTL-based store of recently accepted signatures.
+///
+/// Every entry lives for exactly `tolerance`, so insertion order is also
+/// expiry order and purging only ever touches the front of the queue. A
+/// timestamp older than `tolerance` is rejected outright, so an entry that
+/// has expired can never be replayed successfully. Live entries are never
+/// evicted: when all `capacity` slots hold one, new signatures are rejected
+/// until the oldest expires, since forgetting a live entry would let its
+/// signature be replayed.
+pub(crate) struct ReplayCache {
+    tolerance: Duration,
+    capacity: usize,
+    inner: Mutex<Inner>,
+    clock: Arc<dyn Clock>,
+}
+
+#[derive(Default)]
+struct Inner {
+    seen: HashMap<String, Instant>,
+    order: VecDeque<(Instant, String)>,
+}
+
+impl ReplayCache {
+    #[must_use]
+    pub(crate) fn new(tolerance: Duration, capacity: usize) -> Self {
+        Self {
+            tolerance,
+            capacity: capacity.max(1),
+            inner: Mutex::new(Inner::default()),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Accept `key` (a signature or nonce) signed at `timestamp`, or reject
+    /// it as stale or replayed, or because the cache is full. `wall_now` is
+    /// the trusted current time the timestamp is judged against.
+    pub(crate) fn check(
+        &self,
+        key: &str,
+        timestamp: SystemTime,
+        wall_now: SystemTime,
+    ) -> Result<(), PluginError> {
+        self.check_at(key, timestamp, wall_now, self.clock.instant())
+    }
+
+    fn check_at(
+        &self,
+        key: &str,
+        timestamp: SystemTime,
+        wall_now: SystemTime,
+        now: Instant,
+    ) -> Result<(), PluginError> {
+        let skew = wall_now
+            .duration_since(timestamp)
+            .unwrap_or_else(|e| e.duration());
+        if skew > self.tolerance {
+            return Err(PluginError::AuthFailed(
+                "signature timestamp outside tolerance window".into(),
+            ));
+        }
+
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.purge_expired(now);
+        if inner.seen.contains_key(key) {
+            return Err(PluginError::AuthFailed("signature already used".into()));
+        }
+        // At capacity with nothing expired: fail closed rather than forget
+        // a signature that could still be replayed.
+        if inner.order.len() >= self.capacity {
+            return Err(PluginError::AuthFailed(
+                "replay cache full; try again later".into(),
+            ));
+        }
+        let expires_at = now + self.tolerance;
+        inner.seen.insert(key.to_string(), expires_at);
+        inner.order.push_back((expires_at, key.to_string()));
+        Ok(())
+    }
+}
+
+impl Inner {
+    fn purge_expired(&mut self, now: Instant) {
+        while self
+            .order
+            .front()
+            .is_some_and(|(expires_at, _)| *expires_at <= now)
+        {
+            self.pop_oldest();
+        }
+    }
+
+    fn pop_oldest(&mut self) {
+        if let Some((_, key)) = self.order.pop_front() {
+            self.seen.remove(&key);
+        }
+    }
+}
+
+impl Default for ReplayCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(300), DEFAULT_REPLAY_CACHE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: Duration = Duration::from_secs(300);
+
+    #[test]
+    fn same_signature_twice_is_rejected() {
+        let cache = ReplayCache::new(TOLERANCE, 16);
+        let signed_at = SystemTime::now();
+
+        cache.check("sig-a", signed_at, signed_at).unwrap();
+        let err = cache.check("sig-a", signed_at, signed_at).unwrap_err();
+        assert!(matches!(err, PluginError::AuthFailed(_)));
+    }
+
+    #[test]
+    fn distinct_nonce_passes() {
+        let cache = ReplayCache::new(TOLERANCE, 16);
+        let signed_at = SystemTime::now();
+
+        cache.check("nonce-1", signed_at, signed_at).unwrap();
+        cache.check("nonce-2", signed_at, signed_at).unwrap();
+    }
+
+    #[test]
+    fn stale_timestamp_is_rejected() {
+        let cache = ReplayCache::new(TOLERANCE, 16);
+        let signed_at = SystemTime::now() - TOLERANCE - Duration::from_secs(1);
+
+        let err = cache
+            .check("sig-old", signed_at, SystemTime::now())
+            .unwrap_err();
+        assert!(matches!(err, PluginError::AuthFailed(_)));
+    }
+
+    #[test]
+    fn entries_expire_after_tolerance() {
+        let cache = ReplayCache::new(TOLERANCE, 16);
+        let wall = SystemTime::now();
+        let now = Instant::now();
+
+        cache.check_at("sig-a", wall, wall, now).unwrap();
+        // Past the window the entry is gone; the timestamp check (against a
+        // fresh signature time) is what keeps old signatures out.
+        let later = now + TOLERANCE + Duration::from_secs(1);
+        cache.check_at("sig-a", wall, wall, later).unwrap();
+        assert_eq!(cache.inner.lock().unwrap().seen.len(), 1);
+    }
+
+    #[test]
+    fn full_cache_rejects_instead_of_evicting() {
+        let cache = ReplayCache::new(TOLERANCE, 2);
+        let wall = SystemTime::now();
+        let now = Instant::now();
+
+        for key in ["a", "b"] {
+            cache.check_at(key, wall, wall, now).unwrap();
+        }
+        let err = cache.check_at("c", wall, wall, now).unwrap_err();
+        assert!(matches!(err, PluginError::AuthFailed(_)));
+        // "a" is still remembered, so it cannot be replayed.
+        assert!(cache.check_at("a", wall, wall, now).is_err());
+
+        // Once the oldest entries expire there is room again.
+        let later = now + TOLERANCE;
+        cache.check_at("c", wall, wall, later).unwrap();
+    }
+}
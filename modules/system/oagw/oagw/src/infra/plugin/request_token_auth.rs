@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use credstore_sdk::{CredStoreClientV1, SecretRef};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::domain::plugin::{
+    AuthContext, AuthPlugin, ConfigKey, ConfigValueType, PluginError, resolve_secret_ref,
+};
+
+/// Keys accepted in the plugin configuration. `signed_headers` only applies
+/// together with `secret_ref`.
+const CONFIG_SCHEMA: &[ConfigKey] = &[
+    ConfigKey::required("header", ConfigValueType::String),
+    ConfigKey::optional("prefix", ConfigValueType::String),
+    ConfigKey::optional("secret_ref", ConfigValueType::SecretRef),
+    ConfigKey::optional("signed_headers", ConfigValueType::String),
+];
+
+/// Parsed configuration from `AuthContext::config`.
+struct RequestTokenConfig {
+    /// Outbound header that receives the token.
+    header: String,
+    /// Prefix prepended to the token (e.g. "Nonce ").
+    prefix: String,
+    /// HMAC key reference. Without it every request gets a random UUID.
+    secret_ref: Option<String>,
+    /// Outbound headers whose values are signed after the method and path,
+    /// in the configured order.
+    signed_headers: Vec<String>,
+}
+
+impl RequestTokenConfig {
+    fn parse(config: &HashMap<String, String>) -> Result<Self, PluginError> {
+        let header = config
+            .get("header")
+            .filter(|h| !h.is_empty())
+            .ok_or_else(|| PluginError::InvalidConfig("header is required".into()))?
+            .to_lowercase();
+        let secret_ref = config.get("secret_ref").cloned();
+        let signed_headers: Vec<String> = config
+            .get("signed_headers")
+            .map(|list| {
+                list.split(',')
+                    .map(str::trim)
+                    .filter(|h| !h.is_empty())
+                    .map(str::to_lowercase)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !signed_headers.is_empty() && secret_ref.is_none() {
+            return Err(PluginError::InvalidConfig(
+                "signed_headers requires secret_ref".into(),
+            ));
+        }
+        Ok(Self {
+            header,
+            prefix: config.get("prefix").cloned().unwrap_or_default(),
+            secret_ref,
+            signed_headers,
+        })
+    }
+}
+
+/// Auth plugin that mints a token for every request and injects it into a
+/// configured header, for upstreams with nonce-style schemes.
+///
+/// Without a `secret_ref` the token is a random UUID. With one, it is the
+/// hex HMAC-SHA256, keyed by that secret, of the method, the outbound path
+/// and the `signed_headers` values, each on its own line; identical
+/// requests therefore get identical tokens.
+pub struct RequestTokenAuthPlugin {
+    credstore: Arc<dyn CredStoreClientV1>,
+}
+
+impl RequestTokenAuthPlugin {
+    #[must_use]
+    pub fn new(credstore: Arc<dyn CredStoreClientV1>) -> Self {
+        Self { credstore }
+    }
+
+    /// Resolve the HMAC key for the calling tenant.
+    async fn resolve_key(
+        &self,
+        ctx: &AuthContext,
+        secret_ref: &str,
+    ) -> Result<Vec<u8>, PluginError> {
+        let tenant_id = ctx.security_context.subject_tenant_id();
+        let raw_ref = resolve_secret_ref(secret_ref, tenant_id);
+        let key = SecretRef::new(&raw_ref)
+            .map_err(|e| PluginError::Internal(format!("invalid secret ref '{raw_ref}': {e}")))?;
+        let response = self
+            .credstore
+            .get(&ctx.security_context, &key)
+            .await
+            .map_err(|e| PluginError::Internal(format!("credstore error: {e}")))?
+            .ok_or_else(|| PluginError::SecretNotFound(secret_ref.to_string()))?;
+        Ok(response.value.as_bytes().to_vec())
+    }
+}
+
+/// Hex HMAC-SHA256 of `method`, `path` and each of `signed_headers` (empty
+/// when absent), joined by newlines.
+fn derive_token(key: &[u8], ctx: &AuthContext, signed_headers: &[String]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(ctx.method.as_bytes());
+    mac.update(b"\n");
+    mac.update(ctx.path.as_bytes());
+    for name in signed_headers {
+        mac.update(b"\n");
+        mac.update(ctx.headers.get(name).unwrap_or_default().as_bytes());
+    }
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[async_trait]
+impl AuthPlugin for RequestTokenAuthPlugin {
+    async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+        let config = RequestTokenConfig::parse(&ctx.config)?;
+        let token = match config.secret_ref.as_deref() {
+            Some(secret_ref) => {
+                let key = self.resolve_key(ctx, secret_ref).await?;
+                derive_token(&key, ctx, &config.signed_headers)
+            }
+            None => Uuid::new_v4().to_string(),
+        };
+        ctx.headers
+            .insert(config.header, format!("{}{token}", config.prefix));
+        Ok(())
+    }
+
+    fn config_schema(&self) -> &'static [ConfigKey] {
+        CONFIG_SCHEMA
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::test_support::{AuthContextBuilder, MockCredStoreClient};
+
+    use super::*;
+
+    fn plugin() -> RequestTokenAuthPlugin {
+        RequestTokenAuthPlugin::new(Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "nonce-key".into(),
+            "k3y".into(),
+        )])))
+    }
+
+    async fn token(plugin: &RequestTokenAuthPlugin, builder: AuthContextBuilder) -> String {
+        let mut ctx = builder.build();
+        plugin.authenticate(&mut ctx).await.unwrap();
+        ctx.headers.get("x-nonce").unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn each_request_gets_a_fresh_token() {
+        let plugin = plugin();
+        let config = HashMap::from([("header".into(), "X-Nonce".into())]);
+        let first = token(
+            &plugin,
+            AuthContextBuilder::new().with_config(config.clone()),
+        )
+        .await;
+        let second = token(&plugin, AuthContextBuilder::new().with_config(config)).await;
+
+        assert_ne!(first, second);
+        assert!(Uuid::parse_str(&first).is_ok());
+    }
+
+    #[tokio::test]
+    async fn hmac_token_is_stable_for_identical_requests() {
+        let plugin = plugin();
+        let config = HashMap::from([
+            ("header".into(), "x-nonce".into()),
+            ("prefix".into(), "v1=".into()),
+            ("secret_ref".into(), "cred://nonce-key".into()),
+            ("signed_headers".into(), "X-Date".into()),
+        ]);
+        let request = |date: &str| {
+            AuthContextBuilder::new()
+                .with_config(config.clone())
+                .with_method("POST")
+                .with_path("/v1/orders?id=7")
+                .with_header("x-date", date)
+        };
+
+        let first = token(&plugin, request("Mon")).await;
+        assert_eq!(token(&plugin, request("Mon")).await, first);
+        assert_ne!(token(&plugin, request("Tue")).await, first);
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"k3y").unwrap();
+        mac.update(b"POST\n/v1/orders?id=7\nMon");
+        assert_eq!(
+            first,
+            format!("v1={}", hex::encode(mac.finalize().into_bytes()))
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_key_is_reported() {
+        let config = HashMap::from([
+            ("header".into(), "x-nonce".into()),
+            ("secret_ref".into(), "cred://absent".into()),
+        ]);
+        let mut ctx = AuthContextBuilder::new().with_config(config).build();
+        let err = plugin().authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::SecretNotFound(ref r) if r == "cred://absent"));
+    }
+
+    #[tokio::test]
+    async fn signed_headers_without_key_is_invalid() {
+        let config = HashMap::from([
+            ("header".into(), "x-nonce".into()),
+            ("signed_headers".into(), "x-date".into()),
+        ]);
+        let mut ctx = AuthContextBuilder::new().with_config(config).build();
+        let err = plugin().authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::InvalidConfig(_)));
+    }
+}
@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use credstore_sdk::CredStoreClientV1;
+
+use super::secret_resolver::SecretResolvers;
+use crate::domain::plugin::{
+    AuthContext, AuthPlugin, ConfigKey, ConfigValueType, PluginError, resolve_secret_ref,
+};
+
+/// Keys accepted in the plugin configuration; see [`RotatingApiKeyConfig`].
+const CONFIG_SCHEMA: &[ConfigKey] = &[
+    ConfigKey::required("header", ConfigValueType::String),
+    ConfigKey::optional("prefix", ConfigValueType::String),
+    ConfigKey::required("secret_refs", ConfigValueType::String),
+    ConfigKey::optional("strategy", ConfigValueType::String),
+    ConfigKey::optional("cooldown_secs", ConfigValueType::String),
+];
+
+/// How long a key that got a `429` is passed over when unset.
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+/// Longest accepted `cooldown_secs`: one day.
+const MAX_COOLDOWN_SECS: u64 = 86_400;
+
+/// Order in which the keys take turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Strategy {
+    /// Each key in its configured order.
+    RoundRobin,
+    /// The key unused for longest.
+    LeastRecentlyUsed,
+}
+
+/// Parsed configuration from `AuthContext::config`.
+struct RotatingApiKeyConfig {
+    /// Outbound header that receives the key.
+    header: String,
+    /// Prefix prepended to the key (e.g. "Bearer ").
+    prefix: String,
+    /// Comma-separated secret references, each resolved like the API key
+    /// plugin's `secret_ref` (so `{tenant}` templating applies).
+    secret_refs: Vec<String>,
+    /// `round_robin` (default) or `least_recently_used`.
+    strategy: Strategy,
+    /// How long a key the upstream answered with `429` is passed over while
+    /// another key is available. Default: 60; at most a day.
+    cooldown: Duration,
+}
+
+impl RotatingApiKeyConfig {
+    fn parse(config: &HashMap<String, String>) -> Result<Self, PluginError> {
+        let header = config
+            .get("header")
+            .filter(|h| !h.is_empty())
+            .ok_or_else(|| PluginError::InvalidConfig("header is required".into()))?
+            .to_lowercase();
+        let secret_refs: Vec<String> = config
+            .get("secret_refs")
+            .map(|list| {
+                list.split(',')
+                    .map(str::trim)
+                    .filter(|r| !r.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if secret_refs.is_empty() {
+            return Err(PluginError::InvalidConfig(
+                "secret_refs must list at least one secret reference".into(),
+            ));
+        }
+        let strategy = match config.get("strategy").map(String::as_str) {
+            None | Some("round_robin") => Strategy::RoundRobin,
+            Some("least_recently_used") => Strategy::LeastRecentlyUsed,
+            Some(other) => {
+                return Err(PluginError::InvalidConfig(format!(
+                    "strategy must be round_robin or least_recently_used, got '{other}'"
+                )));
+            }
+        };
+        let cooldown = match config.get("cooldown_secs") {
+            None => DEFAULT_COOLDOWN,
+            Some(secs) => match secs.parse::<u64>() {
+                Ok(secs) if secs <= MAX_COOLDOWN_SECS => Duration::from_secs(secs),
+                _ => {
+                    return Err(PluginError::InvalidConfig(format!(
+                        "cooldown_secs must be a whole number of seconds up to \
+                         {MAX_COOLDOWN_SECS}, got '{secs}'"
+                    )));
+                }
+            },
+        };
+        Ok(Self {
+            header,
+            prefix: config.get("prefix").cloned().unwrap_or_default(),
+            secret_refs,
+            strategy,
+            cooldown,
+        })
+    }
+}
+
+/// Usage of one key, identified by its tenant-resolved reference.
+#[derive(Debug, Clone, Copy, Default)]
+struct KeyUsage {
+    last_used: Option<Instant>,
+    /// Passed over until then, after the upstream answered `429`.
+    throttled_until: Option<Instant>,
+}
+
+/// Shared by every upstream configured with the plugin.
+#[derive(Default)]
+struct RotationState {
+    keys: HashMap<String, KeyUsage>,
+    /// Next round-robin position, per configured key list.
+    cursors: HashMap<Vec<String>, usize>,
+}
+
+impl RotationState {
+    /// Pick the next of `keys` by `strategy`, skipping keys still cooling
+    /// down after a `429`. When all of them are, the one whose cooldown
+    /// ends first is used.
+    fn pick(&mut self, keys: &[String], strategy: Strategy, now: Instant) -> usize {
+        let usage = |state: &Self, i: usize| state.keys.get(&keys[i]).copied().unwrap_or_default();
+        let available: Vec<usize> = (0..keys.len())
+            .filter(|&i| usage(self, i).throttled_until.is_none_or(|t| t <= now))
+            .collect();
+
+        let picked = if available.is_empty() {
+            (0..keys.len())
+                .min_by_key(|&i| usage(self, i).throttled_until)
+                .unwrap_or_default()
+        } else {
+            match strategy {
+                Strategy::RoundRobin => {
+                    let cursor = self.cursors.get(keys).copied().unwrap_or_default() % keys.len();
+                    available
+                        .iter()
+                        .copied()
+                        .find(|&i| i >= cursor)
+                        .unwrap_or(available[0])
+                }
+                // `None` (never used) sorts first; ties go to the earlier key.
+                Strategy::LeastRecentlyUsed => available
+                    .iter()
+                    .copied()
+                    .min_by_key(|&i| usage(self, i).last_used)
+                    .unwrap_or_default(),
+            }
+        };
+
+        self.cursors.insert(keys.to_vec(), picked + 1);
+        self.keys.entry(keys[picked].clone()).or_default().last_used = Some(now);
+        picked
+    }
+}
+
+/// Auth plugin that spreads requests across several API keys, for upstreams
+/// that rate-limit per key. Each request gets one key, picked round-robin
+/// or least-recently-used, injected into the configured header like the API
+/// key plugin does. A key the upstream answers with `429` is passed over for
+/// `cooldown_secs` while another key is available.
+pub struct RotatingApiKeyAuthPlugin {
+    secrets: SecretResolvers,
+    state: Mutex<RotationState>,
+}
+
+impl RotatingApiKeyAuthPlugin {
+    #[must_use]
+    pub fn new(credstore: Arc<dyn CredStoreClientV1>) -> Self {
+        Self {
+            secrets: SecretResolvers::new(credstore),
+            state: Mutex::new(RotationState::default()),
+        }
+    }
+
+    /// Cooldowns are kept per key; `cooldown_secs` comes from the request
+    /// that picked the key.
+    fn throttle(&self, key: &str, cooldown: Duration) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .keys
+            .entry(key.to_string())
+            .or_default()
+            .throttled_until = Some(Instant::now() + cooldown);
+    }
+}
+
+#[async_trait]
+impl AuthPlugin for RotatingApiKeyAuthPlugin {
+    async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+        let config = RotatingApiKeyConfig::parse(&ctx.config)?;
+        let tenant_id = ctx.security_context.subject_tenant_id();
+        // Keys are told apart by their resolved reference so that tenants
+        // with templated references do not share cooldowns.
+        let keys: Vec<String> = config
+            .secret_refs
+            .iter()
+            .map(|r| resolve_secret_ref(r, tenant_id))
+            .collect();
+        let picked = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.pick(&keys, config.strategy, Instant::now())
+        };
+
+        let secret_ref = &config.secret_refs[picked];
+        let secret = self
+            .secrets
+            .resolve(&ctx.security_context, secret_ref)
+            .await?
+            .ok_or_else(|| PluginError::SecretNotFound(secret_ref.clone()))?;
+        ctx.headers
+            .insert(config.header, format!("{}{secret}", config.prefix));
+        // The cooldown travels with the credential: `upstream_responded`
+        // has no access to the configuration.
+        ctx.credential = Some(format!("{}|{}", config.cooldown.as_secs(), keys[picked]));
+        Ok(())
+    }
+
+    fn config_schema(&self) -> &'static [ConfigKey] {
+        CONFIG_SCHEMA
+    }
+
+    fn upstream_responded(&self, credential: &str, status: u16) {
+        if status != http::StatusCode::TOO_MANY_REQUESTS.as_u16() {
+            return;
+        }
+        let Some((cooldown, key)) = credential
+            .split_once('|')
+            .and_then(|(secs, key)| Some((Duration::from_secs(secs.parse().ok()?), key)))
+        else {
+            return;
+        };
+        tracing::debug!(
+            key,
+            ?cooldown,
+            "API key throttled by upstream, rotating away"
+        );
+        self.throttle(key, cooldown);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use crate::domain::plugin::{AuthPlugin, PluginError};
+    use crate::domain::test_support::{AuthContextBuilder, MockCredStoreClient};
+
+    use super::*;
+
+    fn plugin() -> RotatingApiKeyAuthPlugin {
+        RotatingApiKeyAuthPlugin::new(Arc::new(MockCredStoreClient::with_secrets(vec![
+            ("key-a".into(), "sk-a".into()),
+            ("key-b".into(), "sk-b".into()),
+            ("key-c".into(), "sk-c".into()),
+        ])))
+    }
+
+    fn config(strategy: &str) -> HashMap<String, String> {
+        HashMap::from([
+            ("header".into(), "authorization".into()),
+            ("prefix".into(), "Bearer ".into()),
+            (
+                "secret_refs".into(),
+                "cred://key-a, cred://key-b, cred://key-c".into(),
+            ),
+            ("strategy".into(), strategy.into()),
+        ])
+    }
+
+    /// Authenticate once, returning the injected header and the credential.
+    async fn next_key(
+        plugin: &RotatingApiKeyAuthPlugin,
+        config: &HashMap<String, String>,
+    ) -> (String, String) {
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(config.clone())
+            .build();
+        plugin.authenticate(&mut ctx).await.unwrap();
+        (
+            ctx.headers.get("authorization").unwrap().to_string(),
+            ctx.credential.unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn round_robin_cycles_through_the_keys() {
+        let plugin = plugin();
+        let config = config("round_robin");
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            seen.push(next_key(&plugin, &config).await.0);
+        }
+        assert_eq!(
+            seen,
+            ["Bearer sk-a", "Bearer sk-b", "Bearer sk-c", "Bearer sk-a"]
+        );
+    }
+
+    #[tokio::test]
+    async fn least_recently_used_picks_the_idle_key() {
+        let plugin = plugin();
+        let config = config("least_recently_used");
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            seen.push(next_key(&plugin, &config).await.0);
+        }
+        assert_eq!(seen, ["Bearer sk-a", "Bearer sk-b", "Bearer sk-c"]);
+        assert_eq!(next_key(&plugin, &config).await.0, "Bearer sk-a");
+    }
+
+    #[tokio::test]
+    async fn throttled_key_is_passed_over() {
+        let plugin = plugin();
+        let config = config("round_robin");
+        let (first, credential) = next_key(&plugin, &config).await;
+        assert_eq!(first, "Bearer sk-a");
+        plugin.upstream_responded(&credential, 429);
+
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            seen.push(next_key(&plugin, &config).await.0);
+        }
+        assert_eq!(
+            seen,
+            ["Bearer sk-b", "Bearer sk-c", "Bearer sk-b", "Bearer sk-c"]
+        );
+
+        // Other statuses leave the rotation alone.
+        let (_, credential) = next_key(&plugin, &config).await;
+        plugin.upstream_responded(&credential, 503);
+        assert_eq!(next_key(&plugin, &config).await.0, "Bearer sk-c");
+    }
+
+    #[tokio::test]
+    async fn all_keys_throttled_uses_the_first_to_recover() {
+        let plugin = plugin();
+        let mut config = config("round_robin");
+        config.insert("secret_refs".into(), "cred://key-a,cred://key-b".into());
+        let (_, a) = next_key(&plugin, &config).await;
+        config.insert("cooldown_secs".into(), "1".into());
+        let (_, b) = next_key(&plugin, &config).await;
+        plugin.upstream_responded(&a, 429);
+        plugin.upstream_responded(&b, 429);
+
+        assert_eq!(next_key(&plugin, &config).await.0, "Bearer sk-b");
+    }
+
+    #[tokio::test]
+    async fn invalid_config_is_rejected() {
+        let plugin = plugin();
+        for (key, value) in [
+            ("secret_refs", " , "),
+            ("strategy", "random"),
+            ("cooldown_secs", "soon"),
+            ("cooldown_secs", "86401"),
+        ] {
+            let mut config = config("round_robin");
+            config.insert(key.into(), value.into());
+            let mut ctx = AuthContextBuilder::new().with_config(config).build();
+            let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+            assert!(matches!(err, PluginError::InvalidConfig(_)), "{key}: {err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_secret_returns_not_found() {
+        let plugin = RotatingApiKeyAuthPlugin::new(Arc::new(MockCredStoreClient::empty()));
+        let mut ctx = AuthContextBuilder::new()
+            .with_config(config("round_robin"))
+            .build();
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(
+            matches!(err, PluginError::SecretNotFound(ref r) if r == "cred://key-a"),
+            "{err:?}"
+        );
+    }
+}
@@ -0,0 +1,282 @@
+//! Scheme-based secret reference resolution for auth plugins: `cred://`
+//! references go to the credential store, other schemes to the
+//! [`SecretResolver`] registered in the `ClientHub` under that scheme.
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use credstore_sdk::{CredStoreClientV1, SecretRef};
+use modkit::client_hub::{ClientHub, ClientScope};
+use modkit_security::SecurityContext;
+use oagw_sdk::{SecretResolver, SecretResolverError};
+
+use crate::domain::plugin::{PluginError, TENANT_PLACEHOLDER, resolve_secret_ref};
+
+/// Scheme served by the credential store unless a resolver overrides it.
+const CRED_SCHEME: &str = "cred";
+
+/// Resolves secret references by scheme.
+#[derive(Clone)]
+pub(crate) struct SecretResolvers {
+    credstore: Arc<dyn CredStoreClientV1>,
+    hub: Option<Arc<ClientHub>>,
+}
+
+impl SecretResolvers {
+    pub(crate) fn new(credstore: Arc<dyn CredStoreClientV1>) -> Self {
+        Self {
+            credstore,
+            hub: None,
+        }
+    }
+
+    /// Look up resolvers for schemes other than `cred://` in `hub`.
+    #[must_use]
+    pub(crate) fn with_hub(mut self, hub: Arc<ClientHub>) -> Self {
+        self.hub = Some(hub);
+        self
+    }
+
+    /// Resolve `secret_ref` for the caller; `None` when the secret does not
+    /// exist. [`TENANT_PLACEHOLDER`] is replaced with the caller's tenant ID.
+    ///
+    /// # Errors
+    /// `InvalidConfig` for a scheme without a resolver; `Internal` when the
+    /// backend fails or the value is not UTF-8.
+    pub(crate) async fn resolve(
+        &self,
+        ctx: &SecurityContext,
+        secret_ref: &str,
+    ) -> Result<Option<String>, PluginError> {
+        let tenant_id = ctx.subject_tenant_id();
+        let parsed = secret_ref.split_once("://");
+        let resolver = parsed.and_then(|(scheme, _)| {
+            let hub = self.hub.as_ref()?;
+            hub.try_get_scoped::<dyn SecretResolver>(&ClientScope::new(scheme))
+        });
+
+        match (parsed, resolver) {
+            (Some((scheme, reference)), Some(resolver)) => {
+                let reference = reference.replace(TENANT_PLACEHOLDER, &tenant_id.to_string());
+                resolver.resolve(ctx, &reference).await.map_err(|e| {
+                    PluginError::Internal(format!("{scheme} secret resolver error: {e}"))
+                })
+            }
+            (None, _) | (Some((CRED_SCHEME, _)), None) => {
+                let raw_ref = resolve_secret_ref(secret_ref, tenant_id);
+                let key = SecretRef::new(&raw_ref).map_err(|e| {
+                    PluginError::Internal(format!("invalid secret ref '{raw_ref}': {e}"))
+                })?;
+                let Some(response) = self
+                    .credstore
+                    .get(ctx, &key)
+                    .await
+                    .map_err(|e| PluginError::Internal(format!("credstore error: {e}")))?
+                else {
+                    return Ok(None);
+                };
+                let value = std::str::from_utf8(response.value.as_bytes())
+                    .map_err(|_| PluginError::Internal("secret value is not valid UTF-8".into()))?;
+                Ok(Some(value.to_string()))
+            }
+            (Some((scheme, _)), None) => Err(PluginError::InvalidConfig(format!(
+                "no secret resolver registered for scheme '{scheme}://'"
+            ))),
+        }
+    }
+}
+
+/// Resolves `env://NAME` from the gateway's environment.
+pub(crate) struct EnvSecretResolver;
+
+#[async_trait]
+impl SecretResolver for EnvSecretResolver {
+    async fn resolve(
+        &self,
+        _ctx: &SecurityContext,
+        reference: &str,
+    ) -> Result<Option<String>, SecretResolverError> {
+        match std::env::var(reference) {
+            Ok(value) => Ok(Some(value)),
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(std::env::VarError::NotUnicode(_)) => Err(SecretResolverError::InvalidReference {
+                reference: reference.to_string(),
+                reason: "value is not valid UTF-8".into(),
+            }),
+        }
+    }
+}
+
+/// Resolves `file:///path` by reading the file, which must lie inside the
+/// configured directory. Trailing line breaks are stripped.
+pub(crate) struct FileSecretResolver {
+    dir: PathBuf,
+    canonical_dir: PathBuf,
+}
+
+impl FileSecretResolver {
+    /// Serve files under `dir`, which must exist.
+    pub(crate) fn new(dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let dir = dir.into();
+        let canonical_dir = std::fs::canonicalize(&dir)?;
+        Ok(Self { dir, canonical_dir })
+    }
+
+    fn invalid(reference: &str, reason: &str) -> SecretResolverError {
+        SecretResolverError::InvalidReference {
+            reference: reference.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl SecretResolver for FileSecretResolver {
+    async fn resolve(
+        &self,
+        _ctx: &SecurityContext,
+        reference: &str,
+    ) -> Result<Option<String>, SecretResolverError> {
+        let path = Path::new(reference);
+        if !path.is_absolute() {
+            return Err(Self::invalid(reference, "path must be absolute"));
+        }
+        if !path.starts_with(&self.dir) && !path.starts_with(&self.canonical_dir) {
+            return Err(Self::invalid(
+                reference,
+                "path is outside the secret directory",
+            ));
+        }
+        // Re-check after resolving `..` and symlinks.
+        let canonical = match tokio::fs::canonicalize(path).await {
+            Ok(canonical) => canonical,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(SecretResolverError::Unavailable(e.to_string())),
+        };
+        if !canonical.starts_with(&self.canonical_dir) {
+            return Err(Self::invalid(
+                reference,
+                "path is outside the secret directory",
+            ));
+        }
+        match tokio::fs::read_to_string(&canonical).await {
+            Ok(value) => Ok(Some(value.trim_end_matches(['\r', '\n']).to_string())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(SecretResolverError::Unavailable(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::domain::test_support::MockCredStoreClient;
+
+    use super::*;
+
+    fn secret_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("oagw-secrets-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn resolvers(hub: ClientHub) -> SecretResolvers {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "openai-key".into(),
+            "sk-cred".into(),
+        )]));
+        SecretResolvers::new(credstore).with_hub(Arc::new(hub))
+    }
+
+    #[tokio::test]
+    async fn env_resolver_reads_variable() {
+        let ctx = SecurityContext::anonymous();
+        // Cargo sets this for test binaries.
+        let value = EnvSecretResolver
+            .resolve(&ctx, "CARGO_MANIFEST_DIR")
+            .await
+            .unwrap();
+        assert_eq!(value.as_deref(), Some(env!("CARGO_MANIFEST_DIR")));
+        let missing = EnvSecretResolver
+            .resolve(&ctx, "OAGW_TEST_SECRET_THAT_DOES_NOT_EXIST")
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn file_resolver_reads_file_inside_dir() {
+        let dir = secret_dir();
+        std::fs::write(dir.join("api-key"), "sk-file\n").unwrap();
+        let resolver = FileSecretResolver::new(&dir).unwrap();
+        let ctx = SecurityContext::anonymous();
+
+        let path = dir.join("api-key");
+        let value = resolver
+            .resolve(&ctx, path.to_str().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(value.as_deref(), Some("sk-file"));
+
+        let missing = dir.join("other");
+        let value = resolver
+            .resolve(&ctx, missing.to_str().unwrap())
+            .await
+            .unwrap();
+        assert!(value.is_none());
+    }
+
+    #[tokio::test]
+    async fn file_resolver_rejects_paths_outside_dir() {
+        let dir = secret_dir();
+        let resolver = FileSecretResolver::new(&dir).unwrap();
+        let ctx = SecurityContext::anonymous();
+
+        let escape = dir.join("..").join("..").join("etc").join("passwd");
+        for reference in ["/etc/passwd", "relative/key", escape.to_str().unwrap()] {
+            let err = resolver.resolve(&ctx, reference).await.unwrap_err();
+            assert!(
+                matches!(err, SecretResolverError::InvalidReference { .. }),
+                "{reference}: {err}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn dispatches_by_scheme() {
+        let dir = secret_dir();
+        std::fs::write(dir.join("key"), "sk-file").unwrap();
+        let hub = ClientHub::new();
+        hub.register_scoped::<dyn SecretResolver>(
+            ClientScope::new("file"),
+            Arc::new(FileSecretResolver::new(&dir).unwrap()),
+        );
+        let resolvers = resolvers(hub);
+        let ctx = SecurityContext::anonymous();
+
+        let file_ref = format!("file://{}", dir.join("key").display());
+        let value = resolvers.resolve(&ctx, &file_ref).await.unwrap();
+        assert_eq!(value.as_deref(), Some("sk-file"));
+
+        let value = resolvers.resolve(&ctx, "cred://openai-key").await.unwrap();
+        assert_eq!(value.as_deref(), Some("sk-cred"));
+        let value = resolvers.resolve(&ctx, "openai-key").await.unwrap();
+        assert_eq!(value.as_deref(), Some("sk-cred"));
+    }
+
+    #[tokio::test]
+    async fn unregistered_scheme_is_invalid_config() {
+        let resolvers = resolvers(ClientHub::new());
+        let err = resolvers
+            .resolve(&SecurityContext::anonymous(), "env://OPENAI_KEY")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, PluginError::InvalidConfig(ref msg) if msg.contains("env://")),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,353 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use url::Url;
+
+use crate::domain::plugin::{AuthContext, AuthPlugin, ConfigKey, ConfigValueType, PluginError};
+
+/// RFC 8693 grant and token type identifiers.
+const TOKEN_EXCHANGE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+/// Keys accepted in the plugin configuration. Without `source_header` the
+/// caller's token is taken from the `SecurityContext`; `audience` only
+/// applies together with `token_exchange_endpoint`.
+const CONFIG_SCHEMA: &[ConfigKey] = &[
+    ConfigKey::optional("source_header", ConfigValueType::String),
+    ConfigKey::optional("token_exchange_endpoint", ConfigValueType::Url),
+    ConfigKey::optional("audience", ConfigValueType::String),
+];
+
+/// Parsed configuration from `AuthContext::config`.
+struct TokenPassthroughConfig {
+    /// Inbound header carrying the caller's token. It must be forwarded by
+    /// the upstream's header passthrough rules to reach the plugin.
+    source_header: Option<String>,
+    token_exchange_endpoint: Option<Url>,
+    audience: Option<String>,
+}
+
+impl TokenPassthroughConfig {
+    fn parse(config: &HashMap<String, String>) -> Result<Self, PluginError> {
+        let token_exchange_endpoint = config
+            .get("token_exchange_endpoint")
+            .map(|s| {
+                Url::parse(s).map_err(|e| {
+                    PluginError::InvalidConfig(format!("invalid token_exchange_endpoint URL: {e}"))
+                })
+            })
+            .transpose()?;
+
+        let audience = config.get("audience").cloned();
+        if audience.is_some() && token_exchange_endpoint.is_none() {
+            return Err(PluginError::InvalidConfig(
+                "audience requires token_exchange_endpoint".into(),
+            ));
+        }
+
+        Ok(Self {
+            source_header: config.get("source_header").map(|h| h.to_lowercase()),
+            token_exchange_endpoint,
+            audience,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenExchangeResponse {
+    access_token: String,
+}
+
+/// Auth plugin that forwards the calling subject's own bearer token to the
+/// upstream, optionally exchanging it first (RFC 8693).
+///
+/// The token is never logged and never included in error messages.
+pub struct TokenPassthroughAuthPlugin {
+    http_config: Option<modkit_http::HttpClientConfig>,
+}
+
+impl TokenPassthroughAuthPlugin {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { http_config: None }
+    }
+
+    /// Override the HTTP client config used for token exchange requests.
+    #[must_use]
+    pub(crate) fn with_http_config(mut self, config: modkit_http::HttpClientConfig) -> Self {
+        self.http_config = Some(config);
+        self
+    }
+
+    /// The caller's token from the configured header, or from the security
+    /// context when no header is configured. A `Bearer ` prefix is stripped.
+    fn caller_token(
+        ctx: &mut AuthContext,
+        config: &TokenPassthroughConfig,
+    ) -> Result<SecretString, PluginError> {
+        let token = match config.source_header.as_deref() {
+            Some(header) => {
+                let value = ctx.headers.get(header).map(strip_bearer).map(str::to_owned);
+                // The token travels in `Authorization`; don't duplicate it.
+                ctx.headers.remove(header);
+                value.map(SecretString::from)
+            }
+            None => ctx.security_context.bearer_token().cloned(),
+        };
+        token
+            .filter(|t| !t.expose_secret().is_empty())
+            .ok_or_else(|| PluginError::AuthFailed("caller token missing".into()))
+    }
+
+    /// Exchange `subject_token` for a token accepted by the upstream.
+    async fn exchange(
+        &self,
+        endpoint: &Url,
+        audience: Option<&str>,
+        subject_token: &SecretString,
+    ) -> Result<SecretString, PluginError> {
+        let http_config = self
+            .http_config
+            .clone()
+            .unwrap_or_else(modkit_http::HttpClientConfig::token_endpoint);
+        let client = modkit_http::HttpClientBuilder::with_config(http_config)
+            .build()
+            .map_err(|e| PluginError::Internal(format!("token exchange client: {e}")))?;
+
+        let mut form = vec![
+            ("grant_type", TOKEN_EXCHANGE_GRANT_TYPE),
+            ("subject_token", subject_token.expose_secret()),
+            ("subject_token_type", ACCESS_TOKEN_TYPE),
+        ];
+        if let Some(audience) = audience {
+            form.push(("audience", audience));
+        }
+
+        let response = client
+            .post(endpoint.as_str())
+            .form(&form)
+            .map_err(|e| PluginError::Internal(format!("token exchange request: {e}")))?
+            .send()
+            .await
+            .map_err(|e| PluginError::Internal(format!("token exchange failed: {e}")))?;
+
+        let status = response.status();
+        if status.is_client_error() {
+            return Err(PluginError::AuthFailed(format!(
+                "token exchange rejected the caller token: {status}"
+            )));
+        }
+        if !status.is_success() {
+            return Err(PluginError::Internal(format!(
+                "token exchange failed: {status}"
+            )));
+        }
+        let body: TokenExchangeResponse = response
+            .json()
+            .await
+            .map_err(|e| PluginError::Internal(format!("invalid token exchange response: {e}")))?;
+        Ok(SecretString::from(body.access_token))
+    }
+}
+
+impl Default for TokenPassthroughAuthPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn strip_bearer(value: &str) -> &str {
+    let value = value.trim();
+    match value.get(..7) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("bearer ") => value[7..].trim_start(),
+        _ => value,
+    }
+}
+
+#[async_trait]
+impl AuthPlugin for TokenPassthroughAuthPlugin {
+    async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+        let config = TokenPassthroughConfig::parse(&ctx.config)?;
+        let mut token = Self::caller_token(ctx, &config)?;
+        if let Some(ref endpoint) = config.token_exchange_endpoint {
+            token = self
+                .exchange(endpoint, config.audience.as_deref(), &token)
+                .await?;
+        }
+
+        ctx.headers
+            .insert("authorization", format!("Bearer {}", token.expose_secret()));
+        Ok(())
+    }
+
+    fn config_schema(&self) -> &'static [ConfigKey] {
+        CONFIG_SCHEMA
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::prelude::*;
+    use modkit_security::SecurityContext;
+    use uuid::Uuid;
+
+    use crate::domain::plugin::AuthHeaders;
+
+    use super::*;
+
+    fn security_context(bearer: Option<&str>) -> SecurityContext {
+        let mut builder = SecurityContext::builder()
+            .subject_tenant_id(Uuid::new_v4())
+            .subject_id(Uuid::new_v4());
+        if let Some(token) = bearer {
+            builder = builder.bearer_token(token.to_string());
+        }
+        builder.build().expect("test security context")
+    }
+
+    fn make_auth_ctx(
+        headers: AuthHeaders,
+        config: HashMap<String, String>,
+        bearer: Option<&str>,
+    ) -> AuthContext {
+        AuthContext {
+            headers,
+            config,
+            security_context: security_context(bearer),
+            early_response: None,
+            body_fields: Vec::new(),
+            query: Vec::new(),
+            method: "GET".into(),
+            path: "/".into(),
+            body: None,
+            now: std::time::SystemTime::now(),
+            credential: None,
+        }
+    }
+
+    fn make_plugin() -> TokenPassthroughAuthPlugin {
+        TokenPassthroughAuthPlugin::new()
+            .with_http_config(modkit_http::HttpClientConfig::for_testing())
+    }
+
+    #[tokio::test]
+    async fn forwards_security_context_token() {
+        let mut ctx = make_auth_ctx(AuthHeaders::new(), HashMap::new(), Some("caller-token"));
+
+        make_plugin().authenticate(&mut ctx).await.unwrap();
+
+        assert_eq!(
+            ctx.headers.get("authorization"),
+            Some("Bearer caller-token")
+        );
+    }
+
+    #[tokio::test]
+    async fn forwards_token_from_source_header() {
+        let mut headers = AuthHeaders::new();
+        headers.insert("x-user-token", "Bearer from-header");
+        let mut ctx = make_auth_ctx(
+            headers,
+            HashMap::from([("source_header".into(), "X-User-Token".into())]),
+            Some("ignored"),
+        );
+
+        make_plugin().authenticate(&mut ctx).await.unwrap();
+
+        assert_eq!(ctx.headers.get("authorization"), Some("Bearer from-header"));
+        assert!(!ctx.headers.contains_key("x-user-token"));
+    }
+
+    #[tokio::test]
+    async fn missing_security_context_token_fails() {
+        let mut ctx = make_auth_ctx(AuthHeaders::new(), HashMap::new(), None);
+
+        let err = make_plugin().authenticate(&mut ctx).await.unwrap_err();
+
+        assert!(matches!(err, PluginError::AuthFailed(_)));
+        assert!(!ctx.headers.contains_key("authorization"));
+    }
+
+    #[tokio::test]
+    async fn missing_source_header_fails() {
+        let mut ctx = make_auth_ctx(
+            AuthHeaders::new(),
+            HashMap::from([("source_header".into(), "x-user-token".into())]),
+            Some("not-used"),
+        );
+
+        let err = make_plugin().authenticate(&mut ctx).await.unwrap_err();
+
+        assert!(matches!(err, PluginError::AuthFailed(_)));
+    }
+
+    #[tokio::test]
+    async fn exchanges_token_before_forwarding() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/exchange")
+                .body_includes("subject_token=caller-token")
+                .body_includes("audience=billing-api");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"access_token":"exchanged-token","token_type":"Bearer"}"#);
+        });
+        let mut ctx = make_auth_ctx(
+            AuthHeaders::new(),
+            HashMap::from([
+                (
+                    "token_exchange_endpoint".into(),
+                    format!("http://localhost:{}/exchange", server.port()),
+                ),
+                ("audience".into(), "billing-api".into()),
+            ]),
+            Some("caller-token"),
+        );
+
+        make_plugin().authenticate(&mut ctx).await.unwrap();
+
+        mock.assert();
+        assert_eq!(
+            ctx.headers.get("authorization"),
+            Some("Bearer exchanged-token")
+        );
+    }
+
+    #[tokio::test]
+    async fn rejected_exchange_fails_without_leaking_token() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/exchange");
+            then.status(401);
+        });
+        let mut ctx = make_auth_ctx(
+            AuthHeaders::new(),
+            HashMap::from([(
+                "token_exchange_endpoint".into(),
+                format!("http://localhost:{}/exchange", server.port()),
+            )]),
+            Some("caller-token"),
+        );
+
+        let err = make_plugin().authenticate(&mut ctx).await.unwrap_err();
+
+        assert!(matches!(err, PluginError::AuthFailed(_)));
+        assert!(!err.to_string().contains("caller-token"));
+    }
+
+    #[tokio::test]
+    async fn audience_without_exchange_endpoint_is_invalid() {
+        let mut ctx = make_auth_ctx(
+            AuthHeaders::new(),
+            HashMap::from([("audience".into(), "billing-api".into())]),
+            Some("caller-token"),
+        );
+
+        let err = make_plugin().authenticate(&mut ctx).await.unwrap_err();
+
+        assert!(matches!(err, PluginError::InvalidConfig(_)));
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use credstore_sdk::{CredStoreClientV1, SecretRef};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use super::replay_cache::ReplayCache;
+use crate::domain::plugin::{
+    AuthContext, AuthPlugin, ConfigKey, ConfigValueType, PluginError, resolve_secret_ref,
+};
+
+/// Keys accepted in the plugin configuration.
+const CONFIG_SCHEMA: &[ConfigKey] = &[
+    ConfigKey::required("secret_ref", ConfigValueType::SecretRef),
+    ConfigKey::optional("signature_header", ConfigValueType::String),
+    ConfigKey::optional("timestamp_header", ConfigValueType::String),
+    ConfigKey::optional("nonce_header", ConfigValueType::String),
+];
+
+/// Parsed configuration from `AuthContext::config`.
+struct WebhookSignatureConfig {
+    /// HMAC key reference.
+    secret_ref: String,
+    /// Inbound header carrying the hex signature.
+    signature_header: String,
+    /// Inbound header carrying the signing time, in Unix seconds.
+    timestamp_header: String,
+    /// Inbound header carrying a nonce. Without it the signature itself is
+    /// what may not be reused.
+    nonce_header: Option<String>,
+}
+
+impl WebhookSignatureConfig {
+    fn parse(config: &HashMap<String, String>) -> Result<Self, PluginError> {
+        let secret_ref = config
+            .get("secret_ref")
+            .filter(|r| !r.is_empty())
+            .ok_or_else(|| PluginError::InvalidConfig("secret_ref is required".into()))?
+            .clone();
+        let header = |key: &str, default: &str| {
+            config
+                .get(key)
+                .filter(|h| !h.is_empty())
+                .map_or_else(|| default.to_string(), |h| h.to_lowercase())
+        };
+        Ok(Self {
+            secret_ref,
+            signature_header: header("signature_header", "x-webhook-signature"),
+            timestamp_header: header("timestamp_header", "x-webhook-timestamp"),
+            nonce_header: config
+                .get("nonce_header")
+                .filter(|h| !h.is_empty())
+                .map(|h| h.to_lowercase()),
+        })
+    }
+}
+
+/// Auth plugin that verifies signed inbound webhooks before they are
+/// forwarded.
+///
+/// The signature is the hex HMAC-SHA256, keyed by the `secret_ref` secret,
+/// of the timestamp header's value, a `.`, and the request body. A missing
+/// or wrong signature, a timestamp outside the tolerance window, or a
+/// signature (or nonce) already accepted within it fails the request with
+/// 401.
+pub struct WebhookSignatureAuthPlugin {
+    credstore: Arc<dyn CredStoreClientV1>,
+    replays: ReplayCache,
+}
+
+impl WebhookSignatureAuthPlugin {
+    #[must_use]
+    pub fn new(credstore: Arc<dyn CredStoreClientV1>) -> Self {
+        Self {
+            credstore,
+            replays: ReplayCache::default(),
+        }
+    }
+
+    /// Use `replays` to remember accepted signatures.
+    #[cfg(test)]
+    #[must_use]
+    fn with_replay_cache(mut self, replays: ReplayCache) -> Self {
+        self.replays = replays;
+        self
+    }
+
+    /// Resolve the HMAC key for the calling tenant.
+    async fn resolve_key(
+        &self,
+        ctx: &AuthContext,
+        secret_ref: &str,
+    ) -> Result<Vec<u8>, PluginError> {
+        let tenant_id = ctx.security_context.subject_tenant_id();
+        let raw_ref = resolve_secret_ref(secret_ref, tenant_id);
+        let key = SecretRef::new(&raw_ref)
+            .map_err(|e| PluginError::Internal(format!("invalid secret ref '{raw_ref}': {e}")))?;
+        let response = self
+            .credstore
+            .get(&ctx.security_context, &key)
+            .await
+            .map_err(|e| PluginError::Internal(format!("credstore error: {e}")))?
+            .ok_or_else(|| PluginError::SecretNotFound(secret_ref.to_string()))?;
+        Ok(response.value.as_bytes().to_vec())
+    }
+}
+
+/// Check `signature` (hex) against the HMAC-SHA256 of `timestamp`, `.` and
+/// `body`, in constant time.
+fn verify_signature(key: &[u8], timestamp: &str, body: &[u8], signature: &str) -> bool {
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+#[async_trait]
+impl AuthPlugin for WebhookSignatureAuthPlugin {
+    async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+        let config = WebhookSignatureConfig::parse(&ctx.config)?;
+        let header = |name: &str| {
+            ctx.headers
+                .get(name)
+                .map(str::to_string)
+                .ok_or_else(|| PluginError::AuthFailed(format!("missing {name} header")))
+        };
+        let signature = header(&config.signature_header)?;
+        let timestamp = header(&config.timestamp_header)?;
+        let nonce = config.nonce_header.as_deref().map(header).transpose()?;
+        let signed_at = timestamp
+            .parse::<u64>()
+            .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .map_err(|_| PluginError::AuthFailed("invalid signature timestamp".into()))?;
+
+        // Verify before remembering anything, so that only correctly signed
+        // requests take up replay cache slots.
+        let key = self.resolve_key(ctx, &config.secret_ref).await?;
+        let body = ctx.body.as_deref().unwrap_or_default();
+        if !verify_signature(&key, &timestamp, body, &signature) {
+            return Err(PluginError::AuthFailed("invalid webhook signature".into()));
+        }
+        let tenant_id = ctx.security_context.subject_tenant_id();
+        let seen = nonce.unwrap_or(signature);
+        self.replays
+            .check(&format!("{tenant_id}:{seen}"), signed_at, ctx.now)
+    }
+
+    fn config_schema(&self) -> &'static [ConfigKey] {
+        CONFIG_SCHEMA
+    }
+
+    fn requires_body(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::test_support::{AuthContextBuilder, MockCredStoreClient};
+
+    use super::*;
+
+    const BODY: &str = r#"{"event":"paid"}"#;
+
+    fn plugin() -> WebhookSignatureAuthPlugin {
+        WebhookSignatureAuthPlugin::new(Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "hook-key".into(),
+            "k3y".into(),
+        )])))
+    }
+
+    fn sign(timestamp: &str, body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"k3y").unwrap();
+        mac.update(format!("{timestamp}.{body}").as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn now_secs() -> String {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            .to_string()
+    }
+
+    fn signed_request(config: HashMap<String, String>, timestamp: &str) -> AuthContext {
+        AuthContextBuilder::new()
+            .with_config(config)
+            .with_method("POST")
+            .with_header("x-webhook-timestamp", timestamp)
+            .with_header("x-webhook-signature", &sign(timestamp, BODY))
+            .with_body(BODY)
+            .build()
+    }
+
+    fn config() -> HashMap<String, String> {
+        HashMap::from([("secret_ref".into(), "cred://hook-key".into())])
+    }
+
+    #[tokio::test]
+    async fn same_signed_request_twice_is_rejected() {
+        let plugin = plugin();
+        let timestamp = now_secs();
+
+        let mut ctx = signed_request(config(), &timestamp);
+        plugin.authenticate(&mut ctx).await.unwrap();
+
+        let mut ctx = signed_request(config(), &timestamp);
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::AuthFailed(ref m) if m.contains("already used")));
+    }
+
+    #[tokio::test]
+    async fn distinct_nonce_passes() {
+        let plugin = plugin();
+        let timestamp = now_secs();
+        let mut config = config();
+        config.insert("nonce_header".into(), "X-Webhook-Nonce".into());
+
+        for nonce in ["n-1", "n-2"] {
+            let mut ctx = signed_request(config.clone(), &timestamp);
+            ctx.headers.insert("x-webhook-nonce", nonce);
+            plugin.authenticate(&mut ctx).await.unwrap();
+        }
+        let mut ctx = signed_request(config, &timestamp);
+        ctx.headers.insert("x-webhook-nonce", "n-1");
+        assert!(plugin.authenticate(&mut ctx).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn tampered_body_is_rejected() {
+        let mut ctx = signed_request(config(), &now_secs());
+        ctx.body = Some(r#"{"event":"refunded"}"#.into());
+        let err = plugin().authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::AuthFailed(ref m) if m.contains("invalid")));
+    }
+
+    #[tokio::test]
+    async fn stale_timestamp_is_rejected() {
+        let stale = (SystemTime::now() - Duration::from_secs(3600))
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            .to_string();
+        let mut ctx = signed_request(config(), &stale);
+        let err = plugin().authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::AuthFailed(ref m) if m.contains("tolerance")));
+    }
+
+    #[tokio::test]
+    async fn forged_requests_do_not_fill_the_replay_cache() {
+        let plugin = plugin().with_replay_cache(ReplayCache::new(Duration::from_secs(300), 1));
+        let timestamp = now_secs();
+        for _ in 0..3 {
+            let mut ctx = signed_request(config(), &timestamp);
+            ctx.headers.insert("x-webhook-signature", "00ff");
+            assert!(plugin.authenticate(&mut ctx).await.is_err());
+        }
+        let mut ctx = signed_request(config(), &timestamp);
+        plugin.authenticate(&mut ctx).await.unwrap();
+    }
+}
@@ -0,0 +1,108 @@
+//! Injection of auth plugin `body_fields` into buffered JSON request
+//! bodies (e.g. an API key the upstream expects in the payload).
+
+use bytes::Bytes;
+use serde_json::{Map, Value};
+
+/// Set each `(pointer, value)` in `fields` as a string in the JSON object
+/// `body`, creating missing intermediate objects, and return the
+/// re-serialized body.
+///
+/// # Errors
+/// A message suitable for the client when the body is not a JSON object, a
+/// pointer is malformed, or a pointer crosses a non-object value.
+pub(crate) fn inject(body: &[u8], fields: &[(String, String)]) -> Result<Bytes, String> {
+    let mut doc: Value = serde_json::from_slice(body).map_err(|_| {
+        "request body must be a JSON object to receive the upstream credential".to_string()
+    })?;
+    if !doc.is_object() {
+        return Err("request body must be a JSON object to receive the upstream credential".into());
+    }
+    for (pointer, value) in fields {
+        set(&mut doc, pointer, Value::String(value.clone()))?;
+    }
+    serde_json::to_vec(&doc)
+        .map(Bytes::from)
+        .map_err(|e| format!("failed to serialize request body: {e}"))
+}
+
+/// Set `pointer` (RFC 6901) in `doc`. Only object members are traversed;
+/// an array or scalar on the way is an error rather than being replaced.
+fn set(doc: &mut Value, pointer: &str, value: Value) -> Result<(), String> {
+    let Some(path) = pointer.strip_prefix('/') else {
+        return Err(format!(
+            "invalid JSON pointer '{pointer}': must start with '/'"
+        ));
+    };
+    let tokens: Vec<String> = path
+        .split('/')
+        .map(|t| t.replace("~1", "/").replace("~0", "~"))
+        .collect();
+    let Some((last, parents)) = tokens.split_last() else {
+        return Err(format!("invalid JSON pointer '{pointer}'"));
+    };
+    let mut current = doc;
+    for token in parents {
+        let Value::Object(map) = current else {
+            return Err(format!(
+                "cannot set '{pointer}': '{token}' is not inside a JSON object"
+            ));
+        };
+        current = map
+            .entry(token.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+    let Value::Object(map) = current else {
+        return Err(format!(
+            "cannot set '{pointer}': '{last}' is not inside a JSON object"
+        ));
+    };
+    map.insert(last.clone(), value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inject_one(body: &str, pointer: &str) -> Result<Value, String> {
+        let out = inject(body.as_bytes(), &[(pointer.into(), "sk-123".into())])?;
+        Ok(serde_json::from_slice(&out).unwrap())
+    }
+
+    #[test]
+    fn sets_top_level_field() {
+        let doc = inject_one(r#"{"model":"m1","api_key":"placeholder"}"#, "/api_key").unwrap();
+        assert_eq!(doc, serde_json::json!({"model": "m1", "api_key": "sk-123"}));
+    }
+
+    #[test]
+    fn sets_nested_field_creating_parents() {
+        let doc = inject_one(r#"{"model":"m1"}"#, "/auth/credentials/key").unwrap();
+        assert_eq!(
+            doc,
+            serde_json::json!({"model": "m1", "auth": {"credentials": {"key": "sk-123"}}})
+        );
+
+        let doc = inject_one(r#"{"a/b":{"x":1}}"#, "/a~1b/key").unwrap();
+        assert_eq!(doc, serde_json::json!({"a/b": {"x": 1, "key": "sk-123"}}));
+    }
+
+    #[test]
+    fn rejects_non_json_and_non_object_targets() {
+        for body in ["", "not json", "[1,2]", "\"text\""] {
+            let err = inject_one(body, "/api_key").unwrap_err();
+            assert!(err.contains("must be a JSON object"), "{body}: {err}");
+        }
+        assert!(
+            inject_one("{}", "api_key")
+                .unwrap_err()
+                .contains("must start with '/'")
+        );
+        assert!(
+            inject_one(r#"{"auth":"x"}"#, "/auth/key")
+                .unwrap_err()
+                .contains("not inside a JSON object")
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Evaluation of [`BodyMatcher`] conditions against buffered bodies: per-upstream
+//! `retry_on_body` on responses and per-route `match_body` on requests.
+
+use http::Method;
+use regex::Regex;
+
+use crate::domain::model::BodyMatcher;
+
+/// A [`BodyMatcher`] prepared for repeated evaluation within one request.
+pub(crate) enum CompiledBodyMatcher<'a> {
+    JsonPointer {
+        pointer: &'a str,
+        value: &'a serde_json::Value,
+    },
+    Regex(Regex),
+}
+
+impl<'a> CompiledBodyMatcher<'a> {
+    /// Compile the matcher. Returns `None` for a pattern that does not
+    /// compile; the control plane rejects those, so this only guards against
+    /// stale stored config.
+    pub(crate) fn new(matcher: &'a BodyMatcher) -> Option<Self> {
+        match matcher {
+            BodyMatcher::JsonPointer { pointer, value } => Some(Self::JsonPointer {
+                pointer: pointer.as_str(),
+                value,
+            }),
+            BodyMatcher::Regex { pattern } => match Regex::new(pattern) {
+                Ok(re) => Some(Self::Regex(re)),
+                Err(e) => {
+                    tracing::warn!(error = %e, "invalid body matcher pattern, ignoring");
+                    None
+                }
+            },
+        }
+    }
+
+    /// Whether `body` satisfies the condition. Bodies that are not
+    /// valid JSON (for pointers) or UTF-8 (for patterns) never match.
+    pub(crate) fn matches(&self, body: &[u8]) -> bool {
+        match self {
+            Self::JsonPointer { pointer, value } => {
+                serde_json::from_slice::<serde_json::Value>(body)
+                    .ok()
+                    .is_some_and(|json| json.pointer(pointer) == Some(*value))
+            }
+            Self::Regex(re) => std::str::from_utf8(body).is_ok_and(|s| re.is_match(s)),
+        }
+    }
+}
+
+/// Methods that are safe to replay after the upstream already answered.
+pub(crate) fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn json_pointer_matches_expected_value() {
+        let m = BodyMatcher::JsonPointer {
+            pointer: "/error/code".into(),
+            value: json!("overloaded"),
+        };
+        let m = CompiledBodyMatcher::new(&m).unwrap();
+        assert!(m.matches(br#"{"error":{"code":"overloaded"}}"#));
+        assert!(!m.matches(br#"{"error":{"code":"invalid"}}"#));
+        assert!(!m.matches(br#"{"data":[]}"#));
+        assert!(!m.matches(b"not json"));
+    }
+
+    #[test]
+    fn regex_matches_utf8_body() {
+        let m = BodyMatcher::Regex {
+            pattern: r#""status"\s*:\s*"retry""#.into(),
+        };
+        let m = CompiledBodyMatcher::new(&m).unwrap();
+        assert!(m.matches(br#"{"status": "retry"}"#));
+        assert!(!m.matches(br#"{"status": "ok"}"#));
+        assert!(!m.matches(&[0xff, 0xfe]));
+    }
+
+    #[test]
+    fn invalid_regex_is_ignored() {
+        let m = BodyMatcher::Regex {
+            pattern: "(".into(),
+        };
+        assert!(CompiledBodyMatcher::new(&m).is_none());
+    }
+
+    #[test]
+    fn only_idempotent_methods_retry() {
+        assert!(is_idempotent(&Method::GET));
+        assert!(is_idempotent(&Method::PUT));
+        assert!(!is_idempotent(&Method::POST));
+        assert!(!is_idempotent(&Method::PATCH));
+    }
+}
@@ -0,0 +1,150 @@
+//! Per-route `body_rewrite`: find/replace rules applied to buffered
+//! response bodies (e.g. replacing internal URLs with the public host).
+
+use std::borrow::Cow;
+
+use http::HeaderMap;
+use regex::Regex;
+
+use super::headers;
+use crate::domain::model::BodyRewriteRule;
+
+/// Rules among `rules` that apply to a response with these headers.
+///
+/// Compressed responses are never rewritten. A rule without
+/// `content_types` applies to textual media types only.
+pub(crate) fn applicable<'a>(
+    rules: &'a [BodyRewriteRule],
+    resp_headers: &HeaderMap,
+) -> Vec<&'a BodyRewriteRule> {
+    let encoded = resp_headers
+        .get(http::header::CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| !v.trim().eq_ignore_ascii_case("identity"));
+    if encoded {
+        return Vec::new();
+    }
+    let Some(media_type) = resp_headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(';').next().unwrap_or_default().trim())
+    else {
+        return Vec::new();
+    };
+    rules
+        .iter()
+        .filter(|rule| {
+            if rule.content_types.is_empty() {
+                is_textual(media_type)
+            } else {
+                headers::content_type_allowed(resp_headers, &rule.content_types, false)
+            }
+        })
+        .collect()
+}
+
+/// Apply `rules` in order. Returns `None` when the body is not UTF-8 or no
+/// rule changed it.
+pub(crate) fn rewrite(body: &[u8], rules: &[&BodyRewriteRule]) -> Option<String> {
+    let original = std::str::from_utf8(body).ok()?;
+    let mut text = Cow::Borrowed(original);
+    for rule in rules {
+        if rule.regex {
+            // The control plane rejects invalid patterns; this only guards
+            // against stale stored config.
+            let Ok(re) = Regex::new(&rule.find) else {
+                tracing::warn!(pattern = %rule.find, "invalid body_rewrite pattern, ignoring");
+                continue;
+            };
+            if let Cow::Owned(s) = re.replace_all(&text, rule.replace.as_str()) {
+                text = Cow::Owned(s);
+            }
+        } else if text.contains(rule.find.as_str()) {
+            text = Cow::Owned(text.replace(rule.find.as_str(), &rule.replace));
+        }
+    }
+    match text {
+        Cow::Owned(s) if s != original => Some(s),
+        _ => None,
+    }
+}
+
+/// Text-like media types: `text/*` (except event streams), JSON, XML, and
+/// JavaScript, including `+json`/`+xml` suffixes.
+fn is_textual(media_type: &str) -> bool {
+    let m = media_type.to_ascii_lowercase();
+    if m == "text/event-stream" {
+        return false;
+    }
+    m.starts_with("text/")
+        || m.ends_with("+json")
+        || m.ends_with("+xml")
+        || matches!(
+            m.as_str(),
+            "application/json" | "application/xml" | "application/javascript"
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(find: &str, replace: &str, regex: bool) -> BodyRewriteRule {
+        BodyRewriteRule {
+            find: find.into(),
+            replace: replace.into(),
+            regex,
+            content_types: vec![],
+        }
+    }
+
+    fn with_type(content_type: &str) -> HeaderMap {
+        let mut h = HeaderMap::new();
+        h.insert(http::header::CONTENT_TYPE, content_type.parse().unwrap());
+        h
+    }
+
+    #[test]
+    fn default_rules_apply_to_textual_types_only() {
+        let rules = [rule("a", "b", false)];
+        for t in [
+            "application/json; charset=utf-8",
+            "text/html",
+            "application/problem+json",
+        ] {
+            assert_eq!(applicable(&rules, &with_type(t)).len(), 1, "{t}");
+        }
+        for t in ["application/octet-stream", "image/png", "text/event-stream"] {
+            assert!(applicable(&rules, &with_type(t)).is_empty(), "{t}");
+        }
+        assert!(applicable(&rules, &HeaderMap::new()).is_empty());
+
+        let mut gzipped = with_type("application/json");
+        gzipped.insert(http::header::CONTENT_ENCODING, "gzip".parse().unwrap());
+        assert!(applicable(&rules, &gzipped).is_empty());
+    }
+
+    #[test]
+    fn explicit_content_types_replace_the_default() {
+        let rules = [BodyRewriteRule {
+            content_types: vec!["application/vnd.acme".into()],
+            ..rule("a", "b", false)
+        }];
+        assert_eq!(
+            applicable(&rules, &with_type("application/vnd.acme")).len(),
+            1
+        );
+        assert!(applicable(&rules, &with_type("text/html")).is_empty());
+    }
+
+    #[test]
+    fn unchanged_or_non_utf8_bodies_yield_none() {
+        let r = rule("internal", "public", false);
+        assert_eq!(rewrite(b"no match", &[&r]), None);
+        assert_eq!(rewrite(&[0xff, 0xfe], &[&r]), None);
+        assert_eq!(
+            rewrite(b"internal.host", &[&r]).as_deref(),
+            Some("public.host")
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Per-route `body_transformers`: pluggable [`BodyTransformer`]s, looked up
+//! by name in the `ClientHub`, applied to buffered request and response
+//! bodies.
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use http::HeaderMap;
+use oagw_sdk::BodyTransformer;
+
+use super::headers;
+
+/// Whether `transformer` handles a body with these headers. Compressed
+/// bodies are never transformed.
+pub(crate) fn applies(transformer: &dyn BodyTransformer, headers: &HeaderMap) -> bool {
+    let encoded = headers
+        .get(http::header::CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| !v.trim().eq_ignore_ascii_case("identity"));
+    let content_types = transformer.content_types();
+    !encoded
+        && !content_types.is_empty()
+        && headers::content_type_allowed(headers, &content_types, false)
+}
+
+/// Run the transformers that handle `headers`' content type over `body`, in
+/// order.
+pub(crate) fn apply(
+    transformers: &[Arc<dyn BodyTransformer>],
+    headers: &HeaderMap,
+    body: Bytes,
+) -> Bytes {
+    transformers
+        .iter()
+        .filter(|t| applies(t.as_ref(), headers))
+        .fold(body, |body, t| t.transform(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    struct Upper(Vec<String>);
+
+    impl BodyTransformer for Upper {
+        fn content_types(&self) -> Vec<String> {
+            self.0.clone()
+        }
+
+        fn transform(&self, body: Bytes) -> Bytes {
+            Bytes::from(body.to_ascii_uppercase())
+        }
+    }
+
+    struct Suffix;
+
+    impl BodyTransformer for Suffix {
+        fn content_types(&self) -> Vec<String> {
+            vec!["text/plain".into()]
+        }
+
+        fn transform(&self, body: Bytes) -> Bytes {
+            let mut out = body.to_vec();
+            out.extend_from_slice(b"!");
+            Bytes::from(out)
+        }
+    }
+
+    fn headers(content_type: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_str(content_type).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn applies_matches_media_type_only() {
+        let json = Upper(vec!["application/json".into()]);
+        assert!(applies(&json, &headers("Application/JSON; charset=utf-8")));
+        assert!(!applies(&json, &headers("text/plain")));
+        assert!(!applies(&json, &HeaderMap::new()));
+        assert!(!applies(&Upper(vec![]), &headers("application/json")));
+
+        let mut gzipped = headers("application/json");
+        gzipped.insert(
+            http::header::CONTENT_ENCODING,
+            HeaderValue::from_static("gzip"),
+        );
+        assert!(!applies(&json, &gzipped));
+    }
+
+    #[test]
+    fn apply_runs_matching_transformers_in_order() {
+        let transformers: Vec<Arc<dyn BodyTransformer>> = vec![
+            Arc::new(Upper(vec!["text/plain".into()])),
+            Arc::new(Suffix),
+            Arc::new(Upper(vec!["application/json".into()])),
+        ];
+        let out = apply(&transformers, &headers("text/plain"), Bytes::from("hi"));
+        assert_eq!(out, Bytes::from("HI!"));
+        let out = apply(&transformers, &headers("text/html"), Bytes::from("hi"));
+        assert_eq!(out, Bytes::from("hi"));
+    }
+}
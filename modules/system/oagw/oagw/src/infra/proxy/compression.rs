@@ -0,0 +1,135 @@
+//! Gzip compression of buffered responses for clients that accept it.
+
+use std::io::Write;
+
+use bytes::Bytes;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use http::{HeaderMap, HeaderValue};
+use oagw_sdk::body::Body;
+
+/// Whether `Accept-Encoding` admits gzip: listed (or `*`) with a non-zero
+/// quality.
+pub(crate) fn accepts_gzip(req_headers: &HeaderMap) -> bool {
+    req_headers
+        .get_all(http::header::ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|item| {
+            let mut parts = item.split(';');
+            let coding = parts.next().unwrap_or_default().trim();
+            let quality = parts
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (coding.eq_ignore_ascii_case("gzip") || coding == "*") && quality > 0.0
+        })
+}
+
+/// Whether `response` is worth gzipping: not already encoded, not an event
+/// stream, and a body of at least `min_size` bytes (buffered, or declared by
+/// `Content-Length`).
+pub(crate) fn compressible(response: &http::Response<Body>, min_size: usize) -> bool {
+    let headers = response.headers();
+    let encoded = headers
+        .get(http::header::CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| !v.trim().eq_ignore_ascii_case("identity"));
+    let event_stream = headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.trim_start().starts_with("text/event-stream"));
+    let len = match response.body() {
+        Body::Bytes(b) => Some(b.len()),
+        Body::Empty => Some(0),
+        Body::Stream(_) => headers
+            .get(http::header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<usize>().ok()),
+    };
+    !encoded && !event_stream && len.is_some_and(|len| len >= min_size)
+}
+
+/// Rebuild a response from `parts` with `raw` gzipped, fixing up
+/// `Content-Encoding`, `Content-Length` and `Vary`. Falls back to the
+/// identity body if compression fails.
+pub(crate) fn gzip_response(mut parts: http::response::Parts, raw: Bytes) -> http::Response<Body> {
+    let compressed = match gzip(&raw) {
+        Ok(compressed) => compressed,
+        Err(e) => {
+            tracing::warn!(error = %e, "gzip compression failed, sending identity");
+            return http::Response::from_parts(parts, Body::Bytes(raw));
+        }
+    };
+    parts.headers.insert(
+        http::header::CONTENT_ENCODING,
+        HeaderValue::from_static("gzip"),
+    );
+    parts.headers.insert(
+        http::header::CONTENT_LENGTH,
+        HeaderValue::from(compressed.len()),
+    );
+    parts.headers.append(
+        http::header::VARY,
+        HeaderValue::from_static("accept-encoding"),
+    );
+    http::Response::from_parts(parts, Body::Bytes(compressed))
+}
+
+fn gzip(raw: &[u8]) -> std::io::Result<Bytes> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(raw)?;
+    Ok(Bytes::from(encoder.finish()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accept(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::ACCEPT_ENCODING,
+            HeaderValue::from_str(value).unwrap(),
+        );
+        headers
+    }
+
+    fn response(body: Vec<u8>) -> http::Response<Body> {
+        http::Response::builder()
+            .header(http::header::CONTENT_LENGTH, body.len())
+            .body(Body::Bytes(Bytes::from(body)))
+            .unwrap()
+    }
+
+    #[test]
+    fn accept_encoding_parsing() {
+        assert!(accepts_gzip(&accept("gzip")));
+        assert!(accepts_gzip(&accept("br, GZIP;q=0.5")));
+        assert!(accepts_gzip(&accept("*")));
+        assert!(!accepts_gzip(&accept("gzip;q=0")));
+        assert!(!accepts_gzip(&accept("br, deflate")));
+        assert!(!accepts_gzip(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn only_large_identity_bodies_are_compressible() {
+        assert!(compressible(&response(vec![b'a'; 2048]), 1024));
+        assert!(!compressible(&response(b"tiny".to_vec()), 1024));
+
+        let mut encoded = response(vec![b'a'; 2048]);
+        encoded.headers_mut().insert(
+            http::header::CONTENT_ENCODING,
+            HeaderValue::from_static("br"),
+        );
+        assert!(!compressible(&encoded, 1024));
+
+        let mut events = response(vec![b'a'; 2048]);
+        events.headers_mut().insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("text/event-stream"),
+        );
+        assert!(!compressible(&events, 1024));
+    }
+}
@@ -0,0 +1,366 @@
+//! Cached resolution of upstream hostnames.
+//!
+//! Every upstream connection and every endpoint discovery cycle resolves
+//! through one [`DnsCache`], so pinned (`dns_override`) addresses apply
+//! everywhere and any check on resolved addresses (e.g. an SSRF deny-list)
+//! sees the address that is actually dialled.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use tracing::{debug, warn};
+
+use crate::domain::clock::{Clock, SystemClock};
+use crate::infra::metrics::OagwMetrics;
+
+/// Default lifetime of a cached resolution.
+pub(crate) const DEFAULT_DNS_CACHE_TTL: Duration = Duration::from_secs(30);
+
+struct CachedAddrs {
+    addrs: Arc<[IpAddr]>,
+    resolved_at: Instant,
+    /// Set on every cache hit; cleared by each refresh cycle so that hosts
+    /// no longer in use are dropped instead of being refreshed forever.
+    used: AtomicBool,
+}
+
+/// Hostname lookup behind a [`DnsCache`].
+#[async_trait]
+pub trait HostResolver: Send + Sync {
+    /// Addresses for `host`.
+    ///
+    /// # Errors
+    /// Returns the resolver error, or `NotFound` when the host has no
+    /// addresses.
+    async fn lookup(&self, host: &str) -> io::Result<Arc<[IpAddr]>>;
+}
+
+/// The operating system resolver.
+pub struct SystemResolver;
+
+#[async_trait]
+impl HostResolver for SystemResolver {
+    async fn lookup(&self, host: &str) -> io::Result<Arc<[IpAddr]>> {
+        let addrs: Arc<[IpAddr]> = tokio::net::lookup_host((host, 0))
+            .await?
+            .map(|sock| sock.ip())
+            .collect();
+        if addrs.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no addresses for {host}"),
+            ));
+        }
+        Ok(addrs)
+    }
+}
+
+/// Hostname → IP address cache with a fixed TTL and optional pinned
+/// addresses. A zero TTL disables caching; pinned addresses always apply.
+pub struct DnsCache {
+    ttl: Duration,
+    /// Lower-cased hostname → pinned address, never resolved.
+    overrides: HashMap<String, IpAddr>,
+    entries: DashMap<String, CachedAddrs>,
+    resolver: Arc<dyn HostResolver>,
+    clock: Arc<dyn Clock>,
+    metrics: OagwMetrics,
+}
+
+impl DnsCache {
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            overrides: HashMap::new(),
+            entries: DashMap::new(),
+            resolver: Arc::new(SystemResolver),
+            clock: Arc::new(SystemClock),
+            metrics: OagwMetrics::new(&opentelemetry::global::meter("oagw")),
+        }
+    }
+
+    /// Record lookups and the number of cached hosts on `metrics`.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: OagwMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Age entries by `clock` instead of the system clock.
+    #[cfg(test)]
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Look hosts up through `resolver` instead of the system resolver.
+    #[cfg(test)]
+    #[must_use]
+    pub fn with_resolver(mut self, resolver: Arc<dyn HostResolver>) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Pin hostnames to fixed addresses, bypassing DNS entirely.
+    #[must_use]
+    pub fn with_overrides(mut self, overrides: HashMap<String, IpAddr>) -> Self {
+        self.overrides = overrides
+            .into_iter()
+            .map(|(host, ip)| (host.to_ascii_lowercase(), ip))
+            .collect();
+        self
+    }
+
+    /// Resolve `host` to socket addresses on `port`. IP literals and pinned
+    /// hosts are answered without DNS; other hosts are served from the cache
+    /// while their entry is younger than the TTL.
+    ///
+    /// # Errors
+    /// Returns the resolver error, or `NotFound` when the host has no
+    /// addresses.
+    pub async fn resolve(&self, host: &str, port: u16) -> io::Result<Vec<SocketAddr>> {
+        let host = host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_ascii_lowercase();
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Ok(vec![SocketAddr::new(ip, port)]);
+        }
+        if let Some(ip) = self.overrides.get(&host) {
+            return Ok(vec![SocketAddr::new(*ip, port)]);
+        }
+
+        let cached = self.cached(&host);
+        self.metrics.record_cache_lookup("dns", cached.is_some());
+        let addrs = match cached {
+            Some(addrs) => addrs,
+            None => {
+                let addrs = self.resolver.lookup(&host).await?;
+                if !self.ttl.is_zero() {
+                    self.store(host, addrs.clone());
+                }
+                addrs
+            }
+        };
+        Ok(addrs.iter().map(|ip| SocketAddr::new(*ip, port)).collect())
+    }
+
+    /// Start re-resolving cached hosts every TTL in the background, so that
+    /// requests rarely wait on DNS. Hosts not used since the previous cycle
+    /// are evicted. The task ends once the cache is dropped.
+    pub fn spawn_refresh(self: &Arc<Self>) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let cache = Arc::downgrade(self);
+        let period = self.ttl;
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let Some(cache) = cache.upgrade() else {
+                    break;
+                };
+                cache.refresh().await;
+            }
+        });
+    }
+
+    fn cached(&self, host: &str) -> Option<Arc<[IpAddr]>> {
+        let entry = self.entries.get(host)?;
+        if self
+            .clock
+            .instant()
+            .saturating_duration_since(entry.resolved_at)
+            >= self.ttl
+        {
+            return None;
+        }
+        entry.used.store(true, Ordering::Relaxed);
+        Some(entry.addrs.clone())
+    }
+
+    fn store(&self, host: String, addrs: Arc<[IpAddr]>) {
+        self.entries.insert(
+            host,
+            CachedAddrs {
+                addrs,
+                resolved_at: self.clock.instant(),
+                used: AtomicBool::new(true),
+            },
+        );
+        self.metrics.record_cache_size("dns", self.entries.len());
+    }
+
+    /// One refresh cycle. A failed lookup keeps the previous addresses until
+    /// they expire.
+    async fn refresh(&self) {
+        self.entries
+            .retain(|_, entry| entry.used.swap(false, Ordering::Relaxed));
+        self.metrics.record_cache_size("dns", self.entries.len());
+        let hosts: Vec<String> = self.entries.iter().map(|e| e.key().clone()).collect();
+        for host in hosts {
+            match self.resolver.lookup(&host).await {
+                Ok(addrs) => {
+                    if let Some(mut entry) = self.entries.get_mut(&host) {
+                        entry.addrs = addrs;
+                        entry.resolved_at = self.clock.instant();
+                    }
+                }
+                Err(e) => warn!(host = %host, error = %e, "DNS refresh failed"),
+            }
+        }
+        debug!(hosts = self.entries.len(), "DNS cache refreshed");
+    }
+}
+
+impl Default for DnsCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_DNS_CACHE_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn ip_literals_bypass_dns() {
+        let cache = DnsCache::new(Duration::from_secs(30));
+        let addrs = cache.resolve("127.0.0.1", 8080).await.unwrap();
+        assert_eq!(addrs, vec!["127.0.0.1:8080".parse().unwrap()]);
+        let addrs = cache.resolve("[::1]", 443).await.unwrap();
+        assert_eq!(addrs, vec!["[::1]:443".parse().unwrap()]);
+        assert!(cache.entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn override_pins_resolution() {
+        let cache = DnsCache::new(Duration::from_secs(30)).with_overrides(HashMap::from([(
+            "API.Example.invalid".into(),
+            "10.1.2.3".parse().unwrap(),
+        )]));
+        let addrs = cache.resolve("api.example.invalid", 443).await.unwrap();
+        assert_eq!(addrs, vec!["10.1.2.3:443".parse().unwrap()]);
+    }
+
+    #[tokio::test]
+    async fn cached_resolution_is_reused_within_ttl() {
+        let cache = DnsCache::new(Duration::from_secs(30));
+        let first = cache.resolve("localhost", 80).await.unwrap();
+        let resolved_at = cache.entries.get("localhost").unwrap().resolved_at;
+
+        let second = cache.resolve("localhost", 8080).await.unwrap();
+
+        assert_eq!(
+            first.iter().map(SocketAddr::ip).collect::<Vec<_>>(),
+            second.iter().map(SocketAddr::ip).collect::<Vec<_>>()
+        );
+        assert!(second.iter().all(|a| a.port() == 8080));
+        assert_eq!(
+            cache.entries.get("localhost").unwrap().resolved_at,
+            resolved_at
+        );
+    }
+
+    #[tokio::test]
+    async fn expired_entry_is_resolved_again() {
+        let cache = DnsCache::new(Duration::from_secs(30));
+        let stale: Arc<[IpAddr]> = Arc::from(vec!["192.0.2.1".parse::<IpAddr>().unwrap()]);
+        cache.entries.insert(
+            "localhost".into(),
+            CachedAddrs {
+                addrs: stale,
+                resolved_at: Instant::now() - Duration::from_secs(31),
+                used: AtomicBool::new(true),
+            },
+        );
+
+        let addrs = cache.resolve("localhost", 80).await.unwrap();
+
+        assert!(addrs.iter().all(|a| a.ip().is_loopback()));
+    }
+
+    /// Resolver answering loopback and counting its lookups.
+    #[derive(Default)]
+    struct CountingResolver(std::sync::atomic::AtomicUsize);
+
+    #[async_trait]
+    impl HostResolver for CountingResolver {
+        async fn lookup(&self, _host: &str) -> io::Result<Arc<[IpAddr]>> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(Arc::from(vec![IpAddr::from([127, 0, 0, 1])]))
+        }
+    }
+
+    #[tokio::test]
+    async fn entry_expires_when_clock_passes_ttl() {
+        let clock = crate::domain::test_support::MockClock::default();
+        let resolver = Arc::new(CountingResolver::default());
+        let cache = DnsCache::new(Duration::from_secs(30))
+            .with_resolver(resolver.clone())
+            .with_clock(Arc::new(clock.clone()));
+
+        cache.resolve("api.example.invalid", 443).await.unwrap();
+        clock.advance(Duration::from_secs(29));
+        cache.resolve("api.example.invalid", 443).await.unwrap();
+        assert_eq!(resolver.0.load(Ordering::SeqCst), 1);
+
+        clock.advance(Duration::from_secs(1));
+        cache.resolve("api.example.invalid", 443).await.unwrap();
+        assert_eq!(resolver.0.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn zero_ttl_disables_caching() {
+        let cache = DnsCache::new(Duration::ZERO);
+        cache.resolve("localhost", 80).await.unwrap();
+        assert!(cache.entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn refresh_evicts_unused_hosts() {
+        let cache = DnsCache::new(Duration::from_secs(30));
+        cache.resolve("localhost", 80).await.unwrap();
+
+        cache.refresh().await;
+        assert!(cache.entries.contains_key("localhost"));
+
+        cache.refresh().await;
+        assert!(cache.entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn lookups_and_size_are_recorded() {
+        use crate::domain::test_support::RecordingMetricsSink;
+        use crate::infra::metrics::{CACHE_LOOKUPS, CACHE_SIZE};
+
+        let sink = Arc::new(RecordingMetricsSink::default());
+        let cache = DnsCache::new(Duration::from_secs(30))
+            .with_metrics(OagwMetrics::with_sink(sink.clone()));
+        cache.resolve("127.0.0.1", 80).await.unwrap();
+        cache.resolve("localhost", 80).await.unwrap();
+        cache.resolve("localhost", 443).await.unwrap();
+
+        let dns = ("cache", "dns");
+        assert_eq!(
+            sink.values(CACHE_LOOKUPS, &[dns, ("result", "miss")]),
+            [1.0]
+        );
+        assert_eq!(sink.values(CACHE_LOOKUPS, &[dns, ("result", "hit")]), [1.0]);
+        assert_eq!(sink.values(CACHE_SIZE, &[dns]), [1.0]);
+
+        cache.refresh().await;
+        cache.refresh().await;
+        assert_eq!(sink.values(CACHE_SIZE, &[dns]), [1.0, 1.0, 0.0]);
+    }
+}
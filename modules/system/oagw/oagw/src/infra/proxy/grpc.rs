@@ -0,0 +1,59 @@
+//! gRPC passthrough helpers.
+//!
+//! The Pingora bridge hands responses back to the data plane over an HTTP/1.1
+//! session, which cannot carry HTTP/2 trailers. For gRPC upstreams the
+//! trailers (`grpc-status`, `grpc-message`, ...) are therefore appended to the
+//! response body as a final length-prefixed frame with the trailer flag set,
+//! using the gRPC-Web wire encoding.
+
+use bytes::{BufMut, Bytes, BytesMut};
+use http::HeaderMap;
+
+/// Flag byte marking a length-prefixed frame as a trailer frame (gRPC-Web).
+pub(crate) const TRAILER_FRAME_FLAG: u8 = 0x80;
+
+/// Encode upstream trailers as a gRPC-Web trailer frame:
+/// `0x80 | u32 BE length | "name: value\r\n"*`.
+pub(crate) fn encode_trailer_frame(trailers: &HeaderMap) -> Bytes {
+    let mut block = Vec::new();
+    for (name, value) in trailers {
+        block.extend_from_slice(name.as_str().as_bytes());
+        block.extend_from_slice(b": ");
+        block.extend_from_slice(value.as_bytes());
+        block.extend_from_slice(b"\r\n");
+    }
+
+    let mut frame = BytesMut::with_capacity(5 + block.len());
+    frame.put_u8(TRAILER_FRAME_FLAG);
+    frame.put_u32(u32::try_from(block.len()).unwrap_or(u32::MAX));
+    frame.extend_from_slice(&block);
+    frame.freeze()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    #[test]
+    fn trailer_frame_has_flag_length_and_lines() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("0"));
+        trailers.insert("grpc-message", HeaderValue::from_static("ok"));
+
+        let frame = encode_trailer_frame(&trailers);
+
+        assert_eq!(frame[0], TRAILER_FRAME_FLAG);
+        let len = u32::from_be_bytes([frame[1], frame[2], frame[3], frame[4]]) as usize;
+        assert_eq!(len, frame.len() - 5);
+        let block = std::str::from_utf8(&frame[5..]).unwrap();
+        assert!(block.contains("grpc-status: 0\r\n"));
+        assert!(block.contains("grpc-message: ok\r\n"));
+    }
+
+    #[test]
+    fn empty_trailers_encode_to_header_only_frame() {
+        let frame = encode_trailer_frame(&HeaderMap::new());
+        assert_eq!(&frame[..], &[TRAILER_FRAME_FLAG, 0, 0, 0, 0]);
+    }
+}
@@ -1,9 +1,18 @@
+use crate::domain::header_template;
 use crate::domain::model::{PassthroughMode, RequestHeaderRules};
+use std::borrow::Cow;
+use std::net::IpAddr;
+
 use http::{HeaderMap, HeaderName, HeaderValue};
+use modkit_security::SecurityContext;
 use oagw_sdk::api::ErrorSource;
 
 const HOP_BY_HOP_HEADERS: &[&str] = &[
     "connection",
+    // The gateway answers `Expect: 100-continue` itself: the client is told
+    // to continue once the request body is first read, which happens only
+    // after the request has passed every check that could reject it.
+    "expect",
     "keep-alive",
     "proxy-authenticate",
     "proxy-authorization",
@@ -35,10 +44,11 @@ pub fn apply_passthrough(
         PassthroughMode::Allowlist => {
             let mut h = HeaderMap::new();
             for name in allowlist {
-                if let Ok(n) = HeaderName::from_bytes(name.to_lowercase().as_bytes())
-                    && let Some(v) = inbound.get(&n)
-                {
-                    h.insert(n, v.clone());
+                if let Ok(n) = HeaderName::from_bytes(name.to_lowercase().as_bytes()) {
+                    h.remove(&n);
+                    for v in inbound.get_all(&n) {
+                        h.append(n.clone(), v.clone());
+                    }
                 }
             }
             h
@@ -85,6 +95,44 @@ pub fn strip_hop_by_hop(headers: &mut HeaderMap) {
     }
 }
 
+/// Remove every header in `names`, e.g. the operator's extra hop-by-hop set.
+pub fn strip_headers(headers: &mut HeaderMap, names: &[HeaderName]) {
+    for name in names {
+        headers.remove(name);
+    }
+}
+
+/// Headers that frame or encode a response body. A response header
+/// allowlist never removes them, or the client could not read the body.
+const BODY_FRAMING_HEADERS: &[&str] = &[
+    "content-length",
+    "transfer-encoding",
+    "content-encoding",
+    "trailer",
+];
+
+/// Keep only the response headers named in `allowlist` (case-insensitive),
+/// body framing headers, `Vary`, and `x-oagw-*` headers. `Vary` is kept so
+/// that no cache, the gateway's included, serves one variant for another.
+/// Upstream `x-oagw-*` headers are sanitized earlier, so the ones left were
+/// set by the gateway.
+pub fn retain_allowlisted(headers: &mut HeaderMap, allowlist: &[String]) {
+    let dropped: Vec<HeaderName> = headers
+        .keys()
+        .filter(|name| {
+            let name = name.as_str();
+            !BODY_FRAMING_HEADERS.contains(&name)
+                && name != "vary"
+                && !name.starts_with("x-oagw-")
+                && !allowlist.iter().any(|a| a.eq_ignore_ascii_case(name))
+        })
+        .cloned()
+        .collect();
+    for name in dropped {
+        headers.remove(name);
+    }
+}
+
 /// Remove X-OAGW-* internal headers.
 pub fn strip_internal_headers(headers: &mut HeaderMap) {
     let to_remove: Vec<HeaderName> = headers
@@ -120,8 +168,13 @@ pub fn sanitize_response_headers(headers: &mut HeaderMap) {
     strip_internal_headers(headers);
 }
 
-/// Apply set/add/remove header rules from upstream config.
-pub fn apply_header_rules(headers: &mut HeaderMap, rules: &RequestHeaderRules) {
+/// Apply set/add/remove header rules from upstream config. `{{ctx.<field>}}`
+/// placeholders in set/add values are filled from `ctx`.
+pub fn apply_header_rules(
+    headers: &mut HeaderMap,
+    rules: &RequestHeaderRules,
+    ctx: &SecurityContext,
+) {
     // Remove first.
     for name in &rules.remove {
         if let Ok(n) = HeaderName::from_bytes(name.to_lowercase().as_bytes()) {
@@ -132,7 +185,7 @@ pub fn apply_header_rules(headers: &mut HeaderMap, rules: &RequestHeaderRules) {
     for (name, value) in &rules.set {
         if let (Ok(n), Ok(v)) = (
             HeaderName::from_bytes(name.to_lowercase().as_bytes()),
-            HeaderValue::from_str(value),
+            HeaderValue::from_str(&unfold(&header_template::render(value, ctx))),
         ) {
             headers.insert(n, v);
         }
@@ -141,13 +194,47 @@ pub fn apply_header_rules(headers: &mut HeaderMap, rules: &RequestHeaderRules) {
     for (name, value) in &rules.add {
         if let (Ok(n), Ok(v)) = (
             HeaderName::from_bytes(name.to_lowercase().as_bytes()),
-            HeaderValue::from_str(value),
+            HeaderValue::from_str(&unfold(&header_template::render(value, ctx))),
         ) {
             headers.append(n, v);
         }
     }
 }
 
+/// Replace each obsolete line folding in a header value (a line break
+/// followed by spaces or tabs) with a single space. A folded value is not a
+/// valid header value and would otherwise be dropped. Line breaks that do
+/// not start a fold are kept, so such values stay invalid.
+pub fn unfold(value: &str) -> Cow<'_, str> {
+    if !value.contains('\n') {
+        return Cow::Borrowed(value);
+    }
+    let mut unfolded = String::with_capacity(value.len());
+    for (i, line) in value.split('\n').enumerate() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if i == 0 {
+            unfolded.push_str(line);
+        } else if line.starts_with([' ', '\t']) {
+            unfolded.truncate(unfolded.trim_end_matches([' ', '\t']).len());
+            unfolded.push(' ');
+            unfolded.push_str(line.trim_start_matches([' ', '\t']));
+        } else {
+            unfolded.push_str("\r\n");
+            unfolded.push_str(line);
+        }
+    }
+    Cow::Owned(unfolded)
+}
+
+/// The first header whose name and value together are longer than `max`
+/// bytes, with that length.
+pub fn oversized_header(headers: &HeaderMap, max: u64) -> Option<(&HeaderName, u64)> {
+    headers.iter().find_map(|(name, value)| {
+        let size = u64::try_from(name.as_str().len() + value.len()).unwrap_or(u64::MAX);
+        (size > max).then_some((name, size))
+    })
+}
+
 /// Set the Host header to match the upstream endpoint.
 pub fn set_host_header(headers: &mut HeaderMap, host: &str, port: u16) {
     let host_value = if port == 443 || port == 80 {
@@ -160,12 +247,210 @@ pub fn set_host_header(headers: &mut HeaderMap, host: &str, port: u16) {
     }
 }
 
+/// Set `X-Forwarded-For`, `-Proto` and `-Host` on `outbound` for a request
+/// received from `peer`.
+///
+/// Inbound `X-Forwarded-*` values are only kept when `peer` is one of
+/// `trusted_proxies`. `X-Forwarded-For` is then trimmed to start at the
+/// rightmost address that is not a trusted proxy, the client as seen by the
+/// first trusted hop; anything left of it was supplied by the client and
+/// cannot be trusted. Otherwise the headers start fresh from `peer`.
+pub fn set_forwarded_headers(
+    inbound: &HeaderMap,
+    outbound: &mut HeaderMap,
+    peer: IpAddr,
+    trusted_proxies: &[IpAddr],
+) {
+    let peer = peer.to_canonical();
+    let trusted_peer = trusted_proxies.contains(&peer);
+
+    let mut chain = Vec::new();
+    if trusted_peer {
+        let hops: Vec<&str> = inbound
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(str::trim)
+            .filter(|hop| !hop.is_empty())
+            .collect();
+        for hop in hops.into_iter().rev() {
+            let Ok(ip) = hop.parse::<IpAddr>() else {
+                break;
+            };
+            chain.push(ip);
+            if !trusted_proxies.contains(&ip) {
+                break;
+            }
+        }
+        chain.reverse();
+    }
+    chain.push(peer);
+    let chain = chain
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    if let Ok(v) = HeaderValue::from_str(&chain) {
+        outbound.insert("x-forwarded-for", v);
+    }
+
+    let inherited = |name: &str| inbound.get(name).filter(|_| trusted_peer).cloned();
+    outbound.insert(
+        "x-forwarded-proto",
+        inherited("x-forwarded-proto").unwrap_or(HeaderValue::from_static("http")),
+    );
+    match inherited("x-forwarded-host").or_else(|| inbound.get(http::header::HOST).cloned()) {
+        Some(host) => {
+            outbound.insert("x-forwarded-host", host);
+        }
+        None => {
+            outbound.remove("x-forwarded-host");
+        }
+    }
+}
+
+/// Whether the `Content-Type` media type is in `allowed`. Parameters such as
+/// `charset` are ignored and matching is case-insensitive. An empty allowlist
+/// accepts anything; a missing header is accepted only if `allow_missing`.
+pub fn content_type_allowed(headers: &HeaderMap, allowed: &[String], allow_missing: bool) -> bool {
+    if allowed.is_empty() {
+        return true;
+    }
+    let Some(value) = headers.get(http::header::CONTENT_TYPE) else {
+        return allow_missing;
+    };
+    let Ok(value) = value.to_str() else {
+        return false;
+    };
+    let media_type = value.split(';').next().unwrap_or_default().trim();
+    allowed
+        .iter()
+        .any(|a| a.trim().eq_ignore_ascii_case(media_type))
+}
+
+/// Set `Content-Type` to `content_type` unless it already names the same
+/// media type, whose parameters are then kept. Returns whether it changed.
+pub fn coerce_content_type(headers: &mut HeaderMap, content_type: &str) -> bool {
+    let media_type = |v: &str| v.split(';').next().unwrap_or_default().trim().to_owned();
+    let current = headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(media_type);
+    if current.is_some_and(|c| c.eq_ignore_ascii_case(&media_type(content_type))) {
+        return false;
+    }
+    let Ok(value) = HeaderValue::from_str(content_type) else {
+        return false;
+    };
+    headers.insert(http::header::CONTENT_TYPE, value);
+    true
+}
+
+/// Whether the `Content-Type` is JSON: `application/json` or any
+/// `+json` structured-syntax suffix.
+pub fn is_json_content_type(headers: &HeaderMap) -> bool {
+    headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .map(|v| v.trim().to_ascii_lowercase())
+        .is_some_and(|v| v == "application/json" || v.ends_with("+json"))
+}
+
+/// Whether the request asks to upgrade the connection to WebSocket.
+pub fn is_websocket_upgrade(headers: &HeaderMap) -> bool {
+    headers
+        .get(http::header::UPGRADE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            v.split(',')
+                .any(|t| t.trim().eq_ignore_ascii_case("websocket"))
+        })
+}
+
+/// Copy the client's `Sec-WebSocket-*` handshake headers into `out`; the
+/// upstream cannot accept the upgrade without them, whatever the
+/// passthrough mode.
+pub fn copy_websocket_handshake(inbound: &HeaderMap, out: &mut HeaderMap) {
+    for (name, value) in inbound {
+        if name.as_str().starts_with("sec-websocket-") {
+            out.append(name.clone(), value.clone());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
     use super::*;
 
+    #[test]
+    fn allowlist_keeps_listed_framing_vary_and_gateway_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", "application/json".parse().unwrap());
+        headers.insert("content-length", "2".parse().unwrap());
+        headers.insert("etag", "\"v1\"".parse().unwrap());
+        headers.insert("server", "nginx".parse().unwrap());
+        headers.insert("vary", "accept".parse().unwrap());
+        headers.insert("x-oagw-original-status", "404".parse().unwrap());
+
+        retain_allowlisted(&mut headers, &["Content-Type".into(), "ETag".into()]);
+
+        let mut names: Vec<&str> = headers.keys().map(HeaderName::as_str).collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            [
+                "content-length",
+                "content-type",
+                "etag",
+                "vary",
+                "x-oagw-original-status"
+            ]
+        );
+    }
+
+    #[test]
+    fn unfold_joins_folded_lines_with_a_space() {
+        assert_eq!(unfold("plain"), "plain");
+        assert_eq!(unfold("part one\r\n  part two"), "part one part two");
+        assert_eq!(unfold("a \n\tb\r\n c"), "a b c");
+        // A break without a fold is not repaired.
+        assert!(HeaderValue::from_str(&unfold("a\r\nb")).is_err());
+    }
+
+    #[test]
+    fn oversized_header_measures_name_and_value() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-short", "abc".parse().unwrap());
+        headers.insert("x-long", "0123456789".parse().unwrap());
+        assert!(oversized_header(&headers, 16).is_none());
+        let (name, size) = oversized_header(&headers, 15).unwrap();
+        assert_eq!((name.as_str(), size), ("x-long", 16));
+    }
+
+    #[test]
+    fn websocket_handshake_detected_and_copied() {
+        let mut inbound = HeaderMap::new();
+        inbound.insert("upgrade", "h2c, WebSocket".parse().unwrap());
+        inbound.insert(
+            "sec-websocket-key",
+            "dGhlIHNhbXBsZSBub25jZQ==".parse().unwrap(),
+        );
+        inbound.insert("sec-websocket-version", "13".parse().unwrap());
+        inbound.insert("x-custom", "no".parse().unwrap());
+        assert!(is_websocket_upgrade(&inbound));
+        assert!(!is_websocket_upgrade(&HeaderMap::new()));
+
+        let mut out = HeaderMap::new();
+        copy_websocket_handshake(&inbound, &mut out);
+        let mut names: Vec<&str> = out.keys().map(HeaderName::as_str).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["sec-websocket-key", "sec-websocket-version"]);
+    }
+
     #[test]
     fn hop_by_hop_stripped() {
         let mut headers = HeaderMap::new();
@@ -221,6 +506,18 @@ mod tests {
         assert_eq!(headers.get("x-custom").unwrap(), "keep-me");
     }
 
+    #[test]
+    fn hop_by_hop_strips_expect() {
+        let mut headers = HeaderMap::new();
+        headers.insert("expect", "100-continue".parse().unwrap());
+        headers.insert("x-custom", "keep-me".parse().unwrap());
+
+        strip_hop_by_hop(&mut headers);
+
+        assert!(headers.get("expect").is_none());
+        assert_eq!(headers.get("x-custom").unwrap(), "keep-me");
+    }
+
     #[test]
     fn hop_by_hop_connection_empty_and_invalid_tokens() {
         let mut headers = HeaderMap::new();
@@ -285,7 +582,7 @@ mod tests {
             passthrough_allowlist: vec![],
         };
 
-        apply_header_rules(&mut headers, &rules);
+        apply_header_rules(&mut headers, &rules, &SecurityContext::anonymous());
         assert_eq!(headers.get("x-api-version").unwrap(), "v2");
     }
 
@@ -306,7 +603,7 @@ mod tests {
             passthrough_allowlist: vec![],
         };
 
-        apply_header_rules(&mut headers, &rules);
+        apply_header_rules(&mut headers, &rules, &SecurityContext::anonymous());
         let values: Vec<&str> = headers
             .get_all("x-tag")
             .iter()
@@ -330,7 +627,7 @@ mod tests {
             passthrough_allowlist: vec![],
         };
 
-        apply_header_rules(&mut headers, &rules);
+        apply_header_rules(&mut headers, &rules, &SecurityContext::anonymous());
         assert!(headers.get("x-remove-me").is_none());
         assert_eq!(headers.get("x-keep-me").unwrap(), "stay");
     }
@@ -375,6 +672,18 @@ mod tests {
         assert!(out.get("x-blocked").is_none());
     }
 
+    #[test]
+    fn passthrough_allowlist_keeps_all_values() {
+        let mut inbound = HeaderMap::new();
+        inbound.append("accept", "application/json".parse().unwrap());
+        inbound.append("accept", "text/plain".parse().unwrap());
+
+        let out = apply_passthrough(&inbound, &PassthroughMode::Allowlist, &["accept".into()]);
+
+        let values: Vec<_> = out.get_all("accept").iter().collect();
+        assert_eq!(values, ["application/json", "text/plain"]);
+    }
+
     #[test]
     fn passthrough_all_strips_authorization() {
         let mut inbound = HeaderMap::new();
@@ -444,4 +753,105 @@ mod tests {
         assert!(headers.get("x-oagw-trace-id").is_none());
         assert_eq!(headers.get("x-custom").unwrap(), "keep");
     }
+
+    #[test]
+    fn content_type_allowlist_ignores_parameters_and_case() {
+        let allowed = vec!["application/json".to_string()];
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            "Application/JSON; charset=utf-8".parse().unwrap(),
+        );
+        assert!(content_type_allowed(&headers, &allowed, false));
+
+        headers.insert(http::header::CONTENT_TYPE, "text/plain".parse().unwrap());
+        assert!(!content_type_allowed(&headers, &allowed, false));
+        assert!(content_type_allowed(&headers, &[], false));
+    }
+
+    #[test]
+    fn missing_content_type_follows_allow_missing() {
+        let allowed = vec!["application/json".to_string()];
+        assert!(content_type_allowed(&HeaderMap::new(), &allowed, true));
+        assert!(!content_type_allowed(&HeaderMap::new(), &allowed, false));
+    }
+
+    #[test]
+    fn coerce_content_type_replaces_other_media_types_only() {
+        let mut headers = HeaderMap::new();
+        assert!(coerce_content_type(&mut headers, "application/json"));
+        assert_eq!(headers[http::header::CONTENT_TYPE], "application/json");
+
+        headers.insert(http::header::CONTENT_TYPE, "text/plain".parse().unwrap());
+        assert!(coerce_content_type(&mut headers, "application/json"));
+        assert_eq!(headers[http::header::CONTENT_TYPE], "application/json");
+
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            "Application/JSON; charset=utf-8".parse().unwrap(),
+        );
+        assert!(!coerce_content_type(&mut headers, "application/json"));
+        assert_eq!(
+            headers[http::header::CONTENT_TYPE],
+            "Application/JSON; charset=utf-8"
+        );
+    }
+
+    fn forwarded(inbound: &[(&str, &str)], peer: &str, trusted: &[&str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in inbound {
+            headers.append(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                value.parse().unwrap(),
+            );
+        }
+        let trusted: Vec<IpAddr> = trusted.iter().map(|ip| ip.parse().unwrap()).collect();
+        // Passthrough may have copied the inbound headers already.
+        let mut outbound = headers.clone();
+        set_forwarded_headers(&headers, &mut outbound, peer.parse().unwrap(), &trusted);
+        outbound
+    }
+
+    #[test]
+    fn forwarded_headers_set_fresh_for_direct_client() {
+        let out = forwarded(&[("host", "gw.example.com")], "203.0.113.7", &[]);
+        assert_eq!(out["x-forwarded-for"], "203.0.113.7");
+        assert_eq!(out["x-forwarded-proto"], "http");
+        assert_eq!(out["x-forwarded-host"], "gw.example.com");
+    }
+
+    #[test]
+    fn forwarded_for_from_untrusted_peer_is_replaced() {
+        let out = forwarded(
+            &[
+                ("x-forwarded-for", "1.2.3.4"),
+                ("x-forwarded-proto", "https"),
+                ("x-forwarded-host", "spoofed.example.com"),
+                ("host", "gw.example.com"),
+            ],
+            "203.0.113.7",
+            &["10.0.0.1"],
+        );
+        assert_eq!(out["x-forwarded-for"], "203.0.113.7");
+        assert_eq!(out["x-forwarded-proto"], "http");
+        assert_eq!(out["x-forwarded-host"], "gw.example.com");
+    }
+
+    #[test]
+    fn forwarded_for_from_trusted_proxy_is_appended_and_trimmed() {
+        let out = forwarded(
+            &[
+                ("x-forwarded-for", "6.6.6.6, 198.51.100.9"),
+                ("x-forwarded-for", "10.0.0.2"),
+                ("x-forwarded-proto", "https"),
+                ("x-forwarded-host", "api.example.com"),
+            ],
+            "::ffff:10.0.0.1",
+            &["10.0.0.1", "10.0.0.2"],
+        );
+        // 6.6.6.6 sits left of the client address and is dropped.
+        assert_eq!(out["x-forwarded-for"], "198.51.100.9, 10.0.0.2, 10.0.0.1");
+        assert_eq!(out["x-forwarded-proto"], "https");
+        assert_eq!(out["x-forwarded-host"], "api.example.com");
+    }
 }
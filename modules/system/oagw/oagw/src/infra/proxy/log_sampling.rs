@@ -0,0 +1,266 @@
+//! Sampling of proxied requests for detailed request/response logging.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use http::HeaderMap;
+use oagw_sdk::body::Body;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::infra::metrics::ErrorStatusCodes;
+
+/// Log target of sampled request/response records, so they can be routed or
+/// filtered independently of the proxy's operational logs.
+pub(crate) const REQUEST_LOG_TARGET: &str = "oagw::request_log";
+
+/// Decides which requests get detailed logs, per the route's
+/// `log_sample_rate`.
+pub(crate) struct LogSampler {
+    rng: Mutex<StdRng>,
+}
+
+impl LogSampler {
+    pub(crate) fn new() -> Self {
+        Self {
+            rng: Mutex::new(StdRng::from_os_rng()),
+        }
+    }
+
+    /// Deterministic sampler, for reproducible sampling in tests.
+    pub(crate) fn with_seed(seed: u64) -> Self {
+        Self {
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    /// Whether to log this request in detail. Rates of 0 (or unset) and 1
+    /// are decided without drawing from the RNG.
+    pub(crate) fn sample(&self, rate: Option<f64>) -> bool {
+        match rate {
+            None => false,
+            Some(rate) if rate <= 0.0 => false,
+            Some(rate) if rate >= 1.0 => true,
+            Some(rate) => {
+                let mut rng = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+                rng.random::<f64>() < rate
+            }
+        }
+    }
+}
+
+impl Default for LogSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Stands in for redacted header values and body fields.
+const REDACTED: &str = "[REDACTED]";
+
+/// Headers whose values never appear in sampled logs.
+const REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+];
+
+fn redacted_headers(headers: &HeaderMap) -> String {
+    let fields: Vec<String> = headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if REDACTED_HEADERS.contains(&name.as_str()) {
+                REDACTED
+            } else {
+                value.to_str().unwrap_or("[non-ascii]")
+            };
+            format!("{name}: {value}")
+        })
+        .collect();
+    fields.join(", ")
+}
+
+/// A JSON `body` with the fields at the route's `log_redact_paths` masked.
+/// `None` (body not logged) when the route sets no paths or the body is not
+/// JSON, since only JSON bodies can be redacted.
+fn redacted_body(body: &[u8], paths: &[String]) -> Option<String> {
+    if paths.is_empty() {
+        return None;
+    }
+    let mut value: Value = serde_json::from_slice(body).ok()?;
+    for path in paths {
+        let segments: Vec<String> = path
+            .split('/')
+            .skip(1)
+            .map(|s| s.replace("~1", "/").replace("~0", "~"))
+            .collect();
+        redact_at(&mut value, &segments);
+    }
+    Some(value.to_string())
+}
+
+/// Mask the value at `segments` below `value`; a `*` segment matches every
+/// array element or object member. Missing fields are skipped.
+fn redact_at(value: &mut Value, segments: &[String]) {
+    let Some((first, rest)) = segments.split_first() else {
+        *value = Value::String(REDACTED.into());
+        return;
+    };
+    match value {
+        Value::Object(map) if first == "*" => {
+            map.values_mut().for_each(|v| redact_at(v, rest));
+        }
+        Value::Object(map) => {
+            if let Some(v) = map.get_mut(first) {
+                redact_at(v, rest);
+            }
+        }
+        Value::Array(items) if first == "*" => {
+            items.iter_mut().for_each(|v| redact_at(v, rest));
+        }
+        Value::Array(items) => {
+            if let Some(v) = first.parse().ok().and_then(|i: usize| items.get_mut(i)) {
+                redact_at(v, rest);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Log the inbound request of a sampled route: the size of an already
+/// buffered body, and the body itself when the route sets
+/// `log_redact_paths`.
+pub(crate) fn log_request(
+    route_id: Uuid,
+    method: &http::Method,
+    path: &str,
+    headers: &HeaderMap,
+    body: Option<&[u8]>,
+    redact_paths: &[String],
+) {
+    let logged_body = body.and_then(|b| redacted_body(b, redact_paths));
+    tracing::info!(
+        target: REQUEST_LOG_TARGET,
+        %route_id,
+        %method,
+        path,
+        headers = %redacted_headers(headers),
+        body_len = body.map(<[u8]>::len),
+        body = logged_body.as_deref(),
+        "sampled proxy request"
+    );
+}
+
+/// Log the outcome of a sampled request, with a buffered response body
+/// redacted as for [`log_request`].
+pub(crate) fn log_response(
+    route_id: Uuid,
+    result: &Result<http::Response<Body>, DomainError>,
+    elapsed: Duration,
+    error_statuses: &ErrorStatusCodes,
+    redact_paths: &[String],
+) {
+    let elapsed_ms = elapsed.as_millis();
+    match result {
+        Ok(response) => {
+            let (body_len, logged_body) = match response.body() {
+                Body::Bytes(b) => (Some(b.len()), redacted_body(b, redact_paths)),
+                Body::Empty => (Some(0), None),
+                Body::Stream(_) => (None, None),
+            };
+            tracing::info!(
+                target: REQUEST_LOG_TARGET,
+                %route_id,
+                status = response.status().as_u16(),
+                result = error_statuses.result_label(response.status()),
+                headers = %redacted_headers(response.headers()),
+                body_len,
+                body = logged_body.as_deref(),
+                elapsed_ms,
+                "sampled proxy response"
+            );
+        }
+        Err(e) => tracing::info!(
+            target: REQUEST_LOG_TARGET,
+            %route_id,
+            result = "error",
+            error = %e,
+            elapsed_ms,
+            "sampled proxy response"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draws(sampler: &LogSampler, rate: Option<f64>, n: usize) -> Vec<bool> {
+        (0..n).map(|_| sampler.sample(rate)).collect()
+    }
+
+    #[test]
+    fn zero_or_unset_rate_samples_nothing() {
+        let sampler = LogSampler::new();
+        assert!(draws(&sampler, Some(0.0), 100).iter().all(|s| !s));
+        assert!(draws(&sampler, None, 100).iter().all(|s| !s));
+    }
+
+    #[test]
+    fn full_rate_samples_everything() {
+        let sampler = LogSampler::new();
+        assert!(draws(&sampler, Some(1.0), 100).iter().all(|s| *s));
+    }
+
+    #[test]
+    fn credentials_are_redacted() {
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer secret".parse().unwrap());
+        headers.insert("x-trace", "abc".parse().unwrap());
+
+        let logged = redacted_headers(&headers);
+
+        assert!(!logged.contains("secret"));
+        assert!(logged.contains("authorization: [REDACTED]"));
+        assert!(logged.contains("x-trace: abc"));
+    }
+
+    #[test]
+    fn body_fields_at_redact_paths_are_masked() {
+        let body = br#"{"user":"ann","password":"hunter2","tokens":[{"id":1,"value":"t1"},{"id":2,"value":"t2"}]}"#;
+        let paths = vec!["/password".to_string(), "/tokens/*/value".to_string()];
+
+        let logged: Value = serde_json::from_str(&redacted_body(body, &paths).unwrap()).unwrap();
+
+        assert_eq!(
+            logged,
+            serde_json::json!({
+                "user": "ann",
+                "password": "[REDACTED]",
+                "tokens": [{"id": 1, "value": "[REDACTED]"}, {"id": 2, "value": "[REDACTED]"}],
+            })
+        );
+    }
+
+    #[test]
+    fn bodies_are_not_logged_without_redact_paths_or_json() {
+        assert!(redacted_body(br#"{"password":"hunter2"}"#, &[]).is_none());
+        assert!(redacted_body(b"password=hunter2", &["/password".to_string()]).is_none());
+    }
+
+    #[test]
+    fn seeded_half_rate_samples_expected_subset() {
+        let sampler = LogSampler::with_seed(42);
+        let sampled: Vec<usize> = draws(&sampler, Some(0.5), 16)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.then_some(i))
+            .collect();
+        assert_eq!(sampled, vec![3, 4, 5, 8, 9, 12, 13, 15]);
+    }
+}
@@ -1,10 +1,24 @@
 use authz_resolver_sdk::pep::ResourceType;
 
+pub(crate) mod body_inject;
+pub(crate) mod body_retry;
+pub(crate) mod body_rewrite;
+pub(crate) mod body_transform;
+pub(crate) mod compression;
+pub(crate) mod dns_cache;
+pub(crate) mod grpc;
 pub(crate) mod headers;
+pub(crate) mod log_sampling;
 pub(crate) mod pingora_proxy;
 pub(crate) mod request_builder;
+pub(crate) mod request_schema;
+pub(crate) mod response_cache;
 pub(crate) mod service;
 pub(crate) mod session_bridge;
+pub(crate) mod srv_targets;
+pub(crate) mod stream_idle;
+pub(crate) mod trailers;
+pub(crate) mod transform_hook;
 
 pub(crate) use service::DataPlaneServiceImpl;
 
@@ -19,6 +33,16 @@ pub(crate) mod resources {
     };
 }
 
+/// Resource properties attached to proxy `invoke` evaluations so that
+/// policies can decide per route, upstream, method, and path.
+pub(crate) mod properties {
+    pub const ROUTE_ID: &str = "route_id";
+    pub const UPSTREAM_ID: &str = "upstream_id";
+    pub const METHOD: &str = "method";
+    /// Request path below the upstream alias, after normalization.
+    pub const PATH: &str = "path";
+}
+
 pub(crate) mod actions {
     /// Action name for invoking (proxying a request to) an upstream.
     pub const INVOKE: &str = "invoke";
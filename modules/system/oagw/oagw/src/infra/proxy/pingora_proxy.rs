@@ -6,20 +6,24 @@ use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use bytes::Bytes;
 use dashmap::DashMap;
-use pingora_core::protocols::Digest;
+use oagw_sdk::body::Trailers;
+use pingora_core::protocols::tls::{ALPN, SslDigest};
+use pingora_core::protocols::{Digest, TcpKeepalive};
 use pingora_core::upstreams::peer::HttpPeer;
 use pingora_http::ResponseHeader;
 use pingora_load_balancing::discovery::ServiceDiscovery;
 use pingora_load_balancing::health_check::TcpHealthCheck;
-use pingora_load_balancing::selection::RoundRobin;
+use pingora_load_balancing::selection::{BackendIter, BackendSelection, Consistent, RoundRobin};
 use pingora_load_balancing::{Backend, Backends, LoadBalancer};
 use pingora_proxy::{HttpProxy, ProxyHttp, Session, http_proxy};
 use tokio::sync::watch;
 use tracing::{info, warn};
 use uuid::Uuid;
 
+use super::dns_cache::DnsCache;
+use super::{grpc, trailers};
 use crate::domain::error::DomainError;
-use crate::domain::model::{Endpoint, Scheme};
+use crate::domain::model::{Endpoint, KeepaliveConfig, Scheme, UpstreamProtocol};
 use crate::domain::services::EndpointSelector;
 use modkit::api::Problem;
 
@@ -34,6 +38,27 @@ pub(crate) const H_ENDPOINT_HOST: &str = "x-oagw-internal-endpoint-host";
 pub(crate) const H_ENDPOINT_PORT: &str = "x-oagw-internal-endpoint-port";
 pub(crate) const H_ENDPOINT_SCHEME: &str = "x-oagw-internal-endpoint-scheme";
 pub(crate) const H_INSTANCE_URI: &str = "x-oagw-internal-instance-uri";
+pub(crate) const H_UPSTREAM_PROTOCOL: &str = "x-oagw-internal-upstream-protocol";
+pub(crate) const H_READ_TIMEOUT_MS: &str = "x-oagw-internal-read-timeout-ms";
+pub(crate) const H_FORCE_CHUNKED: &str = "x-oagw-internal-force-chunked";
+/// Names the [`trailers::TrailerSlot`] that receives the response trailers.
+pub(crate) const H_TRAILER_SLOT: &str = "x-oagw-internal-trailer-slot";
+/// Upstream keep-alive probing; see [`encode_keepalive`].
+pub(crate) const H_KEEPALIVE: &str = "x-oagw-internal-keepalive";
+/// Upstream TLS version and cipher policy; see [`encode_tls_policy`].
+pub(crate) const H_TLS_POLICY: &str = "x-oagw-internal-tls-policy";
+/// Name the upstream certificate is verified against, and sent as SNI.
+pub(crate) const H_TLS_SERVER_NAME: &str = "x-oagw-internal-tls-server-name";
+/// Connection-establishment retries as `<max retries>,<backoff ms>`.
+pub(crate) const H_CONNECT_RETRY: &str = "x-oagw-internal-connect-retry";
+/// Upstream attempts one send may make in all, connect and stale
+/// pooled-connection retries included; unlimited when absent.
+pub(crate) const H_ATTEMPT_BUDGET: &str = "x-oagw-internal-attempt-budget";
+/// Set on responses: the upstream attempts the send made.
+pub(crate) const H_UPSTREAM_ATTEMPTS: &str = "x-oagw-internal-upstream-attempts";
+/// Set on responses whose body the upstream delimited by closing the
+/// connection; Pingora re-frames them towards the data plane.
+pub(crate) const H_EOF_DELIMITED: &str = "x-oagw-internal-eof-delimited";
 
 /// Hop-by-hop headers that must not be forwarded in responses (mirrors headers.rs).
 const HOP_BY_HOP: &[&str] = &[
@@ -47,6 +72,12 @@ const HOP_BY_HOP: &[&str] = &[
     "upgrade",
 ];
 
+/// Default lifetime of an idle pooled upstream connection.
+const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// Unanswered TCP keep-alive probes before a connection is dropped, unless
+/// the upstream sets `tcp_probe_count`.
+const DEFAULT_TCP_KEEPALIVE_PROBES: u32 = 3;
+
 // ---------------------------------------------------------------------------
 // PingoraProxy — ProxyHttp implementation (D3)
 // ---------------------------------------------------------------------------
@@ -54,9 +85,13 @@ const HOP_BY_HOP: &[&str] = &[
 pub struct PingoraProxy {
     connect_timeout: Duration,
     read_timeout: Duration,
+    /// How long an idle upstream connection stays in the pool.
+    idle_timeout: Duration,
     /// When true, skip TLS certificate verification for upstream connections.
     /// **Test use only** — allows self-signed certs in integration tests.
     skip_upstream_tls_verify: bool,
+    /// Resolves upstream hostnames before connecting.
+    dns: Arc<DnsCache>,
 }
 
 impl PingoraProxy {
@@ -64,10 +99,27 @@ impl PingoraProxy {
         Self {
             connect_timeout,
             read_timeout,
+            idle_timeout: DEFAULT_POOL_IDLE_TIMEOUT,
             skip_upstream_tls_verify: false,
+            dns: Arc::new(DnsCache::default()),
         }
     }
 
+    /// Resolve upstream hostnames through `dns` (shared with the endpoint
+    /// selector).
+    #[must_use]
+    pub fn with_dns_cache(mut self, dns: Arc<DnsCache>) -> Self {
+        self.dns = dns;
+        self
+    }
+
+    /// Close pooled upstream connections after `idle_timeout` without use.
+    #[must_use]
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
     /// Skip upstream TLS certificate verification. **Test use only.**
     #[must_use]
     #[allow(dead_code)]
@@ -106,20 +158,22 @@ struct DnsDiscovery {
     endpoints: Vec<Endpoint>,
     /// Shared map updated on each `discover()` cycle.
     addr_map: AddrMap,
+    dns: Arc<DnsCache>,
 }
 
 impl DnsDiscovery {
-    fn new(endpoints: Vec<Endpoint>, addr_map: AddrMap) -> Box<Self> {
+    fn new(endpoints: Vec<Endpoint>, addr_map: AddrMap, dns: Arc<DnsCache>) -> Box<Self> {
         Box::new(Self {
             endpoints,
             addr_map,
+            dns,
         })
     }
 
     /// Resolve endpoints to `Backend`s and rebuild the reverse-lookup map.
     ///
-    /// Uses async `tokio::net::lookup_host` to avoid blocking the Tokio
-    /// worker thread during DNS resolution.
+    /// Resolution goes through the shared [`DnsCache`], which resolves
+    /// asynchronously and never blocks the Tokio worker thread.
     async fn resolve(&self) -> (BTreeSet<Backend>, HashMap<String, Endpoint>) {
         let mut backends = BTreeSet::new();
         let mut map = HashMap::with_capacity(self.endpoints.len());
@@ -127,7 +181,7 @@ impl DnsDiscovery {
         for ep in &self.endpoints {
             let addr_str = format!("{}:{}", ep.host, ep.port);
 
-            let resolved = tokio::net::lookup_host(addr_str.clone()).await;
+            let resolved = self.dns.resolve(&ep.host, ep.port).await;
             match resolved {
                 Ok(addrs) => {
                     for sock in addrs {
@@ -171,8 +225,8 @@ impl ServiceDiscovery for DnsDiscovery {
 // ---------------------------------------------------------------------------
 
 /// Cache entry: load balancer + shared reverse-lookup map + shutdown handle.
-struct LbEntry {
-    lb: Arc<LoadBalancer<RoundRobin>>,
+struct LbEntry<S: BackendSelection = RoundRobin> {
+    lb: Arc<LoadBalancer<S>>,
     /// Shared reverse-lookup map updated by [`DnsDiscovery::discover`].
     addr_map: AddrMap,
     /// Dropping this sender signals the background update task to stop.
@@ -186,30 +240,50 @@ struct LbEntry {
 /// caches it in a `DashMap`, and attaches a `TcpHealthCheck` with 10s interval.
 /// DNS re-resolution runs every 30s via the [`DnsDiscovery`] `ServiceDiscovery`
 /// implementation. Dropping the cache entry (via `invalidate()`) stops the
-/// background task.
+/// background task. Sticky upstreams get a separate `LoadBalancer<Consistent>`
+/// (Ketama ring) built the same way on first `select_sticky()` call.
 pub struct PingoraEndpointSelector {
     cache: DashMap<Uuid, LbEntry>,
+    sticky_cache: DashMap<Uuid, LbEntry<Consistent>>,
+    dns: Arc<DnsCache>,
 }
 
 impl PingoraEndpointSelector {
     pub fn new() -> Self {
         Self {
             cache: DashMap::new(),
+            sticky_cache: DashMap::new(),
+            dns: Arc::new(DnsCache::default()),
         }
     }
 
-    /// Build a `LoadBalancer<RoundRobin>` from domain endpoints using
+    /// Resolve endpoint hostnames through `dns` (shared with the proxy).
+    #[must_use]
+    pub fn with_dns_cache(mut self, dns: Arc<DnsCache>) -> Self {
+        self.dns = dns;
+        self
+    }
+
+    /// Build a `LoadBalancer<S>` from domain endpoints using
     /// [`DnsDiscovery`] for dynamic DNS re-resolution.
     ///
-    /// DNS resolution uses async `tokio::net::lookup_host` to avoid blocking
+    /// DNS resolution goes through the shared [`DnsCache`] and does not block
     /// the Tokio worker thread.
-    async fn build_entry(&self, endpoints: &[Endpoint]) -> Option<LbEntry> {
+    async fn build_entry<S>(&self, endpoints: &[Endpoint]) -> Option<LbEntry<S>>
+    where
+        S: BackendSelection + Send + Sync + 'static,
+        S::Iter: BackendIter,
+    {
         let addr_map: AddrMap = Arc::new(ArcSwap::from_pointee(HashMap::new()));
 
-        let mut backends = Backends::new(DnsDiscovery::new(endpoints.to_vec(), addr_map.clone()));
+        let mut backends = Backends::new(DnsDiscovery::new(
+            endpoints.to_vec(),
+            addr_map.clone(),
+            self.dns.clone(),
+        ));
         backends.set_health_check(TcpHealthCheck::new());
 
-        let mut lb = LoadBalancer::<RoundRobin>::from_backends(backends);
+        let mut lb = LoadBalancer::<S>::from_backends(backends);
         lb.health_check_frequency = Some(Duration::from_secs(10));
         lb.update_frequency = Some(Duration::from_secs(30));
 
@@ -265,10 +339,73 @@ impl EndpointSelector for PingoraEndpointSelector {
         map.get(&addr_key).cloned()
     }
 
+    async fn select_sticky(
+        &self,
+        upstream_id: Uuid,
+        endpoints: &[Endpoint],
+        key: &[u8],
+    ) -> Option<Endpoint> {
+        if let Some(entry) = self.sticky_cache.get(&upstream_id) {
+            let backend = entry.lb.select(key, 256)?;
+            let addr_key = backend.addr.to_string();
+            let map = entry.addr_map.load();
+            return map.get(&addr_key).cloned();
+        }
+
+        let entry = self.build_entry(endpoints).await?;
+        let entry_ref = self.sticky_cache.entry(upstream_id).or_insert(entry);
+        let backend = entry_ref.lb.select(key, 256)?;
+        let addr_key = backend.addr.to_string();
+        let map = entry_ref.addr_map.load();
+        map.get(&addr_key).cloned()
+    }
+
+    fn healthy_count(&self, upstream_id: Uuid) -> usize {
+        if let Some(entry) = self.cache.get(&upstream_id) {
+            return healthy_endpoints(entry.value());
+        }
+        self.sticky_cache
+            .get(&upstream_id)
+            .map_or(0, |entry| healthy_endpoints(entry.value()))
+    }
+
     fn invalidate(&self, upstream_id: Uuid) {
         // Removing the entry drops LbEntry, which drops _shutdown_tx,
         // which signals the background update task to stop.
         self.cache.remove(&upstream_id);
+        self.sticky_cache.remove(&upstream_id);
+    }
+}
+
+/// Distinct endpoints with at least one backend address passing its health
+/// check. An endpoint may resolve to several addresses.
+fn healthy_endpoints<S: BackendSelection>(entry: &LbEntry<S>) -> usize {
+    let backends = entry.lb.backends();
+    let map = entry.addr_map.load();
+    let mut healthy: Vec<&Endpoint> = Vec::new();
+    for backend in backends.get_backend().iter() {
+        if backends.ready(backend)
+            && let Some(endpoint) = map.get(&backend.addr.to_string())
+            && !healthy.contains(&endpoint)
+        {
+            healthy.push(endpoint);
+        }
+    }
+    healthy.len()
+}
+
+/// ALPN for the upstream connection.
+///
+/// HTTP/2 protocols (`h2c`, `grpc`) always use H2: over cleartext this means
+/// prior knowledge. Otherwise H2H1 for TLS, H1 for WebSocket and cleartext.
+fn select_alpn(scheme: Scheme, protocol: UpstreamProtocol) -> ALPN {
+    let tls = matches!(scheme, Scheme::Https | Scheme::Wss | Scheme::Wt);
+    if protocol.is_h2() {
+        ALPN::H2
+    } else if tls && !matches!(scheme, Scheme::Wss) {
+        ALPN::H2H1
+    } else {
+        ALPN::H1
     }
 }
 
@@ -278,7 +415,39 @@ impl EndpointSelector for PingoraEndpointSelector {
 
 pub struct ProxyCtx {
     endpoint: Endpoint,
+    protocol: UpstreamProtocol,
+    /// Per-request read timeout resolved by the data plane (route > upstream > global).
+    read_timeout: Option<Duration>,
     instance_uri: String,
+    /// Re-frame the upstream request body as `Transfer-Encoding: chunked`.
+    force_chunked: bool,
+    /// Receives the upstream response trailers for the data plane.
+    trailers: Option<Trailers>,
+    /// Keep-alive probing for the upstream connection.
+    keepalive: PeerKeepalive,
+    /// TLS version and cipher suites the upstream session must use.
+    tls_policy: PeerTlsPolicy,
+    /// Certificate name and SNI; the endpoint host when unset.
+    tls_server_name: Option<String>,
+    /// Extra connection attempts after a failed connect, and the pause
+    /// before each.
+    connect_retry: Option<(u32, Duration)>,
+    /// Failed connection attempts retried so far.
+    connect_failures: u32,
+    /// A connect just failed and will be retried after the backoff.
+    connect_backoff_pending: bool,
+    /// Upstream attempts this send may make, from [`H_ATTEMPT_BUDGET`].
+    attempt_budget: Option<u32>,
+    /// Upstream attempts made so far, the first included.
+    attempts: u32,
+}
+
+impl ProxyCtx {
+    /// Whether the attempt budget leaves room for another attempt.
+    fn may_retry(&self) -> bool {
+        self.attempt_budget
+            .is_none_or(|budget| self.attempts < budget)
+    }
 }
 
 impl Default for ProxyCtx {
@@ -289,11 +458,177 @@ impl Default for ProxyCtx {
                 host: String::new(),
                 port: 443,
             },
+            protocol: UpstreamProtocol::Http1,
+            read_timeout: None,
             instance_uri: String::new(),
+            force_chunked: false,
+            trailers: None,
+            keepalive: PeerKeepalive::default(),
+            tls_policy: PeerTlsPolicy::default(),
+            tls_server_name: None,
+            connect_retry: None,
+            connect_failures: 0,
+            connect_backoff_pending: false,
+            attempt_budget: None,
+            attempts: 0,
         }
     }
 }
 
+// ---------------------------------------------------------------------------
+// Upstream keep-alive probing
+// ---------------------------------------------------------------------------
+
+/// Keep-alive settings applied to the upstream peer.
+#[derive(Debug, Clone, Default)]
+struct PeerKeepalive {
+    tcp: Option<TcpKeepalive>,
+    h2_ping_interval: Option<Duration>,
+}
+
+impl PeerKeepalive {
+    fn apply(&self, peer: &mut HttpPeer) {
+        peer.options.tcp_keepalive.clone_from(&self.tcp);
+        peer.options.h2_ping_interval = self.h2_ping_interval;
+    }
+}
+
+/// Encode an upstream's keep-alive settings for [`H_KEEPALIVE`] as
+/// `<tcp idle>,<tcp interval>,<tcp probes>,<h2 ping>`, in seconds; empty
+/// fields are unset. `None` when nothing is enabled.
+pub(crate) fn encode_keepalive(config: &KeepaliveConfig) -> Option<String> {
+    let idle = config.tcp_idle_secs;
+    if idle.is_none() && config.h2_ping_interval_secs.is_none() {
+        return None;
+    }
+    let field = |v: Option<u64>| v.map(|v| v.to_string()).unwrap_or_default();
+    let interval = idle.map(|idle| config.tcp_interval_secs.unwrap_or(idle));
+    let probes = idle.map(|_| {
+        u64::from(
+            config
+                .tcp_probe_count
+                .unwrap_or(DEFAULT_TCP_KEEPALIVE_PROBES),
+        )
+    });
+    Some(format!(
+        "{},{},{},{}",
+        field(idle),
+        field(interval),
+        field(probes),
+        field(config.h2_ping_interval_secs)
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// Upstream TLS policy
+// ---------------------------------------------------------------------------
+
+/// TLS requirements checked against the negotiated upstream session. The
+/// connector offers TLS 1.2 and 1.3 with its full suite list, so the policy
+/// is enforced once the handshake has picked a version and cipher.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct PeerTlsPolicy {
+    /// Minor version of the lowest accepted TLS 1.x version.
+    min_version: Option<u8>,
+    cipher_suites: Vec<String>,
+}
+
+impl PeerTlsPolicy {
+    /// Why `digest` breaks the policy, or `None` when it complies.
+    fn violation(&self, digest: &SslDigest) -> Option<String> {
+        if let Some(min) = self.min_version {
+            let minor = digest
+                .version
+                .strip_prefix("TLSv1_")
+                .and_then(|v| v.parse::<u8>().ok());
+            if minor.is_none_or(|minor| minor < min) {
+                return Some(format!(
+                    "upstream negotiated {}, below the minimum TLS 1.{min}",
+                    digest.version
+                ));
+            }
+        }
+        if !self.cipher_suites.is_empty() && !self.cipher_suites.iter().any(|c| *c == digest.cipher)
+        {
+            return Some(format!(
+                "upstream negotiated cipher suite {}, which is not allowed",
+                digest.cipher
+            ));
+        }
+        None
+    }
+}
+
+/// Encode an upstream's TLS policy for [`H_TLS_POLICY`] as
+/// `<min version>;<suite>,<suite>`; empty fields are unset. `None` when
+/// neither is configured.
+pub(crate) fn encode_tls_policy(
+    min_version: Option<&str>,
+    cipher_suites: &[String],
+) -> Option<String> {
+    if min_version.is_none() && cipher_suites.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{};{}",
+        min_version.unwrap_or_default(),
+        cipher_suites.join(",")
+    ))
+}
+
+/// Inverse of [`encode_tls_policy`]. An unreadable minimum version maps to
+/// TLS 1.3 so a mangled value never weakens the policy.
+fn parse_tls_policy(value: &str) -> PeerTlsPolicy {
+    let (min_version, cipher_suites) = value.split_once(';').unwrap_or((value, ""));
+    let min_version = match min_version.trim() {
+        "" => None,
+        v => Some(
+            v.strip_prefix("1.")
+                .and_then(|minor| minor.parse().ok())
+                .unwrap_or(3),
+        ),
+    };
+    PeerTlsPolicy {
+        min_version,
+        cipher_suites: cipher_suites
+            .split(',')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(str::to_string)
+            .collect(),
+    }
+}
+
+/// Parse [`H_CONNECT_RETRY`]; malformed values disable connect retries.
+fn parse_connect_retry(value: &str) -> Option<(u32, Duration)> {
+    let (max_retries, backoff_ms) = value.split_once(',')?;
+    let max_retries = max_retries.trim().parse().ok()?;
+    let backoff_ms = backoff_ms.trim().parse().ok()?;
+    Some((max_retries, Duration::from_millis(backoff_ms)))
+}
+
+/// Inverse of [`encode_keepalive`]; malformed values disable probing.
+fn parse_keepalive(value: &str) -> PeerKeepalive {
+    let fields: Vec<Option<u64>> = value.split(',').map(|f| f.trim().parse().ok()).collect();
+    let [idle, interval, probes, ping] = fields[..] else {
+        return PeerKeepalive::default();
+    };
+    let tcp = match (idle, interval, probes) {
+        (Some(idle), Some(interval), Some(probes)) => Some(TcpKeepalive {
+            idle: Duration::from_secs(idle),
+            interval: Duration::from_secs(interval),
+            count: usize::try_from(probes).unwrap_or(usize::MAX),
+            #[cfg(target_os = "linux")]
+            user_timeout: Duration::ZERO,
+        }),
+        _ => None,
+    };
+    PeerKeepalive {
+        tcp,
+        h2_ping_interval: ping.map(Duration::from_secs),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // ProxyHttp trait implementation
 // ---------------------------------------------------------------------------
@@ -343,6 +678,21 @@ impl ProxyHttp for PingoraProxy {
                 _ => Scheme::Https,
             };
         }
+        if let Some(v) = req
+            .headers
+            .get(H_UPSTREAM_PROTOCOL)
+            .and_then(|v| v.to_str().ok())
+        {
+            ctx.protocol = UpstreamProtocol::from_protocol(v);
+        }
+        if let Some(v) = req
+            .headers
+            .get(H_READ_TIMEOUT_MS)
+            .and_then(|v| v.to_str().ok())
+            && let Ok(ms) = v.parse()
+        {
+            ctx.read_timeout = Some(Duration::from_millis(ms));
+        }
         if let Some(v) = req
             .headers
             .get(H_INSTANCE_URI)
@@ -351,6 +701,35 @@ impl ProxyHttp for PingoraProxy {
             ctx.instance_uri = v.to_string();
         }
 
+        ctx.force_chunked = req.headers.contains_key(H_FORCE_CHUNKED);
+        ctx.trailers = req
+            .headers
+            .get(H_TRAILER_SLOT)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .and_then(trailers::lookup);
+        if let Some(v) = req.headers.get(H_KEEPALIVE).and_then(|v| v.to_str().ok()) {
+            ctx.keepalive = parse_keepalive(v);
+        }
+        if let Some(v) = req.headers.get(H_TLS_POLICY).and_then(|v| v.to_str().ok()) {
+            ctx.tls_policy = parse_tls_policy(v);
+        }
+        ctx.tls_server_name = req
+            .headers
+            .get(H_TLS_SERVER_NAME)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        ctx.connect_retry = req
+            .headers
+            .get(H_CONNECT_RETRY)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_connect_retry);
+        ctx.attempt_budget = req
+            .headers
+            .get(H_ATTEMPT_BUDGET)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok());
+
         // Strip all internal headers before forwarding.
         let to_remove: Vec<http::HeaderName> = session
             .req_header()
@@ -373,21 +752,43 @@ impl ProxyHttp for PingoraProxy {
         _session: &mut Session,
         ctx: &mut Self::CTX,
     ) -> pingora_core::Result<Box<HttpPeer>> {
+        ctx.attempts += 1;
+        if std::mem::take(&mut ctx.connect_backoff_pending)
+            && let Some((_, backoff)) = ctx.connect_retry
+        {
+            tokio::time::sleep(backoff).await;
+        }
         let ep = &ctx.endpoint;
         let tls = matches!(ep.scheme, Scheme::Https | Scheme::Wss | Scheme::Wt);
 
-        let mut peer = HttpPeer::new(format!("{}:{}", ep.host, ep.port), tls, ep.host.clone());
+        // Resolve here rather than in `HttpPeer::new`, which would block on
+        // DNS and panic when the host does not resolve.
+        let addr = self
+            .dns
+            .resolve(&ep.host, ep.port)
+            .await
+            .ok()
+            .and_then(|addrs| addrs.into_iter().next())
+            .ok_or_else(|| {
+                pingora_core::Error::explain(
+                    pingora_core::ErrorType::ConnectNoRoute,
+                    format!("failed to resolve upstream host {}", ep.host),
+                )
+            })?;
+        // The connector verifies the certificate against the SNI name, so
+        // a configured server name replaces the host for both.
+        let sni = ctx
+            .tls_server_name
+            .clone()
+            .unwrap_or_else(|| ep.host.clone());
+        let mut peer = HttpPeer::new(addr, tls, sni);
 
         peer.options.connection_timeout = Some(self.connect_timeout);
-        peer.options.read_timeout = Some(self.read_timeout);
-        peer.options.idle_timeout = Some(Duration::from_secs(90));
+        peer.options.read_timeout = Some(ctx.read_timeout.unwrap_or(self.read_timeout));
+        peer.options.idle_timeout = Some(self.idle_timeout);
+        ctx.keepalive.apply(&mut peer);
 
-        // ALPN: H2H1 for HTTPS, H1 for WebSocket and cleartext.
-        peer.options.alpn = if tls && !matches!(ep.scheme, Scheme::Wss) {
-            pingora_core::protocols::tls::ALPN::H2H1
-        } else {
-            pingora_core::protocols::tls::ALPN::H1
-        };
+        peer.options.alpn = select_alpn(ep.scheme, ctx.protocol);
 
         if self.skip_upstream_tls_verify {
             peer.options.verify_cert = false;
@@ -397,13 +798,33 @@ impl ProxyHttp for PingoraProxy {
         Ok(Box::new(peer))
     }
 
-    /// No-op — headers are already prepared by proxy_request() steps 3–5. (D3)
+    /// Headers are already prepared by proxy_request() steps 3–5 (D3); only
+    /// body framing is adjusted here. Pingora picks the upstream body writer
+    /// from these headers, so exactly one framing header must remain.
     async fn upstream_request_filter(
         &self,
         _session: &mut Session,
-        _upstream_request: &mut pingora_http::RequestHeader,
-        _ctx: &mut Self::CTX,
+        upstream_request: &mut pingora_http::RequestHeader,
+        ctx: &mut Self::CTX,
     ) -> pingora_core::Result<()> {
+        // The bridge's `Connection: close` governs the in-memory session
+        // only; dropping it lets upstream connections return to the pool.
+        // A WebSocket handshake must announce the upgrade hop-by-hop.
+        if upstream_request.headers.contains_key(http::header::UPGRADE) {
+            upstream_request.insert_header(http::header::CONNECTION, "upgrade")?;
+        } else {
+            upstream_request.remove_header(&http::header::CONNECTION);
+        }
+        if ctx.force_chunked {
+            upstream_request.insert_header(http::header::TRANSFER_ENCODING, "chunked")?;
+        }
+        if upstream_request
+            .headers
+            .contains_key(http::header::TRANSFER_ENCODING)
+        {
+            // RFC 9112 §6.3: Transfer-Encoding overrides Content-Length.
+            upstream_request.remove_header(&http::header::CONTENT_LENGTH);
+        }
         Ok(())
     }
 
@@ -412,7 +833,7 @@ impl ProxyHttp for PingoraProxy {
         &self,
         _session: &mut Session,
         upstream_response: &mut ResponseHeader,
-        _ctx: &mut Self::CTX,
+        ctx: &mut Self::CTX,
     ) -> pingora_core::Result<()> {
         let status = upstream_response.status;
         let content_type = upstream_response
@@ -426,6 +847,17 @@ impl ProxyHttp for PingoraProxy {
             "upstream response received"
         );
 
+        let eof_delimited = ctx.protocol == UpstreamProtocol::Http1
+            && !status.is_informational()
+            && status != http::StatusCode::NO_CONTENT
+            && status != http::StatusCode::NOT_MODIFIED
+            && !upstream_response
+                .headers
+                .contains_key(http::header::CONTENT_LENGTH)
+            && !upstream_response
+                .headers
+                .contains_key(http::header::TRANSFER_ENCODING);
+
         // Strip Connection-nominated headers.
         if let Some(conn_value) = upstream_response
             .headers
@@ -456,13 +888,76 @@ impl ProxyHttp for PingoraProxy {
         for name in &to_remove {
             upstream_response.remove_header(name);
         }
+        if eof_delimited {
+            let _ = upstream_response.insert_header(H_EOF_DELIMITED, "1");
+        }
+        let _ = upstream_response.insert_header(H_UPSTREAM_ATTEMPTS, ctx.attempts.to_string());
+
+        // gRPC trailers are appended to the body as a trailer frame, so the
+        // upstream Content-Length (if any) no longer matches what we write.
+        if ctx.protocol == UpstreamProtocol::Grpc {
+            upstream_response.remove_header(&http::header::CONTENT_LENGTH);
+        }
 
         Ok(())
     }
 
-    // No fail_to_connect override: OAGW does not retry on connection failure.
-    // Per DESIGN.md §311 and scenario 12.6, upstream sees exactly one request
-    // attempt. Connection-establishment retries would violate this invariant.
+    /// Hand the upstream trailers to the data plane through the request's
+    /// trailer slot. For gRPC they are also encoded as the final body frame,
+    /// so that clients without trailer support still see `grpc-status`.
+    async fn response_trailer_filter(
+        &self,
+        _session: &mut Session,
+        upstream_trailers: &mut http::HeaderMap,
+        ctx: &mut Self::CTX,
+    ) -> pingora_core::Result<Option<Bytes>> {
+        if let Some(trailers) = &ctx.trailers {
+            trailers.set(upstream_trailers.clone());
+        }
+        if ctx.protocol != UpstreamProtocol::Grpc {
+            return Ok(None);
+        }
+        Ok(Some(grpc::encode_trailer_frame(upstream_trailers)))
+    }
+
+    /// Retry a refused or timed-out connect when the data plane enabled
+    /// connect retries and the attempt budget allows. The upstream never
+    /// received the request, so it still sees a single attempt (DESIGN.md
+    /// §311, scenario 12.6). TLS and resolution failures are not retried
+    /// here. Failover to an upstream's
+    /// secondary endpoints is opt-in and happens in the data plane, as a
+    /// separate attempt against a different endpoint.
+    fn fail_to_connect(
+        &self,
+        _session: &mut Session,
+        peer: &HttpPeer,
+        ctx: &mut Self::CTX,
+        mut e: Box<pingora_core::Error>,
+    ) -> Box<pingora_core::Error> {
+        let transient = matches!(
+            e.etype,
+            pingora_core::ErrorType::ConnectRefused
+                | pingora_core::ErrorType::ConnectTimedout
+                | pingora_core::ErrorType::ConnectError
+        );
+        if transient
+            && let Some((max_retries, _)) = ctx.connect_retry
+            && ctx.connect_failures < max_retries
+            && ctx.may_retry()
+        {
+            ctx.connect_failures += 1;
+            ctx.connect_backoff_pending = true;
+            warn!(
+                peer = %peer,
+                attempt = ctx.connect_failures,
+                error = %e,
+                instance = %ctx.instance_uri,
+                "upstream connect failed, retrying"
+            );
+            e.set_retry(true);
+        }
+        e
+    }
 
     /// Reconnect on stale pooled connection errors for idempotent methods.
     ///
@@ -471,13 +966,14 @@ impl ProxyHttp for PingoraProxy {
     /// been sent — but this is not guaranteed (partial header write before
     /// RST is possible). Reconnecting is therefore safe only for idempotent
     /// methods (RFC 9110 §9.2.2). Non-idempotent methods (POST, PATCH) are
-    /// not retried, consistent with DESIGN.md and scenario 12.6.
+    /// not retried, consistent with DESIGN.md and scenario 12.6. Neither is
+    /// a request whose attempt budget is spent.
     fn error_while_proxy(
         &self,
         _peer: &HttpPeer,
         session: &mut Session,
         mut e: Box<pingora_core::Error>,
-        _ctx: &mut Self::CTX,
+        ctx: &mut Self::CTX,
         client_reused: bool,
     ) -> Box<pingora_core::Error> {
         if client_reused {
@@ -489,7 +985,7 @@ impl ProxyHttp for PingoraProxy {
                     | http::Method::DELETE
                     | http::Method::OPTIONS
             );
-            e.retry.decide_reuse(idempotent);
+            e.retry.decide_reuse(idempotent && ctx.may_retry());
         }
         e
     }
@@ -530,6 +1026,9 @@ impl ProxyHttp for PingoraProxy {
             _ => DomainError::DownstreamError {
                 detail: match &e.etype {
                     pingora_core::ErrorType::ConnectRefused => "upstream connection refused",
+                    pingora_core::ErrorType::ConnectNoRoute => {
+                        "upstream host could not be resolved"
+                    }
                     pingora_core::ErrorType::TLSHandshakeFailure
                     | pingora_core::ErrorType::TLSHandshakeTimedout => {
                         "upstream TLS handshake failed"
@@ -550,6 +1049,7 @@ impl ProxyHttp for PingoraProxy {
         if let Ok(mut resp) = ResponseHeader::build(status, Some(body_bytes.len())) {
             let _ = resp.insert_header("content-type", "application/problem+json");
             let _ = resp.insert_header("x-oagw-error-source", "gateway");
+            let _ = resp.insert_header(H_UPSTREAM_ATTEMPTS, ctx.attempts.to_string());
             let _ = session.write_response_header(Box::new(resp), false).await;
             let _ = session.write_response_body(Some(body_bytes), true).await;
         } else {
@@ -570,9 +1070,20 @@ impl ProxyHttp for PingoraProxy {
         peer: &HttpPeer,
         #[cfg(unix)] _fd: std::os::unix::io::RawFd,
         #[cfg(windows)] _sock: std::os::windows::io::RawSocket,
-        _digest: Option<&Digest>,
+        digest: Option<&Digest>,
         ctx: &mut Self::CTX,
     ) -> pingora_core::Result<()> {
+        // Checked on reused connections too: they may have been opened for
+        // a request to the same peer under a different policy.
+        if let Some(ssl) = digest.and_then(|d| d.ssl_digest.as_deref())
+            && let Some(violation) = ctx.tls_policy.violation(ssl)
+        {
+            warn!(peer = %peer, instance = %ctx.instance_uri, %violation, "Upstream TLS policy violated");
+            return Err(pingora_core::Error::explain(
+                pingora_core::ErrorType::TLSHandshakeFailure,
+                violation,
+            ));
+        }
         info!(
             reused,
             peer = %peer,
@@ -649,6 +1160,40 @@ mod tests {
         assert!(port_b > 0, "port 10002 should be selected at least once");
     }
 
+    #[tokio::test]
+    async fn select_sticky_pins_key_and_spreads_keys() {
+        let selector = PingoraEndpointSelector::new();
+        let id = Uuid::new_v4();
+        let endpoints = vec![
+            ep("127.0.0.1", 11001, Scheme::Https),
+            ep("127.0.0.1", 11002, Scheme::Https),
+            ep("127.0.0.1", 11003, Scheme::Https),
+        ];
+
+        let first = selector
+            .select_sticky(id, &endpoints, b"session-42")
+            .await
+            .unwrap();
+        for _ in 0..10 {
+            let again = selector
+                .select_sticky(id, &endpoints, b"session-42")
+                .await
+                .unwrap();
+            assert_eq!(again.port, first.port, "same key must pick same endpoint");
+        }
+
+        let mut ports = std::collections::HashSet::new();
+        for i in 0..64 {
+            let key = format!("session-{i}");
+            let selected = selector
+                .select_sticky(id, &endpoints, key.as_bytes())
+                .await
+                .unwrap();
+            ports.insert(selected.port);
+        }
+        assert_eq!(ports.len(), 3, "keys should spread across all endpoints");
+    }
+
     #[tokio::test]
     async fn invalidate_causes_rebuild() {
         let selector = PingoraEndpointSelector::new();
@@ -746,7 +1291,7 @@ mod tests {
             ep("127.0.0.1", 8001, Scheme::Https),
             ep("127.0.0.1", 8002, Scheme::Https),
         ];
-        let discovery = DnsDiscovery::new(endpoints, addr_map);
+        let discovery = DnsDiscovery::new(endpoints, addr_map, Arc::new(DnsCache::default()));
 
         let (backends, map) = discovery.resolve().await;
 
@@ -763,7 +1308,7 @@ mod tests {
     async fn dns_discovery_resolve_hostname_endpoints() {
         let addr_map = make_addr_map();
         let endpoints = vec![ep("localhost", 9001, Scheme::Https)];
-        let discovery = DnsDiscovery::new(endpoints, addr_map);
+        let discovery = DnsDiscovery::new(endpoints, addr_map, Arc::new(DnsCache::default()));
 
         let (backends, map) = discovery.resolve().await;
 
@@ -784,7 +1329,8 @@ mod tests {
             ep("127.0.0.1", 7001, Scheme::Https),
             ep("127.0.0.1", 7002, Scheme::Https),
         ];
-        let discovery = DnsDiscovery::new(endpoints, addr_map.clone());
+        let discovery =
+            DnsDiscovery::new(endpoints, addr_map.clone(), Arc::new(DnsCache::default()));
 
         let (backends, _health) = discovery.discover().await.unwrap();
 
@@ -805,7 +1351,8 @@ mod tests {
     async fn dns_discovery_discover_replaces_addr_map() {
         let addr_map = make_addr_map();
         let endpoints = vec![ep("127.0.0.1", 6001, Scheme::Http)];
-        let discovery = DnsDiscovery::new(endpoints, addr_map.clone());
+        let discovery =
+            DnsDiscovery::new(endpoints, addr_map.clone(), Arc::new(DnsCache::default()));
 
         // First discover.
         discovery.discover().await.unwrap();
@@ -836,7 +1383,7 @@ mod tests {
             443,
             Scheme::Https,
         )];
-        let discovery = DnsDiscovery::new(endpoints, addr_map);
+        let discovery = DnsDiscovery::new(endpoints, addr_map, Arc::new(DnsCache::default()));
 
         let (backends, map) = discovery.resolve().await;
 
@@ -922,17 +1469,13 @@ mod tests {
     // configuration without constructing a full Pingora Session. The
     // logic under test is:
     //   tls = matches!(scheme, Https | Wss | Wt)
-    //   alpn = if tls && !Wss { H2H1 } else { H1 }
+    //   alpn = select_alpn(scheme, protocol)
 
     /// Build an HttpPeer using the same logic as `upstream_peer`.
     fn build_peer(scheme: Scheme, host: &str, port: u16) -> HttpPeer {
         let tls = matches!(scheme, Scheme::Https | Scheme::Wss | Scheme::Wt);
         let mut peer = HttpPeer::new(format!("{host}:{port}"), tls, host.to_string());
-        peer.options.alpn = if tls && !matches!(scheme, Scheme::Wss) {
-            pingora_core::protocols::tls::ALPN::H2H1
-        } else {
-            pingora_core::protocols::tls::ALPN::H1
-        };
+        peer.options.alpn = select_alpn(scheme, UpstreamProtocol::Http1);
         peer
     }
 
@@ -980,11 +1523,122 @@ mod tests {
         );
     }
 
+    #[test]
+    fn alpn_h2c_uses_h2_prior_knowledge() {
+        assert_eq!(select_alpn(Scheme::Http, UpstreamProtocol::H2c), ALPN::H2);
+    }
+
+    #[test]
+    fn alpn_grpc_uses_h2_for_cleartext_and_tls() {
+        assert_eq!(select_alpn(Scheme::Http, UpstreamProtocol::Grpc), ALPN::H2);
+        assert_eq!(select_alpn(Scheme::Https, UpstreamProtocol::Grpc), ALPN::H2);
+    }
+
+    #[test]
+    fn keepalive_settings_reach_peer() {
+        use pingora_core::upstreams::peer::Peer;
+
+        let encoded = encode_keepalive(&KeepaliveConfig {
+            tcp_idle_secs: Some(30),
+            tcp_interval_secs: None,
+            tcp_probe_count: None,
+            h2_ping_interval_secs: Some(20),
+        })
+        .unwrap();
+        assert_eq!(encoded, "30,30,3,20");
+
+        let mut peer = build_peer(Scheme::Http, "127.0.0.1", 80);
+        parse_keepalive(&encoded).apply(&mut peer);
+
+        let tcp = peer.tcp_keepalive().expect("TCP keep-alive enabled");
+        assert_eq!(tcp.idle, Duration::from_secs(30));
+        assert_eq!(tcp.interval, Duration::from_secs(30));
+        assert_eq!(tcp.count, 3);
+        assert_eq!(peer.h2_ping_interval(), Some(Duration::from_secs(20)));
+    }
+
+    #[test]
+    fn tls_policy_round_trips_and_rejects_weaker_sessions() {
+        let suites = vec!["TLS13_AES_256_GCM_SHA384".to_string()];
+        let policy = parse_tls_policy(&encode_tls_policy(Some("1.3"), &suites).unwrap());
+        assert_eq!(
+            policy,
+            PeerTlsPolicy {
+                min_version: Some(3),
+                cipher_suites: suites,
+            }
+        );
+
+        let digest = |version: &'static str, cipher: &'static str| {
+            SslDigest::new(cipher, version, None, None, vec![])
+        };
+        assert_eq!(
+            policy.violation(&digest("TLSv1_3", "TLS13_AES_256_GCM_SHA384")),
+            None
+        );
+        let low = policy
+            .violation(&digest("TLSv1_2", "TLS13_AES_256_GCM_SHA384"))
+            .unwrap();
+        assert!(low.contains("TLSv1_2"), "{low}");
+        let cipher = policy
+            .violation(&digest("TLSv1_3", "TLS13_AES_128_GCM_SHA256"))
+            .unwrap();
+        assert!(cipher.contains("TLS13_AES_128_GCM_SHA256"), "{cipher}");
+    }
+
+    #[test]
+    fn tls_policy_is_off_unless_configured() {
+        assert_eq!(encode_tls_policy(None, &[]), None);
+        let policy = parse_tls_policy(&encode_tls_policy(Some("1.2"), &[]).unwrap());
+        assert_eq!(policy.min_version, Some(2));
+        assert!(policy.cipher_suites.is_empty());
+        assert_eq!(
+            PeerTlsPolicy::default().violation(&SslDigest::new("", "TLSv1_2", None, None, vec![])),
+            None
+        );
+    }
+
+    #[test]
+    fn keepalive_is_off_unless_configured() {
+        use pingora_core::upstreams::peer::Peer;
+
+        assert_eq!(encode_keepalive(&KeepaliveConfig::default()), None);
+
+        let ping_only = encode_keepalive(&KeepaliveConfig {
+            h2_ping_interval_secs: Some(15),
+            ..Default::default()
+        })
+        .unwrap();
+        let mut peer = build_peer(Scheme::Http, "127.0.0.1", 80);
+        parse_keepalive(&ping_only).apply(&mut peer);
+        assert!(peer.tcp_keepalive().is_none());
+        assert_eq!(peer.h2_ping_interval(), Some(Duration::from_secs(15)));
+
+        let mut peer = build_peer(Scheme::Http, "127.0.0.1", 80);
+        parse_keepalive("garbage").apply(&mut peer);
+        assert!(peer.tcp_keepalive().is_none());
+        assert!(peer.h2_ping_interval().is_none());
+    }
+
+    #[test]
+    fn connect_retry_header_parses() {
+        assert_eq!(
+            parse_connect_retry("2,50"),
+            Some((2, Duration::from_millis(50)))
+        );
+        assert_eq!(parse_connect_retry("2"), None);
+        assert_eq!(parse_connect_retry("two,50"), None);
+    }
+
     #[test]
     fn peer_timeouts_propagate() {
         let proxy = PingoraProxy::new(Duration::from_secs(7), Duration::from_secs(15));
         // Verify timeouts are stored correctly on the proxy.
         assert_eq!(proxy.connect_timeout, Duration::from_secs(7));
         assert_eq!(proxy.read_timeout, Duration::from_secs(15));
+        assert_eq!(proxy.idle_timeout, DEFAULT_POOL_IDLE_TIMEOUT);
+
+        let proxy = proxy.with_idle_timeout(Duration::from_secs(20));
+        assert_eq!(proxy.idle_timeout, Duration::from_secs(20));
     }
 }
@@ -1,7 +1,8 @@
 use crate::domain::error::DomainError;
-use crate::domain::model::{Endpoint, Scheme};
+use crate::domain::model::{Endpoint, Scheme, TrailingSlash};
 
-/// Build the full upstream URL from endpoint, route path, path suffix, and query params.
+/// Build the full upstream URL from endpoint, route path, path suffix, and query params,
+/// applying the upstream's `trailing_slash` policy to the path.
 ///
 /// # Errors
 ///
@@ -11,6 +12,7 @@ pub fn build_upstream_url(
     route_path: &str,
     path_suffix: &str,
     query_params: &[(String, String)],
+    trailing_slash: TrailingSlash,
 ) -> Result<String, DomainError> {
     let scheme = match endpoint.scheme {
         Scheme::Http => "http",
@@ -31,8 +33,23 @@ pub fn build_upstream_url(
         format!("{}:{}", endpoint.host, endpoint.port)
     };
 
+    Ok(format!(
+        "{scheme}://{host_port}{}",
+        build_upstream_path(route_path, path_suffix, query_params, trailing_slash)
+    ))
+}
+
+/// Build the upstream path and query string (the URL without scheme and
+/// authority) from route path, path suffix, and query params.
+#[must_use]
+pub fn build_upstream_path(
+    route_path: &str,
+    path_suffix: &str,
+    query_params: &[(String, String)],
+    trailing_slash: TrailingSlash,
+) -> String {
     // Combine route path + path suffix, avoiding double slashes.
-    let path = if path_suffix.is_empty() {
+    let mut path = if path_suffix.is_empty() {
         route_path.to_string()
     } else if route_path.ends_with('/') && path_suffix.starts_with('/') {
         format!("{}{}", route_path, &path_suffix[1..])
@@ -41,18 +58,26 @@ pub fn build_upstream_url(
     } else {
         format!("{route_path}{path_suffix}")
     };
-
-    let mut url = format!("{scheme}://{host_port}{path}");
+    match trailing_slash {
+        TrailingSlash::Preserve => {}
+        // The root path has no slash to strip.
+        TrailingSlash::Strip => path.truncate(path.trim_end_matches('/').len().max(1)),
+        TrailingSlash::Add => {
+            if !path.ends_with('/') {
+                path.push('/');
+            }
+        }
+    }
 
     if !query_params.is_empty() {
-        url.push('?');
+        path.push('?');
         let qs = form_urlencoded::Serializer::new(String::new())
             .extend_pairs(query_params)
             .finish();
-        url.push_str(&qs);
+        path.push_str(&qs);
     }
 
-    Ok(url)
+    path
 }
 
 fn is_default_port(scheme: &str, port: u16) -> bool {
@@ -78,6 +103,7 @@ mod tests {
             "/v1/chat",
             "/completions",
             &[],
+            TrailingSlash::Preserve,
         )
         .unwrap();
         assert_eq!(url, "https://api.openai.com/v1/chat/completions");
@@ -90,6 +116,7 @@ mod tests {
             "/v1/chat",
             "/models/gpt-4",
             &[("version".into(), "2".into())],
+            TrailingSlash::Preserve,
         )
         .unwrap();
         assert_eq!(url, "https://api.openai.com/v1/chat/models/gpt-4?version=2");
@@ -97,21 +124,40 @@ mod tests {
 
     #[test]
     fn nonstandard_port() {
-        let url = build_upstream_url(&endpoint("localhost", 8080), "/api", "", &[]).unwrap();
+        let url = build_upstream_url(
+            &endpoint("localhost", 8080),
+            "/api",
+            "",
+            &[],
+            TrailingSlash::Preserve,
+        )
+        .unwrap();
         assert_eq!(url, "https://localhost:8080/api");
     }
 
     #[test]
     fn empty_suffix() {
-        let url =
-            build_upstream_url(&endpoint("api.openai.com", 443), "/v1/models", "", &[]).unwrap();
+        let url = build_upstream_url(
+            &endpoint("api.openai.com", 443),
+            "/v1/models",
+            "",
+            &[],
+            TrailingSlash::Preserve,
+        )
+        .unwrap();
         assert_eq!(url, "https://api.openai.com/v1/models");
     }
 
     #[test]
     fn avoids_double_slash() {
-        let url =
-            build_upstream_url(&endpoint("api.openai.com", 443), "/v1/", "/chat", &[]).unwrap();
+        let url = build_upstream_url(
+            &endpoint("api.openai.com", 443),
+            "/v1/",
+            "/chat",
+            &[],
+            TrailingSlash::Preserve,
+        )
+        .unwrap();
         assert_eq!(url, "https://api.openai.com/v1/chat");
     }
 
@@ -122,6 +168,7 @@ mod tests {
             "/api",
             "/data",
             &[("key".into(), "val".into()), ("foo".into(), "bar".into())],
+            TrailingSlash::Preserve,
         )
         .unwrap();
         assert_eq!(url, "https://example.com/api/data?key=val&foo=bar");
@@ -134,7 +181,7 @@ mod tests {
             host: "127.0.0.1".into(),
             port: 3000,
         };
-        let url = build_upstream_url(&ep, "/v1/test", "", &[]).unwrap();
+        let url = build_upstream_url(&ep, "/v1/test", "", &[], TrailingSlash::Preserve).unwrap();
         assert_eq!(url, "http://127.0.0.1:3000/v1/test");
     }
 
@@ -145,7 +192,7 @@ mod tests {
             host: "example.com".into(),
             port: 80,
         };
-        let url = build_upstream_url(&ep, "/api", "", &[]).unwrap();
+        let url = build_upstream_url(&ep, "/api", "", &[], TrailingSlash::Preserve).unwrap();
         assert_eq!(url, "http://example.com/api");
     }
 
@@ -156,6 +203,7 @@ mod tests {
             "/v1/search",
             "",
             &[("q".into(), "a&b".into())],
+            TrailingSlash::Preserve,
         )
         .unwrap();
         assert_eq!(url, "https://api.openai.com/v1/search?q=a%26b");
@@ -168,7 +216,50 @@ mod tests {
             host: "grpc.example.com".into(),
             port: 443,
         };
-        let err = build_upstream_url(&ep, "/service", "", &[]).unwrap_err();
+        let err =
+            build_upstream_url(&ep, "/service", "", &[], TrailingSlash::Preserve).unwrap_err();
         assert!(matches!(err, DomainError::Validation { .. }));
     }
+
+    #[test]
+    fn trailing_slash_policies() {
+        let cases = [
+            ("/v1/items", "", "/v1/items", "/v1/items", "/v1/items/"),
+            ("/v1/items", "/", "/v1/items/", "/v1/items", "/v1/items/"),
+            (
+                "/v1",
+                "/items/42/",
+                "/v1/items/42/",
+                "/v1/items/42",
+                "/v1/items/42/",
+            ),
+            ("/", "", "/", "/", "/"),
+        ];
+        for (route, suffix, preserve, strip, add) in cases {
+            for (policy, expected) in [
+                (TrailingSlash::Preserve, preserve),
+                (TrailingSlash::Strip, strip),
+                (TrailingSlash::Add, add),
+            ] {
+                assert_eq!(
+                    build_upstream_path(route, suffix, &[], policy),
+                    expected,
+                    "{route} + {suffix} with {policy:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn trailing_slash_applies_before_query() {
+        let query = [("page".to_string(), "2".to_string())];
+        assert_eq!(
+            build_upstream_path("/v1/items", "", &query, TrailingSlash::Add),
+            "/v1/items/?page=2"
+        );
+        assert_eq!(
+            build_upstream_path("/v1/items/", "", &query, TrailingSlash::Strip),
+            "/v1/items?page=2"
+        );
+    }
 }
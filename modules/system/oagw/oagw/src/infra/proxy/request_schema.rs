@@ -0,0 +1,63 @@
+//! Validation of buffered JSON request bodies against a route's
+//! `request_schema`.
+
+/// Check `body` against `schema`. Returns every violation as
+/// `(JSON pointer into the body, message)`; a body that is not JSON at all
+/// is reported as a single violation at the root.
+///
+/// A schema that does not compile is ignored; the control plane rejects
+/// those, so this only guards against stale stored config.
+pub(crate) fn validate(schema: &serde_json::Value, body: &[u8]) -> Vec<(String, String)> {
+    let validator = match jsonschema::validator_for(schema) {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::warn!(error = %e, "invalid request schema, skipping validation");
+            return Vec::new();
+        }
+    };
+    let instance = match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(v) => v,
+        Err(e) => return vec![(String::new(), format!("body is not valid JSON: {e}"))],
+    };
+    validator
+        .iter_errors(&instance)
+        .map(|e| (e.instance_path().to_string(), e.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "required": ["model"],
+            "properties": {
+                "model": {"type": "string"},
+                "max_tokens": {"type": "integer", "minimum": 1}
+            }
+        })
+    }
+
+    #[test]
+    fn conforming_body_has_no_violations() {
+        assert!(validate(&schema(), br#"{"model":"gpt-4","max_tokens":16}"#).is_empty());
+    }
+
+    #[test]
+    fn violations_are_reported_with_pointers() {
+        let violations = validate(&schema(), br#"{"max_tokens":0}"#);
+        assert_eq!(violations.len(), 2);
+        assert!(violations.iter().any(|(path, _)| path.is_empty()));
+        assert!(violations.iter().any(|(path, _)| path == "/max_tokens"));
+    }
+
+    #[test]
+    fn non_json_body_is_a_root_violation() {
+        let violations = validate(&schema(), b"not json");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].0, "");
+    }
+}
@@ -0,0 +1,626 @@
+//! Default in-process [`ResponseCache`] and cacheability rules.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use oagw_sdk::{CachedResponse, ResponseCache};
+use pingora_memory_cache::MemoryCache;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Default number of entries held by [`InMemoryResponseCache`].
+pub(crate) const DEFAULT_RESPONSE_CACHE_CAPACITY: usize = 1_000;
+
+/// Cached entry stored alongside the original key so that hash collisions in
+/// `MemoryCache` cannot serve another key's response.
+#[derive(Clone)]
+struct Entry {
+    key: String,
+    response: CachedResponse,
+}
+
+/// Process-local response cache backed by `pingora_memory_cache`.
+pub struct InMemoryResponseCache {
+    cache: MemoryCache<String, Entry>,
+    capacity: usize,
+    /// Keys stored since the last clear, which `MemoryCache` cannot list.
+    /// May include keys it has since evicted.
+    keys: Mutex<HashSet<String>>,
+}
+
+impl InMemoryResponseCache {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cache: MemoryCache::new(capacity),
+            capacity,
+            keys: Mutex::new(HashSet::new()),
+        }
+    }
+
+    fn is_live(&self, key: &str) -> bool {
+        let (entry, _status) = self.cache.get(key);
+        entry.is_some_and(|e| e.key == key)
+    }
+}
+
+impl Default for InMemoryResponseCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_RESPONSE_CACHE_CAPACITY)
+    }
+}
+
+#[async_trait]
+impl ResponseCache for InMemoryResponseCache {
+    async fn get(&self, key: &str) -> Option<CachedResponse> {
+        let (entry, _status) = self.cache.get(key);
+        entry.filter(|e| e.key == key).map(|e| e.response)
+    }
+
+    async fn put(&self, key: &str, response: CachedResponse, ttl: Duration) {
+        {
+            let mut keys = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+            // Forget keys the cache has evicted once they outnumber its
+            // capacity, so that the set stays bounded.
+            if keys.len() >= self.capacity.saturating_mul(2) {
+                keys.retain(|k| self.is_live(k));
+            }
+            keys.insert(key.to_string());
+        }
+        self.cache.put(
+            key,
+            Entry {
+                key: key.to_string(),
+                response,
+            },
+            Some(ttl),
+        );
+    }
+
+    async fn invalidate(&self, key: &str) {
+        self.keys
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(key);
+        self.cache.remove(key);
+    }
+
+    async fn clear(&self) -> usize {
+        let keys = std::mem::take(&mut *self.keys.lock().unwrap_or_else(|e| e.into_inner()));
+        let mut removed = 0;
+        for key in keys {
+            if self.is_live(&key) {
+                removed += 1;
+            }
+            self.cache.remove(&key);
+        }
+        removed
+    }
+}
+
+/// Cache key for a proxied `GET`, scoped so that entries never cross tenant,
+/// upstream, or route boundaries. The endpoint is deliberately excluded so
+/// that all endpoints of a multi-endpoint upstream share entries.
+pub(crate) fn cache_key(
+    tenant_id: Uuid,
+    upstream_id: Uuid,
+    route_id: Uuid,
+    path: &str,
+    query: &[(String, String)],
+) -> String {
+    let query = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(query)
+        .finish();
+    format!("{tenant_id}:{upstream_id}:{route_id}:GET:{path}?{query}")
+}
+
+/// Cache keys of a proxied `GET`. `private` is only set when responses
+/// marked `Cache-Control: private` may be cached.
+pub(crate) struct CacheKeys {
+    pub(crate) shared: String,
+    pub(crate) private: Option<String>,
+}
+
+impl CacheKeys {
+    /// Keys to look up, the caller's own entry first.
+    pub(crate) fn lookup_order(&self) -> impl Iterator<Item = &str> {
+        self.private
+            .as_deref()
+            .into_iter()
+            .chain([self.shared.as_str()])
+    }
+
+    /// Key to store a response of `scope` under, if any.
+    pub(crate) fn for_scope(&self, scope: CacheScope) -> Option<&str> {
+        match scope {
+            CacheScope::Shared => Some(&self.shared),
+            CacheScope::Private => self.private.as_deref(),
+        }
+    }
+}
+
+/// Key for a response only the subject `subject_id` may be served, derived
+/// from the shared [`cache_key`] of the same request.
+pub(crate) fn private_cache_key(shared_key: &str, subject_id: Uuid) -> String {
+    format!("{subject_id}@{shared_key}")
+}
+
+/// Key under which the response to a request carrying `Idempotency-Key:
+/// {key}` is stored for replay. Scoped by tenant and route like
+/// [`cache_key`], from whose keys it cannot collide.
+pub(crate) fn idempotency_key(tenant_id: Uuid, route_id: Uuid, key: &str) -> String {
+    format!("{tenant_id}:{route_id}:idempotency:{key}")
+}
+
+/// How long a response carrying a validator (`ETag` or `Last-Modified`) is
+/// kept past its freshness so that it can be revalidated instead of refetched.
+pub(crate) const STALE_RETENTION: Duration = Duration::from_secs(3600);
+
+/// Key under which the revalidatable copy of the entry at `key` is kept.
+pub(crate) fn stale_key(key: &str) -> String {
+    format!("{key}:stale")
+}
+
+/// Conditional request headers that revalidate a stored response:
+/// `If-None-Match` from its `ETag` and `If-Modified-Since` from its
+/// `Last-Modified`. Empty when the response carries neither validator.
+pub(crate) fn revalidation_headers(stored: &HeaderMap) -> Vec<(HeaderName, HeaderValue)> {
+    [
+        (http::header::ETAG, http::header::IF_NONE_MATCH),
+        (http::header::LAST_MODIFIED, http::header::IF_MODIFIED_SINCE),
+    ]
+    .into_iter()
+    .filter_map(|(validator, condition)| Some((condition, stored.get(validator)?.clone())))
+    .collect()
+}
+
+/// Update a stored response's headers from a `304 Not Modified` answer to
+/// its revalidation. Headers describing the stored body itself are kept.
+pub(crate) fn merge_not_modified(stored: &mut HeaderMap, not_modified: &HeaderMap) {
+    for name in not_modified.keys() {
+        if [
+            http::header::CONTENT_LENGTH,
+            http::header::CONTENT_TYPE,
+            http::header::CONTENT_ENCODING,
+            http::header::TRANSFER_ENCODING,
+        ]
+        .contains(name)
+        {
+            continue;
+        }
+        stored.remove(name);
+        for value in not_modified.get_all(name) {
+            stored.append(name.clone(), value.clone());
+        }
+    }
+}
+
+/// Age of a stored response at `now`: the `Age` the upstream sent with it
+/// plus the time it has spent in the cache. A `stored_at` in the future,
+/// e.g. from a clock step on another replica, counts as zero.
+pub(crate) fn current_age(cached: &CachedResponse, now: SystemTime) -> Duration {
+    let upstream_age = cached
+        .headers
+        .get(http::header::AGE)
+        .and_then(|v| v.to_str().ok()?.trim().parse::<u64>().ok())
+        .map_or(Duration::ZERO, Duration::from_secs);
+    let resident = now.duration_since(cached.stored_at).unwrap_or_default();
+    upstream_age.saturating_add(resident)
+}
+
+/// Which callers a stored response may be served to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CacheScope {
+    /// Any caller of the tenant.
+    Shared,
+    /// Only the subject whose request produced it.
+    Private,
+}
+
+/// Lifetime and scope of a response, if it may be stored at all.
+///
+/// Only explicit freshness is honoured: `s-maxage` wins over `max-age`, and
+/// `no-store` or `no-cache` disable caching. `private` disables caching
+/// unless `allow_private` is set, in which case the response is stored for
+/// its subject only and `s-maxage`, meant for shared caches, is ignored.
+/// The response to a `credentialed` request is treated as `private` unless
+/// it is marked `public` or carries `s-maxage` (RFC 9111 §3.5).
+pub(crate) fn cache_ttl(
+    headers: &HeaderMap,
+    allow_private: bool,
+    credentialed: bool,
+) -> Option<(Duration, CacheScope)> {
+    let mut max_age = None;
+    let mut s_maxage = None;
+    let mut public = false;
+    let mut scope = CacheScope::Shared;
+    for value in headers.get_all(http::header::CACHE_CONTROL) {
+        let value = value.to_str().ok()?;
+        for directive in value.split(',') {
+            let directive = directive.trim();
+            let (name, arg) = match directive.split_once('=') {
+                Some((n, a)) => (n.trim(), Some(a.trim().trim_matches('"'))),
+                None => (directive, None),
+            };
+            if name.eq_ignore_ascii_case("no-store") || name.eq_ignore_ascii_case("no-cache") {
+                return None;
+            }
+            if name.eq_ignore_ascii_case("private") {
+                if !allow_private {
+                    return None;
+                }
+                scope = CacheScope::Private;
+            }
+            public |= name.eq_ignore_ascii_case("public");
+            let secs = arg.and_then(|a| a.parse::<u64>().ok());
+            if name.eq_ignore_ascii_case("max-age") {
+                max_age = secs;
+            } else if name.eq_ignore_ascii_case("s-maxage") {
+                s_maxage = secs;
+            }
+        }
+    }
+    if credentialed && scope == CacheScope::Shared && !public && s_maxage.is_none() {
+        if !allow_private {
+            return None;
+        }
+        scope = CacheScope::Private;
+    }
+    let secs = match scope {
+        CacheScope::Shared => s_maxage.or(max_age),
+        CacheScope::Private => max_age,
+    };
+    secs.filter(|&secs| secs > 0)
+        .map(|secs| (Duration::from_secs(secs), scope))
+}
+
+/// Request headers a response's `Vary` selects it by, lowercased and
+/// sorted; empty when it has no `Vary`. `None` for `Vary: *`, which no
+/// later request can be known to match, so the response is not stored.
+pub(crate) fn vary_names(headers: &HeaderMap) -> Option<Vec<HeaderName>> {
+    let mut names = Vec::new();
+    for value in headers.get_all(http::header::VARY) {
+        for name in value.to_str().ok()?.split(',') {
+            let name = name.trim();
+            if name == "*" {
+                return None;
+            }
+            if let Ok(name) = HeaderName::from_bytes(name.to_ascii_lowercase().as_bytes()) {
+                names.push(name);
+            }
+        }
+    }
+    names.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    names.dedup();
+    Some(names)
+}
+
+/// Key of the variant of the entry at `key` that a request with `request`
+/// headers selects, given the response's [`vary_names`]. Names and values
+/// are hashed so that no header value can collide with another key.
+pub(crate) fn variant_key(key: &str, vary: &[HeaderName], request: &HeaderMap) -> String {
+    let mut hasher = Sha256::new();
+    for name in vary {
+        hasher.update(name.as_str().as_bytes());
+        hasher.update([0]);
+        for value in request.get_all(name) {
+            hasher.update(value.as_bytes());
+            hasher.update([0]);
+        }
+        hasher.update([1]);
+    }
+    format!("{key}:vary:{}", hex::encode(hasher.finalize()))
+}
+
+/// Lifetime of a 404 or 410 response on a route that caches them for
+/// `route_ttl`. A shorter `s-maxage` or `max-age` from the upstream wins;
+/// `no-store`, `no-cache` and `private` disable caching, as does a
+/// `credentialed` request unless the response is `public` or carries
+/// `s-maxage`.
+pub(crate) fn negative_cache_ttl(
+    headers: &HeaderMap,
+    route_ttl: Duration,
+    credentialed: bool,
+) -> Option<Duration> {
+    let mut max_age = None;
+    let mut s_maxage = None;
+    let mut public = false;
+    for value in headers.get_all(http::header::CACHE_CONTROL) {
+        let value = value.to_str().ok()?;
+        for directive in value.split(',') {
+            let directive = directive.trim();
+            let (name, arg) = match directive.split_once('=') {
+                Some((n, a)) => (n.trim(), Some(a.trim().trim_matches('"'))),
+                None => (directive, None),
+            };
+            if ["no-store", "no-cache", "private"]
+                .iter()
+                .any(|d| name.eq_ignore_ascii_case(d))
+            {
+                return None;
+            }
+            public |= name.eq_ignore_ascii_case("public");
+            let secs = arg.and_then(|a| a.parse::<u64>().ok());
+            if name.eq_ignore_ascii_case("max-age") {
+                max_age = secs;
+            } else if name.eq_ignore_ascii_case("s-maxage") {
+                s_maxage = secs;
+            }
+        }
+    }
+    if credentialed && !public && s_maxage.is_none() {
+        return None;
+    }
+    let ttl = s_maxage
+        .or(max_age)
+        .map_or(route_ttl, |secs| route_ttl.min(Duration::from_secs(secs)));
+    (!ttl.is_zero()).then_some(ttl)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use http::{HeaderValue, StatusCode};
+
+    fn headers(cache_control: &str) -> HeaderMap {
+        let mut h = HeaderMap::new();
+        h.insert(
+            http::header::CACHE_CONTROL,
+            HeaderValue::from_str(cache_control).unwrap(),
+        );
+        h
+    }
+
+    fn cache_ttl_shared(headers: &HeaderMap) -> Option<Duration> {
+        cache_ttl(headers, false, false).map(|(ttl, scope)| {
+            assert_eq!(scope, CacheScope::Shared);
+            ttl
+        })
+    }
+
+    fn response() -> CachedResponse {
+        CachedResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(b"ok"),
+            stored_at: SystemTime::now(),
+            fresh_for: Duration::from_secs(60),
+        }
+    }
+
+    #[tokio::test]
+    async fn clear_removes_and_counts_live_entries() {
+        let cache = InMemoryResponseCache::new(16);
+        for key in ["a", "b", "c"] {
+            cache.put(key, response(), Duration::from_secs(60)).await;
+        }
+        cache.invalidate("b").await;
+        cache.put("d", response(), Duration::ZERO).await;
+
+        assert_eq!(cache.clear().await, 2);
+        assert!(cache.get("a").await.is_none());
+        assert!(cache.get("c").await.is_none());
+        assert_eq!(cache.clear().await, 0);
+    }
+
+    #[test]
+    fn ttl_from_max_age() {
+        assert_eq!(
+            cache_ttl_shared(&headers("public, max-age=60")),
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn s_maxage_overrides_max_age() {
+        assert_eq!(
+            cache_ttl_shared(&headers("max-age=60, s-maxage=5")),
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn not_cacheable_without_explicit_freshness() {
+        assert_eq!(cache_ttl_shared(&HeaderMap::new()), None);
+        assert_eq!(cache_ttl_shared(&headers("max-age=0")), None);
+    }
+
+    #[test]
+    fn no_store_private_and_no_cache_disable_caching() {
+        assert_eq!(cache_ttl_shared(&headers("no-store, max-age=60")), None);
+        assert_eq!(cache_ttl_shared(&headers("private, max-age=60")), None);
+        assert_eq!(cache_ttl_shared(&headers("max-age=60, no-cache")), None);
+    }
+
+    #[test]
+    fn negative_ttl_is_capped_by_upstream_freshness() {
+        let route_ttl = Duration::from_secs(30);
+        assert_eq!(
+            negative_cache_ttl(&HeaderMap::new(), route_ttl, false),
+            Some(route_ttl)
+        );
+        assert_eq!(
+            negative_cache_ttl(&headers("max-age=5"), route_ttl, false),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            negative_cache_ttl(&headers("max-age=600"), route_ttl, false),
+            Some(route_ttl)
+        );
+        assert_eq!(
+            negative_cache_ttl(&headers("max-age=0"), route_ttl, false),
+            None
+        );
+        assert_eq!(
+            negative_cache_ttl(&headers("no-store"), route_ttl, false),
+            None
+        );
+        assert_eq!(
+            negative_cache_ttl(&headers("private"), route_ttl, false),
+            None
+        );
+        assert_eq!(negative_cache_ttl(&HeaderMap::new(), route_ttl, true), None);
+        assert_eq!(
+            negative_cache_ttl(&headers("public"), route_ttl, true),
+            Some(route_ttl)
+        );
+    }
+
+    #[test]
+    fn not_modified_refreshes_stored_headers_but_not_body_framing() {
+        let mut stored = headers("max-age=60");
+        stored.insert(http::header::ETAG, HeaderValue::from_static("\"v1\""));
+        stored.insert(http::header::CONTENT_LENGTH, HeaderValue::from_static("6"));
+        assert_eq!(
+            revalidation_headers(&stored),
+            [(
+                http::header::IF_NONE_MATCH,
+                HeaderValue::from_static("\"v1\"")
+            )]
+        );
+
+        let mut not_modified = headers("max-age=120");
+        not_modified.insert(http::header::CONTENT_LENGTH, HeaderValue::from_static("0"));
+        merge_not_modified(&mut stored, &not_modified);
+
+        assert_eq!(stored[http::header::CACHE_CONTROL], "max-age=120");
+        assert_eq!(stored[http::header::ETAG], "\"v1\"");
+        assert_eq!(stored[http::header::CONTENT_LENGTH], "6");
+        assert!(revalidation_headers(&HeaderMap::new()).is_empty());
+    }
+
+    #[test]
+    fn private_responses_cached_per_subject_when_allowed() {
+        assert_eq!(
+            cache_ttl(&headers("private, max-age=60, s-maxage=5"), true, false),
+            Some((Duration::from_secs(60), CacheScope::Private))
+        );
+        assert_eq!(
+            cache_ttl(&headers("public, max-age=60"), true, false),
+            Some((Duration::from_secs(60), CacheScope::Shared))
+        );
+        assert_eq!(
+            cache_ttl(&headers("private, no-store, max-age=60"), true, false),
+            None
+        );
+    }
+
+    #[test]
+    fn credentialed_responses_are_shared_only_when_public_or_s_maxage() {
+        assert_eq!(cache_ttl(&headers("max-age=60"), false, true), None);
+        assert_eq!(
+            cache_ttl(&headers("max-age=60"), true, true),
+            Some((Duration::from_secs(60), CacheScope::Private))
+        );
+        assert_eq!(
+            cache_ttl(&headers("public, max-age=60"), false, true),
+            Some((Duration::from_secs(60), CacheScope::Shared))
+        );
+        assert_eq!(
+            cache_ttl(&headers("max-age=60, s-maxage=5"), false, true),
+            Some((Duration::from_secs(5), CacheScope::Shared))
+        );
+    }
+
+    #[test]
+    fn variant_key_follows_vary_headers() {
+        let mut response = HeaderMap::new();
+        response.append(http::header::VARY, HeaderValue::from_static("Accept"));
+        response.append(
+            http::header::VARY,
+            HeaderValue::from_static("accept-language, Accept"),
+        );
+        let vary = vary_names(&response).unwrap();
+        assert_eq!(vary, [http::header::ACCEPT, http::header::ACCEPT_LANGUAGE]);
+
+        let request = |accept: &'static str| {
+            let mut h = HeaderMap::new();
+            h.insert(http::header::ACCEPT, HeaderValue::from_static(accept));
+            h.insert(http::header::USER_AGENT, HeaderValue::from_static("ua"));
+            h
+        };
+        let json = variant_key("k", &vary, &request("application/json"));
+        assert_eq!(json, variant_key("k", &vary, &request("application/json")));
+        assert_ne!(json, variant_key("k", &vary, &request("text/html")));
+        assert_ne!(
+            json,
+            variant_key("other", &vary, &request("application/json"))
+        );
+
+        assert_eq!(vary_names(&HeaderMap::new()), Some(vec![]));
+        response.insert(http::header::VARY, HeaderValue::from_static("*"));
+        assert_eq!(vary_names(&response), None);
+    }
+
+    #[test]
+    fn private_keys_differ_per_subject_and_are_looked_up_first() {
+        let shared = cache_key(Uuid::nil(), Uuid::nil(), Uuid::nil(), "/items", &[]);
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let keys = CacheKeys {
+            shared: shared.clone(),
+            private: Some(private_cache_key(&shared, alice)),
+        };
+        assert_ne!(keys.private, Some(private_cache_key(&shared, bob)));
+        assert_ne!(keys.private.as_deref(), Some(shared.as_str()));
+        assert_eq!(
+            keys.lookup_order().collect::<Vec<_>>(),
+            vec![keys.private.as_deref().unwrap(), shared.as_str()]
+        );
+        assert_eq!(keys.for_scope(CacheScope::Shared), Some(shared.as_str()));
+
+        let shared_only = CacheKeys {
+            shared,
+            private: None,
+        };
+        assert_eq!(shared_only.for_scope(CacheScope::Private), None);
+    }
+
+    #[tokio::test]
+    async fn in_memory_put_get_invalidate() {
+        let cache = InMemoryResponseCache::new(16);
+        let response = CachedResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(b"cached"),
+            stored_at: SystemTime::UNIX_EPOCH,
+            fresh_for: Duration::from_secs(60),
+        };
+
+        assert!(cache.get("k").await.is_none());
+        cache
+            .put("k", response.clone(), Duration::from_secs(60))
+            .await;
+        assert_eq!(cache.get("k").await, Some(response));
+
+        cache.invalidate("k").await;
+        assert!(cache.get("k").await.is_none());
+    }
+
+    #[test]
+    fn current_age_adds_upstream_age_to_time_in_cache() {
+        let stored_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let mut cached = CachedResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+            stored_at,
+            fresh_for: Duration::from_secs(60),
+        };
+        let later = stored_at + Duration::from_secs(7);
+
+        assert_eq!(current_age(&cached, later), Duration::from_secs(7));
+        assert_eq!(
+            current_age(&cached, stored_at - Duration::from_secs(5)),
+            Duration::ZERO
+        );
+
+        cached
+            .headers
+            .insert(http::header::AGE, HeaderValue::from_static("30"));
+        assert_eq!(current_age(&cached, later), Duration::from_secs(37));
+    }
+}
@@ -1,40 +1,101 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 
 use async_trait::async_trait;
-use authz_resolver_sdk::PolicyEnforcer;
 use authz_resolver_sdk::pep::AccessRequest;
+use authz_resolver_sdk::{EnforcerError, PolicyEnforcer};
 use bytes::Bytes;
 use credstore_sdk::CredStoreClientV1;
 use futures_util::StreamExt;
 use http::{HeaderMap, HeaderName, HeaderValue};
+use modkit::client_hub::{ClientHub, ClientScope};
 use modkit_security::SecurityContext;
+use oagw_sdk::api::{ClientIp, ErrorSource};
 use oagw_sdk::body::{Body, BodyStream};
+use oagw_sdk::{BodyTransformer, CachedResponse, ResponseCache};
 use pingora_core::apps::HttpServerApp;
 use pingora_proxy::HttpProxy;
 use tokio::io::AsyncWriteExt;
-use tokio::sync::watch;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, watch};
+use tracing::Instrument;
+use uuid::Uuid;
 
-use crate::config::TokenCacheConfig;
+use crate::config::{
+    AuthzErrorPolicy, AuthzMode, BodilessMethodBody, RateLimitHeaderNames, RuntimeConfig,
+    SharedRuntimeConfig, SrvTarget, TokenCacheConfig, UnmatchedRouteBehavior,
+};
+use crate::domain::circuit_breaker::{CallOutcome, CircuitBreaker, CircuitSettings};
+use crate::domain::clock::{Clock, SystemClock};
+use crate::domain::concurrency::{
+    ConcurrencyLimiter, HostConcurrencyLimiter, UpstreamConcurrencyLimiter, UpstreamSlot,
+};
 use crate::domain::error::DomainError;
-use crate::domain::model::{Endpoint, PassthroughMode, PathSuffixMode, Scheme, Upstream};
-use crate::domain::plugin::AuthContext;
-use crate::domain::rate_limit::RateLimiter;
+use crate::domain::gts_helpers::APIKEY_AUTH_PLUGIN_ID;
+use crate::domain::latency::LatencyTracker;
+use crate::domain::model::{
+    ApiVersion, AuthCondition, AuthConfig, AuthRule, ConfigSource, Endpoint, FlushedCaches,
+    GatewayStatus, HttpMethod, MatchRules, PassthroughMode, PathSuffixMode, RateLimitConfig,
+    RecentError, RequestTrace, Route, RouteDescription, RouteKind, Scheme, Sourced, StickyBy,
+    TraceAuthz, TrailingSlash, Upstream, UpstreamProtocol, UpstreamRuntimeState,
+    UpstreamTestResult,
+};
+use crate::domain::plugin::{
+    AuthContext, AuthHeaders, AuthPlugin, EarlyResponse, HeaderProvenance, PluginError,
+};
+use crate::domain::rate_limit::{RateLimitStatus, RateLimiter};
+use crate::domain::readiness::ReadinessState;
+use crate::domain::recent_errors::RecentErrors;
 use crate::domain::services::{ControlPlaneService, DataPlaneService, EndpointSelector};
+use crate::infra::metrics::{BodyLimitExceeded, ErrorStatusCodes, OagwMetrics};
 use crate::infra::plugin::AuthPluginRegistry;
-use crate::infra::proxy::{actions, resources};
+use crate::infra::plugin::apikey_auth::ApiKeyAuthPlugin;
+use crate::infra::proxy::{actions, properties, resources};
 
+use super::compression;
+use super::dns_cache::DnsCache;
 use super::headers;
+use super::log_sampling::{self, LogSampler};
 use super::pingora_proxy::{
-    H_ENDPOINT_HOST, H_ENDPOINT_PORT, H_ENDPOINT_SCHEME, H_INSTANCE_URI, H_UPSTREAM_ID,
-    PingoraProxy,
+    H_ATTEMPT_BUDGET, H_CONNECT_RETRY, H_ENDPOINT_HOST, H_ENDPOINT_PORT, H_ENDPOINT_SCHEME,
+    H_EOF_DELIMITED, H_FORCE_CHUNKED, H_INSTANCE_URI, H_KEEPALIVE, H_READ_TIMEOUT_MS, H_TLS_POLICY,
+    H_TLS_SERVER_NAME, H_TRAILER_SLOT, H_UPSTREAM_ATTEMPTS, H_UPSTREAM_ID, H_UPSTREAM_PROTOCOL,
+    PingoraProxy, encode_keepalive, encode_tls_policy,
+};
+use super::response_cache::{self, InMemoryResponseCache};
+use super::srv_targets::SrvTargets;
+use super::stream_idle::{self, BodyReadIdle, StreamIdleTimeout};
+use super::trailers::TrailerSlot;
+use super::transform_hook::{HookError, TransformHook};
+use super::{
+    body_inject, body_retry, body_rewrite, body_transform, request_builder, request_schema,
+    session_bridge,
 };
-use super::{request_builder, session_bridge};
 
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Connect timeout for the optional readiness probe of a sample upstream.
+const READINESS_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Default bound on each AuthZ evaluation of a proxy request.
+const AUTHZ_TIMEOUT: Duration = Duration::from_secs(5);
+/// Default bound on each call to a route's `transform_hook_url`.
+const TRANSFORM_HOOK_TIMEOUT: Duration = Duration::from_secs(5);
 /// Default maximum request body size: 100 MB.
 const MAX_BODY_SIZE: usize = 100 * 1024 * 1024;
+/// Default maximum length of the request path and query, in bytes.
+const MAX_URL_LENGTH: usize = 8192;
+/// Default `User-Agent` sent upstream.
+pub(crate) const DEFAULT_USER_AGENT: &str = concat!("oagw/", env!("CARGO_PKG_VERSION"));
+/// `Retry-After` hint returned while an upstream is in maintenance mode.
+const MAINTENANCE_RETRY_AFTER_SECS: u64 = 60;
+/// `Retry-After` hint returned when AuthZ is unavailable and requests fail closed.
+const AUTHZ_RETRY_AFTER_SECS: u64 = 1;
+/// Response header carrying the upstream status when a route remapped it.
+const H_ORIGINAL_STATUS: &str = "x-oagw-original-status";
+/// Client header naming a write whose response is replayed on repeats.
+const IDEMPOTENCY_KEY: &str = "idempotency-key";
+/// Prefix of the client headers overriding an auth plugin config key, e.g.
+/// `x-oagw-auth-config-secret_ref`.
+const AUTH_CONFIG_OVERRIDE_PREFIX: &str = "x-oagw-auth-config-";
 
 /// Data Plane service implementation: proxy orchestration and plugin execution.
 pub struct DataPlaneServiceImpl {
@@ -44,15 +105,185 @@ pub struct DataPlaneServiceImpl {
     /// Sender kept alive so receivers see `false` (not shutting down) until drop.
     _shutdown_tx: watch::Sender<bool>,
     shutdown_rx: watch::Receiver<bool>,
+    /// Kept to rebuild credstore-backed auth plugins (see
+    /// [`Self::with_secret_resolvers`]).
+    credstore: Arc<dyn CredStoreClientV1>,
     auth_registry: AuthPluginRegistry,
     rate_limiter: RateLimiter,
-    request_timeout: Duration,
+    circuit_breaker: CircuitBreaker,
+    /// Rolling response time percentiles per upstream, for [`Self::status`].
+    latency: LatencyTracker,
+    /// Breaker thresholds for upstreams that do not override them.
+    circuit_defaults: CircuitSettings,
+    /// Gateway-wide cap on in-flight proxy requests.
+    concurrency_limiter: ConcurrencyLimiter,
+    /// Cap on simultaneous connections to each upstream host.
+    host_limiter: HostConcurrencyLimiter,
+    /// Cap on in-flight requests to each upstream, shared fairly between
+    /// tenants.
+    upstream_limiter: UpstreamConcurrencyLimiter,
+    /// Slots for server-sent event responses in flight; `None` is no cap.
+    stream_slots: Option<Arc<Semaphore>>,
+    /// Bound on waiting for a host connection or upstream slot; `None` waits
+    /// up to the request timeout.
+    queue_wait_timeout: Option<Duration>,
+    /// Pre-send resolution retries; `None` leaves resolution to the proxy.
+    dns_retry: Option<DnsRetry>,
+    /// Extra connection attempts per endpoint and the pause before each,
+    /// applied by the proxy to failed connects only.
+    connect_retry: Option<(u32, Duration)>,
+    /// Static target lists that replace upstream endpoint hosts.
+    srv_targets: SrvTargets,
+    /// Request timeout and body limit; replaceable while running.
+    runtime: SharedRuntimeConfig,
     /// Enforces authorization policy before proxying each request.
     policy_enforcer: PolicyEnforcer,
+    /// Bound on each AuthZ evaluation; `None` waits indefinitely.
+    authz_timeout: Option<Duration>,
+    /// Extra evaluation attempts after a timeout or resolver failure.
+    authz_retries: u32,
+    /// What to do when no attempt produced a decision.
+    authz_on_error: AuthzErrorPolicy,
+    authz_mode: AuthzMode,
     /// When true, allow HTTP (non-TLS) upstream connections.
     allow_http_upstream: bool,
-    /// Maximum request body size in bytes (applies to both buffered and streaming bodies).
-    max_body_size: usize,
+    /// Maximum length of the request path and query in bytes; longer URLs get 414.
+    max_url_length: usize,
+    /// Maximum number of query parameters sent upstream; more get 400.
+    max_query_params: Option<usize>,
+    /// What to do with a body sent on `GET` or `HEAD`.
+    bodiless_method_body: BodilessMethodBody,
+    /// Largest EOF-delimited upstream response body read into memory.
+    max_response_body_size: usize,
+    /// Pass EOF-delimited responses through as streams instead of buffering.
+    stream_unframed_responses: bool,
+    /// Hub the route `body_transformers` are looked up in; without one, any
+    /// route naming a transformer fails.
+    body_transformers: Option<Arc<ClientHub>>,
+    /// Client for route `transform_hook_url`s.
+    transform_hook: TransformHook,
+    /// Startup milestones gating readiness (flipped by the module lifecycle).
+    readiness: Arc<ReadinessState>,
+    /// Optional `host:port` that must accept a TCP connection for readiness.
+    readiness_probe: Option<String>,
+    /// Storage for cacheable `GET` responses (in-process unless overridden).
+    response_cache: Arc<dyn ResponseCache>,
+    /// Cache `Cache-Control: private` responses per subject.
+    private_response_cache: bool,
+    /// Answer `HEAD` from a cached `GET` response.
+    head_from_response_cache: bool,
+    /// Minimum size of buffered responses gzipped for accepting clients;
+    /// `None` disables compression.
+    compress_min_size: Option<usize>,
+    /// Data-plane instruments (auth plugin latency).
+    metrics: OagwMetrics,
+    /// Ends SSE responses whose upstream stops sending; `None` waits forever.
+    stream_idle_timeout: Option<StreamIdleTimeout>,
+    /// Longest gap between request body chunks before the request is
+    /// aborted with 408; `None` waits for the client indefinitely.
+    body_read_idle_timeout: Option<Duration>,
+    /// Picks the requests logged in detail, per route `log_sample_rate`.
+    log_sampler: LogSampler,
+    /// The last proxy requests that failed inside the gateway.
+    recent_errors: RecentErrors,
+    /// What to answer when no route matches.
+    unmatched_route: UnmatchedRouteBehavior,
+    /// Names of the headers reporting rate-limit state.
+    rate_limit_headers: RateLimitHeaderNames,
+    /// `User-Agent` sent upstream when the client's is not passed through.
+    user_agent: HeaderValue,
+    /// Operator-configured headers stripped like hop-by-hop headers.
+    extra_hop_by_hop: Vec<HeaderName>,
+    /// Request headers removed for every route before auth plugins run.
+    strip_inbound: Vec<HeaderName>,
+    /// Response statuses labeled `error` in metrics and sampled logs.
+    error_status_codes: ErrorStatusCodes,
+    /// Auth plugin config keys that `x-oagw-auth-config-<key>` request
+    /// headers may override; empty disables overrides.
+    auth_config_overrides: Vec<String>,
+    /// Proxies whose `X-Forwarded-*` headers are kept; `None` leaves the
+    /// caller's address out of outbound requests.
+    trusted_proxies: Option<Vec<std::net::IpAddr>>,
+    /// Source of the time handed to auth plugins as [`AuthContext::now`].
+    clock: Arc<dyn Clock>,
+    /// Seconds added to `clock` (negative when it runs ahead).
+    clock_skew_correction_secs: i64,
+    /// Records every request sent upstream; a test hook.
+    #[cfg(any(test, feature = "test-utils"))]
+    outbound_capture: Option<crate::domain::test_support::OutboundCapture>,
+}
+
+/// Resolution retries applied before each upstream send.
+struct DnsRetry {
+    dns: Arc<DnsCache>,
+    max_retries: u32,
+    backoff: Duration,
+}
+
+/// What `forward` learned about a request, acted on once it completes.
+#[derive(Default)]
+struct ForwardNotes {
+    /// The matched route and its `log_redact_paths`, when the request was
+    /// sampled for detailed logging.
+    sampled_route: Option<(Uuid, Vec<String>)>,
+    /// The resolved upstream and route, to attribute errors to.
+    target: Option<(Uuid, Uuid)>,
+    /// State of the tightest rate-limit bucket the request consumed from.
+    rate_limit: Option<RateLimitStatus>,
+    /// The upstream whose circuit admitted the request, and its thresholds.
+    circuit: Option<(Uuid, CircuitSettings)>,
+    /// Where and for how long to store the response for idempotency-key
+    /// replay.
+    idempotency: Option<(String, Duration)>,
+    /// Receives the matched route's `max_total_duration_ms` so the caller
+    /// can enforce it while `forward` is still running.
+    sla: Option<watch::Sender<Option<Duration>>>,
+    /// `Deprecation`/`Sunset` headers of the matched route, added to the
+    /// response.
+    deprecation: Vec<(HeaderName, HeaderValue)>,
+    /// Connection slot on the upstream host, held until the response body
+    /// has been consumed.
+    host_slot: Option<OwnedSemaphorePermit>,
+    /// In-flight slot on the upstream, held like `host_slot`.
+    upstream_slot: Option<UpstreamSlot>,
+    /// The auth plugin that picked one of several credentials, and the one
+    /// it picked; told the upstream's response status.
+    credential: Option<(Arc<dyn AuthPlugin>, String)>,
+    /// Set for [`DataPlaneService::trace_request`] runs.
+    trace: Option<TraceState>,
+    /// When the request arrived, before waiting for a concurrency slot;
+    /// the start of the budget sent in the upstream's `budget_header`.
+    arrived: Option<Instant>,
+}
+
+impl ForwardNotes {
+    /// Record into the trace when the request is being traced.
+    fn trace(&mut self, record: impl FnOnce(&mut RequestTrace)) {
+        if let Some(state) = &mut self.trace {
+            record(&mut state.trace);
+        }
+    }
+}
+
+/// A traced request in progress.
+struct TraceState {
+    trace: RequestTrace,
+    /// Stands in for the upstream's response; `None` ends the trace before
+    /// sending.
+    mock_response: Option<http::Response<Body>>,
+    /// The trace ended before sending, so `forward`'s response is a
+    /// placeholder.
+    stopped: bool,
+}
+
+/// Outcome of route resolution once the unmatched-route behaviour applies.
+/// Short-lived, so the large `Target` variant is not boxed.
+#[allow(clippy::large_enum_variant)]
+enum Resolution {
+    /// Proxy to this upstream under this route.
+    Target(Upstream, Route),
+    /// Answer with this response; nothing is proxied.
+    Respond(http::Response<Body>),
 }
 
 impl DataPlaneServiceImpl {
@@ -65,8 +296,13 @@ impl DataPlaneServiceImpl {
         backend_selector: Arc<dyn EndpointSelector>,
         proxy: Arc<HttpProxy<PingoraProxy>>,
     ) -> Self {
-        let auth_registry =
-            AuthPluginRegistry::with_builtins(credstore, token_http_config, token_cache_config);
+        let metrics = OagwMetrics::new(&opentelemetry::global::meter("oagw"));
+        let auth_registry = AuthPluginRegistry::with_builtins(
+            credstore.clone(),
+            token_http_config,
+            token_cache_config,
+            &metrics,
+        );
         let rate_limiter = RateLimiter::new();
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
 
@@ -76,1006 +312,10468 @@ impl DataPlaneServiceImpl {
             proxy,
             _shutdown_tx: shutdown_tx,
             shutdown_rx,
+            credstore,
             auth_registry,
             rate_limiter,
-            request_timeout: REQUEST_TIMEOUT,
+            circuit_breaker: CircuitBreaker::new(),
+            latency: LatencyTracker::new(),
+            circuit_defaults: CircuitSettings {
+                failure_threshold: 5,
+                cooldown: Duration::from_secs(30),
+                half_open_probes: 3,
+            },
+            concurrency_limiter: ConcurrencyLimiter::unlimited(),
+            host_limiter: HostConcurrencyLimiter::unlimited(),
+            upstream_limiter: UpstreamConcurrencyLimiter::unlimited(),
+            stream_slots: None,
+            queue_wait_timeout: None,
+            dns_retry: None,
+            connect_retry: None,
+            srv_targets: SrvTargets::new(HashMap::new()),
+            runtime: Arc::new(arc_swap::ArcSwap::from_pointee(RuntimeConfig {
+                request_timeout: REQUEST_TIMEOUT,
+                max_body_size_bytes: MAX_BODY_SIZE,
+                error_body_template: None,
+            })),
             policy_enforcer,
+            authz_timeout: Some(AUTHZ_TIMEOUT),
+            authz_retries: 0,
+            authz_on_error: AuthzErrorPolicy::default(),
+            authz_mode: AuthzMode::default(),
             allow_http_upstream: false,
-            max_body_size: MAX_BODY_SIZE,
+            max_url_length: MAX_URL_LENGTH,
+            max_query_params: None,
+            bodiless_method_body: BodilessMethodBody::default(),
+            max_response_body_size: MAX_BODY_SIZE,
+            stream_unframed_responses: false,
+            body_transformers: None,
+            transform_hook: TransformHook::new(TRANSFORM_HOOK_TIMEOUT),
+            readiness: Arc::new(ReadinessState::default()),
+            readiness_probe: None,
+            response_cache: Arc::new(InMemoryResponseCache::default()),
+            private_response_cache: false,
+            head_from_response_cache: true,
+            compress_min_size: None,
+            metrics,
+            stream_idle_timeout: None,
+            body_read_idle_timeout: None,
+            log_sampler: LogSampler::new(),
+            recent_errors: RecentErrors::default(),
+            unmatched_route: UnmatchedRouteBehavior::default(),
+            rate_limit_headers: RateLimitHeaderNames::default(),
+            user_agent: HeaderValue::from_static(DEFAULT_USER_AGENT),
+            extra_hop_by_hop: Vec::new(),
+            strip_inbound: Vec::new(),
+            error_status_codes: ErrorStatusCodes::default(),
+            auth_config_overrides: Vec::new(),
+            trusted_proxies: None,
+            clock: Arc::new(SystemClock),
+            clock_skew_correction_secs: 0,
+            #[cfg(any(test, feature = "test-utils"))]
+            outbound_capture: None,
         }
     }
 
-    /// Override the request timeout.
+    /// Record data-plane metrics on the given instruments, including those
+    /// of the built-in auth plugins.
     #[must_use]
-    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
-        self.request_timeout = timeout;
+    pub fn with_metrics(self, metrics: OagwMetrics) -> Self {
+        self.metrics.redirect(&metrics);
         self
     }
 
-    /// Override the maximum request body size.
+    /// Replace the default in-process response cache (e.g. with a shared
+    /// backend registered in the `ClientHub`).
     #[must_use]
-    pub fn with_max_body_size(mut self, size: usize) -> Self {
-        self.max_body_size = size;
+    pub fn with_response_cache(mut self, cache: Arc<dyn ResponseCache>) -> Self {
+        self.response_cache = cache;
         self
     }
 
-    /// Allow HTTP (non-TLS) upstream connections.
+    /// Also cache responses marked `Cache-Control: private`, keyed by the
+    /// caller's subject so that they are never served to anyone else.
     #[must_use]
-    pub fn with_allow_http_upstream(mut self, allow: bool) -> Self {
-        self.allow_http_upstream = allow;
+    pub fn with_private_response_cache(mut self, enabled: bool) -> Self {
+        self.private_response_cache = enabled;
         self
     }
 
-    /// Two-tier endpoint selection (D1):
-    /// 1. `X-OAGW-Target-Host` header → validate against endpoint list
-    /// 2. Round-robin via `BackendSelector` for multi-endpoint, direct for single
-    async fn select_endpoint(
-        &self,
-        upstream: &Upstream,
-        req_headers: &http::HeaderMap,
-        instance_uri: &str,
-    ) -> Result<Endpoint, DomainError> {
-        let endpoints = &upstream.server.endpoints;
+    /// Answer `HEAD` requests from a fresh cached `GET` response of the same
+    /// resource, returning its headers without the body.
+    #[must_use]
+    pub fn with_head_from_response_cache(mut self, enabled: bool) -> Self {
+        self.head_from_response_cache = enabled;
+        self
+    }
 
-        if endpoints.is_empty() {
-            return Err(DomainError::DownstreamError {
-                detail: "upstream has no endpoints".into(),
-                instance: instance_uri.to_string(),
-            });
-        }
+    /// Gzip buffered responses of at least `min_size` bytes that the
+    /// upstream sent uncompressed, when the client accepts gzip.
+    #[must_use]
+    pub fn with_response_compression(mut self, enabled: bool, min_size: usize) -> Self {
+        self.compress_min_size = enabled.then_some(min_size);
+        self
+    }
 
-        // Tier 1: Explicit selection via X-OAGW-Target-Host header.
-        if let Some(target_host) = req_headers
-            .get("x-oagw-target-host")
-            .and_then(|v| v.to_str().ok())
-        {
-            // Validate format: allowlist of safe hostname/IP characters.
-            // Rejects null bytes, @, \, Unicode homoglyphs, and port/path syntax.
-            if target_host.is_empty()
-                || !target_host
-                    .bytes()
-                    .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_'))
-            {
-                return Err(DomainError::InvalidTargetHost {
-                    instance: instance_uri.to_string(),
-                });
-            }
+    /// Share the readiness milestones tracked by the module lifecycle.
+    #[must_use]
+    pub(crate) fn with_readiness(mut self, readiness: Arc<ReadinessState>) -> Self {
+        self.readiness = readiness;
+        self
+    }
 
-            // Find matching endpoint by host.
-            return endpoints
-                .iter()
-                .find(|ep| ep.host.eq_ignore_ascii_case(target_host))
-                .cloned()
-                .ok_or_else(|| {
-                    let valid_hosts: Vec<&str> =
-                        endpoints.iter().map(|ep| ep.host.as_str()).collect();
-                    tracing::warn!(
-                        target_host,
-                        ?valid_hosts,
-                        "X-OAGW-Target-Host does not match any configured endpoint"
-                    );
-                    DomainError::UnknownTargetHost {
-                        detail: format!(
-                            "X-OAGW-Target-Host '{}' does not match any configured endpoint",
-                            target_host
-                        ),
-                        instance: instance_uri.to_string(),
-                    }
-                });
-        }
+    /// Require a sample upstream (`host:port`) to be reachable for readiness.
+    #[must_use]
+    pub fn with_readiness_probe(mut self, endpoint: Option<String>) -> Self {
+        self.readiness_probe = endpoint;
+        self
+    }
 
-        // Tier 2: Automatic selection.
-        if endpoints.len() == 1 {
-            // Single-endpoint: use directly, no LB overhead.
-            return Ok(endpoints[0].clone());
-        }
+    /// Override the request timeout.
+    #[cfg(any(test, feature = "test-utils"))]
+    #[must_use]
+    pub fn with_request_timeout(self, timeout: Duration) -> Self {
+        self.runtime.rcu(|current| RuntimeConfig {
+            request_timeout: timeout,
+            ..RuntimeConfig::clone(current)
+        });
+        self
+    }
 
-        // Multi-endpoint: round-robin via BackendSelector.
-        self.backend_selector
-            .select(upstream.id, endpoints)
-            .await
-            .ok_or_else(|| DomainError::DownstreamError {
-                detail: "all backends are unhealthy".into(),
-                instance: instance_uri.to_string(),
-            })
+    /// Read the request timeout and body limit from `runtime`, so that
+    /// replacing its contents takes effect on the next request. The
+    /// limits it holds replace any set before.
+    #[must_use]
+    pub fn with_runtime_config(mut self, runtime: SharedRuntimeConfig) -> Self {
+        self.runtime = runtime;
+        self
     }
-}
 
-#[async_trait]
-impl DataPlaneService for DataPlaneServiceImpl {
-    async fn proxy_request(
-        &self,
-        ctx: SecurityContext,
-        req: http::Request<Body>,
-    ) -> Result<http::Response<Body>, DomainError> {
-        let instance_uri = req.uri().to_string();
+    /// Cap the number of in-flight proxy requests across the gateway.
+    /// Requests beyond the cap wait up to `queue_timeout` for a slot.
+    #[must_use]
+    pub fn with_global_concurrency(
+        mut self,
+        max_in_flight: Option<usize>,
+        queue_timeout: Duration,
+    ) -> Self {
+        self.concurrency_limiter = ConcurrencyLimiter::new(max_in_flight, queue_timeout);
+        self
+    }
 
-        self.policy_enforcer
-            .access_scope_with(
-                &ctx,
-                &resources::PROXY,
-                actions::INVOKE,
-                None,
-                &AccessRequest::new()
-                    .require_constraints(false)
-                    .context_tenant_id(ctx.subject_tenant_id()),
-            )
-            .await?;
+    /// Cap simultaneous connections to each upstream host (`host:port`).
+    /// Requests beyond the cap wait for a free connection; see
+    /// [`Self::with_queue_wait_timeout`].
+    #[must_use]
+    pub fn with_max_connections_per_host(mut self, max: Option<usize>) -> Self {
+        self.host_limiter = HostConcurrencyLimiter::new(max);
+        self
+    }
 
-        // Extract alias from the raw path first, then normalize only the
-        // suffix. This prevents path traversal (e.g. `/../../admin/...`)
-        // from influencing alias extraction.
-        let (alias, path_suffix) = {
-            let path = req.uri().path();
-            let trimmed = path.strip_prefix('/').unwrap_or(path);
-            let (alias, raw_suffix) = match trimmed.find('/') {
-                Some(pos) => (&trimmed[..pos], &trimmed[pos..]),
-                None => (trimmed, ""),
-            };
-            (alias.to_string(), normalize_path(raw_suffix))
-        };
+    /// Cap in-flight requests to each upstream. Requests beyond the cap
+    /// queue per tenant and freed slots go to the queued tenants in turn;
+    /// see [`Self::with_queue_wait_timeout`].
+    #[must_use]
+    pub fn with_max_concurrent_requests_per_upstream(mut self, max: Option<usize>) -> Self {
+        self.upstream_limiter = UpstreamConcurrencyLimiter::new(max);
+        self
+    }
 
-        // Parse query parameters with proper URL decoding.
-        let query_params: Vec<(String, String)> = req
-            .uri()
-            .query()
-            .map(|q| {
-                form_urlencoded::parse(q.as_bytes())
-                    .map(|(k, v)| (k.into_owned(), v.into_owned()))
-                    .collect()
-            })
-            .unwrap_or_default();
+    /// Bound the wait for a host connection or upstream slot. A request
+    /// still waiting after `timeout` fails with `GatewayOverloaded` (`503`) rather than a
+    /// timeout, and the wait never eats into the upstream timeout. `None`
+    /// waits up to the request timeout and fails with `RequestTimeout`.
+    #[must_use]
+    pub fn with_queue_wait_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.queue_wait_timeout = timeout;
+        self
+    }
 
-        // Decompose request into parts. Keep body as-is for conditional handling.
-        let (parts, body) = req.into_parts();
-        let method = parts.method;
-        let req_headers = parts.headers;
+    /// Retry a refused or timed-out connect to an endpoint up to
+    /// `max_retries` times, `backoff` apart. The upstream never received
+    /// those attempts, so they do not count against upstream retry or
+    /// failover budgets. Zero retries disables this.
+    #[must_use]
+    pub fn with_connect_retry(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.connect_retry = (max_retries > 0).then_some((max_retries, backoff));
+        self
+    }
 
-        // Reject WebSocket upgrade requests — the current bridge is unidirectional
-        // and cannot support the bidirectional tunnel that WebSocket requires.
-        if req_headers
-            .get(http::header::UPGRADE)
-            .and_then(|v| v.to_str().ok())
-            .is_some_and(|v| {
-                v.split(',')
-                    .any(|t| t.trim().eq_ignore_ascii_case("websocket"))
-            })
-        {
-            return Err(DomainError::ProtocolError {
-                detail: "WebSocket upgrade is not supported by the proxy".into(),
-                instance: instance_uri,
-            });
-        }
+    /// Resolve each endpoint through `dns` before sending, retrying lookup
+    /// failures up to `max_retries` times `backoff` apart. These retries do
+    /// not count against upstream retry or failover budgets. `dns` must be
+    /// the cache the proxy dials from, so a successful retry is reused for
+    /// the connection. Zero retries disables this.
+    #[must_use]
+    pub fn with_dns_retry(
+        mut self,
+        dns: Arc<DnsCache>,
+        max_retries: u32,
+        backoff: Duration,
+    ) -> Self {
+        self.dns_retry = (max_retries > 0).then_some(DnsRetry {
+            dns,
+            max_retries,
+            backoff,
+        });
+        self
+    }
 
-        // Conditional body conversion — keep streams for streaming request bodies.
-        let max_body = self.max_body_size;
-        let (body_bytes, body_stream): (Bytes, Option<BodyStream>) = match body {
-            Body::Empty => (Bytes::new(), None),
-            Body::Bytes(b) => {
-                if b.len() > max_body {
-                    return Err(DomainError::PayloadTooLarge {
-                        detail: format!(
-                            "request body of {} bytes exceeds maximum of {max_body} bytes",
-                            b.len()
-                        ),
-                        instance: instance_uri,
-                    });
-                }
-                (b, None)
-            }
-            Body::Stream(s) => (Bytes::new(), Some(s)),
-        };
+    /// End server-sent event responses with `terminal_event` once no chunk
+    /// has arrived from the upstream for `timeout`. `None` disables this.
+    #[must_use]
+    pub fn with_stream_idle_timeout(
+        mut self,
+        timeout: Option<Duration>,
+        terminal_event: impl Into<Bytes>,
+    ) -> Self {
+        let terminal_event = terminal_event.into();
+        self.stream_idle_timeout = timeout.map(|timeout| StreamIdleTimeout {
+            timeout,
+            terminal_event,
+        });
+        self
+    }
 
-        // 1+2. Resolve upstream + route in one pass (single hierarchy walk).
-        let (upstream, route) = self
-            .cp
-            .resolve_proxy_target(&ctx, &alias, method.as_ref(), &path_suffix)
-            .await?;
+    /// Cap server-sent event responses in flight at once. A stream beyond
+    /// the cap is refused with `GatewayOverloaded` (`503`) and its upstream
+    /// connection closed; streams already running are unaffected. `None`
+    /// disables this.
+    #[must_use]
+    pub fn with_max_concurrent_streams(mut self, max: Option<usize>) -> Self {
+        self.stream_slots = max.map(|n| Arc::new(Semaphore::new(n)));
+        self
+    }
 
-        // 2b. Validate query parameters against route's allowlist.
-        if let Some(ref http_match) = route.match_rules.http
-            && !query_params.is_empty()
-        {
-            for (key, _) in &query_params {
-                if !http_match.query_allowlist.contains(key) {
-                    return Err(DomainError::Validation {
-                        detail: format!(
-                            "query parameter '{}' is not in the route's query_allowlist",
-                            key
-                        ),
-                        instance: instance_uri,
-                    });
-                }
-            }
-        }
+    /// Send requests for an endpoint host listed in `targets` to one of its
+    /// targets: the lowest priority value wins, weights share load within
+    /// it, and lower priorities are tried as failover endpoints ahead of the
+    /// upstream's own.
+    #[must_use]
+    pub fn with_srv_targets(mut self, targets: HashMap<String, Vec<SrvTarget>>) -> Self {
+        self.srv_targets = SrvTargets::new(targets);
+        self
+    }
 
-        // 2c. Enforce path_suffix_mode.
-        if let Some(ref http_match) = route.match_rules.http
-            && http_match.path_suffix_mode == PathSuffixMode::Disabled
-        {
-            let route_path = &http_match.path;
-            let extra = path_suffix.strip_prefix(route_path.as_str()).unwrap_or("");
-            if !extra.is_empty() {
-                return Err(DomainError::Validation {
-                    detail: format!(
-                        "path suffix not allowed: route path_suffix_mode is disabled but request has extra path '{}'",
-                        extra
-                    ),
-                    instance: instance_uri,
-                });
-            }
-        }
+    /// Abort a request with 408 once its client has sent no body data for
+    /// `timeout`. `None` disables this.
+    #[must_use]
+    pub fn with_body_read_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.body_read_idle_timeout = timeout;
+        self
+    }
 
-        // 3. Prepare outbound headers (passthrough + strip).
-        let mode = upstream
-            .headers
-            .as_ref()
-            .and_then(|h| h.request.as_ref())
-            .map_or(PassthroughMode::None, |r| r.passthrough);
-        let allowlist: Vec<String> = upstream
-            .headers
-            .as_ref()
-            .and_then(|h| h.request.as_ref())
-            .map_or_else(Vec::new, |r| r.passthrough_allowlist.clone());
-        let mut outbound_headers = headers::apply_passthrough(&req_headers, &mode, &allowlist);
-        headers::strip_hop_by_hop(&mut outbound_headers);
-        headers::strip_internal_headers(&mut outbound_headers);
+    /// Override the maximum request body size.
+    #[cfg(any(test, feature = "test-utils"))]
+    #[must_use]
+    pub fn with_max_body_size(self, size: usize) -> Self {
+        self.runtime.rcu(|current| RuntimeConfig {
+            max_body_size_bytes: size,
+            ..RuntimeConfig::clone(current)
+        });
+        self
+    }
 
-        // 4. Execute auth plugin.
-        if let Some(ref auth) = upstream.auth {
-            let plugin = self.auth_registry.resolve(&auth.plugin_type).map_err(|e| {
-                DomainError::AuthenticationFailed {
-                    detail: e.to_string(),
-                    instance: instance_uri.clone(),
-                }
-            })?;
-            let auth_headers: HashMap<String, String> = outbound_headers
-                .iter()
-                .filter_map(|(k, v)| {
-                    v.to_str()
-                        .ok()
-                        .map(|s| (k.as_str().to_string(), s.to_string()))
-                })
-                .collect();
+    /// Current upstream request timeout.
+    fn request_timeout(&self) -> Duration {
+        self.runtime.load().request_timeout
+    }
+
+    /// Current maximum request body size, for buffered and streaming bodies.
+    fn max_body_size(&self) -> usize {
+        self.runtime.load().max_body_size_bytes
+    }
+
+    /// Configure upstream responses framed by neither `Content-Length` nor
+    /// chunked encoding: buffered up to `max_size` bytes (so body rewrites,
+    /// caching and body retries apply), or passed through as streams.
+    #[must_use]
+    pub fn with_unframed_responses(mut self, max_size: usize, stream: bool) -> Self {
+        self.max_response_body_size = max_size;
+        self.stream_unframed_responses = stream;
+        self
+    }
+
+    /// Look up route `body_transformers` in `hub`, where they are registered
+    /// as scoped [`BodyTransformer`] clients named by their scope.
+    #[must_use]
+    pub fn with_body_transformers(mut self, hub: Arc<ClientHub>) -> Self {
+        self.body_transformers = Some(hub);
+        self
+    }
+
+    /// Bound each call to a route's `transform_hook_url`; the request fails
+    /// with `504` once it expires.
+    #[must_use]
+    pub fn with_transform_hook_timeout(mut self, timeout: Duration) -> Self {
+        self.transform_hook = TransformHook::new(timeout);
+        self
+    }
+
+    /// Let the API key plugin resolve `secret_ref`s with schemes other than
+    /// `cred://` (e.g. `env://`, `file://`) through the `SecretResolver`
+    /// registered in `hub` under the scheme.
+    #[must_use]
+    pub fn with_secret_resolvers(mut self, hub: Arc<ClientHub>) -> Self {
+        self.auth_registry.register(
+            APIKEY_AUTH_PLUGIN_ID,
+            Arc::new(ApiKeyAuthPlugin::new(self.credstore.clone()).with_secret_resolvers(hub)),
+        );
+        self
+    }
+
+    /// Override the maximum request URL (path and query) length.
+    #[must_use]
+    pub fn with_max_url_length(mut self, len: usize) -> Self {
+        self.max_url_length = len;
+        self
+    }
+
+    /// Cap the number of query parameters, counted once the route's
+    /// stripped parameters are dropped and its API version parameter is
+    /// added. `None` disables the cap.
+    #[must_use]
+    pub fn with_max_query_params(mut self, max: Option<usize>) -> Self {
+        self.max_query_params = max;
+        self
+    }
+
+    /// Set what to do with a body sent on `GET` or `HEAD`.
+    #[must_use]
+    pub fn with_bodiless_method_body(mut self, policy: BodilessMethodBody) -> Self {
+        self.bodiless_method_body = policy;
+        self
+    }
+
+    /// Seed the request-log sampler so that sampling is reproducible.
+    #[must_use]
+    pub fn with_log_sampling_seed(mut self, seed: u64) -> Self {
+        self.log_sampler = LogSampler::with_seed(seed);
+        self
+    }
+
+    /// Keep the last `capacity` gateway-generated proxy errors for
+    /// [`DataPlaneService::recent_errors`]; `0` keeps none.
+    #[must_use]
+    pub fn with_recent_errors(mut self, capacity: usize) -> Self {
+        self.recent_errors = RecentErrors::new(capacity);
+        self
+    }
+
+    /// Set what to answer when no route matches a request.
+    #[must_use]
+    pub fn with_unmatched_route(mut self, behavior: UnmatchedRouteBehavior) -> Self {
+        self.unmatched_route = behavior;
+        self
+    }
+
+    /// Rename the headers reporting rate-limit state.
+    #[must_use]
+    pub fn with_rate_limit_headers(mut self, names: RateLimitHeaderNames) -> Self {
+        self.rate_limit_headers = names;
+        self
+    }
+
+    /// Set the `User-Agent` sent upstream. An invalid value is ignored
+    /// with a warning and the default is kept.
+    #[must_use]
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        match HeaderValue::from_str(user_agent) {
+            Ok(v) => self.user_agent = v,
+            Err(_) => tracing::warn!(user_agent, "invalid user_agent, keeping default"),
+        }
+        self
+    }
+
+    /// Correct the time given to auth plugins by `secs` (negative when the
+    /// local clock runs ahead of the upstreams').
+    #[must_use]
+    pub fn with_clock_skew_correction(mut self, secs: i64) -> Self {
+        self.clock_skew_correction_secs = secs;
+        self
+    }
+
+    /// Read time from `clock` instead of the system clock: for auth plugin
+    /// signing, circuit-breaker cooldowns and rate-limit refills. Call before
+    /// serving traffic; breaker and bucket state is reset.
+    #[cfg(any(test, feature = "test-utils"))]
+    #[must_use]
+    pub(crate) fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.circuit_breaker = CircuitBreaker::with_clock(clock.clone());
+        self.rate_limiter = RateLimiter::with_clock(clock.clone());
+        self.clock = clock;
+        self
+    }
+
+    /// Record every request sent upstream in `capture`.
+    #[cfg(any(test, feature = "test-utils"))]
+    #[must_use]
+    pub(crate) fn with_outbound_capture(
+        mut self,
+        capture: crate::domain::test_support::OutboundCapture,
+    ) -> Self {
+        self.outbound_capture = Some(capture);
+        self
+    }
+
+    /// Report a request about to be sent upstream to the test capture.
+    #[cfg(any(test, feature = "test-utils"))]
+    fn capture_outbound(&self, method: &http::Method, url: &str, headers: &HeaderMap) {
+        if let Some(capture) = &self.outbound_capture {
+            capture.record(method, url, headers);
+        }
+    }
+
+    /// The clock's time with the skew correction applied.
+    fn trusted_now(&self) -> SystemTime {
+        let now = self.clock.now();
+        let shift = Duration::from_secs(self.clock_skew_correction_secs.unsigned_abs());
+        if self.clock_skew_correction_secs >= 0 {
+            now.checked_add(shift).unwrap_or(now)
+        } else {
+            now.checked_sub(shift).unwrap_or(now)
+        }
+    }
+
+    /// Disable the auth plugins with the given GTS identifiers: requests to
+    /// routes using them fail with `503` `PluginDisabled`.
+    #[must_use]
+    pub fn with_disabled_auth_plugins(mut self, plugin_ids: &[String]) -> Self {
+        for plugin_id in plugin_ids {
+            self.auth_registry.set_enabled(plugin_id, false);
+        }
+        self
+    }
+
+    /// Strip `names` from requests and responses in addition to the standard
+    /// hop-by-hop headers. Invalid names are ignored with a warning.
+    #[must_use]
+    pub fn with_extra_hop_by_hop_headers(mut self, names: &[String]) -> Self {
+        self.extra_hop_by_hop = names
+            .iter()
+            .filter_map(|name| match HeaderName::from_bytes(name.as_bytes()) {
+                Ok(n) => Some(n),
+                Err(_) => {
+                    tracing::warn!(header = %name, "invalid extra hop-by-hop header, ignoring");
+                    None
+                }
+            })
+            .collect();
+        self
+    }
+
+    /// Remove `names` from every proxied request before auth plugins run;
+    /// routes may add their own. Invalid names are ignored with a warning.
+    #[must_use]
+    pub fn with_strip_inbound_headers(mut self, names: &[String]) -> Self {
+        self.strip_inbound = names
+            .iter()
+            .filter_map(|name| match HeaderName::from_bytes(name.as_bytes()) {
+                Ok(n) => Some(n),
+                Err(_) => {
+                    tracing::warn!(header = %name, "invalid inbound header to strip, ignoring");
+                    None
+                }
+            })
+            .collect();
+        self
+    }
+
+    /// Label responses with these statuses `error` in metrics and sampled
+    /// logs instead of every `5xx`.
+    #[must_use]
+    pub fn with_error_status_codes(mut self, codes: &[u16]) -> Self {
+        self.error_status_codes = ErrorStatusCodes::new(codes.iter().copied());
+        self
+    }
+
+    /// Let `x-oagw-auth-config-<key>` request headers override the listed
+    /// auth plugin config keys (e.g. `secret_ref`) for that request.
+    #[must_use]
+    pub fn with_auth_config_overrides(mut self, keys: &[String]) -> Self {
+        self.auth_config_overrides = keys.to_vec();
+        self
+    }
+
+    /// Forward the caller's address upstream in `X-Forwarded-For` and set
+    /// `X-Forwarded-Proto`/`-Host`. Inbound values are only trusted when the
+    /// request came from one of `trusted_proxies`.
+    #[must_use]
+    pub fn with_forwarded_headers(mut self, trusted_proxies: Vec<std::net::IpAddr>) -> Self {
+        self.trusted_proxies = Some(trusted_proxies);
+        self
+    }
+
+    /// Circuit breaker thresholds for upstreams without overrides; a zero
+    /// `failure_threshold` disables the breaker.
+    #[must_use]
+    pub fn with_circuit_breaker(
+        mut self,
+        failure_threshold: u32,
+        cooldown_secs: u64,
+        half_open_probes: u32,
+    ) -> Self {
+        self.circuit_defaults = CircuitSettings {
+            failure_threshold,
+            cooldown: Duration::from_secs(cooldown_secs),
+            half_open_probes: half_open_probes.max(1),
+        };
+        self
+    }
+
+    /// Bound each AuthZ evaluation by `timeout` (`None` waits indefinitely),
+    /// retry timeouts and resolver failures `retries` times, then apply
+    /// `on_error`.
+    #[must_use]
+    pub fn with_authz_failure_handling(
+        mut self,
+        timeout: Option<Duration>,
+        retries: u32,
+        on_error: AuthzErrorPolicy,
+    ) -> Self {
+        self.authz_timeout = timeout;
+        self.authz_retries = retries;
+        self.authz_on_error = on_error;
+        self
+    }
+
+    /// Decide proxy AuthZ by `mode`: `AllowAll` and `DenyAll` answer
+    /// without evaluating the policy enforcer.
+    #[must_use]
+    pub fn with_authz_mode(mut self, mode: AuthzMode) -> Self {
+        self.authz_mode = mode;
+        self
+    }
+
+    /// Allow HTTP (non-TLS) upstream connections.
+    #[must_use]
+    pub fn with_allow_http_upstream(mut self, allow: bool) -> Self {
+        self.allow_http_upstream = allow;
+        self
+    }
+
+    /// Keep `err` among the recent errors, attributed to `target`
+    /// (`(upstream_id, route_id)`) when known.
+    fn record_error(&self, target: Option<(Uuid, Uuid)>, err: &DomainError) {
+        self.recent_errors.record(self.clock.now(), target, err);
+    }
+
+    /// Set `upstream`'s `budget_header` to the milliseconds left of the
+    /// request's budget: the route's `max_total_duration_ms`, or else
+    /// `timeout`, counted from `arrived`. Never more than `timeout`, which
+    /// bounds the upstream call itself.
+    fn set_budget_header(
+        &self,
+        upstream: &Upstream,
+        route: &Route,
+        arrived: Option<Instant>,
+        timeout: Duration,
+        headers: &mut HeaderMap,
+    ) {
+        let Some(name) = upstream
+            .budget_header
+            .as_deref()
+            .and_then(|name| HeaderName::from_bytes(name.as_bytes()).ok())
+        else {
+            return;
+        };
+        let budget = route
+            .max_total_duration_ms
+            .map_or(timeout, Duration::from_millis);
+        let spent = arrived.map_or(Duration::ZERO, |at| {
+            self.clock.instant().saturating_duration_since(at)
+        });
+        let remaining = budget.saturating_sub(spent).min(timeout);
+        let millis = u64::try_from(remaining.as_millis()).unwrap_or(u64::MAX);
+        headers.insert(name, HeaderValue::from(millis));
+    }
+
+    /// AuthZ decision for the trace, given `authorize`'s result for `route`.
+    fn trace_authz(&self, route: &Route, result: &Result<(), DomainError>) -> TraceAuthz {
+        if result.is_err() {
+            TraceAuthz::Denied
+        } else if route.skip_authz || self.authz_mode == AuthzMode::AllowAll {
+            TraceAuthz::Skipped
+        } else {
+            TraceAuthz::Allowed
+        }
+    }
+
+    /// [`Self::authorize`] the request for `route` of `upstream_id`,
+    /// recording the decision in the request trace.
+    async fn authorize_target(
+        &self,
+        ctx: &SecurityContext,
+        route: &Route,
+        upstream_id: Uuid,
+        method: &http::Method,
+        path: &str,
+        notes: &mut ForwardNotes,
+    ) -> Result<(), DomainError> {
+        let authz = self
+            .authorize(ctx, route, Some(upstream_id), method, path)
+            .await;
+        notes.trace(|t| t.authz = Some(self.trace_authz(route, &authz)));
+        authz
+    }
+
+    /// AuthZ for a matched route. Routes explicitly marked public bypass the
+    /// PDP call, but not `deny_all`; static routes have no upstream to
+    /// report.
+    async fn authorize(
+        &self,
+        ctx: &SecurityContext,
+        route: &Route,
+        upstream_id: Option<Uuid>,
+        method: &http::Method,
+        path: &str,
+    ) -> Result<(), DomainError> {
+        if self.authz_mode == AuthzMode::DenyAll {
+            return Err(DomainError::forbidden(
+                "access denied: authz_mode is deny_all",
+            ));
+        }
+        if route.skip_authz {
+            tracing::info!(
+                route_id = %route.id,
+                upstream_id = ?upstream_id,
+                "authz bypassed for public route"
+            );
+            return Ok(());
+        }
+        if self.authz_mode == AuthzMode::AllowAll {
+            return Ok(());
+        }
+        let mut request = AccessRequest::new()
+            .require_constraints(false)
+            .context_tenant_id(ctx.subject_tenant_id())
+            .resource_property(properties::ROUTE_ID, route.id);
+        if let Some(upstream_id) = upstream_id {
+            request = request.resource_property(properties::UPSTREAM_ID, upstream_id);
+        }
+        let request = request
+            .resource_property(properties::METHOD, method.as_str())
+            .resource_property(properties::PATH, path);
+        let mut failure = String::new();
+        for attempt in 0..=self.authz_retries {
+            let evaluation = self.policy_enforcer.access_scope_with(
+                ctx,
+                &resources::PROXY,
+                actions::INVOKE,
+                None,
+                &request,
+            );
+            let result = match self.authz_timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, evaluation).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        failure = format!("authorization evaluation timed out after {timeout:?}");
+                        tracing::warn!(route_id = %route.id, attempt, "{failure}");
+                        continue;
+                    }
+                },
+                None => evaluation.await,
+            };
+            match result {
+                Ok(_) => return Ok(()),
+                Err(EnforcerError::EvaluationFailed(e)) => {
+                    failure = format!("authorization evaluation failed: {e}");
+                    tracing::warn!(route_id = %route.id, attempt, "{failure}");
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        match self.authz_on_error {
+            AuthzErrorPolicy::Allow => {
+                tracing::warn!(route_id = %route.id, "authz unavailable, allowing request (authz_on_error = allow)");
+                Ok(())
+            }
+            AuthzErrorPolicy::Deny => Err(DomainError::AuthzUnavailable {
+                detail: "authorization is temporarily unavailable".into(),
+                instance: String::new(),
+                retry_after_secs: AUTHZ_RETRY_AFTER_SECS,
+            }),
+        }
+    }
+
+    /// Run an auth plugin, recording its latency labeled by plugin ID and
+    /// outcome. On success, returns the headers the plugin set, changed or
+    /// removed.
+    async fn authenticate_timed(
+        &self,
+        plugin_id: &str,
+        plugin: &dyn AuthPlugin,
+        auth_ctx: &mut AuthContext,
+    ) -> Result<Vec<HeaderProvenance>, PluginError> {
+        let before = auth_ctx.headers.clone();
+        let started = Instant::now();
+        let result = plugin.authenticate(auth_ctx).await;
+        self.metrics
+            .record_auth_plugin(plugin_id, result.is_ok(), started.elapsed());
+        if let Err(e) = result {
+            // Whatever the plugin set before failing is discarded.
+            auth_ctx.headers = before;
+            auth_ctx.body_fields.clear();
+            auth_ctx.query.clear();
+            auth_ctx.early_response = None;
+            return Err(e);
+        }
+        Ok(auth_ctx
+            .headers
+            .changed_since(&before)
+            .into_iter()
+            .map(|header| HeaderProvenance {
+                plugin_id: plugin_id.to_string(),
+                header,
+            })
+            .collect())
+    }
+
+    /// Resolve `endpoint` with the configured DNS retries. A host that still
+    /// fails is left for the send to report as unresolvable.
+    async fn resolve_with_retry(&self, endpoint: &Endpoint) {
+        let Some(retry) = &self.dns_retry else {
+            return;
+        };
+        let mut attempt = 0;
+        loop {
+            match retry.dns.resolve(&endpoint.host, endpoint.port).await {
+                Ok(_) => return,
+                Err(e) if attempt < retry.max_retries => {
+                    attempt += 1;
+                    tracing::debug!(
+                        host = %endpoint.host,
+                        attempt,
+                        error = %e,
+                        "upstream host resolution failed, retrying"
+                    );
+                    tokio::time::sleep(retry.backoff).await;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        host = %endpoint.host,
+                        retries = retry.max_retries,
+                        error = %e,
+                        "upstream host resolution failed"
+                    );
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Wait for an in-flight slot on `upstream_id` for the caller's tenant,
+    /// bounded like [`Self::acquire_host_slot`].
+    async fn acquire_upstream_slot(
+        &self,
+        upstream_id: Uuid,
+        tenant_id: Uuid,
+        timeout: Duration,
+        instance_uri: &str,
+    ) -> Result<Option<UpstreamSlot>, DomainError> {
+        let wait = self.queue_wait_timeout.unwrap_or(timeout);
+        tokio::time::timeout(wait, self.upstream_limiter.acquire(upstream_id, tenant_id))
+            .await
+            .map_err(|_| {
+                let detail =
+                    format!("no slot on upstream {upstream_id} became free within {wait:?}");
+                let instance = instance_uri.to_string();
+                if self.queue_wait_timeout.is_some() {
+                    DomainError::GatewayOverloaded { detail, instance }
+                } else {
+                    DomainError::RequestTimeout { detail, instance }
+                }
+            })
+    }
+
+    /// Wait for a connection slot on `endpoint`'s host: up to the queue
+    /// wait timeout when one is configured, else up to `timeout`.
+    async fn acquire_host_slot(
+        &self,
+        endpoint: &Endpoint,
+        timeout: Duration,
+        instance_uri: &str,
+    ) -> Result<Option<OwnedSemaphorePermit>, DomainError> {
+        let host = format!("{}:{}", endpoint.host, endpoint.port);
+        let wait = self.queue_wait_timeout.unwrap_or(timeout);
+        tokio::time::timeout(wait, self.host_limiter.acquire(&host))
+            .await
+            .map_err(|_| {
+                let detail = format!("no connection to {host} became free within {wait:?}");
+                let instance = instance_uri.to_string();
+                if self.queue_wait_timeout.is_some() {
+                    DomainError::GatewayOverloaded { detail, instance }
+                } else {
+                    DomainError::RequestTimeout { detail, instance }
+                }
+            })
+    }
+
+    /// Buffer and store a `200 OK` response that carries explicit freshness
+    /// on a route with `cache_responses`, handing the buffered copy back to
+    /// the caller. One with a validator is also kept
+    /// [`response_cache::STALE_RETENTION`] past its freshness for
+    /// revalidation. With the route's `negative_cache_ttl_secs`, an upstream
+    /// 404 or 410 is stored under the shared key for at most that long. The
+    /// response to a `credentialed` request is only shared when it allows
+    /// it explicitly. Anything else, a `Vary: *` response, or a body larger
+    /// than `max_body_size`, is passed through untouched.
+    async fn store_if_cacheable(
+        &self,
+        keys: response_cache::CacheKeys,
+        route: &Route,
+        request: &HeaderMap,
+        credentialed: bool,
+        response: http::Response<Body>,
+        instance_uri: String,
+    ) -> Result<http::Response<Body>, DomainError> {
+        let status = response.status();
+        let Some(vary) = response_cache::vary_names(response.headers()) else {
+            return Ok(response);
+        };
+        let stored = if status == http::StatusCode::OK && route.cache_responses {
+            response_cache::cache_ttl(response.headers(), keys.private.is_some(), credentialed)
+                .and_then(|(ttl, scope)| Some((ttl, keys.for_scope(scope)?)))
+        } else if matches!(status, http::StatusCode::NOT_FOUND | http::StatusCode::GONE)
+            && response.extensions().get::<ErrorSource>() == Some(&ErrorSource::Upstream)
+        {
+            route
+                .negative_cache_ttl_secs
+                .map(Duration::from_secs)
+                .and_then(|ttl| {
+                    response_cache::negative_cache_ttl(response.headers(), ttl, credentialed)
+                })
+                .map(|ttl| (ttl, keys.shared.as_str()))
+        } else {
+            None
+        };
+        let Some((ttl, key)) = stored else {
+            return Ok(response);
+        };
+        if !self.fits_buffer(response.headers()) {
+            return Ok(response);
+        }
+
+        let (parts, body) = response.into_parts();
+        let body = read_response_body(body, &instance_uri).await?;
+        let cached = CachedResponse {
+            status: parts.status,
+            headers: parts.headers.clone(),
+            body: body.clone(),
+            stored_at: self.clock.now(),
+            fresh_for: ttl,
+        };
+        if status == http::StatusCode::OK
+            && !response_cache::revalidation_headers(&parts.headers).is_empty()
+        {
+            self.cache_put(
+                &response_cache::stale_key(key),
+                &vary,
+                request,
+                cached.clone(),
+                ttl + response_cache::STALE_RETENTION,
+            )
+            .await;
+        }
+        self.cache_put(key, &vary, request, cached, ttl).await;
+        Ok(http::Response::from_parts(parts, Body::Bytes(body)))
+    }
+
+    /// Store `cached` under `key`. A response that varies by request headers
+    /// goes under the variant `request` selects instead, with a body-less
+    /// copy at `key` recording its `Vary` for [`Self::cache_get`].
+    async fn cache_put(
+        &self,
+        key: &str,
+        vary: &[HeaderName],
+        request: &HeaderMap,
+        cached: CachedResponse,
+        ttl: Duration,
+    ) {
+        if vary.is_empty() {
+            self.response_cache.put(key, cached, ttl).await;
+            return;
+        }
+        let index = CachedResponse {
+            body: Bytes::new(),
+            ..cached.clone()
+        };
+        self.response_cache
+            .put(
+                &response_cache::variant_key(key, vary, request),
+                cached,
+                ttl,
+            )
+            .await;
+        self.response_cache.put(key, index, ttl).await;
+    }
+
+    /// Entry stored under `key` by [`Self::cache_put`], or the variant of it
+    /// matching `request`'s values of the headers the response varies by.
+    async fn cache_get(&self, key: &str, request: &HeaderMap) -> Option<CachedResponse> {
+        let entry = self.response_cache.get(key).await?;
+        let vary = response_cache::vary_names(&entry.headers)?;
+        if vary.is_empty() {
+            return Some(entry);
+        }
+        self.response_cache
+            .get(&response_cache::variant_key(key, &vary, request))
+            .await
+    }
+
+    /// Whether the request carries credentials its cache key does not
+    /// capture (RFC 9111 §3.5): `Authorization`, client headers the upstream
+    /// passes through, headers that select the route's auth, or auth config
+    /// override headers.
+    fn carries_credentials(&self, upstream: &Upstream, route: &Route, req: &HeaderMap) -> bool {
+        let passed_through = upstream
+            .headers
+            .as_ref()
+            .and_then(|h| h.request.as_ref())
+            .is_some_and(|r| match r.passthrough {
+                PassthroughMode::None => false,
+                PassthroughMode::All => true,
+                PassthroughMode::Allowlist => r
+                    .passthrough_allowlist
+                    .iter()
+                    .any(|name| req.contains_key(name.as_str())),
+            });
+        req.contains_key(http::header::AUTHORIZATION)
+            || passed_through
+            || route
+                .auth_rules
+                .iter()
+                .any(|rule| matches!(rule.condition, AuthCondition::Header { .. }))
+            || (!self.auth_config_overrides.is_empty()
+                && req
+                    .keys()
+                    .any(|name| name.as_str().starts_with(AUTH_CONFIG_OVERRIDE_PREFIX)))
+    }
+
+    /// Buffer and store a response for idempotency-key replay, handing the
+    /// buffered copy back to the caller. Server errors and gateway-originated
+    /// responses are not stored so that a retry reaches the upstream again;
+    /// neither are bodies larger than `max_body_size`.
+    async fn store_idempotent(
+        &self,
+        key: String,
+        ttl: Duration,
+        response: http::Response<Body>,
+        instance_uri: &str,
+    ) -> Result<http::Response<Body>, DomainError> {
+        if response.status().is_server_error()
+            || response.extensions().get::<ErrorSource>() != Some(&ErrorSource::Upstream)
+            || !self.fits_buffer(response.headers())
+        {
+            return Ok(response);
+        }
+        let (parts, body) = response.into_parts();
+        let body = read_response_body(body, instance_uri).await?;
+        self.response_cache
+            .put(
+                &key,
+                CachedResponse {
+                    status: parts.status,
+                    headers: parts.headers.clone(),
+                    body: body.clone(),
+                    stored_at: self.clock.now(),
+                    fresh_for: ttl,
+                },
+                ttl,
+            )
+            .await;
+        Ok(http::Response::from_parts(parts, Body::Bytes(body)))
+    }
+
+    /// Apply the route's `body_rewrite` rules to a response whose body can be
+    /// buffered, updating `Content-Length`. Streaming, compressed, binary,
+    /// and oversized responses are passed through untouched.
+    async fn apply_body_rewrite(
+        &self,
+        route: &Route,
+        response: http::Response<Body>,
+        is_head: bool,
+        instance_uri: &str,
+    ) -> Result<http::Response<Body>, DomainError> {
+        if route.body_rewrite.is_empty() || is_head || !self.fits_buffer(response.headers()) {
+            return Ok(response);
+        }
+        let rules = body_rewrite::applicable(&route.body_rewrite, response.headers());
+        if rules.is_empty() {
+            return Ok(response);
+        }
+        let (mut parts, body) = response.into_parts();
+        let body = read_response_body(body, instance_uri).await?;
+        let body = match body_rewrite::rewrite(&body, &rules) {
+            Some(rewritten) => {
+                parts.headers.insert(
+                    http::header::CONTENT_LENGTH,
+                    HeaderValue::from(rewritten.len()),
+                );
+                Bytes::from(rewritten)
+            }
+            None => body,
+        };
+        Ok(http::Response::from_parts(parts, Body::Bytes(body)))
+    }
+
+    /// Read an EOF-delimited response body to the end and hand it on with a
+    /// `Content-Length`, failing once it grows past `max_response_body_size`.
+    /// Framed and server-sent event responses are left as is.
+    async fn buffer_unframed(
+        &self,
+        response: http::Response<Body>,
+        eof_delimited: bool,
+        instance_uri: &str,
+    ) -> Result<http::Response<Body>, DomainError> {
+        if !eof_delimited
+            || self.stream_unframed_responses
+            || StreamIdleTimeout::applies_to(response.headers())
+        {
+            return Ok(response);
+        }
+        let (mut parts, body) = response.into_parts();
+        let body = match body {
+            Body::Stream(mut stream) => {
+                let mut buf = bytes::BytesMut::new();
+                while let Some(chunk) = stream.next().await {
+                    let chunk = chunk.map_err(|e| DomainError::DownstreamError {
+                        detail: format!("failed to read upstream response body: {e}"),
+                        instance: instance_uri.to_string(),
+                    })?;
+                    buf.extend_from_slice(&chunk);
+                    if buf.len() > self.max_response_body_size {
+                        return Err(DomainError::DownstreamError {
+                            detail: format!(
+                                "upstream response without Content-Length exceeds maximum of {} bytes",
+                                self.max_response_body_size
+                            ),
+                            instance: instance_uri.to_string(),
+                        });
+                    }
+                }
+                buf.freeze()
+            }
+            other => read_response_body(other, instance_uri).await?,
+        };
+        parts
+            .headers
+            .insert(http::header::CONTENT_LENGTH, HeaderValue::from(body.len()));
+        Ok(http::Response::from_parts(parts, Body::Bytes(body)))
+    }
+
+    /// The route's response stages, in order: content type check, status
+    /// remap, body rewrite, body transformers and header allowlist. Stages
+    /// that apply are recorded in `trace`.
+    async fn apply_response_stages(
+        &self,
+        route: &Route,
+        transformers: &[Arc<dyn BodyTransformer>],
+        response: http::Response<Body>,
+        is_head: bool,
+        instance_uri: &str,
+        trace: Option<&mut RequestTrace>,
+    ) -> Result<http::Response<Body>, DomainError> {
+        check_response_content_type(route, &response, is_head, instance_uri)?;
+        let mut applied = Vec::new();
+        let status = response.status();
+        let response = apply_status_remap(&route.status_remap, response);
+        if response.status() != status {
+            applied.push("status_remap");
+        }
+        if !is_head
+            && self.fits_buffer(response.headers())
+            && !body_rewrite::applicable(&route.body_rewrite, response.headers()).is_empty()
+        {
+            applied.push("body_rewrite");
+        }
+        let response = self
+            .apply_body_rewrite(route, response, is_head, instance_uri)
+            .await?;
+        if !is_head
+            && self.fits_buffer(response.headers())
+            && transformers
+                .iter()
+                .any(|t| body_transform::applies(t.as_ref(), response.headers()))
+        {
+            applied.push("response_body_transformers");
+        }
+        let mut response = self
+            .apply_body_transformers(transformers, response, is_head, instance_uri)
+            .await?;
+        // Last, so rewriting and transformers still see `Content-Type`.
+        if let Some(allowlist) = &route.response_header_allowlist {
+            headers::retain_allowlisted(response.headers_mut(), allowlist);
+            applied.push("response_header_allowlist");
+        }
+        if let Some(trace) = trace {
+            trace
+                .transforms
+                .extend(applied.into_iter().map(String::from));
+        }
+        Ok(response)
+    }
+
+    /// The route's `body_transformers`, in order. A name without a registered
+    /// transformer fails the request rather than skipping the transformation.
+    fn resolve_body_transformers(
+        &self,
+        route: &Route,
+    ) -> Result<Vec<Arc<dyn BodyTransformer>>, DomainError> {
+        route
+            .body_transformers
+            .iter()
+            .map(|name| {
+                self.body_transformers
+                    .as_ref()
+                    .and_then(|hub| {
+                        hub.try_get_scoped::<dyn BodyTransformer>(&ClientScope::new(name.as_str()))
+                    })
+                    .ok_or_else(|| DomainError::Internal {
+                        message: format!("body transformer '{name}' is not registered"),
+                    })
+            })
+            .collect()
+    }
+
+    /// Run `transformers` over a response body that can be buffered, updating
+    /// `Content-Length`. Responses of content types no transformer handles
+    /// are passed through untouched.
+    async fn apply_body_transformers(
+        &self,
+        transformers: &[Arc<dyn BodyTransformer>],
+        response: http::Response<Body>,
+        is_head: bool,
+        instance_uri: &str,
+    ) -> Result<http::Response<Body>, DomainError> {
+        if is_head
+            || !transformers
+                .iter()
+                .any(|t| body_transform::applies(t.as_ref(), response.headers()))
+            || !self.fits_buffer(response.headers())
+        {
+            return Ok(response);
+        }
+        let (mut parts, body) = response.into_parts();
+        let body = read_response_body(body, instance_uri).await?;
+        let body = body_transform::apply(transformers, &parts.headers, body);
+        parts
+            .headers
+            .insert(http::header::CONTENT_LENGTH, HeaderValue::from(body.len()));
+        Ok(http::Response::from_parts(parts, Body::Bytes(body)))
+    }
+
+    /// Whether a response declares a `Content-Length` small enough to be
+    /// buffered under `max_body_size`.
+    fn fits_buffer(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get(http::header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<usize>().ok())
+            .is_some_and(|len| len <= self.max_body_size())
+    }
+
+    /// Buffer and gzip a [`compression::compressible`] response. Streamed
+    /// bodies are only buffered when their `Content-Length` fits the buffer
+    /// limit; anything else is passed through untouched.
+    async fn compress_response(
+        &self,
+        response: http::Response<Body>,
+        min_size: usize,
+        instance_uri: &str,
+    ) -> Result<http::Response<Body>, DomainError> {
+        if !compression::compressible(&response, min_size)
+            || (matches!(response.body(), Body::Stream(_)) && !self.fits_buffer(response.headers()))
+        {
+            return Ok(response);
+        }
+        let (parts, body) = response.into_parts();
+        let body = read_response_body(body, instance_uri).await?;
+        Ok(compression::gzip_response(parts, body))
+    }
+
+    /// Open an in-memory bridge into Pingora and return the client side.
+    fn open_bridge(&self) -> tokio::io::DuplexStream {
+        let (client_io, server_io) = tokio::io::duplex(65_536);
+
+        // Create Pingora H1 session from the server side of the DuplexStream.
+        // Pingora implements all IO traits for DuplexStream (in ext_io_impl).
+        let session = pingora_core::protocols::http::ServerSession::new_http1(Box::new(server_io));
+
+        // Spawn Pingora proxy processing in background.
+        let proxy = self.proxy.clone();
+        let shutdown = self.shutdown_rx.clone();
+        tokio::spawn(async move {
+            proxy.process_new_http(session, &shutdown).await;
+        });
+        client_io
+    }
+
+    /// Relay a WebSocket handshake through a fresh bridge. Once the upstream
+    /// answers `101`, the client's `tunnel` bytes are written to the bridge
+    /// unframed and the upstream's bytes become the response body; any other
+    /// response is returned as-is.
+    async fn forward_upgrade(
+        &self,
+        method: &http::Method,
+        url: &str,
+        mut outbound_headers: HeaderMap,
+        mut tunnel: BodyStream,
+        timeout: Duration,
+        instance_uri: &str,
+    ) -> Result<http::Response<Body>, DomainError> {
+        // Stripped as hop-by-hop with the rest of the client's headers.
+        outbound_headers.insert(http::header::UPGRADE, HeaderValue::from_static("websocket"));
+        #[cfg(any(test, feature = "test-utils"))]
+        self.capture_outbound(method, url, &outbound_headers);
+        let (client_read, mut client_write) = tokio::io::split(self.open_bridge());
+        let head = session_bridge::serialize_upgrade_wire(method, url, &outbound_headers);
+        client_write
+            .write_all(&head)
+            .await
+            .map_err(|e| DomainError::DownstreamError {
+                detail: format!("failed to write to proxy bridge: {e}"),
+                instance: instance_uri.to_string(),
+            })?;
+
+        let (status, resp_headers, resp_body_stream) = tokio::time::timeout(
+            timeout,
+            session_bridge::parse_response_stream(client_read, false),
+        )
+        .await
+        .map_err(|_| DomainError::RequestTimeout {
+            detail: format!("request to {url} timed out after {timeout:?}"),
+            instance: instance_uri.to_string(),
+        })?
+        .map_err(|e| DomainError::DownstreamError {
+            detail: format!("proxy bridge error: {e}"),
+            instance: instance_uri.to_string(),
+        })?;
+        let switched = status == http::StatusCode::SWITCHING_PROTOCOLS;
+        if switched {
+            tokio::spawn(async move {
+                while let Some(Ok(bytes)) = tunnel.next().await {
+                    if let Err(e) = client_write.write_all(&bytes).await {
+                        tracing::debug!(error = %e, "websocket tunnel write error");
+                        break;
+                    }
+                }
+                // The client is gone: let Pingora close the upstream side.
+                let _ = client_write.shutdown().await;
+            });
+        }
+        let mut response = build_proxy_response(
+            status,
+            resp_headers,
+            resp_body_stream,
+            instance_uri.to_string(),
+            &self.extra_hop_by_hop,
+        )?;
+        if switched {
+            let headers = response.headers_mut();
+            headers.insert(http::header::UPGRADE, HeaderValue::from_static("websocket"));
+            headers.insert(
+                http::header::CONNECTION,
+                HeaderValue::from_static("upgrade"),
+            );
+        }
+        Ok(response)
+    }
+
+    /// Send a fully serialized request over a fresh bridge and parse the
+    /// response head. Safe to call repeatedly for retries.
+    async fn send_buffered(
+        &self,
+        wire: &[u8],
+        is_head: bool,
+        timeout: Duration,
+        url: &str,
+        instance_uri: &str,
+    ) -> Result<http::Response<Body>, DomainError> {
+        let mut client_io = self.open_bridge();
+        client_io
+            .write_all(wire)
+            .await
+            .map_err(|e| DomainError::DownstreamError {
+                detail: format!("failed to write to proxy bridge: {e}"),
+                instance: instance_uri.to_string(),
+            })?;
+        // Do NOT shutdown the write side — Pingora uses Content-Length to
+        // determine the request boundary, and an early write-close is
+        // misinterpreted as "downstream dropped the connection".
+
+        // 9. Parse response.
+        let (status, mut resp_headers, resp_body_stream) = tokio::time::timeout(
+            timeout,
+            session_bridge::parse_response_stream(client_io, is_head),
+        )
+        .await
+        .map_err(|_| DomainError::RequestTimeout {
+            detail: format!("request to {url} timed out after {timeout:?}"),
+            instance: instance_uri.to_string(),
+        })?
+        .map_err(|e| DomainError::DownstreamError {
+            detail: format!("proxy bridge error: {e}"),
+            instance: instance_uri.to_string(),
+        })?;
+
+        let eof_delimited = resp_headers.remove(H_EOF_DELIMITED).is_some() && !is_head;
+        let attempts = resp_headers
+            .remove(H_UPSTREAM_ATTEMPTS)
+            .and_then(|v| v.to_str().ok()?.parse().ok());
+        let mut response = build_proxy_response(
+            status,
+            resp_headers,
+            resp_body_stream,
+            instance_uri.to_string(),
+            &self.extra_hop_by_hop,
+        )?;
+        if let Some(attempts) = attempts {
+            response.extensions_mut().insert(UpstreamAttempts(attempts));
+        }
+        self.buffer_unframed(response, eof_delimited, instance_uri)
+            .await
+    }
+
+    /// Two-tier endpoint selection (D1):
+    /// 1. `X-OAGW-Target-Host` header → validate against endpoint list
+    /// 2. Round-robin via `BackendSelector` for multi-endpoint, direct for single
+    async fn select_endpoint(
+        &self,
+        upstream: &Upstream,
+        req_headers: &http::HeaderMap,
+        sticky_key: Option<&[u8]>,
+        instance_uri: &str,
+    ) -> Result<Endpoint, DomainError> {
+        let endpoints = &upstream.server.endpoints;
+
+        if endpoints.is_empty() {
+            return Err(DomainError::DownstreamError {
+                detail: "upstream has no endpoints".into(),
+                instance: instance_uri.to_string(),
+            });
+        }
+
+        // Tier 1: Explicit selection via X-OAGW-Target-Host header.
+        if let Some(target_host) = req_headers
+            .get("x-oagw-target-host")
+            .and_then(|v| v.to_str().ok())
+        {
+            // Validate format: allowlist of safe hostname/IP characters.
+            // Rejects null bytes, @, \, Unicode homoglyphs, and port/path syntax.
+            if target_host.is_empty()
+                || !target_host
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_'))
+            {
+                return Err(DomainError::InvalidTargetHost {
+                    instance: instance_uri.to_string(),
+                });
+            }
+
+            // Find matching endpoint by host.
+            return endpoints
+                .iter()
+                .find(|ep| ep.host.eq_ignore_ascii_case(target_host))
+                .cloned()
+                .ok_or_else(|| {
+                    let valid_hosts: Vec<&str> =
+                        endpoints.iter().map(|ep| ep.host.as_str()).collect();
+                    tracing::warn!(
+                        target_host,
+                        ?valid_hosts,
+                        "X-OAGW-Target-Host does not match any configured endpoint"
+                    );
+                    DomainError::UnknownTargetHost {
+                        detail: format!(
+                            "X-OAGW-Target-Host '{}' does not match any configured endpoint",
+                            target_host
+                        ),
+                        instance: instance_uri.to_string(),
+                    }
+                });
+        }
+
+        // Tier 2: Automatic selection.
+        if endpoints.len() == 1 {
+            // Single-endpoint: use directly, no LB overhead.
+            return Ok(endpoints[0].clone());
+        }
+
+        // Multi-endpoint: consistent hashing for sticky upstreams that carry
+        // a key, round-robin via BackendSelector otherwise.
+        let selected = match sticky_key {
+            Some(key) => {
+                self.backend_selector
+                    .select_sticky(upstream.id, endpoints, key)
+                    .await
+            }
+            None => self.backend_selector.select(upstream.id, endpoints).await,
+        };
+        selected.ok_or_else(|| {
+            let total = endpoints.len();
+            let healthy = self.backend_selector.healthy_count(upstream.id);
+            DomainError::NoHealthyTarget {
+                detail: format!(
+                    "no healthy upstream target for '{}': {healthy} of {total} targets healthy",
+                    upstream.alias
+                ),
+                instance: instance_uri.to_string(),
+                total,
+                healthy,
+            }
+        })
+    }
+
+    /// Send a `GET /` probe carrying `auth_headers` to one of `upstream`'s
+    /// endpoints. Returns the upstream status and round-trip time; a
+    /// gateway-generated response (e.g. connect failure) is an error.
+    async fn probe_upstream(
+        &self,
+        upstream: &Upstream,
+        auth_headers: &AuthHeaders,
+        instance_uri: &str,
+    ) -> Result<(u16, Duration), DomainError> {
+        let endpoint = self
+            .select_endpoint(upstream, &HeaderMap::new(), None, instance_uri)
+            .await?;
+        let endpoint = self
+            .srv_targets
+            .resolve(&endpoint)
+            .map_or(endpoint, |(target, _)| target);
+        if !self.allow_http_upstream && matches!(endpoint.scheme, Scheme::Http) {
+            return Err(DomainError::Validation {
+                detail: "upstream endpoint uses HTTP; only HTTPS endpoints are permitted".into(),
+                instance: instance_uri.to_string(),
+            });
+        }
+
+        let mut outbound_headers = HeaderMap::new();
+        for (k, v) in auth_headers.iter() {
+            if let (Ok(name), Ok(val)) = (
+                HeaderName::from_bytes(k.as_bytes()),
+                HeaderValue::from_str(v),
+            ) {
+                outbound_headers.append(name, val);
+            }
+        }
+        set_endpoint_headers(&mut outbound_headers, &endpoint);
+        if let Ok(v) = HeaderValue::from_str(&upstream.id.to_string()) {
+            outbound_headers.insert(H_UPSTREAM_ID, v);
+        }
+        outbound_headers.insert(H_UPSTREAM_PROTOCOL, HeaderValue::from_static("http1"));
+        let timeout = resolve_timeout(self.request_timeout(), upstream.timeout_secs, None);
+        if let Ok(v) = HeaderValue::from_str(&timeout.as_millis().to_string()) {
+            outbound_headers.insert(H_READ_TIMEOUT_MS, v);
+        }
+        if let Ok(v) = HeaderValue::from_str(instance_uri) {
+            outbound_headers.insert(H_INSTANCE_URI, v);
+        }
+
+        let url =
+            request_builder::build_upstream_url(&endpoint, "/", "", &[], upstream.trailing_slash)?;
+        let wire = session_bridge::serialize_request_wire(
+            &http::Method::GET,
+            &url,
+            &outbound_headers,
+            Some(&Bytes::new()),
+        );
+        let started = Instant::now();
+        let response = self
+            .send_buffered(&wire, false, timeout, &url, instance_uri)
+            .await?;
+        let latency = started.elapsed();
+        if response.extensions().get::<ErrorSource>() == Some(&ErrorSource::Gateway) {
+            return Err(DomainError::DownstreamError {
+                detail: format!("upstream did not respond: {}", response.status()),
+                instance: instance_uri.to_string(),
+            });
+        }
+        Ok((response.status().as_u16(), latency))
+    }
+
+    /// Resolve the upstream and route for a request. When no route matches,
+    /// the configured [`UnmatchedRouteBehavior`] decides the outcome.
+    async fn resolve_target(
+        &self,
+        ctx: &SecurityContext,
+        alias: &str,
+        method: &http::Method,
+        path_suffix: &str,
+        body: Option<&[u8]>,
+        instance_uri: &str,
+    ) -> Result<Resolution, DomainError> {
+        let miss = match self
+            .cp
+            .resolve_proxy_target(ctx, alias, method.as_ref(), path_suffix, body)
+            .await
+        {
+            Ok((upstream, route)) => return Ok(Resolution::Target(upstream, route)),
+            Err(
+                e @ DomainError::NotFound {
+                    entity: "route", ..
+                },
+            ) => e,
+            Err(e) => return Err(e),
+        };
+        match &self.unmatched_route {
+            UnmatchedRouteBehavior::NotFound => Err(miss),
+            UnmatchedRouteBehavior::CatchAllUpstreamId(id) => {
+                let upstream = self.cp.get_upstream(ctx, *id).await.inspect_err(|_| {
+                    tracing::warn!(upstream_id = %id, "catch-all upstream not found");
+                })?;
+                if !upstream.enabled {
+                    return Err(DomainError::upstream_disabled(upstream.alias.clone()));
+                }
+                tracing::debug!(upstream_id = %id, "no route matched, forwarding to catch-all upstream");
+                let route = catch_all_route(&upstream);
+                Ok(Resolution::Target(upstream, route))
+            }
+            UnmatchedRouteBehavior::StaticResponse {
+                status,
+                headers,
+                body,
+            } => {
+                tracing::debug!(alias, "no route matched, serving configured response");
+                let kind = RouteKind::Static {
+                    status: *status,
+                    headers: headers.clone(),
+                    body: body.clone(),
+                };
+                static_to_response(kind, method == http::Method::HEAD, instance_uri.to_string())
+                    .map(Resolution::Respond)
+            }
+        }
+    }
+
+    /// Consume from the rate-limit bucket `key`, or with `dry_run` only
+    /// report the outcome. A rejection carries the bucket state as headers.
+    fn consume_rate_limit(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+        instance_uri: &str,
+        dry_run: bool,
+    ) -> Result<RateLimitStatus, DomainError> {
+        let result = if dry_run {
+            self.rate_limiter.check(key, config, instance_uri)
+        } else {
+            self.rate_limiter.try_consume(key, config, instance_uri)
+        };
+        result.map_err(|mut e| {
+            if let DomainError::RateLimitExceeded { headers, .. } = &mut e
+                && let Some(status) = self.rate_limiter.status(key)
+            {
+                *headers = rate_limit_header_pairs(&self.rate_limit_headers, status);
+            }
+            e
+        })
+    }
+
+    /// Proxy one request, recording in `notes` what `proxy_request` acts on
+    /// afterwards.
+    async fn forward(
+        &self,
+        ctx: SecurityContext,
+        req: http::Request<Body>,
+        notes: &mut ForwardNotes,
+    ) -> Result<http::Response<Body>, DomainError> {
+        let instance_uri = req.uri().to_string();
+        let url_len = req.uri().path_and_query().map_or(0, |pq| pq.as_str().len());
+        if url_len > self.max_url_length {
+            return Err(DomainError::UriTooLong {
+                detail: format!(
+                    "request URL of {url_len} bytes exceeds maximum of {} bytes",
+                    self.max_url_length
+                ),
+                instance: instance_uri,
+            });
+        }
+        if let Err(reason) = validate_path(req.uri().path()) {
+            return Err(DomainError::Validation {
+                detail: format!("malformed request path: {reason}"),
+                instance: instance_uri,
+            });
+        }
+        let full_path = normalize_path(req.uri().path());
+
+        // Extract alias from the raw path first, then normalize only the
+        // suffix. This prevents path traversal (e.g. `/../../admin/...`)
+        // from influencing alias extraction. Normalization drops a trailing
+        // slash; whether the client sent one is kept for `trailing_slash`.
+        let (alias, path_suffix, client_trailing_slash) = {
+            let path = req.uri().path();
+            let trimmed = path.strip_prefix('/').unwrap_or(path);
+            let (alias, raw_suffix) = match trimmed.find('/') {
+                Some(pos) => (&trimmed[..pos], &trimmed[pos..]),
+                None => (trimmed, ""),
+            };
+            (
+                alias.to_string(),
+                normalize_path(raw_suffix),
+                raw_suffix.len() > 1 && raw_suffix.ends_with('/'),
+            )
+        };
+
+        // Parse query parameters with proper URL decoding.
+        let mut query_params: Vec<(String, String)> = req
+            .uri()
+            .query()
+            .map(|q| {
+                form_urlencoded::parse(q.as_bytes())
+                    .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Decompose request into parts. Keep body as-is for conditional handling.
+        let (parts, body) = req.into_parts();
+        let method = parts.method;
+        let mut req_headers = parts.headers;
+        let client_ip = parts.extensions.get::<ClientIp>().map(|ip| ip.0);
+
+        // A WebSocket handshake carries the client's half of the tunnel as
+        // its body. It is set aside so that the handshake runs through the
+        // pipeline — AuthZ, auth plugin, rate limits — as a bodiless GET.
+        let (body, ws_tunnel) = if headers::is_websocket_upgrade(&req_headers) {
+            if method != http::Method::GET {
+                return Err(DomainError::ProtocolError {
+                    detail: format!("WebSocket upgrade requires GET, got {method}"),
+                    instance: instance_uri,
+                });
+            }
+            let Body::Stream(tunnel) = body else {
+                return Err(DomainError::ProtocolError {
+                    detail: "WebSocket upgrade requires a streaming client connection".into(),
+                    instance: instance_uri,
+                });
+            };
+            (Body::Empty, Some(tunnel))
+        } else {
+            (body, None)
+        };
+
+        // HEAD never carries a request body (RFC 9110 §9.3.2) and a GET body
+        // has no defined semantics. Some upstreams misbehave when one is
+        // sent, so it is forwarded (GET only), dropped or rejected per
+        // `bodiless_method_body`.
+        let is_head = method == http::Method::HEAD;
+        let has_body = match &body {
+            Body::Empty => false,
+            Body::Bytes(b) => !b.is_empty(),
+            Body::Stream(_) => true,
+        };
+        let body = if has_body && (is_head || method == http::Method::GET) {
+            match self.bodiless_method_body {
+                BodilessMethodBody::Reject => {
+                    return Err(DomainError::Validation {
+                        detail: format!("{method} request must not carry a body"),
+                        instance: instance_uri,
+                    });
+                }
+                BodilessMethodBody::Allow if !is_head => body,
+                BodilessMethodBody::Allow | BodilessMethodBody::Strip => {
+                    req_headers.remove(http::header::CONTENT_LENGTH);
+                    req_headers.remove(http::header::TRANSFER_ENCODING);
+                    Body::Empty
+                }
+            }
+        } else {
+            body
+        };
+
+        // Conditional body conversion — keep streams for streaming request bodies.
+        let global_max_body = self.max_body_size();
+        let (mut body_bytes, mut body_stream): (Bytes, Option<BodyStream>) = match body {
+            Body::Empty => (Bytes::new(), None),
+            Body::Bytes(b) => (b, None),
+            Body::Stream(s) => (
+                Bytes::new(),
+                Some(match self.body_read_idle_timeout {
+                    Some(timeout) => stream_idle::wrap_request_body(s, timeout),
+                    None => s,
+                }),
+            ),
+        };
+        let declared_len = req_headers
+            .get(http::header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<usize>().ok());
+
+        // 0. Static-response routes answer locally: no upstream is selected
+        //    and no auth plugin runs, but AuthZ still applies unless the
+        //    route opts out.
+        if let Some(route) = self
+            .cp
+            .resolve_static_route(&ctx, method.as_ref(), &full_path)
+            .await?
+        {
+            let authz = self
+                .authorize(&ctx, &route, None, &method, &full_path)
+                .await;
+            notes.trace(|t| {
+                t.route_id = Some(route.id);
+                t.authz = Some(self.trace_authz(&route, &authz));
+            });
+            authz?;
+            tracing::debug!(route_id = %route.id, "serving static route response");
+            notes.deprecation = deprecation_headers(&route);
+            return static_to_response(route.kind, is_head, instance_uri);
+        }
+
+        // 1+2. Resolve upstream + route in one pass (single hierarchy walk).
+        //      A streaming body is not inspected: routes with a body
+        //      condition win provisionally and are re-resolved below.
+        let buffered = body_stream.is_none().then_some(&body_bytes[..]);
+        let (mut upstream, mut route) = match self
+            .resolve_target(&ctx, &alias, &method, &path_suffix, buffered, &instance_uri)
+            .await?
+        {
+            Resolution::Target(upstream, route) => (upstream, route),
+            Resolution::Respond(response) => return Ok(response),
+        };
+        record_span_target(&upstream, &route);
+        notes.target = Some((upstream.id, route.id));
+        notes.trace(|t| (t.upstream_id, t.route_id) = (Some(upstream.id), Some(route.id)));
+
+        // 2a. AuthZ, before the route's policy shows in any response and
+        //     before any of the body is read.
+        self.authorize_target(&ctx, &route, upstream.id, &method, &path_suffix, notes)
+            .await?;
+
+        // Methods the upstream does not accept are refused, once authorized,
+        // before the body is read or any backend work starts.
+        if !upstream.allowed_methods.is_empty()
+            && !HttpMethod::parse(method.as_str())
+                .is_some_and(|m| upstream.allowed_methods.contains(&m))
+        {
+            return Err(DomainError::MethodNotAllowed {
+                detail: format!(
+                    "method {method} is not allowed for upstream '{}'",
+                    upstream.alias
+                ),
+                instance: instance_uri,
+                allowed: allow_header_methods(&upstream.allowed_methods),
+            });
+        }
+
+        // A declared length over the route's limit is rejected before any
+        // of the body is read; chunked bodies are checked as they arrive.
+        let mut max_body = route_body_limit(&route, global_max_body);
+        if let Some(len) = declared_len.filter(|len| !is_head && *len > max_body) {
+            self.metrics.record_body_limit_exceeded(BodyLimitExceeded {
+                route_id: route.id,
+                declared_len: len,
+                limit: max_body,
+            });
+            return Err(DomainError::PayloadTooLarge {
+                detail: format!("request body of {len} bytes exceeds maximum of {max_body} bytes"),
+                instance: instance_uri,
+            });
+        }
+
+        // 2'. Body-based routing and schema validation force buffering: read
+        //     the body (bounded by `max_body_size`) and, for body-based
+        //     routing, resolve again against its content.
+        let match_body = route
+            .match_rules
+            .http
+            .as_ref()
+            .is_some_and(|m| m.match_body.is_some());
+        if let Some(stream) = body_stream.take_if(|_| match_body || route.request_schema.is_some())
+        {
+            let first_pass = (upstream.id, route.id);
+            body_bytes = buffer_request_body(stream, max_body, &instance_uri).await?;
+            if match_body && body_bytes.len() <= max_body {
+                match self
+                    .resolve_target(
+                        &ctx,
+                        &alias,
+                        &method,
+                        &path_suffix,
+                        Some(&body_bytes),
+                        &instance_uri,
+                    )
+                    .await?
+                {
+                    Resolution::Target(u, r) => (upstream, route) = (u, r),
+                    Resolution::Respond(response) => return Ok(response),
+                }
+                record_span_target(&upstream, &route);
+                notes.target = Some((upstream.id, route.id));
+                notes.trace(|t| (t.upstream_id, t.route_id) = (Some(upstream.id), Some(route.id)));
+                // The body picked another route: it must be authorized too.
+                if notes.target != Some(first_pass) {
+                    self.authorize_target(&ctx, &route, upstream.id, &method, &path_suffix, notes)
+                        .await?;
+                }
+                max_body = route_body_limit(&route, global_max_body);
+            }
+        }
+
+        // Buffered bodies are size-checked once the route is known so that
+        // rejections can be attributed to it.
+        if body_bytes.len() > max_body {
+            self.metrics.record_body_limit_exceeded(BodyLimitExceeded {
+                route_id: route.id,
+                declared_len: declared_len.unwrap_or(body_bytes.len()),
+                limit: max_body,
+            });
+            return Err(DomainError::PayloadTooLarge {
+                detail: format!(
+                    "request body of {} bytes exceeds maximum of {max_body} bytes",
+                    body_bytes.len()
+                ),
+                instance: instance_uri,
+            });
+        }
+
+        // A body without an allowed media type is rejected before any
+        // upstream work; bodyless requests need no Content-Type.
+        let has_body = body_stream.is_some() || !body_bytes.is_empty();
+        if !headers::content_type_allowed(
+            &req_headers,
+            &route.allowed_request_content_types,
+            !has_body,
+        ) {
+            return Err(DomainError::UnsupportedMediaType {
+                detail: format!(
+                    "request content type {:?} is not allowed for this route",
+                    req_headers
+                        .get(http::header::CONTENT_TYPE)
+                        .and_then(|v| v.to_str().ok())
+                        .unwrap_or_default()
+                ),
+                instance: instance_uri,
+            });
+        }
+        // The route's coerced content type replaces the client's from here
+        // on, so body rewrites and transformers see it too.
+        if has_body
+            && let Some(content_type) = &route.coerce_content_type
+            && headers::coerce_content_type(&mut req_headers, content_type)
+        {
+            tracing::debug!(route_id = %route.id, content_type = %content_type, "request content type coerced");
+        }
+
+        // Start the route's SLA clock; `proxy_request` enforces it.
+        if let (Some(ms), Some(sla)) = (route.max_total_duration_ms, &notes.sla) {
+            sla.send_replace(Some(Duration::from_millis(ms)));
+        }
+        notes.deprecation = deprecation_headers(&route);
+        if self.log_sampler.sample(route.log_sample_rate) {
+            log_sampling::log_request(
+                route.id,
+                &method,
+                &path_suffix,
+                &req_headers,
+                body_stream.is_none().then_some(&body_bytes[..]),
+                &route.log_redact_paths,
+            );
+            notes.sampled_route = Some((route.id, route.log_redact_paths.clone()));
+        }
+
+        // 2b. Maintenance mode: answer with a canned 503 before any plugin or
+        //     backend work.
+        if upstream.maintenance {
+            tracing::debug!(upstream_id = %upstream.id, "upstream in maintenance, short-circuiting");
+            return Err(DomainError::UpstreamMaintenance {
+                detail: upstream.maintenance_message.clone().unwrap_or_else(|| {
+                    format!("upstream '{}' is under maintenance", upstream.alias)
+                }),
+                instance: instance_uri,
+                retry_after_secs: Some(MAINTENANCE_RETRY_AFTER_SECS),
+            });
+        }
+
+        // 2c. Drop the route's stripped query parameters, then validate the
+        //     rest against its allowlist.
+        if !route.strip_query_params.is_empty() {
+            query_params.retain(|(key, _)| !route.strip_query_params.contains(key));
+        }
+        if let Some(ref http_match) = route.match_rules.http
+            && !query_params.is_empty()
+        {
+            for (key, _) in &query_params {
+                if !http_match.query_allowlist.contains(key) {
+                    return Err(DomainError::Validation {
+                        detail: format!(
+                            "query parameter '{}' is not in the route's query_allowlist",
+                            key
+                        ),
+                        instance: instance_uri,
+                    });
+                }
+            }
+        }
+        // The route's API version parameter replaces any client-sent value.
+        if let Some(param) = route
+            .api_version
+            .as_ref()
+            .and_then(|v| v.query_param.as_ref().map(|p| (p, &v.value)))
+        {
+            query_params.retain(|(key, _)| key != param.0);
+            query_params.push((param.0.clone(), param.1.clone()));
+        }
+        if let Some(max) = self.max_query_params
+            && query_params.len() > max
+        {
+            return Err(DomainError::Validation {
+                detail: format!(
+                    "request has {} query parameters; at most {max} are allowed",
+                    query_params.len()
+                ),
+                instance: instance_uri,
+            });
+        }
+
+        // 2d. Enforce path_suffix_mode.
+        if let Some(ref http_match) = route.match_rules.http
+            && http_match.path_suffix_mode == PathSuffixMode::Disabled
+        {
+            let route_path = &http_match.path;
+            let extra = path_suffix.strip_prefix(route_path.as_str()).unwrap_or("");
+            if !extra.is_empty() {
+                return Err(DomainError::Validation {
+                    detail: format!(
+                        "path suffix not allowed: route path_suffix_mode is disabled but request has extra path '{}'",
+                        extra
+                    ),
+                    instance: instance_uri,
+                });
+            }
+        }
+
+        // 2d'. Validate a JSON body against the route's request schema.
+        if let Some(schema) = &route.request_schema
+            && body_stream.is_none()
+            && !body_bytes.is_empty()
+            && headers::is_json_content_type(&req_headers)
+        {
+            let violations = request_schema::validate(schema, &body_bytes);
+            if !violations.is_empty() {
+                return Err(DomainError::SchemaViolation {
+                    detail: format!(
+                        "request body violates the route's request_schema ({} violation(s))",
+                        violations.len()
+                    ),
+                    instance: instance_uri,
+                    violations,
+                });
+            }
+        }
+
+        // 2d''. Run the route's body transformers over a request body of a
+        //       content type they handle, buffering a streamed body first.
+        let transformers = self.resolve_body_transformers(&route)?;
+        if transformers
+            .iter()
+            .any(|t| body_transform::applies(t.as_ref(), &req_headers))
+        {
+            if let Some(stream) = body_stream.take() {
+                body_bytes = buffer_request_body(stream, max_body, &instance_uri).await?;
+                if body_bytes.len() > max_body {
+                    return Err(DomainError::PayloadTooLarge {
+                        detail: format!("request body exceeds maximum of {max_body} bytes"),
+                        instance: instance_uri,
+                    });
+                }
+            }
+            body_bytes =
+                body_transform::apply(&transformers, &req_headers, std::mem::take(&mut body_bytes));
+            notes.trace(|t| t.transforms.push("request_body_transformers".into()));
+        }
+
+        // 2e. Response cache lookup, on routes that cache — a hit skips
+        //     plugins, rate limits, and the upstream call entirely.
+        //     In private mode the caller's own entry is tried first. On a
+        //     miss, an expired entry with a validator is revalidated below
+        //     unless the client sent conditions of its own. A HEAD is
+        //     answered from a fresh GET entry but never stored.
+        let cache_lookup = ws_tunnel.is_none()
+            && (route.cache_responses || route.negative_cache_ttl_secs.is_some())
+            && (method == http::Method::GET || (is_head && self.head_from_response_cache));
+        let credentialed =
+            cache_lookup && self.carries_credentials(&upstream, &route, &req_headers);
+        let cache_key = cache_lookup.then(|| {
+            let shared = response_cache::cache_key(
+                ctx.subject_tenant_id(),
+                upstream.id,
+                route.id,
+                &path_suffix,
+                &query_params,
+            );
+            let private = self
+                .private_response_cache
+                .then(|| response_cache::private_cache_key(&shared, ctx.subject_id()));
+            response_cache::CacheKeys { shared, private }
+        });
+        if let Some(ref keys) = cache_key {
+            for key in keys.lookup_order() {
+                if let Some(hit) = self.cache_get(key, &req_headers).await {
+                    let age = response_cache::current_age(&hit, self.clock.now());
+                    if age >= hit.fresh_for {
+                        tracing::debug!(upstream_id = %upstream.id, route_id = %route.id, "response cache entry past freshness");
+                        continue;
+                    }
+                    tracing::debug!(upstream_id = %upstream.id, route_id = %route.id, "response cache hit");
+                    self.metrics.record_cache_lookup("response", true);
+                    let mut resp = if is_head {
+                        cached_to_head_response(hit, instance_uri)?
+                    } else {
+                        cached_to_response(hit, instance_uri)?
+                    };
+                    resp.headers_mut()
+                        .insert(http::header::AGE, age.as_secs().into());
+                    return Ok(resp);
+                }
+            }
+            self.metrics.record_cache_lookup("response", false);
+        }
+        let cache_key = cache_key.filter(|_| !is_head);
+        let mut stale = None;
+        if let Some(ref keys) = cache_key
+            && body_stream.is_none()
+            && !req_headers.contains_key(http::header::IF_NONE_MATCH)
+            && !req_headers.contains_key(http::header::IF_MODIFIED_SINCE)
+        {
+            for key in keys.lookup_order() {
+                stale = self
+                    .cache_get(&response_cache::stale_key(key), &req_headers)
+                    .await;
+                if stale.is_some() {
+                    break;
+                }
+            }
+        }
+
+        // 2e'. Idempotency-key replay: on a route that deduplicates, a
+        //      repeated key is answered with the stored response.
+        if let Some(ttl) = route.idempotency_ttl_secs
+            && ws_tunnel.is_none()
+            && let Some(key) = req_headers
+                .get(IDEMPOTENCY_KEY)
+                .and_then(|v| v.to_str().ok())
+        {
+            let key = response_cache::idempotency_key(ctx.subject_tenant_id(), route.id, key);
+            if let Some(hit) = self.response_cache.get(&key).await {
+                tracing::debug!(route_id = %route.id, "idempotency key replayed");
+                return cached_to_response(hit, instance_uri);
+            }
+            notes.idempotency = Some((key, Duration::from_secs(ttl)));
+        }
+
+        // 3. Prepare outbound headers (passthrough + strip).
+        let mode = upstream
+            .headers
+            .as_ref()
+            .and_then(|h| h.request.as_ref())
+            .map_or(PassthroughMode::None, |r| r.passthrough);
+        let allowlist: Vec<String> = upstream
+            .headers
+            .as_ref()
+            .and_then(|h| h.request.as_ref())
+            .map_or_else(Vec::new, |r| r.passthrough_allowlist.clone());
+        let mut outbound_headers = headers::apply_passthrough(&req_headers, &mode, &allowlist);
+        headers::strip_hop_by_hop(&mut outbound_headers);
+        headers::strip_headers(&mut outbound_headers, &self.extra_hop_by_hop);
+        headers::strip_internal_headers(&mut outbound_headers);
+        // Sensitive inbound headers, gateway-wide then per route. Auth
+        // plugins run later, so headers they set are never stripped.
+        headers::strip_headers(&mut outbound_headers, &self.strip_inbound);
+        for name in &route.strip_inbound_headers {
+            outbound_headers.remove(name.as_str());
+        }
+        if ws_tunnel.is_some() {
+            headers::copy_websocket_handshake(&req_headers, &mut outbound_headers);
+        }
+        // Identify the gateway unless the client's agent is passed through;
+        // the upstream's own setting replaces the gateway default.
+        if !outbound_headers.contains_key(http::header::USER_AGENT) {
+            let user_agent = upstream
+                .user_agent
+                .as_deref()
+                .and_then(|ua| HeaderValue::from_str(ua).ok())
+                .unwrap_or_else(|| self.user_agent.clone());
+            outbound_headers.insert(http::header::USER_AGENT, user_agent);
+        }
+        if let (Some(trusted), Some(client_ip)) = (&self.trusted_proxies, client_ip) {
+            headers::set_forwarded_headers(&req_headers, &mut outbound_headers, client_ip, trusted);
+        }
+        let protocol = upstream.wire_protocol();
+        if protocol == UpstreamProtocol::Grpc {
+            // gRPC servers require `te: trailers`; it is hop-by-hop so re-add it.
+            outbound_headers.insert(http::header::TE, HeaderValue::from_static("trailers"));
+        }
+        // Set before auth and header rules, so an explicit `set` rule wins.
+        if let Some((name, value)) = route.api_version.as_ref().and_then(api_version_header) {
+            outbound_headers.insert(name, value);
+        }
+
+        // 3b. Revalidate an expired cache entry with its validators.
+        if let Some(ref stale) = stale {
+            for (name, value) in response_cache::revalidation_headers(&stale.headers) {
+                outbound_headers.insert(name, value);
+            }
+        }
+
+        // path_suffix is the full path from the proxy URL; strip the route prefix
+        // so we get: endpoint + route_path + remaining_suffix.
+        let route_path = route
+            .match_rules
+            .http
+            .as_ref()
+            .map_or("/", |h| h.path.as_str());
+        let remaining_suffix = path_suffix.strip_prefix(route_path).unwrap_or("");
+        // `preserve` keeps the slash the client sent.
+        let trailing_slash = match upstream.trailing_slash {
+            TrailingSlash::Preserve if client_trailing_slash => TrailingSlash::Add,
+            policy => policy,
+        };
+
+        // 3c. External transform hook: the buffered request goes to the
+        //     route's hook, whose answer replaces the outbound headers and/or
+        //     body. It runs before auth, so credentials never reach the hook.
+        if let Some(hook_url) = route.transform_hook_url.as_deref() {
+            if !self.allow_http_upstream && hook_url.starts_with("http://") {
+                return Err(DomainError::Validation {
+                    detail: "transform hook uses HTTP; only HTTPS hooks are permitted".into(),
+                    instance: instance_uri,
+                });
+            }
+            if let Some(stream) = body_stream.take() {
+                body_bytes = buffer_within_limit(stream, max_body, &instance_uri).await?;
+            }
+            let path = request_builder::build_upstream_path(
+                route_path,
+                remaining_suffix,
+                &query_params,
+                trailing_slash,
+            );
+            self.transform_hook
+                .transform(
+                    hook_url,
+                    method.as_str(),
+                    &path,
+                    &mut outbound_headers,
+                    &mut body_bytes,
+                )
+                .await
+                .map_err(|e| match e {
+                    HookError::Timeout(timeout) => DomainError::RequestTimeout {
+                        detail: format!("transform hook timed out after {timeout:?}"),
+                        instance: instance_uri.clone(),
+                    },
+                    HookError::Failed(detail) => DomainError::DownstreamError {
+                        detail,
+                        instance: instance_uri.clone(),
+                    },
+                })?;
+            if body_bytes.len() > max_body {
+                return Err(DomainError::PayloadTooLarge {
+                    detail: format!(
+                        "transformed request body of {} bytes exceeds maximum of {max_body} bytes",
+                        body_bytes.len()
+                    ),
+                    instance: instance_uri,
+                });
+            }
+            // The hook cannot address the bridge or break message framing.
+            headers::strip_hop_by_hop(&mut outbound_headers);
+            headers::strip_internal_headers(&mut outbound_headers);
+            notes.trace(|t| t.transforms.push("transform_hook".into()));
+        }
+
+        // 4. Execute the auth plugin: that of the first route `auth_rules`
+        //    entry matching the request, else the upstream's, else the
+        //    calling tenant's default.
+        let tenant_default;
+        let auth = match select_auth(&route.auth_rules, &path_suffix, &req_headers) {
+            Some(auth) => auth,
+            None if upstream.auth.is_some() => upstream.auth.as_ref(),
+            None => {
+                tenant_default = self.cp.resolve_tenant_default_auth(&ctx).await?;
+                tenant_default.as_ref()
+            }
+        };
+        if let Some(auth) = auth {
+            let plugin = self
+                .auth_registry
+                .resolve(&auth.plugin_type)
+                .map_err(|e| match e {
+                    PluginError::Disabled(ref plugin_id) => DomainError::PluginDisabled {
+                        detail: format!(
+                            "auth plugin '{plugin_id}' used by route {} is disabled",
+                            route.id
+                        ),
+                        instance: instance_uri.clone(),
+                    },
+                    _ => DomainError::AuthenticationFailed {
+                        detail: e.to_string(),
+                        instance: instance_uri.clone(),
+                    },
+                })?;
+            notes.trace(|t| t.plugins.push(auth.plugin_type.clone()));
+            let auth_headers: AuthHeaders = outbound_headers
+                .iter()
+                .filter_map(|(k, v)| {
+                    v.to_str()
+                        .ok()
+                        .map(|s| (k.as_str().to_string(), s.to_string()))
+                })
+                .collect();
+            let mut config = auth.config.clone().unwrap_or_default();
+            apply_auth_config_overrides(&req_headers, &self.auth_config_overrides, &mut config);
+            // Plugins that sign the body see all of it; the rest leave a
+            // streaming body streaming.
+            if plugin.requires_body()
+                && let Some(stream) = body_stream.take()
+            {
+                body_bytes = buffer_within_limit(stream, max_body, &instance_uri).await?;
+            }
+            let mut auth_ctx = AuthContext {
+                headers: auth_headers,
+                config,
+                security_context: ctx.clone(),
+                early_response: None,
+                body_fields: Vec::new(),
+                query: Vec::new(),
+                method: method.as_str().to_string(),
+                path: request_builder::build_upstream_path(
+                    route_path,
+                    remaining_suffix,
+                    &query_params,
+                    trailing_slash,
+                ),
+                body: body_stream.is_none().then(|| body_bytes.clone()),
+                now: self.trusted_now(),
+                credential: None,
+            };
+            let provenance = self
+                .authenticate_timed(&auth.plugin_type, plugin.as_ref(), &mut auth_ctx)
+                .await
+                .map_err(|e| match e {
+                    PluginError::SecretNotFound(ref s) => DomainError::SecretNotFound {
+                        detail: s.clone(),
+                        instance: instance_uri.clone(),
+                    },
+                    PluginError::Rejected(ref msg) | PluginError::InvalidConfig(ref msg) => {
+                        DomainError::Validation {
+                            detail: msg.clone(),
+                            instance: instance_uri.clone(),
+                        }
+                    }
+                    PluginError::AuthFailed(_)
+                    | PluginError::Internal(_)
+                    | PluginError::Disabled(_) => DomainError::AuthenticationFailed {
+                        detail: e.to_string(),
+                        instance: instance_uri.clone(),
+                    },
+                })?;
+            for applied in &provenance {
+                tracing::debug!(
+                    upstream_id = %upstream.id,
+                    plugin_id = %applied.plugin_id,
+                    header = %applied.header,
+                    "header set by auth plugin"
+                );
+            }
+            if let Some(credential) = auth_ctx.credential.take() {
+                notes.credential = Some((plugin.clone(), credential));
+            }
+            // 4a. The plugin may answer the request itself (e.g. a redirect to
+            //     a login page); the upstream is never called.
+            if let Some(early) = auth_ctx.early_response.take() {
+                tracing::debug!(
+                    upstream_id = %upstream.id,
+                    status = early.status,
+                    "auth plugin short-circuited the request"
+                );
+                return early_to_response(early, instance_uri);
+            }
+            // 4b. Credentials destined for the JSON body force buffering;
+            //     the buffered wire carries the new Content-Length.
+            if !auth_ctx.body_fields.is_empty() {
+                if let Some(stream) = body_stream.take() {
+                    body_bytes = buffer_within_limit(stream, max_body, &instance_uri).await?;
+                }
+                body_bytes =
+                    body_inject::inject(&body_bytes, &auth_ctx.body_fields).map_err(|detail| {
+                        DomainError::Validation {
+                            detail,
+                            instance: instance_uri.clone(),
+                        }
+                    })?;
+            }
+            // 4c. Plugin query parameters win over client-sent ones; a
+            //     parameter set twice keeps the plugin's last value.
+            for (name, value) in std::mem::take(&mut auth_ctx.query) {
+                let replaced = query_params.iter().any(|(key, _)| *key == name);
+                query_params.retain(|(key, _)| *key != name);
+                tracing::debug!(
+                    upstream_id = %upstream.id,
+                    plugin_id = %auth.plugin_type,
+                    query_param = %name,
+                    replaced,
+                    "query parameter set by auth plugin"
+                );
+                query_params.push((name, value));
+            }
+            // 4d. The plugin's headers replace the outbound set as a whole:
+            //     one that cannot be sent fails the request rather than
+            //     going out without it.
+            let mut plugin_headers = HeaderMap::with_capacity(auth_ctx.headers.len());
+            for (k, v) in auth_ctx.headers.iter() {
+                let (Ok(name), Ok(val)) = (
+                    HeaderName::from_bytes(k.as_bytes()),
+                    HeaderValue::from_str(&headers::unfold(v)),
+                ) else {
+                    return Err(DomainError::AuthenticationFailed {
+                        detail: format!(
+                            "auth plugin '{}' set header '{k}' to a value that is not a valid HTTP header",
+                            auth.plugin_type
+                        ),
+                        instance: instance_uri,
+                    });
+                };
+                plugin_headers.append(name, val);
+            }
+            outbound_headers = plugin_headers;
+        }
+
+        // 5. Apply header rules + set Host.
+        if let Some(ref hc) = upstream.headers
+            && let Some(ref rules) = hc.request
+        {
+            headers::apply_header_rules(&mut outbound_headers, rules, &ctx);
+            notes.trace(|t| t.transforms.push("header_rules".into()));
+        }
+        if let Some(max) = upstream.max_header_size_bytes
+            && let Some((name, size)) = headers::oversized_header(&outbound_headers, max)
+        {
+            return Err(DomainError::HeaderTooLarge {
+                detail: format!(
+                    "header '{name}' is {size} bytes; the upstream accepts at most {max}"
+                ),
+                instance: instance_uri,
+            });
+        }
+
+        // 5a. Endpoint selection (D1 — two-tier).
+        let sticky = sticky_key(&upstream, &req_headers, ctx.subject_tenant_id());
+        let endpoint = self
+            .select_endpoint(&upstream, &req_headers, sticky.as_deref(), &instance_uri)
+            .await?;
+        // A static target list replaces the endpoint; its lower priorities
+        // are failed over to before the upstream's own failover endpoints.
+        let (endpoint, srv_failover) = self
+            .srv_targets
+            .resolve(&endpoint)
+            .unwrap_or((endpoint, Vec::new()));
+        notes.trace(|t| t.endpoint = Some(format!("{}:{}", endpoint.host, endpoint.port)));
+
+        // 5b. Enforce HTTPS-only constraint (cpt-cf-oagw-constraint-https-only).
+        if !self.allow_http_upstream && matches!(endpoint.scheme, Scheme::Http) {
+            return Err(DomainError::Validation {
+                detail: "upstream endpoint uses HTTP; only HTTPS endpoints are permitted".into(),
+                instance: instance_uri,
+            });
+        }
+
+        set_endpoint_headers(&mut outbound_headers, &endpoint);
+
+        // 6. Check rate limit (upstream then route). A tenant rate limit set
+        //    on the upstream replaces its own limit for the calling tenant.
+        //    The bucket with the fewest tokens left is the one reported to
+        //    the client.
+        let tenant_limit = self.cp.resolve_tenant_rate_limit(&ctx, upstream.id).await?;
+        let upstream_limit = match &tenant_limit {
+            Some(tl) => Some((
+                format!("upstream:{}:tenant:{}", upstream.id, tl.tenant_id),
+                &tl.rate_limit,
+            )),
+            None => upstream
+                .rate_limit
+                .as_ref()
+                .map(|rl| (format!("upstream:{}", upstream.id), rl)),
+        };
+        let limits = [
+            upstream_limit,
+            route
+                .rate_limit
+                .as_ref()
+                .map(|rl| (format!("route:{}", route.id), rl)),
+        ];
+        // A trace reports what the rate limits and circuit breaker would do
+        // without consuming tokens or taking a probe slot.
+        let dry_run = notes.trace.is_some();
+        for (key, rl) in limits.iter().flatten() {
+            let status = self.consume_rate_limit(key, rl, &instance_uri, dry_run)?;
+            if notes
+                .rate_limit
+                .is_none_or(|tightest| status.remaining < tightest.remaining)
+            {
+                notes.rate_limit = Some(status);
+            }
+        }
+
+        // Fail fast while the upstream's circuit breaker is open.
+        let circuit = self
+            .circuit_defaults
+            .with_overrides(upstream.circuit_breaker.as_ref());
+        if dry_run {
+            self.circuit_breaker
+                .check(upstream.id, &circuit, &instance_uri)?;
+        } else {
+            self.circuit_breaker
+                .try_acquire(upstream.id, &circuit, &instance_uri)?;
+            notes.circuit = Some((upstream.id, circuit));
+        }
+
+        // 7. Build URL: endpoint + route_path + remaining_suffix.
+        let mut url = request_builder::build_upstream_url(
+            &endpoint,
+            route_path,
+            remaining_suffix,
+            &query_params,
+            trailing_slash,
+        )?;
+
+        // 7b. Inject internal context headers for PingoraProxy (D9). The
+        //     endpoint headers were set alongside Host in 5b.
+        if let Ok(v) = HeaderValue::from_str(&upstream.id.to_string()) {
+            outbound_headers.insert(H_UPSTREAM_ID, v);
+        }
+        let protocol_str = match protocol {
+            UpstreamProtocol::Http1 => "http1",
+            UpstreamProtocol::H2c => "h2c",
+            UpstreamProtocol::Grpc => "grpc",
+        };
+        outbound_headers.insert(H_UPSTREAM_PROTOCOL, HeaderValue::from_static(protocol_str));
+        let timeout = resolve_timeout(
+            self.request_timeout(),
+            upstream.timeout_secs,
+            route.timeout_secs,
+        );
+        if let Ok(v) = HeaderValue::from_str(&timeout.as_millis().to_string()) {
+            outbound_headers.insert(H_READ_TIMEOUT_MS, v);
+        }
+        if let Ok(v) = HeaderValue::from_str(&instance_uri) {
+            outbound_headers.insert(H_INSTANCE_URI, v);
+        }
+        if upstream.force_chunked && has_body {
+            outbound_headers.insert(H_FORCE_CHUNKED, HeaderValue::from_static("1"));
+        }
+        if let Some(v) = upstream
+            .keepalive
+            .as_ref()
+            .and_then(encode_keepalive)
+            .and_then(|v| HeaderValue::from_str(&v).ok())
+        {
+            outbound_headers.insert(H_KEEPALIVE, v);
+        }
+        if let Some(v) = encode_tls_policy(
+            upstream.tls_min_version.as_deref(),
+            &upstream.tls_cipher_suites,
+        )
+        .and_then(|v| HeaderValue::from_str(&v).ok())
+        {
+            outbound_headers.insert(H_TLS_POLICY, v);
+        }
+        if let Some(v) = upstream
+            .tls_server_name
+            .as_deref()
+            .and_then(|name| HeaderValue::from_str(name).ok())
+        {
+            outbound_headers.insert(H_TLS_SERVER_NAME, v);
+        }
+        if let Some((max_retries, backoff)) = self.connect_retry
+            && let Ok(v) = HeaderValue::from_str(&format!("{max_retries},{}", backoff.as_millis()))
+        {
+            outbound_headers.insert(H_CONNECT_RETRY, v);
+        }
+
+        // 7c. A traced request is never sent: the mock response takes the
+        //     upstream's place, or the trace ends here.
+        if let Some(state) = &mut notes.trace {
+            let Some(response) = state.mock_response.take() else {
+                state.stopped = true;
+                return Ok(http::Response::new(Body::Empty));
+            };
+            state.trace.upstream_called = true;
+            return self
+                .apply_response_stages(
+                    &route,
+                    &transformers,
+                    response,
+                    is_head,
+                    &instance_uri,
+                    Some(&mut state.trace),
+                )
+                .await;
+        }
+
+        notes.upstream_slot = self
+            .acquire_upstream_slot(upstream.id, ctx.subject_tenant_id(), timeout, &instance_uri)
+            .await?;
+        self.resolve_with_retry(&endpoint).await;
+        notes.host_slot = self
+            .acquire_host_slot(&endpoint, timeout, &instance_uri)
+            .await?;
+        // Queueing and auth are over; what is left of the budget goes to
+        // the upstream.
+        self.set_budget_header(
+            &upstream,
+            &route,
+            notes.arrived,
+            timeout,
+            &mut outbound_headers,
+        );
+        // The slot must outlive every bridge attempt below; trailers reach
+        // the response through the `Trailers` extension.
+        let trailer_slot = TrailerSlot::register();
+        outbound_headers.insert(H_TRAILER_SLOT, HeaderValue::from(trailer_slot.id()));
+        let trailers = trailer_slot.trailers().clone();
+        // Every send below, with its connect retries, failover and body
+        // retries, counts against the route's `max_outbound_calls`.
+        let mut outbound_calls = OutboundCalls::new(&route);
+        outbound_calls.take(&mut outbound_headers, &instance_uri)?;
+
+        // 8. Bridge request into Pingora and write the request / read the
+        //    response from the client side.
+        if let Some(tunnel) = ws_tunnel {
+            return self
+                .forward_upgrade(
+                    &method,
+                    &url,
+                    outbound_headers,
+                    tunnel,
+                    timeout,
+                    &instance_uri,
+                )
+                .await;
+        }
+        if let Some(mut body_stream) = body_stream {
+            // Streaming path: write headers, then forward body chunks concurrently.
+            let (client_read, mut client_write) = tokio::io::split(self.open_bridge());
+
+            #[cfg(any(test, feature = "test-utils"))]
+            self.capture_outbound(&method, &url, &outbound_headers);
+            let header_bytes =
+                session_bridge::serialize_request_wire(&method, &url, &outbound_headers, None);
+            client_write.write_all(&header_bytes).await.map_err(|e| {
+                DomainError::DownstreamError {
+                    detail: format!("failed to write to proxy bridge: {e}"),
+                    instance: instance_uri.clone(),
+                }
+            })?;
+
+            // Spawn task to forward body stream chunks as they arrive.
+            // Enforce max_body_size on the streaming path: signal 413 if exceeded.
+            let (abort_tx, abort_rx) = tokio::sync::oneshot::channel::<BodyAbort>();
+            let body_instance_uri = instance_uri.clone();
+            tokio::spawn(async move {
+                let mut total_bytes: usize = 0;
+                let mut complete = true;
+                while let Some(chunk) = body_stream.next().await {
+                    match chunk {
+                        Ok(bytes) => {
+                            total_bytes = total_bytes.saturating_add(bytes.len());
+                            if total_bytes > max_body {
+                                tracing::warn!(
+                                    total_bytes,
+                                    max_body,
+                                    "streaming body exceeded max size, aborting"
+                                );
+                                let _ = abort_tx.send(BodyAbort::TooLarge(total_bytes));
+                                complete = false;
+                                break;
+                            }
+                            if bytes.is_empty() {
+                                continue;
+                            }
+                            let frame = session_bridge::encode_chunk(&bytes);
+                            if let Err(e) = client_write.write_all(&frame).await {
+                                tracing::debug!(error = %e, "body stream write error");
+                                complete = false;
+                                break;
+                            }
+                        }
+                        Err(e) => {
+                            tracing::debug!(error = %e, "body stream chunk error");
+                            if e.downcast_ref::<BodyReadIdle>().is_some() {
+                                let _ = abort_tx.send(BodyAbort::Idle(e.to_string()));
+                            }
+                            complete = false;
+                            break;
+                        }
+                    }
+                }
+                if complete {
+                    // Terminate the chunked body but keep the write side open —
+                    // an early write-close is misinterpreted by Pingora as
+                    // "downstream dropped the connection".
+                    let _ = client_write.write_all(session_bridge::LAST_CHUNK).await;
+                } else {
+                    // Abort: a truncated chunked body never reaches the upstream
+                    // as a complete request.
+                    let _ = client_write.shutdown().await;
+                }
+            });
+
+            // 9. Parse response from the read half, but short-circuit to 413
+            //    if the body-forwarding task signals a limit breach.
+            //
+            // TODO(hardening): a fast upstream can respond before the body-forwarder
+            // detects the limit breach, causing the client to see 200 instead of 413.
+            // Fix: wrap the write half in a LimitedAsyncWrite that returns io::Error
+            // at the byte limit, so Pingora aborts the exchange before responding.
+            let resp_future = tokio::time::timeout(
+                timeout,
+                session_bridge::parse_response_stream(client_read, is_head),
+            );
+            tokio::select! {
+                biased;
+                Ok(abort) = abort_rx => match abort {
+                    BodyAbort::TooLarge(total) => {
+                        self.metrics.record_body_limit_exceeded(BodyLimitExceeded {
+                            route_id: route.id,
+                            declared_len: declared_len.unwrap_or(total),
+                            limit: max_body,
+                        });
+                        Err(DomainError::PayloadTooLarge {
+                            detail: format!(
+                                "streaming request body of {total} bytes exceeds maximum of {max_body} bytes"
+                            ),
+                            instance: body_instance_uri,
+                        })
+                    }
+                    BodyAbort::Idle(detail) => Err(DomainError::ClientTimeout {
+                        detail,
+                        instance: body_instance_uri,
+                    }),
+                },
+                result = resp_future => {
+                    let (status, mut resp_headers, resp_body_stream) = result
+                        .map_err(|_| DomainError::RequestTimeout {
+                            detail: format!("request to {url} timed out after {timeout:?}"),
+                            instance: instance_uri.clone(),
+                        })?
+                        .map_err(|e| DomainError::DownstreamError {
+                            detail: format!("proxy bridge error: {e}"),
+                            instance: instance_uri.clone(),
+                        })?;
+                    let eof_delimited =
+                        resp_headers.remove(H_EOF_DELIMITED).is_some() && !is_head;
+                    let response =
+                        build_proxy_response(status, resp_headers, resp_body_stream, instance_uri.clone(), &self.extra_hop_by_hop)?;
+                    let response = self
+                        .buffer_unframed(response, eof_delimited, &instance_uri)
+                        .await?;
+                    let mut response = self
+                        .apply_response_stages(&route, &transformers, response, is_head, &instance_uri, None)
+                        .await?;
+                    response.extensions_mut().insert(trailers);
+                    Ok(response)
+                }
+            }
+        } else {
+            // Buffered path: write full request, then read response.
+            let mut wire = session_bridge::serialize_request_wire(
+                &method,
+                &url,
+                &outbound_headers,
+                Some(&body_bytes),
+            );
+            #[cfg(any(test, feature = "test-utils"))]
+            self.capture_outbound(&method, &url, &outbound_headers);
+            let mut result = self
+                .send_buffered(&wire, is_head, timeout, &url, &instance_uri)
+                .await;
+            outbound_calls.settle(&result);
+
+            // 9b. Failover: when the primary fails, try the upstream's
+            //     secondary endpoints strictly in order. Only the buffered
+            //     path can replay the request body.
+            for fallback in srv_failover.iter().chain(&upstream.failover) {
+                if !needs_failover(&result, &method) {
+                    break;
+                }
+                if !self.allow_http_upstream && matches!(fallback.scheme, Scheme::Http) {
+                    tracing::warn!(
+                        upstream_id = %upstream.id,
+                        host = %fallback.host,
+                        "skipping HTTP failover endpoint; only HTTPS endpoints are permitted"
+                    );
+                    continue;
+                }
+                tracing::debug!(
+                    upstream_id = %upstream.id,
+                    host = %fallback.host,
+                    port = fallback.port,
+                    "primary endpoint failed, trying failover endpoint"
+                );
+                outbound_calls.take(&mut outbound_headers, &instance_uri)?;
+                set_endpoint_headers(&mut outbound_headers, fallback);
+                notes.host_slot = None;
+                self.resolve_with_retry(fallback).await;
+                notes.host_slot = self
+                    .acquire_host_slot(fallback, timeout, &instance_uri)
+                    .await?;
+                self.set_budget_header(
+                    &upstream,
+                    &route,
+                    notes.arrived,
+                    timeout,
+                    &mut outbound_headers,
+                );
+                url = request_builder::build_upstream_url(
+                    fallback,
+                    route_path,
+                    remaining_suffix,
+                    &query_params,
+                    trailing_slash,
+                )?;
+                wire = session_bridge::serialize_request_wire(
+                    &method,
+                    &url,
+                    &outbound_headers,
+                    Some(&body_bytes),
+                );
+                #[cfg(any(test, feature = "test-utils"))]
+                self.capture_outbound(&method, &url, &outbound_headers);
+                result = self
+                    .send_buffered(&wire, is_head, timeout, &url, &instance_uri)
+                    .await;
+                outbound_calls.settle(&result);
+            }
+            let mut response = result?;
+
+            // 10. Body-triggered retry: some upstreams report failures as a
+            //     2xx with an error envelope. Only idempotent requests are
+            //     replayed, and only while the response fits the buffer limit.
+            if let Some(retry) = upstream.retry_on_body.as_ref()
+                && body_retry::is_idempotent(&method)
+                && let Some(matcher) = body_retry::CompiledBodyMatcher::new(&retry.matcher)
+            {
+                for attempt in 1..=retry.max_retries {
+                    if !response.status().is_success() || !self.fits_buffer(response.headers()) {
+                        break;
+                    }
+                    let (parts, body) = response.into_parts();
+                    let body = read_response_body(body, &instance_uri).await?;
+                    let matched = matcher.matches(&body);
+                    response = http::Response::from_parts(parts, Body::Bytes(body));
+                    if !matched {
+                        break;
+                    }
+                    tracing::debug!(
+                        upstream_id = %upstream.id,
+                        attempt,
+                        "response body matched retry_on_body, retrying"
+                    );
+                    outbound_calls.take(&mut outbound_headers, &instance_uri)?;
+                    wire = session_bridge::serialize_request_wire(
+                        &method,
+                        &url,
+                        &outbound_headers,
+                        Some(&body_bytes),
+                    );
+                    #[cfg(any(test, feature = "test-utils"))]
+                    self.capture_outbound(&method, &url, &outbound_headers);
+                    let result = self
+                        .send_buffered(&wire, is_head, timeout, &url, &instance_uri)
+                        .await;
+                    outbound_calls.settle(&result);
+                    response = result?;
+                }
+            }
+
+            let mut response = self
+                .apply_response_stages(
+                    &route,
+                    &transformers,
+                    response,
+                    is_head,
+                    &instance_uri,
+                    None,
+                )
+                .await?;
+            response.extensions_mut().insert(trailers);
+            // A 304 to the gateway's own revalidation serves the stored body
+            // under the refreshed headers, and is stored again below.
+            if response.status() == http::StatusCode::NOT_MODIFIED
+                && let Some(mut stale) = stale
+            {
+                tracing::debug!(upstream_id = %upstream.id, route_id = %route.id, "response cache revalidated");
+                response_cache::merge_not_modified(&mut stale.headers, response.headers());
+                response = cached_to_response(stale, instance_uri.clone())?;
+            }
+            match cache_key {
+                Some(key) => {
+                    self.store_if_cacheable(
+                        key,
+                        &route,
+                        &req_headers,
+                        credentialed,
+                        response,
+                        instance_uri,
+                    )
+                    .await
+                }
+                None => Ok(response),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DataPlaneService for DataPlaneServiceImpl {
+    async fn proxy_request(
+        &self,
+        ctx: SecurityContext,
+        req: http::Request<Body>,
+    ) -> Result<http::Response<Body>, DomainError> {
+        let arrived = self.clock.instant();
+        let instance_uri = req.uri().to_string();
+        let compress_min_size = self
+            .compress_min_size
+            .filter(|_| compression::accepts_gzip(req.headers()));
+        let permit = self
+            .concurrency_limiter
+            .acquire(&instance_uri)
+            .await
+            .inspect_err(|e| self.record_error(None, e))?;
+        // Only the method is recorded up front: the URL may carry secrets.
+        // `forward` fills in the upstream and route once they are resolved.
+        let span = tracing::info_span!(
+            "oagw_proxy",
+            http.method = %req.method(),
+            otel.kind = "client",
+            upstream.id = tracing::field::Empty,
+            route.id = tracing::field::Empty,
+            peer.service = tracing::field::Empty,
+            http.status_code = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        let started = Instant::now();
+        let (sla_tx, sla_rx) = watch::channel(None);
+        let mut notes = ForwardNotes {
+            sla: Some(sla_tx),
+            arrived: Some(arrived),
+            ..ForwardNotes::default()
+        };
+        let result = tokio::select! {
+            result = self.forward(ctx, req, &mut notes).instrument(span.clone()) => result,
+            sla = sla_elapsed(sla_rx.clone(), started) => Err(DomainError::RequestTimeout {
+                detail: format!("request exceeded the route's {}ms maximum duration", sla.as_millis()),
+                instance: instance_uri.clone(),
+            }),
+        };
+        match &result {
+            Ok(response) => {
+                span.record("http.status_code", response.status().as_u16());
+                if response.status().is_client_error() || response.status().is_server_error() {
+                    span.record("error", true);
+                }
+            }
+            Err(_) => {
+                span.record("error", true);
+            }
+        }
+        if let Some((route_id, redact_paths)) = &notes.sampled_route {
+            log_sampling::log_response(
+                *route_id,
+                &result,
+                started.elapsed(),
+                &self.error_status_codes,
+                redact_paths,
+            );
+        }
+        if let Some((upstream_id, circuit)) = notes.circuit {
+            self.circuit_breaker
+                .record(upstream_id, &circuit, call_outcome(&result));
+            if let Ok(response) = &result {
+                self.latency.record(upstream_id, started.elapsed());
+                self.metrics.record_proxy_response(
+                    upstream_id,
+                    response.status(),
+                    self.error_status_codes.result_label(response.status()),
+                );
+            }
+        }
+        if let (Some((plugin, credential)), Ok(response)) = (&notes.credential, &result) {
+            plugin.upstream_responded(credential, response.status().as_u16());
+        }
+        let target = notes.target;
+        let mut response = result.inspect_err(|e| self.record_error(target, e))?;
+        if let Some((key, ttl)) = notes.idempotency {
+            response = self
+                .store_idempotent(key, ttl, response, &instance_uri)
+                .await
+                .inspect_err(|e| self.record_error(target, e))?;
+        }
+        if let Some(min_size) = compress_min_size {
+            response = self
+                .compress_response(response, min_size, &instance_uri)
+                .await
+                .inspect_err(|e| self.record_error(target, e))?;
+        }
+        if let Some(status) = notes.rate_limit {
+            for (name, value) in rate_limit_header_pairs(&self.rate_limit_headers, status) {
+                if let (Ok(name), Ok(value)) = (
+                    HeaderName::from_bytes(name.as_bytes()),
+                    HeaderValue::from_str(&value),
+                ) {
+                    response.headers_mut().insert(name, value);
+                }
+            }
+        }
+        for (name, value) in notes.deprecation.drain(..) {
+            response.headers_mut().insert(name, value);
+        }
+        let sla = *sla_rx.borrow();
+        if let Some(sla) = sla {
+            let deadline = started + sla;
+            response = response.map(|body| match body {
+                Body::Stream(stream) => {
+                    Body::Stream(abort_at_deadline(stream, deadline, instance_uri.clone()))
+                }
+                other => other,
+            });
+        }
+        // An SSE response past `max_concurrent_streams` is refused; dropping
+        // it closes the upstream connection.
+        let is_event_stream = matches!(response.body(), Body::Stream(_))
+            && StreamIdleTimeout::applies_to(response.headers());
+        let stream_slot = match &self.stream_slots {
+            Some(slots) if is_event_stream => Some(
+                slots
+                    .clone()
+                    .try_acquire_owned()
+                    .map_err(|_| DomainError::GatewayOverloaded {
+                        detail: "gateway is at its concurrent stream limit".into(),
+                        instance: instance_uri.clone(),
+                    })
+                    .inspect_err(|e| self.record_error(target, e))?,
+            ),
+            _ => None,
+        };
+        if let Some(idle) = &self.stream_idle_timeout
+            && is_event_stream
+        {
+            response = response.map(|body| match body {
+                Body::Stream(stream) => Body::Stream(idle.wrap(stream, instance_uri)),
+                other => other,
+            });
+        }
+        // A streamed body is still in flight after we return: keep the slots
+        // until the stream is dropped.
+        let slots = (
+            permit,
+            notes.upstream_slot.take(),
+            notes.host_slot.take(),
+            stream_slot,
+        );
+        Ok(match slots {
+            (None, None, None, None) => response,
+            slots => response.map(|body| match body {
+                Body::Stream(stream) => Body::Stream(Box::pin(stream.map(move |chunk| {
+                    let _slots = &slots;
+                    chunk
+                }))),
+                other => other,
+            }),
+        })
+    }
+
+    fn remove_rate_limit_key(&self, key: &str) {
+        self.rate_limiter.remove_key(key);
+    }
+
+    fn runtime_state(&self, upstream_id: Uuid) -> UpstreamRuntimeState {
+        UpstreamRuntimeState {
+            upstream_id,
+            circuit: self.circuit_breaker.status(upstream_id),
+            rate_limits: self
+                .rate_limiter
+                .statuses(&format!("upstream:{upstream_id}")),
+        }
+    }
+
+    fn reset_circuit_breaker(&self, upstream_id: Uuid) {
+        self.circuit_breaker.reset(upstream_id);
+    }
+
+    fn reset_rate_limit(&self, tenant_id: Uuid, upstream_id: Uuid) {
+        self.rate_limiter
+            .remove_key(&format!("upstream:{upstream_id}:tenant:{tenant_id}"));
+        self.rate_limiter
+            .remove_key(&format!("upstream:{upstream_id}"));
+    }
+
+    fn recent_errors(&self) -> Vec<RecentError> {
+        self.recent_errors.snapshot()
+    }
+
+    async fn flush_caches(&self) -> FlushedCaches {
+        FlushedCaches {
+            response_cache: self.response_cache.clear().await,
+            token_cache: self.auth_registry.clear_caches(),
+        }
+    }
+
+    async fn status(&self) -> GatewayStatus {
+        let mut reasons = Vec::new();
+        if !self.readiness.plugins_initialized() {
+            reasons.push("auth plugins not initialized".to_string());
+        }
+        if !self.readiness.repos_loaded() {
+            reasons.push("upstream/route configuration not loaded".to_string());
+        }
+        if let Err(e) = self.auth_registry.health().await {
+            reasons.push(format!("auth plugin unhealthy: {e}"));
+        }
+        if let Some(ref endpoint) = self.readiness_probe {
+            match tokio::time::timeout(
+                READINESS_PROBE_TIMEOUT,
+                tokio::net::TcpStream::connect(endpoint.as_str()),
+            )
+            .await
+            {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => reasons.push(format!("sample upstream {endpoint} unreachable: {e}")),
+                Err(_) => reasons.push(format!(
+                    "sample upstream {endpoint} unreachable: timed out after {READINESS_PROBE_TIMEOUT:?}"
+                )),
+            }
+        }
+        GatewayStatus {
+            live: true,
+            ready: reasons.is_empty(),
+            reasons,
+            upstream_latencies: self.latency.snapshot(),
+        }
+    }
+
+    async fn test_upstream(
+        &self,
+        ctx: &SecurityContext,
+        upstream: &Upstream,
+    ) -> UpstreamTestResult {
+        let instance_uri = format!("/oagw/v1/upstreams/{}/test", upstream.id);
+        let mut result = UpstreamTestResult::default();
+
+        let tenant_default = if upstream.auth.is_none() {
+            match self.cp.resolve_tenant_default_auth(ctx).await {
+                Ok(auth) => auth,
+                Err(e) => {
+                    result.error = Some(e.to_string());
+                    return result;
+                }
+            }
+        } else {
+            None
+        };
+        let mut auth_headers = AuthHeaders::new();
+        if let Some(auth) = upstream.auth.as_ref().or(tenant_default.as_ref()) {
+            let plugin = match self.auth_registry.resolve(&auth.plugin_type) {
+                Ok(plugin) => plugin,
+                Err(e) => {
+                    result.error = Some(e.to_string());
+                    return result;
+                }
+            };
             let mut auth_ctx = AuthContext {
-                headers: auth_headers,
+                headers: AuthHeaders::new(),
                 config: auth.config.clone().unwrap_or_default(),
                 security_context: ctx.clone(),
+                early_response: None,
+                body_fields: Vec::new(),
+                query: Vec::new(),
+                method: "GET".into(),
+                path: "/".into(),
+                body: None,
+                now: self.trusted_now(),
+                credential: None,
+            };
+            match self
+                .authenticate_timed(&auth.plugin_type, plugin.as_ref(), &mut auth_ctx)
+                .await
+            {
+                Ok(_) => result.credential_resolved = true,
+                Err(e) => {
+                    // The plugin got as far as refusing: its credentials resolved.
+                    result.credential_resolved =
+                        matches!(e, PluginError::AuthFailed(_) | PluginError::Rejected(_));
+                    result.error = Some(e.to_string());
+                    return result;
+                }
+            }
+            if auth_ctx.early_response.is_some() {
+                result.error = Some("auth plugin answered the probe itself".into());
+                return result;
+            }
+            auth_headers = auth_ctx.headers;
+        } else {
+            result.credential_resolved = true;
+        }
+        result.auth_applied = true;
+
+        match self
+            .probe_upstream(upstream, &auth_headers, &instance_uri)
+            .await
+        {
+            Ok((status, latency)) => {
+                result.upstream_status = Some(status);
+                result.latency_ms = Some(u64::try_from(latency.as_millis()).unwrap_or(u64::MAX));
+            }
+            Err(e) => result.error = Some(e.to_string()),
+        }
+        result
+    }
+
+    async fn describe_route(
+        &self,
+        ctx: &SecurityContext,
+        route_id: Uuid,
+    ) -> Result<RouteDescription, DomainError> {
+        let (selected, effective, route) = self.cp.resolve_route_config(ctx, route_id).await?;
+        let own_or_inherited = |is_own: bool| {
+            if is_own {
+                ConfigSource::Upstream
+            } else {
+                ConfigSource::Ancestor
+            }
+        };
+
+        let tenant_limit = self.cp.resolve_tenant_rate_limit(ctx, effective.id).await?;
+        let rate_limit = match tenant_limit {
+            Some(tl) => Some(Sourced {
+                value: tl.rate_limit,
+                source: ConfigSource::TenantPolicy,
+            }),
+            None => effective.rate_limit.clone().map(|rl| {
+                let source = if route.rate_limit.as_ref() == Some(&rl) {
+                    ConfigSource::Route
+                } else {
+                    own_or_inherited(selected.rate_limit.as_ref() == Some(&rl))
+                };
+                Sourced { value: rl, source }
+            }),
+        };
+
+        let overrides = effective.circuit_breaker.unwrap_or_default();
+        let defaults = self.circuit_defaults;
+        Ok(RouteDescription {
+            upstream_id: effective.id,
+            timeout_secs: first_set(
+                [
+                    (route.timeout_secs, ConfigSource::Route),
+                    (effective.timeout_secs, ConfigSource::Upstream),
+                ],
+                self.request_timeout().as_secs(),
+            ),
+            user_agent: first_set(
+                [(effective.user_agent.clone(), ConfigSource::Upstream)],
+                self.user_agent.to_str().unwrap_or_default().to_string(),
+            ),
+            auth: effective.auth.clone().map(|auth| Sourced {
+                source: own_or_inherited(selected.auth.as_ref() == Some(&auth)),
+                value: auth,
+            }),
+            headers: effective.headers.clone().map(|headers| Sourced {
+                source: own_or_inherited(selected.headers.as_ref() == Some(&headers)),
+                value: headers,
+            }),
+            rate_limit,
+            circuit_failure_threshold: first_set(
+                [(overrides.failure_threshold, ConfigSource::Upstream)],
+                defaults.failure_threshold,
+            ),
+            circuit_cooldown_secs: first_set(
+                [(overrides.cooldown_secs, ConfigSource::Upstream)],
+                defaults.cooldown.as_secs(),
+            ),
+            circuit_half_open_probes: first_set(
+                [(overrides.half_open_probes, ConfigSource::Upstream)],
+                defaults.half_open_probes,
+            ),
+            route,
+        })
+    }
+
+    async fn trace_request(
+        &self,
+        ctx: SecurityContext,
+        req: http::Request<Body>,
+        mock_response: Option<http::Response<Body>>,
+    ) -> RequestTrace {
+        let mut notes = ForwardNotes {
+            trace: Some(TraceState {
+                trace: RequestTrace::default(),
+                mock_response,
+                stopped: false,
+            }),
+            ..ForwardNotes::default()
+        };
+        let result = self.forward(ctx, req, &mut notes).await;
+        let Some(TraceState {
+            mut trace, stopped, ..
+        }) = notes.trace
+        else {
+            unreachable!("trace state is set above");
+        };
+        match result {
+            Ok(_) if stopped => {}
+            Ok(response) => trace.status = Some(response.status().as_u16()),
+            Err(e) => trace.error = Some(e.to_string()),
+        }
+        trace
+    }
+
+    async fn shutdown(&self) {
+        self.response_cache.flush().await;
+    }
+}
+
+/// The first layer that sets a value, falling back to the gateway default.
+fn first_set<T, const N: usize>(layers: [(Option<T>, ConfigSource); N], default: T) -> Sourced<T> {
+    layers
+        .into_iter()
+        .find_map(|(value, source)| value.map(|value| Sourced { value, source }))
+        .unwrap_or(Sourced {
+            value: default,
+            source: ConfigSource::Gateway,
+        })
+}
+
+/// Header carrying a route's API version; validated at write time, so parse
+/// failures only skip the header.
+fn api_version_header(version: &ApiVersion) -> Option<(http::HeaderName, HeaderValue)> {
+    let name = version.header.as_deref().unwrap_or("Api-Version");
+    Some((
+        http::HeaderName::from_bytes(name.as_bytes()).ok()?,
+        HeaderValue::from_str(&version.value).ok()?,
+    ))
+}
+
+/// `Deprecation: true` for a deprecated route and `Sunset` with its sunset
+/// date (RFC 8594), when set.
+fn deprecation_headers(route: &Route) -> Vec<(HeaderName, HeaderValue)> {
+    let mut headers = Vec::new();
+    if route.deprecated {
+        headers.push((
+            HeaderName::from_static("deprecation"),
+            HeaderValue::from_static("true"),
+        ));
+    }
+    if let Some(sunset) = route
+        .sunset
+        .as_deref()
+        .and_then(|s| HeaderValue::from_str(s).ok())
+    {
+        headers.push((HeaderName::from_static("sunset"), sunset));
+    }
+    headers
+}
+
+/// The request body limit for `route`: its own `max_body_size_bytes` when
+/// that is below the gateway-wide `global` limit.
+fn route_body_limit(route: &Route, global: usize) -> usize {
+    route
+        .max_body_size_bytes
+        .and_then(|bytes| usize::try_from(bytes).ok())
+        .map_or(global, |bytes| bytes.min(global))
+}
+
+/// Upstream attempts made for one request, against the route's
+/// `max_outbound_calls`.
+struct OutboundCalls {
+    max: Option<u32>,
+    made: u32,
+}
+
+impl OutboundCalls {
+    fn new(route: &Route) -> Self {
+        Self {
+            max: route.max_outbound_calls,
+            made: 0,
+        }
+    }
+
+    /// Count the first attempt of one more send, and hand Pingora what is
+    /// left of the cap for that send's connect and pooled-connection
+    /// retries through `headers`.
+    fn take(&mut self, headers: &mut HeaderMap, instance: &str) -> Result<(), DomainError> {
+        if let Some(max) = self.max {
+            if self.made >= max {
+                return Err(DomainError::DownstreamError {
+                    detail: format!(
+                        "request needs more than the route's max_outbound_calls of {max}"
+                    ),
+                    instance: instance.to_string(),
+                });
+            }
+            headers.insert(H_ATTEMPT_BUDGET, HeaderValue::from(max - self.made));
+        }
+        self.made += 1;
+        Ok(())
+    }
+
+    /// Count the retries Pingora made on top of the attempt `take` counted.
+    fn settle(&mut self, result: &Result<http::Response<Body>, DomainError>) {
+        if let Ok(response) = result
+            && let Some(UpstreamAttempts(attempts)) = response.extensions().get()
+        {
+            self.made += attempts.saturating_sub(1);
+        }
+    }
+}
+
+/// Upstream attempts Pingora made for one send, reported through
+/// `H_UPSTREAM_ATTEMPTS`.
+#[derive(Debug, Clone, Copy)]
+struct UpstreamAttempts(u32);
+
+/// Auth of the first rule whose condition matches the request path (after
+/// the alias) and headers. `None` when no rule matches; `Some(None)` when
+/// the matching rule sends the request without auth.
+fn select_auth<'a>(
+    rules: &'a [AuthRule],
+    path: &str,
+    req_headers: &HeaderMap,
+) -> Option<Option<&'a AuthConfig>> {
+    rules
+        .iter()
+        .find(|rule| match &rule.condition {
+            AuthCondition::PathPrefix { prefix } => {
+                let prefix = prefix.trim_end_matches('/');
+                path.strip_prefix(prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            }
+            AuthCondition::Header { name, value } => {
+                req_headers.get_all(name.as_str()).iter().any(|v| {
+                    value
+                        .as_deref()
+                        .is_none_or(|expected| v.as_bytes() == expected.as_bytes())
+                })
+            }
+        })
+        .map(|rule| rule.auth.as_ref())
+}
+
+/// Key for consistent-hash endpoint selection per the upstream's `sticky_by`.
+/// `None` (round-robin) when stickiness is off or the header is absent.
+fn sticky_key(upstream: &Upstream, req_headers: &HeaderMap, tenant_id: Uuid) -> Option<Vec<u8>> {
+    match upstream.sticky_by()? {
+        StickyBy::Header(name) => req_headers
+            .get(name.as_str())
+            .map(|v| v.as_bytes().to_vec()),
+        StickyBy::Tenant => Some(tenant_id.as_bytes().to_vec()),
+    }
+}
+
+/// Merge `x-oagw-auth-config-<key>` request headers into an auth plugin's
+/// `config`. Only keys in `allowed` are taken; other overrides are ignored.
+fn apply_auth_config_overrides(
+    req_headers: &HeaderMap,
+    allowed: &[String],
+    config: &mut HashMap<String, String>,
+) {
+    if allowed.is_empty() {
+        return;
+    }
+    for (name, value) in req_headers {
+        let Some(key) = name.as_str().strip_prefix(AUTH_CONFIG_OVERRIDE_PREFIX) else {
+            continue;
+        };
+        if !allowed.iter().any(|a| a == key) {
+            tracing::debug!(key, "ignoring auth config override that is not allowlisted");
+            continue;
+        }
+        if let Ok(value) = value.to_str() {
+            tracing::debug!(key, "auth config overridden by request header");
+            config.insert(key.to_string(), value.to_string());
+        }
+    }
+}
+
+/// Resolves once the route's `max_total_duration_ms`, counted from
+/// `started`, has passed. Pending while no route SLA has been reported.
+async fn sla_elapsed(mut sla: watch::Receiver<Option<Duration>>, started: Instant) -> Duration {
+    let limit = sla.wait_for(Option::is_some).await.map(|limit| *limit);
+    let Ok(Some(limit)) = limit else {
+        return std::future::pending().await;
+    };
+    tokio::time::sleep_until((started + limit).into()).await;
+    limit
+}
+
+/// End a streamed response body with an error once `deadline` passes, so
+/// the client connection is aborted instead of outliving the route's SLA.
+fn abort_at_deadline(stream: BodyStream, deadline: Instant, instance_uri: String) -> BodyStream {
+    Box::pin(futures_util::stream::unfold(Some(stream), move |state| {
+        let instance_uri = instance_uri.clone();
+        async move {
+            let mut stream = state?;
+            match tokio::time::timeout_at(deadline.into(), stream.next()).await {
+                Ok(Some(chunk)) => Some((chunk, Some(stream))),
+                Ok(None) => None,
+                Err(_) => {
+                    tracing::warn!(
+                        instance = %instance_uri,
+                        "response exceeded the route's maximum duration, aborting stream"
+                    );
+                    let err: oagw_sdk::body::BoxError =
+                        "response exceeded the route's maximum duration".into();
+                    Some((Err(err), None))
+                }
+            }
+        }
+    }))
+}
+
+/// Attribute the current `oagw_proxy` span to the resolved upstream and
+/// route. The upstream alias stands in for `peer.service`.
+fn record_span_target(upstream: &Upstream, route: &Route) {
+    let span = tracing::Span::current();
+    span.record("upstream.id", tracing::field::display(upstream.id));
+    span.record("route.id", tracing::field::display(route.id));
+    span.record("peer.service", upstream.alias.as_str());
+}
+
+/// Classify a proxied request for the circuit breaker: gateway-class `5xx`
+/// responses, connection failures, and timeouts count against the upstream.
+fn call_outcome(result: &Result<http::Response<Body>, DomainError>) -> CallOutcome {
+    match result {
+        Ok(resp) => match resp.status() {
+            http::StatusCode::INTERNAL_SERVER_ERROR
+            | http::StatusCode::BAD_GATEWAY
+            | http::StatusCode::SERVICE_UNAVAILABLE
+            | http::StatusCode::GATEWAY_TIMEOUT => CallOutcome::Failure,
+            _ => CallOutcome::Success,
+        },
+        Err(
+            DomainError::DownstreamError { .. }
+            | DomainError::ConnectionTimeout { .. }
+            | DomainError::RequestTimeout { .. },
+        ) => CallOutcome::Failure,
+        Err(_) => CallOutcome::Ignored,
+    }
+}
+
+/// Build the final proxy response: extract error source, sanitize headers
+/// (including the operator's `extra_hop_by_hop`), assemble the
+/// `http::Response<Body>`.
+fn build_proxy_response(
+    status: http::StatusCode,
+    mut resp_headers: HeaderMap,
+    body_stream: BodyStream,
+    instance_uri: String,
+    extra_hop_by_hop: &[HeaderName],
+) -> Result<http::Response<Body>, DomainError> {
+    let error_source = headers::extract_error_source(&resp_headers);
+    headers::sanitize_response_headers(&mut resp_headers);
+    headers::strip_headers(&mut resp_headers, extra_hop_by_hop);
+
+    let mut resp = http::Response::builder()
+        .status(status)
+        .body(Body::Stream(body_stream))
+        .map_err(|e| DomainError::DownstreamError {
+            detail: format!("failed to build response: {e}"),
+            instance: instance_uri,
+        })?;
+    *resp.headers_mut() = resp_headers;
+    resp.extensions_mut().insert(error_source);
+    Ok(resp)
+}
+
+/// Point the outbound request at `endpoint`: `Host` plus the internal
+/// endpoint headers consumed by `PingoraProxy`.
+fn set_endpoint_headers(outbound_headers: &mut HeaderMap, endpoint: &Endpoint) {
+    headers::set_host_header(outbound_headers, &endpoint.host, endpoint.port);
+    let scheme_str = match endpoint.scheme {
+        Scheme::Http => "http",
+        Scheme::Https => "https",
+        Scheme::Wss => "wss",
+        Scheme::Wt => "wt",
+        Scheme::Grpc => "grpc",
+    };
+    if let Ok(v) = HeaderValue::from_str(&endpoint.host) {
+        outbound_headers.insert(H_ENDPOINT_HOST, v);
+    }
+    if let Ok(v) = HeaderValue::from_str(&endpoint.port.to_string()) {
+        outbound_headers.insert(H_ENDPOINT_PORT, v);
+    }
+    outbound_headers.insert(H_ENDPOINT_SCHEME, HeaderValue::from_static(scheme_str));
+}
+
+/// `Allow` header methods for an upstream's `allowed_methods`. HEAD is
+/// listed with GET, since GET routes serve it.
+fn allow_header_methods(allowed: &[HttpMethod]) -> Vec<String> {
+    let mut methods = Vec::with_capacity(allowed.len() + 1);
+    for m in allowed {
+        methods.push(m.as_str().to_string());
+        if *m == HttpMethod::Get {
+            methods.push(http::Method::HEAD.to_string());
+        }
+    }
+    methods
+}
+
+/// Whether an attempt should fail over to the next endpoint. Gateway-side
+/// failures (the request never got an upstream answer) always qualify;
+/// retryable upstream statuses only for idempotent methods, since the
+/// upstream may already have acted on the request.
+fn needs_failover(
+    result: &Result<http::Response<Body>, DomainError>,
+    method: &http::Method,
+) -> bool {
+    let Ok(resp) = result else {
+        return true;
+    };
+    let retryable = matches!(
+        resp.status(),
+        http::StatusCode::BAD_GATEWAY
+            | http::StatusCode::SERVICE_UNAVAILABLE
+            | http::StatusCode::GATEWAY_TIMEOUT
+    );
+    if !retryable {
+        return false;
+    }
+    resp.extensions().get::<ErrorSource>() == Some(&ErrorSource::Gateway)
+        || body_retry::is_idempotent(method)
+}
+
+/// Reject an upstream response whose media type is outside the route's
+/// `allowed_response_content_types`. Responses that carry no body need no
+/// `Content-Type`.
+fn check_response_content_type(
+    route: &Route,
+    resp: &http::Response<Body>,
+    is_head: bool,
+    instance_uri: &str,
+) -> Result<(), DomainError> {
+    let bodyless = is_head
+        || matches!(
+            resp.status(),
+            http::StatusCode::NO_CONTENT | http::StatusCode::NOT_MODIFIED
+        );
+    if headers::content_type_allowed(
+        resp.headers(),
+        &route.allowed_response_content_types,
+        bodyless,
+    ) {
+        return Ok(());
+    }
+    Err(DomainError::DownstreamError {
+        detail: format!(
+            "upstream response content type {:?} is not allowed for this route",
+            resp.headers()
+                .get(http::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+        ),
+        instance: instance_uri.to_string(),
+    })
+}
+
+/// Rewrite the upstream status per the route's `status_remap`, recording the
+/// original in [`H_ORIGINAL_STATUS`]. The body is left untouched.
+fn apply_status_remap(
+    remap: &HashMap<u16, u16>,
+    mut resp: http::Response<Body>,
+) -> http::Response<Body> {
+    let original = resp.status();
+    let Some(mapped) = remap
+        .get(&original.as_u16())
+        .and_then(|&code| http::StatusCode::from_u16(code).ok())
+    else {
+        return resp;
+    };
+    *resp.status_mut() = mapped;
+    resp.headers_mut().insert(
+        HeaderName::from_static(H_ORIGINAL_STATUS),
+        HeaderValue::from(original.as_u16()),
+    );
+    resp
+}
+
+/// Why the task forwarding a streaming request body stopped early.
+enum BodyAbort {
+    /// The body grew past the limit; carries the bytes read so far.
+    TooLarge(usize),
+    /// The client stalled past the body read idle timeout.
+    Idle(String),
+}
+
+/// Buffer a streaming request body, stopping as soon as it grows past
+/// `max_body` so that the caller can reject it without reading the rest.
+async fn buffer_request_body(
+    mut stream: BodyStream,
+    max_body: usize,
+    instance_uri: &str,
+) -> Result<Bytes, DomainError> {
+    let mut buf = bytes::BytesMut::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| {
+            if e.downcast_ref::<BodyReadIdle>().is_some() {
+                DomainError::ClientTimeout {
+                    detail: e.to_string(),
+                    instance: instance_uri.to_string(),
+                }
+            } else {
+                DomainError::Validation {
+                    detail: format!("failed to read request body: {e}"),
+                    instance: instance_uri.to_string(),
+                }
+            }
+        })?;
+        buf.extend_from_slice(&chunk);
+        if buf.len() > max_body {
+            break;
+        }
+    }
+    Ok(buf.freeze())
+}
+
+/// Buffer a streaming request body that the gateway itself needs whole,
+/// rejecting it once it exceeds `max_body`.
+async fn buffer_within_limit(
+    stream: BodyStream,
+    max_body: usize,
+    instance_uri: &str,
+) -> Result<Bytes, DomainError> {
+    let body = buffer_request_body(stream, max_body, instance_uri).await?;
+    if body.len() > max_body {
+        return Err(DomainError::PayloadTooLarge {
+            detail: format!("request body exceeds maximum of {max_body} bytes"),
+            instance: instance_uri.to_string(),
+        });
+    }
+    Ok(body)
+}
+
+/// Buffer an upstream response body.
+async fn read_response_body(body: Body, instance_uri: &str) -> Result<Bytes, DomainError> {
+    body.into_bytes()
+        .await
+        .map_err(|e| DomainError::DownstreamError {
+            detail: format!("failed to read upstream response body: {e}"),
+            instance: instance_uri.to_string(),
+        })
+}
+
+/// Rebuild a client response from a cache entry. Cached entries are always
+/// upstream responses, so the error source is `Upstream`.
+fn cached_to_response(
+    cached: CachedResponse,
+    instance_uri: String,
+) -> Result<http::Response<Body>, DomainError> {
+    let mut resp = http::Response::builder()
+        .status(cached.status)
+        .body(Body::Bytes(cached.body))
+        .map_err(|e| DomainError::DownstreamError {
+            detail: format!("failed to build response: {e}"),
+            instance: instance_uri,
+        })?;
+    *resp.headers_mut() = cached.headers;
+    resp.extensions_mut().insert(ErrorSource::Upstream);
+    Ok(resp)
+}
+
+/// Answer a `HEAD` from a cached `GET` entry: its status and headers, with
+/// `Content-Length` taken from the stored body when the upstream sent none.
+fn cached_to_head_response(
+    mut cached: CachedResponse,
+    instance_uri: String,
+) -> Result<http::Response<Body>, DomainError> {
+    if !cached.headers.contains_key(http::header::CONTENT_LENGTH) {
+        cached.headers.remove(http::header::TRANSFER_ENCODING);
+        cached
+            .headers
+            .insert(http::header::CONTENT_LENGTH, cached.body.len().into());
+    }
+    cached.body = Bytes::new();
+    cached_to_response(cached, instance_uri)
+}
+
+/// Rate-limit state as `(name, value)` header pairs under `names`.
+fn rate_limit_header_pairs(
+    names: &RateLimitHeaderNames,
+    status: RateLimitStatus,
+) -> Vec<(String, String)> {
+    vec![
+        (names.limit.clone(), status.limit.to_string()),
+        (names.remaining.clone(), status.remaining.to_string()),
+        (names.reset.clone(), status.reset_secs.to_string()),
+    ]
+}
+
+/// Build the client response for an auth plugin's [`EarlyResponse`]. The
+/// response originates in the gateway, so the error source is `Gateway`.
+fn early_to_response(
+    early: EarlyResponse,
+    instance_uri: String,
+) -> Result<http::Response<Body>, DomainError> {
+    let mut builder = http::Response::builder().status(early.status);
+    for (name, value) in early.headers.iter() {
+        builder = builder.header(name, value);
+    }
+    let mut resp =
+        builder
+            .body(Body::from(early.body))
+            .map_err(|e| DomainError::DownstreamError {
+                detail: format!("auth plugin returned an invalid response: {e}"),
+                instance: instance_uri,
+            })?;
+    resp.extensions_mut().insert(ErrorSource::Gateway);
+    Ok(resp)
+}
+
+/// Route used for requests sent to the catch-all upstream: no match rules
+/// (so no query allowlist or suffix checks), no overrides.
+fn catch_all_route(upstream: &Upstream) -> Route {
+    Route {
+        id: Uuid::nil(),
+        tenant_id: upstream.tenant_id,
+        upstream_id: upstream.id,
+        match_rules: MatchRules {
+            http: None,
+            grpc: None,
+        },
+        plugins: None,
+        rate_limit: None,
+        tags: vec![],
+        priority: 0,
+        enabled: true,
+        skip_authz: false,
+        timeout_secs: None,
+        status_remap: HashMap::new(),
+        allowed_response_content_types: vec![],
+        allowed_request_content_types: vec![],
+        log_sample_rate: None,
+        request_schema: None,
+        body_rewrite: vec![],
+        auth_rules: vec![],
+        strip_query_params: vec![],
+        strip_inbound_headers: vec![],
+        idempotency_ttl_secs: None,
+        cache_responses: false,
+        negative_cache_ttl_secs: None,
+        response_header_allowlist: None,
+        max_total_duration_ms: None,
+        max_body_size_bytes: None,
+        max_outbound_calls: None,
+        coerce_content_type: None,
+        api_version: None,
+        body_transformers: vec![],
+        log_redact_paths: vec![],
+        transform_hook_url: None,
+        deprecated: false,
+        sunset: None,
+        kind: RouteKind::Proxy,
+    }
+}
+
+/// Build the configured response of a static route. HEAD gets the headers
+/// without the body.
+fn static_to_response(
+    kind: RouteKind,
+    is_head: bool,
+    instance_uri: String,
+) -> Result<http::Response<Body>, DomainError> {
+    let RouteKind::Static {
+        status,
+        headers,
+        body,
+    } = kind
+    else {
+        return Err(DomainError::internal("route is not a static route"));
+    };
+    let mut builder = http::Response::builder().status(status);
+    for (name, value) in &headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    let body = if is_head {
+        Body::Empty
+    } else {
+        Body::from(body)
+    };
+    let mut resp = builder
+        .body(body)
+        .map_err(|e| DomainError::DownstreamError {
+            detail: format!("static route has an invalid response: {e}"),
+            instance: instance_uri,
+        })?;
+    resp.extensions_mut().insert(ErrorSource::Gateway);
+    Ok(resp)
+}
+
+/// Effective proxy timeout with precedence route > upstream > global.
+fn resolve_timeout(
+    global: Duration,
+    upstream_secs: Option<u64>,
+    route_secs: Option<u64>,
+) -> Duration {
+    route_secs
+        .or(upstream_secs)
+        .map_or(global, Duration::from_secs)
+}
+
+/// Reject paths that cannot be forwarded safely: broken percent-escapes,
+/// escapes that decode to control characters, or non-UTF-8 byte sequences.
+/// Raw control characters never reach this point; `http::Uri` rejects them.
+fn validate_path(path: &str) -> Result<(), &'static str> {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes
+                .get(i + 1..i + 3)
+                .and_then(|h| std::str::from_utf8(h).ok())
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+                .ok_or("invalid percent-encoding")?;
+            decoded.push(hex);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    if decoded.iter().any(u8::is_ascii_control) {
+        return Err("control characters are not allowed");
+    }
+    std::str::from_utf8(&decoded).map_err(|_| "path is not valid UTF-8")?;
+    Ok(())
+}
+
+/// Normalize a URL path: collapse consecutive slashes and resolve `.`/`..` segments.
+/// Segments that would escape above the root are discarded.
+fn normalize_path(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for seg in path.split('/') {
+        match seg {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            s => segments.push(s),
+        }
+    }
+    let mut result = String::with_capacity(path.len());
+    if path.starts_with('/') {
+        result.push('/');
+    }
+    result.push_str(&segments.join("/"));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::{Endpoint, Scheme, Server, Upstream};
+    use crate::domain::services::EndpointSelector;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use uuid::Uuid;
+
+    #[test]
+    fn validate_path_accepts_encoded_text() {
+        assert!(validate_path("/alias/v1/files/caf%C3%A9%20menu.txt").is_ok());
+    }
+
+    #[test]
+    fn validate_path_rejects_malformed_paths() {
+        assert!(validate_path("/alias/v1/a%0Ab").is_err());
+        assert!(validate_path("/alias/v1/a%00").is_err());
+        assert!(validate_path("/alias/v1/a%7f").is_err());
+        assert!(validate_path("/alias/v1/a%zz").is_err());
+        assert!(validate_path("/alias/v1/a%4").is_err());
+        assert!(validate_path("/alias/v1/%FF%FE").is_err());
+    }
+
+    #[test]
+    fn normalize_collapses_double_slashes() {
+        assert_eq!(normalize_path("/alias//v1//chat"), "/alias/v1/chat");
+    }
+
+    #[test]
+    fn normalize_resolves_dot_dot() {
+        assert_eq!(normalize_path("/alias/../admin/secret"), "/admin/secret");
+    }
+
+    #[test]
+    fn normalize_clamps_above_root() {
+        assert_eq!(normalize_path("/alias/../../etc/passwd"), "/etc/passwd");
+    }
+
+    #[test]
+    fn normalize_resolves_single_dot() {
+        assert_eq!(normalize_path("/alias/./v1/chat"), "/alias/v1/chat");
+    }
+
+    #[test]
+    fn normalize_preserves_clean_path() {
+        assert_eq!(normalize_path("/alias/v1/chat"), "/alias/v1/chat");
+    }
+
+    // -----------------------------------------------------------------------
+    // select_endpoint() unit tests
+    // -----------------------------------------------------------------------
+
+    fn ep(host: &str, port: u16) -> Endpoint {
+        Endpoint {
+            scheme: Scheme::Https,
+            host: host.to_string(),
+            port,
+        }
+    }
+
+    const GLOBAL: Duration = Duration::from_secs(30);
+
+    #[test]
+    fn timeout_falls_back_to_global() {
+        assert_eq!(resolve_timeout(GLOBAL, None, None), GLOBAL);
+    }
+
+    #[test]
+    fn timeout_upstream_overrides_global() {
+        assert_eq!(
+            resolve_timeout(GLOBAL, Some(5), None),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn timeout_route_overrides_global() {
+        assert_eq!(
+            resolve_timeout(GLOBAL, None, Some(7)),
+            Duration::from_secs(7)
+        );
+    }
+
+    #[test]
+    fn timeout_route_overrides_upstream() {
+        assert_eq!(
+            resolve_timeout(GLOBAL, Some(5), Some(120)),
+            Duration::from_secs(120)
+        );
+    }
+
+    fn upstream_with(endpoints: Vec<Endpoint>) -> Upstream {
+        Upstream {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            alias: "test".to_string(),
+            server: Server { endpoints },
+            protocol: "http".to_string(),
+            enabled: true,
+            auth: None,
+            headers: None,
+            plugins: None,
+            rate_limit: None,
+            tags: vec![],
+            maintenance: false,
+            maintenance_message: None,
+            retry_on_body: None,
+            force_chunked: false,
+            failover: vec![],
+            timeout_secs: None,
+            keepalive: None,
+            circuit_breaker: None,
+            user_agent: None,
+            sticky_by: None,
+            allowed_methods: vec![],
+            trailing_slash: TrailingSlash::default(),
+            max_header_size_bytes: None,
+            tls_min_version: None,
+            tls_cipher_suites: vec![],
+            tls_server_name: None,
+            budget_header: None,
+        }
+    }
+
+    /// Mock BackendSelector that returns endpoints[call_count % endpoints.len()].
+    struct MockSelector {
+        call_count: AtomicUsize,
+    }
+
+    impl MockSelector {
+        fn new() -> Self {
+            Self {
+                call_count: AtomicUsize::new(0),
+            }
+        }
+
+        fn calls(&self) -> usize {
+            self.call_count.load(Ordering::Relaxed)
+        }
+    }
+
+    #[async_trait]
+    impl EndpointSelector for MockSelector {
+        async fn select(&self, _upstream_id: Uuid, endpoints: &[Endpoint]) -> Option<Endpoint> {
+            let idx = self.call_count.fetch_add(1, Ordering::Relaxed) % endpoints.len();
+            Some(endpoints[idx].clone())
+        }
+
+        /// Deterministic stand-in for consistent hashing: key length picks the endpoint.
+        async fn select_sticky(
+            &self,
+            _upstream_id: Uuid,
+            endpoints: &[Endpoint],
+            key: &[u8],
+        ) -> Option<Endpoint> {
+            self.call_count.fetch_add(1, Ordering::Relaxed);
+            Some(endpoints[key.len() % endpoints.len()].clone())
+        }
+
+        fn healthy_count(&self, _upstream_id: Uuid) -> usize {
+            0
+        }
+
+        fn invalidate(&self, _upstream_id: Uuid) {}
+    }
+
+    /// Selector for an upstream whose every endpoint fails its health check.
+    struct AllDownSelector;
+
+    #[async_trait]
+    impl EndpointSelector for AllDownSelector {
+        async fn select(&self, _upstream_id: Uuid, _endpoints: &[Endpoint]) -> Option<Endpoint> {
+            None
+        }
+
+        async fn select_sticky(
+            &self,
+            _upstream_id: Uuid,
+            _endpoints: &[Endpoint],
+            _key: &[u8],
+        ) -> Option<Endpoint> {
+            None
+        }
+
+        fn healthy_count(&self, _upstream_id: Uuid) -> usize {
+            0
+        }
+
+        fn invalidate(&self, _upstream_id: Uuid) {}
+    }
+
+    /// Build a minimal `DataPlaneServiceImpl` with the given `BackendSelector`.
+    fn build_svc(selector: Arc<dyn EndpointSelector>) -> DataPlaneServiceImpl {
+        use authz_resolver_sdk::{
+            AuthZResolverClient, AuthZResolverError, EvaluationRequest, EvaluationResponse,
+            EvaluationResponseContext, PolicyEnforcer,
+        };
+        use credstore_sdk::{CredStoreClientV1, CredStoreError, GetSecretResponse, SecretRef};
+        use modkit_security::SecurityContext;
+
+        struct AllowAllAuthZ;
+        #[async_trait]
+        impl AuthZResolverClient for AllowAllAuthZ {
+            async fn evaluate(
+                &self,
+                _request: EvaluationRequest,
+            ) -> Result<EvaluationResponse, AuthZResolverError> {
+                Ok(EvaluationResponse {
+                    decision: true,
+                    context: EvaluationResponseContext {
+                        constraints: Vec::new(),
+                        deny_reason: None,
+                    },
+                })
+            }
+        }
+
+        struct NoopCredStore;
+        #[async_trait]
+        impl CredStoreClientV1 for NoopCredStore {
+            async fn get(
+                &self,
+                _ctx: &SecurityContext,
+                _key: &SecretRef,
+            ) -> Result<Option<GetSecretResponse>, CredStoreError> {
+                Ok(None)
+            }
+        }
+
+        let credstore: Arc<dyn CredStoreClientV1> = Arc::new(NoopCredStore);
+        let policy_enforcer = PolicyEnforcer::new(Arc::new(AllowAllAuthZ));
+
+        // Minimal CP — never called by select_endpoint().
+        use crate::domain::error::DomainError;
+        use crate::domain::model::*;
+        use crate::domain::services::ControlPlaneService;
+
+        struct NoopCp;
+        #[async_trait]
+        impl ControlPlaneService for NoopCp {
+            async fn create_upstream(
+                &self,
+                _: &SecurityContext,
+                _: CreateUpstreamRequest,
+            ) -> Result<Upstream, DomainError> {
+                unimplemented!()
+            }
+            async fn create_upstreams(
+                &self,
+                _: &SecurityContext,
+                _: Vec<CreateUpstreamRequest>,
+            ) -> Result<Vec<Upstream>, DomainError> {
+                unimplemented!()
+            }
+            async fn get_upstream(
+                &self,
+                _: &SecurityContext,
+                _: Uuid,
+            ) -> Result<Upstream, DomainError> {
+                unimplemented!()
+            }
+            async fn list_upstreams(
+                &self,
+                _: &SecurityContext,
+                _: &ListQuery,
+            ) -> Result<Vec<Upstream>, DomainError> {
+                unimplemented!()
+            }
+            async fn update_upstream(
+                &self,
+                _: &SecurityContext,
+                _: Uuid,
+                _: UpdateUpstreamRequest,
+            ) -> Result<Upstream, DomainError> {
+                unimplemented!()
+            }
+            async fn delete_upstream(
+                &self,
+                _: &SecurityContext,
+                _: Uuid,
+                _: bool,
+            ) -> Result<(), DomainError> {
+                unimplemented!()
+            }
+            async fn create_route(
+                &self,
+                _: &SecurityContext,
+                _: CreateRouteRequest,
+            ) -> Result<Route, DomainError> {
+                unimplemented!()
+            }
+            async fn create_routes(
+                &self,
+                _: &SecurityContext,
+                _: Vec<CreateRouteRequest>,
+            ) -> Result<Vec<Route>, DomainError> {
+                unimplemented!()
+            }
+            async fn get_route(&self, _: &SecurityContext, _: Uuid) -> Result<Route, DomainError> {
+                unimplemented!()
+            }
+            async fn list_routes(
+                &self,
+                _: &SecurityContext,
+                _: Uuid,
+                _: &ListQuery,
+            ) -> Result<Vec<Route>, DomainError> {
+                unimplemented!()
+            }
+            async fn update_route(
+                &self,
+                _: &SecurityContext,
+                _: Uuid,
+                _: UpdateRouteRequest,
+            ) -> Result<Route, DomainError> {
+                unimplemented!()
+            }
+            async fn delete_route(&self, _: &SecurityContext, _: Uuid) -> Result<(), DomainError> {
+                unimplemented!()
+            }
+            async fn validate_config(
+                &self,
+                _: &SecurityContext,
+                _: ConfigSnapshot,
+            ) -> Result<Vec<ConfigProblem>, DomainError> {
+                unimplemented!()
+            }
+            async fn resolve_proxy_target(
+                &self,
+                _: &SecurityContext,
+                _: &str,
+                _: &str,
+                _: &str,
+                _: Option<&[u8]>,
+            ) -> Result<(Upstream, Route), DomainError> {
+                unimplemented!()
+            }
+            async fn resolve_static_route(
+                &self,
+                _: &SecurityContext,
+                _: &str,
+                _: &str,
+            ) -> Result<Option<Route>, DomainError> {
+                unimplemented!()
+            }
+            async fn set_tenant_rate_limit(
+                &self,
+                _: &SecurityContext,
+                _: Uuid,
+                _: Uuid,
+                _: RateLimitConfig,
+            ) -> Result<TenantRateLimit, DomainError> {
+                unimplemented!()
+            }
+            async fn get_tenant_rate_limit(
+                &self,
+                _: &SecurityContext,
+                _: Uuid,
+                _: Uuid,
+            ) -> Result<TenantRateLimit, DomainError> {
+                unimplemented!()
+            }
+            async fn delete_tenant_rate_limit(
+                &self,
+                _: &SecurityContext,
+                _: Uuid,
+                _: Uuid,
+            ) -> Result<(), DomainError> {
+                unimplemented!()
+            }
+            async fn resolve_tenant_rate_limit(
+                &self,
+                _: &SecurityContext,
+                _: Uuid,
+            ) -> Result<Option<TenantRateLimit>, DomainError> {
+                unimplemented!()
+            }
+            async fn set_tenant_default_auth(
+                &self,
+                _: &SecurityContext,
+                _: AuthConfig,
+            ) -> Result<TenantAuthDefault, DomainError> {
+                unimplemented!()
+            }
+            async fn get_tenant_default_auth(
+                &self,
+                _: &SecurityContext,
+            ) -> Result<TenantAuthDefault, DomainError> {
+                unimplemented!()
+            }
+            async fn delete_tenant_default_auth(
+                &self,
+                _: &SecurityContext,
+            ) -> Result<(), DomainError> {
+                unimplemented!()
+            }
+            async fn resolve_tenant_default_auth(
+                &self,
+                _: &SecurityContext,
+            ) -> Result<Option<AuthConfig>, DomainError> {
+                unimplemented!()
+            }
+            async fn resolve_route_config(
+                &self,
+                _: &SecurityContext,
+                _: Uuid,
+            ) -> Result<(Upstream, Upstream, Route), DomainError> {
+                unimplemented!()
+            }
+        }
+
+        let cp: Arc<dyn ControlPlaneService> = Arc::new(NoopCp);
+        let server_conf = Arc::new(pingora_core::server::configuration::ServerConf::default());
+        let pingora = crate::infra::proxy::pingora_proxy::PingoraProxy::new(
+            Duration::from_secs(10),
+            Duration::from_secs(30),
+        );
+        let proxy = Arc::new(crate::infra::proxy::pingora_proxy::new_http_proxy(
+            &server_conf,
+            pingora,
+        ));
+
+        DataPlaneServiceImpl::new(
+            cp,
+            credstore,
+            policy_enforcer,
+            None,
+            TokenCacheConfig::default(),
+            selector,
+            proxy,
+        )
+    }
+
+    // P2 #12: Alias extraction happens on raw path, then suffix is normalized.
+    // Path traversal in the alias segment must not influence which upstream is resolved.
+    #[test]
+    fn alias_extraction_ignores_path_traversal() {
+        // Simulate what proxy_request does: extract alias from raw path, normalize suffix.
+        fn extract(path: &str) -> (String, String) {
+            let trimmed = path.strip_prefix('/').unwrap_or(path);
+            let (alias, raw_suffix) = match trimmed.find('/') {
+                Some(pos) => (&trimmed[..pos], &trimmed[pos..]),
+                None => (trimmed, ""),
+            };
+            (alias.to_string(), normalize_path(raw_suffix))
+        }
+
+        // Normal case.
+        let (alias, suffix) = extract("/myalias/v1/chat");
+        assert_eq!(alias, "myalias");
+        assert_eq!(suffix, "/v1/chat");
+
+        // Path traversal attempt: alias is still the first raw segment.
+        let (alias, suffix) = extract("/myalias/../admin/secret");
+        assert_eq!(alias, "myalias");
+        assert_eq!(suffix, "/admin/secret"); // ".." collapsed in suffix only
+
+        // Deep traversal: alias is still literal first segment.
+        let (alias, suffix) = extract("/myalias/../../etc/passwd");
+        assert_eq!(alias, "myalias");
+        assert_eq!(suffix, "/etc/passwd"); // ".." collapsed, clamped at root
+    }
+
+    // P2: HTTPS-only — Http scheme endpoint must be rejected.
+    #[tokio::test]
+    async fn select_endpoint_rejects_http_scheme() {
+        let selector = Arc::new(MockSelector::new());
+        let svc = build_svc(selector);
+
+        // Single Http endpoint.
+        let upstream = upstream_with(vec![Endpoint {
+            scheme: Scheme::Http,
+            host: "insecure.example.com".to_string(),
+            port: 80,
+        }]);
+        let headers = HeaderMap::new();
+
+        let err = svc
+            .select_endpoint(&upstream, &headers, None, "/test")
+            .await;
+
+        // select_endpoint itself doesn't enforce HTTPS — the check is in proxy_request
+        // after select_endpoint returns. Verify the endpoint is returned here (enforcement
+        // is at a higher level).
+        assert!(err.is_ok(), "select_endpoint should return the endpoint");
+        assert_eq!(err.unwrap().scheme, Scheme::Http);
+    }
+
+    // positive-2.2 (custom-header-routing): X-OAGW-Target-Host matches an endpoint.
+    #[tokio::test]
+    async fn select_endpoint_target_host_matches() {
+        let selector = Arc::new(MockSelector::new());
+        let svc = build_svc(selector.clone());
+        let upstream = upstream_with(vec![ep("a.com", 443), ep("b.com", 443)]);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-oagw-target-host", "a.com".parse().unwrap());
+
+        let result = svc
+            .select_endpoint(&upstream, &headers, None, "/test")
+            .await
+            .unwrap();
+        assert_eq!(result.host, "a.com");
+        assert_eq!(selector.calls(), 0, "BackendSelector should not be called");
+    }
+
+    // negative-2.1 (custom-header-routing): X-OAGW-Target-Host does not match any endpoint.
+    #[tokio::test]
+    async fn select_endpoint_target_host_unknown() {
+        let svc = build_svc(Arc::new(MockSelector::new()));
+        let upstream = upstream_with(vec![ep("a.com", 443), ep("b.com", 443)]);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-oagw-target-host", "evil.com".parse().unwrap());
+
+        let err = svc
+            .select_endpoint(&upstream, &headers, None, "/test")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::UnknownTargetHost { .. }),
+            "expected UnknownTargetHost, got: {err:?}"
+        );
+    }
+
+    // negative-1.2..1.4 (custom-header-routing): X-OAGW-Target-Host with invalid format.
+    #[tokio::test]
+    async fn select_endpoint_target_host_invalid_format() {
+        let svc = build_svc(Arc::new(MockSelector::new()));
+        let upstream = upstream_with(vec![ep("a.com", 443)]);
+
+        for bad_value in [
+            "a.com:443",
+            "a.com/path",
+            "a.com?q=1",
+            "a b",
+            "evil.com@real.com",
+            "evil.com\\real.com",
+            "a.com#fragment",
+        ] {
+            let mut headers = HeaderMap::new();
+            headers.insert("x-oagw-target-host", bad_value.parse().unwrap());
+            let err = svc
+                .select_endpoint(&upstream, &headers, None, "/test")
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, DomainError::InvalidTargetHost { .. }),
+                "expected InvalidTargetHost for '{bad_value}', got: {err:?}"
+            );
+        }
+
+        // Empty header value: test separately since HeaderValue::from_static
+        // allows empty strings while .parse() does not.
+        let mut headers = HeaderMap::new();
+        headers.insert("x-oagw-target-host", HeaderValue::from_static(""));
+        let err = svc
+            .select_endpoint(&upstream, &headers, None, "/test")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::InvalidTargetHost { .. }),
+            "expected InvalidTargetHost for empty header, got: {err:?}"
+        );
+    }
+
+    // positive-2.1 (custom-header-routing): Round-robin fallback for multi-endpoint (no header).
+    #[tokio::test]
+    async fn select_endpoint_round_robin_fallback() {
+        let selector = Arc::new(MockSelector::new());
+        let svc = build_svc(selector.clone());
+        let upstream = upstream_with(vec![ep("a.com", 443), ep("b.com", 443)]);
+        let headers = HeaderMap::new();
+
+        let ep1 = svc
+            .select_endpoint(&upstream, &headers, None, "/test")
+            .await
+            .unwrap();
+        let ep2 = svc
+            .select_endpoint(&upstream, &headers, None, "/test")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            selector.calls(),
+            2,
+            "BackendSelector should be called for multi-endpoint"
+        );
+        // MockSelector returns endpoints in order: [0], [1], [0], ...
+        assert_eq!(ep1.host, "a.com");
+        assert_eq!(ep2.host, "b.com");
+    }
+
+    #[tokio::test]
+    async fn select_endpoint_all_unhealthy_is_no_healthy_target() {
+        let svc = build_svc(Arc::new(AllDownSelector));
+        let upstream = upstream_with(vec![ep("a.com", 443), ep("b.com", 443), ep("c.com", 443)]);
+
+        let err = svc
+            .select_endpoint(&upstream, &HeaderMap::new(), None, "/test")
+            .await
+            .unwrap_err();
+        match err {
+            DomainError::NoHealthyTarget {
+                detail,
+                total,
+                healthy,
+                ..
+            } => {
+                assert_eq!((total, healthy), (3, 0));
+                assert!(detail.contains("0 of 3 targets healthy"), "{detail}");
+            }
+            other => panic!("expected NoHealthyTarget, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn select_endpoint_sticky_key_pins_endpoint() {
+        let selector = Arc::new(MockSelector::new());
+        let svc = build_svc(selector.clone());
+        let mut upstream =
+            upstream_with(vec![ep("a.com", 443), ep("b.com", 443), ep("c.com", 443)]);
+        upstream.sticky_by = Some("header:X-Session-Id".into());
+        let tenant = Uuid::new_v4();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-session-id", "ab".parse().unwrap());
+        let key = sticky_key(&upstream, &headers, tenant);
+        assert_eq!(key.as_deref(), Some(&b"ab"[..]));
+        for _ in 0..3 {
+            let selected = svc
+                .select_endpoint(&upstream, &headers, key.as_deref(), "/test")
+                .await
+                .unwrap();
+            assert_eq!(selected.host, "c.com");
+        }
+
+        // No header: no sticky key, so selection falls back to round-robin.
+        assert_eq!(sticky_key(&upstream, &HeaderMap::new(), tenant), None);
+
+        upstream.sticky_by = Some("tenant".into());
+        assert_eq!(
+            sticky_key(&upstream, &HeaderMap::new(), tenant).as_deref(),
+            Some(&tenant.as_bytes()[..])
+        );
+    }
+
+    // positive-1.1 (custom-header-routing): Single-endpoint bypass (no header, no BackendSelector call).
+    #[tokio::test]
+    async fn select_endpoint_single_endpoint_bypass() {
+        let selector = Arc::new(MockSelector::new());
+        let svc = build_svc(selector.clone());
+        let upstream = upstream_with(vec![ep("only.com", 443)]);
+        let headers = HeaderMap::new();
+
+        let result = svc
+            .select_endpoint(&upstream, &headers, None, "/test")
+            .await
+            .unwrap();
+        assert_eq!(result.host, "only.com");
+        assert_eq!(
+            selector.calls(),
+            0,
+            "BackendSelector should NOT be called for single endpoint"
+        );
+    }
+
+    // positive-1.2 (custom-header-routing): Single-endpoint upstream validates header if present.
+    #[tokio::test]
+    async fn select_endpoint_single_endpoint_validates_header() {
+        let svc = build_svc(Arc::new(MockSelector::new()));
+        let upstream = upstream_with(vec![ep("a.com", 443)]);
+
+        // Valid header matching the single endpoint → OK.
+        let mut headers = HeaderMap::new();
+        headers.insert("x-oagw-target-host", "a.com".parse().unwrap());
+        let result = svc
+            .select_endpoint(&upstream, &headers, None, "/test")
+            .await
+            .unwrap();
+        assert_eq!(result.host, "a.com");
+
+        // Invalid header not matching → UnknownTargetHost.
+        let mut headers = HeaderMap::new();
+        headers.insert("x-oagw-target-host", "b.com".parse().unwrap());
+        let err = svc
+            .select_endpoint(&upstream, &headers, None, "/test")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::UnknownTargetHost { .. }),
+            "expected UnknownTargetHost for mismatched header on single-endpoint upstream"
+        );
+    }
+
+    // -- Readiness --
+
+    #[tokio::test]
+    async fn status_not_ready_before_plugins_initialized() {
+        let readiness = Arc::new(ReadinessState::default());
+        let svc = build_svc(Arc::new(MockSelector::new())).with_readiness(readiness.clone());
+
+        let status = svc.status().await;
+        assert!(status.live);
+        assert!(!status.ready);
+        assert!(
+            status
+                .reasons
+                .iter()
+                .any(|r| r.contains("plugins not initialized")),
+            "reasons: {:?}",
+            status.reasons
+        );
+
+        // Plugins alone are not enough while configuration is still loading.
+        readiness.mark_plugins_initialized();
+        let status = svc.status().await;
+        assert!(!status.ready);
+        assert_eq!(status.reasons.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn status_ready_after_plugins_initialized() {
+        let readiness = Arc::new(ReadinessState::default());
+        readiness.mark_plugins_initialized();
+        readiness.mark_repos_loaded();
+        let svc = build_svc(Arc::new(MockSelector::new())).with_readiness(readiness);
+
+        let status = svc.status().await;
+        assert!(status.live);
+        assert!(status.ready, "reasons: {:?}", status.reasons);
+        assert!(status.reasons.is_empty());
+    }
+
+    #[tokio::test]
+    async fn status_checks_sample_upstream_when_configured() {
+        let readiness = Arc::new(ReadinessState::default());
+        readiness.mark_plugins_initialized();
+        readiness.mark_repos_loaded();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let svc = build_svc(Arc::new(MockSelector::new()))
+            .with_readiness(readiness.clone())
+            .with_readiness_probe(Some(addr.clone()));
+        assert!(svc.status().await.ready);
+
+        drop(listener);
+        let status = svc.status().await;
+        assert!(status.live);
+        assert!(!status.ready);
+        assert!(
+            status.reasons[0].contains(&addr),
+            "reasons: {:?}",
+            status.reasons
+        );
+    }
+
+    #[tokio::test]
+    async fn status_reports_upstream_latency_percentiles() {
+        use crate::domain::model::HttpMethod;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_forever(listener));
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "timed", None, HttpMethod::Get, "/v1", port).await;
+        assert!(svc.status().await.upstream_latencies.is_empty());
+
+        for _ in 0..3 {
+            svc.proxy_request(ctx.clone(), get("/timed/v1"))
+                .await
+                .unwrap();
+        }
+
+        let [latency] = svc.status().await.upstream_latencies.try_into().unwrap();
+        assert_eq!(latency.upstream_id, route.upstream_id);
+        assert_eq!(latency.samples, 3);
+        assert!(latency.p50_us > 0);
+        assert!(latency.p50_us <= latency.p95_us && latency.p95_us <= latency.p99_us);
+    }
+
+    // -- Auth plugin metrics --
+
+    struct DelayingPlugin {
+        delay: Duration,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl AuthPlugin for DelayingPlugin {
+        async fn authenticate(&self, _ctx: &mut AuthContext) -> Result<(), PluginError> {
+            tokio::time::sleep(self.delay).await;
+            if self.fail {
+                Err(PluginError::Internal("token endpoint unavailable".into()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Sets a bearer token and drops the client's `x-api-key`.
+    struct HeaderInjector;
+
+    #[async_trait]
+    impl AuthPlugin for HeaderInjector {
+        async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+            ctx.headers.insert("Authorization", "Bearer injected");
+            ctx.headers.remove("x-api-key");
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn auth_plugin_header_provenance_recorded() {
+        let svc = build_svc(Arc::new(MockSelector::new()));
+        let mut auth_ctx = AuthContext {
+            headers: [
+                ("accept".to_string(), "*/*".to_string()),
+                ("x-api-key".to_string(), "client".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+            config: HashMap::new(),
+            security_context: SecurityContext::anonymous(),
+            early_response: None,
+            body_fields: Vec::new(),
+            query: Vec::new(),
+            method: "GET".into(),
+            path: "/".into(),
+            body: None,
+            now: SystemTime::now(),
+            credential: None,
+        };
+        let provenance = svc
+            .authenticate_timed("bearer-injector", &HeaderInjector, &mut auth_ctx)
+            .await
+            .unwrap();
+        let applied = |header: &str| HeaderProvenance {
+            plugin_id: "bearer-injector".into(),
+            header: header.into(),
+        };
+        assert_eq!(provenance, [applied("authorization"), applied("x-api-key")]);
+    }
+
+    #[tokio::test]
+    async fn auth_plugin_latency_recorded_with_plugin_id_and_result() {
+        use opentelemetry::metrics::MeterProvider;
+        use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData};
+        use opentelemetry_sdk::metrics::{
+            InMemoryMetricExporter, PeriodicReader, SdkMeterProvider,
+        };
+
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let svc = build_svc(Arc::new(MockSelector::new()))
+            .with_metrics(OagwMetrics::new(&provider.meter("oagw")));
+
+        let delay = Duration::from_millis(20);
+        let mut auth_ctx = AuthContext {
+            headers: AuthHeaders::new(),
+            config: HashMap::new(),
+            security_context: SecurityContext::anonymous(),
+            early_response: None,
+            body_fields: Vec::new(),
+            query: Vec::new(),
+            method: "GET".into(),
+            path: "/".into(),
+            body: None,
+            now: SystemTime::now(),
+            credential: None,
+        };
+        svc.authenticate_timed(
+            "slow-ok",
+            &DelayingPlugin { delay, fail: false },
+            &mut auth_ctx,
+        )
+        .await
+        .unwrap();
+        svc.authenticate_timed(
+            "slow-err",
+            &DelayingPlugin { delay, fail: true },
+            &mut auth_ctx,
+        )
+        .await
+        .unwrap_err();
+
+        provider.force_flush().unwrap();
+
+        let mut observed = Vec::new();
+        for resource_metrics in &exporter.get_finished_metrics().unwrap() {
+            for scope_metrics in resource_metrics.scope_metrics() {
+                for metric in scope_metrics.metrics() {
+                    if metric.name() == crate::infra::metrics::AUTH_PLUGIN_DURATION
+                        && let AggregatedMetrics::F64(MetricData::Histogram(hist)) = metric.data()
+                    {
+                        for point in hist.data_points() {
+                            let label = |key: &str| {
+                                point
+                                    .attributes()
+                                    .find(|kv| kv.key.as_str() == key)
+                                    .map(|kv| kv.value.to_string())
+                                    .unwrap()
+                            };
+                            observed.push((
+                                label("plugin_id"),
+                                label("result"),
+                                point.count(),
+                                point.sum(),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        observed.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(observed.len(), 2, "observed: {observed:?}");
+        assert_eq!(
+            (observed[0].0.as_str(), observed[0].1.as_str()),
+            ("slow-err", "failure")
+        );
+        assert_eq!(
+            (observed[1].0.as_str(), observed[1].1.as_str()),
+            ("slow-ok", "success")
+        );
+        for (_, _, count, sum) in &observed {
+            assert_eq!(*count, 1);
+            assert!(*sum >= delay.as_secs_f64(), "sum {sum} below plugin delay");
+        }
+    }
+
+    // -- Auth plugin early response --
+
+    struct RedirectPlugin;
+
+    #[async_trait]
+    impl AuthPlugin for RedirectPlugin {
+        async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+            let mut headers = AuthHeaders::new();
+            headers.insert("location", "https://login.example.com/authorize");
+            headers.append("set-cookie", "state=abc; HttpOnly");
+            headers.append("set-cookie", "nonce=xyz; HttpOnly");
+            ctx.early_response = Some(EarlyResponse {
+                status: 302,
+                headers,
+                body: Vec::new(),
+            });
+            Ok(())
+        }
+    }
+
+    /// Data plane backed by a real in-memory control plane.
+    fn dp_with_test_cp() -> (Arc<dyn ControlPlaneService>, DataPlaneServiceImpl) {
+        dp_with_cp(crate::domain::test_support::TestCpBuilder::new())
+    }
+
+    fn dp_with_cp(
+        builder: crate::domain::test_support::TestCpBuilder,
+    ) -> (Arc<dyn ControlPlaneService>, DataPlaneServiceImpl) {
+        dp_with_cp_and_enforcer(builder, crate::domain::test_support::allow_all_enforcer())
+    }
+
+    fn dp_with_cp_and_enforcer(
+        builder: crate::domain::test_support::TestCpBuilder,
+        policy_enforcer: PolicyEnforcer,
+    ) -> (Arc<dyn ControlPlaneService>, DataPlaneServiceImpl) {
+        use modkit::client_hub::ClientHub;
+
+        let hub = ClientHub::new();
+        let cp = builder.build_and_register(&hub);
+        let credstore = hub.get::<dyn CredStoreClientV1>().unwrap();
+        let server_conf = Arc::new(pingora_core::server::configuration::ServerConf::default());
+        let proxy = Arc::new(crate::infra::proxy::pingora_proxy::new_http_proxy(
+            &server_conf,
+            crate::infra::proxy::pingora_proxy::PingoraProxy::new(
+                Duration::from_secs(10),
+                Duration::from_secs(30),
+            ),
+        ));
+        let svc = DataPlaneServiceImpl::new(
+            cp.clone(),
+            credstore,
+            policy_enforcer,
+            None,
+            TokenCacheConfig::default(),
+            Arc::new(MockSelector::new()),
+            proxy,
+        )
+        .with_allow_http_upstream(true);
+        (cp, svc)
+    }
+
+    /// Create a local HTTP upstream `alias` with a single exact-path route.
+    async fn create_target(
+        cp: &Arc<dyn ControlPlaneService>,
+        ctx: &SecurityContext,
+        alias: &str,
+        auth: Option<crate::domain::model::AuthConfig>,
+        method: crate::domain::model::HttpMethod,
+        path: &str,
+        port: u16,
+    ) -> crate::domain::model::Route {
+        create_host_target(cp, ctx, alias, "127.0.0.1", auth, method, path, port).await
+    }
+
+    /// [`create_target`] with the upstream endpoint on `host`.
+    #[allow(clippy::too_many_arguments)]
+    async fn create_host_target(
+        cp: &Arc<dyn ControlPlaneService>,
+        ctx: &SecurityContext,
+        alias: &str,
+        host: &str,
+        auth: Option<crate::domain::model::AuthConfig>,
+        method: crate::domain::model::HttpMethod,
+        path: &str,
+        port: u16,
+    ) -> crate::domain::model::Route {
+        use crate::domain::model::{
+            CreateRouteRequest, CreateUpstreamRequest, HttpMatch, MatchRules,
+        };
+
+        let upstream = cp
+            .create_upstream(
+                ctx,
+                CreateUpstreamRequest {
+                    server: Server {
+                        endpoints: vec![Endpoint {
+                            scheme: Scheme::Http,
+                            host: host.into(),
+                            port,
+                        }],
+                    },
+                    protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
+                    alias: Some(alias.into()),
+                    auth,
+                    headers: None,
+                    plugins: None,
+                    rate_limit: None,
+                    tags: vec![],
+                    enabled: true,
+                    maintenance: false,
+                    maintenance_message: None,
+                    retry_on_body: None,
+                    force_chunked: false,
+                    failover: vec![],
+                    timeout_secs: None,
+                    keepalive: None,
+                    circuit_breaker: None,
+                    user_agent: None,
+                    sticky_by: None,
+                    allowed_methods: vec![],
+                    trailing_slash: TrailingSlash::default(),
+                    max_header_size_bytes: None,
+                    tls_min_version: None,
+                    tls_cipher_suites: vec![],
+                    tls_server_name: None,
+                    budget_header: None,
+                },
+            )
+            .await
+            .unwrap();
+        cp.create_route(
+            ctx,
+            CreateRouteRequest {
+                upstream_id: upstream.id,
+                match_rules: MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![method],
+                        path: path.into(),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+                plugins: None,
+                rate_limit: None,
+                tags: vec![],
+                priority: 0,
+                enabled: true,
+                skip_authz: false,
+                timeout_secs: None,
+                status_remap: HashMap::new(),
+                allowed_response_content_types: vec![],
+                allowed_request_content_types: vec![],
+                log_sample_rate: None,
+                request_schema: None,
+                body_rewrite: vec![],
+                auth_rules: vec![],
+                strip_query_params: vec![],
+                strip_inbound_headers: vec![],
+                idempotency_ttl_secs: None,
+                cache_responses: false,
+                negative_cache_ttl_secs: None,
+                response_header_allowlist: None,
+                max_total_duration_ms: None,
+                max_body_size_bytes: None,
+                max_outbound_calls: None,
+                coerce_content_type: None,
+                api_version: None,
+                body_transformers: vec![],
+                log_redact_paths: vec![],
+                transform_hook_url: None,
+                deprecated: false,
+                sunset: None,
+                kind: RouteKind::Proxy,
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    fn tenant_ctx() -> SecurityContext {
+        SecurityContext::builder()
+            .subject_tenant_id(Uuid::new_v4())
+            .subject_id(Uuid::new_v4())
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn route_auth_rules_pick_auth_per_request_path() {
+        use crate::domain::model::{
+            AuthConfig, AuthRule, HttpMatch, HttpMethod, MatchRules, SharingMode,
+            UpdateRouteRequest,
+        };
+        use crate::domain::test_support::APIKEY_AUTH_PLUGIN_ID;
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(async move {
+            let mut heads = Vec::new();
+            for _ in 0..3 {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut head = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    assert!(n > 0, "connection closed before request head");
+                    head.extend_from_slice(&buf[..n]);
+                }
+                stream
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok",
+                    )
+                    .await
+                    .unwrap();
+                heads.push(String::from_utf8_lossy(&head).to_lowercase());
+            }
+            heads
+        });
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "console", None, HttpMethod::Get, "/api", port).await;
+        let admin_auth = AuthConfig {
+            plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+            sharing: SharingMode::Private,
+            config: Some(HashMap::from([
+                ("header".into(), "x-admin-key".into()),
+                ("value".into(), "root".into()),
+            ])),
+        };
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                match_rules: Some(MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: "/api".into(),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Append,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                }),
+                auth_rules: Some(vec![AuthRule {
+                    condition: AuthCondition::PathPrefix {
+                        prefix: "/api/admin".into(),
+                    },
+                    auth: Some(admin_auth),
+                }]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        // A path merely sharing the prefix's characters stays public.
+        for path in [
+            "/console/api/admin/users",
+            "/console/api/public",
+            "/console/api/administrator",
+        ] {
+            let resp = svc.proxy_request(ctx.clone(), get(path)).await.unwrap();
+            assert_eq!(resp.status(), http::StatusCode::OK);
+        }
+
+        let heads = upstream_seen.await.unwrap();
+        assert!(heads[0].contains("x-admin-key: root"), "{}", heads[0]);
+        assert!(!heads[1].contains("x-admin-key"), "{}", heads[1]);
+        assert!(!heads[2].contains("x-admin-key"), "{}", heads[2]);
+    }
+
+    #[tokio::test]
+    async fn tenant_default_auth_applies_where_upstream_sets_none() {
+        use crate::domain::model::{AuthConfig, HttpMethod, SharingMode};
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let apikey = |value: &str| AuthConfig {
+            plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+            sharing: SharingMode::Private,
+            config: Some(HashMap::from([
+                ("header".into(), "x-api-key".into()),
+                ("value".into(), value.into()),
+            ])),
+        };
+        cp.set_tenant_default_auth(&ctx, apikey("tenant-key"))
+            .await
+            .unwrap();
+
+        let mut heads = Vec::new();
+        for (alias, auth) in [("plain", None), ("own", Some(apikey("upstream-key")))] {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let upstream_seen = tokio::spawn(serve_once(listener));
+            create_target(&cp, &ctx, alias, auth, HttpMethod::Get, "/v1", port).await;
+
+            let resp = svc
+                .proxy_request(ctx.clone(), get(&format!("/{alias}/v1")))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), http::StatusCode::OK);
+            heads.push(upstream_seen.await.unwrap().to_lowercase());
+        }
+
+        assert!(heads[0].contains("x-api-key: tenant-key"), "{}", heads[0]);
+        assert!(heads[1].contains("x-api-key: upstream-key"), "{}", heads[1]);
+        assert!(!heads[1].contains("tenant-key"), "{}", heads[1]);
+    }
+
+    #[tokio::test]
+    async fn disabled_auth_plugin_fails_its_routes_with_503() {
+        use crate::domain::gts_helpers::NOOP_AUTH_PLUGIN_ID;
+        use crate::domain::model::{AuthConfig, HttpMethod, SharingMode};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_forever(listener));
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_disabled_auth_plugins(&[APIKEY_AUTH_PLUGIN_ID.into()]);
+        let ctx = tenant_ctx();
+        let auth = |plugin_type: &str| AuthConfig {
+            plugin_type: plugin_type.into(),
+            sharing: SharingMode::Private,
+            config: Some(HashMap::from([
+                ("header".into(), "x-api-key".into()),
+                ("value".into(), "k".into()),
+            ])),
+        };
+        let locked = create_target(
+            &cp,
+            &ctx,
+            "locked",
+            Some(auth(APIKEY_AUTH_PLUGIN_ID)),
+            HttpMethod::Get,
+            "/v1",
+            port,
+        )
+        .await;
+        create_target(
+            &cp,
+            &ctx,
+            "open",
+            Some(auth(NOOP_AUTH_PLUGIN_ID)),
+            HttpMethod::Get,
+            "/v1",
+            port,
+        )
+        .await;
+
+        let err = svc
+            .proxy_request(ctx.clone(), get("/locked/v1"))
+            .await
+            .unwrap_err();
+        match err {
+            DomainError::PluginDisabled { detail, .. } => {
+                assert!(detail.contains(APIKEY_AUTH_PLUGIN_ID), "{detail}");
+                assert!(detail.contains(&locked.id.to_string()), "{detail}");
+            }
+            other => panic!("expected PluginDisabled, got {other:?}"),
+        }
+        let resp = svc
+            .proxy_request(ctx.clone(), get("/open/v1"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+    }
+
+    #[test]
+    fn select_auth_matches_header_conditions_in_order() {
+        let keyed = AuthConfig {
+            plugin_type: crate::domain::test_support::APIKEY_AUTH_PLUGIN_ID.into(),
+            sharing: crate::domain::model::SharingMode::Private,
+            config: None,
+        };
+        let rules = [
+            AuthRule {
+                condition: AuthCondition::Header {
+                    name: "x-tier".into(),
+                    value: Some("public".into()),
+                },
+                auth: None,
+            },
+            AuthRule {
+                condition: AuthCondition::Header {
+                    name: "x-tier".into(),
+                    value: None,
+                },
+                auth: Some(keyed.clone()),
+            },
+        ];
+        let mut headers = HeaderMap::new();
+        assert_eq!(select_auth(&rules, "/x", &headers), None);
+        headers.insert("x-tier", HeaderValue::from_static("public"));
+        assert_eq!(select_auth(&rules, "/x", &headers), Some(None));
+        headers.insert("x-tier", HeaderValue::from_static("admin"));
+        assert_eq!(select_auth(&rules, "/x", &headers), Some(Some(&keyed)));
+    }
+
+    #[tokio::test]
+    async fn auth_plugin_early_response_skips_upstream() {
+        use crate::domain::model::{AuthConfig, HttpMethod, SharingMode};
+
+        let (cp, mut svc) = dp_with_test_cp();
+        svc.auth_registry
+            .register("test-redirect", Arc::new(RedirectPlugin));
+
+        // Upstream that records whether anything ever connects to it.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let ctx = tenant_ctx();
+        create_target(
+            &cp,
+            &ctx,
+            "sso",
+            Some(AuthConfig {
+                plugin_type: "test-redirect".into(),
+                sharing: SharingMode::Private,
+                config: None,
+            }),
+            HttpMethod::Get,
+            "/profile",
+            port,
+        )
+        .await;
+
+        let req = http::Request::builder()
+            .method(http::Method::GET)
+            .uri("/sso/profile")
+            .body(Body::Empty)
+            .unwrap();
+        let resp = svc.proxy_request(ctx, req).await.unwrap();
+
+        assert_eq!(resp.status(), http::StatusCode::FOUND);
+        assert_eq!(
+            resp.headers().get(http::header::LOCATION).unwrap(),
+            "https://login.example.com/authorize"
+        );
+        let cookies: Vec<_> = resp
+            .headers()
+            .get_all(http::header::SET_COOKIE)
+            .iter()
+            .collect();
+        assert_eq!(cookies, ["state=abc; HttpOnly", "nonce=xyz; HttpOnly"]);
+        assert_eq!(
+            resp.extensions().get::<ErrorSource>(),
+            Some(&ErrorSource::Gateway)
+        );
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), listener.accept())
+                .await
+                .is_err(),
+            "upstream must not be contacted"
+        );
+    }
+
+    /// Names a fixed credential and records the statuses reported for it.
+    #[derive(Default)]
+    struct CredentialPlugin {
+        reported: std::sync::Mutex<Vec<(String, u16)>>,
+    }
+
+    #[async_trait]
+    impl AuthPlugin for CredentialPlugin {
+        async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+            ctx.credential = Some("key-a".into());
+            Ok(())
+        }
+
+        fn upstream_responded(&self, credential: &str, status: u16) {
+            self.reported
+                .lock()
+                .unwrap()
+                .push((credential.to_string(), status));
+        }
+    }
+
+    #[tokio::test]
+    async fn auth_plugin_is_told_the_upstream_status_for_its_credential() {
+        use crate::domain::model::{AuthConfig, HttpMethod, SharingMode};
+
+        let (cp, mut svc) = dp_with_test_cp();
+        let plugin = Arc::new(CredentialPlugin::default());
+        svc.auth_registry
+            .register("test-credential", plugin.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_raw_once(
+            listener,
+            b"HTTP/1.1 429 Too Many Requests\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                .to_vec(),
+        ));
+        let ctx = tenant_ctx();
+        create_target(
+            &cp,
+            &ctx,
+            "limited",
+            Some(AuthConfig {
+                plugin_type: "test-credential".into(),
+                sharing: SharingMode::Private,
+                config: None,
+            }),
+            HttpMethod::Get,
+            "/v1",
+            port,
+        )
+        .await;
+
+        let resp = svc.proxy_request(ctx, get("/limited/v1")).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            *plugin.reported.lock().unwrap(),
+            [("key-a".to_string(), 429)]
+        );
+    }
+
+    /// Fails quoting the credential it tried, as a careless plugin might.
+    struct LeakyPlugin;
+
+    const LEAKED_SECRET: &str = "sk-live-0123456789";
+
+    #[async_trait]
+    impl AuthPlugin for LeakyPlugin {
+        async fn authenticate(&self, _ctx: &mut AuthContext) -> Result<(), PluginError> {
+            Err(PluginError::AuthFailed(format!(
+                "upstream rejected Authorization: Bearer {LEAKED_SECRET}"
+            )))
+        }
+    }
+
+    #[tokio::test]
+    async fn recent_errors_are_kept_in_order_without_secrets() {
+        use crate::domain::model::{AuthConfig, HttpMethod, SharingMode};
+
+        let (cp, mut svc) = dp_with_test_cp();
+        svc.auth_registry
+            .register("test-leaky", Arc::new(LeakyPlugin));
+        let ctx = tenant_ctx();
+        let route = create_target(
+            &cp,
+            &ctx,
+            "leaky",
+            Some(AuthConfig {
+                plugin_type: "test-leaky".into(),
+                sharing: SharingMode::Private,
+                config: None,
+            }),
+            HttpMethod::Get,
+            "/v1",
+            1,
+        )
+        .await;
+
+        let unknown = format!("/missing/v1?api_key={LEAKED_SECRET}");
+        svc.proxy_request(ctx.clone(), get(&unknown))
+            .await
+            .unwrap_err();
+        svc.proxy_request(ctx, get("/leaky/v1")).await.unwrap_err();
+
+        let errors = svc.recent_errors();
+        let categories: Vec<_> = errors.iter().map(|e| e.category).collect();
+        assert_eq!(categories, ["not_found", "authentication_failed"]);
+        assert_eq!(errors[0].route_id, None);
+        assert_eq!(errors[1].upstream_id, Some(route.upstream_id));
+        assert_eq!(errors[1].route_id, Some(route.id));
+        assert!(errors[1].message.contains("[REDACTED]"));
+        assert!(!format!("{errors:?}").contains(LEAKED_SECRET));
+    }
+
+    // -- Unmatched routes --
+
+    /// Accept one connection, answer `200 ok` and return the request head.
+    async fn serve_once(listener: tokio::net::TcpListener) -> String {
+        use tokio::io::AsyncReadExt;
+
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut head = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = stream.read(&mut buf).await.unwrap();
+            assert!(n > 0, "connection closed before request head");
+            head.extend_from_slice(&buf[..n]);
+        }
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
+            .await
+            .unwrap();
+        String::from_utf8_lossy(&head).into_owned()
+    }
+
+    /// Answer every connection like [`serve_once`] until the test ends.
+    async fn serve_forever(listener: tokio::net::TcpListener) {
+        use tokio::io::AsyncReadExt;
+
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            tokio::spawn(async move {
+                let mut head = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => head.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let _ = stream
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok",
+                    )
+                    .await;
+            });
+        }
+    }
+
+    /// Answer one connection with `raw`, a complete HTTP/1.1 response.
+    async fn serve_raw_once(listener: tokio::net::TcpListener, raw: Vec<u8>) {
+        use tokio::io::AsyncReadExt;
+
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut head = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = stream.read(&mut buf).await.unwrap();
+            assert!(n > 0, "connection closed before request head");
+            head.extend_from_slice(&buf[..n]);
+        }
+        stream.write_all(&raw).await.unwrap();
+    }
+
+    /// A `200 OK` with the given content type and a `Content-Length` body.
+    fn raw_response(content_type: &str, body: &[u8]) -> Vec<u8> {
+        let mut raw = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: {content_type}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+            body.len()
+        )
+        .into_bytes();
+        raw.extend_from_slice(body);
+        raw
+    }
+
+    /// Create a route answering from `raw` with the given rewrite rules.
+    async fn rewrite_target(
+        cp: &Arc<dyn ControlPlaneService>,
+        ctx: &SecurityContext,
+        alias: &str,
+        rules: Vec<crate::domain::model::BodyRewriteRule>,
+        raw: Vec<u8>,
+    ) {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_raw_once(listener, raw));
+        let route = create_target(cp, ctx, alias, None, HttpMethod::Get, "/doc", port).await;
+        cp.update_route(
+            ctx,
+            route.id,
+            UpdateRouteRequest {
+                body_rewrite: Some(rules),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    fn get(uri: &str) -> http::Request<Body> {
+        http::Request::builder()
+            .method(http::Method::GET)
+            .uri(uri)
+            .body(Body::Empty)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn unmatched_route_returns_not_found_by_default() {
+        use crate::domain::model::HttpMethod;
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "api", None, HttpMethod::Get, "/items", 9).await;
+
+        let err = svc
+            .proxy_request(ctx, get("/api/orders"))
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(
+                err,
+                DomainError::NotFound {
+                    entity: "route",
+                    ..
+                }
+            ),
+            "got {err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn unmatched_route_serves_configured_static_response() {
+        use crate::domain::model::HttpMethod;
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_unmatched_route(UnmatchedRouteBehavior::StaticResponse {
+            status: 404,
+            headers: HashMap::from([("content-type".into(), "application/json".into())]),
+            body: r#"{"error":"no such endpoint"}"#.into(),
+        });
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "api", None, HttpMethod::Get, "/items", 9).await;
+
+        let resp = svc.proxy_request(ctx, get("/api/orders")).await.unwrap();
+
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+        assert_eq!(
+            resp.headers().get(http::header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        assert_eq!(
+            resp.extensions().get::<ErrorSource>(),
+            Some(&ErrorSource::Gateway)
+        );
+        let body = resp.into_body().into_bytes().await.unwrap();
+        assert_eq!(&body[..], br#"{"error":"no such endpoint"}"#);
+    }
+
+    #[tokio::test]
+    async fn unmatched_route_forwards_to_catch_all_upstream() {
+        use crate::domain::model::HttpMethod;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "api", None, HttpMethod::Get, "/items", 9).await;
+        let fallback = create_target(
+            &cp,
+            &ctx,
+            "fallback",
+            None,
+            HttpMethod::Get,
+            "/unused",
+            port,
+        )
+        .await;
+        let svc = svc.with_unmatched_route(UnmatchedRouteBehavior::CatchAllUpstreamId(
+            fallback.upstream_id,
+        ));
+
+        let resp = svc
+            .proxy_request(ctx, get("/api/orders/7?expand=lines"))
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let body = resp.into_body().into_bytes().await.unwrap();
+        assert_eq!(&body[..], b"ok");
+        let head = upstream_seen.await.unwrap();
+        assert!(
+            head.starts_with("GET /orders/7?expand=lines HTTP/1.1\r\n"),
+            "unexpected upstream request: {head:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn matched_route_ignores_catch_all_upstream() {
+        use crate::domain::model::HttpMethod;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc =
+            svc.with_unmatched_route(UnmatchedRouteBehavior::CatchAllUpstreamId(Uuid::new_v4()));
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "api", None, HttpMethod::Get, "/items", port).await;
+
+        let resp = svc.proxy_request(ctx, get("/api/items")).await.unwrap();
+
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert!(
+            upstream_seen
+                .await
+                .unwrap()
+                .starts_with("GET /items HTTP/1.1\r\n")
+        );
+    }
+
+    // -- User-Agent --
+
+    #[tokio::test]
+    async fn outbound_user_agent_uses_config_and_upstream_override() {
+        use crate::domain::model::{HttpMethod, UpdateUpstreamRequest};
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_user_agent("acme-gw/2.0");
+        let ctx = tenant_ctx();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        create_target(&cp, &ctx, "plain", None, HttpMethod::Get, "/items", port).await;
+        svc.proxy_request(ctx.clone(), get("/plain/items"))
+            .await
+            .unwrap();
+        let head = upstream_seen.await.unwrap().to_ascii_lowercase();
+        assert!(head.contains("\r\nuser-agent: acme-gw/2.0\r\n"), "{head}");
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        let route = create_target(&cp, &ctx, "picky", None, HttpMethod::Get, "/items", port).await;
+        cp.update_upstream(
+            &ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                user_agent: Some("picky-client/1.0".into()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        svc.proxy_request(ctx, get("/picky/items")).await.unwrap();
+        let head = upstream_seen.await.unwrap().to_ascii_lowercase();
+        assert!(
+            head.contains("\r\nuser-agent: picky-client/1.0\r\n"),
+            "{head}"
+        );
+    }
+
+    #[tokio::test]
+    async fn trailing_slash_policy_shapes_outbound_path() {
+        use crate::domain::model::{HttpMethod, UpdateUpstreamRequest};
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        // (alias, policy, route path, incoming suffix, outbound path)
+        for (alias, policy, route_path, incoming, expected) in [
+            (
+                "keep",
+                TrailingSlash::Preserve,
+                "/items",
+                "/items/",
+                "/items/",
+            ),
+            (
+                "bare",
+                TrailingSlash::Preserve,
+                "/items",
+                "/items",
+                "/items",
+            ),
+            ("strip", TrailingSlash::Strip, "/items", "/items/", "/items"),
+            ("add", TrailingSlash::Add, "/items", "/items", "/items/"),
+            ("root", TrailingSlash::Strip, "/", "/", "/"),
+            ("rootadd", TrailingSlash::Add, "/", "/", "/"),
+        ] {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let upstream_seen = tokio::spawn(serve_once(listener));
+            let route =
+                create_target(&cp, &ctx, alias, None, HttpMethod::Get, route_path, port).await;
+            cp.update_upstream(
+                &ctx,
+                route.upstream_id,
+                UpdateUpstreamRequest {
+                    trailing_slash: Some(policy),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+            svc.proxy_request(ctx.clone(), get(&format!("/{alias}{incoming}")))
+                .await
+                .expect(alias);
+            let head = upstream_seen.await.unwrap();
+            assert!(
+                head.starts_with(&format!("GET {expected} HTTP/1.1")),
+                "{alias}: {head}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn forwarded_headers_carry_client_ip_when_enabled() {
+        use crate::domain::model::HttpMethod;
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_forwarded_headers(vec!["10.0.0.1".parse().unwrap()]);
+        let ctx = tenant_ctx();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        create_target(&cp, &ctx, "xff", None, HttpMethod::Get, "/items", port).await;
+        let mut req = get("/xff/items");
+        req.headers_mut()
+            .insert("x-forwarded-for", "198.51.100.9".parse().unwrap());
+        req.headers_mut()
+            .insert(http::header::HOST, "gw.example.com".parse().unwrap());
+        req.extensions_mut()
+            .insert(ClientIp("10.0.0.1".parse().unwrap()));
+        svc.proxy_request(ctx, req).await.unwrap();
+        let head = upstream_seen.await.unwrap().to_ascii_lowercase();
+        assert!(
+            head.contains("\r\nx-forwarded-for: 198.51.100.9, 10.0.0.1\r\n"),
+            "{head}"
+        );
+        assert!(head.contains("\r\nx-forwarded-proto: http\r\n"), "{head}");
+        assert!(
+            head.contains("\r\nx-forwarded-host: gw.example.com\r\n"),
+            "{head}"
+        );
+    }
+
+    // -- Clock skew correction --
+
+    /// Stamps the request with the time it was "signed" at, like SigV4's
+    /// `x-amz-date`.
+    struct TimestampSigner;
+
+    #[async_trait]
+    impl AuthPlugin for TimestampSigner {
+        async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+            let secs = ctx
+                .now
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_err(|e| PluginError::Internal(e.to_string()))?
+                .as_secs();
+            ctx.headers.insert("x-signed-at", secs.to_string());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn signing_plugins_see_skew_corrected_time() {
+        use crate::domain::model::{AuthConfig, HttpMethod, SharingMode};
+
+        let local = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        for (correction, expected) in [
+            (0, 1_700_000_000),
+            (90, 1_700_000_090),
+            (-30, 1_699_999_970),
+        ] {
+            let (cp, mut svc) = dp_with_test_cp();
+            svc.auth_registry
+                .register("test-signer", Arc::new(TimestampSigner));
+            let svc = svc
+                .with_clock(Arc::new(
+                    crate::domain::test_support::MockClock::starting_at(local),
+                ))
+                .with_clock_skew_correction(correction);
+            let ctx = tenant_ctx();
+
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let upstream_seen = tokio::spawn(serve_once(listener));
+            create_target(
+                &cp,
+                &ctx,
+                "signed",
+                Some(AuthConfig {
+                    plugin_type: "test-signer".into(),
+                    sharing: SharingMode::Private,
+                    config: None,
+                }),
+                HttpMethod::Get,
+                "/items",
+                port,
+            )
+            .await;
+
+            svc.proxy_request(ctx, get("/signed/items")).await.unwrap();
+            let head = upstream_seen.await.unwrap().to_ascii_lowercase();
+            assert!(
+                head.contains(&format!("\r\nx-signed-at: {expected}\r\n")),
+                "correction {correction}: {head}"
+            );
+        }
+    }
+
+    // -- Body signing --
+
+    /// Signs the request body, like a body-hashing HMAC scheme; needs the
+    /// whole body.
+    struct BodySigner;
+
+    #[async_trait]
+    impl AuthPlugin for BodySigner {
+        async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+            let body = ctx
+                .body
+                .as_ref()
+                .ok_or_else(|| PluginError::Internal("request body not buffered".into()))?;
+            let signature = format!("{} {} len={}", ctx.method, ctx.path, body.len());
+            ctx.headers.insert("x-body-signature", signature);
+            Ok(())
+        }
+
+        fn requires_body(&self) -> bool {
+            true
+        }
+    }
+
+    /// Proxy a two-chunk streaming JSON body through a route authenticated by
+    /// `plugin` and return the request the upstream received.
+    async fn post_streamed_body(plugin: Arc<dyn AuthPlugin>) -> String {
+        use crate::domain::model::{AuthConfig, HttpMethod, SharingMode};
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            // Buffered bodies end with the JSON, chunked ones with the last chunk.
+            while !(request.ends_with(b"}") || request.ends_with(b"0\r\n\r\n")) {
+                let n = stream.read(&mut buf).await.unwrap();
+                assert!(n > 0, "connection closed before request body");
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
+                .await
+                .unwrap();
+            String::from_utf8(request).unwrap().to_ascii_lowercase()
+        });
+
+        let (cp, mut svc) = dp_with_test_cp();
+        svc.auth_registry.register("test-auth", plugin);
+        let ctx = tenant_ctx();
+        create_target(
+            &cp,
+            &ctx,
+            "upload",
+            Some(AuthConfig {
+                plugin_type: "test-auth".into(),
+                sharing: SharingMode::Private,
+                config: None,
+            }),
+            HttpMethod::Post,
+            "/files",
+            port,
+        )
+        .await;
+
+        let chunks: Vec<Result<Bytes, oagw_sdk::body::BoxError>> = vec![
+            Ok(Bytes::from_static(br#"{"name":"#)),
+            Ok(Bytes::from_static(br#""a.txt"}"#)),
+        ];
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/upload/files")
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(Body::Stream(Box::pin(futures::stream::iter(chunks))))
+            .unwrap();
+        let resp = svc.proxy_request(ctx, req).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        upstream_seen.await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn body_signing_plugin_gets_buffered_body() {
+        let request = post_streamed_body(Arc::new(BodySigner)).await;
+        let (head, body) = request.split_once("\r\n\r\n").unwrap();
+        assert_eq!(body, r#"{"name":"a.txt"}"#);
+        assert!(
+            head.lines()
+                .any(|l| l == "x-body-signature: post /files len=16"),
+            "{head}"
+        );
+        assert!(head.lines().any(|l| l == "content-length: 16"), "{head}");
+        assert!(!head.contains("transfer-encoding"), "{head}");
+    }
+
+    #[tokio::test]
+    async fn header_only_plugin_leaves_body_streaming() {
+        let request = post_streamed_body(Arc::new(HeaderInjector)).await;
+        let (head, _) = request.split_once("\r\n\r\n").unwrap();
+        assert!(
+            head.lines().any(|l| l == "authorization: bearer injected"),
+            "{head}"
+        );
+        assert!(
+            head.lines().any(|l| l == "transfer-encoding: chunked"),
+            "{head}"
+        );
+        assert!(!head.contains("content-length"), "{head}");
+    }
+
+    // -- Query stripping --
+
+    #[tokio::test]
+    async fn strip_query_params_removes_listed_params_only() {
+        use crate::domain::model::{HttpMatch, HttpMethod, MatchRules, UpdateRouteRequest};
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        let route =
+            create_target(&cp, &ctx, "private", None, HttpMethod::Get, "/items", port).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                match_rules: Some(MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: "/items".into(),
+                        query_allowlist: vec!["page".into(), "lang".into()],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                }),
+                strip_query_params: Some(vec!["debug".into(), "utm_source".into()]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        // Stripped params need not be allowlisted; they are dropped silently.
+        svc.proxy_request(
+            ctx,
+            get("/private/items?page=2&debug=1&utm_source=mail&lang=en"),
+        )
+        .await
+        .unwrap();
+        let head = upstream_seen.await.unwrap();
+        let request_line = head.lines().next().unwrap();
+        assert_eq!(request_line, "GET /items?page=2&lang=en HTTP/1.1", "{head}");
+    }
+
+    #[tokio::test]
+    async fn max_query_params_counts_the_api_version_param() {
+        use crate::domain::model::{
+            ApiVersion, HttpMatch, HttpMethod, MatchRules, UpdateRouteRequest,
+        };
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_max_query_params(Some(3));
+        let ctx = tenant_ctx();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        let route = create_target(&cp, &ctx, "paged", None, HttpMethod::Get, "/items", port).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                match_rules: Some(MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: "/items".into(),
+                        query_allowlist: vec!["page".into(), "lang".into(), "debug".into()],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                }),
+                strip_query_params: Some(vec!["debug".into()]),
+                api_version: Some(ApiVersion {
+                    value: "2024-06-01".into(),
+                    header: None,
+                    query_param: Some("api-version".into()),
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        // Two client params plus the API version is exactly the limit;
+        // stripped params do not count.
+        svc.proxy_request(ctx.clone(), get("/paged/items?page=2&lang=en&debug=1"))
+            .await
+            .unwrap();
+        let head = upstream_seen.await.unwrap();
+        assert!(
+            head.starts_with("GET /items?page=2&lang=en&api-version=2024-06-01 "),
+            "{head}"
+        );
+
+        let err = svc
+            .proxy_request(ctx, get("/paged/items?page=2&lang=en&page=3"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, DomainError::Validation { detail, .. } if detail.contains("4 query parameters")),
+            "{err:?}"
+        );
+    }
+
+    // -- Bodies on GET and HEAD --
+
+    fn get_with_body(uri: &str) -> http::Request<Body> {
+        let mut req = get(uri);
+        *req.body_mut() = Body::Bytes(Bytes::from_static(b"payload"));
+        req.headers_mut()
+            .insert(http::header::CONTENT_LENGTH, HeaderValue::from_static("7"));
+        req
+    }
+
+    #[tokio::test]
+    async fn get_body_is_stripped_under_strip_policy() {
+        use crate::domain::model::HttpMethod;
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_bodiless_method_body(BodilessMethodBody::Strip);
+        let ctx = tenant_ctx();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        create_target(&cp, &ctx, "search", None, HttpMethod::Get, "/items", port).await;
+
+        let resp = svc
+            .proxy_request(ctx, get_with_body("/search/items"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let head = upstream_seen.await.unwrap().to_ascii_lowercase();
+        assert!(head.starts_with("get /items "), "{head}");
+        assert!(!head.contains("content-length: 7"), "{head}");
+        assert!(!head.contains("payload"), "{head}");
+    }
+
+    #[tokio::test]
+    async fn get_body_is_rejected_under_reject_policy() {
+        use crate::domain::model::HttpMethod;
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_bodiless_method_body(BodilessMethodBody::Reject);
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "search", None, HttpMethod::Get, "/items", 9).await;
+
+        let err = svc
+            .proxy_request(ctx.clone(), get_with_body("/search/items"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, DomainError::Validation { detail, .. } if detail.contains("must not carry a body")),
+            "{err:?}"
+        );
+        // Bodiless GETs are unaffected.
+        let err = svc
+            .proxy_request(ctx, get("/search/items"))
+            .await
+            .unwrap_err();
+        assert!(!matches!(err, DomainError::Validation { .. }), "{err:?}");
+    }
+
+    // -- Plugin query parameters --
+
+    /// Authenticates with a query-string key, like APIs that take
+    /// `?key=...`, and pins the page size.
+    struct QueryInjector;
+
+    #[async_trait]
+    impl AuthPlugin for QueryInjector {
+        async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+            ctx.query.push(("key".into(), "k1".into()));
+            ctx.query.push(("page_size".into(), "50".into()));
+            Ok(())
+        }
+    }
+
+    /// Proxy `uri` through a route on `/items` authenticated by
+    /// [`QueryInjector`] that allowlists `page` and `page_size`, and return
+    /// the request line the upstream received.
+    async fn proxy_with_query_injector(uri: &str) -> String {
+        use crate::domain::model::{
+            AuthConfig, HttpMatch, HttpMethod, MatchRules, SharingMode, UpdateRouteRequest,
+        };
+
+        let (cp, mut svc) = dp_with_test_cp();
+        svc.auth_registry
+            .register("test-query-auth", Arc::new(QueryInjector));
+        let ctx = tenant_ctx();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        let route = create_target(
+            &cp,
+            &ctx,
+            "keyed",
+            Some(AuthConfig {
+                plugin_type: "test-query-auth".into(),
+                sharing: SharingMode::Private,
+                config: None,
+            }),
+            HttpMethod::Get,
+            "/items",
+            port,
+        )
+        .await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                match_rules: Some(MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: "/items".into(),
+                        query_allowlist: vec!["page".into(), "page_size".into()],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        svc.proxy_request(ctx, get(uri)).await.unwrap();
+        let head = upstream_seen.await.unwrap();
+        head.lines().next().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn plugin_query_params_reach_upstream() {
+        let request_line = proxy_with_query_injector("/keyed/items").await;
+        assert_eq!(request_line, "GET /items?key=k1&page_size=50 HTTP/1.1");
+    }
+
+    #[tokio::test]
+    async fn plugin_query_params_replace_client_params() {
+        let request_line = proxy_with_query_injector("/keyed/items?page_size=1000&page=3").await;
+        assert_eq!(
+            request_line,
+            "GET /items?page=3&key=k1&page_size=50 HTTP/1.1"
+        );
+    }
+
+    // -- API version --
+
+    #[tokio::test]
+    async fn route_api_version_sets_header_and_query_param() {
+        use crate::domain::model::{ApiVersion, HttpMethod, UpdateRouteRequest};
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        let route = create_target(
+            &cp,
+            &ctx,
+            "versioned",
+            None,
+            HttpMethod::Get,
+            "/items",
+            port,
+        )
+        .await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                api_version: Some(ApiVersion {
+                    value: "2024-06-01".into(),
+                    header: None,
+                    query_param: Some("api-version".into()),
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        svc.proxy_request(ctx, get("/versioned/items"))
+            .await
+            .unwrap();
+        let head = upstream_seen.await.unwrap();
+        let request_line = head.lines().next().unwrap();
+        assert_eq!(
+            request_line, "GET /items?api-version=2024-06-01 HTTP/1.1",
+            "{head}"
+        );
+        assert!(head.contains("api-version: 2024-06-01"), "{head}");
+    }
+
+    #[tokio::test]
+    async fn upstream_header_rule_overrides_route_api_version() {
+        use crate::domain::model::{
+            ApiVersion, HeadersConfig, HttpMethod, RequestHeaderRules, UpdateRouteRequest,
+            UpdateUpstreamRequest,
+        };
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        let route = create_target(&cp, &ctx, "pinned", None, HttpMethod::Get, "/items", port).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                api_version: Some(ApiVersion {
+                    value: "2024-06-01".into(),
+                    header: Some("x-ms-version".into()),
+                    query_param: None,
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        cp.update_upstream(
+            &ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                headers: Some(HeadersConfig {
+                    request: Some(RequestHeaderRules {
+                        set: HashMap::from([("x-ms-version".into(), "2023-01-01".into())]),
+                        ..Default::default()
+                    }),
+                    response: None,
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        svc.proxy_request(ctx, get("/pinned/items")).await.unwrap();
+        let head = upstream_seen.await.unwrap();
+        assert!(head.contains("x-ms-version: 2023-01-01"), "{head}");
+        assert!(!head.contains("2024-06-01"), "{head}");
+    }
+
+    // -- Header templates --
+
+    #[tokio::test]
+    async fn header_rules_interpolate_security_context_ids() {
+        use crate::domain::model::{
+            HeadersConfig, HttpMethod, RequestHeaderRules, UpdateUpstreamRequest,
+        };
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        let route = create_target(&cp, &ctx, "ids", None, HttpMethod::Get, "/items", port).await;
+        cp.update_upstream(
+            &ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                headers: Some(HeadersConfig {
+                    request: Some(RequestHeaderRules {
+                        set: HashMap::from([
+                            ("X-Tenant-Id".into(), "{{ctx.subject_tenant_id}}".into()),
+                            ("X-Subject-Id".into(), "{{ ctx.subject_id }}".into()),
+                        ]),
+                        ..Default::default()
+                    }),
+                    response: None,
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        svc.proxy_request(ctx.clone(), get("/ids/items"))
+            .await
+            .unwrap();
+        let head = upstream_seen.await.unwrap().to_ascii_lowercase();
+        let tenant = format!("\r\nx-tenant-id: {}\r\n", ctx.subject_tenant_id());
+        let subject = format!("\r\nx-subject-id: {}\r\n", ctx.subject_id());
+        assert!(head.contains(&tenant), "{head}");
+        assert!(head.contains(&subject), "{head}");
+    }
+
+    #[tokio::test]
+    async fn oversized_outbound_header_is_rejected() {
+        use crate::domain::model::{
+            HeadersConfig, HttpMethod, RequestHeaderRules, UpdateUpstreamRequest,
+        };
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "sized", None, HttpMethod::Get, "/items", 9).await;
+        cp.update_upstream(
+            &ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                headers: Some(HeadersConfig {
+                    request: Some(RequestHeaderRules {
+                        set: HashMap::from([("X-Large".into(), "c".repeat(200))]),
+                        ..Default::default()
+                    }),
+                    response: None,
+                }),
+                max_header_size_bytes: Some(128),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let err = svc
+            .proxy_request(ctx, get("/sized/items"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, DomainError::HeaderTooLarge { detail, .. } if detail.contains("x-large")),
+            "{err:?}"
+        );
+    }
+
+    struct FoldedHeader;
+
+    #[async_trait]
+    impl AuthPlugin for FoldedHeader {
+        async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+            ctx.headers
+                .insert("x-folded".into(), "part one\r\n  part two".into());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn folded_plugin_header_is_unfolded() {
+        use crate::domain::model::{AuthConfig, HttpMethod, SharingMode};
+
+        let (cp, mut svc) = dp_with_test_cp();
+        svc.auth_registry
+            .register("test-folded-auth", Arc::new(FoldedHeader));
+        let ctx = tenant_ctx();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        create_target(
+            &cp,
+            &ctx,
+            "folded",
+            Some(AuthConfig {
+                plugin_type: "test-folded-auth".into(),
+                sharing: SharingMode::Private,
+                config: None,
+            }),
+            HttpMethod::Get,
+            "/items",
+            port,
+        )
+        .await;
+
+        svc.proxy_request(ctx, get("/folded/items")).await.unwrap();
+        let head = upstream_seen.await.unwrap().to_ascii_lowercase();
+        assert!(
+            head.contains("\r\nx-folded: part one part two\r\n"),
+            "{head}"
+        );
+    }
+
+    /// Sets a bearer token and its type, then fails when `fail` is set.
+    struct TokenTypePlugin {
+        token_type: &'static str,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl AuthPlugin for TokenTypePlugin {
+        async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+            ctx.headers.insert("Authorization", "Bearer minted");
+            ctx.headers.insert("X-Token-Type", self.token_type);
+            if self.fail {
+                return Err(PluginError::AuthFailed("token rejected".into()));
+            }
+            Ok(())
+        }
+    }
+
+    async fn token_type_target(
+        plugin: TokenTypePlugin,
+    ) -> (
+        DataPlaneServiceImpl,
+        SecurityContext,
+        tokio::net::TcpListener,
+    ) {
+        use crate::domain::model::{AuthConfig, HttpMethod, SharingMode};
+
+        let (cp, mut svc) = dp_with_test_cp();
+        svc.auth_registry
+            .register("test-token-type", Arc::new(plugin));
+        let ctx = tenant_ctx();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        create_target(
+            &cp,
+            &ctx,
+            "tokens",
+            Some(AuthConfig {
+                plugin_type: "test-token-type".into(),
+                sharing: SharingMode::Private,
+                config: None,
+            }),
+            HttpMethod::Get,
+            "/items",
+            port,
+        )
+        .await;
+        (svc, ctx, listener)
+    }
+
+    #[tokio::test]
+    async fn plugin_sets_several_headers_in_one_call() {
+        let (svc, ctx, listener) = token_type_target(TokenTypePlugin {
+            token_type: "access",
+            fail: false,
+        })
+        .await;
+        let upstream_seen = tokio::spawn(serve_once(listener));
+
+        svc.proxy_request(ctx, get("/tokens/items")).await.unwrap();
+        let head = upstream_seen.await.unwrap().to_ascii_lowercase();
+        assert!(
+            head.contains("\r\nauthorization: bearer minted\r\n"),
+            "{head}"
+        );
+        assert!(head.contains("\r\nx-token-type: access\r\n"), "{head}");
+    }
+
+    #[tokio::test]
+    async fn failing_plugin_leaves_headers_unchanged() {
+        use crate::domain::test_support::AuthContextBuilder;
+
+        let svc = build_svc(Arc::new(MockSelector::new()));
+        let mut auth_ctx = AuthContextBuilder::new()
+            .with_header("accept", "*/*")
+            .build();
+        let before = auth_ctx.headers.clone();
+
+        let plugin = TokenTypePlugin {
+            token_type: "access",
+            fail: true,
+        };
+        let err = svc
+            .authenticate_timed("test-token-type", &plugin, &mut auth_ctx)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PluginError::AuthFailed(_)), "{err:?}");
+        assert_eq!(auth_ctx.headers, before);
+    }
+
+    #[tokio::test]
+    async fn plugin_header_that_cannot_be_sent_fails_the_request() {
+        // A control character makes the token type unsendable; the bearer
+        // token must not go out without it.
+        let (svc, ctx, _listener) = token_type_target(TokenTypePlugin {
+            token_type: "access\u{0}",
+            fail: false,
+        })
+        .await;
+
+        let err = svc
+            .proxy_request(ctx, get("/tokens/items"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, DomainError::AuthenticationFailed { detail, .. } if detail.contains("X-Token-Type")),
+            "{err:?}"
+        );
+    }
+
+    // -- Route description --
+
+    #[tokio::test]
+    async fn describe_route_reports_timeout_precedence() {
+        use crate::domain::model::{
+            ConfigSource, HttpMethod, UpdateRouteRequest, UpdateUpstreamRequest,
+        };
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "described", None, HttpMethod::Get, "/items", 9).await;
+
+        let d = svc.describe_route(&ctx, route.id).await.unwrap();
+        assert_eq!(d.upstream_id, route.upstream_id);
+        assert_eq!(d.timeout_secs.value, REQUEST_TIMEOUT.as_secs());
+        assert_eq!(d.timeout_secs.source, ConfigSource::Gateway);
+        assert_eq!(d.user_agent.source, ConfigSource::Gateway);
+
+        cp.update_upstream(
+            &ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                timeout_secs: Some(20),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let d = svc.describe_route(&ctx, route.id).await.unwrap();
+        assert_eq!(
+            (d.timeout_secs.value, d.timeout_secs.source),
+            (20, ConfigSource::Upstream)
+        );
+
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                timeout_secs: Some(5),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let d = svc.describe_route(&ctx, route.id).await.unwrap();
+        assert_eq!(
+            (d.timeout_secs.value, d.timeout_secs.source),
+            (5, ConfigSource::Route)
+        );
+        assert_eq!(d.route.timeout_secs, Some(5));
+    }
+
+    // -- Request trace --
+
+    #[tokio::test]
+    async fn trace_request_records_each_stage() {
+        use crate::domain::model::{AuthConfig, HttpMethod, SharingMode, UpdateRouteRequest};
+        use crate::domain::test_support::{APIKEY_AUTH_PLUGIN_ID, TestCpBuilder};
+
+        let (cp, svc) = dp_with_cp(
+            TestCpBuilder::new().with_credentials(vec![("key".into(), "sk-test".into())]),
+        );
+        let ctx = tenant_ctx();
+        // Nothing listens on the port: the upstream is never contacted.
+        let route = create_target(
+            &cp,
+            &ctx,
+            "traced",
+            Some(AuthConfig {
+                plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+                sharing: SharingMode::Private,
+                config: Some(HashMap::from([
+                    ("header".into(), "x-api-key".into()),
+                    ("secret_ref".into(), "cred://key".into()),
+                ])),
+            }),
+            HttpMethod::Get,
+            "/items",
+            9,
+        )
+        .await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                status_remap: Some(HashMap::from([(503, 502)])),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let mock = http::Response::builder()
+            .status(503)
+            .body(Body::Empty)
+            .unwrap();
+
+        let trace = svc
+            .trace_request(ctx.clone(), get("/traced/items"), Some(mock))
+            .await;
+        assert_eq!(trace.route_id, Some(route.id));
+        assert_eq!(trace.upstream_id, Some(route.upstream_id));
+        assert_eq!(trace.endpoint.as_deref(), Some("127.0.0.1:9"));
+        assert_eq!(trace.authz, Some(TraceAuthz::Allowed));
+        assert_eq!(trace.plugins, [APIKEY_AUTH_PLUGIN_ID]);
+        assert_eq!(trace.transforms, ["status_remap"]);
+        assert!(trace.upstream_called);
+        assert_eq!(trace.status, Some(502));
+        assert_eq!(trace.error, None);
+
+        // Without a mock the trace ends before sending.
+        let trace = svc
+            .trace_request(ctx.clone(), get("/traced/items"), None)
+            .await;
+        assert_eq!(trace.plugins, [APIKEY_AUTH_PLUGIN_ID]);
+        assert!(!trace.upstream_called);
+        assert_eq!(trace.status, None);
+
+        let trace = svc.trace_request(ctx, get("/untraced/items"), None).await;
+        assert_eq!(trace.route_id, None);
+        assert!(trace.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn trace_of_half_open_upstream_leaves_probe_for_real_request() {
+        use crate::domain::circuit_breaker::CircuitStatus;
+        use crate::domain::model::HttpMethod;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_circuit_breaker(1, 0, 1);
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "cb-trace", None, HttpMethod::Get, "/x", port).await;
+
+        // Nothing accepts yet: the connection failure opens the circuit, and
+        // the zero cooldown makes the next request a half-open probe.
+        drop(listener);
+        let _ = svc.proxy_request(ctx.clone(), get("/cb-trace/x")).await;
+        assert!(matches!(
+            svc.runtime_state(route.upstream_id).circuit,
+            CircuitStatus::Open { .. }
+        ));
+
+        for _ in 0..2 {
+            let trace = svc
+                .trace_request(ctx.clone(), get("/cb-trace/x"), None)
+                .await;
+            assert_eq!(trace.error, None);
+        }
+
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+            .await
+            .unwrap();
+        tokio::spawn(serve_forever(listener));
+        let resp = svc.proxy_request(ctx, get("/cb-trace/x")).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+    }
+
+    // -- Shutdown --
+
+    #[tokio::test]
+    async fn shutdown_flushes_response_cache_backend() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Default)]
+        struct FlushCounter(AtomicUsize);
+
+        #[async_trait::async_trait]
+        impl ResponseCache for FlushCounter {
+            async fn get(&self, _key: &str) -> Option<CachedResponse> {
+                None
+            }
+            async fn put(&self, _key: &str, _response: CachedResponse, _ttl: Duration) {}
+            async fn invalidate(&self, _key: &str) {}
+            async fn flush(&self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let cache = Arc::new(FlushCounter::default());
+        let (_cp, svc) = dp_with_test_cp();
+        let svc = svc.with_response_cache(cache.clone());
+
+        svc.shutdown().await;
+        assert_eq!(cache.0.load(Ordering::SeqCst), 1);
+    }
+
+    // -- Request schema --
+
+    #[tokio::test]
+    async fn request_schema_rejects_non_conforming_json_body() {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "llm", None, HttpMethod::Post, "/chat", port).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                request_schema: Some(serde_json::json!({
+                    "type": "object",
+                    "required": ["model"],
+                    "properties": {"max_tokens": {"type": "integer", "minimum": 1}}
+                })),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let post = |body: &'static str| {
+            http::Request::builder()
+                .method(http::Method::POST)
+                .uri("/llm/chat")
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(Body::Bytes(Bytes::from_static(body.as_bytes())))
+                .unwrap()
+        };
+
+        let err = svc
+            .proxy_request(ctx.clone(), post(r#"{"max_tokens":0}"#))
+            .await
+            .unwrap_err();
+        let DomainError::SchemaViolation { violations, .. } = err else {
+            panic!("expected schema violation, got {err:?}");
+        };
+        let paths: Vec<_> = violations.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(paths.len(), 2);
+        assert!(paths.contains(&"") && paths.contains(&"/max_tokens"));
+
+        let resp = svc
+            .proxy_request(ctx, post(r#"{"model":"m","max_tokens":8}"#))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert!(
+            upstream_seen
+                .await
+                .unwrap()
+                .starts_with("POST /chat HTTP/1.1\r\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn request_schema_body_is_not_buffered_for_denied_caller() {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_authz_mode(AuthzMode::DenyAll);
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "llm", None, HttpMethod::Post, "/chat", 9).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                request_schema: Some(serde_json::json!({"type": "object"})),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        // The body never yields: buffering it would hang the request.
+        let body: BodyStream = Box::pin(futures::stream::pending());
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/llm/chat")
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(Body::Stream(body))
+            .unwrap();
+        let err = tokio::time::timeout(Duration::from_secs(5), svc.proxy_request(ctx, req))
+            .await
+            .expect("body must not be read")
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Forbidden { .. }), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn allowlisted_auth_config_override_header_changes_secret() {
+        use crate::domain::model::{AuthConfig, HttpMethod, SharingMode};
+        use crate::domain::test_support::{APIKEY_AUTH_PLUGIN_ID, TestCpBuilder};
+
+        let (cp, svc) = dp_with_cp(TestCpBuilder::new().with_credentials(vec![
+            ("prod-key".into(), "sk-prod".into()),
+            ("test-key".into(), "sk-test".into()),
+        ]));
+        let svc = svc.with_auth_config_overrides(&["secret_ref".to_string()]);
+        let ctx = tenant_ctx();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        create_target(
+            &cp,
+            &ctx,
+            "keyed",
+            Some(AuthConfig {
+                plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+                sharing: SharingMode::Private,
+                config: Some(HashMap::from([
+                    ("header".into(), "x-api-key".into()),
+                    ("secret_ref".into(), "cred://prod-key".into()),
+                ])),
+            }),
+            HttpMethod::Get,
+            "/items",
+            port,
+        )
+        .await;
+
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        let mut req = get("/keyed/items");
+        req.headers_mut().insert(
+            "x-oagw-auth-config-secret_ref",
+            "cred://test-key".parse().unwrap(),
+        );
+        // `header` is not allowlisted, so the key still goes to `x-api-key`.
+        req.headers_mut()
+            .insert("x-oagw-auth-config-header", "x-leaked".parse().unwrap());
+        svc.proxy_request(ctx, req).await.unwrap();
+        let head = upstream_seen.await.unwrap().to_ascii_lowercase();
+        assert!(head.contains("\r\nx-api-key: sk-test\r\n"), "{head}");
+        assert!(!head.contains("x-leaked"), "{head}");
+        assert!(!head.contains("x-oagw-auth-config"), "{head}");
+    }
+
+    #[test]
+    fn auth_config_overrides_ignored_unless_allowlisted() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-oagw-auth-config-secret_ref",
+            HeaderValue::from_static("cred://test-key"),
+        );
+        headers.insert(
+            "x-oagw-auth-config-prefix",
+            HeaderValue::from_static("Token "),
+        );
+        let original = HashMap::from([("secret_ref".to_string(), "cred://prod-key".to_string())]);
+
+        let mut config = original.clone();
+        apply_auth_config_overrides(&headers, &[], &mut config);
+        assert_eq!(config, original);
+
+        apply_auth_config_overrides(&headers, &["prefix".to_string()], &mut config);
+        assert_eq!(config["secret_ref"], "cred://prod-key");
+        assert_eq!(config["prefix"], "Token ");
+    }
+
+    // -- Body credential injection --
+
+    #[tokio::test]
+    async fn apikey_body_pointer_sets_secret_in_streamed_json_body() {
+        use crate::domain::model::{AuthConfig, HttpMethod, SharingMode};
+        use crate::domain::test_support::{APIKEY_AUTH_PLUGIN_ID, TestCpBuilder};
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                assert!(n > 0, "connection closed before request body");
+                request.extend_from_slice(&buf[..n]);
+                if request.ends_with(b"}") {
+                    break;
+                }
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
+                .await
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let (cp, svc) = dp_with_cp(
+            TestCpBuilder::new().with_credentials(vec![("odd-key".into(), "sk-body".into())]),
+        );
+        let ctx = tenant_ctx();
+        create_target(
+            &cp,
+            &ctx,
+            "odd",
+            Some(AuthConfig {
+                plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+                sharing: SharingMode::Private,
+                config: Some(HashMap::from([
+                    ("body_pointer".into(), "/auth/api_key".into()),
+                    ("secret_ref".into(), "cred://odd-key".into()),
+                ])),
+            }),
+            HttpMethod::Post,
+            "/run",
+            port,
+        )
+        .await;
+        let post = |body: Body| {
+            http::Request::builder()
+                .method(http::Method::POST)
+                .uri("/odd/run")
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(body)
+                .unwrap()
+        };
+
+        let err = svc
+            .proxy_request(ctx.clone(), post(Body::from("model=m1")))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, DomainError::Validation { detail, .. } if detail.contains("JSON object")),
+            "{err:?}"
+        );
+
+        let chunks: Vec<Result<Bytes, oagw_sdk::body::BoxError>> = vec![
+            Ok(Bytes::from_static(br#"{"model":"#)),
+            Ok(Bytes::from_static(br#""m1"}"#)),
+        ];
+        let resp = svc
+            .proxy_request(
+                ctx,
+                post(Body::Stream(Box::pin(futures::stream::iter(chunks)))),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        let request = upstream_seen.await.unwrap();
+        let (head, body) = request.split_once("\r\n\r\n").unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(body).unwrap(),
+            serde_json::json!({"model": "m1", "auth": {"api_key": "sk-body"}})
+        );
+        let content_length = format!("content-length: {}", body.len());
+        assert!(
+            head.lines()
+                .any(|l| l.eq_ignore_ascii_case(&content_length)),
+            "{head}"
+        );
+        assert!(
+            !head.to_ascii_lowercase().contains("transfer-encoding"),
+            "{head}"
+        );
+    }
+
+    // -- Request log sampling --
+
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl CapturedLogs {
+        fn count(&self, needle: &str) -> usize {
+            String::from_utf8_lossy(&self.0.lock().unwrap())
+                .matches(needle)
+                .count()
+        }
+    }
+
+    /// Send `n` requests through a route with `rate` and count how many were
+    /// logged as sampled requests and responses.
+    async fn sampled_log_counts(rate: f64, n: usize) -> (usize, usize) {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::INFO)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_log_sampling_seed(42);
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "sampled", None, HttpMethod::Get, "/items", 9).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                log_sample_rate: Some(rate),
+                request_schema: None,
+                body_rewrite: None,
+                strip_query_params: None,
+                strip_inbound_headers: None,
+                idempotency_ttl_secs: None,
+                cache_responses: None,
+                negative_cache_ttl_secs: None,
+                response_header_allowlist: None,
+                max_total_duration_ms: None,
+                max_body_size_bytes: None,
+                max_outbound_calls: None,
+                coerce_content_type: None,
+                api_version: None,
+                body_transformers: None,
+                log_redact_paths: None,
+                transform_hook_url: None,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        for _ in 0..n {
+            let req = http::Request::builder()
+                .method(http::Method::GET)
+                .uri("/sampled/items")
+                .header(http::header::AUTHORIZATION, "Bearer caller-secret")
+                .body(Body::Empty)
+                .unwrap();
+            let _ = svc.proxy_request(ctx.clone(), req).await;
+        }
+
+        assert_eq!(
+            logs.count("caller-secret"),
+            0,
+            "credentials must be redacted"
+        );
+        (
+            logs.count("sampled proxy request"),
+            logs.count("sampled proxy response"),
+        )
+    }
+
+    #[tokio::test]
+    async fn zero_log_sample_rate_logs_nothing() {
+        assert_eq!(sampled_log_counts(0.0, 16).await, (0, 0));
+    }
+
+    #[tokio::test]
+    async fn full_log_sample_rate_logs_every_request() {
+        assert_eq!(sampled_log_counts(1.0, 16).await, (16, 16));
+    }
+
+    #[tokio::test]
+    async fn seeded_half_log_sample_rate_logs_expected_subset() {
+        // Seed 42 samples requests 3, 4, 5, 8, 9, 12, 13 and 15 of 16.
+        assert_eq!(sampled_log_counts(0.5, 16).await, (8, 8));
+    }
+
+    #[tokio::test]
+    async fn sampled_request_body_is_logged_with_redact_paths_masked() {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::INFO)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "redacted", None, HttpMethod::Post, "/login", 9).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                log_sample_rate: Some(1.0),
+                log_redact_paths: Some(vec!["/password".into(), "/session/token".into()]),
+                transform_hook_url: None,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/redacted/login")
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(Body::Bytes(Bytes::from_static(
+                br#"{"user":"ann-7","password":"hunter2","session":{"token":"tok-123","ttl":60}}"#,
+            )))
+            .unwrap();
+        let _ = svc.proxy_request(ctx, req).await;
+
+        assert_eq!(logs.count("sampled proxy request"), 1);
+        assert_eq!(logs.count("hunter2"), 0, "password must be redacted");
+        assert_eq!(logs.count("tok-123"), 0, "token must be redacted");
+        assert_eq!(logs.count("[REDACTED]"), 2);
+        assert_eq!(logs.count("ann-7"), 1, "other fields are logged");
+        assert_eq!(logs.count("ttl"), 1);
+    }
+
+    // -- Content-type coercion --
+
+    /// POST a body with `content_type` through a route that coerces to
+    /// `application/json`, and return the request head the upstream received.
+    async fn proxy_with_coerced_content_type(content_type: &str) -> String {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        let route =
+            create_target(&cp, &ctx, "coerced", None, HttpMethod::Post, "/items", port).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                coerce_content_type: Some("application/json".into()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/coerced/items")
+            .header(http::header::CONTENT_TYPE, content_type)
+            .body(Body::Bytes(Bytes::from_static(b"{\"a\":1}")))
+            .unwrap();
+        svc.proxy_request(ctx, req).await.unwrap();
+        upstream_seen.await.unwrap().to_ascii_lowercase()
+    }
+
+    #[tokio::test]
+    async fn coerce_content_type_overrides_client_type() {
+        let head = proxy_with_coerced_content_type("text/plain").await;
+        assert!(
+            head.contains("\r\ncontent-type: application/json\r\n"),
+            "{head}"
+        );
+        assert!(!head.contains("text/plain"), "{head}");
+    }
+
+    #[tokio::test]
+    async fn coerce_content_type_keeps_matching_type() {
+        let head = proxy_with_coerced_content_type("application/json; charset=utf-8").await;
+        assert!(
+            head.contains("\r\ncontent-type: application/json; charset=utf-8\r\n"),
+            "{head}"
+        );
+    }
+
+    // -- Transform hook --
+
+    /// Create a POST route on a local upstream whose requests go through
+    /// `hook_url` first; returns the upstream's view of the request.
+    async fn hooked_target(
+        svc: &DataPlaneServiceImpl,
+        cp: &Arc<dyn ControlPlaneService>,
+        hook_url: String,
+    ) -> Result<String, DomainError> {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+
+        let ctx = tenant_ctx();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        let route = create_target(cp, &ctx, "hooked", None, HttpMethod::Post, "/items", port).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                transform_hook_url: Some(hook_url),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/hooked/items?page=2")
+            .header("x-tier", "free")
+            .body(Body::Bytes(Bytes::from_static(b"{\"a\":1}")))
+            .unwrap();
+        svc.proxy_request(ctx, req).await?;
+        Ok(upstream_seen.await.unwrap().to_ascii_lowercase())
+    }
+
+    #[tokio::test]
+    async fn transform_hook_rewrites_header_before_forwarding() {
+        use httpmock::prelude::*;
+
+        let hook = MockServer::start_async().await;
+        let call = hook
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/transform")
+                    .json_body_includes(r#"{"method":"POST","path":"/items?page=2"}"#)
+                    .json_body_includes(r#"{"headers":{"x-tier":"free"}}"#)
+                    .json_body_includes(r#"{"body":"eyJhIjoxfQ=="}"#);
+                then.status(200)
+                    .json_body(serde_json::json!({ "headers": { "x-tier": "gold" } }));
+            })
+            .await;
+        let (cp, svc) = dp_with_test_cp();
+
+        let head = hooked_target(&svc, &cp, hook.url("/transform"))
+            .await
+            .unwrap();
+
+        call.assert_async().await;
+        assert!(head.contains("\r\nx-tier: gold\r\n"), "{head}");
+        assert!(!head.contains("free"), "{head}");
+    }
+
+    #[tokio::test]
+    async fn transform_hook_timeout_fails_request() {
+        use httpmock::prelude::*;
+
+        let hook = MockServer::start_async().await;
+        hook.mock_async(|when, then| {
+            when.method(POST).path("/transform");
+            then.status(200)
+                .delay(Duration::from_secs(5))
+                .json_body(serde_json::json!({}));
+        })
+        .await;
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_transform_hook_timeout(Duration::from_millis(100));
+
+        let err = hooked_target(&svc, &cp, hook.url("/transform"))
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(err, DomainError::RequestTimeout { ref detail, .. } if detail.contains("transform hook")),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn transform_hook_error_fails_request() {
+        use httpmock::prelude::*;
+
+        let hook = MockServer::start_async().await;
+        hook.mock_async(|when, then| {
+            when.method(POST).path("/transform");
+            then.status(500);
+        })
+        .await;
+        let (cp, svc) = dp_with_test_cp();
+
+        let err = hooked_target(&svc, &cp, hook.url("/transform"))
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(err, DomainError::DownstreamError { ref detail, .. } if detail.contains("500")),
+            "{err:?}"
+        );
+    }
+
+    // -- Request span --
+
+    #[tokio::test]
+    async fn request_span_carries_upstream_attributes() {
+        use crate::domain::model::HttpMethod;
+        use tracing_subscriber::fmt::format::FmtSpan;
+
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::INFO)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+        let route =
+            create_target(&cp, &ctx, "spanned", None, HttpMethod::Get, "/items", port).await;
+
+        let req = http::Request::builder()
+            .method(http::Method::GET)
+            .uri("/spanned/items?api_key=query-secret")
+            .header(http::header::AUTHORIZATION, "Bearer caller-secret")
+            .body(Body::Empty)
+            .unwrap();
+        svc.proxy_request(ctx, req).await.unwrap();
+        upstream_seen.await.unwrap();
+
+        let text = String::from_utf8_lossy(&logs.0.lock().unwrap()).into_owned();
+        let closed = text
+            .lines()
+            .find(|l| l.contains("oagw_proxy{") && l.contains("close"))
+            .unwrap_or_else(|| panic!("no closed request span in:\n{text}"));
+        for attr in [
+            "http.method=GET".to_string(),
+            format!("upstream.id={}", route.upstream_id),
+            format!("route.id={}", route.id),
+            "peer.service=\"spanned\"".to_string(),
+            "http.status_code=200".to_string(),
+        ] {
+            assert!(closed.contains(&attr), "{attr} missing from {closed}");
+        }
+        assert!(!text.contains("caller-secret"), "{text}");
+        assert!(!text.contains("query-secret"), "{text}");
+    }
+
+    // -- DNS retry --
+
+    /// Resolver that fails its first `failures` lookups, then answers
+    /// loopback.
+    struct FlakyResolver {
+        failures: AtomicUsize,
+        lookups: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl super::super::dns_cache::HostResolver for FlakyResolver {
+        async fn lookup(&self, host: &str) -> std::io::Result<Arc<[std::net::IpAddr]>> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            if self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Err(std::io::Error::other(format!(
+                    "temporary failure resolving {host}"
+                )));
+            }
+            Ok(Arc::from(vec![std::net::IpAddr::from([127, 0, 0, 1])]))
+        }
+    }
+
+    #[tokio::test]
+    async fn dns_failure_is_retried_until_host_resolves() {
+        use crate::domain::model::HttpMethod;
+        use modkit::client_hub::ClientHub;
+
+        let resolver = Arc::new(FlakyResolver {
+            failures: AtomicUsize::new(1),
+            lookups: AtomicUsize::new(0),
+        });
+        let dns = Arc::new(DnsCache::default().with_resolver(resolver.clone()));
+
+        let hub = ClientHub::new();
+        let cp = crate::domain::test_support::TestCpBuilder::new().build_and_register(&hub);
+        let server_conf = Arc::new(pingora_core::server::configuration::ServerConf::default());
+        let proxy = Arc::new(crate::infra::proxy::pingora_proxy::new_http_proxy(
+            &server_conf,
+            crate::infra::proxy::pingora_proxy::PingoraProxy::new(
+                Duration::from_secs(10),
+                Duration::from_secs(30),
+            )
+            .with_dns_cache(dns.clone()),
+        ));
+        let svc = DataPlaneServiceImpl::new(
+            cp.clone(),
+            hub.get::<dyn CredStoreClientV1>().unwrap(),
+            crate::domain::test_support::allow_all_enforcer(),
+            None,
+            TokenCacheConfig::default(),
+            Arc::new(MockSelector::new()),
+            proxy,
+        )
+        .with_allow_http_upstream(true)
+        .with_dns_retry(dns, 2, Duration::from_millis(5));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_once(listener));
+        let ctx = tenant_ctx();
+        create_host_target(
+            &cp,
+            &ctx,
+            "flaky",
+            "flaky.example",
+            None,
+            HttpMethod::Get,
+            "/v1",
+            port,
+        )
+        .await;
+
+        let response = svc.proxy_request(ctx, get("/flaky/v1")).await.unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        // One failed lookup, one retry; the proxy then dials from the cache.
+        assert_eq!(resolver.lookups.load(Ordering::SeqCst), 2);
+    }
+
+    // -- Upstream TLS server name --
+
+    /// Serve one HTTPS connection on 127.0.0.1 with a self-signed
+    /// certificate for `cert_name`. Returns the port and a data plane that
+    /// trusts the certificate.
+    async fn tls_upstream(
+        cert_name: &str,
+    ) -> (u16, Arc<dyn ControlPlaneService>, DataPlaneServiceImpl) {
+        use modkit::client_hub::ClientHub;
+        use tokio::io::AsyncReadExt;
+
+        let cert = rcgen::generate_simple_self_signed(vec![cert_name.to_string()]).unwrap();
+        let ca_file = std::env::temp_dir().join(format!("oagw-ca-{}.pem", Uuid::new_v4()));
+        std::fs::write(&ca_file, cert.cert.pem()).unwrap();
+        let config = rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![cert.cert.der().clone()],
+                rustls_pki_types::PrivateKeyDer::Pkcs8(cert.key_pair.serialize_der().into()),
+            )
+            .unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            // A client that rejects the certificate aborts the handshake.
+            let Ok(mut stream) = acceptor.accept(stream).await else {
+                return;
             };
-            plugin
-                .authenticate(&mut auth_ctx)
+            let mut head = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = stream.read(&mut buf).await.unwrap();
+                assert!(n > 0, "connection closed before request head");
+                head.extend_from_slice(&buf[..n]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
                 .await
-                .map_err(|e| match e {
-                    crate::domain::plugin::PluginError::SecretNotFound(ref s) => {
-                        DomainError::SecretNotFound {
-                            detail: s.clone(),
-                            instance: instance_uri.clone(),
+                .unwrap();
+            stream.shutdown().await.ok();
+        });
+
+        let hub = ClientHub::new();
+        let cp = crate::domain::test_support::TestCpBuilder::new().build_and_register(&hub);
+        let server_conf = Arc::new(pingora_core::server::configuration::ServerConf {
+            ca_file: Some(ca_file.to_string_lossy().into_owned()),
+            ..Default::default()
+        });
+        let proxy = Arc::new(crate::infra::proxy::pingora_proxy::new_http_proxy(
+            &server_conf,
+            crate::infra::proxy::pingora_proxy::PingoraProxy::new(
+                Duration::from_secs(10),
+                Duration::from_secs(30),
+            ),
+        ));
+        let svc = DataPlaneServiceImpl::new(
+            cp.clone(),
+            hub.get::<dyn CredStoreClientV1>().unwrap(),
+            crate::domain::test_support::allow_all_enforcer(),
+            None,
+            TokenCacheConfig::default(),
+            Arc::new(MockSelector::new()),
+            proxy,
+        );
+        (port, cp, svc)
+    }
+
+    /// Point `alias` at `https://127.0.0.1:{port}`, verifying the
+    /// certificate against `tls_server_name`.
+    async fn create_tls_target(
+        cp: &Arc<dyn ControlPlaneService>,
+        ctx: &SecurityContext,
+        alias: &str,
+        port: u16,
+        tls_server_name: &str,
+    ) {
+        use crate::domain::model::{HttpMethod, UpdateUpstreamRequest};
+
+        let route = create_target(cp, ctx, alias, None, HttpMethod::Get, "/v1", port).await;
+        cp.update_upstream(
+            ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                server: Some(Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Https,
+                        host: "127.0.0.1".into(),
+                        port,
+                    }],
+                }),
+                tls_server_name: Some(tls_server_name.into()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn certificate_is_verified_against_configured_server_name() {
+        let (port, cp, svc) = tls_upstream("api.internal").await;
+        let ctx = tenant_ctx();
+        create_tls_target(&cp, &ctx, "pinned", port, "api.internal").await;
+
+        let response = svc.proxy_request(ctx, get("/pinned/v1")).await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn certificate_for_another_name_is_rejected() {
+        let (port, cp, svc) = tls_upstream("api.internal").await;
+        let ctx = tenant_ctx();
+        create_tls_target(&cp, &ctx, "mismatched", port, "other.internal").await;
+
+        let err = svc
+            .proxy_request(ctx, get("/mismatched/v1"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::DownstreamError { .. }),
+            "{err:?}"
+        );
+    }
+
+    /// Have `route`'s upstream send its remaining budget in `X-Timeout-Ms`,
+    /// out of a 5 second timeout.
+    async fn send_budget(
+        cp: &Arc<dyn ControlPlaneService>,
+        ctx: &SecurityContext,
+        route: &crate::domain::model::Route,
+    ) {
+        use crate::domain::model::UpdateUpstreamRequest;
+
+        cp.update_upstream(
+            ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                budget_header: Some("X-Timeout-Ms".into()),
+                timeout_secs: Some(5),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Takes `took` of the mock clock's time to authenticate.
+    struct SlowPlugin {
+        clock: crate::domain::test_support::MockClock,
+        took: Duration,
+    }
+
+    #[async_trait]
+    impl AuthPlugin for SlowPlugin {
+        async fn authenticate(&self, _ctx: &mut AuthContext) -> Result<(), PluginError> {
+            self.clock.advance(self.took);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn budget_header_carries_the_remaining_timeout() {
+        use crate::domain::model::HttpMethod;
+        use crate::domain::test_support::MockClock;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream = tokio::spawn(serve_once(listener));
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_clock(Arc::new(MockClock::default()));
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "budget", None, HttpMethod::Get, "/v1", port).await;
+        send_budget(&cp, &ctx, &route).await;
+
+        // A client-sent budget is replaced, never trusted.
+        let req = http::Request::builder()
+            .uri("/budget/v1")
+            .header("x-timeout-ms", "600000")
+            .body(Body::Empty)
+            .unwrap();
+        let response = svc.proxy_request(ctx, req).await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let head = upstream.await.unwrap().to_ascii_lowercase();
+        assert!(head.contains("\r\nx-timeout-ms: 5000\r\n"), "{head}");
+        assert!(!head.contains("600000"), "{head}");
+    }
+
+    #[tokio::test]
+    async fn budget_header_shrinks_by_time_spent_in_auth() {
+        use crate::domain::model::{AuthConfig, HttpMethod, SharingMode, UpdateRouteRequest};
+        use crate::domain::test_support::MockClock;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream = tokio::spawn(serve_once(listener));
+        let clock = MockClock::default();
+        let (cp, mut svc) = dp_with_test_cp();
+        svc.auth_registry.register(
+            "test-slow",
+            Arc::new(SlowPlugin {
+                clock: clock.clone(),
+                took: Duration::from_millis(1500),
+            }),
+        );
+        let svc = svc.with_clock(Arc::new(clock));
+        let ctx = tenant_ctx();
+        let route = create_target(
+            &cp,
+            &ctx,
+            "slow-auth",
+            Some(AuthConfig {
+                plugin_type: "test-slow".into(),
+                sharing: SharingMode::Private,
+                config: None,
+            }),
+            HttpMethod::Get,
+            "/v1",
+            port,
+        )
+        .await;
+        send_budget(&cp, &ctx, &route).await;
+        // The route's 4s maximum duration is tighter than the 5s timeout.
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                max_total_duration_ms: Some(4000),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let response = svc.proxy_request(ctx, get("/slow-auth/v1")).await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let head = upstream.await.unwrap().to_ascii_lowercase();
+        assert!(head.contains("\r\nx-timeout-ms: 2500\r\n"), "{head}");
+    }
+
+    #[tokio::test]
+    async fn refused_connect_is_retried_until_upstream_listens() {
+        use crate::domain::model::HttpMethod;
+
+        // Nothing listens on the port at first, so the first connect is
+        // refused; the upstream comes up well before the retry.
+        let port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let upstream = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+                .await
+                .unwrap();
+            serve_once(listener).await
+        });
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_connect_retry(2, Duration::from_millis(300));
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "late", None, HttpMethod::Get, "/v1", port).await;
+
+        let response = svc.proxy_request(ctx, get("/late/v1")).await.unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert!(upstream.await.unwrap().starts_with("GET /v1 "));
+    }
+
+    #[tokio::test]
+    async fn connect_retries_count_against_max_outbound_calls() {
+        use crate::domain::model::{
+            Endpoint, HttpMethod, Scheme, UpdateRouteRequest, UpdateUpstreamRequest,
+        };
+
+        let dead_port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let live_port = listener.local_addr().unwrap().port();
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_connect_retry(2, Duration::from_millis(1));
+        let ctx = tenant_ctx();
+        let route =
+            create_target(&cp, &ctx, "capped", None, HttpMethod::Get, "/v1", dead_port).await;
+        cp.update_upstream(
+            &ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                failover: Some(vec![Endpoint {
+                    scheme: Scheme::Http,
+                    host: "127.0.0.1".into(),
+                    port: live_port,
+                }]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let cap = |calls| UpdateRouteRequest {
+            max_outbound_calls: Some(calls),
+            ..Default::default()
+        };
+
+        // The refused connect and its two retries use up a cap of 3, so the
+        // failover endpoint is never tried.
+        cp.update_route(&ctx, route.id, cap(3)).await.unwrap();
+        let err = svc
+            .proxy_request(ctx.clone(), get("/capped/v1"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::DownstreamError { ref detail, .. }
+                if detail.contains("max_outbound_calls of 3")),
+            "{err:?}"
+        );
+
+        // One more call to spare reaches the failover endpoint.
+        let upstream = tokio::spawn(serve_once(listener));
+        cp.update_route(&ctx, route.id, cap(4)).await.unwrap();
+        let response = svc.proxy_request(ctx, get("/capped/v1")).await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert!(upstream.await.unwrap().starts_with("GET /v1 "));
+    }
+
+    #[tokio::test]
+    async fn srv_targets_fail_over_to_lower_priority() {
+        use crate::domain::model::HttpMethod;
+
+        let dead_port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let live_port = listener.local_addr().unwrap().port();
+        let upstream = tokio::spawn(serve_once(listener));
+        let target = |port, priority| SrvTarget {
+            host: "127.0.0.1".into(),
+            port,
+            priority,
+            weight: 1,
+        };
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_srv_targets(HashMap::from([(
+            "api.internal".to_string(),
+            vec![target(live_port, 10), target(dead_port, 0)],
+        )]));
+        let ctx = tenant_ctx();
+        create_host_target(
+            &cp,
+            &ctx,
+            "srv",
+            "api.internal",
+            None,
+            HttpMethod::Get,
+            "/v1",
+            443,
+        )
+        .await;
+
+        let response = svc.proxy_request(ctx, get("/srv/v1")).await.unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        let head = upstream.await.unwrap();
+        assert!(head.starts_with("GET /v1 "), "{head}");
+    }
+
+    // -- Deprecation headers --
+
+    #[tokio::test]
+    async fn deprecated_route_sends_deprecation_and_sunset_headers() {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_forever(listener));
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "legacy", None, HttpMethod::Get, "/v1", port).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                deprecated: Some(true),
+                sunset: Some("Sat, 01 Nov 2025 00:00:00 GMT".into()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let response = svc.proxy_request(ctx, get("/legacy/v1")).await.unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(response.headers()["deprecation"], "true");
+        assert_eq!(
+            response.headers()["sunset"],
+            "Sat, 01 Nov 2025 00:00:00 GMT"
+        );
+    }
+
+    #[tokio::test]
+    async fn route_not_deprecated_sends_no_deprecation_headers() {
+        use crate::domain::model::HttpMethod;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_forever(listener));
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "current", None, HttpMethod::Get, "/v1", port).await;
+
+        let response = svc.proxy_request(ctx, get("/current/v1")).await.unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert!(!response.headers().contains_key("deprecation"));
+        assert!(!response.headers().contains_key("sunset"));
+    }
+
+    // -- Body limit events --
+
+    fn has_label<'a>(
+        mut attrs: impl Iterator<Item = &'a opentelemetry::KeyValue>,
+        key: &str,
+        value: &str,
+    ) -> bool {
+        attrs.any(|kv| kv.key.as_str() == key && kv.value.to_string() == value)
+    }
+
+    #[tokio::test]
+    async fn oversized_body_records_limit_exceeded_for_route() {
+        use crate::domain::model::HttpMethod;
+        use opentelemetry::metrics::MeterProvider;
+        use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData};
+        use opentelemetry_sdk::metrics::{
+            InMemoryMetricExporter, PeriodicReader, SdkMeterProvider,
+        };
+
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc
+            .with_max_body_size(16)
+            .with_metrics(OagwMetrics::new(&provider.meter("oagw")));
+
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "uploads", None, HttpMethod::Post, "/files", 9).await;
+
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/uploads/files")
+            .header(http::header::CONTENT_LENGTH, "40")
+            .body(Body::Bytes(Bytes::from(vec![b'x'; 40])))
+            .unwrap();
+        let err = svc.proxy_request(ctx, req).await.unwrap_err();
+        assert!(
+            matches!(err, DomainError::PayloadTooLarge { .. }),
+            "got {err:?}"
+        );
+
+        provider.force_flush().unwrap();
+
+        let route_label = route.id.to_string();
+        let mut count = None;
+        let mut declared = None;
+        for resource_metrics in &exporter.get_finished_metrics().unwrap() {
+            for scope_metrics in resource_metrics.scope_metrics() {
+                for metric in scope_metrics.metrics() {
+                    match metric.data() {
+                        AggregatedMetrics::U64(MetricData::Sum(sum))
+                            if metric.name() == crate::infra::metrics::BODY_LIMIT_EXCEEDED =>
+                        {
+                            for point in sum.data_points() {
+                                if has_label(point.attributes(), "route_id", &route_label) {
+                                    count = Some(point.value());
+                                }
+                            }
                         }
+                        AggregatedMetrics::U64(MetricData::Histogram(hist))
+                            if metric.name() == crate::infra::metrics::BODY_LIMIT_DECLARED_SIZE =>
+                        {
+                            for point in hist.data_points() {
+                                if has_label(point.attributes(), "route_id", &route_label) {
+                                    declared = Some((point.count(), point.sum()));
+                                }
+                            }
+                        }
+                        _ => {}
                     }
-                    crate::domain::plugin::PluginError::Rejected(ref msg)
-                    | crate::domain::plugin::PluginError::InvalidConfig(ref msg) => {
-                        DomainError::Validation {
-                            detail: msg.clone(),
-                            instance: instance_uri.clone(),
+                }
+            }
+        }
+
+        assert_eq!(count, Some(1));
+        assert_eq!(declared, Some((1, 40)));
+    }
+
+    #[tokio::test]
+    async fn oversized_body_from_denied_caller_is_forbidden_and_not_recorded() {
+        use crate::domain::model::HttpMethod;
+        use opentelemetry::metrics::MeterProvider;
+        use opentelemetry_sdk::metrics::{
+            InMemoryMetricExporter, PeriodicReader, SdkMeterProvider,
+        };
+
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc
+            .with_authz_mode(AuthzMode::DenyAll)
+            .with_max_body_size(16)
+            .with_metrics(OagwMetrics::new(&provider.meter("oagw")));
+
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "uploads", None, HttpMethod::Post, "/files", 9).await;
+
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/uploads/files")
+            .header(http::header::CONTENT_LENGTH, "40")
+            .body(Body::Bytes(Bytes::from(vec![b'x'; 40])))
+            .unwrap();
+        let err = svc.proxy_request(ctx, req).await.unwrap_err();
+        assert!(matches!(err, DomainError::Forbidden { .. }), "{err:?}");
+
+        provider.force_flush().unwrap();
+
+        let recorded = exporter.get_finished_metrics().unwrap();
+        let limit_metrics = recorded
+            .iter()
+            .flat_map(|rm| rm.scope_metrics())
+            .flat_map(|sm| sm.metrics())
+            .filter(|m| {
+                m.name() == crate::infra::metrics::BODY_LIMIT_EXCEEDED
+                    || m.name() == crate::infra::metrics::BODY_LIMIT_DECLARED_SIZE
+            })
+            .count();
+        assert_eq!(limit_metrics, 0);
+    }
+
+    #[tokio::test]
+    async fn undeclared_oversized_body_from_denied_caller_is_forbidden() {
+        use crate::domain::model::HttpMethod;
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc
+            .with_authz_mode(AuthzMode::DenyAll)
+            .with_max_body_size(16);
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "uploads", None, HttpMethod::Post, "/files", 9).await;
+
+        // Without Content-Length only the buffered size check applies.
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/uploads/files")
+            .body(Body::Bytes(Bytes::from(vec![b'x'; 40])))
+            .unwrap();
+        let err = svc.proxy_request(ctx, req).await.unwrap_err();
+        assert!(matches!(err, DomainError::Forbidden { .. }), "{err:?}");
+    }
+
+    /// A POST route on `port` whose body limit is `limit` bytes.
+    async fn create_limited_target(
+        cp: &Arc<dyn ControlPlaneService>,
+        ctx: &SecurityContext,
+        alias: &str,
+        port: u16,
+        limit: u64,
+    ) -> Route {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+
+        let route = create_target(cp, ctx, alias, None, HttpMethod::Post, "/files", port).await;
+        cp.update_route(
+            ctx,
+            route.id,
+            UpdateRouteRequest {
+                max_body_size_bytes: Some(limit),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn declared_length_over_route_limit_is_rejected_before_reading_body() {
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        create_limited_target(&cp, &ctx, "small", 9, 32).await;
+
+        // The body never yields: reading any of it would hang the request.
+        let body: BodyStream = Box::pin(futures::stream::pending());
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/small/files")
+            .header(http::header::CONTENT_LENGTH, "1000")
+            .body(Body::Stream(body))
+            .unwrap();
+        let err = tokio::time::timeout(Duration::from_secs(5), svc.proxy_request(ctx, req))
+            .await
+            .expect("body must not be read")
+            .unwrap_err();
+        match err {
+            DomainError::PayloadTooLarge { detail, .. } => {
+                assert!(
+                    detail.contains("1000 bytes exceeds maximum of 32 bytes"),
+                    "{detail}"
+                );
+            }
+            other => panic!("expected PayloadTooLarge, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn chunked_body_over_route_limit_is_rejected_mid_read() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // The upstream reads without answering, so only the limit can end
+        // the request.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            drop(stream);
+        });
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        create_limited_target(&cp, &ctx, "chunky", port, 32).await;
+
+        let pulled = Arc::new(AtomicUsize::new(0));
+        let body: BodyStream = {
+            let pulled = pulled.clone();
+            Box::pin(futures::stream::iter(0..100).map(move |_| {
+                pulled.fetch_add(1, Ordering::SeqCst);
+                Ok(Bytes::from_static(&[b'x'; 16]))
+            }))
+        };
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/chunky/files")
+            .body(Body::Stream(body))
+            .unwrap();
+        let err = tokio::time::timeout(Duration::from_secs(5), svc.proxy_request(ctx, req))
+            .await
+            .unwrap()
+            .unwrap_err();
+        match err {
+            DomainError::PayloadTooLarge { detail, .. } => {
+                assert!(detail.contains("exceeds maximum of 32 bytes"), "{detail}");
+            }
+            other => panic!("expected PayloadTooLarge, got {other:?}"),
+        }
+        // Reading stopped at the first chunk past the limit.
+        assert_eq!(pulled.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn stalled_request_body_is_aborted_after_idle_timeout() {
+        // The upstream reads without answering, so only the idle timeout can
+        // end the request.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            drop(stream);
+        });
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_body_read_idle_timeout(Some(Duration::from_millis(100)));
+        let ctx = tenant_ctx();
+        create_limited_target(&cp, &ctx, "slowloris", port, 1024).await;
+
+        // One chunk, then the client goes quiet.
+        let body: BodyStream = Box::pin(
+            futures::stream::iter([Ok(Bytes::from_static(b"partial"))])
+                .chain(futures::stream::pending()),
+        );
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/slowloris/files")
+            .body(Body::Stream(body))
+            .unwrap();
+        let started = Instant::now();
+        let err = tokio::time::timeout(Duration::from_secs(5), svc.proxy_request(ctx, req))
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::ClientTimeout { .. }),
+            "got {err:?}"
+        );
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn slow_body_within_idle_timeout_reaches_upstream() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        // The upstream answers only once the whole chunked body has arrived.
+        let upstream_seen = tokio::spawn(async move {
+            use tokio::io::AsyncReadExt;
+
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut wire = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !wire.ends_with(b"0\r\n\r\n") {
+                let n = stream.read(&mut buf).await.unwrap();
+                assert!(n > 0, "connection closed before end of body");
+                wire.extend_from_slice(&buf[..n]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&wire).into_owned()
+        });
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_body_read_idle_timeout(Some(Duration::from_millis(500)));
+        let ctx = tenant_ctx();
+        create_limited_target(&cp, &ctx, "trickle", port, 1024).await;
+
+        // Every gap is well under the idle timeout, even though the body as
+        // a whole takes longer than it.
+        let body: BodyStream = Box::pin(futures::stream::iter(0..4).then(|_| async {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            Ok(Bytes::from_static(b"x"))
+        }));
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/trickle/files")
+            .body(Body::Stream(body))
+            .unwrap();
+        let resp = svc.proxy_request(ctx, req).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let wire = upstream_seen.await.unwrap();
+        assert_eq!(wire.matches("1\r\nx\r\n").count(), 4, "{wire}");
+    }
+
+    /// `result` label recorded for one upstream `404` with `error_status_codes`.
+    async fn not_found_result_label(error_status_codes: Option<&[u16]>) -> Option<String> {
+        use crate::domain::model::HttpMethod;
+        use opentelemetry::metrics::MeterProvider;
+        use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData};
+        use opentelemetry_sdk::metrics::{
+            InMemoryMetricExporter, PeriodicReader, SdkMeterProvider,
+        };
+
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let (cp, svc) = dp_with_test_cp();
+        let mut svc = svc.with_metrics(OagwMetrics::new(&provider.meter("oagw")));
+        if let Some(codes) = error_status_codes {
+            svc = svc.with_error_status_codes(codes);
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_raw_once(
+            listener,
+            b"HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".to_vec(),
+        ));
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "lookup", None, HttpMethod::Get, "/items", port).await;
+
+        let response = svc.proxy_request(ctx, get("/lookup/items")).await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+
+        provider.force_flush().unwrap();
+
+        let upstream_label = route.upstream_id.to_string();
+        for resource_metrics in &exporter.get_finished_metrics().unwrap() {
+            for scope_metrics in resource_metrics.scope_metrics() {
+                for metric in scope_metrics.metrics() {
+                    if metric.name() == crate::infra::metrics::PROXY_RESPONSES
+                        && let AggregatedMetrics::U64(MetricData::Sum(sum)) = metric.data()
+                    {
+                        for point in sum.data_points() {
+                            if has_label(point.attributes(), "upstream_id", &upstream_label)
+                                && has_label(point.attributes(), "status", "404")
+                            {
+                                return point
+                                    .attributes()
+                                    .find(|kv| kv.key.as_str() == "result")
+                                    .map(|kv| kv.value.to_string());
+                            }
                         }
                     }
-                    crate::domain::plugin::PluginError::AuthFailed(_)
-                    | crate::domain::plugin::PluginError::Internal(_) => {
-                        DomainError::AuthenticationFailed {
-                            detail: e.to_string(),
-                            instance: instance_uri.clone(),
+                }
+            }
+        }
+        None
+    }
+
+    #[tokio::test]
+    async fn not_found_counts_as_success_unless_configured_as_error() {
+        assert_eq!(
+            not_found_result_label(None).await.as_deref(),
+            Some("success")
+        );
+        assert_eq!(
+            not_found_result_label(Some(&[404, 500, 502, 503]))
+                .await
+                .as_deref(),
+            Some("error")
+        );
+    }
+
+    #[tokio::test]
+    async fn disallowed_method_is_rejected_with_allowed_list() {
+        use crate::domain::model::{HttpMethod, UpdateUpstreamRequest};
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        // Nothing listens on port 9: reaching the backend would fail
+        // differently.
+        let route = create_target(&cp, &ctx, "ro", None, HttpMethod::Delete, "/items", 9).await;
+        cp.update_upstream(
+            &ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                allowed_methods: Some(vec![HttpMethod::Get, HttpMethod::Post]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let req = http::Request::builder()
+            .method(http::Method::DELETE)
+            .uri("/ro/items")
+            .body(Body::Empty)
+            .unwrap();
+        match svc.proxy_request(ctx, req).await {
+            Err(DomainError::MethodNotAllowed { allowed, .. }) => {
+                assert_eq!(allowed, ["GET", "HEAD", "POST"]);
+            }
+            other => panic!("expected MethodNotAllowed, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn disallowed_method_from_denied_caller_is_forbidden() {
+        use crate::domain::model::{HttpMethod, UpdateUpstreamRequest};
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_authz_mode(AuthzMode::DenyAll);
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "ro", None, HttpMethod::Delete, "/items", 9).await;
+        cp.update_upstream(
+            &ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                allowed_methods: Some(vec![HttpMethod::Get]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        // A 405 would list the upstream's methods to a caller the PDP denies.
+        let req = http::Request::builder()
+            .method(http::Method::DELETE)
+            .uri("/ro/items")
+            .body(Body::Empty)
+            .unwrap();
+        let err = svc.proxy_request(ctx, req).await.unwrap_err();
+        assert!(matches!(err, DomainError::Forbidden { .. }), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn allowed_method_reaches_upstream() {
+        use crate::domain::model::{HttpMethod, UpdateUpstreamRequest};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream_seen = tokio::spawn(serve_once(listener));
+
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "ro", None, HttpMethod::Get, "/items", port).await;
+        cp.update_upstream(
+            &ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                allowed_methods: Some(vec![HttpMethod::Get]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let resp = svc.proxy_request(ctx, get("/ro/items")).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert!(
+            upstream_seen
+                .await
+                .unwrap()
+                .starts_with("GET /items HTTP/1.1\r\n")
+        );
+    }
+
+    #[test]
+    fn failover_on_gateway_failure_for_any_method() {
+        let mut resp = http::Response::builder()
+            .status(http::StatusCode::BAD_GATEWAY)
+            .body(Body::Empty)
+            .unwrap();
+        resp.extensions_mut().insert(ErrorSource::Gateway);
+        assert!(needs_failover(&Ok(resp), &http::Method::POST));
+    }
+
+    #[test]
+    fn failover_on_upstream_status_only_when_idempotent() {
+        let upstream_503 = || {
+            let mut resp = http::Response::builder()
+                .status(http::StatusCode::SERVICE_UNAVAILABLE)
+                .body(Body::Empty)
+                .unwrap();
+            resp.extensions_mut().insert(ErrorSource::Upstream);
+            Ok(resp)
+        };
+        assert!(needs_failover(&upstream_503(), &http::Method::GET));
+        assert!(!needs_failover(&upstream_503(), &http::Method::POST));
+
+        let ok = http::Response::builder()
+            .status(http::StatusCode::OK)
+            .body(Body::Empty)
+            .unwrap();
+        assert!(!needs_failover(&Ok(ok), &http::Method::GET));
+    }
+
+    #[tokio::test]
+    async fn rate_limit_headers_report_bucket_state() {
+        use crate::domain::model::{
+            BurstConfig, HttpMethod, RateLimitAlgorithm, RateLimitScope, RateLimitStrategy,
+            SustainedRate, UpdateRouteRequest, Window,
+        };
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_once(listener));
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_rate_limit_headers(RateLimitHeaderNames {
+            limit: "RateLimit-Limit".into(),
+            ..Default::default()
+        });
+        let ctx = tenant_ctx();
+        let route =
+            create_target(&cp, &ctx, "limited", None, HttpMethod::Get, "/items", port).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                rate_limit: Some(RateLimitConfig {
+                    sharing: Default::default(),
+                    algorithm: RateLimitAlgorithm::TokenBucket,
+                    sustained: SustainedRate {
+                        rate: 1,
+                        window: Window::Minute,
+                    },
+                    burst: Some(BurstConfig { capacity: 1 }),
+                    scope: RateLimitScope::Route,
+                    strategy: RateLimitStrategy::Reject,
+                    cost: 1,
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let resp = svc
+            .proxy_request(ctx.clone(), get("/limited/items"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(resp.headers().get("ratelimit-limit").unwrap(), "1");
+        assert_eq!(resp.headers().get("x-ratelimit-remaining").unwrap(), "0");
+        assert_eq!(resp.headers().get("x-ratelimit-reset").unwrap(), "60");
+
+        let err = svc
+            .proxy_request(ctx, get("/limited/items"))
+            .await
+            .unwrap_err();
+        let DomainError::RateLimitExceeded { headers, .. } = err else {
+            panic!("expected RateLimitExceeded, got {err:?}");
+        };
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(header("RateLimit-Limit"), Some("1"));
+        assert_eq!(header("X-RateLimit-Remaining"), Some("0"));
+        assert!(header("X-RateLimit-Reset").is_some_and(|v| v != "0"));
+    }
+
+    #[tokio::test]
+    async fn tenant_rate_limit_replaces_upstream_limit_for_that_tenant() {
+        use crate::domain::model::{
+            BurstConfig, HttpMethod, RateLimitAlgorithm, RateLimitScope, RateLimitStrategy,
+            SharingMode, SustainedRate, UpdateUpstreamRequest, Window,
+        };
+        use crate::domain::test_support::{MockTenantResolverClient, TestCpBuilder};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_forever(listener));
+
+        let (root, child) = (Uuid::new_v4(), Uuid::new_v4());
+        let (cp, svc) = dp_with_cp(
+            TestCpBuilder::new()
+                .with_tenant_resolver(MockTenantResolverClient::with_hierarchy(vec![root, child])),
+        );
+        let ctx_for = |tenant| {
+            SecurityContext::builder()
+                .subject_tenant_id(tenant)
+                .subject_id(Uuid::new_v4())
+                .build()
+                .unwrap()
+        };
+        let (root_ctx, child_ctx) = (ctx_for(root), ctx_for(child));
+        let limit = |rate| RateLimitConfig {
+            sharing: SharingMode::Inherit,
+            algorithm: RateLimitAlgorithm::TokenBucket,
+            sustained: SustainedRate {
+                rate,
+                window: Window::Minute,
+            },
+            burst: Some(BurstConfig { capacity: rate }),
+            scope: RateLimitScope::Tenant,
+            strategy: RateLimitStrategy::Reject,
+            cost: 1,
+        };
+
+        let route = create_target(
+            &cp,
+            &root_ctx,
+            "shared",
+            None,
+            HttpMethod::Get,
+            "/items",
+            port,
+        )
+        .await;
+        cp.update_upstream(
+            &root_ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                rate_limit: Some(limit(100)),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let resp = svc
+            .proxy_request(child_ctx.clone(), get("/shared/items"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        cp.set_tenant_rate_limit(&root_ctx, route.upstream_id, child, limit(1))
+            .await
+            .unwrap();
+
+        // The child starts a fresh bucket under its own limit of one request.
+        let resp = svc
+            .proxy_request(child_ctx.clone(), get("/shared/items"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let err = svc
+            .proxy_request(child_ctx, get("/shared/items"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::RateLimitExceeded { .. }),
+            "expected RateLimitExceeded, got {err:?}"
+        );
+
+        // The owner keeps the upstream's own limit.
+        for _ in 0..3 {
+            let resp = svc
+                .proxy_request(root_ctx.clone(), get("/shared/items"))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), http::StatusCode::OK);
+        }
+    }
+
+    #[tokio::test]
+    async fn hop_by_hop_headers_stripped_both_ways() {
+        use crate::domain::model::{
+            HeadersConfig, HttpMethod, RequestHeaderRules, UpdateUpstreamRequest,
+        };
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut head = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = stream.read(&mut buf).await.unwrap();
+                assert!(n > 0, "connection closed before request head");
+                head.extend_from_slice(&buf[..n]);
+            }
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close, x-conn-named\r\n\
+                      keep-alive: timeout=5\r\nproxy-authenticate: Basic\r\nx-conn-named: 1\r\n\
+                      x-trace-hop: 1\r\nx-end-to-end: kept\r\n\r\nok",
+                )
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&head).to_lowercase()
+        });
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_extra_hop_by_hop_headers(&["X-Trace-Hop".into()]);
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "hops", None, HttpMethod::Get, "/items", port).await;
+        cp.update_upstream(
+            &ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                headers: Some(HeadersConfig {
+                    request: Some(RequestHeaderRules {
+                        passthrough: PassthroughMode::All,
+                        ..Default::default()
+                    }),
+                    response: None,
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let req = http::Request::builder()
+            .method(http::Method::GET)
+            .uri("/hops/items")
+            .header("connection", "x-conn-named")
+            .header("x-conn-named", "1")
+            .header("keep-alive", "timeout=5")
+            .header("te", "gzip")
+            .header("trailer", "x-checksum")
+            .header("x-trace-hop", "1")
+            .header("x-end-to-end", "kept")
+            .body(Body::Empty)
+            .unwrap();
+        let resp = svc.proxy_request(ctx, req).await.unwrap();
+
+        let head = upstream.await.unwrap();
+        for name in [
+            "x-conn-named",
+            "keep-alive",
+            "te:",
+            "trailer",
+            "x-trace-hop",
+        ] {
+            assert!(!head.contains(name), "{name} forwarded upstream:\n{head}");
+        }
+        assert!(head.contains("x-end-to-end: kept"), "{head}");
+
+        let headers = resp.headers();
+        for name in [
+            "connection",
+            "keep-alive",
+            "proxy-authenticate",
+            "x-conn-named",
+            "x-trace-hop",
+        ] {
+            assert!(!headers.contains_key(name), "{name} returned to client");
+        }
+        assert_eq!(headers.get("x-end-to-end").unwrap(), "kept");
+    }
+
+    #[tokio::test]
+    async fn inbound_headers_stripped_globally_and_per_route() {
+        use crate::domain::model::{
+            AuthConfig, HeadersConfig, HttpMethod, RequestHeaderRules, SharingMode,
+            UpdateRouteRequest, UpdateUpstreamRequest,
+        };
+        use crate::domain::test_support::{APIKEY_AUTH_PLUGIN_ID, TestCpBuilder};
+
+        let (cp, svc) = dp_with_cp(
+            TestCpBuilder::new().with_credentials(vec![("key".into(), "sk-upstream".into())]),
+        );
+        let svc = svc.with_strip_inbound_headers(&["X-Debug".into(), "X-Api-Key".into()]);
+        let ctx = tenant_ctx();
+        let auth = AuthConfig {
+            plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+            sharing: SharingMode::Private,
+            config: Some(HashMap::from([
+                ("header".into(), "x-api-key".into()),
+                ("secret_ref".into(), "cred://key".into()),
+            ])),
+        };
+        let mut listeners = Vec::new();
+        for alias in ["strict", "plain"] {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let route = create_target(
+                &cp,
+                &ctx,
+                alias,
+                Some(auth.clone()),
+                HttpMethod::Get,
+                "/items",
+                port,
+            )
+            .await;
+            cp.update_upstream(
+                &ctx,
+                route.upstream_id,
+                UpdateUpstreamRequest {
+                    headers: Some(HeadersConfig {
+                        request: Some(RequestHeaderRules {
+                            passthrough: PassthroughMode::All,
+                            ..Default::default()
+                        }),
+                        response: None,
+                    }),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+            if alias == "strict" {
+                cp.update_route(
+                    &ctx,
+                    route.id,
+                    UpdateRouteRequest {
+                        strip_inbound_headers: Some(vec!["X-Internal-Session".into()]),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+            }
+            listeners.push(listener);
+        }
+
+        let mut heads = Vec::new();
+        for (alias, listener) in ["strict", "plain"].into_iter().zip(listeners) {
+            let upstream = tokio::spawn(serve_once(listener));
+            let req = http::Request::builder()
+                .method(http::Method::GET)
+                .uri(format!("/{alias}/items"))
+                .header("x-debug", "1")
+                .header("x-internal-session", "s3cr3t")
+                .header("x-api-key", "client-key")
+                .body(Body::Empty)
+                .unwrap();
+            svc.proxy_request(ctx.clone(), req).await.unwrap();
+            heads.push(upstream.await.unwrap().to_ascii_lowercase());
+        }
+
+        for head in &heads {
+            assert!(!head.contains("x-debug"), "{head}");
+            assert!(!head.contains("client-key"), "{head}");
+            // The auth plugin sets a globally stripped header after stripping.
+            assert!(head.contains("\r\nx-api-key: sk-upstream\r\n"), "{head}");
+        }
+        assert!(!heads[0].contains("x-internal-session"), "{}", heads[0]);
+        assert!(
+            heads[1].contains("\r\nx-internal-session: s3cr3t\r\n"),
+            "{}",
+            heads[1]
+        );
+    }
+
+    #[tokio::test]
+    async fn upstream_circuit_breaker_override_trips_sooner_than_default() {
+        use crate::domain::model::{CircuitBreakerConfig, HttpMethod, UpdateUpstreamRequest};
+
+        // Nothing listens on these ports: every attempt is a connection failure.
+        let mut ports = Vec::new();
+        for _ in 0..2 {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            ports.push(listener.local_addr().unwrap().port());
+        }
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_circuit_breaker(3, 30, 1);
+        let ctx = tenant_ctx();
+        create_target(
+            &cp,
+            &ctx,
+            "cb-default",
+            None,
+            HttpMethod::Get,
+            "/x",
+            ports[0],
+        )
+        .await;
+        let strict = create_target(
+            &cp,
+            &ctx,
+            "cb-strict",
+            None,
+            HttpMethod::Get,
+            "/x",
+            ports[1],
+        )
+        .await;
+        cp.update_upstream(
+            &ctx,
+            strict.upstream_id,
+            UpdateUpstreamRequest {
+                circuit_breaker: Some(CircuitBreakerConfig {
+                    failure_threshold: Some(1),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let mut opened_after = Vec::new();
+        for alias in ["cb-default", "cb-strict"] {
+            let mut attempts = 0;
+            loop {
+                attempts += 1;
+                let result = svc
+                    .proxy_request(ctx.clone(), get(&format!("/{alias}/x")))
+                    .await;
+                if let Err(DomainError::CircuitBreakerOpen {
+                    retry_after_secs, ..
+                }) = result
+                {
+                    assert!(retry_after_secs > 0 && retry_after_secs <= 30);
+                    break;
+                }
+                assert!(attempts <= 5, "{alias}: circuit never opened");
+            }
+            opened_after.push(attempts - 1);
+        }
+        assert_eq!(opened_after, vec![3, 1]);
+    }
+
+    #[tokio::test]
+    async fn reset_circuit_breaker_lets_traffic_through_immediately() {
+        use crate::domain::circuit_breaker::CircuitStatus;
+        use crate::domain::model::HttpMethod;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_circuit_breaker(1, 30, 1);
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "cb-reset", None, HttpMethod::Get, "/x", port).await;
+
+        // Nothing accepts yet: the connection failure opens the circuit.
+        drop(listener);
+        let _ = svc.proxy_request(ctx.clone(), get("/cb-reset/x")).await;
+        assert!(matches!(
+            svc.runtime_state(route.upstream_id).circuit,
+            CircuitStatus::Open { .. }
+        ));
+        let err = svc
+            .proxy_request(ctx.clone(), get("/cb-reset/x"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::CircuitBreakerOpen { .. }));
+
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+            .await
+            .unwrap();
+        tokio::spawn(serve_forever(listener));
+        svc.reset_circuit_breaker(route.upstream_id);
+        assert_eq!(
+            svc.runtime_state(route.upstream_id).circuit,
+            CircuitStatus::Closed { failures: 0 }
+        );
+        let resp = svc.proxy_request(ctx, get("/cb-reset/x")).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn reset_rate_limit_restores_bucket_capacity() {
+        use crate::domain::model::{
+            BurstConfig, HttpMethod, RateLimitAlgorithm, RateLimitScope, RateLimitStrategy,
+            SustainedRate, UpdateUpstreamRequest, Window,
+        };
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_forever(listener));
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "rl-reset", None, HttpMethod::Get, "/x", port).await;
+        cp.update_upstream(
+            &ctx,
+            route.upstream_id,
+            UpdateUpstreamRequest {
+                rate_limit: Some(RateLimitConfig {
+                    sharing: Default::default(),
+                    algorithm: RateLimitAlgorithm::TokenBucket,
+                    sustained: SustainedRate {
+                        rate: 1,
+                        window: Window::Minute,
+                    },
+                    burst: Some(BurstConfig { capacity: 1 }),
+                    scope: RateLimitScope::Tenant,
+                    strategy: RateLimitStrategy::Reject,
+                    cost: 1,
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let resp = svc
+            .proxy_request(ctx.clone(), get("/rl-reset/x"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let err = svc
+            .proxy_request(ctx.clone(), get("/rl-reset/x"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::RateLimitExceeded { .. }));
+        let state = svc.runtime_state(route.upstream_id);
+        assert_eq!(state.rate_limits.len(), 1);
+        assert_eq!(
+            state.rate_limits[0].0,
+            format!("upstream:{}", route.upstream_id)
+        );
+        assert_eq!(state.rate_limits[0].1.remaining, 0);
+
+        svc.reset_rate_limit(ctx.subject_tenant_id(), route.upstream_id);
+        assert!(svc.runtime_state(route.upstream_id).rate_limits.is_empty());
+        let resp = svc.proxy_request(ctx, get("/rl-reset/x")).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+    }
+
+    /// A `200 OK` delimited by connection close: no `Content-Length` and no
+    /// chunked encoding.
+    fn eof_delimited_response(body: &[u8]) -> Vec<u8> {
+        let mut raw =
+            b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nconnection: close\r\n\r\n"
+                .to_vec();
+        raw.extend_from_slice(body);
+        raw
+    }
+
+    #[tokio::test]
+    async fn eof_delimited_response_within_limit_is_buffered() {
+        use crate::domain::model::HttpMethod;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_raw_once(
+            listener,
+            eof_delimited_response(br#"{"a":1}"#),
+        ));
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_unframed_responses(16, false);
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "eof-ok", None, HttpMethod::Get, "/x", port).await;
+
+        let resp = svc.proxy_request(ctx, get("/eof-ok/x")).await.unwrap();
+        assert_eq!(resp.headers()[http::header::CONTENT_LENGTH], "7");
+        match resp.into_body() {
+            Body::Bytes(body) => assert_eq!(&body[..], br#"{"a":1}"#),
+            _ => panic!("expected a buffered body"),
+        }
+    }
+
+    #[tokio::test]
+    async fn eof_delimited_response_over_limit_is_rejected() {
+        use crate::domain::model::HttpMethod;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_raw_once(
+            listener,
+            eof_delimited_response(&[b'x'; 64]),
+        ));
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_unframed_responses(16, false);
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "eof-big", None, HttpMethod::Get, "/x", port).await;
+
+        let err = svc.proxy_request(ctx, get("/eof-big/x")).await.unwrap_err();
+        match err {
+            DomainError::DownstreamError { detail, .. } => {
+                assert!(detail.contains("exceeds maximum of 16 bytes"), "{detail}");
+            }
+            other => panic!("expected DownstreamError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn eof_delimited_response_streams_when_configured() {
+        use crate::domain::model::HttpMethod;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_raw_once(
+            listener,
+            eof_delimited_response(&[b'x'; 64]),
+        ));
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_unframed_responses(16, true);
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "eof-stream", None, HttpMethod::Get, "/x", port).await;
+
+        let resp = svc.proxy_request(ctx, get("/eof-stream/x")).await.unwrap();
+        assert!(!resp.headers().contains_key(http::header::CONTENT_LENGTH));
+        assert!(matches!(resp.body(), Body::Stream(_)));
+        let body = resp.into_body().into_bytes().await.unwrap();
+        assert_eq!(body.len(), 64);
+    }
+
+    /// Upstream that sends response headers after `head_delay`, then a chunk
+    /// every 50ms for up to five seconds.
+    async fn serve_slow_stream(listener: tokio::net::TcpListener, head_delay: Duration) {
+        use tokio::io::AsyncReadExt;
+
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut head = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            match stream.read(&mut buf).await {
+                Ok(0) | Err(_) => return,
+                Ok(n) => head.extend_from_slice(&buf[..n]),
+            }
+        }
+        tokio::time::sleep(head_delay).await;
+        let head =
+            b"HTTP/1.1 200 OK\r\ncontent-type: text/plain\r\ntransfer-encoding: chunked\r\n\r\n";
+        if stream.write_all(head).await.is_err() {
+            return;
+        }
+        for _ in 0..100 {
+            if stream.write_all(b"5\r\ntick\n\r\n").await.is_err() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        let _ = stream.write_all(b"0\r\n\r\n").await;
+    }
+
+    async fn sla_target(
+        cp: &Arc<dyn ControlPlaneService>,
+        ctx: &SecurityContext,
+        alias: &str,
+        port: u16,
+    ) {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+
+        let route = create_target(cp, ctx, alias, None, HttpMethod::Get, "/feed", port).await;
+        cp.update_route(
+            ctx,
+            route.id,
+            UpdateRouteRequest {
+                max_total_duration_ms: Some(300),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn max_total_duration_aborts_slow_stream_mid_body() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_slow_stream(listener, Duration::ZERO));
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        sla_target(&cp, &ctx, "sla-stream", port).await;
+
+        let started = Instant::now();
+        let resp = svc
+            .proxy_request(ctx, get("/sla-stream/feed"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        let mut body = resp.into_body().into_stream();
+        let mut received = Vec::new();
+        let aborted = loop {
+            match tokio::time::timeout(Duration::from_secs(3), body.next()).await {
+                Ok(Some(Ok(chunk))) => received.extend_from_slice(&chunk),
+                Ok(Some(Err(e))) => break e,
+                Ok(None) => panic!("stream completed despite the SLA"),
+                Err(_) => panic!("stream was not aborted at the SLA"),
+            }
+        };
+        let elapsed = started.elapsed();
+        assert!(
+            aborted.to_string().contains("maximum duration"),
+            "{aborted}"
+        );
+        assert!(received.starts_with(b"tick\n"), "some chunks pass first");
+        assert!(
+            elapsed >= Duration::from_millis(300) && elapsed < Duration::from_secs(2),
+            "aborted after {elapsed:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn max_total_duration_returns_504_before_response_headers() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_slow_stream(listener, Duration::from_secs(5)));
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        sla_target(&cp, &ctx, "sla-head", port).await;
+
+        let started = Instant::now();
+        let err = svc
+            .proxy_request(ctx, get("/sla-head/feed"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::RequestTimeout { .. }),
+            "got {err:?}"
+        );
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    /// Answer every connection with a body counting the calls so far and
+    /// caching headers for the requested path: `private` for `/private`,
+    /// none but `max-age` for `/plain`, `Vary: accept` for `/vary`, and
+    /// `public` otherwise.
+    fn serve_cacheable(listener: tokio::net::TcpListener) -> Arc<std::sync::atomic::AtomicUsize> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::AsyncReadExt;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let upstream_calls = calls.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let n = upstream_calls.fetch_add(1, Ordering::SeqCst) + 1;
+                tokio::spawn(async move {
+                    let mut head = Vec::new();
+                    let mut buf = [0u8; 1024];
+                    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match stream.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(k) => head.extend_from_slice(&buf[..k]),
                         }
                     }
-                })?;
-            outbound_headers = HeaderMap::new();
-            for (k, v) in &auth_ctx.headers {
-                if let (Ok(name), Ok(val)) = (
-                    HeaderName::from_bytes(k.as_bytes()),
-                    HeaderValue::from_str(v),
-                ) {
-                    outbound_headers.insert(name, val);
-                }
+                    let (cache_control, vary) = if head.starts_with(b"GET /private") {
+                        ("private, max-age=60", "")
+                    } else if head.starts_with(b"GET /plain") {
+                        ("max-age=60", "")
+                    } else if head.starts_with(b"GET /vary") {
+                        ("public, max-age=60", "vary: accept\r\n")
+                    } else {
+                        ("public, max-age=60", "")
+                    };
+                    let body = format!("call-{n}");
+                    let _ = stream
+                        .write_all(
+                            format!(
+                                "HTTP/1.1 200 OK\r\ncache-control: {cache_control}\r\n{vary}content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                                body.len()
+                            )
+                            .as_bytes(),
+                        )
+                        .await;
+                });
             }
-        }
+        });
+        calls
+    }
 
-        // 5. Apply header rules + set Host.
-        if let Some(ref hc) = upstream.headers
-            && let Some(ref rules) = hc.request
-        {
-            headers::apply_header_rules(&mut outbound_headers, rules);
-        }
+    /// [`create_target`] for a `GET` route that caches responses.
+    async fn create_caching_target(
+        cp: &Arc<dyn ControlPlaneService>,
+        ctx: &SecurityContext,
+        alias: &str,
+        path: &str,
+        port: u16,
+    ) -> crate::domain::model::Route {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
 
-        // 5a. Endpoint selection (D1 — two-tier).
-        let endpoint = self
-            .select_endpoint(&upstream, &req_headers, &instance_uri)
-            .await?;
+        let route = create_target(cp, ctx, alias, None, HttpMethod::Get, path, port).await;
+        cp.update_route(
+            ctx,
+            route.id,
+            UpdateRouteRequest {
+                cache_responses: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+    }
 
-        // 5b. Enforce HTTPS-only constraint (cpt-cf-oagw-constraint-https-only).
-        if !self.allow_http_upstream && matches!(endpoint.scheme, Scheme::Http) {
-            return Err(DomainError::Validation {
-                detail: "upstream endpoint uses HTTP; only HTTPS endpoints are permitted".into(),
-                instance: instance_uri,
-            });
-        }
+    /// Proxy one request for a 4 KiB `text/plain` upstream response through
+    /// a DP that gzips responses of 1 KiB or more.
+    async fn fetch_compressible(accept_encoding: Option<&str>) -> http::Response<Body> {
+        use crate::domain::model::HttpMethod;
 
-        headers::set_host_header(&mut outbound_headers, &endpoint.host, endpoint.port);
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_response_compression(true, 1024);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_raw_once(
+            listener,
+            raw_response("text/plain", &[b'a'; 4096]),
+        ));
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "large", None, HttpMethod::Get, "/text", port).await;
 
-        // 6. Check rate limit (upstream then route).
-        if let Some(ref rl) = upstream.rate_limit {
-            let key = format!("upstream:{}", upstream.id);
-            self.rate_limiter.try_consume(&key, rl, &instance_uri)?;
-        }
-        if let Some(ref rl) = route.rate_limit {
-            let key = format!("route:{}", route.id);
-            self.rate_limiter.try_consume(&key, rl, &instance_uri)?;
+        let mut req = get("/large/text");
+        if let Some(value) = accept_encoding {
+            req.headers_mut().insert(
+                http::header::ACCEPT_ENCODING,
+                HeaderValue::from_str(value).unwrap(),
+            );
         }
+        svc.proxy_request(ctx, req).await.unwrap()
+    }
 
-        // 7. Build URL.
-        // path_suffix is the full path from the proxy URL; strip the route prefix
-        // so we get: endpoint + route_path + remaining_suffix.
-        let route_path = route
-            .match_rules
-            .http
-            .as_ref()
-            .map_or("/", |h| h.path.as_str());
-        let remaining_suffix = path_suffix.strip_prefix(route_path).unwrap_or("");
-        let url = request_builder::build_upstream_url(
-            &endpoint,
-            route_path,
-            remaining_suffix,
-            &query_params,
-        )?;
+    #[tokio::test]
+    async fn large_response_is_gzipped_when_client_accepts_it() {
+        use std::io::Read;
+
+        let response = fetch_compressible(Some("gzip, br")).await;
+
+        assert_eq!(response.headers()[http::header::CONTENT_ENCODING], "gzip");
+        let declared_len = response.headers()[http::header::CONTENT_LENGTH].clone();
+        let body = response.into_body().into_bytes().await.unwrap();
+        assert_eq!(declared_len, body.len().to_string().as_str());
+        assert!(body.len() < 4096);
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(&body[..])
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, vec![b'a'; 4096]);
+    }
+
+    #[tokio::test]
+    async fn large_response_is_left_alone_without_accept_encoding() {
+        let response = fetch_compressible(None).await;
+
+        assert!(
+            !response
+                .headers()
+                .contains_key(http::header::CONTENT_ENCODING)
+        );
+        assert_eq!(response.headers()[http::header::CONTENT_LENGTH], "4096");
+        let body = response.into_body().into_bytes().await.unwrap();
+        assert_eq!(body, vec![b'a'; 4096]);
+    }
+
+    #[tokio::test]
+    async fn private_response_cache_scopes_entries_by_subject() {
+        use std::sync::atomic::Ordering;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let calls = serve_cacheable(listener);
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_private_response_cache(true);
+        let alice = tenant_ctx();
+        let bob = SecurityContext::builder()
+            .subject_tenant_id(alice.subject_tenant_id())
+            .subject_id(Uuid::new_v4())
+            .build()
+            .unwrap();
+        create_caching_target(&cp, &alice, "mine", "/private", port).await;
+        create_caching_target(&cp, &alice, "shared", "/public", port).await;
 
-        // 7b. Inject internal context headers for PingoraProxy (D9).
-        let scheme_str = match endpoint.scheme {
-            Scheme::Http => "http",
-            Scheme::Https => "https",
-            Scheme::Wss => "wss",
-            Scheme::Wt => "wt",
-            Scheme::Grpc => "grpc",
+        let body = |ctx: &SecurityContext, uri: &'static str| {
+            let ctx = ctx.clone();
+            let svc = &svc;
+            async move {
+                let resp = svc.proxy_request(ctx, get(uri)).await.unwrap();
+                resp.into_body().into_bytes().await.unwrap()
+            }
         };
-        if let Ok(v) = HeaderValue::from_str(&upstream.id.to_string()) {
-            outbound_headers.insert(H_UPSTREAM_ID, v);
-        }
-        if let Ok(v) = HeaderValue::from_str(&endpoint.host) {
-            outbound_headers.insert(H_ENDPOINT_HOST, v);
-        }
-        if let Ok(v) = HeaderValue::from_str(&endpoint.port.to_string()) {
-            outbound_headers.insert(H_ENDPOINT_PORT, v);
-        }
-        outbound_headers.insert(H_ENDPOINT_SCHEME, HeaderValue::from_static(scheme_str));
-        if let Ok(v) = HeaderValue::from_str(&instance_uri) {
-            outbound_headers.insert(H_INSTANCE_URI, v);
-        }
 
-        // 8. Bridge request into Pingora via in-memory DuplexStream.
-        let (client_io, server_io) = tokio::io::duplex(65_536);
+        // Private: each subject gets, and keeps, their own entry.
+        assert_eq!(body(&alice, "/mine/private").await, "call-1");
+        assert_eq!(body(&bob, "/mine/private").await, "call-2");
+        assert_eq!(body(&alice, "/mine/private").await, "call-1");
+        assert_eq!(body(&bob, "/mine/private").await, "call-2");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
 
-        // Create Pingora H1 session from the server side of the DuplexStream.
-        // Pingora implements all IO traits for DuplexStream (in ext_io_impl).
-        let session = pingora_core::protocols::http::ServerSession::new_http1(Box::new(server_io));
+        // Public: one entry serves every subject of the tenant.
+        assert_eq!(body(&alice, "/shared/public").await, "call-3");
+        assert_eq!(body(&bob, "/shared/public").await, "call-3");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
 
-        // Spawn Pingora proxy processing in background.
-        let proxy = self.proxy.clone();
-        let shutdown = self.shutdown_rx.clone();
-        tokio::spawn(async move {
-            proxy.process_new_http(session, &shutdown).await;
-        });
+    #[tokio::test]
+    async fn response_cache_lookups_are_recorded() {
+        use crate::domain::test_support::RecordingMetricsSink;
+        use crate::infra::metrics::CACHE_LOOKUPS;
 
-        // Write the request and read the response from the client side.
-        let timeout = self.request_timeout;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream = tokio::spawn(serve_raw_once(
+            listener,
+            b"HTTP/1.1 200 OK\r\ncache-control: max-age=60\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok".to_vec(),
+        ));
+        let sink = Arc::new(RecordingMetricsSink::default());
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_metrics(OagwMetrics::with_sink(sink.clone()));
+        let ctx = tenant_ctx();
+        create_caching_target(&cp, &ctx, "docs", "/page", port).await;
 
-        if let Some(mut body_stream) = body_stream {
-            // Streaming path: write headers, then forward body chunks concurrently.
-            let (client_read, mut client_write) = tokio::io::split(client_io);
+        for _ in 0..3 {
+            let resp = svc
+                .proxy_request(ctx.clone(), get("/docs/page"))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), http::StatusCode::OK);
+        }
+        upstream.await.unwrap();
 
-            let header_bytes =
-                session_bridge::serialize_request_wire(&method, &url, &outbound_headers, None);
-            client_write.write_all(&header_bytes).await.map_err(|e| {
-                DomainError::DownstreamError {
-                    detail: format!("failed to write to proxy bridge: {e}"),
-                    instance: instance_uri.clone(),
-                }
-            })?;
+        let response = ("cache", "response");
+        assert_eq!(
+            sink.values(CACHE_LOOKUPS, &[response, ("result", "miss")]),
+            [1.0]
+        );
+        assert_eq!(
+            sink.values(CACHE_LOOKUPS, &[response, ("result", "hit")]),
+            [1.0, 1.0]
+        );
+    }
 
-            // Spawn task to forward body stream chunks, then shutdown.
-            // Enforce max_body_size on the streaming path: signal 413 if exceeded.
-            let (limit_tx, limit_rx) = tokio::sync::oneshot::channel::<usize>();
-            let body_instance_uri = instance_uri.clone();
+    #[tokio::test]
+    async fn not_found_is_cached_for_route_negative_ttl() {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let calls = Arc::new(AtomicUsize::new(0));
+        {
+            let calls = calls.clone();
             tokio::spawn(async move {
-                let mut total_bytes: usize = 0;
-                let mut exceeded = false;
-                while let Some(chunk) = body_stream.next().await {
-                    match chunk {
-                        Ok(bytes) => {
-                            total_bytes = total_bytes.saturating_add(bytes.len());
-                            if total_bytes > max_body {
-                                tracing::warn!(
-                                    total_bytes,
-                                    max_body,
-                                    "streaming body exceeded max size, aborting"
-                                );
-                                exceeded = true;
-                                break;
-                            }
-                            if let Err(e) = client_write.write_all(&bytes).await {
-                                tracing::debug!(error = %e, "body stream write error");
-                                break;
+                loop {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    tokio::spawn(async move {
+                        let mut head = Vec::new();
+                        let mut buf = [0u8; 1024];
+                        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                            match stream.read(&mut buf).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(k) => head.extend_from_slice(&buf[..k]),
                             }
                         }
-                        Err(e) => {
-                            tracing::debug!(error = %e, "body stream chunk error");
-                            break;
-                        }
-                    }
-                }
-                if exceeded {
-                    let _ = limit_tx.send(total_bytes);
+                        let _ = stream
+                            .write_all(b"HTTP/1.1 404 Not Found\r\ncontent-length: 7\r\nconnection: close\r\n\r\nmissing")
+                            .await;
+                    });
                 }
-                let _ = client_write.shutdown().await;
             });
+        }
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "docs", None, HttpMethod::Get, "/gone", port).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                negative_cache_ttl_secs: Some(1),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
 
-            // 9. Parse response from the read half, but short-circuit to 413
-            //    if the body-forwarding task signals a limit breach.
-            //
-            // TODO(hardening): a fast upstream can respond before the body-forwarder
-            // detects the limit breach, causing the client to see 200 instead of 413.
-            // Fix: wrap the write half in a LimitedAsyncWrite that returns io::Error
-            // at the byte limit, so Pingora aborts the exchange before responding.
-            let resp_future =
-                tokio::time::timeout(timeout, session_bridge::parse_response_stream(client_read));
-            tokio::select! {
-                biased;
-                Ok(total) = limit_rx => {
-                    return Err(DomainError::PayloadTooLarge {
-                        detail: format!(
-                            "streaming request body of {total} bytes exceeds maximum of {max_body} bytes"
-                        ),
-                        instance: body_instance_uri,
-                    });
-                }
-                result = resp_future => {
-                    let (status, resp_headers, resp_body_stream) = result
-                        .map_err(|_| DomainError::RequestTimeout {
-                            detail: format!("request to {url} timed out after {timeout:?}"),
-                            instance: instance_uri.clone(),
-                        })?
-                        .map_err(|e| DomainError::DownstreamError {
-                            detail: format!("proxy bridge error: {e}"),
-                            instance: instance_uri.clone(),
-                        })?;
-                    Ok(build_proxy_response(status, resp_headers, resp_body_stream, instance_uri)?)
-                }
-            }
-        } else {
-            // Buffered path: write full request, shutdown write side, then read response.
-            let wire = session_bridge::serialize_request_wire(
-                &method,
-                &url,
-                &outbound_headers,
-                Some(&body_bytes),
-            );
-            let mut client_io = client_io;
-            client_io
-                .write_all(&wire)
+        for _ in 0..2 {
+            let resp = svc
+                .proxy_request(ctx.clone(), get("/docs/gone"))
                 .await
-                .map_err(|e| DomainError::DownstreamError {
-                    detail: format!("failed to write to proxy bridge: {e}"),
-                    instance: instance_uri.clone(),
-                })?;
-            // Do NOT shutdown the write side — Pingora uses Content-Length to
-            // determine the request boundary, and an early write-close is
-            // misinterpreted as "downstream dropped the connection".
+                .unwrap();
+            assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
 
-            // 9. Parse response.
-            let (status, resp_headers, resp_body_stream) =
-                tokio::time::timeout(timeout, session_bridge::parse_response_stream(client_io))
-                    .await
-                    .map_err(|_| DomainError::RequestTimeout {
-                        detail: format!("request to {url} timed out after {timeout:?}"),
-                        instance: instance_uri.clone(),
-                    })?
-                    .map_err(|e| DomainError::DownstreamError {
-                        detail: format!("proxy bridge error: {e}"),
-                        instance: instance_uri.clone(),
-                    })?;
+        // Once the TTL passes the upstream is asked again, so a resource
+        // created meanwhile becomes visible.
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        let resp = svc.proxy_request(ctx, get("/docs/gone")).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
 
-            Ok(build_proxy_response(
-                status,
-                resp_headers,
-                resp_body_stream,
-                instance_uri,
-            )?)
+    #[tokio::test]
+    async fn response_header_allowlist_drops_unlisted_headers() {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_raw_once(
+            listener,
+            b"HTTP/1.1 200 OK\r\nx-keep: yes\r\nx-drop: no\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok"
+                .to_vec(),
+        ));
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "strict", None, HttpMethod::Get, "/items", port).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                response_header_allowlist: Some(vec!["X-Keep".into()]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let resp = svc.proxy_request(ctx, get("/strict/items")).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(resp.headers()["x-keep"], "yes");
+        assert!(resp.headers().get("x-drop").is_none());
+        assert_eq!(resp.headers()[http::header::CONTENT_LENGTH], "2");
+    }
+
+    /// Upstream answering every request with `max-age=1` and an `ETag` of
+    /// its call number. With `honor_conditions`, a request whose
+    /// `If-None-Match` carries the previous `ETag` gets a 304. Returns the
+    /// lowercased heads of the requests received.
+    fn serve_revalidating(
+        listener: tokio::net::TcpListener,
+        honor_conditions: bool,
+    ) -> Arc<std::sync::Mutex<Vec<String>>> {
+        use tokio::io::AsyncReadExt;
+
+        let heads = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = heads.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut head = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(k) => head.extend_from_slice(&buf[..k]),
+                    }
+                }
+                let head = String::from_utf8_lossy(&head).to_ascii_lowercase();
+                let n = {
+                    let mut seen = seen.lock().unwrap();
+                    seen.push(head.clone());
+                    seen.len()
+                };
+                let previous = format!("if-none-match: \"v{}\"", n - 1);
+                let response = if honor_conditions && head.lines().any(|l| l == previous) {
+                    format!(
+                        "HTTP/1.1 304 Not Modified\r\ncache-control: max-age=1\r\netag: \"v{}\"\r\nconnection: close\r\n\r\n",
+                        n - 1
+                    )
+                } else {
+                    let body = format!("call-{n}");
+                    format!(
+                        "HTTP/1.1 200 OK\r\ncache-control: max-age=1\r\netag: \"v{n}\"\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                };
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        heads
+    }
+
+    /// Fetch `/docs/page` once, once more after the entry expired, and again
+    /// right away; returns the three bodies.
+    async fn fetch_across_expiry(port: u16) -> Vec<Bytes> {
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        create_caching_target(&cp, &ctx, "docs", "/page", port).await;
+        let mut bodies = Vec::new();
+        for pause in [0, 1100, 0] {
+            tokio::time::sleep(Duration::from_millis(pause)).await;
+            let resp = svc
+                .proxy_request(ctx.clone(), get("/docs/page"))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), http::StatusCode::OK);
+            bodies.push(resp.into_body().into_bytes().await.unwrap());
         }
+        bodies
     }
 
-    fn remove_rate_limit_key(&self, key: &str) {
-        self.rate_limiter.remove_key(key);
+    #[tokio::test]
+    async fn expired_entry_is_revalidated_and_304_serves_stored_body() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let heads = serve_revalidating(listener, true);
+
+        let bodies = fetch_across_expiry(port).await;
+
+        assert_eq!(bodies, ["call-1", "call-1", "call-1"]);
+        let heads = heads.lock().unwrap();
+        // The third fetch is a hit on the entry the 304 refreshed.
+        assert_eq!(heads.len(), 2);
+        assert!(!heads[0].contains("if-none-match"), "{}", heads[0]);
+        assert!(
+            heads[1].lines().any(|l| l == r#"if-none-match: "v1""#),
+            "{}",
+            heads[1]
+        );
     }
-}
 
-/// Build the final proxy response: extract error source, sanitize headers,
-/// assemble the `http::Response<Body>`.
-fn build_proxy_response(
-    status: http::StatusCode,
-    mut resp_headers: HeaderMap,
-    body_stream: BodyStream,
-    instance_uri: String,
-) -> Result<http::Response<Body>, DomainError> {
-    let error_source = headers::extract_error_source(&resp_headers);
-    headers::sanitize_response_headers(&mut resp_headers);
+    #[tokio::test]
+    async fn revalidation_answered_with_200_refreshes_entry() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let heads = serve_revalidating(listener, false);
 
-    let mut resp = http::Response::builder()
-        .status(status)
-        .body(Body::Stream(body_stream))
-        .map_err(|e| DomainError::DownstreamError {
-            detail: format!("failed to build response: {e}"),
-            instance: instance_uri,
-        })?;
-    *resp.headers_mut() = resp_headers;
-    resp.extensions_mut().insert(error_source);
-    Ok(resp)
-}
+        let bodies = fetch_across_expiry(port).await;
 
-/// Normalize a URL path: collapse consecutive slashes and resolve `.`/`..` segments.
-/// Segments that would escape above the root are discarded.
-fn normalize_path(path: &str) -> String {
-    let mut segments: Vec<&str> = Vec::new();
-    for seg in path.split('/') {
-        match seg {
-            "" | "." => {}
-            ".." => {
-                segments.pop();
-            }
-            s => segments.push(s),
-        }
+        assert_eq!(bodies, ["call-1", "call-2", "call-2"]);
+        let heads = heads.lock().unwrap();
+        assert_eq!(heads.len(), 2);
+        assert!(
+            heads[1].lines().any(|l| l == r#"if-none-match: "v1""#),
+            "{}",
+            heads[1]
+        );
     }
-    let mut result = String::with_capacity(path.len());
-    if path.starts_with('/') {
-        result.push('/');
+
+    fn head(uri: &str) -> http::Request<Body> {
+        http::Request::builder()
+            .method(http::Method::HEAD)
+            .uri(uri)
+            .body(Body::Empty)
+            .unwrap()
     }
-    result.push_str(&segments.join("/"));
-    result
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::domain::model::{Endpoint, Scheme, Server, Upstream};
-    use crate::domain::services::EndpointSelector;
-    use std::sync::atomic::{AtomicUsize, Ordering};
-    use uuid::Uuid;
+    #[tokio::test]
+    async fn head_is_answered_from_cached_get_without_body() {
+        use std::sync::atomic::Ordering;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let calls = serve_cacheable(listener);
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        create_caching_target(&cp, &ctx, "docs", "/public", port).await;
+
+        let resp = svc
+            .proxy_request(ctx.clone(), get("/docs/public"))
+            .await
+            .unwrap();
+        assert_eq!(resp.into_body().into_bytes().await.unwrap(), "call-1");
+
+        let resp = svc.proxy_request(ctx, head("/docs/public")).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(resp.headers()[http::header::CONTENT_LENGTH], "6");
+        assert_eq!(
+            resp.headers()[http::header::CACHE_CONTROL],
+            "public, max-age=60"
+        );
+        assert!(resp.into_body().into_bytes().await.unwrap().is_empty());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn cache_hit_carries_age_that_grows_until_entry_expires() {
+        use crate::domain::test_support::MockClock;
+        use std::sync::atomic::Ordering;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let calls = serve_cacheable(listener);
+        let clock = MockClock::default();
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_clock(Arc::new(clock.clone()));
+        let ctx = tenant_ctx();
+        create_caching_target(&cp, &ctx, "docs", "/public", port).await;
+
+        let fetch = || {
+            let ctx = ctx.clone();
+            let svc = &svc;
+            async move {
+                let resp = svc.proxy_request(ctx, get("/docs/public")).await.unwrap();
+                let age = resp.headers().get(http::header::AGE).cloned();
+                (age, resp.into_body().into_bytes().await.unwrap())
+            }
+        };
 
-    #[test]
-    fn normalize_collapses_double_slashes() {
-        assert_eq!(normalize_path("/alias//v1//chat"), "/alias/v1/chat");
-    }
+        let (age, body) = fetch().await;
+        assert!(age.is_none());
+        assert_eq!(body, "call-1");
 
-    #[test]
-    fn normalize_resolves_dot_dot() {
-        assert_eq!(normalize_path("/alias/../admin/secret"), "/admin/secret");
-    }
+        let (age, body) = fetch().await;
+        assert_eq!(age.unwrap(), "0");
+        assert_eq!(body, "call-1");
 
-    #[test]
-    fn normalize_clamps_above_root() {
-        assert_eq!(normalize_path("/alias/../../etc/passwd"), "/etc/passwd");
+        clock.advance(Duration::from_secs(10));
+        let (age, body) = fetch().await;
+        assert_eq!(age.unwrap(), "10");
+        assert_eq!(body, "call-1");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // max-age=60 has passed; the entry is no longer served.
+        clock.advance(Duration::from_secs(50));
+        let (age, body) = fetch().await;
+        assert!(age.is_none());
+        assert_eq!(body, "call-2");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
     }
 
-    #[test]
-    fn normalize_resolves_single_dot() {
-        assert_eq!(normalize_path("/alias/./v1/chat"), "/alias/v1/chat");
+    #[tokio::test]
+    async fn head_miss_goes_upstream_and_is_not_cached() {
+        use std::sync::atomic::Ordering;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let calls = serve_cacheable(listener);
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        create_caching_target(&cp, &ctx, "docs", "/public", port).await;
+
+        svc.proxy_request(ctx.clone(), head("/docs/public"))
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // The HEAD left no (bodiless) entry behind for GET to be served from.
+        let resp = svc.proxy_request(ctx, get("/docs/public")).await.unwrap();
+        assert_eq!(resp.into_body().into_bytes().await.unwrap(), "call-2");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
     }
 
-    #[test]
-    fn normalize_preserves_clean_path() {
-        assert_eq!(normalize_path("/alias/v1/chat"), "/alias/v1/chat");
+    #[tokio::test]
+    async fn head_bypasses_cache_when_disabled() {
+        use std::sync::atomic::Ordering;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let calls = serve_cacheable(listener);
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_head_from_response_cache(false);
+        let ctx = tenant_ctx();
+        create_caching_target(&cp, &ctx, "docs", "/public", port).await;
+
+        svc.proxy_request(ctx.clone(), get("/docs/public"))
+            .await
+            .unwrap();
+        svc.proxy_request(ctx, head("/docs/public")).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
     }
 
-    // -----------------------------------------------------------------------
-    // select_endpoint() unit tests
-    // -----------------------------------------------------------------------
+    #[tokio::test]
+    async fn private_responses_not_cached_by_default() {
+        use std::sync::atomic::Ordering;
 
-    fn ep(host: &str, port: u16) -> Endpoint {
-        Endpoint {
-            scheme: Scheme::Https,
-            host: host.to_string(),
-            port,
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let calls = serve_cacheable(listener);
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        create_caching_target(&cp, &ctx, "mine", "/private", port).await;
+
+        for _ in 0..2 {
+            svc.proxy_request(ctx.clone(), get("/mine/private"))
+                .await
+                .unwrap();
         }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
     }
 
-    fn upstream_with(endpoints: Vec<Endpoint>) -> Upstream {
-        Upstream {
-            id: Uuid::new_v4(),
-            tenant_id: Uuid::new_v4(),
-            alias: "test".to_string(),
-            server: Server { endpoints },
-            protocol: "http".to_string(),
-            enabled: true,
-            auth: None,
-            headers: None,
-            plugins: None,
-            rate_limit: None,
-            tags: vec![],
+    #[tokio::test]
+    async fn responses_not_cached_unless_route_opts_in() {
+        use crate::domain::model::HttpMethod;
+        use std::sync::atomic::Ordering;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let calls = serve_cacheable(listener);
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "docs", None, HttpMethod::Get, "/public", port).await;
+
+        for _ in 0..2 {
+            svc.proxy_request(ctx.clone(), get("/docs/public"))
+                .await
+                .unwrap();
         }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
     }
 
-    /// Mock BackendSelector that returns endpoints[call_count % endpoints.len()].
-    struct MockSelector {
-        call_count: AtomicUsize,
-    }
+    #[tokio::test]
+    async fn credentialed_requests_share_only_public_responses() {
+        use std::sync::atomic::Ordering;
 
-    impl MockSelector {
-        fn new() -> Self {
-            Self {
-                call_count: AtomicUsize::new(0),
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let calls = serve_cacheable(listener);
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        create_caching_target(&cp, &ctx, "plain", "/plain", port).await;
+        create_caching_target(&cp, &ctx, "docs", "/public", port).await;
+
+        let fetch = |uri: &'static str| {
+            let ctx = ctx.clone();
+            let svc = &svc;
+            async move {
+                let mut req = get(uri);
+                req.headers_mut().insert(
+                    http::header::AUTHORIZATION,
+                    HeaderValue::from_static("Bearer caller"),
+                );
+                let resp = svc.proxy_request(ctx, req).await.unwrap();
+                resp.into_body().into_bytes().await.unwrap()
             }
-        }
+        };
 
-        fn calls(&self) -> usize {
-            self.call_count.load(Ordering::Relaxed)
-        }
+        // Freshness alone does not let a credentialed response be shared.
+        assert_eq!(fetch("/plain/plain").await, "call-1");
+        assert_eq!(fetch("/plain/plain").await, "call-2");
+        // `public` does.
+        assert_eq!(fetch("/docs/public").await, "call-3");
+        assert_eq!(fetch("/docs/public").await, "call-3");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // Without credentials, freshness is enough.
+        svc.proxy_request(ctx.clone(), get("/plain/plain"))
+            .await
+            .unwrap();
+        let resp = svc.proxy_request(ctx, get("/plain/plain")).await.unwrap();
+        assert_eq!(resp.into_body().into_bytes().await.unwrap(), "call-4");
     }
 
-    #[async_trait]
-    impl EndpointSelector for MockSelector {
-        async fn select(&self, _upstream_id: Uuid, endpoints: &[Endpoint]) -> Option<Endpoint> {
-            let idx = self.call_count.fetch_add(1, Ordering::Relaxed) % endpoints.len();
-            Some(endpoints[idx].clone())
-        }
+    #[tokio::test]
+    async fn vary_selects_the_cached_variant() {
+        use std::sync::atomic::Ordering;
 
-        fn invalidate(&self, _upstream_id: Uuid) {}
-    }
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let calls = serve_cacheable(listener);
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        create_caching_target(&cp, &ctx, "docs", "/vary", port).await;
 
-    /// Build a minimal `DataPlaneServiceImpl` with the given `BackendSelector`.
-    fn build_svc(selector: Arc<dyn EndpointSelector>) -> DataPlaneServiceImpl {
-        use authz_resolver_sdk::{
-            AuthZResolverClient, AuthZResolverError, EvaluationRequest, EvaluationResponse,
-            EvaluationResponseContext, PolicyEnforcer,
+        let fetch = |accept: &'static str| {
+            let ctx = ctx.clone();
+            let svc = &svc;
+            async move {
+                let mut req = get("/docs/vary");
+                req.headers_mut()
+                    .insert(http::header::ACCEPT, HeaderValue::from_static(accept));
+                let resp = svc.proxy_request(ctx, req).await.unwrap();
+                resp.into_body().into_bytes().await.unwrap()
+            }
         };
-        use credstore_sdk::{CredStoreClientV1, CredStoreError, GetSecretResponse, SecretRef};
-        use modkit_security::SecurityContext;
 
-        struct AllowAllAuthZ;
-        #[async_trait]
-        impl AuthZResolverClient for AllowAllAuthZ {
-            async fn evaluate(
-                &self,
-                _request: EvaluationRequest,
-            ) -> Result<EvaluationResponse, AuthZResolverError> {
-                Ok(EvaluationResponse {
-                    decision: true,
-                    context: EvaluationResponseContext {
-                        constraints: Vec::new(),
-                        deny_reason: None,
-                    },
+        assert_eq!(fetch("application/json").await, "call-1");
+        assert_eq!(fetch("text/csv").await, "call-2");
+        assert_eq!(fetch("application/json").await, "call-1");
+        assert_eq!(fetch("text/csv").await, "call-2");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn max_connections_per_host_queues_request_beyond_cap() {
+        use crate::domain::model::HttpMethod;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::AsyncReadExt;
+
+        // The upstream counts requests and holds its responses until released.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let received = Arc::new(AtomicUsize::new(0));
+        let release = Arc::new(tokio::sync::Notify::new());
+        {
+            let received = received.clone();
+            let release = release.clone();
+            tokio::spawn(async move {
+                loop {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+                    let received = received.clone();
+                    let release = release.clone();
+                    tokio::spawn(async move {
+                        let mut head = Vec::new();
+                        let mut buf = [0u8; 1024];
+                        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                            match stream.read(&mut buf).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(n) => head.extend_from_slice(&buf[..n]),
+                            }
+                        }
+                        received.fetch_add(1, Ordering::SeqCst);
+                        release.notified().await;
+                        let _ = stream
+                            .write_all(
+                                b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok",
+                            )
+                            .await;
+                    });
+                }
+            });
+        }
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc = Arc::new(svc.with_max_connections_per_host(Some(2)));
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "capped", None, HttpMethod::Get, "/items", port).await;
+
+        let requests: Vec<_> = (0..3)
+            .map(|_| {
+                let svc = svc.clone();
+                let ctx = ctx.clone();
+                tokio::spawn(async move {
+                    let resp = svc.proxy_request(ctx, get("/capped/items")).await?;
+                    resp.into_body()
+                        .into_bytes()
+                        .await
+                        .map_err(|e| DomainError::Internal {
+                            message: e.to_string(),
+                        })
                 })
-            }
+            })
+            .collect();
+
+        // Only the cap reaches the upstream; the third request waits.
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(received.load(Ordering::SeqCst), 2);
+
+        // Freeing one slot lets the queued request through.
+        release.notify_one();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(received.load(Ordering::SeqCst), 3);
+        release.notify_one();
+        release.notify_one();
+
+        for request in requests {
+            assert_eq!(request.await.unwrap().unwrap(), "ok");
         }
+    }
 
-        struct NoopCredStore;
-        #[async_trait]
-        impl CredStoreClientV1 for NoopCredStore {
-            async fn get(
-                &self,
-                _ctx: &SecurityContext,
-                _key: &SecretRef,
-            ) -> Result<Option<GetSecretResponse>, CredStoreError> {
-                Ok(None)
-            }
+    #[tokio::test]
+    async fn queue_wait_timeout_sheds_request_waiting_for_host_slot_with_503() {
+        use crate::domain::model::HttpMethod;
+        use tokio::io::AsyncReadExt;
+
+        // The upstream holds every response until released.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let release = Arc::new(tokio::sync::Notify::new());
+        {
+            let release = release.clone();
+            tokio::spawn(async move {
+                loop {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+                    let release = release.clone();
+                    tokio::spawn(async move {
+                        let mut head = Vec::new();
+                        let mut buf = [0u8; 1024];
+                        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                            match stream.read(&mut buf).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(n) => head.extend_from_slice(&buf[..n]),
+                            }
+                        }
+                        release.notified().await;
+                        let _ = stream
+                            .write_all(
+                                b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok",
+                            )
+                            .await;
+                    });
+                }
+            });
         }
 
-        let credstore: Arc<dyn CredStoreClientV1> = Arc::new(NoopCredStore);
-        let policy_enforcer = PolicyEnforcer::new(Arc::new(AllowAllAuthZ));
+        let (cp, svc) = dp_with_test_cp();
+        let svc = Arc::new(
+            svc.with_max_connections_per_host(Some(1))
+                .with_queue_wait_timeout(Some(Duration::from_millis(200))),
+        );
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "capped", None, HttpMethod::Get, "/items", port).await;
 
-        // Minimal CP — never called by select_endpoint().
-        use crate::domain::error::DomainError;
-        use crate::domain::model::*;
-        use crate::domain::services::ControlPlaneService;
+        let holder = {
+            let svc = svc.clone();
+            let ctx = ctx.clone();
+            tokio::spawn(async move { svc.proxy_request(ctx, get("/capped/items")).await })
+        };
+        tokio::time::sleep(Duration::from_millis(100)).await;
 
-        struct NoopCp;
-        #[async_trait]
-        impl ControlPlaneService for NoopCp {
-            async fn create_upstream(
-                &self,
-                _: &SecurityContext,
-                _: CreateUpstreamRequest,
-            ) -> Result<Upstream, DomainError> {
-                unimplemented!()
-            }
-            async fn get_upstream(
-                &self,
-                _: &SecurityContext,
-                _: Uuid,
-            ) -> Result<Upstream, DomainError> {
-                unimplemented!()
-            }
-            async fn list_upstreams(
-                &self,
-                _: &SecurityContext,
-                _: &ListQuery,
-            ) -> Result<Vec<Upstream>, DomainError> {
-                unimplemented!()
-            }
-            async fn update_upstream(
-                &self,
-                _: &SecurityContext,
-                _: Uuid,
-                _: UpdateUpstreamRequest,
-            ) -> Result<Upstream, DomainError> {
-                unimplemented!()
-            }
-            async fn delete_upstream(
-                &self,
-                _: &SecurityContext,
-                _: Uuid,
-            ) -> Result<(), DomainError> {
-                unimplemented!()
-            }
-            async fn create_route(
-                &self,
-                _: &SecurityContext,
-                _: CreateRouteRequest,
-            ) -> Result<Route, DomainError> {
-                unimplemented!()
-            }
-            async fn get_route(&self, _: &SecurityContext, _: Uuid) -> Result<Route, DomainError> {
-                unimplemented!()
-            }
-            async fn list_routes(
-                &self,
-                _: &SecurityContext,
-                _: Uuid,
-                _: &ListQuery,
-            ) -> Result<Vec<Route>, DomainError> {
-                unimplemented!()
-            }
-            async fn update_route(
-                &self,
-                _: &SecurityContext,
-                _: Uuid,
-                _: UpdateRouteRequest,
-            ) -> Result<Route, DomainError> {
-                unimplemented!()
-            }
-            async fn delete_route(&self, _: &SecurityContext, _: Uuid) -> Result<(), DomainError> {
-                unimplemented!()
-            }
-            async fn resolve_proxy_target(
-                &self,
-                _: &SecurityContext,
-                _: &str,
-                _: &str,
-                _: &str,
-            ) -> Result<(Upstream, Route), DomainError> {
-                unimplemented!()
+        // The slot stays taken past the queue wait timeout, well inside the
+        // upstream timeout, so the queued request is shed rather than timed out.
+        let started = Instant::now();
+        let err = svc
+            .proxy_request(ctx, get("/capped/items"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::GatewayOverloaded { .. }),
+            "expected GatewayOverloaded, got {err:?}"
+        );
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        release.notify_one();
+        let resp = holder.await.unwrap().unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn stream_beyond_max_concurrent_streams_is_refused_with_503() {
+        use crate::domain::model::HttpMethod;
+        use tokio::io::AsyncReadExt;
+
+        // Every SSE response sends one event, then holds until released.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (release, released) = watch::channel(false);
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut released = released.clone();
+                tokio::spawn(async move {
+                    let mut head = Vec::new();
+                    let mut buf = [0u8; 1024];
+                    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match stream.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => head.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    let _ = stream
+                        .write_all(
+                            b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ntransfer-encoding: chunked\r\n\r\n9\r\ndata: 1\n\n\r\n",
+                        )
+                        .await;
+                    let _ = released.wait_for(|r| *r).await;
+                    let _ = stream.write_all(b"9\r\ndata: 2\n\n\r\n0\r\n\r\n").await;
+                });
             }
+        });
+
+        let (cp, svc) = dp_with_test_cp();
+        let svc = svc.with_max_concurrent_streams(Some(2));
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "events", None, HttpMethod::Get, "/stream", port).await;
+
+        let mut streams = Vec::new();
+        for _ in 0..2 {
+            let resp = svc
+                .proxy_request(ctx.clone(), get("/events/stream"))
+                .await
+                .unwrap();
+            let mut body = resp.into_body().into_stream();
+            assert_eq!(body.next().await.unwrap().unwrap(), "data: 1\n\n");
+            streams.push(body);
         }
 
-        let cp: Arc<dyn ControlPlaneService> = Arc::new(NoopCp);
-        let server_conf = Arc::new(pingora_core::server::configuration::ServerConf::default());
-        let pingora = crate::infra::proxy::pingora_proxy::PingoraProxy::new(
-            Duration::from_secs(10),
-            Duration::from_secs(30),
+        let err = svc
+            .proxy_request(ctx.clone(), get("/events/stream"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::GatewayOverloaded { .. }),
+            "expected GatewayOverloaded, got {err:?}"
         );
-        let proxy = Arc::new(crate::infra::proxy::pingora_proxy::new_http_proxy(
-            &server_conf,
-            pingora,
-        ));
 
-        DataPlaneServiceImpl::new(
-            cp,
-            credstore,
-            policy_enforcer,
-            None,
-            TokenCacheConfig::default(),
-            selector,
-            proxy,
+        // The streams already running are unaffected and finish normally.
+        release.send(true).unwrap();
+        for body in streams {
+            let rest: Vec<_> = body.map(Result::unwrap).collect().await;
+            assert_eq!(rest.concat(), b"data: 2\n\n");
+        }
+
+        // Their slots are free again.
+        let resp = svc.proxy_request(ctx, get("/events/stream")).await.unwrap();
+        assert_eq!(
+            resp.into_body().into_bytes().await.unwrap(),
+            "data: 1\n\ndata: 2\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn idempotency_key_replays_stored_response_until_a_new_key() {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let upstream_calls = calls.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let n = upstream_calls.fetch_add(1, Ordering::SeqCst) + 1;
+                tokio::spawn(async move {
+                    let mut head = Vec::new();
+                    let mut buf = [0u8; 1024];
+                    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match stream.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(k) => head.extend_from_slice(&buf[..k]),
+                        }
+                    }
+                    let body = format!("order-{n}");
+                    let _ = stream
+                        .write_all(
+                            format!(
+                                "HTTP/1.1 201 Created\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                                body.len()
+                            )
+                            .as_bytes(),
+                        )
+                        .await;
+                });
+            }
+        });
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let route = create_target(&cp, &ctx, "orders", None, HttpMethod::Post, "/x", port).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                idempotency_ttl_secs: Some(60),
+                cache_responses: None,
+                negative_cache_ttl_secs: None,
+                response_header_allowlist: None,
+                ..Default::default()
+            },
         )
+        .await
+        .unwrap();
+        let post = |key: &str| {
+            http::Request::builder()
+                .method(http::Method::POST)
+                .uri("/orders/x")
+                .header("Idempotency-Key", key)
+                .body(Body::Empty)
+                .unwrap()
+        };
+
+        let mut bodies = Vec::new();
+        for key in ["k1", "k1", "k2"] {
+            let resp = svc.proxy_request(ctx.clone(), post(key)).await.unwrap();
+            assert_eq!(resp.status(), http::StatusCode::CREATED);
+            bodies.push(resp.into_body().into_bytes().await.unwrap());
+        }
+        assert_eq!(bodies, ["order-1", "order-1", "order-2"]);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
     }
 
-    // P2 #12: Alias extraction happens on raw path, then suffix is normalized.
-    // Path traversal in the alias segment must not influence which upstream is resolved.
-    #[test]
-    fn alias_extraction_ignores_path_traversal() {
-        // Simulate what proxy_request does: extract alias from raw path, normalize suffix.
-        fn extract(path: &str) -> (String, String) {
-            let trimmed = path.strip_prefix('/').unwrap_or(path);
-            let (alias, raw_suffix) = match trimmed.find('/') {
-                Some(pos) => (&trimmed[..pos], &trimmed[pos..]),
-                None => (trimmed, ""),
-            };
-            (alias.to_string(), normalize_path(raw_suffix))
+    /// Upper-cases bodies of the given media types.
+    struct UppercaseTransformer(&'static str);
+
+    impl BodyTransformer for UppercaseTransformer {
+        fn content_types(&self) -> Vec<String> {
+            vec![self.0.into()]
         }
 
-        // Normal case.
-        let (alias, suffix) = extract("/myalias/v1/chat");
-        assert_eq!(alias, "myalias");
-        assert_eq!(suffix, "/v1/chat");
+        fn transform(&self, body: Bytes) -> Bytes {
+            Bytes::from(body.to_ascii_uppercase())
+        }
+    }
 
-        // Path traversal attempt: alias is still the first raw segment.
-        let (alias, suffix) = extract("/myalias/../admin/secret");
-        assert_eq!(alias, "myalias");
-        assert_eq!(suffix, "/admin/secret"); // ".." collapsed in suffix only
+    fn dp_with_uppercase_transformer() -> (Arc<dyn ControlPlaneService>, DataPlaneServiceImpl) {
+        let hub = Arc::new(ClientHub::new());
+        hub.register_scoped::<dyn BodyTransformer>(
+            ClientScope::new("upper-json"),
+            Arc::new(UppercaseTransformer("application/json")),
+        );
+        let (cp, svc) = dp_with_test_cp();
+        (cp, svc.with_body_transformers(hub))
+    }
 
-        // Deep traversal: alias is still literal first segment.
-        let (alias, suffix) = extract("/myalias/../../etc/passwd");
-        assert_eq!(alias, "myalias");
-        assert_eq!(suffix, "/etc/passwd"); // ".." collapsed, clamped at root
+    async fn transformer_target(
+        cp: &Arc<dyn ControlPlaneService>,
+        ctx: &SecurityContext,
+        alias: &str,
+        names: Vec<String>,
+        method: crate::domain::model::HttpMethod,
+        port: u16,
+    ) {
+        use crate::domain::model::UpdateRouteRequest;
+
+        let route = create_target(cp, ctx, alias, None, method, "/doc", port).await;
+        cp.update_route(
+            ctx,
+            route.id,
+            UpdateRouteRequest {
+                body_transformers: Some(names),
+                log_redact_paths: None,
+                transform_hook_url: None,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
     }
 
-    // P2: HTTPS-only — Http scheme endpoint must be rejected.
     #[tokio::test]
-    async fn select_endpoint_rejects_http_scheme() {
-        let selector = Arc::new(MockSelector::new());
-        let svc = build_svc(selector);
+    async fn body_transformers_run_on_matching_response_content_types_only() {
+        use crate::domain::model::HttpMethod;
 
-        // Single Http endpoint.
-        let upstream = upstream_with(vec![Endpoint {
-            scheme: Scheme::Http,
-            host: "insecure.example.com".to_string(),
-            port: 80,
-        }]);
-        let headers = HeaderMap::new();
+        let (cp, svc) = dp_with_uppercase_transformer();
+        let ctx = tenant_ctx();
+        for (alias, content_type) in [("tf-json", "application/json"), ("tf-text", "text/plain")] {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            tokio::spawn(serve_raw_once(
+                listener,
+                raw_response(content_type, br#"{"name":"ada"}"#),
+            ));
+            transformer_target(
+                &cp,
+                &ctx,
+                alias,
+                vec!["upper-json".into()],
+                HttpMethod::Get,
+                port,
+            )
+            .await;
+        }
 
-        let err = svc.select_endpoint(&upstream, &headers, "/test").await;
+        let resp = svc
+            .proxy_request(ctx.clone(), get("/tf-json/doc"))
+            .await
+            .unwrap();
+        let body = resp.into_body().into_bytes().await.unwrap();
+        assert_eq!(body, br#"{"NAME":"ADA"}"#.as_slice());
 
-        // select_endpoint itself doesn't enforce HTTPS — the check is in proxy_request
-        // after select_endpoint returns. Verify the endpoint is returned here (enforcement
-        // is at a higher level).
-        assert!(err.is_ok(), "select_endpoint should return the endpoint");
-        assert_eq!(err.unwrap().scheme, Scheme::Http);
+        let resp = svc.proxy_request(ctx, get("/tf-text/doc")).await.unwrap();
+        let body = resp.into_body().into_bytes().await.unwrap();
+        assert_eq!(body, br#"{"name":"ada"}"#.as_slice());
     }
 
-    // positive-2.2 (custom-header-routing): X-OAGW-Target-Host matches an endpoint.
     #[tokio::test]
-    async fn select_endpoint_target_host_matches() {
-        let selector = Arc::new(MockSelector::new());
-        let svc = build_svc(selector.clone());
-        let upstream = upstream_with(vec![ep("a.com", 443), ep("b.com", 443)]);
+    async fn body_transformers_run_on_matching_request_bodies() {
+        use crate::domain::model::HttpMethod;
+        use tokio::io::AsyncReadExt;
 
-        let mut headers = HeaderMap::new();
-        headers.insert("x-oagw-target-host", "a.com".parse().unwrap());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !received.ends_with(b"}") {
+                let n = stream.read(&mut buf).await.unwrap();
+                assert!(n > 0, "connection closed before request body");
+                received.extend_from_slice(&buf[..n]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
+                .await
+                .unwrap();
+            let _ = tx.send(String::from_utf8_lossy(&received).into_owned());
+        });
+        let (cp, svc) = dp_with_uppercase_transformer();
+        let ctx = tenant_ctx();
+        transformer_target(
+            &cp,
+            &ctx,
+            "tf-req",
+            vec!["upper-json".into()],
+            HttpMethod::Post,
+            port,
+        )
+        .await;
 
-        let result = svc
-            .select_endpoint(&upstream, &headers, "/test")
-            .await
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/tf-req/doc")
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(Body::Bytes(Bytes::from_static(br#"{"name":"ada"}"#)))
             .unwrap();
-        assert_eq!(result.host, "a.com");
-        assert_eq!(selector.calls(), 0, "BackendSelector should not be called");
+        let resp = svc.proxy_request(ctx, req).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let received = rx.await.unwrap();
+        assert!(received.ends_with(r#"{"NAME":"ADA"}"#), "{received}");
     }
 
-    // negative-2.1 (custom-header-routing): X-OAGW-Target-Host does not match any endpoint.
     #[tokio::test]
-    async fn select_endpoint_target_host_unknown() {
-        let svc = build_svc(Arc::new(MockSelector::new()));
-        let upstream = upstream_with(vec![ep("a.com", 443), ep("b.com", 443)]);
+    async fn unregistered_body_transformer_fails_the_request() {
+        use crate::domain::model::HttpMethod;
 
-        let mut headers = HeaderMap::new();
-        headers.insert("x-oagw-target-host", "evil.com".parse().unwrap());
+        let (cp, svc) = dp_with_uppercase_transformer();
+        let ctx = tenant_ctx();
+        transformer_target(
+            &cp,
+            &ctx,
+            "tf-missing",
+            vec!["redact-pii".into()],
+            HttpMethod::Get,
+            9,
+        )
+        .await;
 
         let err = svc
-            .select_endpoint(&upstream, &headers, "/test")
+            .proxy_request(ctx, get("/tf-missing/doc"))
             .await
             .unwrap_err();
         assert!(
-            matches!(err, DomainError::UnknownTargetHost { .. }),
-            "expected UnknownTargetHost, got: {err:?}"
+            matches!(&err, DomainError::Internal { message } if message.contains("redact-pii")),
+            "{err:?}"
         );
     }
 
-    // negative-1.2..1.4 (custom-header-routing): X-OAGW-Target-Host with invalid format.
-    #[tokio::test]
-    async fn select_endpoint_target_host_invalid_format() {
-        let svc = build_svc(Arc::new(MockSelector::new()));
-        let upstream = upstream_with(vec![ep("a.com", 443)]);
-
-        for bad_value in [
-            "a.com:443",
-            "a.com/path",
-            "a.com?q=1",
-            "a b",
-            "evil.com@real.com",
-            "evil.com\\real.com",
-            "a.com#fragment",
-        ] {
-            let mut headers = HeaderMap::new();
-            headers.insert("x-oagw-target-host", bad_value.parse().unwrap());
-            let err = svc
-                .select_endpoint(&upstream, &headers, "/test")
-                .await
-                .unwrap_err();
-            assert!(
-                matches!(err, DomainError::InvalidTargetHost { .. }),
-                "expected InvalidTargetHost for '{bad_value}', got: {err:?}"
-            );
+    fn rewrite_rule(
+        find: &str,
+        replace: &str,
+        regex: bool,
+    ) -> crate::domain::model::BodyRewriteRule {
+        crate::domain::model::BodyRewriteRule {
+            find: find.into(),
+            replace: replace.into(),
+            regex,
+            content_types: vec![],
         }
+    }
 
-        // Empty header value: test separately since HeaderValue::from_static
-        // allows empty strings while .parse() does not.
-        let mut headers = HeaderMap::new();
-        headers.insert("x-oagw-target-host", HeaderValue::from_static(""));
-        let err = svc
-            .select_endpoint(&upstream, &headers, "/test")
+    #[tokio::test]
+    async fn body_rewrite_replaces_literal_text() {
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let body = br#"{"next":"http://internal.svc:8080/items?page=2","self":"http://internal.svc:8080/items"}"#;
+        rewrite_target(
+            &cp,
+            &ctx,
+            "rw-literal",
+            vec![rewrite_rule(
+                "http://internal.svc:8080",
+                "https://api.example.com",
+                false,
+            )],
+            raw_response("application/json", body),
+        )
+        .await;
+
+        let resp = svc
+            .proxy_request(ctx, get("/rw-literal/doc"))
             .await
-            .unwrap_err();
-        assert!(
-            matches!(err, DomainError::InvalidTargetHost { .. }),
-            "expected InvalidTargetHost for empty header, got: {err:?}"
+            .unwrap();
+        let expected = r#"{"next":"https://api.example.com/items?page=2","self":"https://api.example.com/items"}"#;
+        assert_eq!(
+            resp.headers().get(http::header::CONTENT_LENGTH).unwrap(),
+            &expected.len().to_string()
         );
+        let body = resp.into_body().into_bytes().await.unwrap();
+        assert_eq!(body, expected.as_bytes());
     }
 
-    // positive-2.1 (custom-header-routing): Round-robin fallback for multi-endpoint (no header).
     #[tokio::test]
-    async fn select_endpoint_round_robin_fallback() {
-        let selector = Arc::new(MockSelector::new());
-        let svc = build_svc(selector.clone());
-        let upstream = upstream_with(vec![ep("a.com", 443), ep("b.com", 443)]);
-        let headers = HeaderMap::new();
+    async fn body_rewrite_replaces_regex_matches() {
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let body =
+            br#"<a href="http://node-12.internal/a">a</a><a href="http://node-7.internal/b">b</a>"#;
+        rewrite_target(
+            &cp,
+            &ctx,
+            "rw-regex",
+            vec![rewrite_rule(
+                r"http://node-(\d+)\.internal",
+                "https://public.example.com/n$1",
+                true,
+            )],
+            raw_response("text/html; charset=utf-8", body),
+        )
+        .await;
 
-        let ep1 = svc
-            .select_endpoint(&upstream, &headers, "/test")
+        let resp = svc.proxy_request(ctx, get("/rw-regex/doc")).await.unwrap();
+        let expected = r#"<a href="https://public.example.com/n12/a">a</a><a href="https://public.example.com/n7/b">b</a>"#;
+        assert_eq!(
+            resp.headers().get(http::header::CONTENT_LENGTH).unwrap(),
+            &expected.len().to_string()
+        );
+        let body = resp.into_body().into_bytes().await.unwrap();
+        assert_eq!(body, expected.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn body_rewrite_leaves_binary_and_streamed_bodies_untouched() {
+        let (cp, svc) = dp_with_test_cp();
+        let ctx = tenant_ctx();
+        let rules = || vec![rewrite_rule("internal", "public", false)];
+
+        let mut binary = b"\x89PNG internal ".to_vec();
+        binary.extend_from_slice(&[0xff, 0x00, 0xfe]);
+        rewrite_target(
+            &cp,
+            &ctx,
+            "rw-binary",
+            rules(),
+            raw_response("application/octet-stream", &binary),
+        )
+        .await;
+        // Chunked: no Content-Length, so the body is streamed through.
+        rewrite_target(
+            &cp,
+            &ctx,
+            "rw-chunked",
+            rules(),
+            b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ntransfer-encoding: chunked\r\n\
+              connection: close\r\n\r\n10\r\n{\"internal\":true\r\n1\r\n}\r\n0\r\n\r\n"
+                .to_vec(),
+        )
+        .await;
+
+        let resp = svc
+            .proxy_request(ctx.clone(), get("/rw-binary/doc"))
             .await
             .unwrap();
-        let ep2 = svc
-            .select_endpoint(&upstream, &headers, "/test")
+        assert_eq!(
+            resp.headers().get(http::header::CONTENT_LENGTH).unwrap(),
+            &binary.len().to_string()
+        );
+        let body = resp.into_body().into_bytes().await.unwrap();
+        assert_eq!(body, binary);
+
+        let resp = svc
+            .proxy_request(ctx, get("/rw-chunked/doc"))
             .await
             .unwrap();
+        let body = resp.into_body().into_bytes().await.unwrap();
+        assert_eq!(body, br#"{"internal":true}"#.as_slice());
+    }
 
-        assert_eq!(
-            selector.calls(),
-            2,
-            "BackendSelector should be called for multi-endpoint"
+    /// AuthZ resolver whose first `slow_calls` evaluations hang; later ones
+    /// allow immediately.
+    struct SlowAuthZ {
+        slow_calls: usize,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl authz_resolver_sdk::AuthZResolverClient for SlowAuthZ {
+        async fn evaluate(
+            &self,
+            _request: authz_resolver_sdk::EvaluationRequest,
+        ) -> Result<authz_resolver_sdk::EvaluationResponse, authz_resolver_sdk::AuthZResolverError>
+        {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if call < self.slow_calls {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+            }
+            Ok(authz_resolver_sdk::EvaluationResponse {
+                decision: true,
+                context: authz_resolver_sdk::EvaluationResponseContext {
+                    constraints: Vec::new(),
+                    deny_reason: None,
+                },
+            })
+        }
+    }
+
+    /// A DP whose AuthZ resolver hangs on its first `slow_calls` evaluations.
+    fn dp_with_slow_authz(
+        slow_calls: usize,
+    ) -> (
+        Arc<dyn ControlPlaneService>,
+        DataPlaneServiceImpl,
+        Arc<SlowAuthZ>,
+    ) {
+        let authz = Arc::new(SlowAuthZ {
+            slow_calls,
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        });
+        let (cp, svc) = dp_with_cp_and_enforcer(
+            crate::domain::test_support::TestCpBuilder::new(),
+            PolicyEnforcer::new(authz.clone()),
         );
-        // MockSelector returns endpoints in order: [0], [1], [0], ...
-        assert_eq!(ep1.host, "a.com");
-        assert_eq!(ep2.host, "b.com");
+        (cp, svc, authz)
     }
 
-    // positive-1.1 (custom-header-routing): Single-endpoint bypass (no header, no BackendSelector call).
     #[tokio::test]
-    async fn select_endpoint_single_endpoint_bypass() {
-        let selector = Arc::new(MockSelector::new());
-        let svc = build_svc(selector.clone());
-        let upstream = upstream_with(vec![ep("only.com", 443)]);
-        let headers = HeaderMap::new();
+    async fn authz_timeout_fails_closed_after_retries() {
+        use crate::domain::model::HttpMethod;
 
-        let result = svc
-            .select_endpoint(&upstream, &headers, "/test")
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (cp, svc, authz) = dp_with_slow_authz(usize::MAX);
+        let svc = svc.with_authz_failure_handling(
+            Some(Duration::from_millis(100)),
+            1,
+            AuthzErrorPolicy::Deny,
+        );
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "authz-slow", None, HttpMethod::Get, "/x", port).await;
+
+        let started = Instant::now();
+        let err = svc
+            .proxy_request(ctx, get("/authz-slow/x"))
             .await
-            .unwrap();
-        assert_eq!(result.host, "only.com");
-        assert_eq!(
-            selector.calls(),
+            .unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(
+            matches!(err, DomainError::AuthzUnavailable { .. }),
+            "{err:?}"
+        );
+        // The evaluation failure is logged, not sent to the client.
+        assert!(!err.to_string().contains("timed out"), "{err}");
+        let resp = crate::api::rest::error::error_response(err);
+        assert_eq!(resp.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers().get("retry-after").unwrap(), "1");
+        // One attempt plus one retry, and the upstream is never contacted.
+        assert_eq!(authz.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), listener.accept())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn authz_timeout_fails_open_when_configured() {
+        use crate::domain::model::HttpMethod;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream = tokio::spawn(serve_once(listener));
+        let (cp, svc, _) = dp_with_slow_authz(usize::MAX);
+        let svc = svc.with_authz_failure_handling(
+            Some(Duration::from_millis(100)),
             0,
-            "BackendSelector should NOT be called for single endpoint"
+            AuthzErrorPolicy::Allow,
         );
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "authz-open", None, HttpMethod::Get, "/x", port).await;
+
+        let resp = svc.proxy_request(ctx, get("/authz-open/x")).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        upstream.await.unwrap();
     }
 
-    // positive-1.2 (custom-header-routing): Single-endpoint upstream validates header if present.
     #[tokio::test]
-    async fn select_endpoint_single_endpoint_validates_header() {
-        let svc = build_svc(Arc::new(MockSelector::new()));
-        let upstream = upstream_with(vec![ep("a.com", 443)]);
+    async fn authz_retry_recovers_from_a_timed_out_evaluation() {
+        use crate::domain::model::HttpMethod;
 
-        // Valid header matching the single endpoint → OK.
-        let mut headers = HeaderMap::new();
-        headers.insert("x-oagw-target-host", "a.com".parse().unwrap());
-        let result = svc
-            .select_endpoint(&upstream, &headers, "/test")
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream = tokio::spawn(serve_once(listener));
+        let (cp, svc, authz) = dp_with_slow_authz(1);
+        let svc = svc.with_authz_failure_handling(
+            Some(Duration::from_millis(100)),
+            1,
+            AuthzErrorPolicy::Deny,
+        );
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "authz-retry", None, HttpMethod::Get, "/x", port).await;
+
+        let resp = svc.proxy_request(ctx, get("/authz-retry/x")).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(authz.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        upstream.await.unwrap();
+    }
+    #[tokio::test]
+    async fn authz_mode_allow_all_skips_the_resolver() {
+        use crate::domain::model::HttpMethod;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let upstream = tokio::spawn(serve_once(listener));
+        let (cp, svc, authz) = dp_with_slow_authz(usize::MAX);
+        let svc = svc.with_authz_mode(AuthzMode::AllowAll);
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "authz-allow", None, HttpMethod::Get, "/x", port).await;
+
+        let resp = svc.proxy_request(ctx, get("/authz-allow/x")).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(authz.calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+        upstream.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn authz_mode_deny_all_rejects_without_the_resolver() {
+        use crate::domain::model::HttpMethod;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (cp, svc, authz) = dp_with_slow_authz(usize::MAX);
+        let svc = svc.with_authz_mode(AuthzMode::DenyAll);
+        let ctx = tenant_ctx();
+        create_target(&cp, &ctx, "authz-deny", None, HttpMethod::Get, "/x", port).await;
+
+        let err = svc
+            .proxy_request(ctx, get("/authz-deny/x"))
             .await
-            .unwrap();
-        assert_eq!(result.host, "a.com");
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Forbidden { .. }), "{err:?}");
+        assert_eq!(authz.calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), listener.accept())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn authz_mode_deny_all_also_denies_public_routes() {
+        use crate::domain::model::{HttpMethod, UpdateRouteRequest};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (cp, svc, _) = dp_with_slow_authz(usize::MAX);
+        let svc = svc.with_authz_mode(AuthzMode::DenyAll);
+        let ctx = tenant_ctx();
+        let route =
+            create_target(&cp, &ctx, "authz-public", None, HttpMethod::Get, "/x", port).await;
+        cp.update_route(
+            &ctx,
+            route.id,
+            UpdateRouteRequest {
+                skip_authz: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
 
-        // Invalid header not matching → UnknownTargetHost.
-        let mut headers = HeaderMap::new();
-        headers.insert("x-oagw-target-host", "b.com".parse().unwrap());
         let err = svc
-            .select_endpoint(&upstream, &headers, "/test")
+            .proxy_request(ctx, get("/authz-public/x"))
             .await
             .unwrap_err();
+        assert!(matches!(err, DomainError::Forbidden { .. }), "{err:?}");
         assert!(
-            matches!(err, DomainError::UnknownTargetHost { .. }),
-            "expected UnknownTargetHost for mismatched header on single-endpoint upstream"
+            tokio::time::timeout(Duration::from_millis(100), listener.accept())
+                .await
+                .is_err()
         );
     }
+
+    #[tokio::test]
+    async fn builtin_static_resolver_decides_through_the_enforcer() {
+        use crate::domain::model::HttpMethod;
+        use crate::infra::static_authz::StaticAuthZResolverClient;
+
+        // Without a registered resolver, `allow_all` and `deny_all` still
+        // hand the control plane an enforcer backed by the built-in one.
+        for allow in [true, false] {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let upstream = tokio::spawn(serve_once(listener));
+            let (cp, svc) = dp_with_cp_and_enforcer(
+                crate::domain::test_support::TestCpBuilder::new(),
+                PolicyEnforcer::new(Arc::new(StaticAuthZResolverClient::new(allow))),
+            );
+            let ctx = tenant_ctx();
+            create_target(&cp, &ctx, "authz-static", None, HttpMethod::Get, "/x", port).await;
+
+            let result = svc.proxy_request(ctx, get("/authz-static/x")).await;
+            if allow {
+                assert_eq!(result.unwrap().status(), http::StatusCode::OK);
+                upstream.await.unwrap();
+            } else {
+                let err = result.unwrap_err();
+                assert!(
+                    matches!(&err, DomainError::Forbidden { detail } if detail.contains("deny_all")),
+                    "{err:?}"
+                );
+                upstream.abort();
+            }
+        }
+    }
 }
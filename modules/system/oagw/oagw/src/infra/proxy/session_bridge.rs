@@ -40,14 +40,14 @@ fn url_path_and_query(url: &str) -> &str {
 ///
 /// - **`body = Some(bytes)`** (buffered path) — emits `Content-Length` and
 ///   appends the body after the blank line.
-/// - **`body = None`** (streaming path) — omits `Content-Length`; the caller
-///   writes body chunks afterward and signals the boundary by shutting down
-///   the write half (EOF), which is valid HTTP/1.1 framing for single-shot
-///   requests.
+/// - **`body = None`** (streaming path) — emits `Transfer-Encoding: chunked`;
+///   the caller writes each body chunk with [`encode_chunk`] as it arrives
+///   and finishes with [`LAST_CHUNK`], so the body never has to be buffered.
 ///
 /// In both cases the function emits `Connection: close` (single-shot bridge,
-/// no keep-alive). Any inbound `Content-Length` or `Connection` values
-/// carried in `headers` are dropped to prevent duplicate framing headers.
+/// no keep-alive). Any inbound `Content-Length`, `Transfer-Encoding`, or
+/// `Connection` values carried in `headers` are dropped to prevent duplicate
+/// framing headers.
 pub(crate) fn serialize_request_wire(
     method: &Method,
     url: &str,
@@ -56,6 +56,38 @@ pub(crate) fn serialize_request_wire(
 ) -> Vec<u8> {
     let body_len = body.map_or(0, |b| b.len());
     let mut buf = Vec::with_capacity(512 + body_len);
+    write_request_head(&mut buf, method, url, headers);
+    // Content-Length for the buffered path, chunked framing for the
+    // streaming path — either way Pingora knows where the body ends.
+    match body {
+        Some(b) => {
+            let _ = write!(buf, "Content-Length: {}\r\n", b.len());
+        }
+        None => buf.extend_from_slice(b"Transfer-Encoding: chunked\r\n"),
+    }
+    // Single-shot bridge — no keep-alive on the in-memory session.
+    buf.extend_from_slice(b"Connection: close\r\n");
+    buf.extend_from_slice(b"\r\n");
+    if let Some(b) = body {
+        buf.extend_from_slice(b);
+    }
+    buf
+}
+
+/// Serialize the head of a WebSocket handshake. It carries no body framing
+/// and `Connection: Upgrade` instead of `close`: after a `101` the bridge
+/// carries the raw tunnel bytes in both directions.
+pub(crate) fn serialize_upgrade_wire(method: &Method, url: &str, headers: &HeaderMap) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(512);
+    write_request_head(&mut buf, method, url, headers);
+    buf.extend_from_slice(b"Connection: Upgrade\r\n");
+    buf.extend_from_slice(b"\r\n");
+    buf
+}
+
+/// Write the request line and every header except the framing ones, which
+/// the callers append themselves.
+fn write_request_head(buf: &mut Vec<u8>, method: &Method, url: &str, headers: &HeaderMap) {
     let pq_raw = url_path_and_query(url);
     // Defense-in-depth: strip CR/LF to prevent header injection.
     // Upstream layers (http::Uri, form_urlencoded) already reject CRLF,
@@ -70,8 +102,11 @@ pub(crate) fn serialize_request_wire(
     };
     let _ = write!(buf, "{} {} HTTP/1.1\r\n", method, pq);
     for (name, value) in headers {
-        // Skip framing headers — authoritative values are appended below.
-        if name == http::header::CONTENT_LENGTH || name == http::header::CONNECTION {
+        // Skip framing headers — the caller appends authoritative values.
+        if name == http::header::CONTENT_LENGTH
+            || name == http::header::TRANSFER_ENCODING
+            || name == http::header::CONNECTION
+        {
             continue;
         }
         buf.extend_from_slice(name.as_str().as_bytes());
@@ -79,17 +114,18 @@ pub(crate) fn serialize_request_wire(
         buf.extend_from_slice(value.as_bytes());
         buf.extend_from_slice(b"\r\n");
     }
-    // Include Content-Length only for the buffered path so Pingora knows
-    // the body boundary. The streaming path relies on EOF instead.
-    if let Some(b) = body {
-        let _ = write!(buf, "Content-Length: {}\r\n", b.len());
-    }
-    // Single-shot bridge — no keep-alive on the in-memory session.
-    buf.extend_from_slice(b"Connection: close\r\n");
+}
+
+/// Terminating chunk of a chunked request body.
+pub(crate) const LAST_CHUNK: &[u8] = b"0\r\n\r\n";
+
+/// Frame one non-empty body chunk for the chunked streaming path. Empty input
+/// must not be encoded — a zero-size chunk terminates the body.
+pub(crate) fn encode_chunk(data: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(data.len() + 12);
+    let _ = write!(buf, "{:x}\r\n", data.len());
+    buf.extend_from_slice(data);
     buf.extend_from_slice(b"\r\n");
-    if let Some(b) = body {
-        buf.extend_from_slice(b);
-    }
     buf
 }
 
@@ -100,33 +136,44 @@ pub(crate) fn serialize_request_wire(
 /// Read an HTTP/1.1 response from the client side of a DuplexStream.
 ///
 /// Parses the status line and headers via `httparse`, then returns a
-/// streaming body whose framing strategy depends on the response:
+/// streaming body whose framing strategy depends on the response. Interim
+/// `1xx` responses other than `101` (e.g. `100 Continue`) are skipped; the
+/// gateway answers the client's expectation itself.
 ///
+///
+/// - **Response to HEAD** (`is_head`) → empty; `Content-Length` is kept as
+///   a header but describes the GET representation, not bytes on the wire
 /// - **101 Switching Protocols** → raw unbounded byte stream (WebSocket)
 /// - **Content-Length** → exactly N bytes
 /// - **Transfer-Encoding: chunked** → decoded chunks
 /// - **Otherwise** → read until EOF
 pub(crate) async fn parse_response_stream(
     mut io: impl AsyncRead + Unpin + Send + 'static,
+    is_head: bool,
 ) -> anyhow::Result<(StatusCode, HeaderMap, BodyStream)> {
     // Phase 1: accumulate bytes until httparse can parse a complete header.
     let mut buf = BytesMut::with_capacity(4096);
+    // Set when bytes left over after an interim response may already hold
+    // the next head, which must then be parsed before reading more.
+    let mut unparsed = false;
     let (status, headers, body_offset) = loop {
-        let mut tmp = [0u8; 4096];
-        let n = io
-            .read(&mut tmp)
-            .await
-            .context("failed to read response from proxy")?;
-        if n == 0 {
-            anyhow::bail!("proxy closed connection before sending response headers");
-        }
-        buf.extend_from_slice(&tmp[..n]);
-        if buf.len() > MAX_HEADER_BYTES {
-            anyhow::bail!(
-                "response headers too large ({} bytes exceeds {} byte limit)",
-                buf.len(),
-                MAX_HEADER_BYTES
-            );
+        if !std::mem::take(&mut unparsed) {
+            let mut tmp = [0u8; 4096];
+            let n = io
+                .read(&mut tmp)
+                .await
+                .context("failed to read response from proxy")?;
+            if n == 0 {
+                anyhow::bail!("proxy closed connection before sending response headers");
+            }
+            buf.extend_from_slice(&tmp[..n]);
+            if buf.len() > MAX_HEADER_BYTES {
+                anyhow::bail!(
+                    "response headers too large ({} bytes exceeds {} byte limit)",
+                    buf.len(),
+                    MAX_HEADER_BYTES
+                );
+            }
         }
 
         let mut parsed_headers = [httparse::EMPTY_HEADER; 128];
@@ -134,6 +181,11 @@ pub(crate) async fn parse_response_stream(
         match resp.parse(&buf)? {
             httparse::Status::Complete(offset) => {
                 let status = StatusCode::from_u16(resp.code.unwrap_or(502))?;
+                if status.is_informational() && status != StatusCode::SWITCHING_PROTOCOLS {
+                    let _ = buf.split_to(offset);
+                    unparsed = !buf.is_empty();
+                    continue;
+                }
                 let mut headers = HeaderMap::new();
                 for h in resp.headers.iter() {
                     if let (Ok(name), Ok(value)) = (
@@ -154,7 +206,9 @@ pub(crate) async fn parse_response_stream(
     let remaining = buf.freeze();
 
     // Phase 2: select body-reading strategy.
-    let body_stream = if status == StatusCode::SWITCHING_PROTOCOLS {
+    let body_stream: BodyStream = if is_head {
+        Box::pin(futures_util::stream::empty())
+    } else if status == StatusCode::SWITCHING_PROTOCOLS {
         raw_body_stream(remaining, io)
     } else if is_chunked_encoding(&headers) {
         chunked_body_stream(remaining, io)
@@ -576,6 +630,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn upgrade_head_has_no_body_framing() {
+        let mut headers = HeaderMap::new();
+        headers.insert("upgrade", HeaderValue::from_static("websocket"));
+        headers.insert(http::header::CONNECTION, HeaderValue::from_static("close"));
+        headers.insert(http::header::CONTENT_LENGTH, HeaderValue::from_static("0"));
+        let wire = serialize_upgrade_wire(&Method::GET, "http://example.com/ws", &headers);
+        let text = String::from_utf8_lossy(&wire);
+        assert!(text.starts_with("GET /ws HTTP/1.1\r\n"));
+        assert!(text.contains("upgrade: websocket\r\n"));
+        assert_eq!(text.matches("Connection:").count(), 1);
+        assert!(text.contains("Connection: Upgrade\r\n"));
+        assert!(!text.contains("Content-Length") && !text.contains("Transfer-Encoding"));
+        assert!(text.ends_with("\r\n\r\n"));
+    }
+
+    #[test]
+    fn streaming_uses_chunked_framing() {
+        let wire = serialize_request_wire(
+            &Method::POST,
+            "https://example.com/upload",
+            &HeaderMap::new(),
+            None,
+        );
+        let text = String::from_utf8_lossy(&wire);
+        assert!(text.contains("Transfer-Encoding: chunked\r\n"));
+        assert_eq!(encode_chunk(b"hello"), b"5\r\nhello\r\n");
+        assert_eq!(encode_chunk(&[b'x'; 26])[..4], *b"1a\r\n");
+    }
+
     #[test]
     fn streaming_no_body_bytes() {
         let wire = serialize_request_wire(
@@ -605,7 +689,7 @@ mod tests {
             shut(&mut writer).await;
         });
 
-        let (status, headers, body_stream) = parse_response_stream(reader).await.unwrap();
+        let (status, headers, body_stream) = parse_response_stream(reader, false).await.unwrap();
         assert_eq!(status, StatusCode::OK);
         assert_eq!(
             headers.get("content-length").unwrap().to_str().unwrap(),
@@ -617,6 +701,49 @@ mod tests {
         assert_eq!(all, b"hello world");
     }
 
+    #[tokio::test]
+    async fn parse_response_head_keeps_content_length_without_body() {
+        let (mut writer, reader) = tokio::io::duplex(4096);
+        // Write headers only and keep the writer open: a HEAD response has no
+        // body, so parsing must not wait for the advertised 11 bytes.
+        writer
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 11\r\n\r\n")
+            .await
+            .unwrap();
+
+        let (status, headers, body_stream) = parse_response_stream(reader, true).await.unwrap();
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            headers.get("content-length").unwrap().to_str().unwrap(),
+            "11"
+        );
+
+        let chunks: Vec<Bytes> = body_stream.map(|r| r.unwrap()).collect().await;
+        assert!(chunks.is_empty());
+        drop(writer);
+    }
+
+    #[tokio::test]
+    async fn parse_response_skips_100_continue() {
+        let (mut writer, reader) = tokio::io::duplex(4096);
+        tokio::spawn(async move {
+            writer
+                .write_all(
+                    b"HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 201 Created\r\nContent-Length: 2\r\n\r\nok",
+                )
+                .await
+                .unwrap();
+            shut(&mut writer).await;
+        });
+
+        let (status, _headers, body_stream) = parse_response_stream(reader, false).await.unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+
+        let chunks: Vec<Bytes> = body_stream.map(|r| r.unwrap()).collect().await;
+        let all: Vec<u8> = chunks.iter().flat_map(|c| c.iter().copied()).collect();
+        assert_eq!(all, b"ok");
+    }
+
     #[tokio::test]
     async fn parse_response_chunked() {
         let (mut writer, reader) = tokio::io::duplex(4096);
@@ -631,7 +758,7 @@ mod tests {
             shut(&mut writer).await;
         });
 
-        let (status, _headers, body_stream) = parse_response_stream(reader).await.unwrap();
+        let (status, _headers, body_stream) = parse_response_stream(reader, false).await.unwrap();
         assert_eq!(status, StatusCode::OK);
 
         let chunks: Vec<Bytes> = body_stream.map(|r| r.unwrap()).collect().await;
@@ -653,7 +780,8 @@ mod tests {
             shut(&mut writer).await;
         });
 
-        let (status, _headers, mut body_stream) = parse_response_stream(reader).await.unwrap();
+        let (status, _headers, mut body_stream) =
+            parse_response_stream(reader, false).await.unwrap();
         assert_eq!(status, StatusCode::OK);
 
         // The first (and only) chunk poll should return an error.
@@ -681,7 +809,8 @@ mod tests {
             shut(&mut writer).await;
         });
 
-        let (status, _headers, mut body_stream) = parse_response_stream(reader).await.unwrap();
+        let (status, _headers, mut body_stream) =
+            parse_response_stream(reader, false).await.unwrap();
         assert_eq!(status, StatusCode::OK);
 
         let result = body_stream
@@ -715,7 +844,7 @@ mod tests {
             shut(&mut writer).await;
         });
 
-        let (status, headers, body_stream) = parse_response_stream(reader).await.unwrap();
+        let (status, headers, body_stream) = parse_response_stream(reader, false).await.unwrap();
         assert_eq!(status, StatusCode::SWITCHING_PROTOCOLS);
         assert_eq!(
             headers.get("upgrade").unwrap().to_str().unwrap(),
@@ -741,7 +870,7 @@ mod tests {
             shut(&mut writer).await;
         });
 
-        let (status, _headers, body_stream) = parse_response_stream(reader).await.unwrap();
+        let (status, _headers, body_stream) = parse_response_stream(reader, false).await.unwrap();
         assert_eq!(status, StatusCode::BAD_GATEWAY);
 
         let chunks: Vec<Bytes> = body_stream.map(|r| r.unwrap()).collect().await;
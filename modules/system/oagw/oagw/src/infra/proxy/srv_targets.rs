@@ -0,0 +1,170 @@
+//! Static SRV-style target lists (`srv_targets`): an upstream endpoint host
+//! is replaced by one of its configured `host:port` targets, preferring the
+//! lowest priority value and sharing load by weight within it.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use crate::config::SrvTarget;
+use crate::domain::model::Endpoint;
+
+/// Targets of one endpoint host, grouped by priority.
+struct TargetList {
+    /// Groups in priority order, most preferred first.
+    groups: Vec<Vec<SrvTarget>>,
+    /// Smooth weighted round-robin state for the first group.
+    current: Mutex<Vec<i64>>,
+}
+
+/// Per-host static target lists.
+pub(crate) struct SrvTargets {
+    hosts: HashMap<String, TargetList>,
+}
+
+impl SrvTargets {
+    /// Hosts are matched case-insensitively; empty lists are ignored.
+    pub(crate) fn new(targets: HashMap<String, Vec<SrvTarget>>) -> Self {
+        let hosts = targets
+            .into_iter()
+            .filter(|(_, targets)| !targets.is_empty())
+            .map(|(host, targets)| {
+                let mut by_priority: BTreeMap<u16, Vec<SrvTarget>> = BTreeMap::new();
+                for target in targets {
+                    by_priority.entry(target.priority).or_default().push(target);
+                }
+                let groups: Vec<_> = by_priority.into_values().collect();
+                let current = Mutex::new(vec![0; groups[0].len()]);
+                (host.to_ascii_lowercase(), TargetList { groups, current })
+            })
+            .collect();
+        Self { hosts }
+    }
+
+    /// Resolve `endpoint` against its host's list. Returns the target to
+    /// send to and the lower-priority targets to fail over to, in priority
+    /// order; `None` when the host has no list. The scheme is kept.
+    pub(crate) fn resolve(&self, endpoint: &Endpoint) -> Option<(Endpoint, Vec<Endpoint>)> {
+        let list = self.hosts.get(&endpoint.host.to_ascii_lowercase())?;
+        let to_endpoint = |target: &SrvTarget| Endpoint {
+            scheme: endpoint.scheme,
+            host: target.host.clone(),
+            port: target.port,
+        };
+
+        let preferred = &list.groups[0];
+        let picked = {
+            let mut current = list.current.lock().unwrap_or_else(|e| e.into_inner());
+            pick_weighted(preferred, &mut current)
+        };
+        let fallbacks = list.groups[1..]
+            .iter()
+            .flat_map(|group| {
+                let mut group: Vec<_> = group.iter().collect();
+                group.sort_by_key(|t| std::cmp::Reverse(t.weight));
+                group
+            })
+            .map(to_endpoint)
+            .collect();
+        Some((to_endpoint(&preferred[picked]), fallbacks))
+    }
+}
+
+/// Smooth weighted round-robin: every target gains its weight, the largest
+/// total is picked and pays back the sum of weights. Over any run of
+/// `sum(weights)` picks each target is chosen exactly `weight` times. When
+/// every weight is zero the targets share equally.
+fn pick_weighted(targets: &[SrvTarget], current: &mut [i64]) -> usize {
+    let all_zero = targets.iter().all(|t| t.weight == 0);
+    let weight = |t: &SrvTarget| if all_zero { 1 } else { i64::from(t.weight) };
+    let total: i64 = targets.iter().map(weight).sum();
+
+    let mut best = 0;
+    for (i, target) in targets.iter().enumerate() {
+        current[i] += weight(target);
+        if current[i] > current[best] {
+            best = i;
+        }
+    }
+    current[best] -= total;
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::model::Scheme;
+
+    use super::*;
+
+    fn target(host: &str, priority: u16, weight: u16) -> SrvTarget {
+        SrvTarget {
+            host: host.into(),
+            port: 8443,
+            priority,
+            weight,
+        }
+    }
+
+    fn endpoint(host: &str) -> Endpoint {
+        Endpoint {
+            scheme: Scheme::Https,
+            host: host.into(),
+            port: 443,
+        }
+    }
+
+    fn srv(targets: Vec<SrvTarget>) -> SrvTargets {
+        SrvTargets::new(HashMap::from([("api.internal".to_string(), targets)]))
+    }
+
+    fn picks(srv: &SrvTargets, n: usize) -> Vec<String> {
+        (0..n)
+            .map(|_| srv.resolve(&endpoint("api.internal")).unwrap().0.host)
+            .collect()
+    }
+
+    #[test]
+    fn higher_priority_targets_are_preferred() {
+        let srv = srv(vec![
+            target("backup.internal", 20, 100),
+            target("primary.internal", 10, 1),
+        ]);
+
+        assert!(picks(&srv, 10).iter().all(|h| h == "primary.internal"));
+        let (picked, fallbacks) = srv.resolve(&endpoint("API.internal")).unwrap();
+        assert_eq!((picked.scheme, picked.port), (Scheme::Https, 8443));
+        let fallbacks: Vec<_> = fallbacks.iter().map(|e| e.host.as_str()).collect();
+        assert_eq!(fallbacks, ["backup.internal"]);
+    }
+
+    #[test]
+    fn weights_split_picks_within_a_priority() {
+        let srv = srv(vec![
+            target("a.internal", 0, 3),
+            target("b.internal", 0, 1),
+            target("c.internal", 0, 0),
+            target("d.internal", 5, 9),
+        ]);
+
+        let picks = picks(&srv, 40);
+        let count = |host: &str| picks.iter().filter(|h| *h == host).count();
+        assert_eq!(count("a.internal"), 30);
+        assert_eq!(count("b.internal"), 10);
+        assert_eq!(count("c.internal"), 0);
+        assert_eq!(count("d.internal"), 0);
+    }
+
+    #[test]
+    fn zero_weights_share_equally() {
+        let srv = srv(vec![target("a.internal", 0, 0), target("b.internal", 0, 0)]);
+        assert_eq!(
+            picks(&srv, 4),
+            ["a.internal", "b.internal", "a.internal", "b.internal"]
+        );
+    }
+
+    #[test]
+    fn hosts_without_a_list_are_left_alone() {
+        let srv = srv(vec![target("a.internal", 0, 1)]);
+        assert!(srv.resolve(&endpoint("other.internal")).is_none());
+    }
+}
@@ -0,0 +1,175 @@
+//! Idle timeouts on streamed bodies: graceful termination of server-sent
+//! event streams whose upstream stalls, and aborting request bodies whose
+//! client stalls.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_util::StreamExt;
+use http::HeaderMap;
+use oagw_sdk::body::BodyStream;
+
+/// Event written by default when a stalled stream is cut off.
+pub(crate) const DEFAULT_TERMINAL_EVENT: &str =
+    "event: error\ndata: {\"error\":\"upstream_stream_idle_timeout\"}\n\n";
+
+/// Idle window after which a stalled SSE response is ended, and the event
+/// sent to the client in its place.
+#[derive(Debug, Clone)]
+pub(crate) struct StreamIdleTimeout {
+    pub(crate) timeout: Duration,
+    pub(crate) terminal_event: Bytes,
+}
+
+impl StreamIdleTimeout {
+    /// Whether the response is a server-sent event stream; other streamed
+    /// bodies have no in-band way to signal truncation and are left alone.
+    pub(crate) fn applies_to(headers: &HeaderMap) -> bool {
+        headers
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(';').next())
+            .is_some_and(|v| v.trim().eq_ignore_ascii_case("text/event-stream"))
+    }
+
+    /// Wrap `stream` so that a gap of more than `timeout` between chunks
+    /// ends it with the terminal event instead of hanging or erroring. The
+    /// upstream stream is dropped at that point, closing its connection.
+    pub(crate) fn wrap(&self, stream: BodyStream, instance_uri: String) -> BodyStream {
+        let timeout = self.timeout;
+        let terminal_event = self.terminal_event.clone();
+        Box::pin(futures_util::stream::unfold(Some(stream), move |state| {
+            let terminal_event = terminal_event.clone();
+            let instance_uri = instance_uri.clone();
+            async move {
+                let mut stream = state?;
+                match tokio::time::timeout(timeout, stream.next()).await {
+                    Ok(Some(chunk)) => Some((chunk, Some(stream))),
+                    Ok(None) => None,
+                    Err(_) => {
+                        tracing::warn!(
+                            instance = %instance_uri,
+                            idle_ms = timeout.as_millis(),
+                            "upstream stream idle, truncating with terminal event"
+                        );
+                        Some((Ok(terminal_event), None))
+                    }
+                }
+            }
+        }))
+    }
+}
+
+/// Error ending a request body wrapped by [`wrap_request_body`] once the
+/// client has sent nothing for the idle timeout.
+#[derive(Debug)]
+pub(crate) struct BodyReadIdle(pub(crate) Duration);
+
+impl std::fmt::Display for BodyReadIdle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "no request body data received for {} ms",
+            self.0.as_millis()
+        )
+    }
+}
+
+impl std::error::Error for BodyReadIdle {}
+
+/// Wrap an inbound request body so that a gap of more than `timeout`
+/// between chunks ends it with a [`BodyReadIdle`] error. The client stream
+/// is dropped at that point.
+pub(crate) fn wrap_request_body(stream: BodyStream, timeout: Duration) -> BodyStream {
+    Box::pin(futures_util::stream::unfold(
+        Some(stream),
+        move |state| async move {
+            let mut stream = state?;
+            match tokio::time::timeout(timeout, stream.next()).await {
+                Ok(Some(chunk)) => Some((chunk, Some(stream))),
+                Ok(None) => None,
+                Err(_) => Some((Err(BodyReadIdle(timeout).into()), None)),
+            }
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oagw_sdk::body::BoxError;
+
+    fn idle(ms: u64) -> StreamIdleTimeout {
+        StreamIdleTimeout {
+            timeout: Duration::from_millis(ms),
+            terminal_event: Bytes::from_static(DEFAULT_TERMINAL_EVENT.as_bytes()),
+        }
+    }
+
+    async fn collect(stream: BodyStream) -> Vec<Bytes> {
+        stream.map(Result::unwrap).collect().await
+    }
+
+    #[test]
+    fn applies_only_to_event_streams() {
+        let mut headers = HeaderMap::new();
+        assert!(!StreamIdleTimeout::applies_to(&headers));
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            "text/event-stream; charset=utf-8".parse().unwrap(),
+        );
+        assert!(StreamIdleTimeout::applies_to(&headers));
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            "application/json".parse().unwrap(),
+        );
+        assert!(!StreamIdleTimeout::applies_to(&headers));
+    }
+
+    #[tokio::test]
+    async fn complete_stream_passes_through() {
+        let chunks: Vec<Result<Bytes, BoxError>> = vec![
+            Ok(Bytes::from("data: a\n\n")),
+            Ok(Bytes::from("data: b\n\n")),
+        ];
+        let stream: BodyStream = Box::pin(futures_util::stream::iter(chunks));
+
+        let out = collect(idle(100).wrap(stream, "/test".into())).await;
+
+        assert_eq!(
+            out,
+            vec![Bytes::from("data: a\n\n"), Bytes::from("data: b\n\n")]
+        );
+    }
+
+    #[tokio::test]
+    async fn stalled_request_body_ends_with_idle_error() {
+        let chunks: Vec<Result<Bytes, BoxError>> = vec![Ok(Bytes::from("{\"a\":"))];
+        let stream: BodyStream =
+            Box::pin(futures_util::stream::iter(chunks).chain(futures_util::stream::pending()));
+
+        let mut body = wrap_request_body(stream, Duration::from_millis(50));
+
+        assert_eq!(body.next().await.unwrap().unwrap(), "{\"a\":");
+        let err = body.next().await.unwrap().unwrap_err();
+        assert!(err.downcast_ref::<BodyReadIdle>().is_some(), "{err}");
+        assert!(body.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn stalled_stream_ends_with_terminal_event() {
+        let chunks: Vec<Result<Bytes, BoxError>> = vec![Ok(Bytes::from("data: a\n\n"))];
+        let stream: BodyStream =
+            Box::pin(futures_util::stream::iter(chunks).chain(futures_util::stream::pending()));
+
+        let out = collect(idle(50).wrap(stream, "/test".into())).await;
+
+        assert_eq!(
+            out,
+            vec![
+                Bytes::from("data: a\n\n"),
+                Bytes::from_static(DEFAULT_TERMINAL_EVENT.as_bytes()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Hand-off of upstream response trailers across the Pingora bridge.
+//!
+//! The in-memory bridge speaks HTTP/1.1 to Pingora, whose HTTP/1.1 writer
+//! drops trailers. Instead, the data plane registers a [`Trailers`] handle
+//! per request and names it in an internal request header; the proxy looks
+//! the handle up in `request_filter` and fills it from
+//! `response_trailer_filter`, before the final body chunk is written.
+
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use dashmap::DashMap;
+use oagw_sdk::body::Trailers;
+
+static SLOTS: LazyLock<DashMap<u64, Trailers>> = LazyLock::new(DashMap::new);
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// A registered [`Trailers`] handle, reachable by the proxy until dropped.
+/// Hold it until the response head has been read.
+pub(crate) struct TrailerSlot {
+    id: u64,
+    trailers: Trailers,
+}
+
+impl TrailerSlot {
+    pub(crate) fn register() -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let trailers = Trailers::new();
+        SLOTS.insert(id, trailers.clone());
+        Self { id, trailers }
+    }
+
+    /// Value of the internal header that names this slot.
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub(crate) fn trailers(&self) -> &Trailers {
+        &self.trailers
+    }
+}
+
+impl Drop for TrailerSlot {
+    fn drop(&mut self) {
+        SLOTS.remove(&self.id);
+    }
+}
+
+/// The handle registered under `id`, if its slot is still alive.
+pub(crate) fn lookup(id: u64) -> Option<Trailers> {
+    SLOTS.get(&id).map(|t| t.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderMap;
+
+    #[test]
+    fn slot_is_reachable_until_dropped() {
+        let slot = TrailerSlot::register();
+        let id = slot.id();
+
+        let mut fields = HeaderMap::new();
+        fields.insert("x-checksum", "abc".parse().unwrap());
+        lookup(id).unwrap().set(fields);
+        assert_eq!(
+            slot.trailers().get().unwrap().get("x-checksum").unwrap(),
+            "abc"
+        );
+
+        drop(slot);
+        assert!(lookup(id).is_none());
+    }
+}
@@ -0,0 +1,151 @@
+//! Per-route external transform hook: the request is POSTed to the route's
+//! `transform_hook_url`, which answers with the headers and body to forward
+//! to the upstream instead.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use bytes::Bytes;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+
+/// Largest hook answer read; it carries the base64 body, which the data
+/// plane still checks against the route's body limit once decoded.
+const MAX_HOOK_RESPONSE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Request sent to the hook. `path` is the upstream path with its query;
+/// `body` is base64-encoded.
+#[derive(Serialize)]
+struct HookRequest<'a> {
+    method: &'a str,
+    path: &'a str,
+    headers: BTreeMap<String, String>,
+    body: String,
+}
+
+/// Hook answer. An omitted field keeps the original request's value.
+#[derive(Deserialize)]
+struct HookResponse {
+    #[serde(default)]
+    headers: Option<BTreeMap<String, String>>,
+    #[serde(default)]
+    body: Option<String>,
+}
+
+/// Why a hook call failed; both fail the proxied request.
+#[derive(Debug)]
+pub(crate) enum HookError {
+    /// No answer within the hook timeout.
+    Timeout(Duration),
+    /// Transport error, non-2xx status or malformed answer.
+    Failed(String),
+}
+
+/// Calls transform hooks with a shared HTTP client.
+pub(crate) struct TransformHook {
+    timeout: Duration,
+    client: OnceLock<modkit_http::HttpClient>,
+}
+
+impl TransformHook {
+    pub(crate) fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            client: OnceLock::new(),
+        }
+    }
+
+    fn client(&self) -> Result<&modkit_http::HttpClient, HookError> {
+        if let Some(client) = self.client.get() {
+            return Ok(client);
+        }
+        let mut config = modkit_http::HttpClientConfig::minimal();
+        // The hook timeout below is authoritative; the client's own one
+        // only backs it up.
+        config.request_timeout = self.timeout.saturating_add(Duration::from_secs(1));
+        config.max_body_size = MAX_HOOK_RESPONSE_BYTES;
+        config.redirect = modkit_http::RedirectConfig::disabled();
+        let client = modkit_http::HttpClientBuilder::with_config(config)
+            .build()
+            .map_err(|e| HookError::Failed(format!("transform hook client: {e}")))?;
+        Ok(self.client.get_or_init(|| client))
+    }
+
+    /// Send the request to `url` and apply the hook's answer to `headers`
+    /// and `body`. Multiple values of a header are joined with `, `.
+    pub(crate) async fn transform(
+        &self,
+        url: &str,
+        method: &str,
+        path: &str,
+        headers: &mut HeaderMap,
+        body: &mut Bytes,
+    ) -> Result<(), HookError> {
+        let mut hook_headers = BTreeMap::<String, String>::new();
+        for (name, value) in headers.iter() {
+            let Ok(value) = value.to_str() else { continue };
+            hook_headers
+                .entry(name.as_str().to_string())
+                .and_modify(|v| {
+                    v.push_str(", ");
+                    v.push_str(value);
+                })
+                .or_insert_with(|| value.to_string());
+        }
+        let request = HookRequest {
+            method,
+            path,
+            headers: hook_headers,
+            body: STANDARD.encode(&body[..]),
+        };
+
+        let call = async {
+            let response = self
+                .client()?
+                .post(url)
+                .json(&request)
+                .map_err(|e| HookError::Failed(format!("transform hook request: {e}")))?
+                .send()
+                .await
+                .map_err(|e| HookError::Failed(format!("transform hook call failed: {e}")))?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(HookError::Failed(format!(
+                    "transform hook answered {status}"
+                )));
+            }
+            response
+                .json::<HookResponse>()
+                .await
+                .map_err(|e| HookError::Failed(format!("invalid transform hook response: {e}")))
+        };
+        let answer = tokio::time::timeout(self.timeout, call)
+            .await
+            .map_err(|_| HookError::Timeout(self.timeout))??;
+
+        if let Some(new_body) = answer.body {
+            *body = STANDARD.decode(new_body).map(Bytes::from).map_err(|e| {
+                HookError::Failed(format!("transform hook body is not base64: {e}"))
+            })?;
+        }
+        if let Some(new_headers) = answer.headers {
+            let mut replaced = HeaderMap::with_capacity(new_headers.len());
+            for (name, value) in new_headers {
+                let name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+                    HookError::Failed(format!("transform hook header name '{name}' is invalid"))
+                })?;
+                let value = HeaderValue::from_str(&value).map_err(|_| {
+                    HookError::Failed(format!(
+                        "transform hook header '{name}' has an invalid value"
+                    ))
+                })?;
+                replaced.insert(name, value);
+            }
+            *headers = replaced;
+        }
+        Ok(())
+    }
+}
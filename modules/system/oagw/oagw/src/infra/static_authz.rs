@@ -0,0 +1,40 @@
+//! Built-in AuthZ resolver for deployments running without an external one
+//! (`authz_mode = allow_all | deny_all`).
+
+use async_trait::async_trait;
+use authz_resolver_sdk::models::DenyReason;
+use authz_resolver_sdk::{
+    AuthZResolverClient, AuthZResolverError, EvaluationRequest, EvaluationResponse,
+    EvaluationResponseContext,
+};
+
+/// Resolver answering every evaluation with the same decision and no
+/// constraints.
+pub(crate) struct StaticAuthZResolverClient {
+    allow: bool,
+}
+
+impl StaticAuthZResolverClient {
+    pub(crate) fn new(allow: bool) -> Self {
+        Self { allow }
+    }
+}
+
+#[async_trait]
+impl AuthZResolverClient for StaticAuthZResolverClient {
+    async fn evaluate(
+        &self,
+        _request: EvaluationRequest,
+    ) -> Result<EvaluationResponse, AuthZResolverError> {
+        Ok(EvaluationResponse {
+            decision: self.allow,
+            context: EvaluationResponseContext {
+                constraints: Vec::new(),
+                deny_reason: (!self.allow).then(|| DenyReason {
+                    error_code: "authz_mode_deny_all".into(),
+                    details: Some("all requests are denied by authz_mode = deny_all".into()),
+                }),
+            },
+        })
+    }
+}
@@ -1,5 +1,9 @@
 pub(crate) mod route_repo;
+pub(crate) mod tenant_auth_default_repo;
+pub(crate) mod tenant_rate_limit_repo;
 pub(crate) mod upstream_repo;
 
 pub(crate) use route_repo::InMemoryRouteRepo;
+pub(crate) use tenant_auth_default_repo::InMemoryTenantAuthDefaultRepo;
+pub(crate) use tenant_rate_limit_repo::InMemoryTenantRateLimitRepo;
 pub(crate) use upstream_repo::InMemoryUpstreamRepo;
@@ -1,5 +1,6 @@
 use crate::domain::model::{HttpMethod, ListQuery, Route};
 use crate::domain::repo::{RepositoryError, RouteRepository};
+use crate::infra::proxy::body_retry::CompiledBodyMatcher;
 use async_trait::async_trait;
 use dashmap::DashMap;
 use modkit_macros::domain_model;
@@ -24,6 +25,41 @@ impl InMemoryRouteRepo {
     }
 }
 
+impl InMemoryRouteRepo {
+    /// The first of `ids` that `route` would be ambiguous with.
+    fn find_ambiguous(&self, ids: &[Uuid], route: &Route) -> Option<Uuid> {
+        ids.iter().copied().find(|id| {
+            self.store
+                .get(id)
+                .is_some_and(|other| ambiguous(route, &other))
+        })
+    }
+}
+
+/// Whether `a` and `b` are distinct enabled routes of one tenant that match
+/// some request equally well: same priority, path prefix and body
+/// condition, and a method in common.
+fn ambiguous(a: &Route, b: &Route) -> bool {
+    let (Some(a_http), Some(b_http)) = (&a.match_rules.http, &b.match_rules.http) else {
+        return false;
+    };
+    a.id != b.id
+        && a.tenant_id == b.tenant_id
+        && a.enabled
+        && b.enabled
+        && a.priority == b.priority
+        && a_http.path == b_http.path
+        && a_http.match_body == b_http.match_body
+        && a_http.methods.iter().any(|m| b_http.methods.contains(m))
+}
+
+fn ambiguity_conflict(route: &Route, other: Uuid) -> RepositoryError {
+    RepositoryError::Conflict(format!(
+        "route {other} already matches the same path, methods and body condition at priority {}",
+        route.priority
+    ))
+}
+
 impl Default for InMemoryRouteRepo {
     fn default() -> Self {
         Self::new()
@@ -36,13 +72,14 @@ impl RouteRepository for InMemoryRouteRepo {
         let route_id = route.id;
         let upstream_id = route.upstream_id;
 
+        // Holding the upstream's index entry makes the ambiguity check and
+        // the insert atomic.
+        let mut ids = self.upstream_index.entry(upstream_id).or_default();
+        if let Some(other) = self.find_ambiguous(&ids, &route) {
+            return Err(ambiguity_conflict(&route, other));
+        }
         self.store.insert(route_id, route.clone());
-
-        // Update upstream index.
-        self.upstream_index
-            .entry(upstream_id)
-            .or_default()
-            .push(route_id);
+        ids.push(route_id);
 
         Ok(route)
     }
@@ -93,6 +130,7 @@ impl RouteRepository for InMemoryRouteRepo {
         upstream_id: Uuid,
         method: &str,
         path: &str,
+        body: Option<&[u8]>,
     ) -> Result<Route, RepositoryError> {
         let route_ids: Vec<Uuid> = self
             .upstream_index
@@ -100,11 +138,10 @@ impl RouteRepository for InMemoryRouteRepo {
             .map(|ids| ids.clone())
             .unwrap_or_default();
 
-        let request_method = parse_method(method);
+        let request_method = HttpMethod::parse(method);
 
         let mut best: Option<Route> = None;
-        let mut best_path_len = 0;
-        let mut best_priority = i32::MIN;
+        let mut best_rank = (i32::MIN, 0, false);
 
         for id in &route_ids {
             let Some(route_ref) = self.store.get(id) else {
@@ -135,14 +172,23 @@ impl RouteRepository for InMemoryRouteRepo {
             if !path.starts_with(&http_match.path) {
                 continue;
             }
+            // Body condition must hold, or be undecidable yet.
+            if let (Some(matcher), Some(body)) = (&http_match.match_body, body)
+                && !CompiledBodyMatcher::new(matcher).is_some_and(|m| m.matches(body))
+            {
+                continue;
+            }
 
-            let path_len = http_match.path.len();
-            let priority = route.priority;
-
-            // Select by longest path prefix, then highest priority.
-            if path_len > best_path_len || (path_len == best_path_len && priority > best_priority) {
-                best_path_len = path_len;
-                best_priority = priority;
+            // Select by highest priority, then longest path prefix, then
+            // body-conditioned routes (more specific). Ties go to the route
+            // created first.
+            let rank = (
+                route.priority,
+                http_match.path.len(),
+                http_match.match_body.is_some(),
+            );
+            if best.is_none() || rank > best_rank {
+                best_rank = rank;
                 best = Some(route.clone());
             }
         }
@@ -160,6 +206,14 @@ impl RouteRepository for InMemoryRouteRepo {
                 id: route.id,
             });
         }
+        // Held until the insert, as in `create`.
+        let ids = self.upstream_index.get(&route.upstream_id);
+        if let Some(other) = ids
+            .as_deref()
+            .and_then(|ids| self.find_ambiguous(ids, &route))
+        {
+            return Err(ambiguity_conflict(&route, other));
+        }
         self.store.insert(route.id, route.clone());
         Ok(route)
     }
@@ -217,22 +271,21 @@ impl RouteRepository for InMemoryRouteRepo {
 
         Ok(deleted)
     }
-}
 
-fn parse_method(s: &str) -> Option<HttpMethod> {
-    match s.to_uppercase().as_str() {
-        "GET" => Some(HttpMethod::Get),
-        "POST" => Some(HttpMethod::Post),
-        "PUT" => Some(HttpMethod::Put),
-        "DELETE" => Some(HttpMethod::Delete),
-        "PATCH" => Some(HttpMethod::Patch),
-        _ => None,
+    async fn count(&self, tenant_id: Uuid) -> Result<usize, RepositoryError> {
+        Ok(self
+            .store
+            .iter()
+            .filter(|e| e.value().tenant_id == tenant_id)
+            .count())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::model::{HttpMatch, MatchRules, PathSuffixMode};
+    use std::collections::HashMap;
+
+    use crate::domain::model::{BodyMatcher, HttpMatch, MatchRules, PathSuffixMode, RouteKind};
 
     use super::*;
 
@@ -253,6 +306,7 @@ mod tests {
                     path: path.into(),
                     query_allowlist: vec![],
                     path_suffix_mode: PathSuffixMode::Append,
+                    match_body: None,
                 }),
                 grpc: None,
             },
@@ -261,6 +315,32 @@ mod tests {
             tags: vec![],
             priority,
             enabled: true,
+            skip_authz: false,
+            timeout_secs: None,
+            status_remap: HashMap::new(),
+            allowed_response_content_types: vec![],
+            allowed_request_content_types: vec![],
+            log_sample_rate: None,
+            request_schema: None,
+            body_rewrite: vec![],
+            auth_rules: vec![],
+            strip_query_params: vec![],
+            strip_inbound_headers: vec![],
+            idempotency_ttl_secs: None,
+            cache_responses: false,
+            negative_cache_ttl_secs: None,
+            response_header_allowlist: None,
+            max_total_duration_ms: None,
+            max_body_size_bytes: None,
+            max_outbound_calls: None,
+            coerce_content_type: None,
+            api_version: None,
+            body_transformers: vec![],
+            log_redact_paths: vec![],
+            transform_hook_url: None,
+            deprecated: false,
+            sunset: None,
+            kind: RouteKind::Proxy,
         }
     }
 
@@ -282,7 +362,7 @@ mod tests {
         repo.create(long.clone()).await.unwrap();
 
         let matched = repo
-            .find_matching(tenant, upstream, "POST", "/v1/chat/completions")
+            .find_matching(tenant, upstream, "POST", "/v1/chat/completions", None)
             .await
             .unwrap();
         assert_eq!(matched.id, long.id);
@@ -300,12 +380,98 @@ mod tests {
         repo.create(high.clone()).await.unwrap();
 
         let matched = repo
-            .find_matching(tenant, upstream, "POST", "/v1/chat/completions")
+            .find_matching(tenant, upstream, "POST", "/v1/chat/completions", None)
             .await
             .unwrap();
         assert_eq!(matched.id, high.id);
     }
 
+    #[tokio::test]
+    async fn find_matching_priority_beats_longer_prefix() {
+        let repo = InMemoryRouteRepo::new();
+        let tenant = Uuid::new_v4();
+        let upstream = Uuid::new_v4();
+
+        let broad = make_route(tenant, upstream, vec![HttpMethod::Post], "/v1", 5);
+        let specific = make_route(
+            tenant,
+            upstream,
+            vec![HttpMethod::Post],
+            "/v1/chat/completions",
+            0,
+        );
+        repo.create(specific).await.unwrap();
+        repo.create(broad.clone()).await.unwrap();
+
+        let matched = repo
+            .find_matching(tenant, upstream, "POST", "/v1/chat/completions", None)
+            .await
+            .unwrap();
+        assert_eq!(matched.id, broad.id);
+    }
+
+    #[tokio::test]
+    async fn create_rejects_exact_duplicate() {
+        let repo = InMemoryRouteRepo::new();
+        let tenant = Uuid::new_v4();
+        let upstream = Uuid::new_v4();
+
+        let first = make_route(tenant, upstream, vec![HttpMethod::Post], "/v1/chat", 0);
+        repo.create(first.clone()).await.unwrap();
+
+        // Overlapping methods on the same path and priority are ambiguous.
+        let duplicate = make_route(
+            tenant,
+            upstream,
+            vec![HttpMethod::Get, HttpMethod::Post],
+            "/v1/chat",
+            0,
+        );
+        let err = repo.create(duplicate.clone()).await.unwrap_err();
+        assert!(
+            matches!(&err, RepositoryError::Conflict(msg) if msg.contains(&first.id.to_string())),
+            "{err:?}"
+        );
+        assert!(repo.get_by_id(tenant, duplicate.id).await.is_err());
+
+        // A different method, priority or tenant disambiguates, and a
+        // disabled route never matches.
+        repo.create(make_route(
+            tenant,
+            upstream,
+            vec![HttpMethod::Get],
+            "/v1/chat",
+            0,
+        ))
+        .await
+        .unwrap();
+        repo.create(make_route(
+            tenant,
+            upstream,
+            vec![HttpMethod::Post],
+            "/v1/chat",
+            1,
+        ))
+        .await
+        .unwrap();
+        let other_tenant = make_route(
+            Uuid::new_v4(),
+            upstream,
+            vec![HttpMethod::Post],
+            "/v1/chat",
+            0,
+        );
+        repo.create(other_tenant).await.unwrap();
+        let mut disabled = make_route(tenant, upstream, vec![HttpMethod::Post], "/v1/chat", 0);
+        disabled.enabled = false;
+        repo.create(disabled.clone()).await.unwrap();
+
+        // Enabling it would make it ambiguous again.
+        disabled.enabled = true;
+        let err = repo.update(disabled).await.unwrap_err();
+        assert!(matches!(err, RepositoryError::Conflict(_)), "{err:?}");
+    }
+
     #[tokio::test]
     async fn find_matching_method_mismatch_excluded() {
         let repo = InMemoryRouteRepo::new();
@@ -322,7 +488,7 @@ mod tests {
         repo.create(post_only).await.unwrap();
 
         let result = repo
-            .find_matching(tenant, upstream, "GET", "/v1/chat/completions")
+            .find_matching(tenant, upstream, "GET", "/v1/chat/completions", None)
             .await;
         assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
     }
@@ -338,7 +504,7 @@ mod tests {
         repo.create(route).await.unwrap();
 
         let result = repo
-            .find_matching(tenant, upstream, "POST", "/v1/chat/completions")
+            .find_matching(tenant, upstream, "POST", "/v1/chat/completions", None)
             .await;
         assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
     }
@@ -359,7 +525,83 @@ mod tests {
         repo.create(post_only).await.unwrap();
 
         let result = repo
-            .find_matching(tenant, upstream, "HEAD", "/v1/chat/completions")
+            .find_matching(tenant, upstream, "HEAD", "/v1/chat/completions", None)
+            .await;
+        assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn find_matching_head_matches_get_route() {
+        let repo = InMemoryRouteRepo::new();
+        let tenant = Uuid::new_v4();
+        let upstream = Uuid::new_v4();
+
+        let get_route = make_route(tenant, upstream, vec![HttpMethod::Get], "/v1/models", 0);
+        let get_id = get_route.id;
+        repo.create(get_route).await.unwrap();
+
+        let found = repo
+            .find_matching(tenant, upstream, "HEAD", "/v1/models", None)
+            .await
+            .unwrap();
+        assert_eq!(found.id, get_id);
+    }
+
+    fn with_match_body(mut route: Route, model: &str) -> Route {
+        if let Some(http) = route.match_rules.http.as_mut() {
+            http.match_body = Some(BodyMatcher::JsonPointer {
+                pointer: "/model".into(),
+                value: serde_json::json!(model),
+            });
+        }
+        route
+    }
+
+    #[tokio::test]
+    async fn find_matching_selects_route_by_body_field() {
+        let repo = InMemoryRouteRepo::new();
+        let tenant = Uuid::new_v4();
+        let upstream = Uuid::new_v4();
+
+        let fallback = make_route(tenant, upstream, vec![HttpMethod::Post], "/v1/chat", 0);
+        let gpt = with_match_body(
+            make_route(tenant, upstream, vec![HttpMethod::Post], "/v1/chat", 0),
+            "gpt-4",
+        );
+        repo.create(fallback.clone()).await.unwrap();
+        repo.create(gpt.clone()).await.unwrap();
+
+        let find = |body: Option<&'static [u8]>| {
+            repo.find_matching(tenant, upstream, "POST", "/v1/chat", body)
+        };
+        // At equal priority a satisfied body condition beats a plain route.
+        assert_eq!(
+            find(Some(br#"{"model":"gpt-4"}"#)).await.unwrap().id,
+            gpt.id
+        );
+        assert_eq!(
+            find(Some(br#"{"model":"llama-3"}"#)).await.unwrap().id,
+            fallback.id
+        );
+        assert_eq!(find(Some(b"not json")).await.unwrap().id, fallback.id);
+        // Without a buffered body the condition is assumed to hold.
+        assert_eq!(find(None).await.unwrap().id, gpt.id);
+    }
+
+    #[tokio::test]
+    async fn find_matching_unmatched_body_condition_is_not_found() {
+        let repo = InMemoryRouteRepo::new();
+        let tenant = Uuid::new_v4();
+        let upstream = Uuid::new_v4();
+        repo.create(with_match_body(
+            make_route(tenant, upstream, vec![HttpMethod::Post], "/v1/chat", 0),
+            "gpt-4",
+        ))
+        .await
+        .unwrap();
+
+        let result = repo
+            .find_matching(tenant, upstream, "POST", "/v1/chat", Some(b"{}"))
             .await;
         assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
     }
@@ -464,7 +706,7 @@ mod tests {
 
         // tenant_b's route still findable via find_matching.
         let matched = repo
-            .find_matching(tenant_b, upstream, "GET", "/v1/models")
+            .find_matching(tenant_b, upstream, "GET", "/v1/models", None)
             .await
             .unwrap();
         assert_eq!(matched.id, route_b.id);
@@ -0,0 +1,95 @@
+use crate::domain::model::TenantAuthDefault;
+use crate::domain::repo::{RepositoryError, TenantAuthDefaultRepository};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use modkit_macros::domain_model;
+use uuid::Uuid;
+
+/// In-memory tenant default auth repository backed by `DashMap`.
+#[domain_model]
+pub struct InMemoryTenantAuthDefaultRepo {
+    /// Primary store: tenant_id -> TenantAuthDefault.
+    store: DashMap<Uuid, TenantAuthDefault>,
+}
+
+impl InMemoryTenantAuthDefaultRepo {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            store: DashMap::new(),
+        }
+    }
+}
+
+impl Default for InMemoryTenantAuthDefaultRepo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TenantAuthDefaultRepository for InMemoryTenantAuthDefaultRepo {
+    async fn upsert(
+        &self,
+        default: TenantAuthDefault,
+    ) -> Result<TenantAuthDefault, RepositoryError> {
+        self.store.insert(default.tenant_id, default.clone());
+        Ok(default)
+    }
+
+    async fn get(&self, tenant_id: Uuid) -> Result<TenantAuthDefault, RepositoryError> {
+        self.store
+            .get(&tenant_id)
+            .map(|d| d.clone())
+            .ok_or(RepositoryError::NotFound {
+                entity: "tenant_auth_default",
+                id: tenant_id,
+            })
+    }
+
+    async fn delete(&self, tenant_id: Uuid) -> Result<(), RepositoryError> {
+        self.store
+            .remove(&tenant_id)
+            .map(|_| ())
+            .ok_or(RepositoryError::NotFound {
+                entity: "tenant_auth_default",
+                id: tenant_id,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::model::{AuthConfig, SharingMode};
+
+    use super::*;
+
+    fn make_default(tenant_id: Uuid, plugin_type: &str) -> TenantAuthDefault {
+        TenantAuthDefault {
+            tenant_id,
+            auth: AuthConfig {
+                plugin_type: plugin_type.into(),
+                sharing: SharingMode::Private,
+                config: None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn upsert_replaces_and_delete_removes() {
+        let repo = InMemoryTenantAuthDefaultRepo::new();
+        let (tenant, other) = (Uuid::new_v4(), Uuid::new_v4());
+
+        repo.upsert(make_default(tenant, "first")).await.unwrap();
+        repo.upsert(make_default(tenant, "second")).await.unwrap();
+        repo.upsert(make_default(other, "other")).await.unwrap();
+
+        let got = repo.get(tenant).await.unwrap();
+        assert_eq!(got.auth.plugin_type, "second");
+
+        repo.delete(tenant).await.unwrap();
+        assert!(repo.get(tenant).await.is_err());
+        assert!(repo.delete(tenant).await.is_err());
+        assert!(repo.get(other).await.is_ok());
+    }
+}
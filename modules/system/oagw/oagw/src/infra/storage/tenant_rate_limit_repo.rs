@@ -0,0 +1,123 @@
+use crate::domain::model::TenantRateLimit;
+use crate::domain::repo::{RepositoryError, TenantRateLimitRepository};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use modkit_macros::domain_model;
+use uuid::Uuid;
+
+/// In-memory tenant rate limit repository backed by `DashMap`.
+#[domain_model]
+pub struct InMemoryTenantRateLimitRepo {
+    /// Primary store: (upstream_id, tenant_id) -> TenantRateLimit.
+    store: DashMap<(Uuid, Uuid), TenantRateLimit>,
+}
+
+impl InMemoryTenantRateLimitRepo {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            store: DashMap::new(),
+        }
+    }
+}
+
+impl Default for InMemoryTenantRateLimitRepo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TenantRateLimitRepository for InMemoryTenantRateLimitRepo {
+    async fn upsert(&self, limit: TenantRateLimit) -> Result<TenantRateLimit, RepositoryError> {
+        self.store
+            .insert((limit.upstream_id, limit.tenant_id), limit.clone());
+        Ok(limit)
+    }
+
+    async fn get(
+        &self,
+        upstream_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<TenantRateLimit, RepositoryError> {
+        self.store
+            .get(&(upstream_id, tenant_id))
+            .map(|l| l.clone())
+            .ok_or(RepositoryError::NotFound {
+                entity: "tenant_rate_limit",
+                id: tenant_id,
+            })
+    }
+
+    async fn delete(&self, upstream_id: Uuid, tenant_id: Uuid) -> Result<(), RepositoryError> {
+        self.store
+            .remove(&(upstream_id, tenant_id))
+            .map(|_| ())
+            .ok_or(RepositoryError::NotFound {
+                entity: "tenant_rate_limit",
+                id: tenant_id,
+            })
+    }
+
+    async fn delete_by_upstream(&self, upstream_id: Uuid) -> Result<u64, RepositoryError> {
+        let before = self.store.len();
+        self.store.retain(|(u, _), _| *u != upstream_id);
+        Ok((before - self.store.len()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::model::{
+        RateLimitAlgorithm, RateLimitConfig, RateLimitScope, RateLimitStrategy, SharingMode,
+        SustainedRate, Window,
+    };
+
+    use super::*;
+
+    fn make_limit(upstream_id: Uuid, tenant_id: Uuid, rate: u32) -> TenantRateLimit {
+        TenantRateLimit {
+            upstream_id,
+            tenant_id,
+            rate_limit: RateLimitConfig {
+                sharing: SharingMode::Private,
+                algorithm: RateLimitAlgorithm::TokenBucket,
+                sustained: SustainedRate {
+                    rate,
+                    window: Window::Second,
+                },
+                burst: None,
+                scope: RateLimitScope::Tenant,
+                strategy: RateLimitStrategy::Reject,
+                cost: 1,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn upsert_replaces_existing_policy() {
+        let repo = InMemoryTenantRateLimitRepo::new();
+        let (upstream, tenant) = (Uuid::new_v4(), Uuid::new_v4());
+
+        repo.upsert(make_limit(upstream, tenant, 10)).await.unwrap();
+        repo.upsert(make_limit(upstream, tenant, 2)).await.unwrap();
+
+        let got = repo.get(upstream, tenant).await.unwrap();
+        assert_eq!(got.rate_limit.sustained.rate, 2);
+        assert!(repo.get(upstream, Uuid::new_v4()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn delete_by_upstream_only_touches_that_upstream() {
+        let repo = InMemoryTenantRateLimitRepo::new();
+        let (a, b, tenant) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        repo.upsert(make_limit(a, tenant, 1)).await.unwrap();
+        repo.upsert(make_limit(a, Uuid::new_v4(), 1)).await.unwrap();
+        repo.upsert(make_limit(b, tenant, 1)).await.unwrap();
+
+        assert_eq!(repo.delete_by_upstream(a).await.unwrap(), 2);
+        assert!(repo.get(a, tenant).await.is_err());
+        assert!(repo.get(b, tenant).await.is_ok());
+        assert!(repo.delete(a, tenant).await.is_err());
+    }
+}
@@ -152,11 +152,19 @@ impl UpstreamRepository for InMemoryUpstreamRepo {
         self.alias_index.remove(&(tenant_id, upstream.alias));
         Ok(())
     }
+
+    async fn count(&self, tenant_id: Uuid) -> Result<usize, RepositoryError> {
+        Ok(self
+            .store
+            .iter()
+            .filter(|e| e.value().tenant_id == tenant_id)
+            .count())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::model::{Endpoint, Scheme, Server};
+    use crate::domain::model::{Endpoint, Scheme, Server, TrailingSlash};
 
     use super::*;
 
@@ -179,6 +187,23 @@ mod tests {
             plugins: None,
             rate_limit: None,
             tags: vec![],
+            maintenance: false,
+            maintenance_message: None,
+            retry_on_body: None,
+            force_chunked: false,
+            failover: vec![],
+            timeout_secs: None,
+            keepalive: None,
+            circuit_breaker: None,
+            user_agent: None,
+            sticky_by: None,
+            allowed_methods: vec![],
+            trailing_slash: TrailingSlash::default(),
+            max_header_size_bytes: None,
+            tls_min_version: None,
+            tls_cipher_suites: vec![],
+            tls_server_name: None,
+            budget_header: None,
         }
     }
 
@@ -38,6 +38,10 @@ fn default_cost() -> u32 {
     1
 }
 
+fn default_body_retries() -> u32 {
+    1
+}
+
 #[derive(Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 enum Scheme {
@@ -198,6 +202,87 @@ struct RateLimitConfig {
     cost: u32,
 }
 
+#[derive(Deserialize)]
+struct KeepaliveConfig {
+    #[serde(default)]
+    tcp_idle_secs: Option<u64>,
+    #[serde(default)]
+    tcp_interval_secs: Option<u64>,
+    #[serde(default)]
+    tcp_probe_count: Option<u32>,
+    #[serde(default)]
+    h2_ping_interval_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct CircuitBreakerConfig {
+    #[serde(default)]
+    failure_threshold: Option<u32>,
+    #[serde(default)]
+    cooldown_secs: Option<u64>,
+    #[serde(default)]
+    half_open_probes: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct BodyRewriteRule {
+    find: String,
+    replace: String,
+    #[serde(default)]
+    regex: bool,
+    #[serde(default)]
+    content_types: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct RetryOnBody {
+    #[serde(rename = "match")]
+    matcher: BodyMatcher,
+    #[serde(default = "default_body_retries")]
+    max_retries: u32,
+}
+
+#[derive(Deserialize)]
+struct ApiVersion {
+    value: String,
+    #[serde(default)]
+    header: Option<String>,
+    #[serde(default)]
+    query_param: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AuthRule {
+    condition: AuthCondition,
+    #[serde(default)]
+    auth: Option<AuthConfig>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AuthCondition {
+    PathPrefix {
+        prefix: String,
+    },
+    Header {
+        name: String,
+        #[serde(default)]
+        value: Option<String>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum BodyMatcher {
+    JsonPointer {
+        pointer: String,
+        value: serde_json::Value,
+    },
+    Regex {
+        pattern: String,
+    },
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 enum HttpMethod {
@@ -216,6 +301,15 @@ enum PathSuffixMode {
     Append,
 }
 
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum TrailingSlash {
+    #[default]
+    Preserve,
+    Strip,
+    Add,
+}
+
 #[derive(Deserialize)]
 struct HttpMatch {
     methods: Vec<HttpMethod>,
@@ -224,6 +318,8 @@ struct HttpMatch {
     query_allowlist: Vec<String>,
     #[serde(default)]
     path_suffix_mode: PathSuffixMode,
+    #[serde(default)]
+    match_body: Option<BodyMatcher>,
 }
 
 #[derive(Deserialize)]
@@ -240,6 +336,25 @@ struct MatchRules {
     grpc: Option<GrpcMatch>,
 }
 
+#[derive(Deserialize, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RouteKind {
+    #[default]
+    Proxy,
+    Static {
+        #[serde(default = "default_static_status")]
+        status: u16,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+        #[serde(default)]
+        body: String,
+    },
+}
+
+fn default_static_status() -> u16 {
+    200
+}
+
 /// Intermediate serde struct for deserializing upstream GTS entity content.
 #[derive(Deserialize)]
 struct UpstreamPayload {
@@ -260,12 +375,47 @@ struct UpstreamPayload {
     tags: Vec<String>,
     #[serde(default = "default_true")]
     enabled: bool,
+    #[serde(default)]
+    maintenance: bool,
+    #[serde(default)]
+    maintenance_message: Option<String>,
+    #[serde(default)]
+    retry_on_body: Option<RetryOnBody>,
+    #[serde(default)]
+    force_chunked: bool,
+    #[serde(default)]
+    failover: Vec<Endpoint>,
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+    #[serde(default)]
+    keepalive: Option<KeepaliveConfig>,
+    #[serde(default)]
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default)]
+    user_agent: Option<String>,
+    #[serde(default)]
+    sticky_by: Option<String>,
+    #[serde(default)]
+    allowed_methods: Vec<HttpMethod>,
+    #[serde(default)]
+    trailing_slash: TrailingSlash,
+    #[serde(default)]
+    max_header_size_bytes: Option<u64>,
+    #[serde(default)]
+    tls_min_version: Option<String>,
+    #[serde(default)]
+    tls_cipher_suites: Vec<String>,
+    #[serde(default)]
+    tls_server_name: Option<String>,
+    #[serde(default)]
+    budget_header: Option<String>,
 }
 
 /// Intermediate serde struct for deserializing route GTS entity content.
 #[derive(Deserialize)]
 struct RoutePayload {
     tenant_id: Uuid,
+    #[serde(default)]
     upstream_id: Uuid,
     #[serde(rename = "match")]
     match_rules: MatchRules,
@@ -279,6 +429,58 @@ struct RoutePayload {
     priority: i32,
     #[serde(default = "default_true")]
     enabled: bool,
+    #[serde(default)]
+    skip_authz: bool,
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+    #[serde(default)]
+    status_remap: HashMap<u16, u16>,
+    #[serde(default)]
+    allowed_response_content_types: Vec<String>,
+    #[serde(default)]
+    allowed_request_content_types: Vec<String>,
+    #[serde(default)]
+    log_sample_rate: Option<f64>,
+    #[serde(default)]
+    request_schema: Option<serde_json::Value>,
+    #[serde(default)]
+    body_rewrite: Vec<BodyRewriteRule>,
+    #[serde(default)]
+    auth_rules: Vec<AuthRule>,
+    #[serde(default)]
+    strip_query_params: Vec<String>,
+    #[serde(default)]
+    strip_inbound_headers: Vec<String>,
+    #[serde(default)]
+    idempotency_ttl_secs: Option<u64>,
+    #[serde(default)]
+    cache_responses: bool,
+    #[serde(default)]
+    negative_cache_ttl_secs: Option<u64>,
+    #[serde(default)]
+    response_header_allowlist: Option<Vec<String>>,
+    #[serde(default)]
+    max_total_duration_ms: Option<u64>,
+    #[serde(default)]
+    max_body_size_bytes: Option<u64>,
+    #[serde(default)]
+    max_outbound_calls: Option<u32>,
+    #[serde(default)]
+    coerce_content_type: Option<String>,
+    #[serde(default)]
+    api_version: Option<ApiVersion>,
+    #[serde(default)]
+    body_transformers: Vec<String>,
+    #[serde(default)]
+    log_redact_paths: Vec<String>,
+    #[serde(default)]
+    transform_hook_url: Option<String>,
+    #[serde(default)]
+    deprecated: bool,
+    #[serde(default)]
+    sunset: Option<String>,
+    #[serde(default)]
+    kind: RouteKind,
 }
 
 // ---------------------------------------------------------------------------
@@ -449,6 +651,99 @@ impl From<RateLimitConfig> for domain::RateLimitConfig {
     }
 }
 
+impl From<CircuitBreakerConfig> for domain::CircuitBreakerConfig {
+    fn from(v: CircuitBreakerConfig) -> Self {
+        Self {
+            failure_threshold: v.failure_threshold,
+            cooldown_secs: v.cooldown_secs,
+            half_open_probes: v.half_open_probes,
+        }
+    }
+}
+
+impl From<KeepaliveConfig> for domain::KeepaliveConfig {
+    fn from(v: KeepaliveConfig) -> Self {
+        Self {
+            tcp_idle_secs: v.tcp_idle_secs,
+            tcp_interval_secs: v.tcp_interval_secs,
+            tcp_probe_count: v.tcp_probe_count,
+            h2_ping_interval_secs: v.h2_ping_interval_secs,
+        }
+    }
+}
+
+impl From<BodyRewriteRule> for domain::BodyRewriteRule {
+    fn from(v: BodyRewriteRule) -> Self {
+        Self {
+            find: v.find,
+            replace: v.replace,
+            regex: v.regex,
+            content_types: v.content_types,
+        }
+    }
+}
+
+impl From<RetryOnBody> for domain::RetryOnBody {
+    fn from(v: RetryOnBody) -> Self {
+        Self {
+            matcher: v.matcher.into(),
+            max_retries: v.max_retries,
+        }
+    }
+}
+
+impl From<RouteKind> for domain::RouteKind {
+    fn from(v: RouteKind) -> Self {
+        match v {
+            RouteKind::Proxy => Self::Proxy,
+            RouteKind::Static {
+                status,
+                headers,
+                body,
+            } => Self::Static {
+                status,
+                headers,
+                body,
+            },
+        }
+    }
+}
+
+impl From<ApiVersion> for domain::ApiVersion {
+    fn from(v: ApiVersion) -> Self {
+        Self {
+            value: v.value,
+            header: v.header,
+            query_param: v.query_param,
+        }
+    }
+}
+
+impl From<AuthRule> for domain::AuthRule {
+    fn from(v: AuthRule) -> Self {
+        Self {
+            condition: match v.condition {
+                AuthCondition::PathPrefix { prefix } => {
+                    domain::AuthCondition::PathPrefix { prefix }
+                }
+                AuthCondition::Header { name, value } => {
+                    domain::AuthCondition::Header { name, value }
+                }
+            },
+            auth: v.auth.map(Into::into),
+        }
+    }
+}
+
+impl From<BodyMatcher> for domain::BodyMatcher {
+    fn from(v: BodyMatcher) -> Self {
+        match v {
+            BodyMatcher::JsonPointer { pointer, value } => Self::JsonPointer { pointer, value },
+            BodyMatcher::Regex { pattern } => Self::Regex { pattern },
+        }
+    }
+}
+
 impl From<PluginsConfig> for domain::PluginsConfig {
     fn from(v: PluginsConfig) -> Self {
         Self {
@@ -479,6 +774,16 @@ impl From<PathSuffixMode> for domain::PathSuffixMode {
     }
 }
 
+impl From<TrailingSlash> for domain::TrailingSlash {
+    fn from(v: TrailingSlash) -> Self {
+        match v {
+            TrailingSlash::Preserve => Self::Preserve,
+            TrailingSlash::Strip => Self::Strip,
+            TrailingSlash::Add => Self::Add,
+        }
+    }
+}
+
 impl From<HttpMatch> for domain::HttpMatch {
     fn from(v: HttpMatch) -> Self {
         Self {
@@ -486,6 +791,7 @@ impl From<HttpMatch> for domain::HttpMatch {
             path: v.path,
             query_allowlist: v.query_allowlist,
             path_suffix_mode: v.path_suffix_mode.into(),
+            match_body: v.match_body.map(Into::into),
         }
     }
 }
@@ -522,6 +828,23 @@ impl From<UpstreamPayload> for ProvisionedUpstream {
                 rate_limit: p.rate_limit.map(Into::into),
                 tags: p.tags,
                 enabled: p.enabled,
+                maintenance: p.maintenance,
+                maintenance_message: p.maintenance_message,
+                retry_on_body: p.retry_on_body.map(Into::into),
+                force_chunked: p.force_chunked,
+                failover: p.failover.into_iter().map(Into::into).collect(),
+                timeout_secs: p.timeout_secs,
+                keepalive: p.keepalive.map(Into::into),
+                circuit_breaker: p.circuit_breaker.map(Into::into),
+                user_agent: p.user_agent,
+                sticky_by: p.sticky_by,
+                allowed_methods: p.allowed_methods.into_iter().map(Into::into).collect(),
+                trailing_slash: p.trailing_slash.into(),
+                max_header_size_bytes: p.max_header_size_bytes,
+                tls_min_version: p.tls_min_version,
+                tls_cipher_suites: p.tls_cipher_suites,
+                tls_server_name: p.tls_server_name,
+                budget_header: p.budget_header,
             },
         }
     }
@@ -539,6 +862,32 @@ impl From<RoutePayload> for ProvisionedRoute {
                 tags: p.tags,
                 priority: p.priority,
                 enabled: p.enabled,
+                skip_authz: p.skip_authz,
+                timeout_secs: p.timeout_secs,
+                status_remap: p.status_remap,
+                allowed_response_content_types: p.allowed_response_content_types,
+                allowed_request_content_types: p.allowed_request_content_types,
+                log_sample_rate: p.log_sample_rate,
+                request_schema: p.request_schema,
+                body_rewrite: p.body_rewrite.into_iter().map(Into::into).collect(),
+                auth_rules: p.auth_rules.into_iter().map(Into::into).collect(),
+                strip_query_params: p.strip_query_params,
+                strip_inbound_headers: p.strip_inbound_headers,
+                idempotency_ttl_secs: p.idempotency_ttl_secs,
+                cache_responses: p.cache_responses,
+                negative_cache_ttl_secs: p.negative_cache_ttl_secs,
+                response_header_allowlist: p.response_header_allowlist,
+                max_total_duration_ms: p.max_total_duration_ms,
+                max_body_size_bytes: p.max_body_size_bytes,
+                max_outbound_calls: p.max_outbound_calls,
+                coerce_content_type: p.coerce_content_type,
+                api_version: p.api_version.map(Into::into),
+                body_transformers: p.body_transformers,
+                log_redact_paths: p.log_redact_paths,
+                transform_hook_url: p.transform_hook_url,
+                deprecated: p.deprecated,
+                sunset: p.sunset,
+                kind: p.kind.into(),
             },
         }
     }
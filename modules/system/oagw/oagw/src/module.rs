@@ -1,7 +1,8 @@
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
-use crate::config::{OagwConfig, TokenCacheConfig};
+use crate::config::{AuthzMode, OagwConfig, RuntimeConfig, SharedRuntimeConfig, TokenCacheConfig};
+use crate::domain::readiness::ReadinessState;
 use crate::domain::type_catalog::oagw_gts_entities;
 use crate::domain::type_provisioning::TypeProvisioningService;
 use crate::infra::type_provisioning::TypeProvisioningServiceImpl;
@@ -9,12 +10,15 @@ use async_trait::async_trait;
 use authz_resolver_sdk::{AuthZResolverClient, PolicyEnforcer};
 use credstore_sdk::CredStoreClientV1;
 use modkit::api::OpenApiRegistry;
-use modkit::contracts::SystemCapability;
+use modkit::client_hub::ClientScope;
+use modkit::contracts::{RunnableCapability, SystemCapability};
 use modkit::{Module, ModuleCtx, RestApiCapability};
 use modkit_security::SecurityContext;
 use oagw_sdk::api::ServiceGatewayClientV1;
+use oagw_sdk::{AuditSink, MetricsSink, ResponseCache, SecretResolver};
 use tenant_resolver_sdk::TenantResolverClient;
-use tracing::info;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 use types_registry_sdk::{RegisterResult, RegisterSummary, TypesRegistryClient};
 
 use crate::api::rest::routes;
@@ -22,8 +26,15 @@ use crate::domain::services::{
     ControlPlaneService, ControlPlaneServiceImpl, DataPlaneService, EndpointSelector,
     ServiceGatewayClientV1Facade,
 };
+use crate::infra::metrics::OagwMetrics;
+use crate::infra::plugin::secret_resolver::{EnvSecretResolver, FileSecretResolver};
 use crate::infra::proxy::DataPlaneServiceImpl;
-use crate::infra::storage::{InMemoryRouteRepo, InMemoryUpstreamRepo};
+use crate::infra::proxy::dns_cache::DnsCache;
+use crate::infra::static_authz::StaticAuthZResolverClient;
+use crate::infra::storage::{
+    InMemoryRouteRepo, InMemoryTenantAuthDefaultRepo, InMemoryTenantRateLimitRepo,
+    InMemoryUpstreamRepo,
+};
 
 /// Shared application state injected into all handlers.
 #[derive(Clone)]
@@ -31,19 +42,45 @@ pub struct AppState {
     pub(crate) cp: Arc<dyn ControlPlaneService>,
     pub(crate) dp: Arc<dyn DataPlaneService>,
     pub(crate) backend_selector: Arc<dyn EndpointSelector>,
-    pub(crate) config: crate::config::RuntimeConfig,
+    pub(crate) config: SharedRuntimeConfig,
+}
+
+impl AppState {
+    /// The runtime configuration currently in effect.
+    #[must_use]
+    pub fn runtime_config(&self) -> Arc<RuntimeConfig> {
+        self.config.load_full()
+    }
+
+    /// Replace the runtime configuration. Requests already in flight keep
+    /// the limits they started with; later ones use `config`.
+    ///
+    /// # Errors
+    /// Returns an error, leaving the current configuration in place, when
+    /// `config` fails [`RuntimeConfig::validate`].
+    pub fn update_runtime_config(&self, config: RuntimeConfig) -> anyhow::Result<()> {
+        config.validate()?;
+        info!(
+            request_timeout = ?config.request_timeout,
+            max_body_size_bytes = config.max_body_size_bytes,
+            "OAGW runtime config updated"
+        );
+        self.config.store(Arc::new(config));
+        Ok(())
+    }
 }
 
 /// Outbound API Gateway module: wires repos, services, and routes.
 #[modkit::module(
     name = "oagw",
     deps = ["types-registry", "authz-resolver", "credstore", "tenant-resolver"],
-    capabilities = [system, rest]
+    capabilities = [system, rest, stateful]
 )]
 pub struct OutboundApiGatewayModule {
     state: arc_swap::ArcSwapOption<AppState>,
     registry_client: OnceLock<Arc<dyn TypesRegistryClient>>,
     type_provisioning: OnceLock<Arc<dyn TypeProvisioningService>>,
+    readiness: Arc<ReadinessState>,
 }
 
 impl Default for OutboundApiGatewayModule {
@@ -52,10 +89,26 @@ impl Default for OutboundApiGatewayModule {
             state: arc_swap::ArcSwapOption::from(None),
             registry_client: OnceLock::new(),
             type_provisioning: OnceLock::new(),
+            readiness: Arc::new(ReadinessState::default()),
         }
     }
 }
 
+impl OutboundApiGatewayModule {
+    /// Adjust the request timeout, body limit or error body template of a
+    /// running gateway without a restart.
+    ///
+    /// # Errors
+    /// Returns an error when `init()` has not run or `config` is invalid.
+    pub fn update_runtime_config(&self, config: RuntimeConfig) -> anyhow::Result<()> {
+        self.state
+            .load()
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("AppState not set — init() must run first"))?
+            .update_runtime_config(config)
+    }
+}
+
 #[async_trait]
 impl Module for OutboundApiGatewayModule {
     async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
@@ -65,21 +118,38 @@ impl Module for OutboundApiGatewayModule {
         // -- Control Plane init --
         let upstream_repo = Arc::new(InMemoryUpstreamRepo::new());
         let route_repo = Arc::new(InMemoryRouteRepo::new());
+        let tenant_rate_limit_repo = Arc::new(InMemoryTenantRateLimitRepo::new());
+        let tenant_auth_default_repo = Arc::new(InMemoryTenantAuthDefaultRepo::new());
         let tenant_resolver = ctx.client_hub().get::<dyn TenantResolverClient>()?;
 
         let credstore = ctx.client_hub().get::<dyn CredStoreClientV1>()?;
 
         // -- AuthZ resolver for permission checks --
-        let authz = ctx.client_hub().get::<dyn AuthZResolverClient>()?;
+        let authz: Arc<dyn AuthZResolverClient> = match cfg.authz_mode {
+            AuthzMode::Required => ctx.client_hub().get::<dyn AuthZResolverClient>()?,
+            mode => {
+                warn!(?mode, "OAGW running without an external AuthZ resolver");
+                Arc::new(StaticAuthZResolverClient::new(mode == AuthzMode::AllowAll))
+            }
+        };
         let policy_enforcer = PolicyEnforcer::new(authz);
 
-        let cp: Arc<dyn ControlPlaneService> = Arc::new(ControlPlaneServiceImpl::new(
+        let mut cp_impl = ControlPlaneServiceImpl::new(
             upstream_repo,
             route_repo,
+            tenant_rate_limit_repo,
+            tenant_auth_default_repo,
             tenant_resolver,
             policy_enforcer.clone(),
             credstore.clone(),
-        ));
+        )
+        .with_tenant_quotas(cfg.max_upstreams_per_tenant, cfg.max_routes_per_tenant);
+        // -- Optional audit sink for configuration changes --
+        if let Ok(audit_sink) = ctx.client_hub().get::<dyn AuditSink>() {
+            info!("OAGW audit sink registered; configuration changes will be audited");
+            cp_impl = cp_impl.with_audit_sink(audit_sink);
+        }
+        let cp: Arc<dyn ControlPlaneService> = Arc::new(cp_impl);
 
         // -- Data Plane init (Pingora proxy engine) --
         let server_conf = Arc::new(pingora_core::server::configuration::ServerConf {
@@ -88,14 +158,34 @@ impl Module for OutboundApiGatewayModule {
         });
         let connect_timeout = Duration::from_secs(10);
         let read_timeout = Duration::from_secs(cfg.proxy_timeout_secs);
+        let scope =
+            opentelemetry::InstrumentationScope::builder(Self::MODULE_NAME.to_owned()).build();
+        // -- Optional custom metrics backend; OpenTelemetry otherwise --
+        let metrics = if let Ok(sink) = ctx.client_hub().get::<dyn MetricsSink>() {
+            info!("OAGW custom metrics sink registered");
+            OagwMetrics::with_sink(sink)
+        } else {
+            OagwMetrics::new(&opentelemetry::global::meter_with_scope(scope))
+        };
+
+        let dns_cache = Arc::new(
+            DnsCache::new(Duration::from_secs(cfg.dns_cache_ttl_secs))
+                .with_overrides(cfg.dns_override.clone())
+                .with_metrics(metrics.clone()),
+        );
+        dns_cache.spawn_refresh();
         let pingora_proxy =
-            crate::infra::proxy::pingora_proxy::PingoraProxy::new(connect_timeout, read_timeout);
+            crate::infra::proxy::pingora_proxy::PingoraProxy::new(connect_timeout, read_timeout)
+                .with_idle_timeout(Duration::from_secs(cfg.pool_idle_timeout_secs))
+                .with_dns_cache(dns_cache.clone());
         let proxy = Arc::new(crate::infra::proxy::pingora_proxy::new_http_proxy(
             &server_conf,
             pingora_proxy,
         ));
-        let backend_selector: Arc<dyn EndpointSelector> =
-            Arc::new(crate::infra::proxy::pingora_proxy::PingoraEndpointSelector::new());
+        let backend_selector: Arc<dyn EndpointSelector> = Arc::new(
+            crate::infra::proxy::pingora_proxy::PingoraEndpointSelector::new()
+                .with_dns_cache(dns_cache.clone()),
+        );
 
         let token_http_config = if cfg.allow_http_upstream {
             tracing::warn!("allow_http_upstream is enabled — HTTP token endpoints also allowed");
@@ -108,20 +198,111 @@ impl Module for OutboundApiGatewayModule {
 
         let token_cache_config = TokenCacheConfig::from(&cfg);
 
-        let dp: Arc<dyn DataPlaneService> = Arc::new(
-            DataPlaneServiceImpl::new(
-                cp.clone(),
-                credstore,
-                policy_enforcer,
-                token_http_config,
-                token_cache_config,
-                backend_selector.clone(),
-                proxy,
-            )
-            .with_request_timeout(Duration::from_secs(cfg.proxy_timeout_secs))
-            .with_max_body_size(cfg.max_body_size_bytes)
-            .with_allow_http_upstream(cfg.allow_http_upstream),
-        );
+        // -- Opt-in secret resolvers for `env://` and `file://` references --
+        if cfg.env_secret_refs {
+            warn!("OAGW env:// secret references enabled; auth configs can read the environment");
+            ctx.client_hub().register_scoped::<dyn SecretResolver>(
+                ClientScope::new("env"),
+                Arc::new(EnvSecretResolver),
+            );
+        }
+        if let Some(dir) = &cfg.file_secret_dir {
+            let resolver = FileSecretResolver::new(dir)
+                .map_err(|e| anyhow::anyhow!("invalid file_secret_dir '{dir}': {e}"))?;
+            info!(dir = %dir, "OAGW file:// secret references enabled");
+            ctx.client_hub().register_scoped::<dyn SecretResolver>(
+                ClientScope::new("file"),
+                Arc::new(resolver),
+            );
+        }
+
+        // Shared by the proxy handler and the data plane so that
+        // `AppState::update_runtime_config` reaches both.
+        let runtime_config: SharedRuntimeConfig =
+            Arc::new(arc_swap::ArcSwap::from_pointee(RuntimeConfig::from(&cfg)));
+        let mut dp_impl = DataPlaneServiceImpl::new(
+            cp.clone(),
+            credstore,
+            policy_enforcer,
+            token_http_config,
+            token_cache_config,
+            backend_selector.clone(),
+            proxy,
+        )
+        .with_runtime_config(runtime_config.clone())
+        .with_max_url_length(cfg.max_url_length)
+        .with_max_query_params(cfg.max_query_params)
+        .with_bodiless_method_body(cfg.bodiless_method_body)
+        .with_max_connections_per_host(cfg.max_connections_per_host)
+        .with_max_concurrent_requests_per_upstream(cfg.max_concurrent_requests_per_upstream)
+        .with_queue_wait_timeout(cfg.queue_wait_timeout_ms.map(Duration::from_millis))
+        .with_dns_retry(
+            dns_cache,
+            cfg.dns_retry_max,
+            Duration::from_millis(cfg.dns_retry_backoff_ms),
+        )
+        .with_connect_retry(
+            cfg.connect_retry_max,
+            Duration::from_millis(cfg.connect_retry_backoff_ms),
+        )
+        .with_srv_targets(cfg.srv_targets.clone())
+        .with_unframed_responses(
+            cfg.max_response_body_size_bytes,
+            cfg.stream_unframed_responses,
+        )
+        .with_allow_http_upstream(cfg.allow_http_upstream)
+        .with_global_concurrency(
+            cfg.max_global_concurrency,
+            Duration::from_millis(cfg.queue_timeout_ms),
+        )
+        .with_stream_idle_timeout(
+            cfg.stream_idle_timeout_ms.map(Duration::from_millis),
+            cfg.stream_idle_terminal_event.clone(),
+        )
+        .with_max_concurrent_streams(cfg.max_concurrent_streams)
+        .with_body_read_idle_timeout(cfg.body_read_idle_timeout_ms.map(Duration::from_millis))
+        .with_recent_errors(cfg.recent_errors_capacity)
+        .with_unmatched_route(cfg.unmatched_route.clone())
+        .with_rate_limit_headers(cfg.rate_limit_headers.clone())
+        .with_user_agent(&cfg.user_agent)
+        .with_extra_hop_by_hop_headers(&cfg.extra_hop_by_hop_headers)
+        .with_strip_inbound_headers(&cfg.strip_inbound_headers)
+        .with_private_response_cache(cfg.private_response_cache)
+        .with_head_from_response_cache(cfg.head_from_response_cache)
+        .with_response_compression(cfg.compress_responses, cfg.compress_min_size_bytes)
+        .with_auth_config_overrides(&cfg.auth_config_override_keys)
+        .with_disabled_auth_plugins(&cfg.disabled_auth_plugins)
+        .with_error_status_codes(&cfg.error_status_codes)
+        .with_clock_skew_correction(cfg.clock_skew_correction_secs)
+        .with_circuit_breaker(
+            cfg.cb_failure_threshold,
+            cfg.cb_cooldown_secs,
+            cfg.cb_half_open_probes,
+        )
+        .with_authz_failure_handling(
+            (cfg.authz_timeout_ms > 0).then(|| Duration::from_millis(cfg.authz_timeout_ms)),
+            cfg.authz_retries,
+            cfg.authz_on_error,
+        )
+        .with_authz_mode(cfg.authz_mode)
+        .with_transform_hook_timeout(Duration::from_millis(cfg.transform_hook_timeout_ms))
+        .with_body_transformers(ctx.client_hub())
+        .with_secret_resolvers(ctx.client_hub())
+        .with_readiness(self.readiness.clone())
+        .with_readiness_probe(cfg.readiness_probe_endpoint.clone())
+        .with_metrics(metrics);
+        if cfg.forward_client_ip {
+            dp_impl = dp_impl.with_forwarded_headers(cfg.trusted_proxies.clone());
+        }
+        if let Some(seed) = cfg.log_sampling_seed {
+            dp_impl = dp_impl.with_log_sampling_seed(seed);
+        }
+        if let Ok(response_cache) = ctx.client_hub().get::<dyn ResponseCache>() {
+            info!("OAGW external response cache registered");
+            dp_impl = dp_impl.with_response_cache(response_cache);
+        }
+        let dp: Arc<dyn DataPlaneService> = Arc::new(dp_impl);
+        self.readiness.mark_plugins_initialized();
 
         // -- Facade (for external SDK consumers) --
         let oagw: Arc<dyn ServiceGatewayClientV1> =
@@ -165,7 +346,7 @@ impl Module for OutboundApiGatewayModule {
             cp,
             dp,
             backend_selector,
-            config: (&cfg).into(),
+            config: runtime_config,
         };
 
         self.state.store(Some(Arc::new(app_state)));
@@ -242,7 +423,31 @@ impl SystemCapability for OutboundApiGatewayModule {
         self.type_provisioning
             .set(provisioning)
             .map_err(|_| anyhow::anyhow!("TypeProvisioningService already set"))?;
+        self.readiness.mark_repos_loaded();
+
+        Ok(())
+    }
+}
 
+#[async_trait]
+impl RunnableCapability for OutboundApiGatewayModule {
+    async fn start(&self, _cancel: CancellationToken) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn stop(&self, cancel: CancellationToken) -> anyhow::Result<()> {
+        let Some(state) = self.state.load_full() else {
+            return Ok(());
+        };
+        info!("Flushing OAGW data plane state");
+        tokio::select! {
+            () = state.dp.shutdown() => {
+                info!("OAGW data plane state flushed");
+            }
+            () = cancel.cancelled() => {
+                info!("OAGW flush cancelled by framework deadline");
+            }
+        }
         Ok(())
     }
 }
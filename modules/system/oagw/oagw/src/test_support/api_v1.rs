@@ -49,6 +49,14 @@ impl<'a> ApiV1<'a> {
         )
     }
 
+    pub fn test_upstream(&self, id: &str) -> RequestCase<'a> {
+        RequestCase::new(
+            self.harness,
+            Method::POST,
+            format!("/oagw/v1/upstreams/{id}/test"),
+        )
+    }
+
     // -- Route CRUD --
 
     pub fn post_route(&self) -> RequestCase<'a> {
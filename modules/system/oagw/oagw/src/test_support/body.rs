@@ -52,6 +52,12 @@ impl IntoBody for Vec<u8> {
     }
 }
 
+impl IntoBody for Body {
+    fn into_body(self) -> (Body, Option<HeaderValue>) {
+        (self, None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
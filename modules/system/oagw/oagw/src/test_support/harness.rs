@@ -1,5 +1,7 @@
 //! Top-level test harness that wires all components together.
 
+use std::collections::HashMap;
+use std::net::IpAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -7,6 +9,7 @@ use authz_resolver_sdk::AuthZResolverClient;
 use modkit::client_hub::ClientHub;
 use modkit_security::SecurityContext;
 use oagw_sdk::api::ServiceGatewayClientV1;
+use oagw_sdk::{MetricsSink, ResponseCache};
 use uuid::Uuid;
 
 use crate::api::rest::routes::test_router;
@@ -20,6 +23,7 @@ pub struct AppHarness {
     facade: Arc<dyn ServiceGatewayClientV1>,
     ctx: SecurityContext,
     router: axum::Router,
+    state: crate::module::AppState,
 }
 
 impl AppHarness {
@@ -44,6 +48,11 @@ impl AppHarness {
         &self.ctx
     }
 
+    /// State shared by the router, e.g. to update the runtime config live.
+    pub fn app_state(&self) -> &crate::module::AppState {
+        &self.state
+    }
+
     pub(crate) fn router(&self) -> &axum::Router {
         &self.router
     }
@@ -56,7 +65,13 @@ pub struct AppHarnessBuilder {
     request_timeout: Option<Duration>,
     authz_client: Option<Arc<dyn AuthZResolverClient>>,
     max_body_size: Option<usize>,
+    max_url_length: Option<usize>,
     skip_upstream_tls_verify: bool,
+    response_cache: Option<Arc<dyn ResponseCache>>,
+    metrics_sink: Option<Arc<dyn MetricsSink>>,
+    global_concurrency: Option<(usize, Duration)>,
+    dns_override: HashMap<String, IpAddr>,
+    stream_idle_timeout: Option<Duration>,
 }
 
 impl AppHarnessBuilder {
@@ -82,12 +97,53 @@ impl AppHarnessBuilder {
         self
     }
 
+    /// Override the maximum request URL length (useful for URL-limit tests).
+    pub fn with_max_url_length(mut self, len: usize) -> Self {
+        self.max_url_length = Some(len);
+        self
+    }
+
+    /// Cap in-flight proxy requests (useful for load-shedding tests).
+    pub fn with_global_concurrency(
+        mut self,
+        max_in_flight: usize,
+        queue_timeout: Duration,
+    ) -> Self {
+        self.global_concurrency = Some((max_in_flight, queue_timeout));
+        self
+    }
+
     /// Skip upstream TLS certificate verification. **Test use only.**
     pub fn with_skip_upstream_tls_verify(mut self, allow: bool) -> Self {
         self.skip_upstream_tls_verify = allow;
         self
     }
 
+    /// End SSE responses that stall for `timeout` with the default terminal
+    /// event.
+    pub fn with_stream_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.stream_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Pin hostnames to fixed addresses, bypassing DNS.
+    pub fn with_dns_override(mut self, overrides: HashMap<String, IpAddr>) -> Self {
+        self.dns_override = overrides;
+        self
+    }
+
+    /// Override the response cache backend (useful for cache tests).
+    pub fn with_response_cache(mut self, cache: Arc<dyn ResponseCache>) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
+    /// Record metrics through `sink` (useful for metrics tests).
+    pub fn with_metrics_sink(mut self, sink: Arc<dyn MetricsSink>) -> Self {
+        self.metrics_sink = Some(sink);
+        self
+    }
+
     pub async fn build(self) -> AppHarness {
         let hub = ClientHub::new();
 
@@ -106,7 +162,23 @@ impl AppHarnessBuilder {
         if let Some(size) = self.max_body_size {
             dp_builder = dp_builder.with_max_body_size(size);
         }
+        if let Some(len) = self.max_url_length {
+            dp_builder = dp_builder.with_max_url_length(len);
+        }
+        if let Some(cache) = self.response_cache {
+            dp_builder = dp_builder.with_response_cache(cache);
+        }
+        if let Some(sink) = self.metrics_sink {
+            dp_builder = dp_builder.with_metrics_sink(sink);
+        }
+        if let Some((max_in_flight, queue_timeout)) = self.global_concurrency {
+            dp_builder = dp_builder.with_global_concurrency(max_in_flight, queue_timeout);
+        }
         dp_builder = dp_builder.with_skip_upstream_tls_verify(self.skip_upstream_tls_verify);
+        dp_builder = dp_builder.with_dns_override(self.dns_override);
+        if let Some(timeout) = self.stream_idle_timeout {
+            dp_builder = dp_builder.with_stream_idle_timeout(timeout);
+        }
         dp_builder =
             dp_builder.with_token_http_config(modkit_http::HttpClientConfig::for_testing());
 
@@ -118,12 +190,13 @@ impl AppHarnessBuilder {
             .build()
             .expect("test security context");
 
-        let router = test_router(app_state.state, ctx.clone());
+        let router = test_router(app_state.state.clone(), ctx.clone());
 
         AppHarness {
             facade: app_state.facade,
             ctx,
             router,
+            state: app_state.state,
         }
     }
 }
@@ -23,6 +23,7 @@ use axum::response::IntoResponse;
 use axum::routing::{get, post};
 use bytes::Bytes;
 use dashmap::DashMap;
+use futures_util::StreamExt;
 use serde_json::{Value, json};
 use tokio::net::TcpListener;
 use tokio::sync::Mutex;
@@ -38,6 +39,9 @@ pub enum MockBody {
     Json(Value),
     Text(String),
     Sse(Vec<String>),
+    /// Sends the events, then keeps the connection open without finishing
+    /// the body (an upstream that stalls mid-stream).
+    SseStalled(Vec<String>),
     /// Body delivery is gated on a channel signal.
     /// When the sender fires, the inner body is delivered.
     /// When the sender is dropped without firing, the handler aborts the connection.
@@ -119,6 +123,20 @@ impl MockResponse {
                 }
                 builder.body(axum::body::Body::from(sse_body)).unwrap()
             }
+            MockBody::SseStalled(chunks) => {
+                let events = chunks.into_iter().map(|chunk| {
+                    Ok::<_, std::io::Error>(Bytes::from(format!("data: {chunk}\n\n")))
+                });
+                let body =
+                    futures_util::stream::iter(events).chain(futures_util::stream::pending());
+                let mut builder = axum::response::Response::builder()
+                    .status(StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK))
+                    .header("content-type", "text/event-stream");
+                for (k, v) in &self.headers {
+                    builder = builder.header(k.as_str(), v.as_str());
+                }
+                builder.body(axum::body::Body::from_stream(body)).unwrap()
+            }
         }
     }
 }
@@ -274,7 +292,7 @@ impl MockUpstream {
             .route("/error/500", get(error_500))
             // Response header test
             .route("/response-headers", get(response_with_bad_headers))
-            // WebSocket (future use)
+            // WebSocket echo
             .route("/ws/echo", get(ws_echo))
             // WebTransport stub (future use)
             .route("/wt/stub", get(wt_stub))
@@ -602,7 +620,7 @@ async fn error_500(
 }
 
 // ---------------------------------------------------------------------------
-// WebSocket handlers (future use — OAGW WS proxy not in this phase)
+// WebSocket handlers
 // ---------------------------------------------------------------------------
 
 async fn ws_echo(
@@ -15,7 +15,8 @@ pub use response::TestResponse;
 
 pub use crate::domain::gts_helpers::{format_route_gts, format_upstream_gts, parse_resource_gts};
 pub use crate::domain::test_support::{
-    APIKEY_AUTH_PLUGIN_ID, CapturingAuthZResolverClient, DenyingAuthZResolverClient,
-    OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID, OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID, TestAppState,
-    TestCpBuilder, TestCredStoreClient, TestDpBuilder, build_test_app_state, build_test_gateway,
+    APIKEY_AUTH_PLUGIN_ID, CapturingAuthZResolverClient, DenyingAuthZResolverClient, MockClock,
+    OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID, OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID, OutboundCapture,
+    OutboundRequest, TestAppState, TestCpBuilder, TestCredStoreClient, TestDpBuilder, TestGateway,
+    TestHttpClient, build_test_app_state, build_test_gateway, spawn_test_gateway,
 };
@@ -0,0 +1,274 @@
+//! E2E tests verifying gRPC passthrough to an h2c (HTTP/2 prior knowledge) upstream.
+//!
+//! Spins up a cleartext HTTP/2-only server that answers a unary gRPC call with
+//! `grpc-status` / `grpc-message` trailers, and asserts that the request reaches
+//! it over HTTP/2 and that the trailers survive the Pingora bridge (encoded as
+//! the final gRPC-Web trailer frame of the response body, and as HTTP
+//! trailers of the proxied response).
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use http::{HeaderMap, HeaderValue, Method, StatusCode};
+use http_body_util::StreamBody;
+use hyper::body::{Frame, Incoming};
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use oagw::test_support::AppHarness;
+use oagw_sdk::{Body, Trailers};
+use oagw_sdk::{
+    CreateRouteRequest, CreateUpstreamRequest, Endpoint, HttpMatch, HttpMethod, MatchRules,
+    PathSuffixMode, Scheme, Server,
+};
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+
+const GRPC_PROTOCOL_ID: &str = "gts.x.core.oagw.protocol.v1~x.core.oagw.grpc.v1";
+const H2C_PROTOCOL_ID: &str = "gts.x.core.oagw.protocol.v1~x.core.oagw.h2c.v1";
+
+// ---------------------------------------------------------------------------
+// h2c gRPC mock upstream
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+struct GrpcRecordedRequest {
+    uri: String,
+    version: hyper::Version,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+struct GrpcMockState {
+    recorded: Mutex<Vec<GrpcRecordedRequest>>,
+}
+
+/// Length-prefixed gRPC message frame (uncompressed).
+fn grpc_frame(payload: &[u8]) -> Bytes {
+    let mut buf = BytesMut::with_capacity(5 + payload.len());
+    buf.put_u8(0);
+    buf.put_u32(payload.len() as u32);
+    buf.extend_from_slice(payload);
+    buf.freeze()
+}
+
+/// Start a cleartext server that only speaks HTTP/2 (prior knowledge) and
+/// echoes each unary call's message back, followed by gRPC trailers.
+async fn start_grpc_mock() -> (SocketAddr, Arc<GrpcMockState>, tokio::task::JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind gRPC mock");
+    let addr = listener.local_addr().expect("local addr");
+
+    let state = Arc::new(GrpcMockState {
+        recorded: Mutex::new(Vec::new()),
+    });
+
+    let state_clone = state.clone();
+    let handle = tokio::spawn(async move {
+        loop {
+            let (tcp_stream, _) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(_) => continue,
+            };
+            let state = state_clone.clone();
+
+            tokio::spawn(async move {
+                let io = TokioIo::new(tcp_stream);
+
+                let service = service_fn(move |req: Request<Incoming>| {
+                    let state = state.clone();
+                    async move {
+                        let uri = req.uri().to_string();
+                        let version = req.version();
+                        let headers = req.headers().clone();
+                        let body = http_body_util::BodyExt::collect(req.into_body())
+                            .await
+                            .map(|b| b.to_bytes().to_vec())
+                            .unwrap_or_default();
+
+                        state.recorded.lock().await.push(GrpcRecordedRequest {
+                            uri,
+                            version,
+                            headers,
+                            body: body.clone(),
+                        });
+
+                        // Echo the request message payload back.
+                        let payload = body.get(5..).unwrap_or_default().to_vec();
+                        let mut trailers = HeaderMap::new();
+                        trailers.insert("grpc-status", HeaderValue::from_static("0"));
+                        trailers.insert("grpc-message", HeaderValue::from_static("ok"));
+
+                        let frames: Vec<Result<Frame<Bytes>, hyper::Error>> = vec![
+                            Ok(Frame::data(grpc_frame(&payload))),
+                            Ok(Frame::trailers(trailers)),
+                        ];
+                        let resp = Response::builder()
+                            .status(200)
+                            .header("content-type", "application/grpc")
+                            .body(StreamBody::new(futures_util::stream::iter(frames)))
+                            .unwrap();
+                        Ok::<_, hyper::Error>(resp)
+                    }
+                });
+
+                if let Err(e) = hyper_util::server::conn::auto::Builder::new(TokioExecutor::new())
+                    .http2_only()
+                    .serve_connection(io, service)
+                    .await
+                {
+                    eprintln!("gRPC mock connection error: {e}");
+                }
+            });
+        }
+    });
+
+    (addr, state, handle)
+}
+
+/// Split a gRPC(-Web) response body into `(flags, payload)` frames.
+fn split_frames(mut body: &[u8]) -> Vec<(u8, Vec<u8>)> {
+    let mut frames = Vec::new();
+    while body.len() >= 5 {
+        let len = u32::from_be_bytes([body[1], body[2], body[3], body[4]]) as usize;
+        frames.push((body[0], body[5..5 + len].to_vec()));
+        body = &body[5 + len..];
+    }
+    assert!(body.is_empty(), "trailing bytes after last frame");
+    frames
+}
+
+// ---------------------------------------------------------------------------
+// E2E tests
+// ---------------------------------------------------------------------------
+
+/// Create an upstream `alias` at `127.0.0.1:port` speaking `protocol`, with a
+/// `POST /helloworld.Greeter/SayHello` route.
+async fn create_h2_target(h: &AppHarness, alias: &str, protocol: &str, port: u16) {
+    let ctx = h.security_context().clone();
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port,
+                    }],
+                },
+                protocol,
+            )
+            .alias(alias)
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx,
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Post],
+                        path: "/helloworld.Greeter/SayHello".into(),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+}
+
+/// E2E: a unary gRPC call is proxied over h2c and the upstream trailers
+/// (`grpc-status`, `grpc-message`) survive the round trip.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn e2e_grpc_unary_call_preserves_trailers() {
+    let (mock_addr, mock_state, _handle) = start_grpc_mock().await;
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+
+    create_h2_target(&h, "e2e-grpc", GRPC_PROTOCOL_ID, mock_addr.port()).await;
+
+    let req = http::Request::builder()
+        .method(Method::POST)
+        .uri("/e2e-grpc/helloworld.Greeter/SayHello")
+        .header("content-type", "application/grpc")
+        .body(Body::from(grpc_frame(b"\x0a\x05world")))
+        .unwrap();
+    let response = h.facade().proxy_request(ctx, req).await.unwrap();
+    let trailers = response.extensions().get::<Trailers>().cloned().unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/grpc"
+    );
+
+    let body = response.into_body().into_bytes().await.unwrap();
+    let frames = split_frames(&body);
+    assert_eq!(frames.len(), 2, "expected message frame + trailer frame");
+    assert_eq!(frames[0], (0, b"\x0a\x05world".to_vec()));
+
+    let (flags, trailer_block) = &frames[1];
+    assert_eq!(*flags, 0x80, "last frame must carry the trailer flag");
+    let trailer_block = String::from_utf8(trailer_block.clone()).unwrap();
+    assert!(
+        trailer_block.contains("grpc-status: 0\r\n"),
+        "missing grpc-status trailer: {trailer_block:?}"
+    );
+    assert!(
+        trailer_block.contains("grpc-message: ok\r\n"),
+        "missing grpc-message trailer: {trailer_block:?}"
+    );
+    assert_eq!(trailers.get().unwrap().get("grpc-status").unwrap(), "0");
+
+    let recorded = mock_state.recorded.lock().await;
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0].version, hyper::Version::HTTP_2);
+    assert!(recorded[0].uri.ends_with("/helloworld.Greeter/SayHello"));
+    assert_eq!(
+        recorded[0].headers.get("content-type").unwrap(),
+        "application/grpc"
+    );
+    assert_eq!(recorded[0].headers.get("te").unwrap(), "trailers");
+    assert_eq!(recorded[0].body, grpc_frame(b"\x0a\x05world").to_vec());
+}
+
+/// E2E: trailers from a plain h2c upstream reach a REST client as HTTP
+/// trailers, without being folded into the response body.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn e2e_h2c_response_trailers_reach_client() {
+    let (mock_addr, _mock_state, _handle) = start_grpc_mock().await;
+    let h = AppHarness::builder().build().await;
+    create_h2_target(&h, "e2e-h2c", H2C_PROTOCOL_ID, mock_addr.port()).await;
+
+    let resp = h
+        .api_v1()
+        .proxy_post("e2e-h2c", "helloworld.Greeter/SayHello")
+        .with_body(grpc_frame(b"\x0a\x05world").to_vec())
+        .with_header(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/grpc"),
+        )
+        .expect_status(200)
+        .await;
+
+    assert_eq!(
+        split_frames(resp.bytes()),
+        vec![(0, b"\x0a\x05world".to_vec())]
+    );
+    let trailers = resp.trailers().expect("response carries trailers");
+    assert_eq!(trailers.get("grpc-status").unwrap(), "0");
+    assert_eq!(trailers.get("grpc-message").unwrap(), "ok");
+}
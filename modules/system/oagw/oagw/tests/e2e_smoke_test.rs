@@ -350,16 +350,21 @@ async fn e2e_rate_limit_returns_429() {
         .await;
 
     // First request succeeds.
-    h.api_v1()
+    let resp = h
+        .api_v1()
         .proxy_get("e2e-rl", "v1/models")
         .expect_status(200)
         .await;
+    resp.assert_header("x-ratelimit-limit", "1");
+    resp.assert_header("x-ratelimit-remaining", "0");
 
     // Second request should be rate limited.
-    h.api_v1()
+    let resp = h
+        .api_v1()
         .proxy_get("e2e-rl", "v1/models")
         .expect_status(429)
         .await;
+    resp.assert_header("x-ratelimit-remaining", "0");
 }
 
 // 10.5: E2E — management lifecycle.
@@ -427,8 +432,12 @@ async fn e2e_management_lifecycle() {
         .expect_status(201)
         .await;
 
-    // Delete upstream (cascades routes).
-    h.api_v1().delete_upstream(&uid).expect_status(204).await;
+    // Delete is blocked while the route exists, then cascades on request.
+    h.api_v1().delete_upstream(&uid).expect_status(409).await;
+    h.api_v1()
+        .delete_upstream(&format!("{uid}?cascade=true"))
+        .expect_status(204)
+        .await;
 
     // List (gone).
     let resp = h.api_v1().list_upstreams().expect_status(200).await;
@@ -550,6 +559,168 @@ async fn e2e_body_exceeding_limit_returns_413() {
         .await;
 }
 
+// E2E: a URL longer than `max_url_length` (default 8 KiB) returns 414
+// without reaching the upstream.
+#[tokio::test]
+async fn e2e_url_exceeding_limit_returns_414() {
+    let h = AppHarness::builder().build().await;
+    create_post_route(&h, "e2e-long-url", "/v1/test").await;
+
+    let query = format!("q={}", "a".repeat(9000));
+    h.api_v1()
+        .proxy_post("e2e-long-url", &format!("v1/test?{query}"))
+        .with_body("{}")
+        .expect_status(414)
+        .await;
+}
+
+// E2E: a path with a percent-encoded control character returns 400.
+#[tokio::test]
+async fn e2e_control_char_path_returns_400() {
+    let h = AppHarness::builder().build().await;
+    create_post_route(&h, "e2e-ctl-path", "/v1/test").await;
+
+    h.api_v1()
+        .proxy_post("e2e-ctl-path", "v1/test%0Ainjected")
+        .with_body("{}")
+        .expect_status(400)
+        .await;
+}
+
+/// Create an upstream pointing at the shared mock plus a single POST route.
+async fn create_post_route(h: &AppHarness, alias: &str, path: &str) {
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(serde_json::json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": alias,
+            "enabled": true,
+            "tags": []
+        }))
+        .expect_status(201)
+        .await;
+    let uid = resp.json()["id"].as_str().unwrap().to_string();
+    let (_, upstream_uuid) = parse_resource_gts(&uid).unwrap();
+    h.api_v1()
+        .post_route()
+        .with_body(serde_json::json!({
+            "upstream_id": upstream_uuid,
+            "match": {
+                "http": {
+                    "methods": ["POST"],
+                    "path": path
+                }
+            },
+            "enabled": true,
+            "tags": [],
+            "priority": 0
+        }))
+        .expect_status(201)
+        .await;
+}
+
+/// Body that yields `chunks` chunks of `chunk_size` bytes, with no
+/// Content-Length known up front.
+fn chunked_upload(chunk_size: usize, chunks: usize) -> axum::body::Body {
+    let stream = futures_util::stream::iter(
+        (0..chunks)
+            .map(move |_| Ok::<_, std::io::Error>(bytes::Bytes::from(vec![b'u'; chunk_size]))),
+    );
+    axum::body::Body::from_stream(stream)
+}
+
+// E2E: a large streamed upload is forwarded through the REST handler without
+// being buffered and reaches the upstream intact.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn e2e_large_streamed_upload_succeeds() {
+    let h = AppHarness::builder().build().await;
+    create_post_route(&h, "e2e-upload", "/echo").await;
+
+    let (chunk_size, chunks) = (64 * 1024, 32); // 2 MiB
+    let resp = h
+        .api_v1()
+        .proxy_post("e2e-upload", "echo")
+        .with_body(chunked_upload(chunk_size, chunks))
+        .expect_status(200)
+        .await;
+
+    let echoed = resp.json()["body"].as_str().unwrap().len();
+    assert_eq!(echoed, chunk_size * chunks);
+}
+
+// E2E: a streamed upload without Content-Length is aborted with 413 once it
+// crosses the body limit mid-stream.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn e2e_streamed_upload_aborts_at_limit() {
+    // Gate the upstream so it cannot answer before the limit is detected.
+    let mut guard = MockGuard::new();
+    let _gate = guard.mock_gated(
+        "POST",
+        "/v1/upload",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Json(serde_json::json!({"ok": true})),
+        },
+    );
+
+    let h = AppHarness::builder()
+        .with_max_body_size(256 * 1024)
+        .build()
+        .await;
+    create_post_route(&h, "e2e-upload-limit", &guard.path("/v1/upload")).await;
+
+    h.api_v1()
+        .proxy_post("e2e-upload-limit", &guard.path("/v1/upload")[1..])
+        .with_body(chunked_upload(64 * 1024, 16)) // 1 MiB > 256 KiB
+        .expect_status(413)
+        .await;
+}
+
+// E2E: raising the body limit through the runtime config takes effect for
+// the next request without rebuilding the gateway.
+#[tokio::test]
+async fn e2e_runtime_body_limit_update_accepts_previously_rejected_size() {
+    let h = AppHarness::builder().with_max_body_size(1024).build().await;
+    create_post_route(&h, "e2e-live-limit", "/echo").await;
+    let body = "x".repeat(4096);
+
+    h.api_v1()
+        .proxy_post("e2e-live-limit", "echo")
+        .with_body(body.clone())
+        .expect_status(413)
+        .await;
+
+    let state = h.app_state();
+    let mut config = (*state.runtime_config()).clone();
+    config.max_body_size_bytes = 8192;
+    state.update_runtime_config(config).unwrap();
+
+    let resp = h
+        .api_v1()
+        .proxy_post("e2e-live-limit", "echo")
+        .with_body(body.clone())
+        .expect_status(200)
+        .await;
+    assert_eq!(resp.json()["body"].as_str().unwrap(), body);
+}
+
+// E2E: an invalid runtime config is rejected and the current one stays.
+#[tokio::test]
+async fn e2e_invalid_runtime_config_update_is_rejected() {
+    let h = AppHarness::builder().with_max_body_size(1024).build().await;
+    let state = h.app_state();
+    let mut config = (*state.runtime_config()).clone();
+    config.max_body_size_bytes = 0;
+
+    assert!(state.update_runtime_config(config).is_err());
+    assert_eq!(state.runtime_config().max_body_size_bytes, 1024);
+}
+
 // 10.4: E2E — upstream timeout returns 504 via gated mock that never responds.
 // Uses multi_thread runtime so the timer driver runs on a dedicated thread,
 // preventing stalls when other test binaries compete for CPU.
@@ -678,6 +849,99 @@ async fn e2e_authz_forbidden_returns_403() {
     );
 }
 
+// 10.11a: E2E — a `skip_authz` route is served even when AuthZ denies, while a
+// normal route on the same upstream is still rejected.
+#[tokio::test]
+async fn e2e_skip_authz_route_bypasses_denying_authz() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/health",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Json(serde_json::json!({"status": "ok"})),
+        },
+    );
+
+    let h = AppHarness::builder()
+        .with_authz_client(Arc::new(DenyingAuthZResolverClient))
+        .build()
+        .await;
+
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(serde_json::json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": "e2e-public-health",
+            "enabled": true,
+            "tags": []
+        }))
+        .expect_status(201)
+        .await;
+    let upstream_id = resp.json()["id"].as_str().unwrap().to_string();
+    let (_, upstream_uuid) = parse_resource_gts(&upstream_id).unwrap();
+
+    let health_path = guard.path("/health");
+    let resp = h
+        .api_v1()
+        .post_route()
+        .with_body(serde_json::json!({
+            "upstream_id": upstream_uuid,
+            "match": {
+                "http": {
+                    "methods": ["GET"],
+                    "path": health_path
+                }
+            },
+            "enabled": true,
+            "tags": [],
+            "priority": 0,
+            "skip_authz": true
+        }))
+        .expect_status(201)
+        .await;
+    assert_eq!(resp.json()["skip_authz"], true);
+
+    h.api_v1()
+        .post_route()
+        .with_body(serde_json::json!({
+            "upstream_id": upstream_uuid,
+            "match": {
+                "http": {
+                    "methods": ["GET"],
+                    "path": "/v1/models"
+                }
+            },
+            "enabled": true,
+            "tags": [],
+            "priority": 0
+        }))
+        .expect_status(201)
+        .await;
+
+    // Public route: served despite the denying PDP.
+    h.api_v1()
+        .proxy_get("e2e-public-health", &health_path[1..])
+        .expect_status(200)
+        .await;
+
+    // Normal route: still denied.
+    let resp = h
+        .api_v1()
+        .proxy_get("e2e-public-health", "v1/models")
+        .expect_status(403)
+        .await;
+    assert_eq!(
+        resp.json()["type"],
+        "gts.x.core.errors.err.v1~x.oagw.authz.forbidden.v1"
+    );
+}
+
 // 10.12: E2E — proxy authz evaluation request carries caller's tenant context.
 #[tokio::test]
 async fn e2e_authz_request_carries_tenant_context() {
@@ -759,3 +1023,187 @@ async fn e2e_authz_request_carries_tenant_context() {
     assert_eq!(req.resource.resource_type, "gts.x.core.oagw.proxy.v1~");
     assert_eq!(req.action.name, "invoke");
 }
+
+// 10.13: E2E — proxy authz evaluation request carries route, upstream, method
+// and path so policies can decide per target.
+#[tokio::test]
+async fn e2e_authz_request_carries_target_metadata() {
+    let capturing = Arc::new(CapturingAuthZResolverClient::new());
+
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/v1/orders",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Json(serde_json::json!({"ok": true})),
+        },
+    );
+
+    let h = AppHarness::builder()
+        .with_authz_client(capturing.clone())
+        .build()
+        .await;
+
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(serde_json::json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": "e2e-authz-target",
+            "enabled": true,
+            "tags": []
+        }))
+        .expect_status(201)
+        .await;
+    let (_, upstream_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+
+    let route_path = guard.path("/v1/orders");
+    let resp = h
+        .api_v1()
+        .post_route()
+        .with_body(serde_json::json!({
+            "upstream_id": upstream_uuid,
+            "match": {
+                "http": {
+                    "methods": ["POST"],
+                    "path": route_path
+                }
+            },
+            "enabled": true,
+            "tags": [],
+            "priority": 0
+        }))
+        .expect_status(201)
+        .await;
+    let (_, route_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+
+    h.api_v1()
+        .proxy_post("e2e-authz-target", &route_path[1..])
+        .with_body(serde_json::json!({"sku": "a-1"}))
+        .expect_status(200)
+        .await;
+
+    let requests = capturing.recorded();
+    let req = requests
+        .iter()
+        .find(|r| r.resource.resource_type == "gts.x.core.oagw.proxy.v1~")
+        .expect("expected a proxy evaluation request");
+    let props = &req.resource.properties;
+    assert_eq!(props["route_id"], route_uuid.to_string());
+    assert_eq!(props["upstream_id"], upstream_uuid.to_string());
+    assert_eq!(props["method"], "POST");
+    assert_eq!(props["path"], route_path.as_str());
+}
+
+fn static_route_body(path: &str, skip_authz: bool) -> serde_json::Value {
+    serde_json::json!({
+        "match": {
+            "http": {
+                "methods": ["GET"],
+                "path": path
+            }
+        },
+        "kind": {
+            "type": "static",
+            "status": 200,
+            "headers": {"content-type": "application/json"},
+            "body": "{\"issuer\":\"https://id.example.com\"}"
+        },
+        "enabled": true,
+        "tags": [],
+        "priority": 0,
+        "skip_authz": skip_authz
+    })
+}
+
+// 10.14: E2E — static route answers locally without any upstream.
+#[tokio::test]
+async fn e2e_static_route_returns_configured_response() {
+    let h = AppHarness::builder().build().await;
+
+    let resp = h
+        .api_v1()
+        .post_route()
+        .with_body(static_route_body(
+            "/.well-known/openid-configuration",
+            false,
+        ))
+        .expect_status(201)
+        .await;
+    assert_eq!(resp.json()["kind"]["type"], "static");
+
+    let resp = h
+        .api_v1()
+        .proxy_get(".well-known", "openid-configuration")
+        .expect_status(200)
+        .await;
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/json"
+    );
+    assert_eq!(resp.json()["issuer"], "https://id.example.com");
+}
+
+// 10.15: E2E — static routes are still subject to AuthZ unless public.
+#[tokio::test]
+async fn e2e_static_route_applies_authz_unless_skipped() {
+    let h = AppHarness::builder()
+        .with_authz_client(Arc::new(DenyingAuthZResolverClient))
+        .build()
+        .await;
+
+    h.api_v1()
+        .post_route()
+        .with_body(static_route_body("/static/private", false))
+        .expect_status(201)
+        .await;
+    h.api_v1()
+        .post_route()
+        .with_body(static_route_body("/static/public", true))
+        .expect_status(201)
+        .await;
+
+    h.api_v1()
+        .proxy_get("static", "private")
+        .expect_status(403)
+        .await;
+    h.api_v1()
+        .proxy_get("static", "public")
+        .expect_status(200)
+        .await;
+}
+
+// 10.16: E2E — a static route must not reference an upstream.
+#[tokio::test]
+async fn e2e_static_route_with_upstream_is_rejected() {
+    let h = AppHarness::builder().build().await;
+
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(serde_json::json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": "e2e-static-upstream",
+            "enabled": true,
+            "tags": []
+        }))
+        .expect_status(201)
+        .await;
+    let (_, upstream_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+
+    let mut body = static_route_body("/e2e-static-upstream/info", false);
+    body["upstream_id"] = serde_json::json!(upstream_uuid);
+    h.api_v1()
+        .post_route()
+        .with_body(body)
+        .expect_status(400)
+        .await;
+}
@@ -1,16 +1,26 @@
 use http::{Method, StatusCode};
 use oagw::test_support::{
-    APIKEY_AUTH_PLUGIN_ID, AppHarness, MockBody, MockGuard, MockResponse, MockUpstream,
-    OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID, parse_resource_gts,
+    APIKEY_AUTH_PLUGIN_ID, AppHarness, CapturingAuthZResolverClient, DenyingAuthZResolverClient,
+    MockBody, MockGuard, MockResponse, MockUpstream, OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID,
+    TestCpBuilder, TestDpBuilder, build_test_gateway, parse_resource_gts, spawn_test_gateway,
 };
 use oagw_sdk::Body;
 use oagw_sdk::api::ErrorSource;
+use oagw_sdk::metrics::{
+    AUTH_PLUGIN_DURATION, BODY_LIMIT_DECLARED_SIZE, BODY_LIMIT_EXCEEDED, MetricLabel,
+};
 use oagw_sdk::{
-    BurstConfig, CreateRouteRequest, CreateUpstreamRequest, Endpoint, HttpMatch, HttpMethod,
-    MatchRules, PathSuffixMode, RateLimitAlgorithm, RateLimitConfig, RateLimitScope,
-    RateLimitStrategy, Scheme, Server, SharingMode, SustainedRate, Window,
+    BodyMatcher, BurstConfig, CreateRouteRequest, CreateRouteRequestBuilder, CreateUpstreamRequest,
+    Endpoint, HeadersConfig, HttpMatch, HttpMethod, MatchRules, PassthroughMode, PathSuffixMode,
+    RateLimitAlgorithm, RateLimitConfig, RateLimitScope, RateLimitStrategy, RequestHeaderRules,
+    RetryOnBody, Scheme, Server, SharingMode, SustainedRate, UpdateUpstreamRequest, Window,
 };
+use oagw_sdk::{CachedResponse, MetricsSink, ResponseCache};
 use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 async fn setup_openai_mock() -> AppHarness {
     let h = AppHarness::builder()
@@ -69,6 +79,57 @@ async fn setup_openai_mock() -> AppHarness {
     h
 }
 
+/// Create an upstream `alias` on `127.0.0.1:port` and one route on it
+/// matching `method` and `path` exactly, after `tweak` has adjusted the
+/// route.
+async fn create_test_route(
+    h: &AppHarness,
+    alias: &str,
+    port: u16,
+    method: HttpMethod,
+    path: String,
+    tweak: impl FnOnce(CreateRouteRequestBuilder) -> CreateRouteRequestBuilder,
+) {
+    let ctx = h.security_context().clone();
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port,
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias(alias)
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let route = CreateRouteRequest::builder(
+        upstream.id,
+        MatchRules {
+            http: Some(HttpMatch {
+                methods: vec![method],
+                path,
+                query_allowlist: vec![],
+                path_suffix_mode: PathSuffixMode::Disabled,
+                match_body: None,
+            }),
+            grpc: None,
+        },
+    );
+    h.facade()
+        .create_route(ctx, tweak(route).build())
+        .await
+        .unwrap();
+}
+
 // 6.13: Full pipeline — proxy POST /v1/chat/completions with JSON body.
 #[tokio::test]
 async fn proxy_chat_completion_round_trip() {
@@ -164,6 +225,7 @@ async fn proxy_injects_auth_header() {
                         path: guard.path("/v1/chat/completions"),
                         query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -198,6 +260,115 @@ async fn proxy_injects_auth_header() {
     assert_eq!(auth_header, "Bearer sk-test123");
 }
 
+// 6.13 (multi-value): Both inbound Accept values reach the upstream alongside auth.
+#[tokio::test]
+async fn proxy_forwards_every_value_of_repeated_header() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/v1/models",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"data": []})),
+        },
+    );
+
+    let h = AppHarness::builder()
+        .with_credentials(vec![("cred://openai-key".into(), "sk-test123".into())])
+        .build()
+        .await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("multi-hdr-test")
+            .auth(oagw_sdk::AuthConfig {
+                plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+                sharing: SharingMode::Private,
+                config: Some(
+                    [
+                        ("header".into(), "authorization".into()),
+                        ("prefix".into(), "Bearer ".into()),
+                        ("secret_ref".into(), "cred://openai-key".into()),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            })
+            .headers(HeadersConfig {
+                request: Some(RequestHeaderRules {
+                    passthrough: PassthroughMode::Allowlist,
+                    passthrough_allowlist: vec!["accept".into()],
+                    ..Default::default()
+                }),
+                response: None,
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/v1/models"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri(format!("/multi-hdr-test{}", guard.path("/v1/models")))
+        .header(http::header::ACCEPT, "application/json")
+        .header(http::header::ACCEPT, "text/event-stream")
+        .body(Body::Empty)
+        .unwrap();
+    let response = h.facade().proxy_request(ctx, req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 1);
+    let accepts: Vec<&str> = recorded[0]
+        .headers
+        .iter()
+        .filter(|(k, _)| k == "accept")
+        .map(|(_, v)| v.as_str())
+        .collect();
+    assert_eq!(accepts, ["application/json", "text/event-stream"]);
+    assert!(
+        recorded[0]
+            .headers
+            .iter()
+            .any(|(k, v)| k == "authorization" && v == "Bearer sk-test123")
+    );
+}
+
 // 6.14: SSE streaming — proxy to dynamic SSE mock via MockGuard.
 #[tokio::test]
 async fn proxy_sse_streaming() {
@@ -252,6 +423,7 @@ async fn proxy_sse_streaming() {
                         path: guard.path("/v1/chat/completions/stream"),
                         query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -308,6 +480,96 @@ async fn proxy_upstream_500_passthrough() {
     );
 }
 
+// HEAD is matched against GET routes, forwarded as HEAD without a body, and
+// the client receives the upstream headers (including Content-Length) only.
+#[tokio::test]
+async fn proxy_head_forwards_as_head_without_body() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "HEAD",
+        "/v1/resource",
+        MockResponse {
+            status: 200,
+            headers: vec![
+                ("content-type".into(), "application/json".into()),
+                ("x-resource-version".into(), "7".into()),
+            ],
+            body: MockBody::Json(json!({"id": "resource-1"})),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("head-test")
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/v1/resource"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    // A body attached by the caller must not reach the upstream.
+    let req = http::Request::builder()
+        .method(Method::HEAD)
+        .uri(format!("/head-test{}", guard.path("/v1/resource")))
+        .body(Body::from("should-not-be-sent"))
+        .unwrap();
+    let response = h.facade().proxy_request(ctx, req).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get("x-resource-version").unwrap(), "7");
+    let content_length = response
+        .headers()
+        .get(http::header::CONTENT_LENGTH)
+        .expect("content-length must be preserved")
+        .to_str()
+        .unwrap()
+        .parse::<usize>()
+        .unwrap();
+    assert!(content_length > 0);
+    let body = response.into_body().into_bytes().await.unwrap();
+    assert!(body.is_empty());
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0].method, "HEAD");
+    assert!(recorded[0].body.is_empty());
+}
+
 // 6.17: Pipeline abort — nonexistent alias returns 404 without calling mock.
 #[tokio::test]
 async fn proxy_nonexistent_alias_returns_404() {
@@ -372,14 +634,25 @@ async fn proxy_disabled_upstream_returns_503() {
     }
 }
 
-// 6.17: Pipeline abort — rate limit exceeded returns 429.
+// The outbound capture sees the request as sent upstream: the auth plugin's
+// header and the upstream's header rules both applied, internal headers gone.
 #[tokio::test]
-async fn proxy_rate_limit_exceeded_returns_429() {
-    let h = AppHarness::builder().build().await;
-    let ctx = h.security_context().clone();
+async fn outbound_capture_records_auth_and_header_rule_headers() {
+    let mock = MockUpstream::start().await;
+    let hub = modkit::client_hub::ClientHub::new();
+    let (dp_builder, capture) = TestDpBuilder::new().with_outbound_capture();
+    let gateway = build_test_gateway(
+        &hub,
+        TestCpBuilder::new().with_credentials(vec![("cred://models-key".into(), "sk-cap".into())]),
+        dp_builder,
+    );
+    let ctx = modkit_security::SecurityContext::builder()
+        .subject_tenant_id(uuid::Uuid::new_v4())
+        .subject_id(uuid::Uuid::new_v4())
+        .build()
+        .unwrap();
 
-    let upstream = h
-        .facade()
+    let upstream = gateway
         .create_upstream(
             ctx.clone(),
             CreateUpstreamRequest::builder(
@@ -387,30 +660,33 @@ async fn proxy_rate_limit_exceeded_returns_429() {
                     endpoints: vec![Endpoint {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
-                        port: h.mock_port(),
+                        port: mock.addr().port(),
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
             )
-            .alias("rate-limited")
-            .rate_limit(RateLimitConfig {
+            .alias("captured")
+            .auth(oagw_sdk::AuthConfig {
+                plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
                 sharing: SharingMode::Private,
-                algorithm: RateLimitAlgorithm::TokenBucket,
-                sustained: SustainedRate {
-                    rate: 1,
-                    window: Window::Minute,
-                },
-                burst: Some(BurstConfig { capacity: 1 }),
-                scope: RateLimitScope::Tenant,
-                strategy: RateLimitStrategy::Reject,
-                cost: 1,
+                config: Some(HashMap::from([
+                    ("header".into(), "authorization".into()),
+                    ("prefix".into(), "Bearer ".into()),
+                    ("secret_ref".into(), "cred://models-key".into()),
+                ])),
+            })
+            .headers(HeadersConfig {
+                request: Some(RequestHeaderRules {
+                    set: HashMap::from([("x-client-tag".into(), "oagw-test".into())]),
+                    ..Default::default()
+                }),
+                response: None,
             })
             .build(),
         )
         .await
         .unwrap();
-
-    h.facade()
+    gateway
         .create_route(
             ctx.clone(),
             CreateRouteRequest::builder(
@@ -420,7 +696,8 @@ async fn proxy_rate_limit_exceeded_returns_429() {
                         methods: vec![HttpMethod::Get],
                         path: "/v1/models".into(),
                         query_allowlist: vec![],
-                        path_suffix_mode: PathSuffixMode::Append,
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -430,84 +707,72 @@ async fn proxy_rate_limit_exceeded_returns_429() {
         .await
         .unwrap();
 
-    // First request should succeed.
     let req = http::Request::builder()
         .method(Method::GET)
-        .uri("/rate-limited/v1/models")
+        .uri("/captured/v1/models")
         .body(Body::Empty)
         .unwrap();
-    let response = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
+    let response = gateway.proxy_request(ctx, req).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
 
-    // Second request should be rate limited.
-    let req = http::Request::builder()
-        .method(Method::GET)
-        .uri("/rate-limited/v1/models")
-        .body(Body::Empty)
-        .unwrap();
-    match h.facade().proxy_request(ctx.clone(), req).await {
-        Err(err) => assert!(matches!(
-            err,
-            oagw_sdk::error::ServiceGatewayError::RateLimitExceeded { .. }
-        )),
-        Ok(_) => panic!("expected rate limit error"),
-    }
+    let captured = capture.captured();
+    assert_eq!(captured.len(), 1);
+    let sent = &captured[0];
+    assert_eq!(sent.method, Method::GET);
+    assert!(sent.url.ends_with("/v1/models"), "{}", sent.url);
+    assert_eq!(sent.headers["authorization"], "Bearer sk-cap");
+    assert_eq!(sent.headers["x-client-tag"], "oagw-test");
+    assert!(
+        sent.headers
+            .keys()
+            .all(|name| !name.as_str().starts_with("x-oagw-")),
+        "{:?}",
+        sent.headers
+    );
 }
 
-// 6.16: Upstream timeout — proxy to gated mock that never responds, assert 504.
-// Uses multi_thread runtime so the timer driver runs on a dedicated thread,
-// preventing stalls when other test binaries compete for CPU.
-#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
-async fn proxy_upstream_timeout_returns_504() {
-    let mut guard = MockGuard::new();
-    // Register a gated route that will never respond (sender kept alive but not signaled).
-    let _gate = guard.mock_gated(
-        "GET",
-        "/timeout",
-        MockResponse {
-            status: 200,
-            headers: vec![],
-            body: MockBody::Json(json!({"ok": true})),
-        },
-    );
+// A gateway spawned on a real port proxies over HTTP end to end, and its
+// error responses carry the Problem envelope.
+#[tokio::test]
+async fn spawned_gateway_proxies_over_http() {
+    use http_body_util::BodyExt;
 
-    let h = AppHarness::builder()
-        .with_request_timeout(std::time::Duration::from_millis(500))
-        .build()
-        .await;
-    let ctx = h.security_context().clone();
+    let mock = MockUpstream::start().await;
+    let hub = modkit::client_hub::ClientHub::new();
+    let gateway = spawn_test_gateway(&hub, TestCpBuilder::new(), TestDpBuilder::new()).await;
 
-    let upstream = h
-        .facade()
+    let upstream = gateway
+        .facade
         .create_upstream(
-            ctx.clone(),
+            gateway.ctx.clone(),
             CreateUpstreamRequest::builder(
                 Server {
                     endpoints: vec![Endpoint {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
-                        port: h.mock_port(),
+                        port: mock.addr().port(),
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
             )
-            .alias("timeout-upstream")
+            .alias("spawned")
             .build(),
         )
         .await
         .unwrap();
-
-    h.facade()
+    gateway
+        .facade
         .create_route(
-            ctx.clone(),
+            gateway.ctx.clone(),
             CreateRouteRequest::builder(
                 upstream.id,
                 MatchRules {
                     http: Some(HttpMatch {
-                        methods: vec![HttpMethod::Get],
-                        path: guard.path("/timeout"),
+                        methods: vec![HttpMethod::Post],
+                        path: "/v1/chat/completions".into(),
                         query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -518,22 +783,39 @@ async fn proxy_upstream_timeout_returns_504() {
         .unwrap();
 
     let req = http::Request::builder()
-        .method(Method::GET)
-        .uri(format!("/timeout-upstream{}", guard.path("/timeout")))
-        .body(Body::Empty)
+        .method(Method::POST)
+        .uri(gateway.url("/oagw/v1/proxy/spawned/v1/chat/completions"))
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(axum::body::Body::from(r#"{"model":"gpt-4"}"#))
         .unwrap();
-    match h.facade().proxy_request(ctx.clone(), req).await {
-        Err(err) => assert!(matches!(
-            err,
-            oagw_sdk::error::ServiceGatewayError::RequestTimeout { .. }
-        )),
-        Ok(_) => panic!("expected timeout error"),
-    }
+    let resp = gateway.client.request(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        body["choices"][0]["message"]["content"],
+        "Hello from mock server"
+    );
+    let recorded = mock.recorded_requests().await;
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0].body, br#"{"model":"gpt-4"}"#);
+
+    let req = http::Request::builder()
+        .uri(gateway.url("/oagw/v1/proxy/missing/v1/models"))
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let resp = gateway.client.request(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+        resp.headers()[http::header::CONTENT_TYPE],
+        "application/problem+json"
+    );
 }
 
-// 8.9: Query allowlist enforcement.
+// Maintenance mode short-circuits with 503 + Retry-After and never reaches the backend.
 #[tokio::test]
-async fn proxy_query_allowlist_allowed_param_succeeds() {
+async fn proxy_maintenance_mode_returns_503_and_clearing_restores_traffic() {
+    let mock = MockUpstream::start().await;
     let h = AppHarness::builder().build().await;
     let ctx = h.security_context().clone();
 
@@ -546,12 +828,14 @@ async fn proxy_query_allowlist_allowed_param_succeeds() {
                     endpoints: vec![Endpoint {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
-                        port: h.mock_port(),
+                        port: mock.addr().port(),
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
             )
-            .alias("ql-test")
+            .alias("maintenance-upstream")
+            .maintenance(true)
+            .maintenance_message("scheduled maintenance until 02:00 UTC")
             .build(),
         )
         .await
@@ -566,8 +850,9 @@ async fn proxy_query_allowlist_allowed_param_succeeds() {
                     http: Some(HttpMatch {
                         methods: vec![HttpMethod::Get],
                         path: "/v1/models".into(),
-                        query_allowlist: vec!["version".into()],
+                        query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Append,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -579,15 +864,52 @@ async fn proxy_query_allowlist_allowed_param_succeeds() {
 
     let req = http::Request::builder()
         .method(Method::GET)
-        .uri("/ql-test/v1/models?version=2")
+        .uri("/maintenance-upstream/v1/models")
+        .body(Body::Empty)
+        .unwrap();
+    match h.facade().proxy_request(ctx.clone(), req).await {
+        Err(oagw_sdk::error::ServiceGatewayError::UpstreamMaintenance {
+            detail,
+            retry_after_secs,
+            ..
+        }) => {
+            assert_eq!(detail, "scheduled maintenance until 02:00 UTC");
+            assert!(retry_after_secs.is_some());
+        }
+        Err(other) => panic!("expected UpstreamMaintenance, got {other:?}"),
+        Ok(_) => panic!("expected maintenance error"),
+    }
+    assert!(
+        mock.recorded_requests().await.is_empty(),
+        "backend must not be called while in maintenance"
+    );
+
+    // Clearing the flag restores traffic.
+    let updated = h
+        .facade()
+        .update_upstream(
+            ctx.clone(),
+            upstream.id,
+            UpdateUpstreamRequest::builder().maintenance(false).build(),
+        )
+        .await
+        .unwrap();
+    assert!(!updated.maintenance);
+    assert!(updated.maintenance_message.is_none());
+
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri("/maintenance-upstream/v1/models")
         .body(Body::Empty)
         .unwrap();
     let response = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(mock.recorded_requests().await.len(), 1);
 }
 
+// 6.17: Pipeline abort — rate limit exceeded returns 429.
 #[tokio::test]
-async fn proxy_query_allowlist_unknown_param_rejected() {
+async fn proxy_rate_limit_exceeded_returns_429() {
     let h = AppHarness::builder().build().await;
     let ctx = h.security_context().clone();
 
@@ -605,7 +927,19 @@ async fn proxy_query_allowlist_unknown_param_rejected() {
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
             )
-            .alias("ql-reject")
+            .alias("rate-limited")
+            .rate_limit(RateLimitConfig {
+                sharing: SharingMode::Private,
+                algorithm: RateLimitAlgorithm::TokenBucket,
+                sustained: SustainedRate {
+                    rate: 1,
+                    window: Window::Minute,
+                },
+                burst: Some(BurstConfig { capacity: 1 }),
+                scope: RateLimitScope::Tenant,
+                strategy: RateLimitStrategy::Reject,
+                cost: 1,
+            })
             .build(),
         )
         .await
@@ -620,8 +954,9 @@ async fn proxy_query_allowlist_unknown_param_rejected() {
                     http: Some(HttpMatch {
                         methods: vec![HttpMethod::Get],
                         path: "/v1/models".into(),
-                        query_allowlist: vec!["version".into()],
+                        query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Append,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -631,24 +966,165 @@ async fn proxy_query_allowlist_unknown_param_rejected() {
         .await
         .unwrap();
 
+    // First request should succeed.
     let req = http::Request::builder()
         .method(Method::GET)
-        .uri("/ql-reject/v1/models?version=2&debug=true")
+        .uri("/rate-limited/v1/models")
+        .body(Body::Empty)
+        .unwrap();
+    let response = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Second request should be rate limited.
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri("/rate-limited/v1/models")
         .body(Body::Empty)
         .unwrap();
     match h.facade().proxy_request(ctx.clone(), req).await {
         Err(err) => assert!(matches!(
             err,
-            oagw_sdk::error::ServiceGatewayError::ValidationError { .. }
+            oagw_sdk::error::ServiceGatewayError::RateLimitExceeded { .. }
         )),
-        Ok(_) => panic!("expected validation error"),
+        Ok(_) => panic!("expected rate limit error"),
     }
 }
 
-// 13.5: Non-existent auth plugin ID returns error through proxy pipeline.
+// 6.17: Global concurrency cap — helper wiring a GET route to a gated mock.
+async fn create_gated_route(h: &AppHarness, guard: &MockGuard, alias: &str) {
+    let path = guard.path("/slow");
+    create_test_route(h, alias, h.mock_port(), HttpMethod::Get, path, |r| r).await;
+}
+
+fn gated_request(guard: &MockGuard, alias: &str) -> http::Request<Body> {
+    http::Request::builder()
+        .method(Method::GET)
+        .uri(format!("/{alias}{}", guard.path("/slow")))
+        .body(Body::Empty)
+        .unwrap()
+}
+
+// 6.17: Global concurrency cap — beyond the cap with no queue, requests are shed with 503.
 #[tokio::test]
-async fn proxy_nonexistent_auth_plugin_returns_error() {
-    let h = AppHarness::builder().build().await;
+async fn proxy_global_concurrency_sheds_beyond_cap() {
+    let mut guard = MockGuard::new();
+    let gate = guard.mock_gated(
+        "GET",
+        "/slow",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Json(json!({"ok": true})),
+        },
+    );
+
+    let h = AppHarness::builder()
+        .with_global_concurrency(1, Duration::ZERO)
+        .build()
+        .await;
+    let ctx = h.security_context().clone();
+    create_gated_route(&h, &guard, "shed-upstream").await;
+
+    let (first, second) = tokio::join!(
+        h.facade()
+            .proxy_request(ctx.clone(), gated_request(&guard, "shed-upstream")),
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            let second = h
+                .facade()
+                .proxy_request(ctx.clone(), gated_request(&guard, "shed-upstream"))
+                .await;
+            gate.send(()).unwrap();
+            second
+        }
+    );
+
+    assert_eq!(first.unwrap().status(), StatusCode::OK);
+    match second {
+        Err(oagw_sdk::error::ServiceGatewayError::GatewayOverloaded { .. }) => {}
+        other => panic!("expected GatewayOverloaded, got {other:?}"),
+    }
+
+    // Under the cap again: the next request proceeds.
+    let third = h
+        .facade()
+        .proxy_request(ctx, gated_request(&guard, "shed-upstream"))
+        .await
+        .unwrap();
+    assert_eq!(third.status(), StatusCode::OK);
+}
+
+// 6.17: Global concurrency cap — a queued request proceeds once a slot frees up.
+#[tokio::test]
+async fn proxy_global_concurrency_queues_until_slot_frees() {
+    let mut guard = MockGuard::new();
+    let gate = guard.mock_gated(
+        "GET",
+        "/slow",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Json(json!({"ok": true})),
+        },
+    );
+
+    let h = AppHarness::builder()
+        .with_global_concurrency(1, Duration::from_secs(5))
+        .build()
+        .await;
+    let ctx = h.security_context().clone();
+    create_gated_route(&h, &guard, "queue-upstream").await;
+
+    // The slot is held until the response body is consumed, so drain it.
+    let (first, second, ()) = tokio::join!(
+        async {
+            let resp = h
+                .facade()
+                .proxy_request(ctx.clone(), gated_request(&guard, "queue-upstream"))
+                .await
+                .unwrap();
+            let status = resp.status();
+            resp.into_body().into_bytes().await.unwrap();
+            status
+        },
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            h.facade()
+                .proxy_request(ctx.clone(), gated_request(&guard, "queue-upstream"))
+                .await
+        },
+        async {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            gate.send(()).unwrap();
+        }
+    );
+
+    assert_eq!(first, StatusCode::OK);
+    assert_eq!(second.unwrap().status(), StatusCode::OK);
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+}
+
+// 6.16: Upstream timeout — proxy to gated mock that never responds, assert 504.
+// Uses multi_thread runtime so the timer driver runs on a dedicated thread,
+// preventing stalls when other test binaries compete for CPU.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn proxy_upstream_timeout_returns_504() {
+    let mut guard = MockGuard::new();
+    // Register a gated route that will never respond (sender kept alive but not signaled).
+    let _gate = guard.mock_gated(
+        "GET",
+        "/timeout",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Json(json!({"ok": true})),
+        },
+    );
+
+    let h = AppHarness::builder()
+        .with_request_timeout(std::time::Duration::from_millis(500))
+        .build()
+        .await;
     let ctx = h.security_context().clone();
 
     let upstream = h
@@ -665,12 +1141,7 @@ async fn proxy_nonexistent_auth_plugin_returns_error() {
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
             )
-            .alias("bad-auth")
-            .auth(oagw_sdk::AuthConfig {
-                plugin_type: "gts.x.core.oagw.auth.v1~nonexistent.plugin.v1".into(),
-                sharing: SharingMode::Private,
-                config: None,
-            })
+            .alias("timeout-upstream")
             .build(),
         )
         .await
@@ -684,9 +1155,10 @@ async fn proxy_nonexistent_auth_plugin_returns_error() {
                 MatchRules {
                     http: Some(HttpMatch {
                         methods: vec![HttpMethod::Get],
-                        path: "/v1/test".into(),
+                        path: guard.path("/timeout"),
                         query_allowlist: vec![],
-                        path_suffix_mode: PathSuffixMode::Append,
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -698,36 +1170,21 @@ async fn proxy_nonexistent_auth_plugin_returns_error() {
 
     let req = http::Request::builder()
         .method(Method::GET)
-        .uri("/bad-auth/v1/test")
+        .uri(format!("/timeout-upstream{}", guard.path("/timeout")))
         .body(Body::Empty)
         .unwrap();
     match h.facade().proxy_request(ctx.clone(), req).await {
         Err(err) => assert!(matches!(
             err,
-            oagw_sdk::error::ServiceGatewayError::AuthenticationFailed { .. }
+            oagw_sdk::error::ServiceGatewayError::RequestTimeout { .. }
         )),
-        Ok(_) => panic!("expected authentication error for non-existent plugin"),
+        Ok(_) => panic!("expected timeout error"),
     }
 }
 
-// 13.6: Assert on recorded_requests() URI and body content.
+// 8.9: Query allowlist enforcement.
 #[tokio::test]
-async fn proxy_recorded_request_has_correct_uri_and_body() {
-    let mut guard = MockGuard::new();
-    guard.mock(
-        "POST",
-        "/v1/chat/completions",
-        MockResponse {
-            status: 200,
-            headers: vec![("content-type".into(), "application/json".into())],
-            body: MockBody::Json(json!({
-                "id": "chatcmpl-rec-test",
-                "object": "chat.completion",
-                "choices": [{"index": 0, "message": {"role": "assistant", "content": "ok"}, "finish_reason": "stop"}]
-            })),
-        },
-    );
-
+async fn proxy_query_allowlist_allowed_param_succeeds() {
     let h = AppHarness::builder().build().await;
     let ctx = h.security_context().clone();
 
@@ -745,7 +1202,7 @@ async fn proxy_recorded_request_has_correct_uri_and_body() {
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
             )
-            .alias("rec-test")
+            .alias("ql-test")
             .build(),
         )
         .await
@@ -758,10 +1215,11 @@ async fn proxy_recorded_request_has_correct_uri_and_body() {
                 upstream.id,
                 MatchRules {
                     http: Some(HttpMatch {
-                        methods: vec![HttpMethod::Post],
-                        path: guard.path("/v1/chat/completions"),
-                        query_allowlist: vec![],
-                        path_suffix_mode: PathSuffixMode::Disabled,
+                        methods: vec![HttpMethod::Get],
+                        path: "/v1/models".into(),
+                        query_allowlist: vec!["version".into()],
+                        path_suffix_mode: PathSuffixMode::Append,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -771,29 +1229,17 @@ async fn proxy_recorded_request_has_correct_uri_and_body() {
         .await
         .unwrap();
 
-    let body_payload = r#"{"model":"gpt-4","messages":[{"role":"user","content":"Hello"}]}"#;
     let req = http::Request::builder()
-        .method(Method::POST)
-        .uri(format!("/rec-test{}", guard.path("/v1/chat/completions")))
-        .header(http::header::CONTENT_TYPE, "application/json")
-        .body(Body::from(body_payload))
+        .method(Method::GET)
+        .uri("/ql-test/v1/models?version=2")
+        .body(Body::Empty)
         .unwrap();
-    let response = h.facade().proxy_request(ctx, req).await.unwrap();
+    let response = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
-
-    let recorded = guard.recorded_requests().await;
-    assert_eq!(recorded.len(), 1);
-    assert!(recorded[0].uri.ends_with("/v1/chat/completions"));
-    assert_eq!(recorded[0].method, "POST");
-
-    let body_str = String::from_utf8(recorded[0].body.clone()).unwrap();
-    assert!(body_str.contains("gpt-4"));
-    assert!(body_str.contains("Hello"));
 }
 
-// Response header sanitization: hop-by-hop and x-oagw-* headers stripped from upstream response.
 #[tokio::test]
-async fn proxy_response_headers_sanitized() {
+async fn proxy_query_allowlist_unknown_param_rejected() {
     let h = AppHarness::builder().build().await;
     let ctx = h.security_context().clone();
 
@@ -811,7 +1257,7 @@ async fn proxy_response_headers_sanitized() {
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
             )
-            .alias("resp-hdr-test")
+            .alias("ql-reject")
             .build(),
         )
         .await
@@ -825,9 +1271,10 @@ async fn proxy_response_headers_sanitized() {
                 MatchRules {
                     http: Some(HttpMatch {
                         methods: vec![HttpMethod::Get],
-                        path: "/response-headers".into(),
-                        query_allowlist: vec![],
+                        path: "/v1/models".into(),
+                        query_allowlist: vec!["version".into()],
                         path_suffix_mode: PathSuffixMode::Append,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -839,24 +1286,233 @@ async fn proxy_response_headers_sanitized() {
 
     let req = http::Request::builder()
         .method(Method::GET)
-        .uri("/resp-hdr-test/response-headers")
+        .uri("/ql-reject/v1/models?version=2&debug=true")
         .body(Body::Empty)
         .unwrap();
-    let response = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    match h.facade().proxy_request(ctx.clone(), req).await {
+        Err(err) => assert!(matches!(
+            err,
+            oagw_sdk::error::ServiceGatewayError::ValidationError { .. }
+        )),
+        Ok(_) => panic!("expected validation error"),
+    }
+}
 
-    let resp_headers = response.headers();
+// 13.5: Non-existent auth plugin ID returns error through proxy pipeline.
+#[tokio::test]
+async fn proxy_nonexistent_auth_plugin_returns_error() {
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
 
-    // Safe headers should be preserved.
-    assert_eq!(
-        resp_headers.get("x-custom-safe").unwrap(),
-        "keep-me",
-        "safe custom header should be forwarded"
-    );
-    assert!(
-        resp_headers.get("content-type").is_some(),
-        "content-type should be preserved"
-    );
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("bad-auth")
+            .auth(oagw_sdk::AuthConfig {
+                plugin_type: "gts.x.core.oagw.auth.v1~nonexistent.plugin.v1".into(),
+                sharing: SharingMode::Private,
+                config: None,
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: "/v1/test".into(),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Append,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri("/bad-auth/v1/test")
+        .body(Body::Empty)
+        .unwrap();
+    match h.facade().proxy_request(ctx.clone(), req).await {
+        Err(err) => assert!(matches!(
+            err,
+            oagw_sdk::error::ServiceGatewayError::AuthenticationFailed { .. }
+        )),
+        Ok(_) => panic!("expected authentication error for non-existent plugin"),
+    }
+}
+
+// 13.6: Assert on recorded_requests() URI and body content.
+#[tokio::test]
+async fn proxy_recorded_request_has_correct_uri_and_body() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/v1/chat/completions",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({
+                "id": "chatcmpl-rec-test",
+                "object": "chat.completion",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "ok"}, "finish_reason": "stop"}]
+            })),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("rec-test")
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Post],
+                        path: guard.path("/v1/chat/completions"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let body_payload = r#"{"model":"gpt-4","messages":[{"role":"user","content":"Hello"}]}"#;
+    let req = http::Request::builder()
+        .method(Method::POST)
+        .uri(format!("/rec-test{}", guard.path("/v1/chat/completions")))
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body_payload))
+        .unwrap();
+    let response = h.facade().proxy_request(ctx, req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 1);
+    assert!(recorded[0].uri.ends_with("/v1/chat/completions"));
+    assert_eq!(recorded[0].method, "POST");
+
+    let body_str = String::from_utf8(recorded[0].body.clone()).unwrap();
+    assert!(body_str.contains("gpt-4"));
+    assert!(body_str.contains("Hello"));
+}
+
+// Response header sanitization: hop-by-hop and x-oagw-* headers stripped from upstream response.
+#[tokio::test]
+async fn proxy_response_headers_sanitized() {
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("resp-hdr-test")
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: "/response-headers".into(),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Append,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri("/resp-hdr-test/response-headers")
+        .body(Body::Empty)
+        .unwrap();
+    let response = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let resp_headers = response.headers();
+
+    // Safe headers should be preserved.
+    assert_eq!(
+        resp_headers.get("x-custom-safe").unwrap(),
+        "keep-me",
+        "safe custom header should be forwarded"
+    );
+    assert!(
+        resp_headers.get("content-type").is_some(),
+        "content-type should be preserved"
+    );
 
     // Hop-by-hop headers should be stripped.
     assert!(
@@ -920,6 +1576,7 @@ async fn proxy_path_suffix_disabled_rejects_extra_path() {
                         path: "/v1/models".into(),
                         query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -1011,6 +1668,7 @@ async fn proxy_multi_endpoint_round_robin() {
                         path: "/v1/models".into(),
                         query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Append,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -1091,6 +1749,7 @@ async fn proxy_target_host_header_selects_endpoint() {
                         path: "/v1/models".into(),
                         query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Append,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -1148,6 +1807,7 @@ async fn proxy_target_host_unknown_returns_error() {
                         path: "/v1/models".into(),
                         query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Append,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -1217,6 +1877,7 @@ async fn proxy_all_backends_unreachable() {
                         path: "/v1/models".into(),
                         query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Append,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -1398,6 +2059,7 @@ async fn proxy_with_mock_guard_custom_response() {
                         path: guard.path("/custom/endpoint"),
                         query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -1429,43 +2091,62 @@ async fn proxy_with_mock_guard_custom_response() {
     assert!(recorded[0].uri.contains("/custom/endpoint"));
 }
 
-// P0 #3: WebSocket upgrade requests are rejected with ProtocolError.
-#[tokio::test]
-async fn proxy_websocket_upgrade_rejected() {
-    let h = AppHarness::builder().build().await;
-    let ctx = h.security_context().clone();
+// A WebSocket upgrade is proxied end to end: the handshake gets the
+// upstream's auth, and a frame round-trips through the echo server.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn proxy_websocket_echo_round_trip() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
-    let upstream = h
-        .facade()
+    let mock = MockUpstream::start().await;
+    let hub = modkit::client_hub::ClientHub::new();
+    let gateway = spawn_test_gateway(
+        &hub,
+        TestCpBuilder::new().with_credentials(vec![("cred://ws-key".into(), "sk-ws".into())]),
+        TestDpBuilder::new(),
+    )
+    .await;
+
+    let upstream = gateway
+        .facade
         .create_upstream(
-            ctx.clone(),
+            gateway.ctx.clone(),
             CreateUpstreamRequest::builder(
                 Server {
                     endpoints: vec![Endpoint {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
-                        port: h.mock_port(),
+                        port: mock.addr().port(),
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
             )
-            .alias("ws-reject-test")
+            .alias("ws-echo")
+            .auth(oagw_sdk::AuthConfig {
+                plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+                sharing: SharingMode::Private,
+                config: Some(HashMap::from([
+                    ("header".into(), "authorization".into()),
+                    ("prefix".into(), "Bearer ".into()),
+                    ("secret_ref".into(), "cred://ws-key".into()),
+                ])),
+            })
             .build(),
         )
         .await
         .unwrap();
-
-    h.facade()
+    gateway
+        .facade
         .create_route(
-            ctx.clone(),
+            gateway.ctx.clone(),
             CreateRouteRequest::builder(
                 upstream.id,
                 MatchRules {
                     http: Some(HttpMatch {
                         methods: vec![HttpMethod::Get],
-                        path: "/v1/ws".into(),
+                        path: "/ws/echo".into(),
                         query_allowlist: vec![],
-                        path_suffix_mode: PathSuffixMode::Append,
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -1475,24 +2156,62 @@ async fn proxy_websocket_upgrade_rejected() {
         .await
         .unwrap();
 
-    let req = http::Request::builder()
-        .method(Method::GET)
-        .uri("/ws-reject-test/v1/ws")
-        .header("upgrade", "websocket")
-        .header("connection", "upgrade")
-        .body(Body::Empty)
+    let addr = gateway.base_url.strip_prefix("http://").unwrap();
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(
+            b"GET /oagw/v1/proxy/ws-echo/ws/echo HTTP/1.1\r\n\
+              Host: gateway\r\n\
+              Upgrade: websocket\r\n\
+              Connection: Upgrade\r\n\
+              Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+              Sec-WebSocket-Version: 13\r\n\r\n",
+        )
+        .await
         .unwrap();
 
-    match h.facade().proxy_request(ctx.clone(), req).await {
-        Err(err) => assert!(
-            matches!(
-                err,
-                oagw_sdk::error::ServiceGatewayError::ProtocolError { .. }
-            ),
-            "expected ProtocolError for WebSocket upgrade, got: {err:?}"
-        ),
-        Ok(_) => panic!("expected error for WebSocket upgrade request"),
+    // Read the response head byte by byte so no frame bytes are consumed.
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        head.push(stream.read_u8().await.unwrap());
     }
+    let head = String::from_utf8(head).unwrap();
+    assert!(head.starts_with("HTTP/1.1 101"), "{head}");
+    assert!(
+        head.to_ascii_lowercase()
+            .contains("sec-websocket-accept: s3pplmbitxaq9kygzzhzrbk+xoo="),
+        "{head}"
+    );
+
+    // Client frames are masked; the echo comes back unmasked.
+    let payload = b"hello over oagw";
+    let mask = [0x12, 0x34, 0x56, 0x78];
+    let mut frame = vec![0x81, 0x80 | u8::try_from(payload.len()).unwrap()];
+    frame.extend_from_slice(&mask);
+    frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+    stream.write_all(&frame).await.unwrap();
+
+    let mut echoed = vec![0u8; 2 + payload.len()];
+    tokio::time::timeout(Duration::from_secs(5), stream.read_exact(&mut echoed))
+        .await
+        .expect("echo timed out")
+        .unwrap();
+    assert_eq!(echoed[..2], [0x81, u8::try_from(payload.len()).unwrap()]);
+    assert_eq!(&echoed[2..], payload);
+
+    let recorded = mock.recorded_requests().await;
+    let handshake = recorded
+        .iter()
+        .find(|r| r.uri.ends_with("/ws/echo"))
+        .expect("handshake reached the upstream");
+    assert!(
+        handshake
+            .headers
+            .iter()
+            .any(|(name, value)| name == "authorization" && value == "Bearer sk-ws"),
+        "{:?}",
+        handshake.headers
+    );
 }
 
 // P4 #18: Pingora fail_to_proxy produces valid RFC 9457 Problem body with correct GTS type.
@@ -1535,6 +2254,7 @@ async fn proxy_unreachable_backend_returns_rfc9457_problem_body() {
                         path: "/v1/test".into(),
                         query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Append,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -1655,6 +2375,7 @@ async fn proxy_streaming_body_exceeding_limit_returns_413() {
                         path: guard.path("/v1/upload"),
                         query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -1790,6 +2511,7 @@ async fn proxy_oauth2_client_cred_injects_bearer_token() {
                         path: guard.path("/api/resource"),
                         query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -1892,6 +2614,7 @@ async fn proxy_oauth2_missing_credentials_returns_error() {
                         path: guard.path("/api/resource"),
                         query_allowlist: vec![],
                         path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
                     }),
                     grpc: None,
                 },
@@ -1914,3 +2637,1707 @@ async fn proxy_oauth2_missing_credentials_returns_error() {
     // Should fail with a secret-not-found error.
     assert!(response.is_err());
 }
+
+/// Response cache backend that records every `get`/`put` call.
+#[derive(Default)]
+struct RecordingCache {
+    entries: Mutex<HashMap<String, CachedResponse>>,
+    gets: AtomicUsize,
+    puts: AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl ResponseCache for RecordingCache {
+    async fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.gets.fetch_add(1, Ordering::SeqCst);
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    async fn put(&self, key: &str, response: CachedResponse, _ttl: Duration) {
+        self.puts.fetch_add(1, Ordering::SeqCst);
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), response);
+    }
+
+    async fn invalidate(&self, key: &str) {
+        self.entries.lock().unwrap().remove(key);
+    }
+}
+
+// A registered cache backend is consulted on GET, written on a cacheable
+// miss, and serves the second request without reaching the upstream.
+#[tokio::test]
+async fn proxy_response_cache_backend_consulted_and_written() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/v1/catalog",
+        MockResponse {
+            status: 200,
+            headers: vec![("cache-control".into(), "public, max-age=60".into())],
+            body: MockBody::Json(json!({"items": ["a", "b"]})),
+        },
+    );
+
+    let cache = Arc::new(RecordingCache::default());
+    let h = AppHarness::builder()
+        .with_response_cache(cache.clone())
+        .build()
+        .await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("cache-test")
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/v1/catalog"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .cache_responses(true)
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let get = || {
+        http::Request::builder()
+            .method(Method::GET)
+            .uri(format!("/cache-test{}", guard.path("/v1/catalog")))
+            .body(Body::Empty)
+            .unwrap()
+    };
+
+    // Miss: the backend is consulted for a fresh and a revalidatable entry,
+    // the upstream answers, the entry is stored.
+    let response = h.facade().proxy_request(ctx.clone(), get()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().into_bytes().await.unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+        json!({"items": ["a", "b"]})
+    );
+    assert_eq!(cache.gets.load(Ordering::SeqCst), 2);
+    assert_eq!(cache.puts.load(Ordering::SeqCst), 1);
+
+    // Hit: served from the backend without another upstream call.
+    let response = h.facade().proxy_request(ctx, get()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get("cache-control").unwrap(),
+        "public, max-age=60"
+    );
+    assert_eq!(response.into_body().into_bytes().await.unwrap(), body);
+    assert_eq!(cache.gets.load(Ordering::SeqCst), 3);
+    assert_eq!(cache.puts.load(Ordering::SeqCst), 1);
+    assert_eq!(guard.recorded_requests().await.len(), 1);
+}
+
+// A 2xx whose body matches the upstream's `retry_on_body` condition is
+// replayed; the first non-matching response is returned to the client.
+#[tokio::test]
+async fn proxy_retries_2xx_when_body_matches_retry_condition() {
+    let mut guard = MockGuard::new();
+    guard.mock_sequence(
+        "GET",
+        "/v1/status",
+        vec![
+            MockResponse {
+                status: 200,
+                headers: vec![("content-type".into(), "application/json".into())],
+                body: MockBody::Json(json!({"error": {"code": "overloaded"}})),
+            },
+            MockResponse {
+                status: 200,
+                headers: vec![("content-type".into(), "application/json".into())],
+                body: MockBody::Json(json!({"status": "ok"})),
+            },
+        ],
+    );
+
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("retry-on-body")
+            .retry_on_body(RetryOnBody {
+                matcher: BodyMatcher::JsonPointer {
+                    pointer: "/error/code".into(),
+                    value: json!("overloaded"),
+                },
+                max_retries: 2,
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/v1/status"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri(format!("/retry-on-body{}", guard.path("/v1/status")))
+        .body(Body::Empty)
+        .unwrap();
+    let response = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().into_bytes().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body, json!({"status": "ok"}));
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+}
+
+// An upstream flagged `force_chunked` receives the request body with
+// chunked framing and no Content-Length.
+#[tokio::test]
+async fn proxy_force_chunked_reframes_request_body() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/v1/ingest",
+        MockResponse {
+            status: 202,
+            headers: vec![],
+            body: MockBody::Text(String::new()),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("chunked-only")
+            .force_chunked(true)
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Post],
+                        path: guard.path("/v1/ingest"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let req = http::Request::builder()
+        .method(Method::POST)
+        .uri(format!("/chunked-only{}", guard.path("/v1/ingest")))
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(r#"{"event":"signup"}"#))
+        .unwrap();
+    let response = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 1);
+    let header = |name: &str| {
+        recorded[0]
+            .headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    };
+    assert_eq!(header("transfer-encoding"), Some("chunked"));
+    assert_eq!(header("content-length"), None);
+    assert_eq!(recorded[0].body, br#"{"event":"signup"}"#);
+}
+
+// A route's `status_remap` rewrites matching upstream statuses, keeping the
+// body and reporting the original status; other statuses pass through.
+#[tokio::test]
+async fn proxy_status_remap_rewrites_mapped_status_only() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/v1/limited",
+        MockResponse {
+            status: 418,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"error": "slow down"})),
+        },
+    );
+    guard.mock(
+        "GET",
+        "/v1/missing",
+        MockResponse {
+            status: 404,
+            headers: vec![],
+            body: MockBody::Text("not here".into()),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("teapot-partner")
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    for path in ["/v1/limited", "/v1/missing"] {
+        h.facade()
+            .create_route(
+                ctx.clone(),
+                CreateRouteRequest::builder(
+                    upstream.id,
+                    MatchRules {
+                        http: Some(HttpMatch {
+                            methods: vec![HttpMethod::Get],
+                            path: guard.path(path),
+                            query_allowlist: vec![],
+                            path_suffix_mode: PathSuffixMode::Disabled,
+                            match_body: None,
+                        }),
+                        grpc: None,
+                    },
+                )
+                .status_remap(HashMap::from([(418, 429)]))
+                .build(),
+            )
+            .await
+            .unwrap();
+    }
+
+    let get = |path: &str| {
+        http::Request::builder()
+            .method(Method::GET)
+            .uri(format!("/teapot-partner{}", guard.path(path)))
+            .body(Body::Empty)
+            .unwrap()
+    };
+
+    let response = h
+        .facade()
+        .proxy_request(ctx.clone(), get("/v1/limited"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(
+        response.headers().get("x-oagw-original-status").unwrap(),
+        "418"
+    );
+    let body = response.into_body().into_bytes().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body, json!({"error": "slow down"}));
+
+    let response = h
+        .facade()
+        .proxy_request(ctx.clone(), get("/v1/missing"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(response.headers().get("x-oagw-original-status").is_none());
+    let body = response.into_body().into_bytes().await.unwrap();
+    assert_eq!(&body[..], b"not here");
+}
+
+/// A loopback port with nothing listening on it.
+fn closed_port() -> u16 {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().port()
+}
+
+async fn create_failover_upstream(
+    h: &AppHarness,
+    guard: &MockGuard,
+    alias: &str,
+    primary_port: u16,
+    failover_port: u16,
+) {
+    let ctx = h.security_context().clone();
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: primary_port,
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias(alias)
+            .failover(vec![Endpoint {
+                scheme: Scheme::Http,
+                host: "127.0.0.1".into(),
+                port: failover_port,
+            }])
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx,
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Post],
+                        path: guard.path("/v1/orders"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+}
+
+fn failover_request(guard: &MockGuard, alias: &str) -> http::Request<Body> {
+    http::Request::builder()
+        .method(Method::POST)
+        .uri(format!("/{alias}{}", guard.path("/v1/orders")))
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(r#"{"sku":"a-1"}"#))
+        .unwrap()
+}
+
+// When the primary endpoint refuses the connection, the request is replayed
+// against the first failover endpoint.
+#[tokio::test]
+async fn proxy_fails_over_to_secondary_when_primary_unreachable() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/v1/orders",
+        MockResponse {
+            status: 201,
+            headers: vec![],
+            body: MockBody::Text("created".into()),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    create_failover_upstream(&h, &guard, "failover-down", closed_port(), h.mock_port()).await;
+
+    let response = h
+        .facade()
+        .proxy_request(
+            h.security_context().clone(),
+            failover_request(&guard, "failover-down"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0].body, br#"{"sku":"a-1"}"#);
+}
+
+// A healthy primary always serves the request; the failover endpoint (here
+// unreachable) is never tried.
+#[tokio::test]
+async fn proxy_prefers_healthy_primary_over_failover() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/v1/orders",
+        MockResponse {
+            status: 201,
+            headers: vec![],
+            body: MockBody::Text("created".into()),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    create_failover_upstream(&h, &guard, "failover-up", h.mock_port(), closed_port()).await;
+
+    for _ in 0..3 {
+        let response = h
+            .facade()
+            .proxy_request(
+                h.security_context().clone(),
+                failover_request(&guard, "failover-up"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+    assert_eq!(guard.recorded_requests().await.len(), 3);
+}
+
+// Failover and body retries share the route's `max_outbound_calls`: with a
+// cap of 2 the unreachable primary and the failover endpoint use it up, so
+// the retry the failover's response asks for fails with 502 instead.
+#[tokio::test]
+async fn proxy_stops_at_max_outbound_calls_across_failover_and_retries() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/v1/status",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"error": {"code": "overloaded"}})),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+    let endpoint = |port| Endpoint {
+        scheme: Scheme::Http,
+        host: "127.0.0.1".into(),
+        port,
+    };
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![endpoint(closed_port())],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("capped-calls")
+            .failover(vec![endpoint(h.mock_port())])
+            .retry_on_body(RetryOnBody {
+                matcher: BodyMatcher::JsonPointer {
+                    pointer: "/error/code".into(),
+                    value: json!("overloaded"),
+                },
+                max_retries: 3,
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/v1/status"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .max_outbound_calls(2)
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri(format!("/capped-calls{}", guard.path("/v1/status")))
+        .body(Body::Empty)
+        .unwrap();
+    let err = h.facade().proxy_request(ctx, req).await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            oagw_sdk::error::ServiceGatewayError::DownstreamError { ref detail, .. }
+                if detail.contains("max_outbound_calls of 2")
+        ),
+        "expected DownstreamError, got: {err:?}"
+    );
+    assert_eq!(guard.recorded_requests().await.len(), 1);
+}
+
+async fn create_content_type_route(h: &AppHarness, guard: &MockGuard, alias: &str) {
+    let path = guard.path("/v1/submit");
+    create_test_route(h, alias, h.mock_port(), HttpMethod::Post, path, |r| {
+        r.allowed_request_content_types(vec!["application/json".into()])
+            .allowed_response_content_types(vec!["application/json".into()])
+    })
+    .await;
+}
+
+// A request body whose media type is not on the route's allowlist is
+// rejected with 415 before reaching the upstream.
+#[tokio::test]
+async fn proxy_rejects_disallowed_request_content_type() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/v1/submit",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"ok": true})),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    create_content_type_route(&h, &guard, "ct-request").await;
+    let path = guard.path("/v1/submit");
+    let path = path.trim_start_matches('/');
+
+    h.api_v1()
+        .proxy_post("ct-request", path)
+        .with_body("<order/>")
+        .with_header(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static("application/xml"),
+        )
+        .expect_status(415)
+        .await;
+    assert!(guard.recorded_requests().await.is_empty());
+
+    h.api_v1()
+        .proxy_post("ct-request", path)
+        .with_body(json!({"order": 1}))
+        .expect_status(200)
+        .await;
+}
+
+// The content-type allowlist is route policy: a caller AuthZ denies gets 403
+// rather than a 415 that would reveal it.
+#[tokio::test]
+async fn proxy_denies_before_checking_request_content_type() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/v1/submit",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"ok": true})),
+        },
+    );
+
+    let h = AppHarness::builder()
+        .with_authz_client(Arc::new(DenyingAuthZResolverClient))
+        .build()
+        .await;
+    create_content_type_route(&h, &guard, "ct-denied").await;
+    let path = guard.path("/v1/submit");
+
+    h.api_v1()
+        .proxy_post("ct-denied", path.trim_start_matches('/'))
+        .with_body("<order/>")
+        .with_header(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static("application/xml"),
+        )
+        .expect_status(403)
+        .await;
+    assert!(guard.recorded_requests().await.is_empty());
+}
+
+// An upstream response whose media type is not on the route's allowlist is
+// answered with 502.
+#[tokio::test]
+async fn proxy_rejects_disallowed_response_content_type() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/v1/submit",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "text/html".into())],
+            body: MockBody::Text("<html>login</html>".into()),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    create_content_type_route(&h, &guard, "ct-response").await;
+    let path = guard.path("/v1/submit");
+
+    h.api_v1()
+        .proxy_post("ct-response", path.trim_start_matches('/'))
+        .with_body(json!({"order": 1}))
+        .expect_status(502)
+        .await;
+}
+
+/// Upstream that answers the first request on each connection, then closes
+/// the connection on the next request without responding — the way a pooled
+/// connection silently dropped by an intermediary behaves. Returns the port
+/// and the number of requests received.
+async fn spawn_one_shot_keepalive_upstream() -> (u16, Arc<AtomicUsize>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let seen = Arc::new(AtomicUsize::new(0));
+    let counter = seen.clone();
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            let counter = counter.clone();
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 8192];
+                let mut served = false;
+                loop {
+                    // Each request in these tests is bodyless or tiny, so one
+                    // read holds the whole request.
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(_) => {}
+                    }
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    if served {
+                        return;
+                    }
+                    served = true;
+                    let _ = stream
+                        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                        .await;
+                }
+            });
+        }
+    });
+    (port, seen)
+}
+
+async fn create_pooled_route(h: &AppHarness, alias: &str, port: u16, method: HttpMethod) {
+    create_test_route(h, alias, port, method, "/v1/items".into(), |r| r).await;
+}
+
+fn pooled_request(alias: &str, method: Method) -> http::Request<Body> {
+    http::Request::builder()
+        .method(method)
+        .uri(format!("/{alias}/v1/items"))
+        .body(Body::Empty)
+        .unwrap()
+}
+
+// A GET that lands on a pooled connection the upstream has since dropped is
+// transparently retried once on a fresh connection.
+#[tokio::test]
+async fn proxy_retries_idempotent_request_on_broken_pooled_connection() {
+    let (port, seen) = spawn_one_shot_keepalive_upstream().await;
+    let h = AppHarness::builder().build().await;
+    create_pooled_route(&h, "pooled-get", port, HttpMethod::Get).await;
+    let ctx = h.security_context().clone();
+
+    let first = h
+        .facade()
+        .proxy_request(ctx.clone(), pooled_request("pooled-get", Method::GET))
+        .await
+        .unwrap();
+    assert_eq!(first.status(), StatusCode::OK);
+    let _ = first.into_body().into_bytes().await.unwrap();
+
+    let second = h
+        .facade()
+        .proxy_request(ctx, pooled_request("pooled-get", Method::GET))
+        .await
+        .unwrap();
+    assert_eq!(second.status(), StatusCode::OK);
+    assert_eq!(seen.load(Ordering::SeqCst), 3);
+}
+
+// A POST on a broken pooled connection is not replayed: it may already have
+// reached the upstream.
+#[tokio::test]
+async fn proxy_does_not_retry_post_on_broken_pooled_connection() {
+    let (port, seen) = spawn_one_shot_keepalive_upstream().await;
+    let h = AppHarness::builder().build().await;
+    create_pooled_route(&h, "pooled-post", port, HttpMethod::Post).await;
+    let ctx = h.security_context().clone();
+
+    let first = h
+        .facade()
+        .proxy_request(ctx.clone(), pooled_request("pooled-post", Method::POST))
+        .await
+        .unwrap();
+    assert_eq!(first.status(), StatusCode::OK);
+    let _ = first.into_body().into_bytes().await.unwrap();
+
+    let second = h
+        .facade()
+        .proxy_request(ctx, pooled_request("pooled-post", Method::POST))
+        .await;
+    match second {
+        Ok(resp) => assert_eq!(resp.status(), StatusCode::BAD_GATEWAY),
+        Err(e) => assert!(matches!(
+            e,
+            oagw_sdk::error::ServiceGatewayError::DownstreamError { .. }
+        )),
+    }
+    assert_eq!(seen.load(Ordering::SeqCst), 2);
+}
+
+async fn create_apikey_upstream(h: &AppHarness, alias: &str, secret_ref: &str) -> uuid::Uuid {
+    h.facade()
+        .create_upstream(
+            h.security_context().clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias(alias)
+            .auth(oagw_sdk::AuthConfig {
+                plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+                sharing: SharingMode::Private,
+                config: Some(
+                    [
+                        ("header".into(), "authorization".into()),
+                        ("prefix".into(), "Bearer ".into()),
+                        ("secret_ref".into(), secret_ref.into()),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            })
+            .build(),
+        )
+        .await
+        .unwrap()
+        .id
+}
+
+// Testing a reachable upstream with a resolvable secret reports every step
+// as successful, without any route configured.
+#[tokio::test]
+async fn test_upstream_reports_working_upstream() {
+    let h = AppHarness::builder()
+        .with_credentials(vec![("cred://openai-key".into(), "sk-test123".into())])
+        .build()
+        .await;
+    let id = create_apikey_upstream(&h, "diag-ok", "cred://openai-key").await;
+
+    let result = h
+        .facade()
+        .test_upstream(h.security_context().clone(), id)
+        .await
+        .unwrap();
+
+    assert!(result.credential_resolved);
+    assert!(result.auth_applied);
+    assert!(result.upstream_status.is_some());
+    assert!(result.latency_ms.is_some());
+    assert_eq!(result.error, None);
+}
+
+// A missing secret is reported as a diagnostic rather than an error, and the
+// upstream is never contacted.
+#[tokio::test]
+async fn test_upstream_reports_missing_secret() {
+    let h = AppHarness::builder().build().await;
+    let id = create_apikey_upstream(&h, "diag-missing", "cred://missing").await;
+
+    let result = h
+        .facade()
+        .test_upstream(h.security_context().clone(), id)
+        .await
+        .unwrap();
+
+    assert!(!result.credential_resolved);
+    assert!(!result.auth_applied);
+    assert_eq!(result.upstream_status, None);
+    assert_eq!(result.latency_ms, None);
+    assert!(result.error.is_some());
+}
+
+// Routes sharing a path are told apart by a JSON field in the request body,
+// whether the body arrives buffered or streamed. The routes differ only in
+// `status_remap`, which makes the winning route observable.
+#[tokio::test]
+async fn proxy_routes_on_request_body_field() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/v1/chat/completions",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"ok": true})),
+        },
+    );
+
+    let authz = Arc::new(CapturingAuthZResolverClient::new());
+    let h = AppHarness::builder()
+        .with_authz_client(authz.clone())
+        .build()
+        .await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("body-routed")
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let mut route_ids = Vec::new();
+    for (match_body, remap) in [
+        (None, HashMap::new()),
+        (
+            Some(BodyMatcher::JsonPointer {
+                pointer: "/model".into(),
+                value: json!("gpt-4"),
+            }),
+            HashMap::from([(200, 203)]),
+        ),
+    ] {
+        let route = h
+            .facade()
+            .create_route(
+                ctx.clone(),
+                CreateRouteRequest::builder(
+                    upstream.id,
+                    MatchRules {
+                        http: Some(HttpMatch {
+                            methods: vec![HttpMethod::Post],
+                            path: guard.path("/v1/chat/completions"),
+                            query_allowlist: vec![],
+                            path_suffix_mode: PathSuffixMode::Disabled,
+                            match_body,
+                        }),
+                        grpc: None,
+                    },
+                )
+                .status_remap(remap)
+                .build(),
+            )
+            .await
+            .unwrap();
+        route_ids.push(route.id.to_string());
+    }
+
+    let post = |body: Body| {
+        http::Request::builder()
+            .method(Method::POST)
+            .uri(format!(
+                "/body-routed{}",
+                guard.path("/v1/chat/completions")
+            ))
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .unwrap()
+    };
+    let streamed = |json: &'static str| {
+        let chunks: Vec<Result<bytes::Bytes, oagw_sdk::body::BoxError>> = json
+            .as_bytes()
+            .chunks(8)
+            .map(|c| Ok(bytes::Bytes::copy_from_slice(c)))
+            .collect();
+        Body::Stream(Box::pin(futures_util::stream::iter(chunks)))
+    };
+
+    let response = h
+        .facade()
+        .proxy_request(ctx.clone(), post(Body::from(r#"{"model":"gpt-4"}"#)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NON_AUTHORITATIVE_INFORMATION);
+
+    let evaluated = authz.recorded().len();
+    let response = h
+        .facade()
+        .proxy_request(ctx.clone(), post(Body::from(r#"{"model":"llama-3"}"#)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    // The body moved the request off the provisional route: both were
+    // authorized, the body-matched one before its body was read.
+    let evaluated: Vec<_> = authz.recorded()[evaluated..]
+        .iter()
+        .filter(|r| r.resource.resource_type == "gts.x.core.oagw.proxy.v1~")
+        .map(|r| {
+            r.resource.properties["route_id"]
+                .as_str()
+                .unwrap()
+                .to_owned()
+        })
+        .collect();
+    assert_eq!(evaluated, [route_ids[1].clone(), route_ids[0].clone()]);
+
+    let response = h
+        .facade()
+        .proxy_request(ctx.clone(), post(streamed(r#"{"model":"gpt-4"}"#)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NON_AUTHORITATIVE_INFORMATION);
+
+    let response = h
+        .facade()
+        .proxy_request(ctx.clone(), post(streamed(r#"{"model":"llama-3"}"#)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Every request reached the upstream with its body intact.
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 4);
+    assert_eq!(
+        recorded[2].body,
+        br#"{"model":"gpt-4"}"#.to_vec(),
+        "buffered body is forwarded unchanged"
+    );
+}
+
+// A hostname upstream pinned through `dns_override` is dialled at the pinned
+// address while keeping its hostname for the Host header.
+#[tokio::test]
+async fn proxy_connects_to_dns_override_address() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/v1/pinned",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"pinned": true})),
+        },
+    );
+
+    let h = AppHarness::builder()
+        .with_dns_override(HashMap::from([(
+            "pinned.oagw.invalid".into(),
+            "127.0.0.1".parse().unwrap(),
+        )]))
+        .build()
+        .await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "pinned.oagw.invalid".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("pinned")
+            .build(),
+        )
+        .await
+        .unwrap();
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/v1/pinned"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri(format!("/pinned{}", guard.path("/v1/pinned")))
+        .body(Body::Empty)
+        .unwrap();
+    let response = h.facade().proxy_request(ctx, req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 1);
+    let host = recorded[0]
+        .headers
+        .iter()
+        .find(|(k, _)| k == "host")
+        .map(|(_, v)| v.as_str());
+    assert!(host.is_some_and(|h| h.starts_with("pinned.oagw.invalid")));
+}
+
+// An SSE upstream that stops emitting mid-stream is cut off after the idle
+// window: the client gets the events sent so far plus the terminal event,
+// and the stream ends instead of hanging.
+#[tokio::test]
+async fn proxy_sse_stalled_stream_ends_with_terminal_event() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/v1/chat/completions/stall",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::SseStalled(vec![r#"{"delta":"Hel"}"#.into()]),
+        },
+    );
+
+    let h = AppHarness::builder()
+        .with_stream_idle_timeout(Duration::from_millis(200))
+        .build()
+        .await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("sse-stall")
+            .build(),
+        )
+        .await
+        .unwrap();
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Post],
+                        path: guard.path("/v1/chat/completions/stall"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let req = http::Request::builder()
+        .method(Method::POST)
+        .uri(format!(
+            "/sse-stall{}",
+            guard.path("/v1/chat/completions/stall")
+        ))
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(r#"{"model":"gpt-4","stream":true}"#))
+        .unwrap();
+    let response = h.facade().proxy_request(ctx, req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = tokio::time::timeout(Duration::from_secs(5), response.into_body().into_bytes())
+        .await
+        .expect("stalled stream must be terminated")
+        .unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(
+        body.starts_with("data: {\"delta\":\"Hel\"}\n\n"),
+        "got: {body}"
+    );
+    assert!(
+        body.ends_with("event: error\ndata: {\"error\":\"upstream_stream_idle_timeout\"}\n\n"),
+        "got: {body}"
+    );
+}
+
+/// One recorded metric observation: value and labels.
+type MetricCall = (f64, Vec<(String, String)>);
+
+/// Metrics sink that records every call, keyed by metric name.
+#[derive(Default)]
+struct CapturingSink {
+    calls: Mutex<Vec<(&'static str, MetricCall)>>,
+}
+
+impl CapturingSink {
+    fn record(&self, name: &'static str, value: f64, labels: &[MetricLabel<'_>]) {
+        let labels = labels
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        self.calls.lock().unwrap().push((name, (value, labels)));
+    }
+
+    fn calls_named(&self, name: &str) -> Vec<MetricCall> {
+        self.calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(n, _)| *n == name)
+            .map(|(_, call)| call.clone())
+            .collect()
+    }
+}
+
+impl MetricsSink for CapturingSink {
+    #[allow(clippy::cast_precision_loss)]
+    fn increment_counter(&self, name: &'static str, value: u64, labels: &[MetricLabel<'_>]) {
+        self.record(name, value as f64, labels);
+    }
+
+    fn record_histogram(&self, name: &'static str, value: f64, labels: &[MetricLabel<'_>]) {
+        self.record(name, value, labels);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn record_gauge(&self, name: &'static str, value: u64, labels: &[MetricLabel<'_>]) {
+        self.record(name, value as f64, labels);
+    }
+}
+
+// A registered metrics sink receives the data plane's counter and histogram
+// calls: auth plugin latency on a proxied request, and the body-limit counter
+// and size histogram on a rejected one.
+#[tokio::test]
+async fn proxy_records_metrics_through_custom_sink() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/v1/metrics-sink",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Json(json!({"ok": true})),
+        },
+    );
+
+    let sink = Arc::new(CapturingSink::default());
+    let h = AppHarness::builder()
+        .with_credentials(vec![("cred://openai-key".into(), "sk-test123".into())])
+        .with_max_body_size(64)
+        .with_metrics_sink(sink.clone())
+        .build()
+        .await;
+    let ctx = h.security_context().clone();
+    let upstream_id = create_apikey_upstream(&h, "metrics-sink", "cred://openai-key").await;
+    let route = h
+        .facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream_id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Post],
+                        path: guard.path("/v1/metrics-sink"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let request = |body: Vec<u8>| {
+        http::Request::builder()
+            .method(Method::POST)
+            .uri(format!("/metrics-sink{}", guard.path("/v1/metrics-sink")))
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    };
+
+    let response = h
+        .facade()
+        .proxy_request(ctx.clone(), request(b"{}".to_vec()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let auth = sink.calls_named(AUTH_PLUGIN_DURATION);
+    assert_eq!(auth.len(), 1, "got: {auth:?}");
+    assert!(auth[0].0 >= 0.0);
+    assert!(auth[0].1.contains(&("result".into(), "success".into())));
+    assert!(auth[0].1.iter().any(|(k, _)| k == "plugin_id"));
+    assert!(sink.calls_named(BODY_LIMIT_EXCEEDED).is_empty());
+
+    let err = h
+        .facade()
+        .proxy_request(ctx, request(vec![b'x'; 100]))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            oagw_sdk::error::ServiceGatewayError::PayloadTooLarge { .. }
+        ),
+        "got: {err:?}"
+    );
+
+    let route_label = vec![("route_id".to_string(), route.id.to_string())];
+    assert_eq!(
+        sink.calls_named(BODY_LIMIT_EXCEEDED),
+        vec![(1.0, route_label.clone())]
+    );
+    assert_eq!(
+        sink.calls_named(BODY_LIMIT_DECLARED_SIZE),
+        vec![(100.0, route_label)]
+    );
+}
+
+fn inline_apikey_auth(pairs: &[(&str, &str)]) -> oagw_sdk::AuthConfig {
+    oagw_sdk::AuthConfig {
+        plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+        sharing: SharingMode::Private,
+        config: Some(
+            pairs
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+        ),
+    }
+}
+
+// An apikey upstream configured with an inline `value` injects it without
+// consulting the credential store.
+#[tokio::test]
+async fn proxy_injects_inline_apikey_value() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/v1/inline-key",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Json(json!({"ok": true})),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("inline-key")
+            .auth(inline_apikey_auth(&[
+                ("header", "authorization"),
+                ("prefix", "Bearer "),
+                ("value", "sk-inline-test"),
+            ]))
+            .build(),
+        )
+        .await
+        .unwrap();
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/v1/inline-key"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri(format!("/inline-key{}", guard.path("/v1/inline-key")))
+        .body(Body::Empty)
+        .unwrap();
+    let response = h.facade().proxy_request(ctx, req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 1);
+    assert!(
+        recorded[0]
+            .headers
+            .iter()
+            .any(|(k, v)| k == "authorization" && v == "Bearer sk-inline-test")
+    );
+}
+
+// Configuring both a `secret_ref` and an inline `value` is rejected.
+#[tokio::test]
+async fn create_upstream_rejects_secret_ref_with_inline_value() {
+    let h = AppHarness::builder()
+        .with_credentials(vec![("cred://openai-key".into(), "sk-test123".into())])
+        .build()
+        .await;
+    let err = h
+        .facade()
+        .create_upstream(
+            h.security_context().clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("inline-and-ref")
+            .auth(inline_apikey_auth(&[
+                ("header", "authorization"),
+                ("secret_ref", "cred://openai-key"),
+                ("value", "sk-inline-test"),
+            ]))
+            .build(),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            oagw_sdk::error::ServiceGatewayError::ValidationError { ref detail, .. }
+                if detail.contains("mutually exclusive")
+        ),
+        "got: {err:?}"
+    );
+}
+
+// The facade reports the running build without a security context.
+#[tokio::test]
+async fn version_info_reports_crate_version() {
+    let h = AppHarness::builder().build().await;
+    let info = h.facade().version_info();
+    assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        info.git_sha.as_deref(),
+        option_env!("OAGW_GIT_SHA").filter(|sha| !sha.is_empty())
+    );
+    assert!(info.features.iter().any(|f| f == "test-utils"));
+}
+
+// Runtime state is listed per upstream and resets authorize the upstream
+// like any other management call.
+#[tokio::test]
+async fn runtime_state_lists_upstreams_and_resets_require_known_upstream() {
+    let h = AppHarness::builder()
+        .with_credentials(vec![("cred://openai-key".into(), "sk-test123".into())])
+        .build()
+        .await;
+    let id = create_apikey_upstream(&h, "runtime-state", "cred://openai-key").await;
+    let ctx = h.security_context().clone();
+
+    let states = h
+        .facade()
+        .list_runtime_states(ctx.clone(), &oagw_sdk::ListQuery::default())
+        .await
+        .unwrap();
+    let state = states.iter().find(|s| s.upstream_id == id).unwrap();
+    assert_eq!(
+        state.circuit,
+        oagw_sdk::CircuitStatus::Closed { failures: 0 }
+    );
+    assert!(state.rate_limits.is_empty());
+
+    h.facade()
+        .reset_circuit_breaker(ctx.clone(), id)
+        .await
+        .unwrap();
+    h.facade()
+        .reset_rate_limit(ctx.clone(), ctx.subject_tenant_id(), id)
+        .await
+        .unwrap();
+    let err = h
+        .facade()
+        .reset_circuit_breaker(ctx, uuid::Uuid::new_v4())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        oagw_sdk::error::ServiceGatewayError::NotFound { .. }
+    ));
+}
+
+/// Requests the mock server received on `path`.
+async fn calls(guard: &MockGuard, path: &str) -> usize {
+    let path = guard.path(path);
+    let recorded = guard.recorded_requests().await;
+    recorded.iter().filter(|r| r.uri.contains(&path)).count()
+}
+
+// Flushing empties the response cache and the OAuth2 token cache, so the
+// next request reaches both the IdP and the upstream again.
+#[tokio::test]
+async fn flush_all_caches_empties_response_and_token_caches() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/oauth/token",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(
+                json!({"access_token":"tok-flush","expires_in":3600,"token_type":"Bearer"}),
+            ),
+        },
+    );
+    guard.mock(
+        "GET",
+        "/v1/catalog",
+        MockResponse {
+            status: 200,
+            headers: vec![("cache-control".into(), "public, max-age=60".into())],
+            body: MockBody::Json(json!({"items": ["a"]})),
+        },
+    );
+
+    let h = AppHarness::builder()
+        .with_credentials(vec![
+            ("cred://oauth2-client-id".into(), "test-id".into()),
+            ("cred://oauth2-client-secret".into(), "test-secret".into()),
+        ])
+        .build()
+        .await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("flush-test")
+            .auth(oagw_sdk::AuthConfig {
+                plugin_type: OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID.into(),
+                sharing: SharingMode::Private,
+                config: Some(
+                    [
+                        (
+                            "token_endpoint".into(),
+                            format!(
+                                "http://127.0.0.1:{}{}",
+                                h.mock_port(),
+                                guard.path("/oauth/token")
+                            ),
+                        ),
+                        ("client_id_ref".into(), "cred://oauth2-client-id".into()),
+                        (
+                            "client_secret_ref".into(),
+                            "cred://oauth2-client-secret".into(),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/v1/catalog"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                        match_body: None,
+                    }),
+                    grpc: None,
+                },
+            )
+            .cache_responses(true)
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let get = || {
+        http::Request::builder()
+            .method(Method::GET)
+            .uri(format!("/flush-test{}", guard.path("/v1/catalog")))
+            .body(Body::Empty)
+            .unwrap()
+    };
+
+    // Populate both caches; the second request is served from them.
+    for _ in 0..2 {
+        let response = h.facade().proxy_request(ctx.clone(), get()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    assert_eq!(calls(&guard, "/v1/catalog").await, 1);
+
+    let flushed = h.facade().flush_all_caches().await;
+    assert_eq!(
+        flushed,
+        oagw_sdk::FlushedCaches {
+            response_cache: 1,
+            token_cache: 1,
+        }
+    );
+    assert_eq!(
+        h.facade().flush_all_caches().await,
+        oagw_sdk::FlushedCaches::default()
+    );
+
+    let response = h.facade().proxy_request(ctx, get()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(calls(&guard, "/v1/catalog").await, 2);
+    assert_eq!(calls(&guard, "/oauth/token").await, 2);
+}